@@ -0,0 +1,379 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  collections::{HashMap, HashSet},
+  env, fs,
+  path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use cargo_metadata::{CargoOpt, DependencyKind, MetadataCommand};
+use serde_json::Value;
+use tauri_utils::attributions::{Attribution, Ecosystem};
+
+/// The environment variable pointing `tauri::generate_context!` to the generated attributions.
+pub(crate) const ATTRIBUTIONS_PATH_ENV: &str = "TAURI_ATTRIBUTIONS_PATH";
+
+/// The third-party license attributions embedded in the app, exposed by `tauri::AppHandle::attributions`.
+///
+/// The attributions list the crates the app depends on, excluding the path dependencies and the build and dev dependencies,
+/// and the packages of the frontend dependency manifest installed in `node_modules`, excluding the `devDependencies`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// let attributions = tauri_build::Attributions::new().frontend_manifest("../package.json");
+/// tauri_build::try_build(tauri_build::Attributes::new().attributions(attributions))
+///   .expect("failed to run build script");
+/// ```
+#[derive(Debug, Default)]
+pub struct Attributions {
+  frontend_manifest: Option<PathBuf>,
+}
+
+impl Attributions {
+  /// Creates the attributions of the crates only.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Also lists the dependencies of the given `package.json` file, relative to the `Cargo.toml` directory.
+  #[must_use]
+  pub fn frontend_manifest<P: Into<PathBuf>>(mut self, path: P) -> Self {
+    self.frontend_manifest.replace(path.into());
+    self
+  }
+}
+
+/// Writes the attributions JSON to the out directory and points the codegen to it.
+pub(crate) fn generate(
+  attributions: &Attributions,
+  out_dir: &Path,
+  target_triple: &str,
+) -> crate::Result<()> {
+  let mut list = cargo_attributions(target_triple)?;
+  if let Some(manifest) = &attributions.frontend_manifest {
+    println!("cargo:rerun-if-changed={}", manifest.display());
+    list.extend(npm_attributions(manifest)?);
+  }
+
+  let path = out_dir.join("tauri-attributions.json");
+  fs::write(&path, serde_json::to_vec(&list)?)?;
+  println!("cargo:rustc-env={ATTRIBUTIONS_PATH_ENV}={}", path.display());
+  // when running codegen in this build script, we need to access the env var directly
+  env::set_var(ATTRIBUTIONS_PATH_ENV, &path);
+  Ok(())
+}
+
+/// The features of the package enabled in this build, from the `CARGO_FEATURE_<name>` variables
+/// Cargo sets for the build script with the name uppercased and `-` replaced by `_`.
+fn active_features<'a>(
+  features: impl IntoIterator<Item = &'a String>,
+  vars: &HashSet<String>,
+) -> Vec<String> {
+  features
+    .into_iter()
+    .filter(|feature| {
+      vars.contains(&format!(
+        "CARGO_FEATURE_{}",
+        feature.to_uppercase().replace('-', "_")
+      ))
+    })
+    .cloned()
+    .collect()
+}
+
+fn cargo_attributions(target_triple: &str) -> crate::Result<Vec<Attribution>> {
+  let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR")?);
+  let manifest_path = manifest_dir.join("Cargo.toml");
+
+  // resolves the dependency graph with the features of this build, so the optional dependencies match the app binary
+  let package_name = env::var("CARGO_PKG_NAME")?;
+  let package = MetadataCommand::new()
+    .manifest_path(&manifest_path)
+    .no_deps()
+    .exec()
+    .context("failed to read the app package with `cargo metadata`")?
+    .packages
+    .into_iter()
+    .find(|package| package.name == package_name)
+    .map(|package| package.features.into_keys().collect::<Vec<_>>())
+    .unwrap_or_default();
+  let vars = env::vars_os()
+    .filter_map(|(key, _)| key.into_string().ok())
+    .filter(|key| key.starts_with("CARGO_FEATURE_"))
+    .collect::<HashSet<_>>();
+  let features = active_features(&package, &vars);
+
+  let metadata = MetadataCommand::new()
+    .manifest_path(manifest_path)
+    .features(CargoOpt::NoDefaultFeatures)
+    .features(CargoOpt::SomeFeatures(features))
+    .other_options(vec!["--filter-platform".into(), target_triple.into()])
+    .exec()
+    .context("failed to read the dependency graph with `cargo metadata`")?;
+  println!(
+    "cargo:rerun-if-changed={}",
+    metadata.workspace_root.join("Cargo.lock")
+  );
+
+  let resolve = metadata
+    .resolve
+    .as_ref()
+    .context("`cargo metadata` did not return the dependency graph")?;
+  let root = resolve
+    .root
+    .as_ref()
+    .context("`cargo metadata` did not return the app package")?;
+  let nodes = resolve
+    .nodes
+    .iter()
+    .map(|node| (&node.id, node))
+    .collect::<HashMap<_, _>>();
+
+  // the crates compiled into the app
+  let mut visited = HashSet::new();
+  let mut stack = vec![root];
+  while let Some(id) = stack.pop() {
+    if !visited.insert(id) {
+      continue;
+    }
+    if let Some(node) = nodes.get(id) {
+      stack.extend(
+        node
+          .deps
+          .iter()
+          .filter(|dep| {
+            dep
+              .dep_kinds
+              .iter()
+              .any(|kind| kind.kind == DependencyKind::Normal)
+          })
+          .map(|dep| &dep.pkg),
+      );
+    }
+  }
+
+  let mut attributions = visited
+    .into_iter()
+    .map(|id| &metadata[id])
+    // the path dependencies are part of the app
+    .filter(|package| package.source.is_some())
+    .map(|package| Attribution {
+      name: package.name.clone(),
+      version: package.version.to_string(),
+      ecosystem: Ecosystem::Cargo,
+      license: package.license.clone(),
+      license_text: package
+        .license
+        .is_none()
+        .then(|| package.license_file())
+        .flatten()
+        .and_then(|path| fs::read_to_string(path).ok()),
+      authors: package.authors.clone(),
+      repository: package.repository.clone(),
+    })
+    .collect::<Vec<_>>();
+  attributions.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+  Ok(attributions)
+}
+
+fn read_json(path: &Path) -> crate::Result<Value> {
+  let contents = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+  serde_json::from_slice(&contents).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+/// The names of the packages the frontend package depends on at runtime.
+fn npm_dependencies(package: &Value) -> impl Iterator<Item = String> + '_ {
+  ["dependencies", "optionalDependencies"]
+    .into_iter()
+    .filter_map(|key| package.get(key)?.as_object())
+    .flat_map(|dependencies| dependencies.keys().cloned())
+}
+
+/// Finds the installed package like Node.js does, looking in the `node_modules` directories of the parent directories.
+fn resolve_npm_package(from: &Path, name: &str) -> Option<PathBuf> {
+  from
+    .ancestors()
+    .map(|dir| dir.join("node_modules").join(name))
+    .find(|dir| dir.join("package.json").is_file())
+    // pnpm links the packages to a store where their dependencies are siblings
+    .and_then(|dir| dir.canonicalize().ok())
+}
+
+/// The text of the `LICENSE` file of the package directory, if any.
+fn license_text(dir: &Path) -> Option<String> {
+  fs::read_dir(dir)
+    .ok()?
+    .flatten()
+    .find(|entry| {
+      let name = entry.file_name().to_string_lossy().to_uppercase();
+      name.starts_with("LICENSE") || name.starts_with("LICENCE")
+    })
+    .and_then(|entry| fs::read_to_string(entry.path()).ok())
+}
+
+fn npm_attribution(name: &str, package: &Value, dir: &Path) -> Attribution {
+  let string = |value: Option<&Value>, key: &str| -> Option<String> {
+    value.and_then(|value| {
+      value
+        .as_str()
+        .or_else(|| value.get(key)?.as_str())
+        .map(ToString::to_string)
+    })
+  };
+
+  // the deprecated `licenses` array of `{ "type": "MIT" }` objects
+  let license = string(package.get("license"), "type").or_else(|| {
+    let licenses = package
+      .get("licenses")?
+      .as_array()?
+      .iter()
+      .filter_map(|license| string(Some(license), "type"))
+      .collect::<Vec<_>>();
+    (!licenses.is_empty()).then(|| licenses.join(" OR "))
+  });
+
+  Attribution {
+    name: name.to_string(),
+    version: string(package.get("version"), "").unwrap_or_default(),
+    ecosystem: Ecosystem::Npm,
+    license_text: license.is_none().then(|| license_text(dir)).flatten(),
+    license,
+    authors: string(package.get("author"), "name").into_iter().collect(),
+    repository: string(package.get("repository"), "url")
+      .map(|url| url.trim_start_matches("git+").to_string()),
+  }
+}
+
+fn npm_attributions(manifest: &Path) -> crate::Result<Vec<Attribution>> {
+  let root = read_json(manifest)?;
+  let root_dir = manifest.parent().unwrap_or(Path::new(".")).canonicalize()?;
+
+  let mut attributions = Vec::new();
+  let mut missing = Vec::new();
+  let mut visited = HashSet::new();
+  let mut queue = npm_dependencies(&root)
+    .map(|name| (root_dir.clone(), name))
+    .collect::<Vec<_>>();
+  while let Some((from, name)) = queue.pop() {
+    let Some(dir) = resolve_npm_package(&from, &name) else {
+      missing.push(name);
+      continue;
+    };
+    if !visited.insert(dir.clone()) {
+      continue;
+    }
+    let package = read_json(&dir.join("package.json"))?;
+    attributions.push(npm_attribution(&name, &package, &dir));
+    queue.extend(npm_dependencies(&package).map(|dependency| (dir.clone(), dependency)));
+  }
+
+  if !missing.is_empty() {
+    missing.sort();
+    missing.dedup();
+    println!(
+      "cargo:warning=the attributions do not include the frontend packages that are not installed: {}",
+      missing.join(", ")
+    );
+  }
+
+  attributions.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+  attributions.dedup();
+  Ok(attributions)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn normalizes_npm_fields() {
+    let dir = Path::new("missing");
+    let attribution = npm_attribution(
+      "left-pad",
+      &json!({
+        "version": "1.3.0",
+        "license": "WTFPL",
+        "author": "azer",
+        "repository": { "type": "git", "url": "git+https://github.com/stevemao/left-pad.git" }
+      }),
+      dir,
+    );
+    assert_eq!(attribution.license.as_deref(), Some("WTFPL"));
+    assert_eq!(attribution.authors, vec!["azer".to_string()]);
+    assert_eq!(
+      attribution.repository.as_deref(),
+      Some("https://github.com/stevemao/left-pad.git")
+    );
+
+    let attribution = npm_attribution(
+      "legacy",
+      &json!({
+        "version": "0.1.0",
+        "licenses": [{ "type": "MIT" }, { "type": "Apache-2.0" }],
+        "author": { "name": "Jane", "email": "jane@example.com" }
+      }),
+      dir,
+    );
+    assert_eq!(attribution.license.as_deref(), Some("MIT OR Apache-2.0"));
+    assert_eq!(attribution.authors, vec!["Jane".to_string()]);
+    assert_eq!(attribution.license_text, None);
+  }
+
+  #[test]
+  fn reads_active_features() {
+    let features = ["default", "custom-protocol", "tray", "dep-x"].map(String::from);
+    let vars = [
+      "CARGO_FEATURE_DEFAULT",
+      "CARGO_FEATURE_CUSTOM_PROTOCOL",
+      "CARGO_FEATURE_OTHER",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect::<HashSet<_>>();
+    assert_eq!(
+      active_features(&features, &vars),
+      vec!["default".to_string(), "custom-protocol".to_string()]
+    );
+  }
+
+  #[test]
+  fn resolves_npm_dependencies() {
+    let root = env::temp_dir().join(format!("tauri-build-attributions-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    let write = |path: &str, contents: Value| {
+      let path = root.join(path);
+      fs::create_dir_all(path.parent().unwrap()).unwrap();
+      fs::write(path, contents.to_string()).unwrap();
+    };
+    write(
+      "package.json",
+      json!({ "dependencies": { "a": "^1" }, "devDependencies": { "vite": "^5" } }),
+    );
+    write(
+      "node_modules/a/package.json",
+      json!({ "version": "1.0.0", "dependencies": { "b": "^2" } }),
+    );
+    // nested version of `b`, shadowing the hoisted one
+    write(
+      "node_modules/a/node_modules/b/package.json",
+      json!({ "version": "2.0.0" }),
+    );
+    write("node_modules/b/package.json", json!({ "version": "1.0.0" }));
+    write("node_modules/a/LICENSE", json!("license text"));
+
+    let attributions = npm_attributions(&root.join("package.json")).unwrap();
+    let versions = attributions
+      .iter()
+      .map(|a| (a.name.as_str(), a.version.as_str()))
+      .collect::<Vec<_>>();
+    assert_eq!(versions, vec![("a", "1.0.0"), ("b", "2.0.0")]);
+    assert!(attributions[0].license_text.is_some());
+
+    fs::remove_dir_all(root).unwrap();
+  }
+}
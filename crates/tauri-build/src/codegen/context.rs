@@ -5,12 +5,11 @@
 use anyhow::{Context, Result};
 use std::{
   env::var,
-  fs::{create_dir_all, File},
-  io::{BufWriter, Write},
+  fs::create_dir_all,
   path::{Path, PathBuf},
 };
 use tauri_codegen::{context_codegen, ContextData};
-use tauri_utils::config::FrontendDist;
+use tauri_utils::{config::FrontendDist, write_if_changed};
 
 // TODO docs
 /// A builder for generating a Tauri application context during compile time.
@@ -101,6 +100,17 @@ impl CodegenContext {
       }
       _ => (),
     }
+    for dist_path in config
+      .app
+      .windows
+      .iter()
+      .filter_map(|w| w.frontend_dist.as_ref())
+    {
+      println!(
+        "cargo:rerun-if-changed={}",
+        config_parent.join(dist_path).display()
+      );
+    }
     for icon in &config.bundle.icon {
       println!(
         "cargo:rerun-if-changed={}",
@@ -146,14 +156,8 @@ impl CodegenContext {
     })?;
     create_dir_all(parent)?;
 
-    let mut file = File::create(&out).map(BufWriter::new).with_context(|| {
-      format!(
-        "Unable to create output file during tauri-build {}",
-        out.display()
-      )
-    })?;
-
-    writeln!(file, "{code}").with_context(|| {
+    // keep the file untouched when the code did not change so the crate including it is not rebuilt
+    write_if_changed(&out, format!("{code}\n")).with_context(|| {
       format!(
         "Unable to write tokenstream to out file during tauri-build {}",
         out.display()
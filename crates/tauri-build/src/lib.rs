@@ -28,6 +28,7 @@ use std::{
 };
 
 mod acl;
+mod attributions;
 #[cfg(feature = "codegen")]
 mod codegen;
 mod manifest;
@@ -39,6 +40,7 @@ mod static_vcruntime;
 pub use codegen::context::CodegenContext;
 
 pub use acl::{AppManifest, DefaultPermissionRule, InlinedPlugin};
+pub use attributions::Attributions;
 
 fn copy_file(from: impl AsRef<Path>, to: impl AsRef<Path>) -> Result<()> {
   let from = from.as_ref();
@@ -352,6 +354,7 @@ pub struct Attributes {
   codegen: Option<codegen::context::CodegenContext>,
   inlined_plugins: HashMap<&'static str, InlinedPlugin>,
   app_manifest: AppManifest,
+  attributions: Option<Attributions>,
 }
 
 impl Attributes {
@@ -405,6 +408,15 @@ impl Attributes {
     self
   }
 
+  /// Generates the third-party license attributions of the app, exposed by `tauri::AppHandle::attributions`.
+  ///
+  /// See [`Attributions`] for more information.
+  #[must_use]
+  pub fn attributions(mut self, attributions: Attributions) -> Self {
+    self.attributions.replace(attributions);
+    self
+  }
+
   #[cfg(feature = "codegen")]
   #[cfg_attr(docsrs, doc(cfg(feature = "codegen")))]
   #[must_use]
@@ -532,6 +544,10 @@ pub fn try_build(attributes: Attributes) -> Result<()> {
   // when running codegen in this build script, we need to access the env var directly
   env::set_var("TAURI_ENV_TARGET_TRIPLE", &target_triple);
 
+  if let Some(attributions) = &attributes.attributions {
+    attributions::generate(attributions, &out_dir, &target_triple)?;
+  }
+
   // TODO: far from ideal, but there's no other way to get the target dir, see <https://github.com/rust-lang/cargo/issues/5457>
   let target_dir = out_dir
     .parent()
@@ -25,6 +25,8 @@ struct AllowlistedDependency {
   kind: DependencyKind,
   all_cli_managed_features: Option<Vec<&'static str>>,
   expected_features: Vec<String>,
+  /// Features that must be enabled but are not managed, so they are never reported for removal.
+  required_features: Vec<String>,
 }
 
 pub fn check(config: &Config, manifest: &mut Manifest) -> Result<()> {
@@ -38,6 +40,7 @@ pub fn check(config: &Config, manifest: &mut Manifest) -> Result<()> {
         PatternKind::Isolation { .. } => vec!["isolation".to_string()],
         _ => vec![],
       },
+      required_features: Vec::new(),
     },
     AllowlistedDependency {
       name: "tauri".into(),
@@ -56,6 +59,12 @@ pub fn check(config: &Config, manifest: &mut Manifest) -> Result<()> {
         .filter(|f| f != &"tray-icon")
         .map(|f| f.to_string())
         .collect::<Vec<String>>(),
+      required_features: config
+        .app
+        .required_features()
+        .into_iter()
+        .map(|f| f.to_string())
+        .collect(),
     },
   ];
 
@@ -129,7 +138,14 @@ fn check_features(dependency: Dependency, metadata: &AllowlistedDependency) -> R
     Dependency::Inherited(dep) => dep.features,
   };
 
-  let diff = if let Some(all_cli_managed_features) = &metadata.all_cli_managed_features {
+  let missing_required = metadata
+    .required_features
+    .iter()
+    .filter(|f| !features.contains(f))
+    .cloned()
+    .collect::<Vec<String>>();
+
+  let mut diff = if let Some(all_cli_managed_features) = &metadata.all_cli_managed_features {
     features_diff(
       &features
         .into_iter()
@@ -147,6 +163,8 @@ fn check_features(dependency: Dependency, metadata: &AllowlistedDependency) -> R
     )
   };
 
+  diff.add.extend(missing_required);
+
   let mut error_message = String::new();
   if !diff.remove.is_empty() {
     error_message.push_str("remove the `");
@@ -214,4 +232,28 @@ mod tests {
       assert_eq!(crate::manifest::features_diff(&current, &expected), result);
     }
   }
+
+  #[test]
+  fn required_features_are_added_but_never_removed() {
+    let metadata = |required: &[&str]| super::AllowlistedDependency {
+      name: "tauri".into(),
+      alias: None,
+      kind: super::DependencyKind::Normal,
+      all_cli_managed_features: Some(vec!["tray-icon"]),
+      expected_features: Vec::new(),
+      required_features: required.iter().map(|f| f.to_string()).collect(),
+    };
+    let dependency = || {
+      cargo_toml::Dependency::Detailed(Box::new(cargo_toml::DependencyDetail {
+        features: vec!["kiosk".into()],
+        ..Default::default()
+      }))
+    };
+
+    assert_eq!(
+      super::check_features(dependency(), &metadata(&["kiosk", "panel-windows"])),
+      Err("add the `panel-windows` feature".into())
+    );
+    assert_eq!(super::check_features(dependency(), &metadata(&[])), Ok(()));
+  }
 }
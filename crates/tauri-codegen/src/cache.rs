@@ -0,0 +1,307 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Reuse of the code generated for the assets and the icons across builds.
+//!
+//! Compressing the assets and decoding the icons is much slower than reading the input files,
+//! so their results are kept in `$OUT_DIR` and reused by the next expansions of the context,
+//! e.g. when only Rust code, another part of the config or another asset changed.
+//!
+//! Each asset has its own cache entry, keyed by its path and the asset options.
+//! The entry records the size and modification time of the file, a hash of its contents,
+//! its compressed copy and its CSP hashes, next to an include file embedding the compressed copy.
+//! A file whose size or modification time changed is only compressed again when its contents changed.
+//! The entry is overwritten when the asset changes, so `$OUT_DIR` does not grow with every change.
+//!
+//! The icons are cached as generated code with a fingerprint of their contents in its name,
+//! and the code cached for their previous contents is deleted.
+
+use std::{
+  path::{Path, PathBuf},
+  time::UNIX_EPOCH,
+};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri_utils::write_if_changed;
+use walkdir::WalkDir;
+
+use crate::embedded_assets::{
+  asset_bytes, ensure_out_dir, CspHashes, EmbeddedAssetsError, EmbeddedAssetsResult,
+};
+
+/// What embedding an asset produced.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct AssetRecord {
+  /// The checksum of the asset file.
+  pub(crate) input_hash: String,
+  /// The compressed copy of the asset.
+  pub(crate) output: PathBuf,
+  /// The CSP hashes of the asset.
+  pub(crate) csp_hashes: CspHashes,
+}
+
+/// The size and modification time of a file.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct FileStamp {
+  len: u64,
+  modified_secs: u64,
+  modified_nanos: u32,
+}
+
+impl FileStamp {
+  fn of(path: &Path) -> Option<Self> {
+    let metadata = std::fs::metadata(path).ok()?;
+    let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some(Self {
+      len: metadata.len(),
+      modified_secs: modified.as_secs(),
+      modified_nanos: modified.subsec_nanos(),
+    })
+  }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StampedRecord {
+  stamp: FileStamp,
+  #[serde(flatten)]
+  record: AssetRecord,
+}
+
+/// The cache entry of an asset.
+pub(crate) struct AssetCache {
+  record: PathBuf,
+  include: PathBuf,
+  /// Taken before the file is read, so a change while it is embedded is noticed by the next expansion.
+  stamp: Option<FileStamp>,
+}
+
+impl AssetCache {
+  /// The entry of the asset at `path` in `dir`, where `extra` describes the options the asset is embedded with.
+  pub(crate) fn new(dir: &Path, path: &Path, extra: &str) -> Self {
+    let mut hasher = Sha256::new();
+    update_with_len(&mut hasher, env!("CARGO_PKG_VERSION").as_bytes());
+    update_with_len(&mut hasher, extra.as_bytes());
+    update_with_len(
+      &mut hasher,
+      format!("compression={}", cfg!(feature = "compression")).as_bytes(),
+    );
+    update_with_len(&mut hasher, path.to_string_lossy().as_bytes());
+    let id = hex(&hasher.finalize());
+    Self {
+      record: dir.join(format!("{id}.json")),
+      include: dir.join(format!("{id}.rs")),
+      stamp: FileStamp::of(path),
+    }
+  }
+
+  /// The cached record of the asset, if the asset at `path` did not change since it was stored.
+  pub(crate) fn load(&self, path: &Path) -> Option<AssetRecord> {
+    let stamp = self.stamp?;
+    let mut cached: StampedRecord =
+      serde_json::from_slice(&std::fs::read(&self.record).ok()?).ok()?;
+    if !cached.record.output.is_file() || !self.include.is_file() {
+      return None;
+    }
+    if cached.stamp != stamp {
+      // the file was touched, e.g. by a rebuild of the frontend, check whether its contents changed
+      let contents = std::fs::read(path).ok()?;
+      if crate::checksum(&contents).ok()? != cached.record.input_hash {
+        return None;
+      }
+      cached.stamp = stamp;
+      let _ = write_record(&self.record, &cached);
+    }
+    Some(cached.record)
+  }
+
+  /// Stores the record of the asset at `path` and writes its include file.
+  pub(crate) fn store(&self, path: &Path, record: &AssetRecord) -> EmbeddedAssetsResult<()> {
+    let code = asset_bytes(path, &record.output);
+    write_if_changed(&self.include, code.to_string()).map_err(|error| {
+      EmbeddedAssetsError::AssetWrite {
+        path: self.include.clone(),
+        error,
+      }
+    })?;
+    // an asset that cannot be stamped is embedded again by the next expansion
+    let Some(stamp) = self.stamp else {
+      return Ok(());
+    };
+    write_record(
+      &self.record,
+      &StampedRecord {
+        stamp,
+        record: record.clone(),
+      },
+    )
+  }
+
+  /// The file including the compressed copy of the asset.
+  pub(crate) fn into_include(self) -> PathBuf {
+    self.include
+  }
+}
+
+fn write_record(path: &Path, record: &StampedRecord) -> EmbeddedAssetsResult<()> {
+  let json = serde_json::to_vec(record).expect("failed to serialize the asset record");
+  // write to a temporary file first so an interrupted build does not leave a truncated record behind
+  let tmp = path.with_extension("json.tmp");
+  std::fs::write(&tmp, json)
+    .and_then(|()| std::fs::rename(&tmp, path))
+    .map_err(|error| EmbeddedAssetsError::AssetWrite {
+      path: path.to_path_buf(),
+      error,
+    })
+}
+
+/// Includes the code generated by a previous expansion for the same inputs and contents,
+/// generating it and writing it to `$OUT_DIR` if there is none.
+///
+/// `kind` and `paths` identify the generated code and `extra` must describe anything else it depends on.
+pub(crate) fn cached_code(
+  kind: &str,
+  paths: &[PathBuf],
+  extra: &str,
+  generate: impl FnOnce() -> EmbeddedAssetsResult<TokenStream>,
+) -> EmbeddedAssetsResult<TokenStream> {
+  let out_dir = ensure_out_dir()?;
+  let prefix = format!("tauri-codegen-{kind}-{}-", &hex(&identity(paths))[..16]);
+  let file_name = format!("{prefix}{}.rs", hex(&fingerprint(paths, extra)?));
+  let path = out_dir.join(&file_name);
+  if !path.exists() {
+    write_code(&path, &generate()?)?;
+    remove_stale(&out_dir, &prefix, &file_name);
+  }
+  Ok(quote!(::std::include!(::std::concat!(
+    ::std::env!("OUT_DIR"),
+    "/",
+    #file_name
+  ))))
+}
+
+/// Hashes the input paths, so the code generated for different inputs of the same kind does not collide.
+fn identity(paths: &[PathBuf]) -> [u8; 32] {
+  let mut hasher = Sha256::new();
+  for path in paths {
+    update_with_len(&mut hasher, path.to_string_lossy().as_bytes());
+  }
+  hasher.finalize().into()
+}
+
+/// Hashes the paths and contents of the files of the paths, recursively.
+fn fingerprint(paths: &[PathBuf], extra: &str) -> EmbeddedAssetsResult<[u8; 32]> {
+  let mut files = Vec::new();
+  for path in paths {
+    for entry in WalkDir::new(path).follow_links(true) {
+      let entry = entry.map_err(|error| EmbeddedAssetsError::Walkdir {
+        path: path.clone(),
+        error,
+      })?;
+      if !entry.file_type().is_dir() {
+        files.push(entry.into_path());
+      }
+    }
+  }
+  files.sort();
+
+  let mut hasher = Sha256::new();
+  update_with_len(&mut hasher, env!("CARGO_PKG_VERSION").as_bytes());
+  update_with_len(&mut hasher, extra.as_bytes());
+  update_with_len(
+    &mut hasher,
+    format!("compression={}", cfg!(feature = "compression")).as_bytes(),
+  );
+  for file in files {
+    let contents = std::fs::read(&file).map_err(|error| EmbeddedAssetsError::AssetRead {
+      path: file.clone(),
+      error,
+    })?;
+    update_with_len(&mut hasher, file.to_string_lossy().as_bytes());
+    update_with_len(&mut hasher, &contents);
+  }
+  Ok(hasher.finalize().into())
+}
+
+/// Length prefixes keep the boundaries between the hashed parts unambiguous.
+fn update_with_len(hasher: &mut Sha256, bytes: &[u8]) {
+  hasher.update((bytes.len() as u64).to_le_bytes());
+  hasher.update(bytes);
+}
+
+fn hex(bytes: &[u8]) -> String {
+  bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Deletes the code cached for previous contents of the same inputs.
+fn remove_stale(out_dir: &Path, prefix: &str, current: &str) {
+  let Ok(entries) = std::fs::read_dir(out_dir) else {
+    return;
+  };
+  for entry in entries.flatten() {
+    let file_name = entry.file_name();
+    let Some(file_name) = file_name.to_str() else {
+      continue;
+    };
+    if file_name.starts_with(prefix) && file_name.ends_with(".rs") && file_name != current {
+      let _ = std::fs::remove_file(entry.path());
+    }
+  }
+}
+
+fn write_code(path: &Path, code: &TokenStream) -> EmbeddedAssetsResult<()> {
+  // write to a temporary file first so an interrupted build does not leave a truncated file behind
+  let tmp = path.with_extension("rs.tmp");
+  std::fs::write(&tmp, code.to_string())
+    .and_then(|()| std::fs::rename(&tmp, path))
+    .map_err(|error| EmbeddedAssetsError::AssetWrite {
+      path: path.to_path_buf(),
+      error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reuses_assets_until_their_contents_change() {
+    let dir = std::env::temp_dir().join(format!("tauri-codegen-cache-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let asset = dir.join("index.html");
+    let output = dir.join("compressed");
+    std::fs::write(&asset, "<html></html>").unwrap();
+    std::fs::write(&output, "compressed").unwrap();
+
+    let cache = AssetCache::new(&dir, &asset, "csp=true");
+    assert!(cache.load(&asset).is_none());
+    let record = AssetRecord {
+      input_hash: crate::checksum(b"<html></html>").unwrap(),
+      output: output.clone(),
+      csp_hashes: Default::default(),
+    };
+    cache.store(&asset, &record).unwrap();
+    let include = std::fs::read_to_string(cache.into_include()).unwrap();
+    assert!(include.contains(&output.display().to_string()));
+
+    // the same contents written again are still cached
+    std::fs::write(&asset, "<html></html>").unwrap();
+    let cache = AssetCache::new(&dir, &asset, "csp=true");
+    assert_eq!(cache.load(&asset).unwrap().output, output);
+
+    // other options have their own entry
+    assert!(AssetCache::new(&dir, &asset, "csp=false")
+      .load(&asset)
+      .is_none());
+
+    std::fs::write(&asset, "<html><body></body></html>").unwrap();
+    assert!(AssetCache::new(&dir, &asset, "csp=true")
+      .load(&asset)
+      .is_none());
+
+    let _ = std::fs::remove_dir_all(&dir);
+  }
+}
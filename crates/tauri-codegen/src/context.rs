@@ -8,8 +8,10 @@ use std::path::{Path, PathBuf};
 use std::{ffi::OsStr, str::FromStr};
 
 use crate::{
+  cache,
   embedded_assets::{
-    ensure_out_dir, AssetOptions, CspHashes, EmbeddedAssets, EmbeddedAssetsResult,
+    ensure_out_dir, AssetOptions, CspHashes, EmbeddedAssets, EmbeddedAssetsError,
+    EmbeddedAssetsResult,
   },
   image::CachedIcon,
 };
@@ -179,9 +181,12 @@ pub fn context_codegen(data: ContextData) -> EmbeddedAssetsResult<TokenStream> {
     let assets = EmbeddedAssets::default();
     quote!(#assets)
   } else {
-    let assets = match &config.build.frontend_dist {
+    match &config.build.frontend_dist {
       Some(url) => match url {
-        FrontendDist::Url(_url) => Default::default(),
+        FrontendDist::Url(_url) => {
+          let assets = EmbeddedAssets::default();
+          quote!(#assets)
+        }
         FrontendDist::Directory(path) => {
           let assets_path = config_parent.join(path);
           if !assets_path.exists() {
@@ -189,21 +194,47 @@ pub fn context_codegen(data: ContextData) -> EmbeddedAssetsResult<TokenStream> {
               "The `frontendDist` configuration is set to `{path:?}` but this path doesn't exist"
             )
           }
-          EmbeddedAssets::new(assets_path, &options, map_core_assets(&options))?
+          embed_assets(vec![assets_path], &options)?
         }
-        FrontendDist::Files(files) => EmbeddedAssets::new(
+        FrontendDist::Files(files) => embed_assets(
           files
             .iter()
             .map(|p| config_parent.join(p))
             .collect::<Vec<_>>(),
           &options,
-          map_core_assets(&options),
         )?,
         _ => unimplemented!(),
       },
-      None => Default::default(),
-    };
-    quote!(#assets)
+      None => {
+        let assets = EmbeddedAssets::default();
+        quote!(#assets)
+      }
+    }
+  };
+
+  let window_assets = window_assets_codegen(&config, &config_parent, &options)?;
+
+  // reading every resource slows down the build, so only apps verifying them pay for it
+  #[cfg(feature = "resource-integrity")]
+  let resource_hashes = resource_hashes(&config);
+  #[cfg(not(feature = "resource-integrity"))]
+  let resource_hashes = BTreeMap::<String, String>::new();
+  let resource_hashes_code = if resource_hashes.is_empty() {
+    quote!()
+  } else {
+    let hashes = resource_hashes.iter().map(|(target, hash)| {
+      quote!((::std::string::String::from(#target), ::std::string::String::from(#hash)))
+    });
+    quote!(context.set_resource_hashes(::std::collections::HashMap::from([#(#hashes),*]));)
+  };
+
+  // generated by `tauri_build::Attributes::attributions`
+  let attributions_code = match std::env::var("TAURI_ATTRIBUTIONS_PATH") {
+    Ok(path) => quote!(context.set_attributions(
+      #root::utils::attributions::parse(include_str!(#path))
+        .expect("failed to parse the generated attributions")
+    );),
+    Err(_) => quote!(),
   };
 
   let out_dir = ensure_out_dir()?;
@@ -218,7 +249,7 @@ pub fn context_codegen(data: ContextData) -> EmbeddedAssetsResult<TokenStream> {
         "icons/icon.ico",
       );
       if icon_path.exists() {
-        let icon = CachedIcon::new(&root, &icon_path)?;
+        let icon = cached_icon(&root, &icon_path)?;
         quote!(::std::option::Option::Some(#icon))
       } else {
         let icon_path = find_icon(
@@ -227,7 +258,7 @@ pub fn context_codegen(data: ContextData) -> EmbeddedAssetsResult<TokenStream> {
           |i| i.ends_with(".png"),
           "icons/icon.png",
         );
-        let icon = CachedIcon::new(&root, &icon_path)?;
+        let icon = cached_icon(&root, &icon_path)?;
         quote!(::std::option::Option::Some(#icon))
       }
     } else {
@@ -238,7 +269,7 @@ pub fn context_codegen(data: ContextData) -> EmbeddedAssetsResult<TokenStream> {
         |i| i.ends_with(".png"),
         "icons/icon.png",
       );
-      let icon = CachedIcon::new(&root, &icon_path)?;
+      let icon = cached_icon(&root, &icon_path)?;
       quote!(::std::option::Option::Some(#icon))
     }
   };
@@ -259,7 +290,7 @@ pub fn context_codegen(data: ContextData) -> EmbeddedAssetsResult<TokenStream> {
       );
     }
 
-    let icon = CachedIcon::new_raw(&root, &icon_path)?;
+    let icon = cached_raw_icon(&root, &icon_path)?;
     quote!(::std::option::Option::Some(#icon.to_vec()))
   } else {
     quote!(::std::option::Option::None)
@@ -289,8 +320,29 @@ pub fn context_codegen(data: ContextData) -> EmbeddedAssetsResult<TokenStream> {
   let with_tray_icon_code = if target.is_desktop() {
     if let Some(tray) = &config.app.tray_icon {
       let tray_icon_icon_path = config_parent.join(&tray.icon_path);
-      let icon = CachedIcon::new(&root, &tray_icon_icon_path)?;
-      quote!(context.set_tray_icon(::std::option::Option::Some(#icon));)
+      let icon = cached_icon(&root, &tray_icon_icon_path)?;
+      let set_icon_pair = match (&tray.light_icon_path, &tray.dark_icon_path) {
+        (Some(light), Some(dark)) => {
+          let light = cached_icon(&root, &config_parent.join(light))?;
+          let dark = cached_icon(&root, &config_parent.join(dark))?;
+          quote!(context.set_tray_icon_pair(::std::option::Option::Some((#light, #dark)));)
+        }
+        (Some(_), None) => {
+          return Err(EmbeddedAssetsError::IncompleteTrayIconPair {
+            missing: "darkIconPath",
+          })
+        }
+        (None, Some(_)) => {
+          return Err(EmbeddedAssetsError::IncompleteTrayIconPair {
+            missing: "lightIconPath",
+          })
+        }
+        (None, None) => quote!(),
+      };
+      quote! {
+        context.set_tray_icon(::std::option::Option::Some(#icon));
+        #set_icon_pair
+      }
     } else {
       quote!()
     }
@@ -484,6 +536,9 @@ pub fn context_codegen(data: ContextData) -> EmbeddedAssetsResult<TokenStream> {
 
     #with_tray_icon_code
     #maybe_config_parent_setter
+    #(#window_assets)*
+    #resource_hashes_code
+    #attributions_code
 
     context
   });
@@ -505,6 +560,107 @@ pub fn context_codegen(data: ContextData) -> EmbeddedAssetsResult<TokenStream> {
   }))
 }
 
+/// Generates the code registering the assets of the windows configured with their own `frontendDist`,
+/// keyed by window label.
+///
+/// They are always embedded since `devUrl` only serves the main frontend.
+fn window_assets_codegen(
+  config: &Config,
+  config_parent: &Path,
+  options: &AssetOptions,
+) -> EmbeddedAssetsResult<Vec<TokenStream>> {
+  let mut window_assets = Vec::new();
+  for window in &config.app.windows {
+    if let Some(path) = &window.frontend_dist {
+      let assets_path = config_parent.join(path);
+      if !assets_path.exists() {
+        panic!(
+          "The `frontendDist` configuration of the `{}` window is set to `{path:?}` but this path doesn't exist",
+          window.label
+        )
+      }
+      let assets = embed_assets(vec![assets_path], options)?;
+      let label = &window.label;
+      window_assets.push(quote!(
+        context.set_window_assets(#label, ::std::boxed::Box::new(#assets));
+      ));
+    }
+  }
+  Ok(window_assets)
+}
+
+/// Embeds the assets, reusing the compressed files of a previous expansion for the files that did not change.
+fn embed_assets(paths: Vec<PathBuf>, options: &AssetOptions) -> EmbeddedAssetsResult<TokenStream> {
+  let extra = format!(
+    "csp={} csp_modification={:?}",
+    options.csp, options.dangerous_disable_asset_csp_modification
+  );
+  let assets = EmbeddedAssets::cached(paths, options, &extra, map_core_assets(options))?;
+  Ok(quote!(#assets))
+}
+
+/// Embeds the icon as RGBA data, reusing the code generated by a previous expansion when the file did not change.
+fn cached_icon(root: &TokenStream, path: &Path) -> EmbeddedAssetsResult<TokenStream> {
+  // missing icons are reported by `CachedIcon`
+  if !path.is_file() {
+    let icon = CachedIcon::new(root, path)?;
+    return Ok(quote!(#icon));
+  }
+  let extra = format!("root={root}");
+  cache::cached_code("icon", &[path.to_path_buf()], &extra, || {
+    let icon = CachedIcon::new(root, path)?;
+    Ok(quote!(#icon))
+  })
+}
+
+/// Embeds the icon file as is, reusing the code generated by a previous expansion when the file did not change.
+fn cached_raw_icon(root: &TokenStream, path: &Path) -> EmbeddedAssetsResult<TokenStream> {
+  if !path.is_file() {
+    let icon = CachedIcon::new_raw(root, path)?;
+    return Ok(quote!(#icon));
+  }
+  let extra = format!("root={root}");
+  cache::cached_code("raw-icon", &[path.to_path_buf()], &extra, || {
+    let icon = CachedIcon::new_raw(root, path)?;
+    Ok(quote!(#icon))
+  })
+}
+
+/// Hashes the files of `bundle > resources` with SHA-256, keyed by their path in the resource directory.
+///
+/// The resource patterns are resolved like `tauri-build` does when copying them,
+/// files that cannot be read are skipped since the build script reports them.
+#[cfg(feature = "resource-integrity")]
+fn resource_hashes(config: &Config) -> BTreeMap<String, String> {
+  use tauri_utils::{config::BundleResources, resources::ResourcePaths};
+
+  let resources = match &config.bundle.resources {
+    Some(BundleResources::List(list)) => ResourcePaths::new(list.as_slice(), true),
+    Some(BundleResources::Map(map)) => ResourcePaths::from_map(map, true),
+    None => return BTreeMap::new(),
+  };
+
+  let mut hashes = BTreeMap::new();
+  for resource in resources.iter().flatten() {
+    let Ok(bytes) = std::fs::read(resource.path()) else {
+      continue;
+    };
+
+    let hash = Sha256::digest(&bytes)
+      .iter()
+      .map(|b| format!("{b:02x}"))
+      .collect::<String>();
+    let target = resource
+      .target()
+      .components()
+      .map(|c| c.as_os_str().to_string_lossy())
+      .collect::<Vec<_>>()
+      .join("/");
+    hashes.insert(target, hash);
+  }
+  hashes
+}
+
 fn find_icon(
   config: &Config,
   config_parent: &Path,
@@ -520,3 +676,53 @@ fn find_icon(
     .unwrap_or(default);
   config_parent.join(icon_path)
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tauri_utils::config::WindowConfig;
+
+  #[test]
+  fn embeds_window_frontends_by_label() {
+    let dir = std::env::temp_dir().join(format!(
+      "tauri-codegen-window-assets-{}",
+      std::process::id()
+    ));
+    let out_dir = dir.join("out");
+    std::fs::create_dir_all(dir.join("popup")).unwrap();
+    std::fs::create_dir_all(&out_dir).unwrap();
+    std::fs::write(dir.join("popup/index.html"), "<html></html>").unwrap();
+    std::env::set_var("OUT_DIR", &out_dir);
+
+    let mut config = Config::default();
+    config.app.windows = vec![
+      WindowConfig {
+        label: "main".into(),
+        ..Default::default()
+      },
+      WindowConfig {
+        label: "popup".into(),
+        frontend_dist: Some("popup".into()),
+        ..Default::default()
+      },
+    ];
+
+    let options = AssetOptions::new(PatternKind::Brownfield);
+    let window_assets = window_assets_codegen(&config, &dir, &options).unwrap();
+
+    assert_eq!(window_assets.len(), 1);
+    let code = window_assets[0].to_string();
+    assert!(code.starts_with("context . set_window_assets (\"popup\""));
+    assert!(code.contains("\"index.html\" => include !"));
+    // the include file of the asset embeds `index.html`
+    let include = std::fs::read_dir(out_dir.join("tauri-codegen-assets"))
+      .unwrap()
+      .map(|entry| entry.unwrap().path())
+      .filter(|path| path.extension() == Some(OsStr::new("rs")))
+      .map(|path| std::fs::read_to_string(path).unwrap())
+      .collect::<String>();
+    assert!(include.contains("/index.html"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+  }
+}
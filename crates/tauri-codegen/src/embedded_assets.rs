@@ -5,6 +5,7 @@
 use base64::Engine;
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens, TokenStreamExt};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::{
   collections::HashMap,
@@ -16,14 +17,23 @@ use tauri_utils::{assets::AssetKey, config::DisabledCspModificationKind};
 use thiserror::Error;
 use walkdir::{DirEntry, WalkDir};
 
+use crate::cache::{AssetCache, AssetRecord};
+
 #[cfg(feature = "compression")]
 use brotli::enc::backward_references::BrotliEncoderParams;
 
 /// The subdirectory inside the target directory we want to place assets.
 const TARGET_PATH: &str = "tauri-codegen-assets";
 
-/// (key, (original filepath, compressed bytes))
-type Asset = (AssetKey, (PathBuf, PathBuf));
+/// (key, embedded asset)
+type Asset = (AssetKey, EmbeddedAsset);
+
+/// An asset file, its compressed copy and the file including them when the asset is cached.
+struct EmbeddedAsset {
+  input: PathBuf,
+  output: PathBuf,
+  include: Option<PathBuf>,
+}
 
 /// All possible errors while reading and compressing an [`EmbeddedAssets`] directory
 #[derive(Debug, Error)]
@@ -61,6 +71,9 @@ pub enum EmbeddedAssetsError {
 
   #[error("version error: {0}")]
   Version(#[from] semver::Error),
+
+  #[error("the `{missing}` of the tray icon is not set, `lightIconPath` and `darkIconPath` must be set together")]
+  IncompleteTrayIconPair { missing: &'static str },
 }
 
 pub type EmbeddedAssetsResult<T> = Result<T, EmbeddedAssetsError>;
@@ -76,7 +89,7 @@ pub type EmbeddedAssetsResult<T> = Result<T, EmbeddedAssetsError>;
 /// the compressed assets in that application's binary.
 #[derive(Default)]
 pub struct EmbeddedAssets {
-  assets: HashMap<AssetKey, (PathBuf, PathBuf)>,
+  assets: HashMap<AssetKey, EmbeddedAsset>,
   csp_hashes: CspHashes,
 }
 
@@ -97,14 +110,11 @@ impl From<Vec<PathBuf>> for EmbeddedAssetsInput {
 /// Holds a list of (prefix, entry)
 struct RawEmbeddedAssets {
   paths: Vec<(PathBuf, DirEntry)>,
-  csp_hashes: CspHashes,
 }
 
 impl RawEmbeddedAssets {
   /// Creates a new list of (prefix, entry) from a collection of inputs.
-  fn new(input: EmbeddedAssetsInput, options: &AssetOptions) -> Result<Self, EmbeddedAssetsError> {
-    let mut csp_hashes = CspHashes::default();
-
+  fn new(input: EmbeddedAssetsInput) -> Result<Self, EmbeddedAssetsError> {
     input
       .0
       .into_iter()
@@ -130,15 +140,7 @@ impl RawEmbeddedAssets {
           Ok(entry) if entry.file_type().is_dir() => None,
 
           // compress all files encountered
-          Ok(entry) => {
-            if let Err(error) = csp_hashes
-              .add_if_applicable(&entry, &options.dangerous_disable_asset_csp_modification)
-            {
-              Some(Err(error))
-            } else {
-              Some(Ok((prefix, entry)))
-            }
-          }
+          Ok(entry) => Some(Ok((prefix, entry))),
 
           // pass down error through filter to fail when encountering any error
           Err(error) => Some(Err(EmbeddedAssetsError::Walkdir {
@@ -148,12 +150,12 @@ impl RawEmbeddedAssets {
         }
       })
       .collect::<Result<Vec<(PathBuf, DirEntry)>, _>>()
-      .map(|paths| Self { paths, csp_hashes })
+      .map(|paths| Self { paths })
   }
 }
 
 /// Holds all hashes that we will apply on the CSP tag/header.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct CspHashes {
   /// Scripts that are part of the asset collection (JS or MJS files).
   pub(crate) scripts: Vec<String>,
@@ -195,6 +197,13 @@ impl CspHashes {
 
     Ok(())
   }
+
+  /// Adds the hashes of another collection of assets.
+  fn extend(&mut self, other: CspHashes) {
+    self.scripts.extend(other.scripts);
+    self.inline_scripts.extend(other.inline_scripts);
+    self.styles.extend(other.styles);
+  }
 }
 
 /// Options used to embed assets.
@@ -252,6 +261,28 @@ impl EmbeddedAssets {
   pub fn new(
     input: impl Into<EmbeddedAssetsInput>,
     options: &AssetOptions,
+    map: impl FnMut(&AssetKey, &Path, &mut Vec<u8>, &mut CspHashes) -> Result<(), EmbeddedAssetsError>,
+  ) -> Result<Self, EmbeddedAssetsError> {
+    Self::build(input.into(), options, None, map)
+  }
+
+  /// Like [`Self::new`], but reuses the compressed file and the CSP hashes of the assets
+  /// that did not change since a previous expansion, see [`crate::cache`].
+  ///
+  /// `map` must only depend on the asset it is called with and on what `extra` describes.
+  pub(crate) fn cached(
+    input: impl Into<EmbeddedAssetsInput>,
+    options: &AssetOptions,
+    extra: &str,
+    map: impl FnMut(&AssetKey, &Path, &mut Vec<u8>, &mut CspHashes) -> Result<(), EmbeddedAssetsError>,
+  ) -> Result<Self, EmbeddedAssetsError> {
+    Self::build(input.into(), options, Some(extra), map)
+  }
+
+  fn build(
+    input: EmbeddedAssetsInput,
+    options: &AssetOptions,
+    cache: Option<&str>,
     mut map: impl FnMut(
       &AssetKey,
       &Path,
@@ -259,30 +290,106 @@ impl EmbeddedAssets {
       &mut CspHashes,
     ) -> Result<(), EmbeddedAssetsError>,
   ) -> Result<Self, EmbeddedAssetsError> {
-    // we need to pre-compute all files now, so that we can inject data from all files into a few
-    let RawEmbeddedAssets { paths, csp_hashes } = RawEmbeddedAssets::new(input.into(), options)?;
+    let RawEmbeddedAssets { paths } = RawEmbeddedAssets::new(input)?;
 
-    struct CompressState {
-      csp_hashes: CspHashes,
-      assets: HashMap<AssetKey, (PathBuf, PathBuf)>,
-    }
+    // we must canonicalize the base of our paths to allow long paths on windows
+    let out_dir = std::env::var("OUT_DIR")
+      .map_err(|_| EmbeddedAssetsError::OutDir)
+      .map(PathBuf::from)
+      .and_then(|p| p.canonicalize().map_err(|_| EmbeddedAssetsError::OutDir))
+      .map(|p| p.join(TARGET_PATH))?;
 
-    let CompressState { assets, csp_hashes } = paths.into_iter().try_fold(
-      CompressState {
-        csp_hashes,
-        assets: HashMap::new(),
-      },
-      move |mut state, (prefix, entry)| {
-        let (key, asset) =
-          Self::compress_file(&prefix, entry.path(), &mut map, &mut state.csp_hashes)?;
-        state.assets.insert(key, asset);
-        Result::<_, EmbeddedAssetsError>::Ok(state)
-      },
-    )?;
+    // make sure that our output directory is created
+    std::fs::create_dir_all(&out_dir).map_err(|_| EmbeddedAssetsError::OutDir)?;
+
+    let mut csp_hashes = CspHashes::default();
+    let mut assets = HashMap::new();
+    for (prefix, entry) in paths {
+      // the hashes are collected per asset so they can be cached with it
+      let mut hashes = CspHashes::default();
+      let (key, asset) = Self::embed_file(
+        &out_dir,
+        &prefix,
+        &entry,
+        options,
+        cache,
+        &mut map,
+        &mut hashes,
+      )?;
+      csp_hashes.extend(hashes);
+      assets.insert(key, asset);
+    }
 
     Ok(Self { assets, csp_hashes })
   }
 
+  /// Compresses a file, or reuses its cached copy when `cache` is set and the file did not change.
+  fn embed_file(
+    out_dir: &Path,
+    prefix: &Path,
+    entry: &DirEntry,
+    options: &AssetOptions,
+    cache: Option<&str>,
+    map: &mut impl FnMut(
+      &AssetKey,
+      &Path,
+      &mut Vec<u8>,
+      &mut CspHashes,
+    ) -> Result<(), EmbeddedAssetsError>,
+    csp_hashes: &mut CspHashes,
+  ) -> Result<Asset, EmbeddedAssetsError> {
+    let path = entry.path();
+
+    // get a key to the asset path without the asset directory prefix
+    let key = path
+      .strip_prefix(prefix)
+      .map(AssetKey::from) // format the path for use in assets
+      .map_err(|_| EmbeddedAssetsError::PrefixInvalid {
+        prefix: prefix.to_owned(),
+        path: path.to_owned(),
+      })?;
+
+    let cache = cache.map(|extra| AssetCache::new(out_dir, path, extra));
+    if let Some(record) = cache.as_ref().and_then(|cache| cache.load(path)) {
+      *csp_hashes = record.csp_hashes;
+      let include = cache.map(AssetCache::into_include);
+      return Ok((
+        key,
+        EmbeddedAsset {
+          input: path.into(),
+          output: record.output,
+          include,
+        },
+      ));
+    }
+
+    csp_hashes.add_if_applicable(entry, &options.dangerous_disable_asset_csp_modification)?;
+    let (output, input_hash) = Self::compress_file(out_dir, &key, path, map, csp_hashes)?;
+
+    let include = match cache {
+      Some(cache) => {
+        cache.store(
+          path,
+          &AssetRecord {
+            input_hash,
+            output: output.clone(),
+            csp_hashes: csp_hashes.clone(),
+          },
+        )?;
+        Some(cache.into_include())
+      }
+      None => None,
+    };
+    Ok((
+      key,
+      EmbeddedAsset {
+        input: path.into(),
+        output,
+        include,
+      },
+    ))
+  }
+
   /// Use highest compression level for release, the fastest one for everything else
   #[cfg(feature = "compression")]
   fn compression_settings() -> BrotliEncoderParams {
@@ -300,9 +407,10 @@ impl EmbeddedAssets {
     settings
   }
 
-  /// Compress a file and spit out the information in a [`HashMap`] friendly form.
+  /// Compress a file into the output directory, returning the compressed file and the checksum of the file.
   fn compress_file(
-    prefix: &Path,
+    out_dir: &Path,
+    key: &AssetKey,
     path: &Path,
     map: &mut impl FnMut(
       &AssetKey,
@@ -311,33 +419,15 @@ impl EmbeddedAssets {
       &mut CspHashes,
     ) -> Result<(), EmbeddedAssetsError>,
     csp_hashes: &mut CspHashes,
-  ) -> Result<Asset, EmbeddedAssetsError> {
+  ) -> Result<(PathBuf, String), EmbeddedAssetsError> {
     let mut input = std::fs::read(path).map_err(|error| EmbeddedAssetsError::AssetRead {
       path: path.to_owned(),
       error,
     })?;
-
-    // get a key to the asset path without the asset directory prefix
-    let key = path
-      .strip_prefix(prefix)
-      .map(AssetKey::from) // format the path for use in assets
-      .map_err(|_| EmbeddedAssetsError::PrefixInvalid {
-        prefix: prefix.to_owned(),
-        path: path.to_owned(),
-      })?;
+    let input_hash = crate::checksum(&input).map_err(EmbeddedAssetsError::Hex)?;
 
     // perform any caller-requested input manipulation
-    map(&key, path, &mut input, csp_hashes)?;
-
-    // we must canonicalize the base of our paths to allow long paths on windows
-    let out_dir = std::env::var("OUT_DIR")
-      .map_err(|_| EmbeddedAssetsError::OutDir)
-      .map(PathBuf::from)
-      .and_then(|p| p.canonicalize().map_err(|_| EmbeddedAssetsError::OutDir))
-      .map(|p| p.join(TARGET_PATH))?;
-
-    // make sure that our output directory is created
-    std::fs::create_dir_all(&out_dir).map_err(|_| EmbeddedAssetsError::OutDir)?;
+    map(key, path, &mut input, csp_hashes)?;
 
     // get a hash of the input - allows for caching existing files
     let hash = crate::checksum(&input).map_err(EmbeddedAssetsError::Hex)?;
@@ -382,23 +472,25 @@ impl EmbeddedAssets {
       }
     }
 
-    Ok((key, (path.into(), out_path)))
+    Ok((out_path, input_hash))
   }
 }
 
 impl ToTokens for EmbeddedAssets {
   fn to_tokens(&self, tokens: &mut TokenStream) {
     let mut assets = TokenStream::new();
-    for (key, (input, output)) in &self.assets {
+    for (key, asset) in &self.assets {
       let key: &str = key.as_ref();
-      let input = input.display().to_string();
-      let output = output.display().to_string();
-
-      // add original asset as a compiler dependency, rely on dead code elimination to clean it up
-      assets.append_all(quote!(#key => {
-        const _: &[u8] = include_bytes!(#input);
-        include_bytes!(#output)
-      },));
+      match &asset.include {
+        Some(include) => {
+          let include = include.display().to_string();
+          assets.append_all(quote!(#key => include!(#include),));
+        }
+        None => {
+          let value = asset_bytes(&asset.input, &asset.output);
+          assets.append_all(quote!(#key => #value,));
+        }
+      }
     }
 
     let mut global_hashes = TokenStream::new();
@@ -432,6 +524,18 @@ impl ToTokens for EmbeddedAssets {
   }
 }
 
+/// The bytes of the compressed asset.
+pub(crate) fn asset_bytes(input: &Path, output: &Path) -> TokenStream {
+  let input = input.display().to_string();
+  let output = output.display().to_string();
+
+  // add original asset as a compiler dependency, rely on dead code elimination to clean it up
+  quote!({
+    const _: &[u8] = include_bytes!(#input);
+    include_bytes!(#output)
+  })
+}
+
 pub(crate) fn ensure_out_dir() -> EmbeddedAssetsResult<PathBuf> {
   let out_dir = std::env::var("OUT_DIR")
     .map_err(|_| EmbeddedAssetsError::OutDir)
@@ -25,6 +25,7 @@ pub use tauri_utils::config::{parse::ConfigError, Config};
 use tauri_utils::platform::Target;
 use tauri_utils::write_if_changed;
 
+mod cache;
 mod context;
 pub mod embedded_assets;
 pub mod image;
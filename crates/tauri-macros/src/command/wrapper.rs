@@ -38,6 +38,13 @@ struct WrapperAttributes {
   root: TokenStream2,
   execution_context: ExecutionContext,
   argument_case: ArgumentCase,
+  cache: Option<Cache>,
+}
+
+/// The response caching of the command, set with `cache = "30s"` and `key = "args"`.
+struct Cache {
+  ttl_ms: u64,
+  by_args: bool,
 }
 
 impl Parse for WrapperAttributes {
@@ -46,7 +53,9 @@ impl Parse for WrapperAttributes {
       root: quote!(::tauri),
       execution_context: ExecutionContext::Blocking,
       argument_case: ArgumentCase::Camel,
+      cache: None,
     };
+    let mut cache_key: Option<(bool, Span)> = None;
 
     let attrs = Punctuated::<WrapperAttributeKind, Token![,]>::parse_terminated(input)?;
     for attr in attrs {
@@ -72,6 +81,38 @@ impl Parse for WrapperAttributes {
                 }
               };
             }
+          } else if v.path.is_ident("cache") {
+            let ttl_ms = match &v.value {
+              Expr::Lit(ExprLit {
+                lit: Lit::Str(s),
+                attrs: _,
+              }) => parse_duration_ms(&s.value()),
+              _ => None,
+            }
+            .ok_or_else(|| {
+              syn::Error::new_spanned(
+                &v.value,
+                "expected a duration such as \"500ms\", \"30s\", \"5m\" or \"1h\"",
+              )
+            })?;
+            wrapper_attributes.cache.replace(Cache {
+              ttl_ms,
+              by_args: true,
+            });
+          } else if v.path.is_ident("key") {
+            let by_args = match &v.value {
+              Expr::Lit(ExprLit {
+                lit: Lit::Str(s),
+                attrs: _,
+              }) => match s.value().as_str() {
+                "args" => Some(true),
+                "none" => Some(false),
+                _ => None,
+              },
+              _ => None,
+            }
+            .ok_or_else(|| syn::Error::new_spanned(&v.value, "expected \"args\" or \"none\""))?;
+            cache_key.replace((by_args, v.value.span()));
           } else if v.path.is_ident("root") {
             if let Expr::Lit(ExprLit {
               lit: Lit::Str(s),
@@ -92,7 +133,7 @@ impl Parse for WrapperAttributes {
         WrapperAttributeKind::Meta(Meta::Path(_)) => {
           return Err(syn::Error::new(
             input.span(),
-            "unexpected input, expected one of `rename_all`, `root`, `async`",
+            "unexpected input, expected one of `rename_all`, `root`, `async`, `cache`, `key`",
           ));
         }
         WrapperAttributeKind::Async => {
@@ -101,10 +142,35 @@ impl Parse for WrapperAttributes {
       }
     }
 
+    if let Some((by_args, span)) = cache_key {
+      match &mut wrapper_attributes.cache {
+        Some(cache) => cache.by_args = by_args,
+        None => return Err(syn::Error::new(span, "`key` requires `cache`")),
+      }
+    }
+
     Ok(wrapper_attributes)
   }
 }
 
+/// Parses a duration such as `500ms`, `30s`, `5m` or `1h` into milliseconds.
+fn parse_duration_ms(duration: &str) -> Option<u64> {
+  let duration = duration.trim();
+  let split = duration
+    .find(|c: char| !c.is_ascii_digit())
+    .unwrap_or(duration.len());
+  let (value, unit) = duration.split_at(split);
+  let value = value.parse::<u64>().ok()?;
+  let factor = match unit.trim() {
+    "ms" => 1,
+    "s" => 1000,
+    "m" => 60 * 1000,
+    "h" => 60 * 60 * 1000,
+    _ => return None,
+  };
+  value.checked_mul(factor).filter(|ms| *ms > 0)
+}
+
 /// The execution context of the command.
 enum ExecutionContext {
   Async,
@@ -224,7 +290,16 @@ pub fn wrapper(attributes: TokenStream, item: TokenStream) -> TokenStream {
     acl,
   } = invoke;
 
-  let root = attrs.root;
+  let root = &attrs.root;
+
+  let maybe_cache = match &attrs.cache {
+    Some(Cache { ttl_ms, by_args }) => quote!(
+      if #root::ipc::private::cache_response(&#message, &#resolver, #ttl_ms, #by_args) {
+        return true;
+      }
+    ),
+    None => quote!(),
+  };
 
   let kind = match attrs.execution_context {
     ExecutionContext::Async if function.sig.asyncness.is_none() => "sync_threadpool",
@@ -271,6 +346,8 @@ pub fn wrapper(attributes: TokenStream, item: TokenStream) -> TokenStream {
 
           #maybe_span
 
+          #maybe_cache
+
           #body
       }};
     }
@@ -29,6 +29,13 @@ mod context;
 
 /// Mark a function as a command handler. It creates a wrapper function with the necessary glue code.
 ///
+/// # Response caching
+///
+/// `#[command(cache = "30s")]` caches the successful responses of a read-only command for the given duration
+/// (`ms`, `s`, `m` or `h`), keyed by its arguments. Use `key = "none"` to share a single response for all arguments.
+/// Responses are never shared between webviews or origins, they are also keyed by the label and origin of the calling webview.
+/// The cache is invalidated with `AppHandle::invalidate_command_cache` and `AppHandle::clear_command_cache`.
+///
 /// # Stability
 /// The output of this macro is managed internally by Tauri,
 /// and should not be accessed directly on normal applications.
@@ -0,0 +1,189 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! HTTP cache control of the platform webviews.
+
+use tauri_runtime::webview::CacheMode;
+use wry::WebView;
+
+/// Clears the disk and memory caches of the webview, keeping the cookies and the storage.
+pub fn clear_cache(webview: &WebView) {
+  #[cfg(windows)]
+  {
+    use webview2_com::{
+      ClearBrowsingDataCompletedHandler,
+      Microsoft::Web::WebView2::Win32::{
+        ICoreWebView2Profile2, ICoreWebView2_13, COREWEBVIEW2_BROWSING_DATA_KINDS_DISK_CACHE,
+      },
+    };
+    use windows::core::Interface;
+    use wry::WebViewExtWindows;
+
+    let result = unsafe {
+      webview
+        .controller()
+        .CoreWebView2()
+        .and_then(|webview| webview.cast::<ICoreWebView2_13>())
+        .and_then(|webview| webview.Profile())
+        .and_then(|profile| profile.cast::<ICoreWebView2Profile2>())
+        .and_then(|profile| {
+          profile.ClearBrowsingData(
+            COREWEBVIEW2_BROWSING_DATA_KINDS_DISK_CACHE,
+            &ClearBrowsingDataCompletedHandler::create(Box::new(|_| Ok(()))),
+          )
+        })
+    };
+    if let Err(e) = result {
+      log::error!("failed to clear the webview cache: {e}");
+    }
+  }
+
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  {
+    use webkit2gtk::{WebContextExt, WebViewExt, WebsiteDataManagerExtManual, WebsiteDataTypes};
+    use wry::WebViewExtUnix;
+
+    if let Some(manager) = webview
+      .webview()
+      .context()
+      .and_then(|context| context.website_data_manager())
+    {
+      manager.clear(
+        WebsiteDataTypes::DISK_CACHE | WebsiteDataTypes::MEMORY_CACHE,
+        gtk::glib::TimeSpan::from_seconds(0),
+        None::<&gtk::gio::Cancellable>,
+        |result| {
+          if let Err(e) = result {
+            log::error!("failed to clear the webview cache: {e}");
+          }
+        },
+      );
+    }
+  }
+
+  #[cfg(target_os = "macos")]
+  {
+    use objc2::{class, msg_send, runtime::AnyObject};
+    use objc2_foundation::{NSSet, NSString};
+    use wry::WebViewExtMacOS;
+
+    unsafe {
+      let webview: *mut AnyObject = webview.webview().cast();
+      let configuration: *mut AnyObject = msg_send![webview, configuration];
+      let store: *mut AnyObject = msg_send![configuration, websiteDataStore];
+      let types = NSSet::from_vec(vec![
+        NSString::from_str("WKWebsiteDataTypeDiskCache"),
+        NSString::from_str("WKWebsiteDataTypeMemoryCache"),
+      ]);
+      let since: *mut AnyObject = msg_send![class!(NSDate), distantPast];
+      let handler = block2::RcBlock::new(|| {});
+      let () = msg_send![
+        store,
+        removeDataOfTypes: &*types,
+        modifiedSince: since,
+        completionHandler: &*handler
+      ];
+    }
+  }
+
+  #[cfg(target_os = "android")]
+  {
+    use wry::WebViewExtAndroid;
+
+    webview.handle().exec(|env, _activity, webview| {
+      if let Err(e) = env.call_method(webview, "clearCache", "(Z)V", &[true.into()]) {
+        log::error!("failed to clear the webview cache: {e}");
+      }
+    });
+  }
+
+  #[cfg(target_os = "ios")]
+  let _ = webview;
+}
+
+/// Sets how the webview uses its HTTP cache.
+pub fn set_cache_mode(webview: &WebView, mode: CacheMode) {
+  #[cfg(windows)]
+  {
+    use webview2_com::CallDevToolsProtocolMethodCompletedHandler;
+    use windows::core::{w, HSTRING};
+    use wry::WebViewExtWindows;
+
+    let parameters = HSTRING::from(format!(
+      r#"{{"cacheDisabled":{}}}"#,
+      mode == CacheMode::Disabled
+    ));
+    let result = unsafe {
+      webview.controller().CoreWebView2().and_then(|webview| {
+        // the cache override only applies while the network domain is enabled
+        webview.CallDevToolsProtocolMethod(
+          w!("Network.enable"),
+          w!("{}"),
+          &CallDevToolsProtocolMethodCompletedHandler::create(Box::new(|_, _| Ok(()))),
+        )?;
+        webview.CallDevToolsProtocolMethod(
+          w!("Network.setCacheDisabled"),
+          &parameters,
+          &CallDevToolsProtocolMethodCompletedHandler::create(Box::new(|_, _| Ok(()))),
+        )
+      })
+    };
+    if let Err(e) = result {
+      log::error!("failed to set the webview cache mode: {e}");
+    }
+  }
+
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  {
+    use webkit2gtk::{CacheModel, WebContextExt, WebViewExt};
+    use wry::WebViewExtUnix;
+
+    if let Some(context) = webview.webview().context() {
+      context.set_cache_model(match mode {
+        CacheMode::Default => CacheModel::WebBrowser,
+        CacheMode::Disabled => CacheModel::DocumentViewer,
+      });
+    }
+  }
+
+  #[cfg(target_os = "android")]
+  {
+    use wry::WebViewExtAndroid;
+
+    // WebSettings.LOAD_DEFAULT and WebSettings.LOAD_NO_CACHE
+    let mode: i32 = match mode {
+      CacheMode::Default => -1,
+      CacheMode::Disabled => 2,
+    };
+    webview.handle().exec(move |env, _activity, webview| {
+      let result = env
+        .call_method(
+          webview,
+          "getSettings",
+          "()Landroid/webkit/WebSettings;",
+          &[],
+        )
+        .and_then(|settings| settings.l())
+        .and_then(|settings| env.call_method(&settings, "setCacheMode", "(I)V", &[mode.into()]));
+      if let Err(e) = result {
+        log::error!("failed to set the webview cache mode: {e}");
+      }
+    });
+  }
+
+  #[cfg(any(target_os = "macos", target_os = "ios"))]
+  let _ = (webview, mode);
+}
@@ -20,7 +20,7 @@ use raw_window_handle::{DisplayHandle, HasDisplayHandle, HasWindowHandle};
 use tauri_runtime::{
   dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size},
   monitor::Monitor,
-  webview::{DetachedWebview, DownloadEvent, PendingWebview, WebviewIpcHandler},
+  webview::{CacheMode, DetachedWebview, DownloadEvent, PendingWebview, WebviewIpcHandler},
   window::{
     CursorIcon, DetachedWindow, DragDropEvent, PendingWindow, RawWindow, WebviewEvent,
     WindowBuilder, WindowBuilderBase, WindowEvent, WindowId, WindowSizeConstraints,
@@ -118,6 +118,7 @@ use std::{
 pub type WebviewId = u32;
 type IpcHandler = dyn Fn(Request<String>) + 'static;
 
+mod cache;
 #[cfg(any(
   windows,
   target_os = "linux",
@@ -1251,6 +1252,10 @@ pub enum WebviewMessage {
   SetAutoResize(bool),
   SetZoom(f64),
   ClearAllBrowsingData,
+  ClearCache,
+  SetCacheMode(CacheMode),
+  SetAdditionalHeaders(http::HeaderMap),
+  SetHtmlDragDropRegions(Vec<tauri_runtime::Rect>),
   // Getters
   Url(Sender<Result<String>>),
   Bounds(Sender<Result<tauri_runtime::Rect>>),
@@ -1540,6 +1545,50 @@ impl<T: UserEvent> WebviewDispatch<T> for WryWebviewDispatcher<T> {
     )
   }
 
+  fn clear_cache(&self) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::ClearCache,
+      ),
+    )
+  }
+
+  fn set_cache_mode(&self, mode: CacheMode) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::SetCacheMode(mode),
+      ),
+    )
+  }
+
+  fn set_additional_headers(&self, headers: http::HeaderMap) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::SetAdditionalHeaders(headers),
+      ),
+    )
+  }
+
+  fn set_html_drag_drop_regions(&self, regions: Vec<tauri_runtime::Rect>) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::SetHtmlDragDropRegions(regions),
+      ),
+    )
+  }
+
   fn hide(&self) -> Result<()> {
     send_user_message(
       &self.context,
@@ -2085,6 +2134,8 @@ pub struct WebviewWrapper {
   // the key of the WebContext if it's not shared
   context_key: Option<PathBuf>,
   bounds: Arc<Mutex<Option<WebviewBounds>>>,
+  additional_headers: Rc<RefCell<http::HeaderMap>>,
+  html_drag_drop_regions: HtmlDragDropRegions,
 }
 
 impl Deref for WebviewWrapper {
@@ -3145,7 +3196,13 @@ fn handle_user_message<T: UserEvent>(
             }
           }
           WebviewMessage::Navigate(url) => {
-            if let Err(e) = webview.load_url(url.as_str()) {
+            let headers = webview.additional_headers.borrow().clone();
+            let result = if headers.is_empty() {
+              webview.load_url(url.as_str())
+            } else {
+              webview.load_url_with_headers(url.as_str(), headers)
+            };
+            if let Err(e) = result {
               log::error!("failed to navigate to url {}: {}", url, e);
             }
           }
@@ -3237,11 +3294,28 @@ fn handle_user_message<T: UserEvent>(
               log::error!("failed to set webview zoom: {e}");
             }
           }
+          WebviewMessage::SetAdditionalHeaders(headers) => {
+            *webview.additional_headers.borrow_mut() = headers;
+          }
+          WebviewMessage::SetHtmlDragDropRegions(regions) => {
+            let scale_factor = window.scale_factor();
+            *webview.html_drag_drop_regions.borrow_mut() = regions
+              .into_iter()
+              .map(|region| {
+                (
+                  region.position.to_physical(scale_factor),
+                  region.size.to_physical(scale_factor),
+                )
+              })
+              .collect();
+          }
           WebviewMessage::ClearAllBrowsingData => {
             if let Err(e) = webview.clear_all_browsing_data() {
               log::error!("failed to clear webview browsing data: {e}");
             }
           }
+          WebviewMessage::ClearCache => cache::clear_cache(&webview),
+          WebviewMessage::SetCacheMode(mode) => cache::set_cache_mode(&webview, mode),
           // Getters
           WebviewMessage::Url(tx) => {
             tx.send(
@@ -3937,6 +4011,19 @@ enum WebviewKind {
   WindowChild,
 }
 
+/// Physical regions of a webview where the native drag and drop handler is skipped.
+type HtmlDragDropRegions = Rc<RefCell<Vec<(PhysicalPosition<i32>, PhysicalSize<u32>)>>>;
+
+#[cfg(not(windows))]
+fn is_in_html_drag_drop_region(regions: &HtmlDragDropRegions, (x, y): (i32, i32)) -> bool {
+  regions.borrow().iter().any(|(position, size)| {
+    x >= position.x
+      && y >= position.y
+      && x < position.x + size.width as i32
+      && y < position.y + size.height as i32
+  })
+}
+
 #[derive(Debug, Clone)]
 struct WebviewBounds {
   x_rate: f32,
@@ -3945,6 +4032,24 @@ struct WebviewBounds {
   height_rate: f32,
 }
 
+/// The browser arguments wry passes to WebView2 when none are set.
+#[cfg(windows)]
+const WRY_DEFAULT_BROWSER_ARGS: &str =
+  "--disable-features=msWebOOUI,msPdfOOUI,msSmartScreenProtection";
+
+/// Appends browser arguments, skipping the switches that are already set.
+#[cfg(windows)]
+fn append_browser_args(args: &mut String, extra_args: Vec<String>) {
+  let switch = |arg: &str| arg.split('=').next().unwrap_or(arg).to_string();
+  let existing = args.split_whitespace().map(switch).collect::<Vec<_>>();
+  for arg in extra_args {
+    if !existing.contains(&switch(&arg)) {
+      args.push(' ');
+      args.push_str(&arg);
+    }
+  }
+}
+
 fn create_webview<T: UserEvent>(
   kind: WebviewKind,
   window: &Window,
@@ -4011,10 +4116,31 @@ fn create_webview<T: UserEvent>(
     .with_accept_first_mouse(webview_attributes.accept_first_mouse)
     .with_hotkeys_zoom(webview_attributes.zoom_hotkeys_enabled);
 
+  let additional_headers = webview_attributes.additional_headers.unwrap_or_default();
+  if !additional_headers.is_empty() {
+    webview_builder = webview_builder.with_headers(additional_headers.clone());
+  }
+
+  let html_drag_drop_regions = HtmlDragDropRegions::default();
   if webview_attributes.drag_drop_handler_enabled {
     let proxy = context.proxy.clone();
     let window_id_ = window_id.clone();
+    #[cfg(not(windows))]
+    let html_drag_drop_regions = html_drag_drop_regions.clone();
     webview_builder = webview_builder.with_drag_drop_handler(move |event| {
+      // on Windows the webview cannot handle the drops the native handler rejects
+      #[cfg(not(windows))]
+      match &event {
+        WryDragDropEvent::Enter { position, .. }
+        | WryDragDropEvent::Over { position }
+        | WryDragDropEvent::Drop { position, .. }
+          if is_in_html_drag_drop_region(&html_drag_drop_regions, *position) =>
+        {
+          return false;
+        }
+        _ => {}
+      }
+
       let event = match event {
         WryDragDropEvent::Enter {
           paths,
@@ -4142,7 +4268,20 @@ fn create_webview<T: UserEvent>(
 
   #[cfg(windows)]
   {
-    if let Some(additional_browser_args) = webview_attributes.additional_browser_args {
+    let mut additional_browser_args = webview_attributes.additional_browser_args;
+    let mut extra_args = Vec::new();
+    if !webview_attributes.hardware_acceleration {
+      extra_args.push("--disable-gpu".to_string());
+    }
+    if let Some(size) = webview_attributes.disk_cache_size {
+      extra_args.push(format!("--disk-cache-size={size}"));
+    }
+    if !extra_args.is_empty() {
+      // setting the arguments replaces the ones wry passes by default, so they are kept
+      let args = additional_browser_args.get_or_insert_with(|| WRY_DEFAULT_BROWSER_ARGS.into());
+      append_browser_args(args, extra_args);
+    }
+    if let Some(additional_browser_args) = additional_browser_args {
       webview_builder = webview_builder.with_additional_browser_args(&additional_browser_args);
     }
 
@@ -4282,6 +4421,43 @@ fn create_webview<T: UserEvent>(
     }
   }
 
+  #[cfg(windows)]
+  if let Some(enabled) = webview_attributes.autofill {
+    if let Err(e) = set_autofill_enabled(&webview, enabled) {
+      log::warn!("failed to set the webview autofill settings: {e}");
+    }
+  }
+
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  {
+    use webkit2gtk::{HardwareAccelerationPolicy, SettingsExt, WebContextExt, WebViewExt};
+    if !webview_attributes.hardware_acceleration {
+      if let Some(settings) = webview.webview().settings() {
+        settings.set_hardware_acceleration_policy(HardwareAccelerationPolicy::Never);
+      }
+    }
+    // spell checking is disabled by default on WebKitGTK
+    if let Some(context) = webview.webview().context() {
+      if let Some(enabled) = webview_attributes.spellcheck {
+        context.set_spell_checking_enabled(enabled);
+      }
+      if !webview_attributes.spellcheck_languages.is_empty() {
+        let languages = webview_attributes
+          .spellcheck_languages
+          .iter()
+          .map(String::as_str)
+          .collect::<Vec<_>>();
+        context.set_spell_checking_languages(&languages);
+      }
+    }
+  }
+
   #[cfg(windows)]
   if kind == WebviewKind::WindowContent {
     let controller = webview.controller();
@@ -4331,9 +4507,28 @@ fn create_webview<T: UserEvent>(
       web_context_key
     },
     bounds: Arc::new(Mutex::new(webview_bounds)),
+    additional_headers: Rc::new(RefCell::new(additional_headers)),
+    html_drag_drop_regions,
   })
 }
 
+#[cfg(windows)]
+fn set_autofill_enabled(webview: &WebView, enabled: bool) -> windows::core::Result<()> {
+  use webview2_com::Microsoft::Web::WebView2::Win32::ICoreWebView2Settings4;
+  use windows::core::Interface;
+
+  unsafe {
+    let settings = webview
+      .controller()
+      .CoreWebView2()?
+      .Settings()?
+      .cast::<ICoreWebView2Settings4>()?;
+    settings.SetIsGeneralAutofillEnabled(enabled)?;
+    settings.SetIsPasswordAutosaveEnabled(enabled)?;
+  }
+  Ok(())
+}
+
 /// Create a wry ipc handler from a tauri ipc handler.
 fn create_ipc_handler<T: UserEvent>(
   _kind: WebviewKind,
@@ -48,7 +48,7 @@ pub type WindowEventId = u32;
 pub type WebviewEventId = u32;
 
 /// A rectangular region.
-#[derive(Clone, Copy, Debug, Serialize)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct Rect {
   /// Rect position.
   pub position: dpi::Position,
@@ -525,6 +525,18 @@ pub trait WebviewDispatch<T: UserEvent>: Debug + Clone + Send + Sync + Sized + '
 
   /// Clear all browsing data for this webview.
   fn clear_all_browsing_data(&self) -> Result<()>;
+
+  /// Clears the HTTP cache of this webview, keeping the cookies and the storage.
+  fn clear_cache(&self) -> Result<()>;
+
+  /// Sets how this webview uses its HTTP cache.
+  fn set_cache_mode(&self, mode: webview::CacheMode) -> Result<()>;
+
+  /// Sets the headers sent with the next navigations made with [`Self::navigate`].
+  fn set_additional_headers(&self, headers: http::HeaderMap) -> Result<()>;
+
+  /// Sets the regions of the webview where the webview handles drag and drop itself instead of the native handler.
+  fn set_html_drag_drop_regions(&self, regions: Vec<Rect>) -> Result<()>;
 }
 
 /// Window dispatcher. A thread-safe handle to the window APIs.
@@ -61,6 +61,17 @@ pub struct CreationContext<'a, 'b> {
   pub webview: &'a jni::objects::JObject<'b>,
 }
 
+/// How the webview uses its HTTP cache, see [`crate::WebviewDispatch::set_cache_mode`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CacheMode {
+  /// Responses are cached following their HTTP caching headers.
+  #[default]
+  Default,
+  /// Every request is loaded from the network.
+  Disabled,
+}
+
 /// Kind of event for the page load handler.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PageLoadEvent {
@@ -209,6 +220,13 @@ pub struct WebviewAttributes {
   pub proxy_url: Option<Url>,
   pub zoom_hotkeys_enabled: bool,
   pub browser_extensions_enabled: bool,
+  pub spellcheck: Option<bool>,
+  pub spellcheck_languages: Vec<String>,
+  pub autocorrect: Option<bool>,
+  pub autofill: Option<bool>,
+  pub additional_headers: Option<http::HeaderMap>,
+  pub hardware_acceleration: bool,
+  pub disk_cache_size: Option<u64>,
 }
 
 impl From<&WindowConfig> for WebviewAttributes {
@@ -237,6 +255,20 @@ impl From<&WindowConfig> for WebviewAttributes {
     }
     builder = builder.zoom_hotkeys_enabled(config.zoom_hotkeys_enabled);
     builder = builder.browser_extensions_enabled(config.browser_extensions_enabled);
+    if let Some(enabled) = config.spellcheck {
+      builder = builder.spellcheck(enabled);
+    }
+    builder = builder.spellcheck_languages(config.spellcheck_languages.clone());
+    if let Some(enabled) = config.autocorrect {
+      builder = builder.autocorrect(enabled);
+    }
+    if let Some(enabled) = config.autofill {
+      builder = builder.autofill(enabled);
+    }
+    builder = builder.hardware_acceleration(config.hardware_acceleration);
+    if let Some(size) = config.disk_cache_size {
+      builder = builder.disk_cache_size(size);
+    }
     builder
   }
 }
@@ -261,6 +293,13 @@ impl WebviewAttributes {
       proxy_url: None,
       zoom_hotkeys_enabled: false,
       browser_extensions_enabled: false,
+      spellcheck: None,
+      spellcheck_languages: Vec::new(),
+      autocorrect: None,
+      autofill: None,
+      additional_headers: None,
+      hardware_acceleration: true,
+      disk_cache_size: None,
     }
   }
 
@@ -378,6 +417,81 @@ impl WebviewAttributes {
     self.browser_extensions_enabled = enabled;
     self
   }
+
+  /// Whether the text fields are spell checked. Defaults to the webview engine behavior.
+  #[must_use]
+  pub fn spellcheck(mut self, enabled: bool) -> Self {
+    self.spellcheck = Some(enabled);
+    self
+  }
+
+  /// The languages used to spell check, such as `en_US`.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux**: Set on the web context, so it applies to all webviews sharing the same data directory.
+  /// - **Windows / macOS / iOS / Android** - Unsupported, the system languages are used.
+  #[must_use]
+  pub fn spellcheck_languages(mut self, languages: Vec<String>) -> Self {
+    self.spellcheck_languages = languages;
+    self
+  }
+
+  /// Whether the text fields are automatically corrected while typing. Defaults to the webview engine behavior.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows / Linux / Android** - Unsupported, the engines do not correct text.
+  #[must_use]
+  pub fn autocorrect(mut self, enabled: bool) -> Self {
+    self.autocorrect = Some(enabled);
+    self
+  }
+
+  /// Whether the webview suggests previously entered values and saved passwords in forms.
+  /// Defaults to the webview engine behavior.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows**: Also controls WebView2's `IsGeneralAutofillEnabled` and `IsPasswordAutosaveEnabled` settings.
+  #[must_use]
+  pub fn autofill(mut self, enabled: bool) -> Self {
+    self.autofill = Some(enabled);
+    self
+  }
+
+  /// Headers sent with the initial page request and the navigations made with [`crate::WebviewDispatch::navigate`].
+  #[must_use]
+  pub fn additional_headers(mut self, headers: http::HeaderMap) -> Self {
+    self.additional_headers = Some(headers);
+    self
+  }
+
+  /// Whether the webview renders with the GPU. Defaults to `true`.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows**: Passes `--disable-gpu` to the browser process, which is shared by the webviews with the same data directory,
+  /// so they must all use the same value.
+  /// - **macOS / iOS / Android**: Unsupported.
+  #[must_use]
+  pub fn hardware_acceleration(mut self, enabled: bool) -> Self {
+    self.hardware_acceleration = enabled;
+    self
+  }
+
+  /// The maximum size in bytes of the HTTP disk cache.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows**: Passes `--disk-cache-size` to the browser process, which is shared by the webviews with the same data directory,
+  /// so they must all use the same value.
+  /// - **macOS / Linux / iOS / Android**: Unsupported.
+  #[must_use]
+  pub fn disk_cache_size(mut self, size: u64) -> Self {
+    self.disk_cache_size = Some(size);
+    self
+  }
 }
 
 /// IPC handler.
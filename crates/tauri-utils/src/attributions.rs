@@ -0,0 +1,46 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Third-party license attributions generated at build time.
+
+use serde::{Deserialize, Serialize};
+
+/// The package registry of an [`Attribution`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Ecosystem {
+  /// A Rust crate.
+  Cargo,
+  /// A frontend package.
+  Npm,
+}
+
+/// A third-party package the app depends on, with its license.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attribution {
+  /// The package name.
+  pub name: String,
+  /// The package version.
+  pub version: String,
+  /// The package registry.
+  pub ecosystem: Ecosystem,
+  /// The SPDX license expression, e.g. `MIT OR Apache-2.0`.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub license: Option<String>,
+  /// The license text, for the packages without an SPDX license expression.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub license_text: Option<String>,
+  /// The package authors.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub authors: Vec<String>,
+  /// The source repository URL.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub repository: Option<String>,
+}
+
+/// Parses the attributions JSON generated by `tauri-build`.
+pub fn parse(json: &str) -> serde_json::Result<Vec<Attribution>> {
+  serde_json::from_str(json)
+}
@@ -1278,6 +1278,17 @@ pub struct WindowConfig {
   /// The window webview URL.
   #[serde(default)]
   pub url: WebviewUrl,
+  /// Path to a directory of frontend assets loaded by this window instead of `build.frontendDist`,
+  /// relative to the `tauri.conf.json` file.
+  ///
+  /// The directory is embedded separately from the main frontend, so a small frontend such as a tray popup
+  /// does not need to be bundled with the main app frontend.
+  /// It is served to all webviews of this window from the embedded assets, also in development,
+  /// including child webviews added with a different label.
+  /// In production it shares the origin of the main frontend, but in development the other windows load
+  /// `build.devUrl` so this window is on a different origin.
+  #[serde(alias = "frontend-dist")]
+  pub frontend_dist: Option<PathBuf>,
   /// The user agent for the webview
   #[serde(alias = "user-agent")]
   pub user_agent: Option<String>,
@@ -1472,6 +1483,79 @@ pub struct WindowConfig {
   /// - **MacOS / Linux / iOS / Android** - Unsupported.
   #[serde(default)]
   pub browser_extensions_enabled: bool,
+  /// Whether the text fields are spell checked. Defaults to the webview engine behavior.
+  ///
+  /// It is set as a default on the text fields of the pages on the origin of the window URL,
+  /// so attributes set by the page take precedence, like `autocorrect` and `autofill`.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux**: Also enables spell checking on the WebKitGTK web context, which is shared by all webviews with the same data directory.
+  pub spellcheck: Option<bool>,
+  /// The languages used to spell check, such as `en_US`.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux**: Set on the web context, so it applies to all webviews sharing the same data directory.
+  /// - **Windows / macOS / iOS / Android** - Unsupported, the system languages are used.
+  #[serde(default, alias = "spellcheck-languages")]
+  pub spellcheck_languages: Vec<String>,
+  /// Whether the text fields are automatically corrected while typing. Defaults to the webview engine behavior.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows / Linux / Android** - Unsupported, the engines do not correct text.
+  pub autocorrect: Option<bool>,
+  /// Whether the webview suggests previously entered values and saved passwords in forms.
+  /// Defaults to the webview engine behavior.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows**: Also controls WebView2's `IsGeneralAutofillEnabled` and `IsPasswordAutosaveEnabled` settings.
+  pub autofill: Option<bool>,
+  /// Whether the webview renders with the GPU. Disable it to work around graphics driver bugs,
+  /// such as blank windows, at the cost of rendering performance.
+  ///
+  /// See also `app > softwareRenderingFallback`.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows**: Passes `--disable-gpu` to the browser process, which is shared by the webviews with the same data directory,
+  /// so they must all use the same value.
+  /// - **macOS / iOS / Android**: Unsupported.
+  #[serde(default = "default_true", alias = "hardware-acceleration")]
+  pub hardware_acceleration: bool,
+  /// The maximum size in bytes of the HTTP disk cache of the webview.
+  ///
+  /// Use `Webview::clear_cache` and `Webview::set_cache_mode` to control the cache at runtime.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows**: Passes `--disk-cache-size` to the browser process, which is shared by the webviews with the same data directory,
+  /// so they must all use the same value.
+  /// - **macOS / Linux / iOS / Android**: Unsupported.
+  #[serde(alias = "disk-cache-size")]
+  pub disk_cache_size: Option<u64>,
+  /// The animation played when the window is shown with `Window::show_animated`,
+  /// and when it is created if it is visible.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux**: The slide effects fade the window.
+  /// - **iOS / Android**: Unsupported.
+  #[serde(alias = "show-animation")]
+  pub show_animation: Option<WindowAnimation>,
+  /// The animation played when the window is hidden with `Window::hide_animated`.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux**: The slide effects fade the window.
+  /// - **iOS / Android**: Unsupported.
+  #[serde(alias = "hide-animation")]
+  pub hide_animation: Option<WindowAnimation>,
+  /// The type of the window, see [`WindowType`].
+  #[serde(default, alias = "window-type")]
+  pub window_type: WindowType,
 }
 
 impl Default for WindowConfig {
@@ -1479,6 +1563,7 @@ impl Default for WindowConfig {
     Self {
       label: default_window_label(),
       url: WebviewUrl::default(),
+      frontend_dist: None,
       create: true,
       user_agent: None,
       drag_drop_enabled: true,
@@ -1520,10 +1605,83 @@ impl Default for WindowConfig {
       proxy_url: None,
       zoom_hotkeys_enabled: false,
       browser_extensions_enabled: false,
+      spellcheck: None,
+      spellcheck_languages: Vec::new(),
+      autocorrect: None,
+      autofill: None,
+      hardware_acceleration: true,
+      disk_cache_size: None,
+      show_animation: None,
+      hide_animation: None,
+      window_type: WindowType::Normal,
+    }
+  }
+}
+
+/// The type of a window.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum WindowType {
+  /// A regular application window.
+  #[default]
+  Normal,
+  /// A floating panel such as the popover of a tray icon: it has no taskbar entry,
+  /// stays on top of the other windows and is hidden when it loses focus.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **macOS**: Follows the active space, including full screen spaces, and does not activate the application when shown.
+  /// - **Windows**: Uses a tool window, which is also hidden from the Alt+Tab switcher.
+  /// - **iOS / Android**: Unsupported.
+  Panel,
+}
+
+/// The visual effect of a [`WindowAnimation`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum WindowAnimationEffect {
+  /// Fades the window in or out.
+  #[default]
+  Fade,
+  /// Slides the window upwards.
+  SlideUp,
+  /// Slides the window downwards.
+  SlideDown,
+  /// Slides the window to the left.
+  SlideLeft,
+  /// Slides the window to the right.
+  SlideRight,
+}
+
+/// An animation played when a window is shown or hidden.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct WindowAnimation {
+  /// The visual effect of the animation.
+  #[serde(default)]
+  pub effect: WindowAnimationEffect,
+  /// The duration of the animation in milliseconds.
+  #[serde(default = "default_animation_duration")]
+  pub duration: u64,
+}
+
+impl Default for WindowAnimation {
+  fn default() -> Self {
+    Self {
+      effect: Default::default(),
+      duration: default_animation_duration(),
     }
   }
 }
 
+fn default_animation_duration() -> u64 {
+  200
+}
+
 fn default_window_label() -> String {
   "main".to_string()
 }
@@ -1870,6 +2028,11 @@ pub struct AppConfig {
   /// Configuration for app tray icon.
   #[serde(alias = "tray-icon")]
   pub tray_icon: Option<TrayIconConfig>,
+  /// The menu of the app, created at startup unless a menu is set with `Builder::menu`.
+  ///
+  /// On Windows and Linux the menu is shown on every window, on macOS it replaces the default app menu.
+  /// The items remain mutable at runtime through `AppHandle::menu`.
+  pub menu: Option<Vec<MenuItemConfig>>,
   /// MacOS private API configuration. Enables the transparent background API and sets the `fullScreenEnabled` preference to `true`.
   #[serde(rename = "macOSPrivateApi", alias = "macos-private-api", default)]
   pub macos_private_api: bool,
@@ -1879,6 +2042,39 @@ pub struct AppConfig {
   /// If set to true "identifier" will be set as GTK app ID (on systems that use GTK).
   #[serde(rename = "enableGTKAppId", alias = "enable-gtk-app-id", default)]
   pub enable_gtk_app_id: bool,
+  /// Configuration for the splashscreen window displayed while the main window loads.
+  pub splashscreen: Option<SplashscreenConfig>,
+  /// Kiosk mode configuration, for digital signage and single-purpose devices.
+  pub kiosk: Option<KioskConfig>,
+  /// The command line interface of the app, see `AppHandle::cli_matches`.
+  pub cli: Option<CliConfig>,
+  /// Whether the webviews render without the GPU after a graphics failure was detected,
+  /// such as a blocklisted driver or a GPU that failed to initialize.
+  ///
+  /// The failure is persisted in the app cache directory and the fallback applies from the next launch,
+  /// see `AppHandle::software_rendering`. The webviews are only probed for failures when it is enabled.
+  #[serde(default, alias = "software-rendering-fallback")]
+  pub software_rendering_fallback: bool,
+  /// The resource limits of the sidecars, keyed by the sidecar file name without the target triple,
+  /// such as `my-sidecar` for the `binaries/my-sidecar` entry of `bundle > externalBin`.
+  ///
+  /// The `*` key applies to the sidecars without an entry.
+  /// The limits are applied when the sidecar is spawned with `tauri::process::spawn_sidecar`.
+  #[serde(default, alias = "sidecar-limits")]
+  pub sidecar_limits: HashMap<String, ProcessLimits>,
+  /// Signed frontend patches replacing the embedded frontend assets without updating the app.
+  ///
+  /// Patches are disabled unless set, and require the `frontend-patches` Cargo feature.
+  #[serde(alias = "frontend-patches")]
+  pub frontend_patches: Option<FrontendPatchesConfig>,
+  /// The size limits of the IPC payloads exchanged with the webviews.
+  #[serde(default)]
+  pub ipc: IpcConfig,
+  /// The initial runtime feature flags of the app, shared between Rust and the frontend.
+  ///
+  /// See `AppHandle::features` for more information.
+  #[serde(default, alias = "feature-flags")]
+  pub feature_flags: HashMap<String, serde_json::Value>,
 }
 
 impl AppConfig {
@@ -1912,6 +2108,48 @@ impl AppConfig {
     features.sort_unstable();
     features
   }
+
+  /// Returns the Cargo features required by the configuration that are not managed by the CLI,
+  /// since the APIs they enable can also be used from code.
+  ///
+  /// The build fails if one of them is not enabled, but they are never removed.
+  pub fn required_features(&self) -> Vec<&str> {
+    let mut features = Vec::new();
+    if self.kiosk.as_ref().is_some_and(|k| k.suppress_shortcuts) {
+      features.push("kiosk");
+    }
+    if self
+      .windows
+      .iter()
+      .any(|w| w.window_type == WindowType::Panel)
+    {
+      features.push("panel-windows");
+    }
+    if self
+      .windows
+      .iter()
+      .any(|w| w.show_animation.is_some() || w.hide_animation.is_some())
+    {
+      features.push("window-animations");
+    }
+    if self
+      .tray_icon
+      .as_ref()
+      .is_some_and(|t| t.light_icon_path.is_some() || t.dark_icon_path.is_some())
+    {
+      features.push("theme-icons");
+    }
+    if self
+      .sidecar_limits
+      .values()
+      .any(|l| l.max_memory.is_some() || l.max_cpu_time.is_some() || l.cpu_rate.is_some())
+    {
+      features.push("process-limits");
+    }
+
+    features.sort_unstable();
+    features
+  }
 }
 
 /// Configuration for application tray icon.
@@ -1931,6 +2169,17 @@ pub struct TrayIconConfig {
   /// or else it's going to bloat your final executable
   #[serde(alias = "icon-path")]
   pub icon_path: PathBuf,
+  /// Path to the icon displayed when the system uses a light theme, usually a dark glyph.
+  ///
+  /// `lightIconPath` and `darkIconPath` must be set together, they are used instead of `iconPath`
+  /// and the tray icon switches between them when the system theme changes.
+  #[serde(alias = "light-icon-path")]
+  pub light_icon_path: Option<PathBuf>,
+  /// Path to the icon displayed when the system uses a dark theme, usually a light glyph.
+  ///
+  /// See `lightIconPath`.
+  #[serde(alias = "dark-icon-path")]
+  pub dark_icon_path: Option<PathBuf>,
   /// A Boolean value that determines whether the image represents a [template](https://developer.apple.com/documentation/appkit/nsimage/1520017-template?language=objc) image on macOS.
   #[serde(default, alias = "icon-as-template")]
   pub icon_as_template: bool,
@@ -1941,6 +2190,332 @@ pub struct TrayIconConfig {
   pub title: Option<String>,
   /// Tray icon tooltip on Windows and macOS
   pub tooltip: Option<String>,
+  /// The menu of the tray icon.
+  pub menu: Option<Vec<MenuItemConfig>>,
+}
+
+/// An item of a menu defined in the configuration.
+///
+/// The kind of item depends on the fields that are set:
+/// `predefined` creates a predefined item, `items` a submenu, `checked` a check item, and otherwise a regular item.
+///
+/// ```json
+/// [
+///   {
+///     "id": "file",
+///     "textKey": "menu.file",
+///     "items": [
+///       { "id": "open", "textKey": "menu.open", "accelerator": "CmdOrCtrl+O" },
+///       { "id": "autosave", "text": "Autosave", "checked": true },
+///       { "predefined": "separator" },
+///       { "predefined": "quit" }
+///     ]
+///   }
+/// ]
+/// ```
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MenuItemConfig {
+  /// The id of the item, used to handle its menu events and to retrieve it at runtime.
+  ///
+  /// A unique id is generated when not set.
+  pub id: Option<String>,
+  /// The text of the item.
+  ///
+  /// An `&` before a character assigns it as the mnemonic of the item. Use `&&` to display a `&`.
+  pub text: Option<String>,
+  /// The translation key of the text of the item, resolved with `Builder::menu_translator`.
+  ///
+  /// Falls back to `text`, then to the key itself when the translator does not know the key.
+  #[serde(alias = "text-key")]
+  pub text_key: Option<String>,
+  /// The keyboard shortcut of the item, such as `CmdOrCtrl+O`.
+  pub accelerator: Option<String>,
+  /// Whether the item is enabled.
+  #[serde(default = "default_true")]
+  pub enabled: bool,
+  /// Makes the item a check item with the given initial state.
+  pub checked: Option<bool>,
+  /// Makes the item a submenu with the given items.
+  pub items: Option<Vec<MenuItemConfig>>,
+  /// Makes the item a predefined item, whose action is implemented by the operating system.
+  pub predefined: Option<PredefinedMenuItemConfig>,
+}
+
+/// A predefined menu item, see [`MenuItemConfig::predefined`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum PredefinedMenuItemConfig {
+  /// A separator.
+  Separator,
+  /// Copies the selection.
+  Copy,
+  /// Cuts the selection.
+  Cut,
+  /// Pastes the clipboard.
+  Paste,
+  /// Selects all.
+  SelectAll,
+  /// Undoes the last edit.
+  Undo,
+  /// Redoes the last undone edit.
+  Redo,
+  /// Minimizes the window.
+  Minimize,
+  /// Maximizes the window.
+  Maximize,
+  /// Toggles the fullscreen mode of the window.
+  Fullscreen,
+  /// Hides the app.
+  Hide,
+  /// Hides the other apps.
+  HideOthers,
+  /// Shows all the apps.
+  ShowAll,
+  /// Closes the window.
+  CloseWindow,
+  /// Quits the app.
+  Quit,
+  /// Shows the about dialog of the app.
+  About,
+  /// The services submenu of macOS.
+  Services,
+}
+
+/// Configuration for the splashscreen window displayed while the main window loads.
+///
+/// The splashscreen window is created before the other windows of `app > windows`.
+/// It is closed and the main window is shown when the main window finished loading its page,
+/// or when `AppHandle::splash_done` or the `splashDone` JavaScript API is called.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SplashscreenConfig {
+  /// The label of the splashscreen window, which must be configured in `app > windows`.
+  pub window: String,
+  /// The label of the main window. It should be configured with `visible: false` and is shown when the splashscreen closes.
+  ///
+  /// When not set, the splashscreen is only closed by an explicit `splash_done` call.
+  pub main: Option<String>,
+  /// Whether the splashscreen closes when the main window finished loading its page.
+  /// Set to `false` to close it with an explicit `splash_done` call once the app is ready.
+  #[serde(default = "default_true", alias = "auto-close")]
+  pub auto_close: bool,
+  /// The minimum time in milliseconds the splashscreen is displayed for, to avoid a flash on fast startups.
+  #[serde(default, alias = "min-duration")]
+  pub min_duration: u64,
+  /// The duration in milliseconds of the fade out animation of the splashscreen content before it closes.
+  ///
+  /// The page of the splashscreen fades out rather than the native window,
+  /// so the splashscreen window must be configured with `transparent: true`, otherwise it closes without the fade.
+  #[serde(default, alias = "fade-duration")]
+  pub fade_duration: u64,
+}
+
+/// Kiosk mode configuration, for digital signage and single-purpose devices.
+///
+/// Kiosk mode is only supported on desktop platforms.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct KioskConfig {
+  /// Keeps every window fullscreen, restoring it when it is minimized or leaves fullscreen.
+  #[serde(default = "default_true")]
+  pub fullscreen: bool,
+  /// Suppresses the OS shortcuts used to leave the app where the platform permits it.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Suppresses the Windows key, Alt+Tab, Alt+Esc, Ctrl+Esc and Alt+F4 while the app is in the foreground.
+  ///   Ctrl+Alt+Del cannot be suppressed. Requires the `kiosk` Cargo feature of `tauri`, the build fails without it.
+  /// - **macOS:** Disables process switching, force quit, session termination and hiding the app, and auto hides the Dock and menu bar.
+  /// - **Linux:** Unsupported, shortcuts must be disabled in the compositor configuration.
+  #[serde(default = "default_true", alias = "suppress-shortcuts")]
+  pub suppress_shortcuts: bool,
+  /// The time in milliseconds after which a webview that stopped sending heartbeats is considered unresponsive,
+  /// for instance when its content process crashed or its page is stuck in a loop. `0` disables the watchdog.
+  ///
+  /// A webview is only watched once it sent its first heartbeat, which requires access to the core webview commands.
+  #[serde(default = "default_watchdog_timeout", alias = "watchdog-timeout")]
+  pub watchdog_timeout: u64,
+  /// Whether unresponsive webviews are reloaded.
+  #[serde(default = "default_true", alias = "relaunch-on-crash")]
+  pub relaunch_on_crash: bool,
+}
+
+fn default_watchdog_timeout() -> u64 {
+  10_000
+}
+
+/// The command line interface of the app, parsed with `AppHandle::cli_matches`.
+///
+/// Arguments forwarded by secondary instances are parsed with the same definition,
+/// see `tauri::cli::forward_args`.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CliConfig {
+  /// The description of the command, shown in the help message.
+  pub description: Option<String>,
+  /// The arguments of the command.
+  #[serde(default)]
+  pub args: Vec<CliArg>,
+  /// The subcommands, by name. A subcommand is matched by the first positional argument.
+  #[serde(default)]
+  pub subcommands: HashMap<String, CliConfig>,
+}
+
+/// An argument of the [`CliConfig`].
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CliArg {
+  /// The name of the argument, used as its key in the matches and as its long flag, e.g. `--name`.
+  pub name: String,
+  /// The short flag of the argument, e.g. `-n`.
+  pub short: Option<char>,
+  /// The description of the argument, shown in the help message.
+  pub description: Option<String>,
+  /// The position of the argument, starting at 1. Positional arguments take a value and have no flag.
+  pub index: Option<usize>,
+  /// Whether the flag is followed by a value, e.g. `--name value` or `--name=value`.
+  /// Flags without a value are matched as `true` and count their occurrences.
+  #[serde(default, alias = "takes-value")]
+  pub takes_value: bool,
+  /// The type the values are parsed as.
+  #[serde(default, rename = "type")]
+  pub value_type: CliValueType,
+  /// Whether the argument can be set multiple times, its values are then matched as an array.
+  /// A positional argument with `multiple` takes all the remaining positional values.
+  #[serde(default)]
+  pub multiple: bool,
+  /// The values accepted for the argument.
+  #[serde(alias = "possible-values")]
+  pub possible_values: Option<Vec<String>>,
+  /// Whether parsing fails when the argument is not set.
+  #[serde(default)]
+  pub required: bool,
+}
+
+/// The type of the values of a [`CliArg`].
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum CliValueType {
+  /// The value is kept as a string.
+  #[default]
+  String,
+  /// The value must be a signed integer.
+  Integer,
+  /// The value must be a number.
+  Number,
+  /// The value must be `true` or `false`.
+  Boolean,
+}
+
+/// Resource limits applied to a sidecar process, so a runaway helper cannot exhaust the machine resources.
+///
+/// See `tauri::process::spawn_limited`.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ProcessLimits {
+  /// The maximum memory of the process in bytes.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Limits the committed memory with a job object. Requires the `process-limits` Cargo feature of `tauri`.
+  /// - **Linux:** Limits the virtual address space.
+  /// - **macOS / BSD:** Unsupported.
+  #[serde(alias = "max-memory")]
+  pub max_memory: Option<u64>,
+  /// The maximum CPU time of the process in seconds. The process is terminated when it is exceeded.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Requires the `process-limits` Cargo feature of `tauri`.
+  #[serde(alias = "max-cpu-time")]
+  pub max_cpu_time: Option<u64>,
+  /// The maximum CPU usage of the process, as a percentage of the total CPU time of the machine, from 1 to 100.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Requires the `process-limits` Cargo feature of `tauri`.
+  /// - **Linux / macOS / BSD:** Unsupported.
+  #[serde(alias = "cpu-rate")]
+  pub cpu_rate: Option<u8>,
+  /// The scheduling priority of the process, from -20 (most favorable) to 19 (least favorable).
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Mapped to the closest priority class.
+  /// - **Linux / macOS / BSD:** Negative values require elevated privileges.
+  pub nice: Option<i8>,
+}
+
+/// The configuration of the signed frontend patches, see `tauri::frontend_patches`.
+///
+/// Frontend patches are only supported on desktop platforms.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FrontendPatchesConfig {
+  /// The public key verifying the signature of the patches, in the format used by the updater:
+  /// the content of the minisign public key file generated by `tauri signer generate`.
+  pub pubkey: String,
+}
+
+/// The size limits of the IPC payloads exchanged with the webviews.
+///
+/// Payloads exceeding a limit are rejected with an error naming the limit,
+/// instead of failing differently on each platform.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct IpcConfig {
+  /// The maximum size in bytes of the arguments of a command invoked by the frontend.
+  /// The command is rejected when it is exceeded. Unlimited by default.
+  #[serde(alias = "max-request-size")]
+  pub max_request_size: Option<u64>,
+  /// The maximum size in bytes of the response of a command.
+  /// The frontend receives an error instead of the response when it is exceeded. Unlimited by default.
+  #[serde(alias = "max-response-size")]
+  pub max_response_size: Option<u64>,
+  /// The maximum size in bytes of the payload of an event emitted to the frontend.
+  /// Emitting the event fails when it is exceeded. Unlimited by default.
+  #[serde(alias = "max-event-size")]
+  pub max_event_size: Option<u64>,
+  /// The size in bytes above which the payloads delivered to the webviews by evaluating a script,
+  /// such as the events and the command responses on macOS and iOS, are sent in chunks reassembled by the webview.
+  ///
+  /// Defaults to 1 MiB. Set to `0` to always send the payloads in a single script.
+  #[serde(default = "default_eval_chunk_size", alias = "eval-chunk-size")]
+  pub eval_chunk_size: u64,
+}
+
+fn default_eval_chunk_size() -> u64 {
+  1024 * 1024
+}
+
+impl Default for IpcConfig {
+  fn default() -> Self {
+    Self {
+      max_request_size: None,
+      max_response_size: None,
+      max_event_size: None,
+      eval_chunk_size: default_eval_chunk_size(),
+    }
+  }
 }
 
 /// General configuration for the iOS target.
@@ -2446,11 +3021,51 @@ mod build {
     }
   }
 
+  impl ToTokens for WindowAnimationEffect {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::utils::config::WindowAnimationEffect };
+
+      tokens.append_all(match self {
+        Self::Fade => quote! { #prefix::Fade },
+        Self::SlideUp => quote! { #prefix::SlideUp },
+        Self::SlideDown => quote! { #prefix::SlideDown },
+        Self::SlideLeft => quote! { #prefix::SlideLeft },
+        Self::SlideRight => quote! { #prefix::SlideRight },
+      })
+    }
+  }
+
+  impl ToTokens for WindowType {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::utils::config::WindowType };
+
+      tokens.append_all(match self {
+        Self::Normal => quote! { #prefix::Normal },
+        Self::Panel => quote! { #prefix::Panel },
+      })
+    }
+  }
+
+  impl ToTokens for WindowAnimation {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let effect = self.effect;
+      let duration = self.duration;
+
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::WindowAnimation,
+        effect,
+        duration
+      );
+    }
+  }
+
   impl ToTokens for WindowConfig {
     fn to_tokens(&self, tokens: &mut TokenStream) {
       let label = str_lit(&self.label);
       let create = &self.create;
       let url = &self.url;
+      let frontend_dist = opt_lit(self.frontend_dist.as_ref().map(path_buf_lit).as_ref());
       let user_agent = opt_str_lit(self.user_agent.as_ref());
       let drag_drop_enabled = self.drag_drop_enabled;
       let center = self.center;
@@ -2491,12 +3106,22 @@ mod build {
       let parent = opt_str_lit(self.parent.as_ref());
       let zoom_hotkeys_enabled = self.zoom_hotkeys_enabled;
       let browser_extensions_enabled = self.browser_extensions_enabled;
+      let spellcheck = opt_lit(self.spellcheck.as_ref());
+      let spellcheck_languages = vec_lit(&self.spellcheck_languages, str_lit);
+      let autocorrect = opt_lit(self.autocorrect.as_ref());
+      let autofill = opt_lit(self.autofill.as_ref());
+      let hardware_acceleration = self.hardware_acceleration;
+      let disk_cache_size = opt_lit(self.disk_cache_size.as_ref());
+      let show_animation = opt_lit(self.show_animation.as_ref());
+      let hide_animation = opt_lit(self.hide_animation.as_ref());
+      let window_type = self.window_type;
 
       literal_struct!(
         tokens,
         ::tauri::utils::config::WindowConfig,
         label,
         url,
+        frontend_dist,
         create,
         user_agent,
         drag_drop_enabled,
@@ -2537,7 +3162,16 @@ mod build {
         incognito,
         parent,
         zoom_hotkeys_enabled,
-        browser_extensions_enabled
+        browser_extensions_enabled,
+        spellcheck,
+        spellcheck_languages,
+        autocorrect,
+        autofill,
+        hardware_acceleration,
+        disk_cache_size,
+        show_animation,
+        hide_animation,
+        window_type
       );
     }
   }
@@ -2592,6 +3226,47 @@ mod build {
     }
   }
 
+  impl ToTokens for BundleTypeRole {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::utils::config::BundleTypeRole };
+
+      tokens.append_all(match self {
+        Self::Editor => quote! { #prefix::Editor },
+        Self::Viewer => quote! { #prefix::Viewer },
+        Self::Shell => quote! { #prefix::Shell },
+        Self::QLGenerator => quote! { #prefix::QLGenerator },
+        Self::None => quote! { #prefix::None },
+      })
+    }
+  }
+
+  impl ToTokens for AssociationExt {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let ext = str_lit(&self.0);
+      tokens.append_all(quote! { ::tauri::utils::config::AssociationExt(#ext) })
+    }
+  }
+
+  impl ToTokens for FileAssociation {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let ext = vec_lit(&self.ext, identity);
+      let name = opt_str_lit(self.name.as_ref());
+      let description = opt_str_lit(self.description.as_ref());
+      let role = &self.role;
+      let mime_type = opt_str_lit(self.mime_type.as_ref());
+
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::FileAssociation,
+        ext,
+        name,
+        description,
+        role,
+        mime_type
+      );
+    }
+  }
+
   impl ToTokens for BundleConfig {
     fn to_tokens(&self, tokens: &mut TokenStream) {
       let publisher = quote!(None);
@@ -2603,7 +3278,7 @@ mod build {
       let resources = quote!(None);
       let copyright = quote!(None);
       let category = quote!(None);
-      let file_associations = quote!(None);
+      let file_associations = opt_vec_lit(self.file_associations.as_ref(), identity);
       let short_description = quote!(None);
       let long_description = quote!(None);
       let use_local_tools_dir = self.use_local_tools_dir;
@@ -2790,21 +3465,79 @@ mod build {
       let icon_as_template = self.icon_as_template;
       let menu_on_left_click = self.menu_on_left_click;
       let icon_path = path_buf_lit(&self.icon_path);
+      let light_icon_path = opt_lit(self.light_icon_path.as_ref().map(path_buf_lit).as_ref());
+      let dark_icon_path = opt_lit(self.dark_icon_path.as_ref().map(path_buf_lit).as_ref());
       let title = opt_str_lit(self.title.as_ref());
       let tooltip = opt_str_lit(self.tooltip.as_ref());
+      let menu = opt_vec_lit(self.menu.as_ref(), identity);
       literal_struct!(
         tokens,
         ::tauri::utils::config::TrayIconConfig,
         id,
         icon_path,
+        light_icon_path,
+        dark_icon_path,
         icon_as_template,
         menu_on_left_click,
         title,
-        tooltip
+        tooltip,
+        menu
+      );
+    }
+  }
+
+  impl ToTokens for MenuItemConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let id = opt_str_lit(self.id.as_ref());
+      let text = opt_str_lit(self.text.as_ref());
+      let text_key = opt_str_lit(self.text_key.as_ref());
+      let accelerator = opt_str_lit(self.accelerator.as_ref());
+      let enabled = self.enabled;
+      let checked = opt_lit(self.checked.as_ref());
+      let items = opt_vec_lit(self.items.as_ref(), identity);
+      let predefined = opt_lit(self.predefined.as_ref());
+
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::MenuItemConfig,
+        id,
+        text,
+        text_key,
+        accelerator,
+        enabled,
+        checked,
+        items,
+        predefined
       );
     }
   }
 
+  impl ToTokens for PredefinedMenuItemConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::utils::config::PredefinedMenuItemConfig };
+
+      tokens.append_all(match self {
+        Self::Separator => quote! { #prefix::Separator },
+        Self::Copy => quote! { #prefix::Copy },
+        Self::Cut => quote! { #prefix::Cut },
+        Self::Paste => quote! { #prefix::Paste },
+        Self::SelectAll => quote! { #prefix::SelectAll },
+        Self::Undo => quote! { #prefix::Undo },
+        Self::Redo => quote! { #prefix::Redo },
+        Self::Minimize => quote! { #prefix::Minimize },
+        Self::Maximize => quote! { #prefix::Maximize },
+        Self::Fullscreen => quote! { #prefix::Fullscreen },
+        Self::Hide => quote! { #prefix::Hide },
+        Self::HideOthers => quote! { #prefix::HideOthers },
+        Self::ShowAll => quote! { #prefix::ShowAll },
+        Self::CloseWindow => quote! { #prefix::CloseWindow },
+        Self::Quit => quote! { #prefix::Quit },
+        Self::About => quote! { #prefix::About },
+        Self::Services => quote! { #prefix::Services },
+      })
+    }
+  }
+
   impl ToTokens for FsScope {
     fn to_tokens(&self, tokens: &mut TokenStream) {
       let prefix = quote! { ::tauri::utils::config::FsScope };
@@ -2831,14 +3564,181 @@ mod build {
     }
   }
 
+  impl ToTokens for SplashscreenConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let window = str_lit(&self.window);
+      let main = opt_str_lit(self.main.as_ref());
+      let auto_close = self.auto_close;
+      let min_duration = self.min_duration;
+      let fade_duration = self.fade_duration;
+
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::SplashscreenConfig,
+        window,
+        main,
+        auto_close,
+        min_duration,
+        fade_duration
+      );
+    }
+  }
+
+  impl ToTokens for KioskConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let fullscreen = self.fullscreen;
+      let suppress_shortcuts = self.suppress_shortcuts;
+      let watchdog_timeout = self.watchdog_timeout;
+      let relaunch_on_crash = self.relaunch_on_crash;
+
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::KioskConfig,
+        fullscreen,
+        suppress_shortcuts,
+        watchdog_timeout,
+        relaunch_on_crash
+      );
+    }
+  }
+
+  impl ToTokens for CliConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let description = opt_str_lit(self.description.as_ref());
+      let args = vec_lit(&self.args, identity);
+      let subcommands = map_lit(
+        quote! { ::std::collections::HashMap },
+        &self.subcommands,
+        str_lit,
+        identity,
+      );
+
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::CliConfig,
+        description,
+        args,
+        subcommands
+      );
+    }
+  }
+
+  impl ToTokens for CliArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let name = str_lit(&self.name);
+      let short = opt_lit(self.short.as_ref());
+      let description = opt_str_lit(self.description.as_ref());
+      let index = opt_lit(self.index.as_ref());
+      let takes_value = self.takes_value;
+      let value_type = &self.value_type;
+      let multiple = self.multiple;
+      let possible_values = opt_vec_lit(self.possible_values.as_ref(), str_lit);
+      let required = self.required;
+
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::CliArg,
+        name,
+        short,
+        description,
+        index,
+        takes_value,
+        value_type,
+        multiple,
+        possible_values,
+        required
+      );
+    }
+  }
+
+  impl ToTokens for CliValueType {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::utils::config::CliValueType };
+
+      tokens.append_all(match self {
+        Self::String => quote! { #prefix::String },
+        Self::Integer => quote! { #prefix::Integer },
+        Self::Number => quote! { #prefix::Number },
+        Self::Boolean => quote! { #prefix::Boolean },
+      })
+    }
+  }
+
+  impl ToTokens for ProcessLimits {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let max_memory = opt_lit(self.max_memory.as_ref());
+      let max_cpu_time = opt_lit(self.max_cpu_time.as_ref());
+      let cpu_rate = opt_lit(self.cpu_rate.as_ref());
+      let nice = opt_lit(self.nice.as_ref());
+
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::ProcessLimits,
+        max_memory,
+        max_cpu_time,
+        cpu_rate,
+        nice
+      );
+    }
+  }
+
+  impl ToTokens for IpcConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let max_request_size = opt_lit(self.max_request_size.as_ref());
+      let max_response_size = opt_lit(self.max_response_size.as_ref());
+      let max_event_size = opt_lit(self.max_event_size.as_ref());
+      let eval_chunk_size = self.eval_chunk_size;
+
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::IpcConfig,
+        max_request_size,
+        max_response_size,
+        max_event_size,
+        eval_chunk_size
+      );
+    }
+  }
+
+  impl ToTokens for FrontendPatchesConfig {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let pubkey = str_lit(&self.pubkey);
+
+      literal_struct!(
+        tokens,
+        ::tauri::utils::config::FrontendPatchesConfig,
+        pubkey
+      );
+    }
+  }
+
   impl ToTokens for AppConfig {
     fn to_tokens(&self, tokens: &mut TokenStream) {
       let windows = vec_lit(&self.windows, identity);
       let security = &self.security;
       let tray_icon = opt_lit(self.tray_icon.as_ref());
+      let menu = opt_vec_lit(self.menu.as_ref(), identity);
       let macos_private_api = self.macos_private_api;
       let with_global_tauri = self.with_global_tauri;
       let enable_gtk_app_id = self.enable_gtk_app_id;
+      let splashscreen = opt_lit(self.splashscreen.as_ref());
+      let kiosk = opt_lit(self.kiosk.as_ref());
+      let cli = opt_lit(self.cli.as_ref());
+      let software_rendering_fallback = self.software_rendering_fallback;
+      let sidecar_limits = map_lit(
+        quote! { ::std::collections::HashMap },
+        &self.sidecar_limits,
+        str_lit,
+        identity,
+      );
+      let frontend_patches = opt_lit(self.frontend_patches.as_ref());
+      let ipc = &self.ipc;
+      let feature_flags = map_lit(
+        quote! { ::std::collections::HashMap },
+        &self.feature_flags,
+        str_lit,
+        json_value_lit,
+      );
 
       literal_struct!(
         tokens,
@@ -2846,9 +3746,18 @@ mod build {
         windows,
         security,
         tray_icon,
+        menu,
         macos_private_api,
         with_global_tauri,
-        enable_gtk_app_id
+        enable_gtk_app_id,
+        splashscreen,
+        kiosk,
+        cli,
+        software_rendering_fallback,
+        sidecar_limits,
+        frontend_patches,
+        ipc,
+        feature_flags
       );
     }
   }
@@ -2925,9 +3834,18 @@ mod test {
         capabilities: Vec::new(),
       },
       tray_icon: None,
+      menu: None,
       macos_private_api: false,
       with_global_tauri: false,
       enable_gtk_app_id: false,
+      splashscreen: None,
+      kiosk: None,
+      cli: None,
+      software_rendering_fallback: false,
+      sidecar_limits: HashMap::new(),
+      frontend_patches: None,
+      ipc: Default::default(),
+      feature_flags: HashMap::new(),
     };
 
     // create a build config
@@ -2972,4 +3890,43 @@ mod test {
     assert_eq!(d_bundle, bundle);
     assert_eq!(d_windows, app.windows);
   }
+
+  #[test]
+  fn deserializes_menu_items() {
+    let items: Vec<MenuItemConfig> = serde_json::from_value(serde_json::json!([
+      {
+        "id": "file",
+        "textKey": "menu.file",
+        "items": [
+          { "id": "autosave", "text": "Autosave", "checked": true },
+          { "predefined": "selectAll" }
+        ]
+      }
+    ]))
+    .unwrap();
+
+    assert_eq!(
+      items,
+      vec![MenuItemConfig {
+        id: Some("file".into()),
+        text_key: Some("menu.file".into()),
+        enabled: true,
+        items: Some(vec![
+          MenuItemConfig {
+            id: Some("autosave".into()),
+            text: Some("Autosave".into()),
+            enabled: true,
+            checked: Some(true),
+            ..Default::default()
+          },
+          MenuItemConfig {
+            enabled: true,
+            predefined: Some(PredefinedMenuItemConfig::SelectAll),
+            ..Default::default()
+          },
+        ]),
+        ..Default::default()
+      }]
+    );
+  }
 }
@@ -24,6 +24,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 pub mod acl;
 pub mod assets;
+pub mod attributions;
 pub mod config;
 pub mod html;
 pub mod io;
@@ -133,7 +133,13 @@ const PLUGINS: &[(&str, &[(&str, bool)])] = &[
       ("print", false),
       ("reparent", false),
       ("clear_all_browsing_data", false),
+      ("clear_webview_cache", false),
+      ("set_webview_cache_mode", false),
+      ("set_webview_html_drag_drop_regions", false),
       // internal
+      ("internal_ime_composition", true),
+      ("internal_kiosk_heartbeat", true),
+      ("internal_gpu_failure", true),
       ("internal_toggle_devtools", true),
     ],
   ),
@@ -147,6 +153,10 @@ const PLUGINS: &[(&str, &[(&str, bool)])] = &[
       ("app_hide", false),
       ("default_window_icon", false),
       ("set_app_theme", false),
+      ("splash_done", true),
+      ("attributions", true),
+      ("cli_matches", false),
+      ("diagnostics_bundle", false),
     ],
   ),
   (
@@ -203,6 +213,34 @@ const PLUGINS: &[(&str, &[(&str, bool)])] = &[
       ("set_show_menu_on_left_click", true),
     ],
   ),
+  (
+    "core:serial",
+    &[
+      ("available_ports", false),
+      ("open", false),
+      ("write", false),
+    ],
+  ),
+  (
+    "core:bluetooth",
+    &[
+      ("start_scan", false),
+      ("stop_scan", false),
+      ("connect", false),
+      ("disconnect", false),
+      ("read", false),
+      ("write", false),
+      ("subscribe", false),
+      ("unsubscribe", false),
+    ],
+  ),
+  ("core:audio", &[("devices", false), ("watch", false)]),
+  ("core:printer", &[("list", false), ("print_raw", false)]),
+  (
+    "core:integrity",
+    &[("hash_file", false), ("verify_signature", false)],
+  ),
+  ("core:font", &[("families", false), ("install", false)]),
 ];
 
 // checks if the given Cargo feature is enabled.
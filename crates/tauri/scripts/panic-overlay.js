@@ -0,0 +1,74 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  // only the top level document shows the overlay
+  if (window.top !== window) return
+
+  const report = __TEMPLATE_report__
+  const id = '__tauri_panic_overlay__'
+
+  function show() {
+    let overlay = document.getElementById(id)
+    if (!overlay) {
+      overlay = document.createElement('div')
+      overlay.id = id
+      Object.assign(overlay.style, {
+        position: 'fixed',
+        inset: '0',
+        zIndex: '2147483647',
+        overflow: 'auto',
+        padding: '24px',
+        background: 'rgba(24, 24, 27, 0.96)',
+        color: '#fafafa',
+        font: '13px/1.5 ui-monospace, SFMono-Regular, Menlo, Consolas, monospace'
+      })
+      const close = document.createElement('button')
+      close.textContent = 'Dismiss'
+      Object.assign(close.style, { float: 'right', font: 'inherit' })
+      close.addEventListener('click', () => overlay.remove())
+      overlay.appendChild(close)
+      document.body.appendChild(overlay)
+    }
+
+    const section = document.createElement('section')
+    section.style.marginBottom = '24px'
+
+    const title = document.createElement('h2')
+    title.style.color = '#f87171'
+    title.style.margin = '0 0 8px'
+    title.textContent =
+      report.source.type === 'command'
+        ? `command ${report.source.name} panicked`
+        : `listener of the ${report.source.name} event panicked`
+    section.appendChild(title)
+
+    const message = document.createElement('pre')
+    message.style.whiteSpace = 'pre-wrap'
+    message.textContent = report.location
+      ? `${report.message}\n    at ${report.location}`
+      : report.message
+    section.appendChild(message)
+
+    if (report.backtrace) {
+      const details = document.createElement('details')
+      const summary = document.createElement('summary')
+      summary.textContent = 'Backtrace'
+      const backtrace = document.createElement('pre')
+      backtrace.style.whiteSpace = 'pre-wrap'
+      backtrace.style.opacity = '0.7'
+      backtrace.textContent = report.backtrace
+      details.append(summary, backtrace)
+      section.appendChild(details)
+    }
+
+    overlay.appendChild(section)
+  }
+
+  if (document.body) {
+    show()
+  } else {
+    window.addEventListener('DOMContentLoaded', show, { once: true })
+  }
+})()
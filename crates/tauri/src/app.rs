@@ -36,7 +36,11 @@ use tauri_runtime::{
   window::DragDropEvent,
   RuntimeInitArgs,
 };
-use tauri_utils::{assets::AssetsIter, PackageInfo};
+use tauri_utils::{
+  assets::{AssetsIter, CspHash},
+  config::SplashscreenConfig,
+  PackageInfo,
+};
 
 use serde::Serialize;
 use std::{
@@ -51,7 +55,17 @@ use crate::{event::EventId, runtime::RuntimeHandle, Event, EventTarget};
 #[cfg(target_os = "macos")]
 use crate::ActivationPolicy;
 
+#[cfg(desktop)]
+pub(crate) mod gpu;
+#[cfg(desktop)]
+pub(crate) mod kiosk;
 pub(crate) mod plugin;
+pub(crate) mod shutdown;
+pub(crate) mod splashscreen;
+
+#[cfg(desktop)]
+pub use gpu::GpuFailure;
+pub use shutdown::ShutdownReason;
 
 #[cfg(desktop)]
 pub(crate) type GlobalMenuEventListener<T> = Box<dyn Fn(&T, crate::menu::MenuEvent) + Send + Sync>;
@@ -70,6 +84,33 @@ pub type OnPageLoad<R> = dyn Fn(&Webview<R>, &PageLoadPayload<'_>) + Send + Sync
 /// The exit code on [`RunEvent::ExitRequested`] when [`AppHandle#method.restart`] is called.
 pub const RESTART_EXIT_CODE: i32 = i32::MAX;
 
+/// Where the window event handlers registered with [`Builder::on_window_event`] run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WindowEventDelivery {
+  /// Run the handlers on the event loop thread, blocking all window interaction while they run.
+  MainThread,
+  /// Run the handlers in order on a dedicated thread, the event loop never waits for them.
+  ///
+  /// When `capacity` events are waiting for the handlers,
+  /// new [`WindowEvent::Resized`], [`WindowEvent::Moved`] and [`DragDropEvent::Over`] events are dropped
+  /// since a later event of the same kind supersedes them. Other events are always queued.
+  ///
+  /// [`WindowEvent::CloseRequested`] keeps the window open until the handlers ran,
+  /// then the window is destroyed unless a handler called [`CloseRequestApi::prevent_close`]
+  /// or a JavaScript `onCloseRequested` listener is registered.
+  Dedicated {
+    /// How many events can be waiting for the handlers before coalescable events are dropped.
+    capacity: usize,
+  },
+}
+
+impl Default for WindowEventDelivery {
+  fn default() -> Self {
+    Self::Dedicated { capacity: 256 }
+  }
+}
+
 /// Api exposed on the `ExitRequested` event.
 #[derive(Debug)]
 pub struct ExitRequestApi(Sender<ExitRequestedEventAction>);
@@ -85,7 +126,7 @@ impl ExitRequestApi {
 
 /// Api exposed on the `CloseRequested` event.
 #[derive(Debug, Clone)]
-pub struct CloseRequestApi(Sender<bool>);
+pub struct CloseRequestApi(pub(crate) Sender<bool>);
 
 impl CloseRequestApi {
   /// Prevents the window from being closed.
@@ -169,6 +210,12 @@ impl From<RuntimeWindowEvent> for WindowEvent {
 pub enum WebviewEvent {
   /// An event associated with the drag and drop action.
   DragDrop(DragDropEvent),
+  /// An input method editor composition happened in the webview.
+  ///
+  /// Only delivered to the handlers registered with [`Builder::on_webview_event`],
+  /// the compositions are not forwarded when there are none.
+  /// Fast composition updates are coalesced, so only the latest text is delivered.
+  ImeComposition(crate::keyboard::ImeComposition),
 }
 
 impl From<RuntimeWebviewEvent> for WebviewEvent {
@@ -244,6 +291,32 @@ pub enum RunEvent {
     /// Indicates whether the NSApplication object found any visible windows in your application.
     has_visible_windows: bool,
   },
+  /// Emitted by the kiosk mode watchdog when a webview stopped sending heartbeats,
+  /// for instance because its content process crashed. See the `app > kiosk` configuration.
+  #[non_exhaustive]
+  #[cfg(desktop)]
+  #[cfg_attr(docsrs, doc(cfg(desktop)))]
+  WebviewUnresponsive {
+    /// The webview label.
+    label: String,
+    /// Whether the webview is being reloaded, see the `relaunchOnCrash` configuration.
+    reloading: bool,
+  },
+  /// Emitted when a webview cannot render with the GPU, for instance because the graphics driver is blocklisted.
+  ///
+  /// Each webview reports at most one failure per launch.
+  /// Only emitted when the `app > softwareRenderingFallback` configuration is enabled.
+  #[non_exhaustive]
+  #[cfg(desktop)]
+  #[cfg_attr(docsrs, doc(cfg(desktop)))]
+  GpuFailure {
+    /// The webview label.
+    label: String,
+    /// The detected failure.
+    failure: GpuFailure,
+    /// Whether the webviews render without the GPU from the next launch.
+    fallback: bool,
+  },
 }
 
 impl From<EventLoopMessage> for RunEvent {
@@ -253,6 +326,20 @@ impl From<EventLoopMessage> for RunEvent {
       EventLoopMessage::MenuEvent(e) => Self::MenuEvent(e),
       #[cfg(all(desktop, feature = "tray-icon"))]
       EventLoopMessage::TrayIconEvent(e) => Self::TrayIconEvent(e),
+      #[cfg(desktop)]
+      EventLoopMessage::WebviewUnresponsive { label, reloading } => {
+        Self::WebviewUnresponsive { label, reloading }
+      }
+      #[cfg(desktop)]
+      EventLoopMessage::GpuFailure {
+        label,
+        failure,
+        fallback,
+      } => Self::GpuFailure {
+        label,
+        failure,
+        fallback,
+      },
     }
   }
 }
@@ -260,7 +347,7 @@ impl From<EventLoopMessage> for RunEvent {
 /// The asset resolver is a helper to access the [`tauri_utils::assets::Assets`] interface.
 #[derive(Debug, Clone)]
 pub struct AssetResolver<R: Runtime> {
-  manager: Arc<AppManager<R>>,
+  pub(crate) manager: Arc<AppManager<R>>,
 }
 
 impl<R: Runtime> AssetResolver<R> {
@@ -311,6 +398,14 @@ impl<R: Runtime> AssetResolver<R> {
   pub fn iter(&self) -> Box<AssetsIter<'_>> {
     self.manager.assets.iter()
   }
+
+  /// Gets the hashes of the inline scripts and styles of the embedded HTML asset on the given path,
+  /// which are added to its `Content-Security-Policy` when it is served to a webview.
+  ///
+  /// Returns the hashes shared by all HTML assets if the path is not an HTML asset.
+  pub fn csp_hashes(&self, html_path: &str) -> Box<dyn Iterator<Item = CspHash<'_>> + '_> {
+    self.manager.assets.csp_hashes(&html_path.into())
+  }
 }
 
 /// A handle to the currently running application.
@@ -439,7 +534,14 @@ impl<R: Runtime> AppHandle<R> {
   ///   });
   /// ```
   pub fn remove_plugin(&self, plugin: &'static str) -> bool {
-    self.manager().plugins.lock().unwrap().unregister(plugin)
+    let removed = self.manager().plugins.lock().unwrap().unregister(plugin);
+    #[cfg(feature = "localhost-server")]
+    if removed {
+      if let Some(server) = self.try_state::<crate::localhost::LocalhostServer<R>>() {
+        server.remove_routes(plugin);
+      }
+    }
+    removed
   }
 
   /// Exits the app by triggering [`RunEvent::ExitRequested`] and [`RunEvent::Exit`].
@@ -452,7 +554,13 @@ impl<R: Runtime> AppHandle<R> {
   }
 
   /// Restarts the app by triggering [`RunEvent::ExitRequested`] with code [`RESTART_EXIT_CODE`] and [`RunEvent::Exit`]..
+  ///
+  /// The [`Builder::on_shutdown`] handlers run before the app restarts.
   pub fn restart(&self) -> ! {
+    // the process is replaced before the event loop handles the exit request
+    if let Some(shutdown) = self.try_state::<shutdown::Shutdown<R>>() {
+      shutdown.run(self, ShutdownReason::Exit);
+    }
     if self.runtime_handle.request_exit(RESTART_EXIT_CODE).is_err() {
       self.cleanup_before_exit();
     }
@@ -514,6 +622,7 @@ pub struct App<R: Runtime> {
   manager: Arc<AppManager<R>>,
   handle: AppHandle<R>,
   ran_setup: bool,
+  lazy_plugins: Vec<Box<dyn Plugin<R>>>,
 }
 
 impl<R: Runtime> fmt::Debug for App<R> {
@@ -629,11 +738,54 @@ macro_rules! shared_app_impl {
         self.manager.package_info()
       }
 
+      /// Gets the runtime feature flags of the app, shared with the frontend.
+      ///
+      /// See [`crate::feature_flags`] for more information.
+      pub fn features(&self) -> &crate::feature_flags::FeatureFlags<R> {
+        self
+          .state::<crate::feature_flags::FeatureFlags<R>>()
+          .inner()
+      }
+
+      /// Gets the third-party license attributions of the app, e.g. to show an "Open source licenses" screen.
+      ///
+      /// The list is empty unless the build script enables them with `tauri_build::Attributes::attributions`.
+      pub fn attributions(&self) -> &[crate::utils::attributions::Attribution] {
+        &self.manager.attributions
+      }
+
+      /// Parses the command line arguments of the app with the `app > cli` configuration.
+      ///
+      /// The raw arguments are available with [`Manager::env`]. Arguments of the secondary instances
+      /// are delivered with [`crate::cli::forward_args`].
+      ///
+      /// # Examples
+      /// ```,no_run
+      /// tauri::Builder::default()
+      ///   .setup(|app| {
+      ///     let matches = app.cli_matches()?;
+      ///     if let Some(help) = matches.args.get("help") {
+      ///       println!("{}", help.value.as_str().unwrap_or_default());
+      ///       std::process::exit(0);
+      ///     }
+      ///     Ok(())
+      ///   });
+      /// ```
+      pub fn cli_matches(&self) -> crate::Result<crate::cli::Matches> {
+        let config = self
+          .config()
+          .app
+          .cli
+          .as_ref()
+          .ok_or(crate::cli::Error::NotConfigured)?;
+        crate::cli::parse(config, self.env().args_os.iter().skip(1)).map_err(Into::into)
+      }
+
       /// The application's asset resolver.
+      ///
+      /// Also available on every [`Manager`] with [`Manager::asset_resolver`].
       pub fn asset_resolver(&self) -> AssetResolver<R> {
-        AssetResolver {
-          manager: self.manager.clone(),
-        }
+        Manager::asset_resolver(self)
       }
 
       /// Returns the primary monitor of the system.
@@ -715,12 +867,32 @@ macro_rules! shared_app_impl {
         self.manager.window.default_icon.as_ref()
       }
 
+      /// Whether one of the windows of the app is focused,
+      /// to avoid notifying the user about events they can already see.
+      pub fn is_frontmost(&self) -> crate::Result<bool> {
+        for window in self.manager.windows().values() {
+          if window.is_focused()? {
+            return Ok(true);
+          }
+        }
+        Ok(false)
+      }
+
       /// Returns the app-wide menu.
       #[cfg(desktop)]
       pub fn menu(&self) -> Option<Menu<R>> {
         self.manager.menu.menu_lock().clone()
       }
 
+      /// Updates the text of the menu items defined in the configuration with a `textKey`,
+      /// using the translator set with [`Builder::menu_translator`], e.g. after the user changed the language.
+      #[cfg(desktop)]
+      pub fn retranslate_menus(&self) -> crate::Result<()> {
+        self
+          .state::<crate::menu::config::ConfigMenus<R>>()
+          .retranslate(self.app_handle())
+      }
+
       /// Sets the app-wide menu and returns the previous one.
       ///
       /// If a window was not created with an explicit menu or had one set explicitly,
@@ -863,9 +1035,354 @@ macro_rules! shared_app_impl {
         Ok(())
       }
 
+      /// Latency percentiles of the IPC commands resolved so far, slowest median first.
+      ///
+      /// In development, this report is also logged when the app exits.
+      #[cfg(feature = "tracing")]
+      #[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+      pub fn command_latencies(&self) -> Vec<crate::ipc::CommandLatency> {
+        self.manager.command_latencies.report()
+      }
+
+      /// Removes the cached responses of a command declared with `#[tauri::command(cache = "...")]`,
+      /// so the next call runs it again.
+      ///
+      /// The command name is the one invoked by the frontend, such as `list_files` or `plugin:fs|read_dir`.
+      ///
+      /// # Examples
+      ///
+      /// ```rust,no_run
+      /// #[tauri::command(cache = "30s", key = "args")]
+      /// fn list_files(dir: std::path::PathBuf) -> Vec<String> {
+      ///   // expensive directory scan
+      ///   Vec::new()
+      /// }
+      ///
+      /// #[tauri::command]
+      /// fn create_file(app: tauri::AppHandle, path: std::path::PathBuf) -> std::io::Result<()> {
+      ///   std::fs::write(path, "")?;
+      ///   app.invalidate_command_cache("list_files");
+      ///   Ok(())
+      /// }
+      /// ```
+      pub fn invalidate_command_cache(&self, command: &str) {
+        self.manager.command_cache.invalidate(command);
+      }
+
+      /// Removes the cached responses of all commands.
+      pub fn clear_command_cache(&self) {
+        self.manager.command_cache.clear();
+      }
+
+      /// Collects the resource usage of the application process and its child processes,
+      /// including the webview engine processes where the platform exposes them.
+      ///
+      /// See [`crate::process::metrics`] for platform-specific behavior.
+      pub fn process_metrics(&self) -> crate::process::ProcessMetrics {
+        crate::process::metrics()
+      }
+
+      /// Emits the [`crate::process::PROCESS_METRICS_EVENT`] event to all targets with the current
+      /// [`crate::process::ProcessMetrics`] every `interval`, so memory usage can be monitored from the frontend.
+      ///
+      /// Abort the returned task to stop watching.
+      ///
+      /// # Examples
+      /// ```,no_run
+      /// use std::time::Duration;
+      ///
+      /// tauri::Builder::default()
+      ///   .setup(|app| {
+      ///     app.watch_process_metrics(Duration::from_secs(60));
+      ///     Ok(())
+      ///   });
+      /// ```
+      pub fn watch_process_metrics(
+        &self,
+        interval: std::time::Duration,
+      ) -> crate::async_runtime::JoinHandle<()> {
+        let app = self.app_handle().clone();
+        crate::async_runtime::spawn(async move {
+          let mut ticker = tokio::time::interval(interval);
+          loop {
+            ticker.tick().await;
+            let Ok(metrics) = crate::async_runtime::spawn_blocking(crate::process::metrics).await
+            else {
+              break;
+            };
+            if let Err(e) = app.emit(crate::process::PROCESS_METRICS_EVENT, metrics) {
+              log::error!("failed to emit process metrics: {e}");
+            }
+          }
+        })
+      }
+
+      /// Emits the [`crate::network::SYSTEM_PROXY_CHANGED_EVENT`] event to all targets with the new
+      /// [`crate::network::SystemProxy`] when the system proxy configuration changes, checking it every `interval`.
+      ///
+      /// Listen to the event on the Rust side to rebuild HTTP clients or restart sidecars with the new configuration.
+      /// Abort the returned task to stop watching. It stops by itself if the configuration cannot be read,
+      /// see [`crate::network::system_proxy`].
+      ///
+      /// # Examples
+      /// ```,no_run
+      /// use std::time::Duration;
+      /// use tauri::{network::SYSTEM_PROXY_CHANGED_EVENT, Listener};
+      ///
+      /// tauri::Builder::default()
+      ///   .setup(|app| {
+      ///     app.watch_system_proxy(Duration::from_secs(30));
+      ///     app.listen(SYSTEM_PROXY_CHANGED_EVENT, |event| {
+      ///       println!("proxy changed: {}", event.payload());
+      ///     });
+      ///     Ok(())
+      ///   });
+      /// ```
+      pub fn watch_system_proxy(
+        &self,
+        interval: std::time::Duration,
+      ) -> crate::async_runtime::JoinHandle<()> {
+        let app = self.app_handle().clone();
+        crate::async_runtime::spawn(async move {
+          let mut ticker = tokio::time::interval(interval);
+          let mut current = None;
+          loop {
+            ticker.tick().await;
+            let proxy =
+              match crate::async_runtime::spawn_blocking(crate::network::system_proxy).await {
+                Ok(Ok(proxy)) => proxy,
+                Ok(Err(e)) => {
+                  log::error!("failed to read the system proxy: {e}");
+                  break;
+                }
+                Err(_) => break,
+              };
+            // the first check only records the initial configuration
+            let changed = current.as_ref().is_some_and(|current| *current != proxy);
+            if changed {
+              if let Err(e) = app.emit(crate::network::SYSTEM_PROXY_CHANGED_EVENT, &proxy) {
+                log::error!("failed to emit system proxy change: {e}");
+              }
+            }
+            current.replace(proxy);
+          }
+        })
+      }
+
+      /// Calls `on_change` with the new list of [`crate::audio::AudioDevice`]s when a device is added or removed
+      /// or the default device changes, until the returned watcher is dropped.
+      ///
+      /// Use it to keep a device picker in sync with the devices `getUserMedia` can use.
+      /// See [`crate::audio::watch`] for the platform-specific behavior.
+      ///
+      /// # Examples
+      /// ```,no_run
+      /// use tauri::Manager;
+      ///
+      /// struct AudioDevices(tauri::audio::DeviceWatcher);
+      ///
+      /// tauri::Builder::default()
+      ///   .setup(|app| {
+      ///     let watcher = app.watch_audio_devices(|devices| {
+      ///       println!("{} audio devices", devices.len());
+      ///     })?;
+      ///     app.manage(AudioDevices(watcher));
+      ///     Ok(())
+      ///   });
+      /// ```
+      #[cfg(feature = "audio")]
+      #[cfg_attr(docsrs, doc(cfg(feature = "audio")))]
+      pub fn watch_audio_devices<F: Fn(Vec<crate::audio::AudioDevice>) + Send + 'static>(
+        &self,
+        on_change: F,
+      ) -> crate::Result<crate::audio::DeviceWatcher> {
+        crate::audio::watch(on_change)
+      }
+
+      /// Adds the file to the recent documents of the operating system,
+      /// shown in the app jump list on Windows, the Dock menu on macOS and the file choosers "Recent" location on Linux.
+      ///
+      /// The documents opened through the [`bundle > fileAssociations`](https://v2.tauri.app/reference/config/#fileassociations)
+      /// are added automatically: from the open events on macOS, and from the command line arguments of the app on Windows and Linux.
+      /// The documents passed to an already running instance, e.g. through the single instance plugin, must be added by the app.
+      ///
+      /// ## Platform-specific
+      ///
+      /// - **macOS:** The file type must be declared in the [`bundle > fileAssociations`](https://v2.tauri.app/reference/config/#fileassociations) configuration.
+      ///
+      /// # Examples
+      /// ```,no_run
+      /// tauri::Builder::default()
+      ///   .setup(|app| {
+      ///     if let Some(path) = std::env::args_os().nth(1) {
+      ///       app.add_recent_document(path)?;
+      ///     }
+      ///     Ok(())
+      ///   });
+      /// ```
+      #[cfg(all(desktop, feature = "recent-documents"))]
+      #[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "recent-documents"))))]
+      pub fn add_recent_document<P: AsRef<std::path::Path>>(&self, path: P) -> crate::Result<()> {
+        // the recent documents are resolved later, possibly from another working directory
+        let path = dunce::canonicalize(path)?;
+        let app = self.app_handle();
+        crate::run_main_thread!(app, move || crate::recent_documents::add(&path))?
+      }
+
+      /// Clears the recent documents of the app, see [`Self::add_recent_document`].
+      ///
+      /// ## Platform-specific
+      ///
+      /// - **Linux:** The list is shared by all apps, only the documents not opened by other apps are removed.
+      #[cfg(all(desktop, feature = "recent-documents"))]
+      #[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "recent-documents"))))]
+      pub fn clear_recent_documents(&self) -> crate::Result<()> {
+        let app = self.app_handle();
+        crate::run_main_thread!(app, crate::recent_documents::clear)?
+      }
+
+      /// Emits the [`crate::network::NETWORK_STATUS_CHANGED_EVENT`] event to all targets with the new
+      /// [`crate::network::NetworkStatus`] when the network connectivity changes.
+      ///
+      /// Changes are detected with the platform notifications rather than polling,
+      /// so Rust listeners can pause synchronization when the app goes offline or on a metered connection.
+      ///
+      /// ## Platform-specific
+      ///
+      /// - **Windows:** Returns an [`std::io::ErrorKind::Unsupported`] error unless the `network-status` feature is enabled.
+      ///
+      /// # Examples
+      /// ```,no_run
+      /// use tauri::Listener;
+      ///
+      /// tauri::Builder::default()
+      ///   .setup(|app| {
+      ///     app.watch_network_status()?;
+      ///     app.listen(tauri::network::NETWORK_STATUS_CHANGED_EVENT, |event| {
+      ///       println!("network status changed: {}", event.payload());
+      ///     });
+      ///     Ok(())
+      ///   });
+      /// ```
+      pub fn watch_network_status(&self) -> crate::Result<()> {
+        let app = self.app_handle().clone();
+        let last = std::sync::Mutex::new(crate::network::status());
+        let on_change = move || {
+          let status = crate::network::status();
+          let mut last = last.lock().unwrap();
+          if *last != status {
+            *last = status.clone();
+            if let Err(e) = app.emit(crate::network::NETWORK_STATUS_CHANGED_EVENT, status) {
+              log::error!("failed to emit network status change: {e}");
+            }
+          }
+        };
+        let app = self.app_handle();
+        crate::run_main_thread!(app, move || crate::network::watch_status(on_change))?
+          .map_err(Into::into)
+      }
+
+      /// Returns the keyboard layout or input method currently selected by the user.
+      ///
+      /// The layout is shared by all windows of the app since they run on the main thread.
+      ///
+      /// ## Platform-specific
+      ///
+      /// - **Windows:** Returns an [`std::io::ErrorKind::Unsupported`] error unless the `keyboard-layout` feature is enabled.
+      /// - **Linux:** Reads the GNOME input sources, the name and languages are not available.
+      /// - **Android / iOS:** Unsupported, returns `None`.
+      pub fn keyboard_layout(&self) -> crate::Result<Option<crate::keyboard::KeyboardLayout>> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.run_on_main_thread(move || {
+          let _ = tx.send(crate::keyboard::current_layout());
+        })?;
+        rx.recv()
+          .map_err(|_| crate::Error::FailedToReceiveMessage)?
+          .map_err(Into::into)
+      }
+
+      /// Closes the splashscreen configured in `app > splashscreen` and shows its main window,
+      /// once the configured minimum display time elapsed.
+      ///
+      /// Use it with `autoClose: false` to keep the splashscreen until the app finished its startup work.
+      /// Does nothing if no splashscreen is configured or it was already closed.
+      pub fn splash_done(&self) {
+        self
+          .state::<splashscreen::Splashscreen>()
+          .done(self.app_handle());
+      }
+
+      /// Whether the webviews render without the GPU because a graphics failure was detected on a previous launch.
+      ///
+      /// Always `false` unless `app > softwareRenderingFallback` is enabled.
+      #[cfg(desktop)]
+      pub fn software_rendering(&self) -> bool {
+        self
+          .try_state::<gpu::Gpu>()
+          .is_some_and(|gpu| gpu.software_rendering())
+      }
+
+      /// Forgets the graphics failures detected on previous launches, so the next launch renders with the GPU again,
+      /// for instance after the user updated their graphics driver.
+      #[cfg(desktop)]
+      pub fn reset_gpu_failure(&self) -> crate::Result<()> {
+        match self.try_state::<gpu::Gpu>() {
+          Some(gpu) => gpu.reset(),
+          None => Ok(()),
+        }
+      }
+
+      /// Writes a support bundle to the given path, a zip archive with:
+      ///
+      /// - `config.json`: the app configuration, with secrets such as passwords, tokens and URL credentials masked;
+      /// - `versions.json`: the app, Tauri and webview versions and the platform;
+      /// - `events.json`: the most recent [`RunEvent`]s, with their strings such as paths and URLs masked;
+      /// - `windows.json`: the state of the windows and their webviews, with the URL queries and fragments removed;
+      /// - `scopes.json`: the resolved command permissions and scopes and the asset protocol scope;
+      /// - `logs/`: the end of the files in the app log directory, which are not redacted.
+      ///
+      /// Existing files are not overwritten. The `diagnostics_bundle` command of the JavaScript API
+      /// can only write the bundle in the app log and cache directories.
+      ///
+      /// # Examples
+      ///
+      /// ```rust,no_run
+      /// #[tauri::command]
+      /// fn export_diagnostics(app: tauri::AppHandle, path: std::path::PathBuf) -> tauri::Result<()> {
+      ///   app.diagnostics_bundle(path)
+      /// }
+      /// ```
+      #[cfg(feature = "diagnostics")]
+      #[cfg_attr(docsrs, doc(cfg(feature = "diagnostics")))]
+      pub fn diagnostics_bundle<P: AsRef<std::path::Path>>(&self, path: P) -> crate::Result<()> {
+        crate::diagnostics::write_bundle(self, path.as_ref())
+      }
+
+      /// Returns the installer of the signed frontend patches configured in `app > frontendPatches`.
+      ///
+      /// See the [`crate::frontend_patches`] module.
+      #[cfg(all(desktop, feature = "frontend-patches"))]
+      #[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "frontend-patches"))))]
+      pub fn frontend_patcher(&self) -> crate::frontend_patches::FrontendPatcher<R> {
+        crate::frontend_patches::FrontendPatcher {
+          app: self.app_handle().clone(),
+        }
+      }
+
       /// Runs necessary cleanup tasks before exiting the process.
       /// **You should always exit the tauri app immediately after this function returns and not use any tauri-related APIs.**
       pub fn cleanup_before_exit(&self) {
+        if let Some(shutdown) = self.try_state::<shutdown::Shutdown<R>>() {
+          shutdown.run(self.app_handle(), ShutdownReason::Exit);
+        }
+        #[cfg(all(dev, feature = "tracing"))]
+        self.manager.command_latencies.log_report();
+        if let Some(scheduler) = self.try_state::<crate::scheduler::Scheduler<R>>() {
+          scheduler.shutdown();
+        }
+        if let Some(states) = self.try_state::<crate::persistent_state::PersistentStates>() {
+          states.flush();
+        }
         #[cfg(all(desktop, feature = "tray-icon"))]
         self.manager.tray.icons.lock().unwrap().clear();
         self.manager.resources_table().clear();
@@ -1034,6 +1551,18 @@ impl<R: Runtime> App<R> {
     self.handle.plugin(crate::menu::plugin::init())?;
     #[cfg(all(desktop, feature = "tray-icon"))]
     self.handle.plugin(crate::tray::plugin::init())?;
+    #[cfg(feature = "serial")]
+    self.handle.plugin(crate::serial::plugin::init())?;
+    #[cfg(feature = "bluetooth")]
+    self.handle.plugin(crate::bluetooth::plugin::init())?;
+    #[cfg(feature = "audio")]
+    self.handle.plugin(crate::audio::plugin::init())?;
+    #[cfg(feature = "printer")]
+    self.handle.plugin(crate::printer::plugin::init())?;
+    #[cfg(feature = "integrity")]
+    self.handle.plugin(crate::integrity::plugin::init())?;
+    #[cfg(feature = "font")]
+    self.handle.plugin(crate::font::plugin::init())?;
     Ok(())
   }
 
@@ -1123,6 +1652,27 @@ impl<R: Runtime> App<R> {
         let event = on_event_loop_event(&app_handle, RuntimeRunEvent::Ready, &manager);
         callback(&app_handle, event);
       }
+      RuntimeRunEvent::ExitRequested { code, tx } => {
+        let (api_tx, api_rx) = std::sync::mpsc::channel();
+        let event = on_event_loop_event(
+          &app_handle,
+          RuntimeRunEvent::ExitRequested { code, tx: api_tx },
+          &manager,
+        );
+        callback(&app_handle, event);
+        if let Ok(action) = api_rx.try_recv() {
+          let _ = tx.send(action);
+        } else if code != Some(RESTART_EXIT_CODE)
+          && app_handle
+            .try_state::<shutdown::Shutdown<R>>()
+            .is_some_and(|shutdown| {
+              shutdown.run_then_exit(&app_handle, ShutdownReason::Exit, code.unwrap_or(0))
+            })
+        {
+          // the shutdown handlers run off the event loop thread, which exits the app again once they complete
+          let _ = tx.send(ExitRequestedEventAction::Prevent);
+        }
+      }
       RuntimeRunEvent::Exit => {
         let event = on_event_loop_event(&app_handle, RuntimeRunEvent::Exit, &manager);
         callback(&app_handle, event);
@@ -1190,8 +1740,8 @@ pub struct Builder<R: Runtime> {
   #[cfg(any(windows, target_os = "linux"))]
   runtime_any_thread: bool,
 
-  /// The JS message handler.
-  invoke_handler: Box<InvokeHandler<R>>,
+  /// The JS message handlers.
+  invoke_handlers: Vec<Box<InvokeHandler<R>>>,
 
   /// The script that initializes the `window.__TAURI_INTERNALS__.postMessage` function.
   pub(crate) invoke_initialization_script: String,
@@ -1205,6 +1755,9 @@ pub struct Builder<R: Runtime> {
   /// All passed plugins
   plugins: PluginStore<R>,
 
+  /// Plugins initialized after the setup hook.
+  lazy_plugins: Vec<Box<dyn Plugin<R>>>,
+
   /// The webview protocols available to all windows.
   uri_scheme_protocols: HashMap<String, Arc<UriSchemeProtocol<R>>>,
 
@@ -1215,6 +1768,10 @@ pub struct Builder<R: Runtime> {
   #[cfg(desktop)]
   menu: Option<Box<dyn FnOnce(&AppHandle<R>) -> crate::Result<Menu<R>> + Send>>,
 
+  /// The translator of the text keys of the menus defined in the configuration.
+  #[cfg(desktop)]
+  menu_translator: Option<Box<crate::menu::config::MenuTranslator<R>>>,
+
   /// Enable macOS default menu creation.
   #[allow(unused)]
   enable_macos_default_menu: bool,
@@ -1225,9 +1782,28 @@ pub struct Builder<R: Runtime> {
   /// Webview event handlers that listens to all webviews.
   webview_event_listeners: Vec<GlobalWebviewEventListener<R>>,
 
+  /// Where the window event handlers run.
+  window_event_delivery: WindowEventDelivery,
+
   /// The device event filter.
   device_event_filter: DeviceEventFilter,
 
+  /// How many pre-warmed webview windows to keep.
+  #[cfg(desktop)]
+  webview_pool_size: usize,
+
+  /// The splashscreen configuration overriding `app > splashscreen`.
+  splashscreen: Option<SplashscreenConfig>,
+
+  /// The handlers run when the app shuts down, in registration order.
+  shutdown_handlers: Vec<(String, shutdown::ShutdownHandler<R>)>,
+
+  /// How long the shutdown handlers have to complete.
+  shutdown_deadline: std::time::Duration,
+
+  /// The tokio runtime handle set as the async runtime on build.
+  runtime_handle: Option<crate::async_runtime::TokioHandle>,
+
   pub(crate) invoke_key: String,
 }
 
@@ -1268,7 +1844,7 @@ impl<R: Runtime> Builder<R> {
       #[cfg(any(windows, target_os = "linux"))]
       runtime_any_thread: false,
       setup: Box::new(|_| Ok(())),
-      invoke_handler: Box::new(|_| false),
+      invoke_handlers: Vec::new(),
       invoke_initialization_script: InvokeInitializationScript {
         process_ipc_message_fn: crate::manager::webview::PROCESS_IPC_MESSAGE_FN,
         os_name: std::env::consts::OS,
@@ -1280,14 +1856,24 @@ impl<R: Runtime> Builder<R> {
       .into_string(),
       on_page_load: None,
       plugins: PluginStore::default(),
+      lazy_plugins: Vec::new(),
       uri_scheme_protocols: Default::default(),
       state: StateManager::new(),
       #[cfg(desktop)]
       menu: None,
+      #[cfg(desktop)]
+      menu_translator: None,
       enable_macos_default_menu: true,
       window_event_listeners: Vec::new(),
+      window_event_delivery: Default::default(),
       webview_event_listeners: Vec::new(),
       device_event_filter: Default::default(),
+      #[cfg(desktop)]
+      webview_pool_size: 0,
+      splashscreen: None,
+      shutdown_handlers: Vec::new(),
+      shutdown_deadline: shutdown::DEFAULT_DEADLINE,
+      runtime_handle: None,
       invoke_key,
     }
   }
@@ -1309,6 +1895,8 @@ impl<R: Runtime> Builder<R> {
 
   /// Defines the JS message handler callback.
   ///
+  /// This replaces the handlers added with [`Self::extend_invoke_handler`].
+  ///
   /// # Examples
   /// ```
   /// #[tauri::command]
@@ -1326,7 +1914,39 @@ impl<R: Runtime> Builder<R> {
   where
     F: Fn(Invoke<R>) -> bool + Send + Sync + 'static,
   {
-    self.invoke_handler = Box::new(invoke_handler);
+    self.invoke_handlers = vec![Box::new(invoke_handler)];
+    self
+  }
+
+  /// Adds a JS message handler callback, tried after the previously defined ones
+  /// when they do not handle the invoked command.
+  ///
+  /// This lets the commands be split in several [`generate_handler!`](crate::generate_handler) calls,
+  /// e.g. one per module or crate, instead of a single call listing all of them.
+  /// The handler of a command is looked up until it handles the command once, then the next invokes
+  /// of the command go straight to it: a handler must handle or reject a command based on its name only,
+  /// not on the invoke payload or the ACL.
+  ///
+  /// # Examples
+  /// ```
+  /// mod files {
+  ///   #[tauri::command]
+  ///   pub fn read_file() {}
+  /// }
+  /// mod settings {
+  ///   #[tauri::command]
+  ///   pub fn get_settings() {}
+  /// }
+  /// tauri::Builder::default()
+  ///   .invoke_handler(tauri::generate_handler![files::read_file])
+  ///   .extend_invoke_handler(tauri::generate_handler![settings::get_settings]);
+  /// ```
+  #[must_use]
+  pub fn extend_invoke_handler<F>(mut self, invoke_handler: F) -> Self
+  where
+    F: Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+  {
+    self.invoke_handlers.push(Box::new(invoke_handler));
     self
   }
 
@@ -1485,6 +2105,30 @@ tauri::Builder::default()
     self
   }
 
+  /// Adds a Tauri application plugin that is initialized after the setup hook runs,
+  /// once the event loop had a chance to show the first windows.
+  ///
+  /// Use this for plugins that are not needed to render the first window, so their initialization doesn't delay it.
+  /// Until the plugin is initialized its commands are rejected, and its hooks, including its JavaScript
+  /// initialization script, do not apply to the windows and webviews created before that.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri::{plugin::{Builder as PluginBuilder, TauriPlugin}, Runtime};
+  ///
+  /// fn init_plugin<R: Runtime>() -> TauriPlugin<R> {
+  ///   PluginBuilder::new("analytics").build()
+  /// }
+  ///
+  /// tauri::Builder::default()
+  ///   .plugin_lazy(init_plugin());
+  /// ```
+  #[must_use]
+  pub fn plugin_lazy<P: Plugin<R> + 'static>(mut self, plugin: P) -> Self {
+    self.lazy_plugins.push(Box::new(plugin));
+    self
+  }
+
   /// Add `state` to the state managed by the application.
   ///
   /// This method can be called any number of times as long as each call
@@ -1576,6 +2220,36 @@ tauri::Builder::default()
     self
   }
 
+  /// Add state to the state managed by the application, created by `init` the first time it is accessed.
+  ///
+  /// Use this for state that is expensive to create, so it does not delay the first window.
+  /// See [`Manager::manage_lazy`](crate::Manager::manage_lazy) for more information.
+  ///
+  /// # Panics
+  ///
+  /// Panics if state of type `T` is already being managed.
+  ///
+  /// # Examples
+  /// ```,no_run
+  /// struct Database(String);
+  ///
+  /// tauri::Builder::default()
+  ///   .manage_lazy(|| Database("connection".into()));
+  /// ```
+  #[must_use]
+  pub fn manage_lazy<T, F>(self, init: F) -> Self
+  where
+    T: Send + Sync + 'static,
+    F: FnOnce() -> T + Send + 'static,
+  {
+    let type_name = std::any::type_name::<T>();
+    assert!(
+      self.state.set_lazy(init),
+      "state for type '{type_name}' is already being managed",
+    );
+    self
+  }
+
   /// Sets the menu to use on all windows.
   ///
   /// # Examples
@@ -1606,6 +2280,30 @@ tauri::Builder::default()
     self
   }
 
+  /// Sets the function resolving the `textKey` of the menu items defined in the configuration,
+  /// returning `None` for unknown keys.
+  ///
+  /// Call [`AppHandle::retranslate_menus`] to update the menus when the language changes.
+  ///
+  /// # Examples
+  /// ```
+  /// tauri::Builder::default()
+  ///   .menu_translator(|_app, key| match key {
+  ///     "menu.file" => Some("Fichier".into()),
+  ///     "menu.open" => Some("Ouvrir".into()),
+  ///     _ => None,
+  ///   });
+  /// ```
+  #[must_use]
+  #[cfg(desktop)]
+  pub fn menu_translator<F: Fn(&AppHandle<R>, &str) -> Option<String> + Send + Sync + 'static>(
+    mut self,
+    f: F,
+  ) -> Self {
+    self.menu_translator.replace(Box::new(f));
+    self
+  }
+
   /// Enable or disable the default menu on macOS. Enabled by default.
   ///
   /// # Examples
@@ -1643,6 +2341,22 @@ tauri::Builder::default()
     self
   }
 
+  /// Sets where the handlers registered with [`Self::on_window_event`] run.
+  ///
+  /// By default they run on a dedicated thread holding up to 256 events, see [`WindowEventDelivery`].
+  /// Use [`WindowEventDelivery::MainThread`] for handlers that must run before the event loop continues.
+  ///
+  /// # Examples
+  /// ```
+  /// tauri::Builder::default()
+  ///   .window_event_delivery(tauri::WindowEventDelivery::Dedicated { capacity: 1024 });
+  /// ```
+  #[must_use]
+  pub fn window_event_delivery(mut self, delivery: WindowEventDelivery) -> Self {
+    self.window_event_delivery = delivery;
+    self
+  }
+
   /// Registers a webview event handler for all webviews.
   ///
   /// # Examples
@@ -1790,13 +2504,192 @@ tauri::Builder::default()
     self
   }
 
+  /// Keeps `size` hidden pre-warmed webview windows that can be acquired with [`crate::webview::WebviewPool::acquire`],
+  /// so opening a window only costs a navigation instead of creating a new webview.
+  ///
+  /// The pool is filled after the setup hook runs. Defaults to `0`, disabling the pool.
+  ///
+  /// # Examples
+  /// ```,no_run
+  /// tauri::Builder::default()
+  ///   .webview_pool(2);
+  /// ```
+  #[cfg(desktop)]
+  #[must_use]
+  pub fn webview_pool(mut self, size: usize) -> Self {
+    self.webview_pool_size = size;
+    self
+  }
+
+  /// Displays the splashscreen window described by `config` while the main window loads,
+  /// overriding the `app > splashscreen` configuration.
+  ///
+  /// See [`AppHandle::splash_done`].
+  ///
+  /// # Examples
+  /// ```,no_run
+  /// tauri::Builder::default()
+  ///   .splashscreen(tauri::utils::config::SplashscreenConfig {
+  ///     window: "splashscreen".into(),
+  ///     main: Some("main".into()),
+  ///     auto_close: true,
+  ///     min_duration: 500,
+  ///     fade_duration: 200,
+  ///   });
+  /// ```
+  #[must_use]
+  pub fn splashscreen(mut self, config: SplashscreenConfig) -> Self {
+    self.splashscreen.replace(config);
+    self
+  }
+
+  /// Registers a handler run when the app shuts down, such as flushing a database.
+  ///
+  /// The handlers run once, one after the other in registration order, whichever way the app exits:
+  /// after [`RunEvent::Exit`], on [`AppHandle::exit`], [`AppHandle::restart`] and [`App::cleanup_before_exit`],
+  /// and on desktop when the process is terminated by a signal or the console is closed,
+  /// or when the user logs out or the system shuts down. See [`ShutdownReason`].
+  /// After a termination request the app exits through the event loop, a second request exits immediately.
+  ///
+  /// The shutdown waits for the handlers at most for the deadline set with [`Self::shutdown_deadline`].
+  /// When the app is asked to exit, the handlers run off the event loop thread, which keeps processing events,
+  /// and [`RunEvent::ExitRequested`] is emitted again once they complete.
+  /// [`AppHandle::restart`] and [`App::cleanup_before_exit`] block the calling thread until the handlers complete.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** the system only waits a few seconds for the console close, logout and shutdown events.
+  /// - **macOS:** the logout and shutdown go through [`RunEvent::Exit`].
+  /// - **Android / iOS:** the handlers only run on [`ShutdownReason::Exit`].
+  ///
+  /// # Examples
+  /// ```,no_run
+  /// tauri::Builder::default()
+  ///   .on_shutdown("database", |_app, reason| async move {
+  ///     println!("flushing the database ({reason:?})");
+  ///   })
+  ///   .on_shutdown("logs", |_app, _reason| async move {
+  ///     println!("flushing the logs");
+  ///   });
+  /// ```
+  #[must_use]
+  pub fn on_shutdown<F, Fut>(mut self, name: impl Into<String>, handler: F) -> Self
+  where
+    F: FnOnce(AppHandle<R>, ShutdownReason) -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = ()> + Send + 'static,
+  {
+    self.shutdown_handlers.push((
+      name.into(),
+      Box::new(move |app, reason| Box::pin(handler(app, reason))),
+    ));
+    self
+  }
+
+  /// Sets how long the shutdown waits for the handlers registered with [`Self::on_shutdown`]
+  /// before exiting anyway. Defaults to 5 seconds.
+  ///
+  /// # Examples
+  /// ```,no_run
+  /// tauri::Builder::default()
+  ///   .shutdown_deadline(std::time::Duration::from_secs(2));
+  /// ```
+  #[must_use]
+  pub fn shutdown_deadline(mut self, deadline: std::time::Duration) -> Self {
+    self.shutdown_deadline = deadline;
+    self
+  }
+
+  /// Runs the async tasks of Tauri and of [`crate::async_runtime`] on the given tokio runtime
+  /// instead of a runtime owned by Tauri, e.g. to share the runtime of an embedded server.
+  ///
+  /// The runtime is set when the app is built, see [`crate::async_runtime::try_set`] for the requirements.
+  /// The build fails if the async runtime was already used before, so the tasks never run on two runtimes.
+  ///
+  /// # Examples
+  /// ```,no_run
+  /// let runtime = tokio::runtime::Builder::new_multi_thread()
+  ///   .worker_threads(2)
+  ///   .enable_all()
+  ///   .build()
+  ///   .unwrap();
+  /// tauri::Builder::default()
+  ///   .runtime(runtime.handle().clone());
+  /// ```
+  #[must_use]
+  pub fn runtime(mut self, handle: crate::async_runtime::TokioHandle) -> Self {
+    self.runtime_handle.replace(handle);
+    self
+  }
+
+  /// Installs the given [`tracing::Subscriber`] as the global default subscriber.
+  ///
+  /// The subscriber is installed immediately so it also receives the spans of the builder calls that follow,
+  /// such as [`Self::plugin`] registration, and the `app::build` and `app::setup` spans.
+  /// Tauri records spans for IPC requests and their responses (with the command name and webview label),
+  /// custom protocol requests, event emission and delivery to listeners.
+  ///
+  /// If a global subscriber was already set, this is a no-op and a warning is logged.
+  ///
+  /// # Examples
+  /// ```rust,ignore
+  /// tauri::Builder::default()
+  ///   .with_tracing_subscriber(
+  ///     tracing_subscriber::fmt()
+  ///       .with_max_level(tracing::Level::TRACE)
+  ///       .finish(),
+  ///   );
+  /// ```
+  #[cfg(feature = "tracing")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+  #[must_use]
+  pub fn with_tracing_subscriber<S>(self, subscriber: S) -> Self
+  where
+    S: tracing::Subscriber + Send + Sync + 'static,
+  {
+    if let Err(e) = tracing::subscriber::set_global_default(subscriber) {
+      log::warn!("failed to install the tracing subscriber: {e}");
+    }
+    self
+  }
+
   /// Builds the application.
   #[allow(clippy::type_complexity, unused_mut)]
   #[cfg_attr(
     feature = "tracing",
     tracing::instrument(name = "app::build", skip_all)
   )]
-  pub fn build(mut self, context: Context<R>) -> crate::Result<App<R>> {
+  pub fn build(mut self, mut context: Context<R>) -> crate::Result<App<R>> {
+    if let Some(handle) = self.runtime_handle.take() {
+      crate::async_runtime::try_set(handle)?;
+    }
+
+    if let Some(splashscreen) = self.splashscreen.take() {
+      context.config.app.splashscreen.replace(splashscreen);
+    }
+
+    #[cfg(desktop)]
+    if context
+      .config
+      .app
+      .kiosk
+      .as_ref()
+      .is_some_and(|kiosk| kiosk.fullscreen)
+    {
+      self
+        .window_event_listeners
+        .push(Box::new(kiosk::on_window_event));
+    }
+
+    #[cfg(desktop)]
+    if self.menu.is_none() && context.config.app.menu.is_some() {
+      self.menu = Some(Box::new(|app_handle| {
+        crate::menu::config::build_menu(
+          app_handle,
+          app_handle.config().app.menu.as_deref().unwrap_or_default(),
+        )
+      }));
+    }
+
     #[cfg(target_os = "macos")]
     if self.menu.is_none() && self.enable_macos_default_menu {
       self.menu = Some(Box::new(|app_handle| {
@@ -1804,10 +2697,17 @@ tauri::Builder::default()
       }));
     }
 
-    let manager = Arc::new(AppManager::with_handlers(
+    #[cfg(all(desktop, feature = "frontend-patches"))]
+    let active_patch = {
+      let (assets, active_patch) = crate::frontend_patches::wrap(&context.config, context.assets);
+      context.assets = assets;
+      active_patch
+    };
+
+    let mut manager = AppManager::with_handlers(
       context,
       self.plugins,
-      self.invoke_handler,
+      crate::ipc::handler::compose(self.invoke_handlers),
       self.on_page_load,
       self.uri_scheme_protocols,
       self.state,
@@ -1817,7 +2717,9 @@ tauri::Builder::default()
       HashMap::new(),
       self.invoke_initialization_script,
       self.invoke_key,
-    ));
+    );
+    manager.window.event_delivery = self.window_event_delivery;
+    let manager = Arc::new(manager);
 
     #[cfg(any(
       target_os = "linux",
@@ -1904,8 +2806,12 @@ tauri::Builder::default()
         manager,
       },
       ran_setup: false,
+      lazy_plugins: self.lazy_plugins,
     };
 
+    #[cfg(desktop)]
+    app.manage(crate::menu::config::ConfigMenus::new(self.menu_translator));
+
     #[cfg(desktop)]
     if let Some(menu) = self.menu {
       let menu = menu(&app.handle)?;
@@ -1923,6 +2829,62 @@ tauri::Builder::default()
 
     app.register_core_plugins()?;
 
+    let scheduler = crate::scheduler::Scheduler::new(app.handle.clone());
+    app.manage(scheduler);
+
+    app.manage(crate::persistent_state::PersistentStates::default());
+    app.manage(crate::feature_flags::FeatureFlags::new(app.handle.clone()));
+
+    #[cfg(dev)]
+    crate::panic::install_hook();
+
+    #[cfg(desktop)]
+    if !self.shutdown_handlers.is_empty() {
+      shutdown::listen(app.handle.clone());
+    }
+    app.manage(shutdown::Shutdown::new(
+      self.shutdown_handlers,
+      self.shutdown_deadline,
+    ));
+
+    #[cfg(feature = "diagnostics")]
+    app.manage(crate::diagnostics::Diagnostics::default());
+
+    #[cfg(feature = "localhost-server")]
+    app.manage(crate::localhost::LocalhostServer::new(app.handle.clone())?);
+    #[cfg(feature = "metrics")]
+    app.manage(crate::metrics::Metrics::new(app.handle.clone()));
+    #[cfg(feature = "bluetooth")]
+    app.manage(crate::bluetooth::Bluetooth::<R>::new());
+
+    #[cfg(all(desktop, feature = "frontend-patches"))]
+    {
+      crate::frontend_patches::apply(&app.handle, &active_patch);
+      app.manage(active_patch);
+    }
+
+    app.manage(splashscreen::Splashscreen::new(
+      app.config().app.splashscreen.clone(),
+    ));
+
+    #[cfg(desktop)]
+    if let Some(config) = app.config().app.kiosk.clone() {
+      app.manage(kiosk::Kiosk::new(config));
+    }
+
+    // the webviews are only probed when the fallback is enabled
+    #[cfg(desktop)]
+    if app.config().app.software_rendering_fallback {
+      let gpu = gpu::Gpu::new(&app.handle);
+      app.manage(gpu);
+    }
+
+    #[cfg(desktop)]
+    {
+      let pool = crate::webview::WebviewPool::new(app.handle.clone(), self.webview_pool_size);
+      app.manage(pool);
+    }
+
     let env = Env::default();
     app.manage(env);
 
@@ -1935,6 +2897,12 @@ tauri::Builder::default()
     });
 
     app.manage(ChannelDataIpcQueue::default());
+    #[cfg(feature = "localhost-server")]
+    app.manage(crate::ipc::stream::Streams::default());
+    #[cfg(feature = "auth")]
+    app.manage(crate::auth::AssertionSigner::new()?);
+    #[cfg(feature = "safe-storage")]
+    app.manage(crate::safe_storage::SafeStorageKey::default());
     app.handle.plugin(crate::ipc::channel::plugin())?;
 
     #[cfg(windows)]
@@ -1970,12 +2938,18 @@ tauri::Builder::default()
         if let Some(icon) = &app.manager.tray.icon {
           tray = tray.icon(icon.clone());
         }
+        if let Some((light, dark)) = &app.manager.tray.icon_pair {
+          tray = tray.icon_pair(light.clone(), dark.clone());
+        }
         if let Some(title) = &tray_config.title {
           tray = tray.title(title);
         }
         if let Some(tooltip) = &tray_config.tooltip {
           tray = tray.tooltip(tooltip);
         }
+        if let Some(items) = &tray_config.menu {
+          tray = tray.menu(&crate::menu::config::build_menu(handle, items)?);
+        }
         tray.build(handle)?;
       }
     }
@@ -2061,16 +3035,82 @@ impl<R: Runtime> HasDisplayHandle for App<R> {
 fn setup<R: Runtime>(app: &mut App<R>) -> crate::Result<()> {
   app.ran_setup = true;
 
-  for window_config in app.config().app.windows.iter().filter(|w| w.create) {
-    WebviewWindowBuilder::from_config(app.handle(), window_config)?.build()?;
+  let manager = app.manager.clone();
+
+  #[cfg(desktop)]
+  if let Some(kiosk) = app.try_state::<kiosk::Kiosk>() {
+    kiosk.start(app.handle())?;
+  }
+
+  // the splashscreen is created first so it shows up while the other windows load
+  let splashscreen = app.state::<splashscreen::Splashscreen>();
+  let splashscreen_label = splashscreen.window_label();
+  let mut window_configs = app
+    .config()
+    .app
+    .windows
+    .iter()
+    .filter(|w| w.create)
+    .collect::<Vec<_>>();
+  window_configs.sort_by_key(|w| Some(w.label.as_str()) != splashscreen_label);
+  if let Some(label) = splashscreen_label {
+    if !window_configs.iter().any(|w| w.label == label) {
+      log::warn!("the splashscreen window `{label}` is not configured in `app > windows`");
+    }
+  }
+
+  for window_config in window_configs {
+    manager.startup.measure(
+      || format!("window {}", window_config.label),
+      || WebviewWindowBuilder::from_config(app.handle(), window_config)?.build(),
+    )?;
+  }
+
+  #[cfg(desktop)]
+  if app
+    .try_state::<kiosk::Kiosk>()
+    .is_some_and(|kiosk| kiosk.locks_fullscreen())
+  {
+    for window in manager.windows().values() {
+      window.set_fullscreen(true)?;
+    }
   }
 
   app.manager.assets.setup(app);
 
-  if let Some(setup) = app.setup.take() {
-    (setup)(app).map_err(|e| crate::Error::Setup(e.into()))?;
+  #[cfg(all(desktop, not(target_os = "macos"), feature = "recent-documents"))]
+  if let Some(associations) = &app.config().bundle.file_associations {
+    crate::recent_documents::add_associated_files(associations, std::env::args_os().skip(1));
   }
 
+  if let Some(setup) = app.setup.take() {
+    manager
+      .startup
+      .measure(|| "setup hook".into(), || (setup)(app))
+      .map_err(|e| crate::Error::Setup(e.into()))?;
+  }
+
+  // the closure runs on a later event loop iteration, after the windows had a chance to show up
+  let lazy_plugins = std::mem::take(&mut app.lazy_plugins);
+  let handle = app.handle.clone();
+  app.handle.run_on_main_thread(move || {
+    for mut plugin in lazy_plugins {
+      let mut store = handle.manager.plugins.lock().unwrap();
+      match store.initialize(&mut plugin, &handle, &handle.config().plugins) {
+        Ok(()) => {
+          store.register(plugin);
+        }
+        Err(e) => log::error!("{e}"),
+      }
+    }
+    handle.manager.startup.report();
+
+    #[cfg(desktop)]
+    if let Err(e) = handle.webview_pool().fill() {
+      log::error!("failed to fill the webview pool: {e}");
+    }
+  })?;
+
   Ok(())
 }
 
@@ -2085,6 +3125,10 @@ fn on_event_loop_event<R: Runtime>(
   } = &event
   {
     manager.on_window_close(label);
+    #[cfg(desktop)]
+    if let Some(pool) = app_handle.try_state::<crate::webview::WebviewPool<R>>() {
+      pool.on_window_destroyed();
+    }
   }
 
   let event = match event {
@@ -2161,13 +3205,25 @@ fn on_event_loop_event<R: Runtime>(
             }
           }
         }
+        #[cfg(desktop)]
+        EventLoopMessage::WebviewUnresponsive { .. } => {}
+        #[cfg(desktop)]
+        EventLoopMessage::GpuFailure { .. } => {}
       }
 
       #[allow(unreachable_code)]
       t.into()
     }
     #[cfg(any(target_os = "macos", target_os = "ios"))]
-    RuntimeRunEvent::Opened { urls } => RunEvent::Opened { urls },
+    RuntimeRunEvent::Opened { urls } => {
+      #[cfg(all(target_os = "macos", feature = "recent-documents"))]
+      for path in urls.iter().filter_map(|url| url.to_file_path().ok()) {
+        if let Err(e) = crate::recent_documents::add(&path) {
+          log::error!("{e}");
+        }
+      }
+      RunEvent::Opened { urls }
+    }
     #[cfg(target_os = "macos")]
     RuntimeRunEvent::Reopen {
       has_visible_windows,
@@ -2177,6 +3233,11 @@ fn on_event_loop_event<R: Runtime>(
     _ => unimplemented!(),
   };
 
+  #[cfg(feature = "diagnostics")]
+  if let Some(diagnostics) = app_handle.try_state::<crate::diagnostics::Diagnostics>() {
+    diagnostics.record(&event);
+  }
+
   manager
     .plugins
     .lock()
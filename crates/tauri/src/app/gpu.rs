@@ -0,0 +1,133 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashSet, path::PathBuf, sync::Mutex};
+
+use serde::Deserialize;
+use tauri_runtime::{EventLoopProxy, RuntimeHandle};
+
+use crate::{AppHandle, EventLoopMessage, Manager, Runtime};
+
+/// The file persisting a detected graphics failure in the app cache directory.
+const FAILURE_MARKER: &str = "gpu-failure";
+
+/// A graphics failure detected in a webview, see [`crate::RunEvent::GpuFailure`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum GpuFailure {
+  /// The GPU failed to initialize, so the webview cannot use WebGL.
+  Unavailable,
+  /// The webview renders with a software renderer, usually because the graphics driver is blocklisted.
+  SoftwareRenderer {
+    /// The name of the renderer, such as `SwiftShader` or `llvmpipe`.
+    renderer: String,
+  },
+}
+
+/// The graphics failure detection and software rendering fallback configured in `app > softwareRenderingFallback`.
+pub(crate) struct Gpu {
+  marker: Option<PathBuf>,
+  software_rendering: bool,
+  reported: Mutex<HashSet<String>>,
+}
+
+impl Gpu {
+  pub fn new<R: Runtime>(app: &AppHandle<R>) -> Self {
+    let marker = app
+      .path()
+      .app_cache_dir()
+      .ok()
+      .map(|dir| dir.join(FAILURE_MARKER));
+    let software_rendering = marker.as_ref().is_some_and(|m| m.exists());
+    if software_rendering {
+      log::warn!("a graphics failure was detected on a previous launch, rendering without the GPU");
+    }
+
+    Self {
+      marker,
+      software_rendering,
+      reported: Default::default(),
+    }
+  }
+
+  /// Whether the webviews are created without hardware acceleration because of a previous failure.
+  pub fn software_rendering(&self) -> bool {
+    self.software_rendering
+  }
+
+  /// The script probing the GPU of the webviews, unless they already render without it.
+  pub fn probe_script(&self) -> Option<&'static str> {
+    (!self.software_rendering).then_some(include_str!("./scripts/gpu-probe.js"))
+  }
+
+  /// Reports a failure detected by the webview with the given label, once per webview.
+  pub fn report<R: Runtime>(&self, app: &AppHandle<R>, label: &str, failure: GpuFailure) {
+    if !self.reported.lock().unwrap().insert(label.into()) {
+      return;
+    }
+    log::warn!("graphics failure detected in the webview `{label}`: {failure:?}");
+
+    let fallback = match self.persist(&failure) {
+      Ok(()) => true,
+      Err(e) => {
+        log::error!("failed to persist the graphics failure: {e}");
+        false
+      }
+    };
+
+    let _ = app
+      .runtime_handle
+      .create_proxy()
+      .send_event(EventLoopMessage::GpuFailure {
+        label: label.into(),
+        failure,
+        fallback,
+      });
+  }
+
+  fn persist(&self, failure: &GpuFailure) -> std::io::Result<()> {
+    let Some(marker) = &self.marker else {
+      return Err(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        "unknown app cache directory",
+      ));
+    };
+    if let Some(dir) = marker.parent() {
+      std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(marker, format!("{failure:?}"))
+  }
+
+  /// Forgets the persisted failure so the next launch renders with the GPU again.
+  pub fn reset(&self) -> crate::Result<()> {
+    match &self.marker {
+      Some(marker) if marker.exists() => std::fs::remove_file(marker).map_err(Into::into),
+      _ => Ok(()),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn deserializes_failures() {
+    assert_eq!(
+      serde_json::from_value::<GpuFailure>(serde_json::json!({ "kind": "unavailable" })).unwrap(),
+      GpuFailure::Unavailable
+    );
+    assert_eq!(
+      serde_json::from_value::<GpuFailure>(serde_json::json!({
+        "kind": "softwareRenderer",
+        "renderer": "Google SwiftShader"
+      }))
+      .unwrap(),
+      GpuFailure::SoftwareRenderer {
+        renderer: "Google SwiftShader".into()
+      }
+    );
+  }
+}
@@ -0,0 +1,231 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  collections::HashMap,
+  sync::Mutex,
+  time::{Duration, Instant},
+};
+
+use serialize_to_javascript::{default_template, DefaultTemplate, Template};
+use tauri_runtime::{EventLoopProxy, RuntimeHandle};
+use tauri_utils::config::KioskConfig;
+
+use crate::{AppHandle, EventLoopMessage, Manager, Runtime, Window, WindowEvent};
+
+/// The state of the kiosk mode configured in `app > kiosk`.
+pub(crate) struct Kiosk {
+  config: KioskConfig,
+  heartbeats: Mutex<HashMap<String, Instant>>,
+}
+
+impl Kiosk {
+  pub fn new(config: KioskConfig) -> Self {
+    Self {
+      config,
+      heartbeats: Default::default(),
+    }
+  }
+
+  /// Whether the windows are kept fullscreen.
+  pub fn locks_fullscreen(&self) -> bool {
+    self.config.fullscreen
+  }
+
+  /// The script sending heartbeats to the watchdog, if enabled.
+  pub fn heartbeat_script(&self) -> crate::Result<Option<String>> {
+    if self.config.watchdog_timeout == 0 {
+      return Ok(None);
+    }
+
+    #[derive(Template)]
+    #[default_template("./scripts/heartbeat.js")]
+    struct Heartbeat {
+      interval: u64,
+    }
+
+    Heartbeat {
+      interval: (self.config.watchdog_timeout / 4).max(1),
+    }
+    .render_default(&Default::default())
+    .map(|script| Some(script.into_string()))
+    .map_err(Into::into)
+  }
+
+  /// Records a heartbeat of the webview with the given label.
+  pub fn heartbeat(&self, label: &str) {
+    self
+      .heartbeats
+      .lock()
+      .unwrap()
+      .insert(label.into(), Instant::now());
+  }
+
+  /// Suppresses the OS shortcuts and starts the watchdog. Must be called on the main thread.
+  pub fn start<R: Runtime>(&self, app: &AppHandle<R>) -> crate::Result<()> {
+    if self.config.suppress_shortcuts {
+      platform::suppress_shortcuts()?;
+    }
+
+    if self.config.watchdog_timeout > 0 {
+      let timeout = Duration::from_millis(self.config.watchdog_timeout);
+      let reload = self.config.relaunch_on_crash;
+      let app = app.clone();
+      std::thread::Builder::new()
+        .name("tauri kiosk watchdog".into())
+        .spawn(move || loop {
+          std::thread::sleep(timeout / 2);
+          watch(&app, timeout, reload);
+        })?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Checks the heartbeats of the webviews, reporting and reloading the unresponsive ones.
+fn watch<R: Runtime>(app: &AppHandle<R>, timeout: Duration, reload: bool) {
+  let unresponsive = {
+    let kiosk = app.state::<Kiosk>();
+    let mut heartbeats = kiosk.heartbeats.lock().unwrap();
+    let unresponsive = heartbeats
+      .iter()
+      .filter(|(_, last)| last.elapsed() > timeout)
+      .map(|(label, _)| label.clone())
+      .collect::<Vec<_>>();
+    // watched again once the webview sends a heartbeat, so a page that cannot send them is only reloaded once
+    for label in &unresponsive {
+      heartbeats.remove(label);
+    }
+    unresponsive
+  };
+
+  for label in unresponsive {
+    // the webview was closed
+    let Some(mut webview) = app.manager.get_webview(&label) else {
+      continue;
+    };
+
+    let reloading = reload
+      && match webview.url().and_then(|url| webview.navigate(url)) {
+        Ok(()) => true,
+        Err(e) => {
+          log::error!("failed to reload the unresponsive webview `{label}`: {e}");
+          false
+        }
+      };
+
+    let _ = app
+      .runtime_handle
+      .create_proxy()
+      .send_event(EventLoopMessage::WebviewUnresponsive { label, reloading });
+  }
+}
+
+/// Keeps the windows fullscreen, registered as a global window event listener.
+pub(crate) fn on_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) {
+  if !matches!(event, WindowEvent::Resized(_) | WindowEvent::Focused(_)) {
+    return;
+  }
+  if window.is_minimized().unwrap_or_default() {
+    let _ = window.unminimize();
+  }
+  if !window.is_fullscreen().unwrap_or(true) {
+    let _ = window.set_fullscreen(true);
+  }
+}
+
+#[cfg(all(windows, feature = "kiosk"))]
+mod platform {
+  use windows::Win32::{
+    Foundation::{HINSTANCE, LPARAM, LRESULT, WPARAM},
+    System::Threading::GetCurrentProcessId,
+    UI::{
+      Input::KeyboardAndMouse::{
+        GetAsyncKeyState, VIRTUAL_KEY, VK_CONTROL, VK_ESCAPE, VK_F4, VK_LWIN, VK_RWIN, VK_TAB,
+      },
+      WindowsAndMessaging::{
+        CallNextHookEx, GetForegroundWindow, GetWindowThreadProcessId, SetWindowsHookExW,
+        HC_ACTION, HHOOK, KBDLLHOOKSTRUCT, LLKHF_ALTDOWN, WH_KEYBOARD_LL,
+      },
+    },
+  };
+
+  fn is_foreground() -> bool {
+    let mut process_id = 0;
+    unsafe {
+      GetWindowThreadProcessId(GetForegroundWindow(), Some(&mut process_id));
+      process_id == GetCurrentProcessId()
+    }
+  }
+
+  unsafe extern "system" fn keyboard_hook(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code == HC_ACTION as i32 && is_foreground() {
+      let info = &*(lparam.0 as *const KBDLLHOOKSTRUCT);
+      let key = VIRTUAL_KEY(info.vkCode as u16);
+      let alt = info.flags.0 & LLKHF_ALTDOWN.0 != 0;
+      let ctrl = GetAsyncKeyState(VK_CONTROL.0 as i32) < 0;
+      if key == VK_LWIN
+        || key == VK_RWIN
+        || (alt && (key == VK_TAB || key == VK_ESCAPE || key == VK_F4))
+        || (ctrl && key == VK_ESCAPE)
+      {
+        return LRESULT(1);
+      }
+    }
+    CallNextHookEx(HHOOK::default(), code, wparam, lparam)
+  }
+
+  pub fn suppress_shortcuts() -> std::io::Result<()> {
+    // the hook runs on the main thread message loop for the lifetime of the app
+    unsafe { SetWindowsHookExW(WH_KEYBOARD_LL, Some(keyboard_hook), HINSTANCE::default(), 0) }?;
+    Ok(())
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use objc2::{class, msg_send, runtime::AnyObject};
+
+  const AUTO_HIDE_DOCK: usize = 1 << 0;
+  const AUTO_HIDE_MENU_BAR: usize = 1 << 2;
+  const DISABLE_APPLE_MENU: usize = 1 << 4;
+  const DISABLE_PROCESS_SWITCHING: usize = 1 << 5;
+  const DISABLE_FORCE_QUIT: usize = 1 << 6;
+  const DISABLE_SESSION_TERMINATION: usize = 1 << 7;
+  const DISABLE_HIDE_APPLICATION: usize = 1 << 8;
+
+  pub fn suppress_shortcuts() -> std::io::Result<()> {
+    // the auto hide options are the ones allowed with fullscreen windows
+    let options = AUTO_HIDE_DOCK
+      | AUTO_HIDE_MENU_BAR
+      | DISABLE_APPLE_MENU
+      | DISABLE_PROCESS_SWITCHING
+      | DISABLE_FORCE_QUIT
+      | DISABLE_SESSION_TERMINATION
+      | DISABLE_HIDE_APPLICATION;
+    unsafe {
+      let app: *mut AnyObject = msg_send![class!(NSApplication), sharedApplication];
+      let () = msg_send![app, setPresentationOptions: options];
+    }
+    Ok(())
+  }
+}
+
+#[cfg(all(windows, not(feature = "kiosk")))]
+mod platform {
+  pub fn suppress_shortcuts() -> std::io::Result<()> {
+    Err(std::io::Error::new(
+      std::io::ErrorKind::Unsupported,
+      "suppressing the shortcuts of the kiosk mode on Windows requires the `kiosk` feature",
+    ))
+  }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+mod platform {
+  pub fn suppress_shortcuts() -> std::io::Result<()> {
+    Ok(())
+  }
+}
@@ -57,6 +57,31 @@ pub async fn set_app_theme<R: Runtime>(app: AppHandle<R>, theme: Option<Theme>)
   app.set_theme(theme);
 }
 
+#[command(root = "crate")]
+pub fn splash_done<R: Runtime>(app: AppHandle<R>) {
+  app.splash_done();
+}
+
+#[command(root = "crate")]
+pub fn attributions<R: Runtime>(app: AppHandle<R>) -> Vec<crate::utils::attributions::Attribution> {
+  app.attributions().to_vec()
+}
+
+#[command(root = "crate")]
+pub fn cli_matches<R: Runtime>(app: AppHandle<R>) -> crate::Result<crate::cli::Matches> {
+  app.cli_matches()
+}
+
+#[cfg(feature = "diagnostics")]
+#[command(root = "crate")]
+pub async fn diagnostics_bundle<R: Runtime>(
+  app: AppHandle<R>,
+  path: std::path::PathBuf,
+) -> crate::Result<()> {
+  crate::diagnostics::check_command_destination(&app, &path)?;
+  app.diagnostics_bundle(path)
+}
+
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
   Builder::new("app")
     .invoke_handler(crate::generate_handler![
@@ -67,6 +92,11 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
       app_hide,
       default_window_icon,
       set_app_theme,
+      splash_done,
+      attributions,
+      cli_matches,
+      #[cfg(feature = "diagnostics")]
+      diagnostics_bundle,
     ])
     .build()
 }
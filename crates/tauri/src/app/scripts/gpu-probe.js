@@ -0,0 +1,61 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  // only the top level document reports for the webview
+  if (window.top !== window) return
+
+  const SOFTWARE_RENDERERS =
+    /swiftshader|llvmpipe|softpipe|basic render driver|software/i
+
+  function context(options) {
+    const canvas = document.createElement('canvas')
+    return canvas.getContext('webgl', options)
+  }
+
+  function release(gl) {
+    const extension = gl.getExtension('WEBGL_lose_context')
+    if (extension) extension.loseContext()
+  }
+
+  function rendererOf(gl) {
+    const info = gl.getExtension('WEBGL_debug_renderer_info')
+    return String(
+      gl.getParameter(info ? info.UNMASKED_RENDERER_WEBGL : gl.RENDERER)
+    )
+  }
+
+  function probe() {
+    // fails when only a software renderer is available
+    const hardware = context({ failIfMajorPerformanceCaveat: true })
+    if (hardware) {
+      const renderer = rendererOf(hardware)
+      release(hardware)
+      // not every engine honors failIfMajorPerformanceCaveat
+      return SOFTWARE_RENDERERS.test(renderer)
+        ? { kind: 'softwareRenderer', renderer }
+        : null
+    }
+
+    const software = context()
+    if (!software) return { kind: 'unavailable' }
+    const renderer = rendererOf(software)
+    release(software)
+    return { kind: 'softwareRenderer', renderer }
+  }
+
+  let failure
+  try {
+    failure = probe()
+  } catch (e) {
+    failure = { kind: 'unavailable' }
+  }
+
+  if (failure) {
+    window.__TAURI_INTERNALS__
+      .invoke('plugin:webview|internal_gpu_failure', { failure })
+      // webviews without access to the command do not report
+      .catch(() => {})
+  }
+})()
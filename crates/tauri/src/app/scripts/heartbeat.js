@@ -0,0 +1,20 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  // only the top level document reports for the webview
+  if (window.top !== window) return
+
+  const interval = __TEMPLATE_interval__
+
+  function heartbeat() {
+    window.__TAURI_INTERNALS__
+      .invoke('plugin:webview|internal_kiosk_heartbeat')
+      // webviews without access to the command are not watched
+      .catch(() => {})
+  }
+
+  heartbeat()
+  setInterval(heartbeat, interval)
+})()
@@ -0,0 +1,418 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  future::Future,
+  pin::Pin,
+  sync::{
+    mpsc::{channel, Receiver, RecvTimeoutError},
+    Arc, Mutex, OnceLock,
+  },
+  time::Duration,
+};
+
+use crate::{AppHandle, Runtime};
+
+/// The default time the shutdown handlers have to complete.
+pub(crate) const DEFAULT_DEADLINE: Duration = Duration::from_secs(5);
+
+pub(crate) type ShutdownHandler<R> =
+  Box<dyn FnOnce(AppHandle<R>, ShutdownReason) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send>;
+
+/// Runs the shutdown sequence when the operating system asks the process to stop,
+/// then exits the app with the given exit code.
+#[cfg(desktop)]
+static LISTENER: OnceLock<Box<dyn Fn(ShutdownReason, i32) + Send + Sync>> = OnceLock::new();
+
+/// Why the shutdown handlers registered with [`crate::Builder::on_shutdown`] run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ShutdownReason {
+  /// The app exits normally, after [`crate::RunEvent::Exit`] or a call to [`AppHandle::exit`],
+  /// [`AppHandle::restart`] or [`crate::App::cleanup_before_exit`].
+  Exit,
+  /// The process was asked to terminate, with `SIGTERM` or `SIGINT` on Unix and Ctrl+C or Ctrl+Break on Windows.
+  Terminate,
+  /// The terminal running the app was closed, with `SIGHUP` on Unix and the console close event on Windows.
+  ConsoleClose,
+  /// The user is logging out or the system is shutting down.
+  SessionEnd,
+}
+
+/// The shutdown handlers registered with [`crate::Builder::on_shutdown`].
+pub(crate) struct Shutdown<R: Runtime> {
+  handlers: Mutex<Option<Vec<(String, ShutdownHandler<R>)>>>,
+  deadline: Duration,
+}
+
+impl<R: Runtime> Shutdown<R> {
+  pub fn new(handlers: Vec<(String, ShutdownHandler<R>)>, deadline: Duration) -> Self {
+    Self {
+      handlers: Mutex::new(Some(handlers)),
+      deadline,
+    }
+  }
+
+  /// Runs the handlers in registration order, once per process, and waits for them until the deadline.
+  pub fn run(&self, app: &AppHandle<R>, reason: ShutdownReason) {
+    if let Some(running) = self.start(app, reason) {
+      running.wait();
+    }
+  }
+
+  /// Starts the handlers without blocking the calling thread and exits the app with the given code
+  /// once they complete or the deadline passes.
+  ///
+  /// Returns `false` if the handlers already ran, in which case the app can exit right away.
+  pub fn run_then_exit(&self, app: &AppHandle<R>, reason: ShutdownReason, exit_code: i32) -> bool {
+    let Some(running) = self.start(app, reason) else {
+      return false;
+    };
+    let app = app.clone();
+    let spawned = std::thread::Builder::new()
+      .name("tauri-shutdown-deadline".into())
+      .spawn(move || {
+        running.wait();
+        app.exit(exit_code);
+      });
+    if let Err(e) = spawned {
+      log::error!("failed to wait for the shutdown handlers: {e}");
+      return false;
+    }
+    true
+  }
+
+  /// Runs the handlers on a dedicated thread, which lets them block and the sequence start from an async context.
+  fn start(&self, app: &AppHandle<R>, reason: ShutdownReason) -> Option<Running> {
+    let handlers = self.handlers.lock().unwrap().take()?;
+    if handlers.is_empty() {
+      return None;
+    }
+
+    let current = Arc::new(Mutex::new(String::new()));
+    let (tx, rx) = channel();
+    let app = app.clone();
+    let current_ = current.clone();
+    let spawned = std::thread::Builder::new()
+      .name("tauri-shutdown".into())
+      .spawn(move || {
+        for (name, handler) in handlers {
+          *current_.lock().unwrap() = name;
+          crate::async_runtime::block_on(handler(app.clone(), reason));
+        }
+        let _ = tx.send(());
+      });
+    if let Err(e) = spawned {
+      log::error!("failed to run the shutdown handlers: {e}");
+      return None;
+    }
+
+    Some(Running {
+      rx,
+      current,
+      deadline: self.deadline,
+    })
+  }
+}
+
+/// The shutdown handlers running on their dedicated thread.
+struct Running {
+  rx: Receiver<()>,
+  current: Arc<Mutex<String>>,
+  deadline: Duration,
+}
+
+impl Running {
+  /// Waits for the handlers until the deadline.
+  fn wait(self) {
+    match self.rx.recv_timeout(self.deadline) {
+      Ok(()) => (),
+      Err(RecvTimeoutError::Timeout) => log::error!(
+        "the shutdown handler `{}` did not complete within {:?}, exiting anyway",
+        self.current.lock().unwrap(),
+        self.deadline
+      ),
+      Err(RecvTimeoutError::Disconnected) => log::error!(
+        "the shutdown handler `{}` panicked, skipping the next handlers",
+        self.current.lock().unwrap()
+      ),
+    }
+  }
+}
+
+/// Runs the shutdown sequence on the termination requests of the operating system,
+/// which otherwise end the process without going through [`crate::RunEvent::Exit`].
+#[cfg(desktop)]
+pub(crate) fn listen<R: Runtime>(app: AppHandle<R>) {
+  use crate::Manager;
+
+  let listener = Box::new(move |reason, exit_code| {
+    if let Some(shutdown) = app.try_state::<Shutdown<R>>() {
+      shutdown.run(&app, reason);
+    }
+    // the event loop exits through the regular exit sequence, falling back to exiting the process
+    app.exit(exit_code);
+  });
+  // the signal handlers are process wide, only the first app listens to them
+  if LISTENER.set(listener).is_err() {
+    return;
+  }
+  if let Err(e) = platform::listen() {
+    log::error!("failed to listen to the termination requests: {e}");
+  }
+}
+
+#[cfg(desktop)]
+fn on_termination_request(reason: ShutdownReason, exit_code: i32) {
+  if let Some(listener) = LISTENER.get() {
+    listener(reason, exit_code);
+  }
+}
+
+#[cfg(all(windows, feature = "shutdown-signals"))]
+mod platform {
+  use windows::{
+    core::{w, PCWSTR},
+    Win32::{
+      Foundation::{
+        BOOL, FALSE, HINSTANCE, HWND, LPARAM, LRESULT, STATUS_CONTROL_C_EXIT, TRUE, WPARAM,
+      },
+      System::{
+        Console::{
+          SetConsoleCtrlHandler, CTRL_BREAK_EVENT, CTRL_CLOSE_EVENT, CTRL_C_EVENT,
+          CTRL_LOGOFF_EVENT, CTRL_SHUTDOWN_EVENT,
+        },
+        LibraryLoader::GetModuleHandleW,
+      },
+      UI::WindowsAndMessaging::{
+        CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW,
+        TranslateMessage, HMENU, MSG, WINDOW_EX_STYLE, WM_ENDSESSION, WM_QUERYENDSESSION,
+        WNDCLASSW, WS_OVERLAPPED,
+      },
+    },
+  };
+
+  use super::ShutdownReason;
+
+  const CLASS_NAME: PCWSTR = w!("TAURI_SHUTDOWN_LISTENER");
+
+  unsafe extern "system" fn console_handler(event: u32) -> BOOL {
+    let reason = match event {
+      CTRL_C_EVENT | CTRL_BREAK_EVENT => ShutdownReason::Terminate,
+      CTRL_CLOSE_EVENT => ShutdownReason::ConsoleClose,
+      CTRL_LOGOFF_EVENT | CTRL_SHUTDOWN_EVENT => ShutdownReason::SessionEnd,
+      _ => return FALSE,
+    };
+    super::on_termination_request(reason, STATUS_CONTROL_C_EXIT.0);
+    // the process is killed once the handler returns, before the event loop exits
+    std::process::exit(STATUS_CONTROL_C_EXIT.0)
+  }
+
+  unsafe extern "system" fn window_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+  ) -> LRESULT {
+    match msg {
+      WM_QUERYENDSESSION => LRESULT(1),
+      // the process is terminated once the top level windows return from this message
+      WM_ENDSESSION if wparam.0 != 0 => {
+        super::on_termination_request(ShutdownReason::SessionEnd, 0);
+        LRESULT(0)
+      }
+      _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+  }
+
+  pub fn listen() -> std::io::Result<()> {
+    unsafe { SetConsoleCtrlHandler(Some(console_handler), TRUE) }?;
+
+    // GUI processes are not notified of the session end through the console handler,
+    // only their hidden top level windows receive WM_ENDSESSION
+    std::thread::Builder::new()
+      .name("tauri-shutdown-listener".into())
+      .spawn(|| unsafe {
+        let instance = GetModuleHandleW(PCWSTR::null()).unwrap_or_default();
+        let class = WNDCLASSW {
+          lpfnWndProc: Some(window_proc),
+          hInstance: HINSTANCE(instance.0),
+          lpszClassName: CLASS_NAME,
+          ..Default::default()
+        };
+        RegisterClassW(&class);
+
+        if let Err(e) = CreateWindowExW(
+          WINDOW_EX_STYLE::default(),
+          CLASS_NAME,
+          CLASS_NAME,
+          WS_OVERLAPPED,
+          0,
+          0,
+          0,
+          0,
+          HWND::default(),
+          HMENU::default(),
+          instance,
+          None,
+        ) {
+          log::error!("failed to listen to the session end: {e}");
+          return;
+        }
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, HWND::default(), 0, 0).as_bool() {
+          let _ = TranslateMessage(&msg);
+          DispatchMessageW(&msg);
+        }
+      })?;
+
+    Ok(())
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd",
+  target_os = "macos"
+))]
+mod platform {
+  use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+
+  use super::ShutdownReason;
+
+  const SIGNALS: &[libc::c_int] = &[libc::SIGINT, libc::SIGTERM, libc::SIGHUP];
+
+  /// The write end of the pipe forwarding the signals to the listener thread.
+  static PIPE: AtomicI32 = AtomicI32::new(-1);
+  static RECEIVED: AtomicBool = AtomicBool::new(false);
+
+  extern "C" fn on_signal(signal: libc::c_int) {
+    // only async-signal-safe functions can be called here
+    unsafe {
+      // a second signal skips the remaining handlers
+      if RECEIVED.swap(true, Ordering::SeqCst) {
+        libc::_exit(128 + signal);
+      }
+      let byte = signal as u8;
+      libc::write(
+        PIPE.load(Ordering::SeqCst),
+        &byte as *const u8 as *const libc::c_void,
+        1,
+      );
+    }
+  }
+
+  pub fn listen() -> std::io::Result<()> {
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+      return Err(std::io::Error::last_os_error());
+    }
+    let [read, write] = fds;
+    PIPE.store(write, Ordering::SeqCst);
+
+    std::thread::Builder::new()
+      .name("tauri-shutdown-listener".into())
+      .spawn(move || {
+        let mut byte = 0u8;
+        if unsafe { libc::read(read, &mut byte as *mut u8 as *mut libc::c_void, 1) } != 1 {
+          return;
+        }
+        let signal = byte as libc::c_int;
+        let reason = if signal == libc::SIGHUP {
+          ShutdownReason::ConsoleClose
+        } else {
+          ShutdownReason::Terminate
+        };
+        super::on_termination_request(reason, 128 + signal);
+      })?;
+
+    for signal in SIGNALS {
+      unsafe { libc::signal(*signal, on_signal as libc::sighandler_t) };
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(all(
+  desktop,
+  not(any(
+    all(windows, feature = "shutdown-signals"),
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos"
+  ))
+))]
+mod platform {
+  pub fn listen() -> std::io::Result<()> {
+    #[cfg(windows)]
+    log::warn!(
+      "the shutdown handlers do not run on console close and session end without the `shutdown-signals` feature"
+    );
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  use super::*;
+  use crate::test::{mock_app, MockRuntime};
+
+  #[test]
+  fn runs_handlers_once_in_order() {
+    let app = mock_app();
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let handler = |name: &'static str| -> (String, ShutdownHandler<MockRuntime>) {
+      let calls = calls.clone();
+      (
+        name.into(),
+        Box::new(move |_app, reason| {
+          Box::pin(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            calls.lock().unwrap().push((name, reason));
+          })
+        }),
+      )
+    };
+    let shutdown = Shutdown::new(vec![handler("database"), handler("logs")], DEFAULT_DEADLINE);
+
+    shutdown.run(app.handle(), ShutdownReason::Terminate);
+    shutdown.run(app.handle(), ShutdownReason::Exit);
+    assert_eq!(
+      *calls.lock().unwrap(),
+      vec![
+        ("database", ShutdownReason::Terminate),
+        ("logs", ShutdownReason::Terminate)
+      ]
+    );
+  }
+
+  #[test]
+  fn stops_waiting_at_the_deadline() {
+    let app = mock_app();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls_ = calls.clone();
+    let slow: ShutdownHandler<MockRuntime> = Box::new(move |_app, _reason| {
+      Box::pin(async move {
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        calls_.fetch_add(1, Ordering::SeqCst);
+      })
+    });
+    let shutdown = Shutdown::new(vec![("slow".into(), slow)], Duration::from_millis(50));
+
+    let started = std::time::Instant::now();
+    shutdown.run(app.handle(), ShutdownReason::Exit);
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+  }
+}
@@ -0,0 +1,107 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  sync::atomic::{AtomicBool, Ordering},
+  time::{Duration, Instant},
+};
+
+use tauri_utils::config::SplashscreenConfig;
+
+use crate::{AppHandle, Manager, Runtime};
+
+/// The state of the splashscreen configured in `app > splashscreen`.
+pub(crate) struct Splashscreen {
+  config: Option<SplashscreenConfig>,
+  started: Instant,
+  done: AtomicBool,
+}
+
+impl Splashscreen {
+  pub fn new(config: Option<SplashscreenConfig>) -> Self {
+    Self {
+      config,
+      started: Instant::now(),
+      done: AtomicBool::new(false),
+    }
+  }
+
+  /// The label of the splashscreen window, if configured.
+  pub fn window_label(&self) -> Option<&str> {
+    self.config.as_ref().map(|c| c.window.as_str())
+  }
+
+  /// Whether the splashscreen closes when the webview with the given label finished loading.
+  pub fn closes_on_load(&self, label: &str) -> bool {
+    self
+      .config
+      .as_ref()
+      .is_some_and(|c| c.auto_close && c.main.as_deref() == Some(label))
+  }
+
+  /// Closes the splashscreen and shows the main window once the minimum display time elapsed.
+  ///
+  /// Only the first call has an effect.
+  pub fn done<R: Runtime>(&self, app: &AppHandle<R>) {
+    let Some(config) = self.config.clone() else {
+      return;
+    };
+    if self.done.swap(true, Ordering::SeqCst) {
+      return;
+    }
+
+    let remaining =
+      Duration::from_millis(config.min_duration).saturating_sub(self.started.elapsed());
+    // the page fades out, so the window background must be transparent for the window to fade
+    let transparent = app
+      .config()
+      .app
+      .windows
+      .iter()
+      .any(|w| w.label == config.window && w.transparent);
+    let fade = if config.fade_duration > 0 && !transparent {
+      log::warn!(
+        "the splashscreen window `{}` must be `transparent` to fade out, closing it without the fade",
+        config.window
+      );
+      Duration::ZERO
+    } else {
+      Duration::from_millis(config.fade_duration)
+    };
+    let app = app.clone();
+    crate::async_runtime::spawn(async move {
+      tokio::time::sleep(remaining).await;
+
+      let main = config
+        .main
+        .as_deref()
+        .and_then(|label| app.get_webview_window(label));
+      let splash = app.get_webview_window(&config.window);
+
+      // the main window is shown behind the splashscreen, whose page fades out on top of it
+      if let Some(main) = &main {
+        if let Err(e) = main.show() {
+          log::error!("failed to show the main window: {e}");
+        }
+      }
+      if let Some(splash) = &splash {
+        if !fade.is_zero() {
+          let script = format!(
+            "document.documentElement.style.transition = 'opacity {}ms ease-out'; document.documentElement.style.opacity = '0';",
+            fade.as_millis()
+          );
+          let _ = splash.eval(&script);
+          tokio::time::sleep(fade).await;
+        }
+        // destroy so close handlers keeping windows alive do not apply to the splashscreen
+        if let Err(e) = splash.destroy() {
+          log::error!("failed to close the splashscreen: {e}");
+        }
+      }
+      if let Some(main) = &main {
+        let _ = main.set_focus();
+      }
+    });
+  }
+}
@@ -0,0 +1,545 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Zip and tar.gz archive extraction and creation.
+//!
+//! The archives are processed safely for untrusted content such as downloads:
+//! the entries escaping the destination directory and the links are rejected,
+//! and every file read or written can be checked against a [`Scope`] with [`ArchiveOptions::scope`].
+//!
+//! The progress is emitted to all targets as the [`ARCHIVE_PROGRESS_EVENT`] event,
+//! so it can be displayed by the frontend.
+
+use std::{
+  fs::{self, File},
+  io::{self, BufReader, BufWriter, Read, Write},
+  marker::PhantomData,
+  path::{Component, Path, PathBuf},
+  time::{Duration, Instant},
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::Serialize;
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::{scope::fs::Scope, Emitter, Runtime};
+
+/// Event emitted with an [`ArchiveProgress`] payload while an archive is extracted or created.
+pub const ARCHIVE_PROGRESS_EVENT: &str = "tauri://archive-progress";
+
+/// Minimum interval between two [`ARCHIVE_PROGRESS_EVENT`] events of the same operation.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Archive errors.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+  /// The format could not be determined from the file extension, see [`ArchiveOptions::format`].
+  #[error("unknown archive format of {0}")]
+  UnknownFormat(PathBuf),
+  /// The path is not allowed by the [`ArchiveOptions::scope`].
+  #[error("path not allowed on the configured scope: {0}")]
+  Forbidden(PathBuf),
+  /// The archive entry escapes the destination directory or is a link.
+  #[error("unsafe archive entry `{0}`")]
+  UnsafeEntry(String),
+}
+
+/// The format of an archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ArchiveFormat {
+  /// A zip archive, compressed with deflate when created.
+  Zip,
+  /// A gzip compressed tar archive.
+  TarGz,
+}
+
+impl ArchiveFormat {
+  /// The format matching the extension of the path: `.zip`, `.tar.gz` or `.tgz`.
+  pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+    let name = path.as_ref().file_name()?.to_str()?.to_lowercase();
+    if name.ends_with(".zip") {
+      Some(Self::Zip)
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+      Some(Self::TarGz)
+    } else {
+      None
+    }
+  }
+}
+
+/// The options of [`extract`] and [`create`].
+#[derive(Debug, Clone, Default)]
+pub struct ArchiveOptions {
+  format: Option<ArchiveFormat>,
+  scope: Option<Scope>,
+}
+
+impl ArchiveOptions {
+  /// The format of the archive. Defaults to the format matching the archive file extension.
+  #[must_use]
+  pub fn format(mut self, format: ArchiveFormat) -> Self {
+    self.format.replace(format);
+    self
+  }
+
+  /// Only reads and writes the files allowed by the scope, including the archive itself,
+  /// e.g. the scope of the file system plugin.
+  #[must_use]
+  pub fn scope(mut self, scope: Scope) -> Self {
+    self.scope.replace(scope);
+    self
+  }
+
+  fn format_of(&self, archive: &Path) -> Result<ArchiveFormat, Error> {
+    self
+      .format
+      .or_else(|| ArchiveFormat::from_path(archive))
+      .ok_or_else(|| Error::UnknownFormat(archive.to_path_buf()))
+  }
+
+  fn check(&self, path: &Path) -> Result<(), Error> {
+    match &self.scope {
+      Some(scope) if !scope.is_allowed(path) => Err(Error::Forbidden(path.to_path_buf())),
+      _ => Ok(()),
+    }
+  }
+}
+
+/// The progress of an archive extraction or creation.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ArchiveProgress {
+  /// The path of the archive.
+  pub archive: PathBuf,
+  /// The number of entries processed.
+  pub entries: u64,
+  /// The number of entries of the archive, when known in advance.
+  pub total_entries: Option<u64>,
+  /// The number of uncompressed bytes processed.
+  pub bytes: u64,
+  /// Whether the operation completed.
+  pub done: bool,
+}
+
+struct Reporter<'a, R: Runtime, E: Emitter<R>> {
+  emitter: &'a E,
+  progress: ArchiveProgress,
+  last_emit: Option<Instant>,
+  _runtime: PhantomData<R>,
+}
+
+impl<'a, R: Runtime, E: Emitter<R>> Reporter<'a, R, E> {
+  fn new(emitter: &'a E, archive: &Path, total_entries: Option<u64>) -> Self {
+    Self {
+      emitter,
+      progress: ArchiveProgress {
+        archive: archive.to_path_buf(),
+        entries: 0,
+        total_entries,
+        bytes: 0,
+        done: false,
+      },
+      last_emit: None,
+      _runtime: PhantomData,
+    }
+  }
+
+  fn entry(&mut self, bytes: u64) {
+    self.progress.entries += 1;
+    self.progress.bytes += bytes;
+    if self
+      .last_emit
+      .map_or(true, |last| last.elapsed() >= PROGRESS_INTERVAL)
+    {
+      self.emit();
+    }
+  }
+
+  fn finish(mut self) {
+    self.progress.done = true;
+    self.emit();
+  }
+
+  fn emit(&mut self) {
+    self.last_emit.replace(Instant::now());
+    if let Err(e) = self.emitter.emit(ARCHIVE_PROGRESS_EVENT, &self.progress) {
+      log::error!("failed to emit archive progress: {e}");
+    }
+  }
+}
+
+/// The relative path of an entry, `None` if it is absolute or has `..` components escaping the destination.
+fn enclosed(name: &str) -> Option<PathBuf> {
+  if name.contains('\0') {
+    return None;
+  }
+  let mut path = PathBuf::new();
+  for component in Path::new(name).components() {
+    match component {
+      Component::Normal(component) => path.push(component),
+      Component::CurDir => {}
+      Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+    }
+  }
+  Some(path)
+}
+
+/// Extracts the archive to the destination directory, creating it if needed.
+///
+/// Fails without writing the remaining entries if an entry escapes the destination, is a link
+/// or is not allowed by the [`ArchiveOptions::scope`]. The files extracted before are not removed.
+///
+/// This function blocks until the archive is extracted, so it must not be called on the main thread.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri::{archive::ArchiveOptions, Manager};
+///
+/// #[tauri::command]
+/// async fn install_assets(app: tauri::AppHandle, archive: std::path::PathBuf) -> tauri::Result<()> {
+///   let destination = app.path().app_data_dir()?.join("assets");
+///   tauri::async_runtime::spawn_blocking(move || {
+///     tauri::archive::extract(&app, archive, destination, ArchiveOptions::default())
+///   })
+///   .await?
+/// }
+/// ```
+pub fn extract<R: Runtime, E: Emitter<R>, P: AsRef<Path>, D: AsRef<Path>>(
+  emitter: &E,
+  archive: P,
+  destination: D,
+  options: ArchiveOptions,
+) -> crate::Result<()> {
+  let archive = archive.as_ref();
+  let destination = destination.as_ref();
+  let format = options.format_of(archive)?;
+  options.check(archive)?;
+  options.check(destination)?;
+  fs::create_dir_all(destination)?;
+  let destination = dunce::canonicalize(destination)?;
+
+  let file = File::open(archive)?;
+  match format {
+    ArchiveFormat::Zip => extract_zip(emitter, archive, file, &destination, &options),
+    ArchiveFormat::TarGz => extract_tar_gz(emitter, archive, file, &destination, &options),
+  }
+}
+
+/// Creates the directories of an entry and checks them against the scope.
+fn prepare_entry(
+  destination: &Path,
+  relative: &Path,
+  options: &ArchiveOptions,
+) -> crate::Result<PathBuf> {
+  let path = destination.join(relative);
+  options.check(&path)?;
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent)?;
+  }
+  Ok(path)
+}
+
+fn write_entry(path: &Path, reader: &mut impl Read) -> crate::Result<u64> {
+  let mut writer = BufWriter::new(File::create(path)?);
+  let bytes = io::copy(reader, &mut writer)?;
+  writer.flush()?;
+  Ok(bytes)
+}
+
+fn extract_zip<R: Runtime, E: Emitter<R>>(
+  emitter: &E,
+  archive: &Path,
+  file: File,
+  destination: &Path,
+  options: &ArchiveOptions,
+) -> crate::Result<()> {
+  let mut zip = ZipArchive::new(BufReader::new(file))?;
+  let mut reporter = Reporter::new(emitter, archive, Some(zip.len() as u64));
+  for i in 0..zip.len() {
+    let mut entry = zip.by_index(i)?;
+    let relative = enclosed(entry.name())
+      // the symbolic links are stored as files with the target as content
+      .filter(|_| !entry.is_symlink())
+      .ok_or_else(|| Error::UnsafeEntry(entry.name().into()))?;
+    let path = prepare_entry(destination, &relative, options)?;
+    let bytes = if entry.is_dir() {
+      fs::create_dir_all(&path)?;
+      0
+    } else {
+      write_entry(&path, &mut entry)?
+    };
+    reporter.entry(bytes);
+  }
+  reporter.finish();
+  Ok(())
+}
+
+fn extract_tar_gz<R: Runtime, E: Emitter<R>>(
+  emitter: &E,
+  archive: &Path,
+  file: File,
+  destination: &Path,
+  options: &ArchiveOptions,
+) -> crate::Result<()> {
+  let mut tar = tar::Archive::new(GzDecoder::new(BufReader::new(file)));
+  let mut reporter = Reporter::new(emitter, archive, None);
+  for entry in tar.entries()? {
+    let mut entry = entry?;
+    let name = String::from_utf8_lossy(&entry.path_bytes()).into_owned();
+    let entry_type = entry.header().entry_type();
+    let relative = enclosed(&name)
+      .filter(|_| entry_type.is_file() || entry_type.is_dir())
+      .ok_or(Error::UnsafeEntry(name))?;
+    let path = prepare_entry(destination, &relative, options)?;
+    let bytes = if entry_type.is_dir() {
+      fs::create_dir_all(&path)?;
+      0
+    } else {
+      write_entry(&path, &mut entry)?
+    };
+    reporter.entry(bytes);
+  }
+  reporter.finish();
+  Ok(())
+}
+
+/// Lists the files and directories of the source directory with their archive names,
+/// the directories before their content. The links are skipped.
+fn collect_entries(
+  source: &Path,
+  relative: &Path,
+  entries: &mut Vec<(PathBuf, PathBuf)>,
+) -> io::Result<()> {
+  let mut children = fs::read_dir(source.join(relative))?.collect::<io::Result<Vec<_>>>()?;
+  children.sort_by_key(|entry| entry.file_name());
+  for child in children {
+    let file_type = child.file_type()?;
+    let name = relative.join(child.file_name());
+    if file_type.is_dir() {
+      entries.push((child.path(), name.clone()));
+      collect_entries(source, &name, entries)?;
+    } else if file_type.is_file() {
+      entries.push((child.path(), name));
+    }
+  }
+  Ok(())
+}
+
+/// The archive name of the entry, with `/` separators on all platforms.
+fn entry_name(relative: &Path) -> String {
+  relative
+    .components()
+    .map(|component| component.as_os_str().to_string_lossy())
+    .collect::<Vec<_>>()
+    .join("/")
+}
+
+/// Creates an archive from the content of the source directory, or from a single file.
+///
+/// The links of the source directory are skipped.
+/// This function blocks until the archive is written, so it must not be called on the main thread.
+pub fn create<R: Runtime, E: Emitter<R>, S: AsRef<Path>, P: AsRef<Path>>(
+  emitter: &E,
+  source: S,
+  archive: P,
+  options: ArchiveOptions,
+) -> crate::Result<()> {
+  let source = source.as_ref();
+  let archive = archive.as_ref();
+  let format = options.format_of(archive)?;
+  options.check(archive)?;
+
+  let mut entries = Vec::new();
+  if source.is_dir() {
+    collect_entries(source, Path::new(""), &mut entries)?;
+  } else {
+    let name = source
+      .file_name()
+      .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid source path"))?;
+    entries.push((source.to_path_buf(), PathBuf::from(name)));
+  }
+  for (path, _) in &entries {
+    options.check(path)?;
+  }
+
+  let file = BufWriter::new(File::create(archive)?);
+  let mut reporter = Reporter::new(emitter, archive, Some(entries.len() as u64));
+  match format {
+    ArchiveFormat::Zip => {
+      let mut zip = ZipWriter::new(file);
+      let file_options =
+        SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+      for (path, relative) in entries {
+        let name = entry_name(&relative);
+        let bytes = if path.is_dir() {
+          zip.add_directory(name, file_options)?;
+          0
+        } else {
+          zip.start_file(name, file_options)?;
+          io::copy(&mut File::open(&path)?, &mut zip)?
+        };
+        reporter.entry(bytes);
+      }
+      zip.finish()?.flush()?;
+    }
+    ArchiveFormat::TarGz => {
+      let mut tar = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+      for (path, relative) in entries {
+        let name = entry_name(&relative);
+        let bytes = if path.is_dir() {
+          tar.append_dir(name, &path)?;
+          0
+        } else {
+          let mut file = File::open(&path)?;
+          let len = file.metadata()?.len();
+          tar.append_file(name, &mut file)?;
+          len
+        };
+        reporter.entry(bytes);
+      }
+      tar.into_inner()?.finish()?.flush()?;
+    }
+  }
+  reporter.finish();
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test::{mock_app, MockRuntime};
+
+  #[test]
+  fn detects_format() {
+    assert_eq!(
+      ArchiveFormat::from_path("assets.ZIP"),
+      Some(ArchiveFormat::Zip)
+    );
+    assert_eq!(
+      ArchiveFormat::from_path("/tmp/assets.tar.gz"),
+      Some(ArchiveFormat::TarGz)
+    );
+    assert_eq!(
+      ArchiveFormat::from_path("assets.tgz"),
+      Some(ArchiveFormat::TarGz)
+    );
+    assert_eq!(ArchiveFormat::from_path("assets.tar"), None);
+  }
+
+  #[test]
+  fn rejects_escaping_entries() {
+    assert_eq!(enclosed("a/./b.txt"), Some(PathBuf::from("a/b.txt")));
+    assert_eq!(enclosed("../evil.txt"), None);
+    assert_eq!(enclosed("a/../../evil.txt"), None);
+    assert_eq!(enclosed("/etc/passwd"), None);
+    assert_eq!(enclosed("a\0b"), None);
+  }
+
+  fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("tauri-archive-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+  }
+
+  fn round_trip(format: ArchiveFormat, extension: &str) {
+    let app = mock_app();
+    let dir = temp_dir(extension);
+    let source = dir.join("source");
+    fs::create_dir_all(source.join("nested")).unwrap();
+    fs::write(source.join("a.txt"), "a").unwrap();
+    fs::write(source.join("nested").join("b.txt"), "bb").unwrap();
+
+    let archive = dir.join(format!("archive.{extension}"));
+    create::<MockRuntime, _, _, _>(app.handle(), &source, &archive, ArchiveOptions::default())
+      .unwrap();
+    assert_eq!(ArchiveFormat::from_path(&archive), Some(format));
+
+    let destination = dir.join("destination");
+    extract::<MockRuntime, _, _, _>(
+      app.handle(),
+      &archive,
+      &destination,
+      ArchiveOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(fs::read_to_string(destination.join("a.txt")).unwrap(), "a");
+    assert_eq!(
+      fs::read_to_string(destination.join("nested").join("b.txt")).unwrap(),
+      "bb"
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+  }
+
+  #[test]
+  fn zip_round_trip() {
+    round_trip(ArchiveFormat::Zip, "zip");
+  }
+
+  #[test]
+  fn tar_gz_round_trip() {
+    round_trip(ArchiveFormat::TarGz, "tar.gz");
+  }
+
+  #[test]
+  fn rejects_zip_slip() {
+    let app = mock_app();
+    let dir = temp_dir("zip-slip");
+    let archive = dir.join("evil.zip");
+    let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+    zip
+      .start_file("../evil.txt", SimpleFileOptions::default())
+      .unwrap();
+    zip.write_all(b"evil").unwrap();
+    zip.finish().unwrap();
+
+    let result = extract::<MockRuntime, _, _, _>(
+      app.handle(),
+      &archive,
+      dir.join("destination"),
+      ArchiveOptions::default(),
+    );
+    assert!(matches!(
+      result,
+      Err(crate::Error::Archive(Error::UnsafeEntry(_)))
+    ));
+    assert!(!dir.join("evil.txt").exists());
+
+    fs::remove_dir_all(dir).unwrap();
+  }
+
+  #[test]
+  fn enforces_scope() {
+    let app = mock_app();
+    let dir = temp_dir("scope");
+    let source = dir.join("source.txt");
+    fs::write(&source, "source").unwrap();
+    let archive = dir.join("archive.zip");
+    create::<MockRuntime, _, _, _>(app.handle(), &source, &archive, ArchiveOptions::default())
+      .unwrap();
+
+    let scope = Scope::new(
+      app.handle(),
+      &tauri_utils::config::FsScope::AllowedPaths(Vec::new()),
+    )
+    .unwrap();
+    let result = extract::<MockRuntime, _, _, _>(
+      app.handle(),
+      &archive,
+      dir.join("destination"),
+      ArchiveOptions::default().scope(scope),
+    );
+    assert!(matches!(
+      result,
+      Err(crate::Error::Archive(Error::Forbidden(_)))
+    ));
+
+    fs::remove_dir_all(dir).unwrap();
+  }
+}
@@ -252,6 +252,35 @@ pub fn set(handle: TokioHandle) {
     .unwrap_or_else(|_| panic!("runtime already initialized"))
 }
 
+/// Sets the runtime to use to execute asynchronous tasks, like [`set`] but returning an error instead of panicking.
+///
+/// The runtime must be a multi-thread tokio runtime: the main thread runs the event loop,
+/// so the tasks of a current-thread runtime would never make progress.
+/// Tauri never shuts the runtime down, it must outlive the app.
+///
+/// With a runtime set by the app:
+///
+/// - [`spawn`] and [`spawn_blocking`] run the tasks on that runtime.
+/// - [`block_on`] uses [`TokioHandle::block_on`], which panics when called from an async task of the runtime;
+///   use [`spawn`] and await the [`JoinHandle`] there instead.
+///
+/// # Errors
+///
+/// - [`crate::Error::AsyncRuntimeAlreadySet`] if the runtime was already set or used,
+///   since the functions of this module initialize the default runtime on first use.
+/// - [`crate::Error::UnsupportedAsyncRuntime`] if the runtime is a current-thread runtime.
+pub fn try_set(handle: TokioHandle) -> crate::Result<()> {
+  if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::CurrentThread {
+    return Err(crate::Error::UnsupportedAsyncRuntime);
+  }
+  RUNTIME
+    .set(GlobalRuntime {
+      runtime: None,
+      handle: RuntimeHandle::Tokio(handle),
+    })
+    .map_err(|_| crate::Error::AsyncRuntimeAlreadySet)
+}
+
 /// Returns a handle of the async runtime.
 pub fn handle() -> RuntimeHandle {
   let runtime = RUNTIME.get_or_init(default_runtime);
@@ -330,6 +359,25 @@ mod tests {
     assert_eq!(handle.block_on(async { 0 }), 0);
   }
 
+  #[test]
+  fn try_set_rejects_invalid_runtimes() {
+    let current_thread = tokio::runtime::Builder::new_current_thread()
+      .build()
+      .unwrap();
+    assert!(matches!(
+      try_set(current_thread.handle().clone()),
+      Err(crate::Error::UnsupportedAsyncRuntime)
+    ));
+
+    // initializes the default runtime
+    handle();
+    let runtime = TokioRuntime::new().unwrap();
+    assert!(matches!(
+      try_set(runtime.handle().clone()),
+      Err(crate::Error::AsyncRuntimeAlreadySet)
+    ));
+  }
+
   #[tokio::test]
   async fn handle_abort() {
     let handle = handle();
@@ -0,0 +1,784 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The audio capture and playback devices of the system.
+//!
+//! The webview `navigator.mediaDevices.enumerateDevices` API only returns the device labels after a `getUserMedia` permission prompt,
+//! so this module lists them from the operating system audio stack instead, with the same labels the webview uses.
+//! Use [`watch`] to be notified when a device is connected or the default device changes.
+
+use serde::Serialize;
+
+pub(crate) mod plugin;
+
+/// Audio device errors.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+  /// The audio devices could not be read from the operating system.
+  #[error("audio devices are not available: {0}")]
+  Unavailable(String),
+}
+
+/// The direction of an [`AudioDevice`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AudioDeviceKind {
+  /// A capture device such as a microphone, `audioinput` in the webview.
+  Input,
+  /// A playback device such as speakers or headphones, `audiooutput` in the webview.
+  Output,
+}
+
+/// An audio capture or playback device.
+///
+/// See [`devices`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct AudioDevice {
+  /// The identifier of the device in the operating system audio stack, unique for its [`Self::kind`].
+  ///
+  /// This is not the webview `deviceId`, which is randomized for each origin.
+  pub id: String,
+  /// The human readable name of the device, matching the `label` of the webview `MediaDeviceInfo`.
+  pub label: String,
+  /// Whether the device captures or plays audio.
+  pub kind: AudioDeviceKind,
+  /// Whether this is the default device of its [`Self::kind`], used by `getUserMedia` when no device is requested.
+  pub is_default: bool,
+}
+
+/// Lists the active audio capture devices followed by the playback devices.
+///
+/// ## Platform-specific
+///
+/// - **Windows:** Uses the `eConsole` role for the default devices.
+/// - **Linux:** Requires the `pactl` command of PulseAudio or PipeWire. Monitor sources are not included.
+/// - **Android / iOS:** Unsupported, returns an empty list.
+pub fn devices() -> crate::Result<Vec<AudioDevice>> {
+  platform::devices().map_err(Into::into)
+}
+
+/// Watches the audio devices, see [`watch`].
+///
+/// The devices are watched until it is dropped.
+pub struct DeviceWatcher {
+  _watcher: platform::Watcher,
+}
+
+impl crate::Resource for DeviceWatcher {}
+
+/// Calls `on_change` with the new list of [`AudioDevice`]s when a device is added or removed,
+/// is renamed or the default device changes, until the returned [`DeviceWatcher`] is dropped.
+///
+/// The devices are listed again when the operating system notifies a change,
+/// and `on_change` is called on a dedicated thread only when the list differs from the previous one.
+///
+/// ## Platform-specific
+///
+/// - **Windows:** Registers an `IMMNotificationClient`.
+/// - **macOS:** Adds Core Audio property listeners to the system object.
+/// - **Linux:** Runs `pactl subscribe` for as long as the devices are watched.
+/// - **Android / iOS:** Unsupported, `on_change` is never called.
+pub fn watch<F: Fn(Vec<AudioDevice>) + Send + 'static>(
+  on_change: F,
+) -> crate::Result<DeviceWatcher> {
+  let (tx, rx) = std::sync::mpsc::channel();
+  let watcher = platform::watch(tx)?;
+  std::thread::spawn(move || {
+    let mut current = platform::devices().ok();
+    // the sender is dropped with the platform watcher
+    while rx.recv().is_ok() {
+      // a single change is usually notified several times
+      while rx.try_recv().is_ok() {}
+      match platform::devices() {
+        Ok(devices) => {
+          if current.as_ref() != Some(&devices) {
+            on_change(devices.clone());
+            current.replace(devices);
+          }
+        }
+        Err(e) => log::debug!("failed to list the audio devices: {e}"),
+      }
+    }
+  });
+  Ok(DeviceWatcher { _watcher: watcher })
+}
+
+/// Whether a `pactl subscribe` event line is about a sink, a source or the server, whose changes include the default devices.
+#[cfg_attr(any(windows, target_os = "macos", mobile), allow(dead_code))]
+fn is_pactl_device_event(line: &str) -> bool {
+  // Event 'new' on sink #42
+  line
+    .split_once(" on ")
+    .and_then(|(_, facility)| facility.split(" #").next())
+    .is_some_and(|facility| matches!(facility.trim(), "sink" | "source" | "server"))
+}
+
+/// Parses the `Default Sink` and `Default Source` of the `pactl info` output.
+#[cfg_attr(any(windows, target_os = "macos", mobile), allow(dead_code))]
+fn parse_pactl_defaults(output: &str) -> (Option<String>, Option<String>) {
+  let mut sink = None;
+  let mut source = None;
+  for line in output.lines() {
+    if let Some(name) = line.strip_prefix("Default Sink: ") {
+      sink.replace(name.trim().to_string());
+    } else if let Some(name) = line.strip_prefix("Default Source: ") {
+      source.replace(name.trim().to_string());
+    }
+  }
+  (sink, source)
+}
+
+/// Parses the devices of the `pactl list sinks` or `pactl list sources` output.
+#[cfg_attr(any(windows, target_os = "macos", mobile), allow(dead_code))]
+fn parse_pactl_list(
+  output: &str,
+  kind: AudioDeviceKind,
+  default: Option<&str>,
+) -> Vec<AudioDevice> {
+  let mut devices = Vec::new();
+  let mut name: Option<String> = None;
+  let mut description: Option<String> = None;
+  // each device starts with an unindented `Sink #0` or `Source #0` line
+  for line in output.lines().chain(std::iter::once("")) {
+    if !line.starts_with(char::is_whitespace) {
+      if let (Some(id), Some(label)) = (name.take(), description.take()) {
+        // monitor sources capture the output of a sink
+        if !(kind == AudioDeviceKind::Input && id.ends_with(".monitor")) {
+          devices.push(AudioDevice {
+            is_default: default == Some(id.as_str()),
+            id,
+            label,
+            kind,
+          });
+        }
+      }
+    } else if let Some(value) = line.strip_prefix("\tName: ") {
+      name.replace(value.trim().to_string());
+    } else if let Some(value) = line.strip_prefix("\tDescription: ") {
+      description.replace(value.trim().to_string());
+    }
+  }
+  devices
+}
+
+#[cfg(windows)]
+mod platform {
+  use super::{AudioDevice, AudioDeviceKind, Error};
+  use std::sync::mpsc::{channel, sync_channel, Sender};
+  use windows::{
+    core::{implement, PCWSTR},
+    Win32::{
+      Devices::FunctionDiscovery::PKEY_Device_FriendlyName,
+      Media::Audio::{
+        eCapture, eConsole, eRender, EDataFlow, ERole, IMMDevice, IMMDeviceEnumerator,
+        IMMNotificationClient, IMMNotificationClient_Impl, MMDeviceEnumerator, DEVICE_STATE,
+        DEVICE_STATE_ACTIVE,
+      },
+      System::Com::{
+        CoCreateInstance, CoInitializeEx, CoTaskMemFree, CoUninitialize, CLSCTX_ALL,
+        COINIT_MULTITHREADED, STGM_READ,
+      },
+      UI::Shell::PropertiesSystem::PROPERTYKEY,
+    },
+  };
+
+  unsafe fn id(device: &IMMDevice) -> windows::core::Result<String> {
+    let id = device.GetId()?;
+    let value = String::from_utf16_lossy(id.as_wide());
+    CoTaskMemFree(Some(id.0 as _));
+    Ok(value)
+  }
+
+  unsafe fn enumerate() -> windows::core::Result<Vec<AudioDevice>> {
+    let enumerator: IMMDeviceEnumerator = CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+    let mut devices = Vec::new();
+    for (flow, kind) in [
+      (eCapture, AudioDeviceKind::Input),
+      (eRender, AudioDeviceKind::Output),
+    ] {
+      // there is no default endpoint when no device is connected
+      let default = enumerator
+        .GetDefaultAudioEndpoint(flow, eConsole)
+        .and_then(|device| id(&device))
+        .ok();
+      let collection = enumerator.EnumAudioEndpoints(flow, DEVICE_STATE_ACTIVE)?;
+      for i in 0..collection.GetCount()? {
+        let device = collection.Item(i)?;
+        let id = id(&device)?;
+        let label = device
+          .OpenPropertyStore(STGM_READ)?
+          .GetValue(&PKEY_Device_FriendlyName)?
+          .to_string();
+        devices.push(AudioDevice {
+          is_default: default.as_ref() == Some(&id),
+          id,
+          label,
+          kind,
+        });
+      }
+    }
+    Ok(devices)
+  }
+
+  pub fn devices() -> Result<Vec<AudioDevice>, Error> {
+    unsafe {
+      // fails if the thread already uses a single-threaded apartment, which works as well
+      let initialized = CoInitializeEx(None, COINIT_MULTITHREADED).is_ok();
+      let devices = enumerate();
+      if initialized {
+        CoUninitialize();
+      }
+      devices.map_err(|e| Error::Unavailable(e.to_string()))
+    }
+  }
+
+  #[implement(IMMNotificationClient)]
+  struct Client(Sender<()>);
+
+  impl IMMNotificationClient_Impl for Client_Impl {
+    fn OnDeviceStateChanged(
+      &self,
+      _device: &PCWSTR,
+      _state: DEVICE_STATE,
+    ) -> windows::core::Result<()> {
+      let _ = self.0.send(());
+      Ok(())
+    }
+
+    fn OnDeviceAdded(&self, _device: &PCWSTR) -> windows::core::Result<()> {
+      let _ = self.0.send(());
+      Ok(())
+    }
+
+    fn OnDeviceRemoved(&self, _device: &PCWSTR) -> windows::core::Result<()> {
+      let _ = self.0.send(());
+      Ok(())
+    }
+
+    fn OnDefaultDeviceChanged(
+      &self,
+      _flow: EDataFlow,
+      _role: ERole,
+      _device: &PCWSTR,
+    ) -> windows::core::Result<()> {
+      let _ = self.0.send(());
+      Ok(())
+    }
+
+    fn OnPropertyValueChanged(
+      &self,
+      _device: &PCWSTR,
+      key: &PROPERTYKEY,
+    ) -> windows::core::Result<()> {
+      // the other properties, such as the format, change often and do not affect the list
+      if *key == PKEY_Device_FriendlyName {
+        let _ = self.0.send(());
+      }
+      Ok(())
+    }
+  }
+
+  /// Stops the notifications when dropped.
+  pub struct Watcher {
+    _stop: Sender<()>,
+  }
+
+  pub fn watch(tx: Sender<()>) -> Result<Watcher, Error> {
+    let (stop_tx, stop_rx) = channel::<()>();
+    let (ready_tx, ready_rx) = sync_channel(1);
+    // the COM objects live on their own thread for as long as the devices are watched
+    std::thread::spawn(move || unsafe {
+      let initialized = CoInitializeEx(None, COINIT_MULTITHREADED).is_ok();
+      let registration = (|| -> windows::core::Result<_> {
+        let enumerator: IMMDeviceEnumerator =
+          CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+        let client: IMMNotificationClient = Client(tx).into();
+        enumerator.RegisterEndpointNotificationCallback(&client)?;
+        Ok((enumerator, client))
+      })();
+      match registration {
+        Ok((enumerator, client)) => {
+          let _ = ready_tx.send(Ok(()));
+          // returns once the watcher is dropped
+          let _ = stop_rx.recv();
+          let _ = enumerator.UnregisterEndpointNotificationCallback(&client);
+        }
+        Err(e) => {
+          let _ = ready_tx.send(Err(Error::Unavailable(e.to_string())));
+        }
+      }
+      if initialized {
+        CoUninitialize();
+      }
+    });
+    ready_rx
+      .recv()
+      .map_err(|_| Error::Unavailable("the audio device watcher stopped".into()))??;
+    Ok(Watcher { _stop: stop_tx })
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use super::{AudioDevice, AudioDeviceKind, Error};
+  use objc2_foundation::NSString;
+  use std::{ffi::c_void, sync::mpsc::Sender};
+
+  const fn fourcc(code: &[u8; 4]) -> u32 {
+    u32::from_be_bytes(*code)
+  }
+
+  const SYSTEM_OBJECT: u32 = 1;
+  const UNKNOWN_OBJECT: u32 = 0;
+  const ELEMENT_MAIN: u32 = 0;
+  const SCOPE_GLOBAL: u32 = fourcc(b"glob");
+  const SCOPE_INPUT: u32 = fourcc(b"inpt");
+  const SCOPE_OUTPUT: u32 = fourcc(b"outp");
+  const HARDWARE_DEVICES: u32 = fourcc(b"dev#");
+  const HARDWARE_DEFAULT_INPUT_DEVICE: u32 = fourcc(b"dIn ");
+  const HARDWARE_DEFAULT_OUTPUT_DEVICE: u32 = fourcc(b"dOut");
+  const DEVICE_UID: u32 = fourcc(b"uid ");
+  const DEVICE_STREAMS: u32 = fourcc(b"stm#");
+  const OBJECT_NAME: u32 = fourcc(b"lnam");
+
+  #[repr(C)]
+  struct AudioObjectPropertyAddress {
+    selector: u32,
+    scope: u32,
+    element: u32,
+  }
+
+  impl AudioObjectPropertyAddress {
+    fn new(selector: u32, scope: u32) -> Self {
+      Self {
+        selector,
+        scope,
+        element: ELEMENT_MAIN,
+      }
+    }
+  }
+
+  #[link(name = "CoreAudio", kind = "framework")]
+  extern "C" {
+    fn AudioObjectGetPropertyDataSize(
+      object: u32,
+      address: *const AudioObjectPropertyAddress,
+      qualifier_size: u32,
+      qualifier: *const c_void,
+      size: *mut u32,
+    ) -> i32;
+    fn AudioObjectGetPropertyData(
+      object: u32,
+      address: *const AudioObjectPropertyAddress,
+      qualifier_size: u32,
+      qualifier: *const c_void,
+      size: *mut u32,
+      data: *mut c_void,
+    ) -> i32;
+    fn AudioObjectAddPropertyListener(
+      object: u32,
+      address: *const AudioObjectPropertyAddress,
+      listener: PropertyListener,
+      client_data: *mut c_void,
+    ) -> i32;
+    fn AudioObjectRemovePropertyListener(
+      object: u32,
+      address: *const AudioObjectPropertyAddress,
+      listener: PropertyListener,
+      client_data: *mut c_void,
+    ) -> i32;
+  }
+
+  type PropertyListener = unsafe extern "C" fn(
+    object: u32,
+    address_count: u32,
+    addresses: *const AudioObjectPropertyAddress,
+    client_data: *mut c_void,
+  ) -> i32;
+
+  #[link(name = "CoreFoundation", kind = "framework")]
+  extern "C" {
+    fn CFRelease(object: *const c_void);
+  }
+
+  unsafe fn size(object: u32, address: &AudioObjectPropertyAddress) -> Option<u32> {
+    let mut size = 0;
+    let status = AudioObjectGetPropertyDataSize(object, address, 0, std::ptr::null(), &mut size);
+    (status == 0).then_some(size)
+  }
+
+  unsafe fn default_device(selector: u32) -> Option<u32> {
+    let address = AudioObjectPropertyAddress::new(selector, SCOPE_GLOBAL);
+    let mut device = UNKNOWN_OBJECT;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    let status = AudioObjectGetPropertyData(
+      SYSTEM_OBJECT,
+      &address,
+      0,
+      std::ptr::null(),
+      &mut size,
+      (&mut device as *mut u32).cast(),
+    );
+    (status == 0 && device != UNKNOWN_OBJECT).then_some(device)
+  }
+
+  unsafe fn string(object: u32, selector: u32) -> Option<String> {
+    let address = AudioObjectPropertyAddress::new(selector, SCOPE_GLOBAL);
+    let mut value: *const NSString = std::ptr::null();
+    let mut size = std::mem::size_of::<*const NSString>() as u32;
+    let status = AudioObjectGetPropertyData(
+      object,
+      &address,
+      0,
+      std::ptr::null(),
+      &mut size,
+      (&mut value as *mut *const NSString).cast(),
+    );
+    if status != 0 || value.is_null() {
+      return None;
+    }
+    // CFString is toll-free bridged with NSString
+    let string = (*value).to_string();
+    CFRelease(value.cast());
+    Some(string)
+  }
+
+  pub fn devices() -> Result<Vec<AudioDevice>, Error> {
+    unsafe {
+      let address = AudioObjectPropertyAddress::new(HARDWARE_DEVICES, SCOPE_GLOBAL);
+      let mut len = size(SYSTEM_OBJECT, &address)
+        .ok_or_else(|| Error::Unavailable("failed to read the audio devices".into()))?;
+      let mut ids = vec![UNKNOWN_OBJECT; len as usize / std::mem::size_of::<u32>()];
+      let status = AudioObjectGetPropertyData(
+        SYSTEM_OBJECT,
+        &address,
+        0,
+        std::ptr::null(),
+        &mut len,
+        ids.as_mut_ptr().cast(),
+      );
+      if status != 0 {
+        return Err(Error::Unavailable(format!(
+          "failed to read the audio devices: OSStatus {status}"
+        )));
+      }
+      ids.truncate(len as usize / std::mem::size_of::<u32>());
+
+      let mut devices = Vec::new();
+      for (scope, kind, default) in [
+        (
+          SCOPE_INPUT,
+          AudioDeviceKind::Input,
+          default_device(HARDWARE_DEFAULT_INPUT_DEVICE),
+        ),
+        (
+          SCOPE_OUTPUT,
+          AudioDeviceKind::Output,
+          default_device(HARDWARE_DEFAULT_OUTPUT_DEVICE),
+        ),
+      ] {
+        for &device in &ids {
+          // a device has streams in the scopes it supports, some devices support both directions
+          let streams = AudioObjectPropertyAddress::new(DEVICE_STREAMS, scope);
+          if !size(device, &streams).is_some_and(|size| size > 0) {
+            continue;
+          }
+          let (Some(id), Some(label)) = (string(device, DEVICE_UID), string(device, OBJECT_NAME))
+          else {
+            continue;
+          };
+          devices.push(AudioDevice {
+            id,
+            label,
+            kind,
+            is_default: default == Some(device),
+          });
+        }
+      }
+      Ok(devices)
+    }
+  }
+
+  /// The system object properties that change when a device is added or removed or the default device changes.
+  const WATCHED_PROPERTIES: [u32; 3] = [
+    HARDWARE_DEVICES,
+    HARDWARE_DEFAULT_INPUT_DEVICE,
+    HARDWARE_DEFAULT_OUTPUT_DEVICE,
+  ];
+
+  unsafe extern "C" fn on_property_changed(
+    _object: u32,
+    _address_count: u32,
+    _addresses: *const AudioObjectPropertyAddress,
+    client_data: *mut c_void,
+  ) -> i32 {
+    let tx = &*(client_data as *const Sender<()>);
+    let _ = tx.send(());
+    0
+  }
+
+  /// Removes the property listeners when dropped.
+  pub struct Watcher {
+    tx: *mut Sender<()>,
+    registered: usize,
+  }
+
+  // the sender is only shared with the Core Audio listener thread
+  unsafe impl Send for Watcher {}
+  unsafe impl Sync for Watcher {}
+
+  impl Drop for Watcher {
+    fn drop(&mut self) {
+      unsafe {
+        for selector in &WATCHED_PROPERTIES[..self.registered] {
+          let address = AudioObjectPropertyAddress::new(*selector, SCOPE_GLOBAL);
+          AudioObjectRemovePropertyListener(
+            SYSTEM_OBJECT,
+            &address,
+            on_property_changed,
+            self.tx.cast(),
+          );
+        }
+        drop(Box::from_raw(self.tx));
+      }
+    }
+  }
+
+  pub fn watch(tx: Sender<()>) -> Result<Watcher, Error> {
+    let mut watcher = Watcher {
+      tx: Box::into_raw(Box::new(tx)),
+      registered: 0,
+    };
+    for selector in WATCHED_PROPERTIES {
+      let address = AudioObjectPropertyAddress::new(selector, SCOPE_GLOBAL);
+      let status = unsafe {
+        AudioObjectAddPropertyListener(
+          SYSTEM_OBJECT,
+          &address,
+          on_property_changed,
+          watcher.tx.cast(),
+        )
+      };
+      if status != 0 {
+        // the listeners added so far are removed by the watcher
+        return Err(Error::Unavailable(format!(
+          "failed to watch the audio devices: OSStatus {status}"
+        )));
+      }
+      watcher.registered += 1;
+    }
+    Ok(watcher)
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use super::{
+    is_pactl_device_event, parse_pactl_defaults, parse_pactl_list, AudioDevice, AudioDeviceKind,
+    Error,
+  };
+  use std::{
+    io::{BufRead, BufReader},
+    process::{Child, Command, Stdio},
+    sync::mpsc::Sender,
+  };
+
+  fn pactl(args: &[&str]) -> Result<String, Error> {
+    let output = Command::new("pactl")
+      .args(args)
+      // the field names are translated
+      .env("LC_ALL", "C")
+      .output()
+      .map_err(|e| Error::Unavailable(format!("failed to run pactl: {e}")))?;
+    if !output.status.success() {
+      return Err(Error::Unavailable(
+        String::from_utf8_lossy(&output.stderr).trim().to_string(),
+      ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+  }
+
+  pub fn devices() -> Result<Vec<AudioDevice>, Error> {
+    let (default_sink, default_source) = parse_pactl_defaults(&pactl(&["info"])?);
+    let mut devices = parse_pactl_list(
+      &pactl(&["list", "sources"])?,
+      AudioDeviceKind::Input,
+      default_source.as_deref(),
+    );
+    devices.extend(parse_pactl_list(
+      &pactl(&["list", "sinks"])?,
+      AudioDeviceKind::Output,
+      default_sink.as_deref(),
+    ));
+    Ok(devices)
+  }
+
+  /// Stops `pactl subscribe` when dropped.
+  pub struct Watcher(Child);
+
+  impl Drop for Watcher {
+    fn drop(&mut self) {
+      let _ = self.0.kill();
+      let _ = self.0.wait();
+    }
+  }
+
+  pub fn watch(tx: Sender<()>) -> Result<Watcher, Error> {
+    let mut child = Command::new("pactl")
+      .arg("subscribe")
+      .env("LC_ALL", "C")
+      .stdin(Stdio::null())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::null())
+      .spawn()
+      .map_err(|e| Error::Unavailable(format!("failed to run pactl: {e}")))?;
+    let Some(stdout) = child.stdout.take() else {
+      let _ = child.kill();
+      return Err(Error::Unavailable("failed to read the pactl output".into()));
+    };
+    // the output ends when the watcher kills pactl, dropping the sender
+    std::thread::spawn(move || {
+      for line in BufReader::new(stdout).lines() {
+        let Ok(line) = line else { break };
+        if is_pactl_device_event(&line) && tx.send(()).is_err() {
+          break;
+        }
+      }
+    });
+    Ok(Watcher(child))
+  }
+}
+
+#[cfg(mobile)]
+mod platform {
+  use super::{AudioDevice, Error};
+  use std::sync::mpsc::Sender;
+
+  pub struct Watcher;
+
+  pub fn devices() -> Result<Vec<AudioDevice>, Error> {
+    Ok(Vec::new())
+  }
+
+  pub fn watch(_tx: Sender<()>) -> Result<Watcher, Error> {
+    Ok(Watcher)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const SOURCES: &str = "Source #0
+\tState: SUSPENDED
+\tName: alsa_output.pci-0000_00_1f.3.analog-stereo.monitor
+\tDescription: Monitor of Built-in Audio Analog Stereo
+\tDriver: module-alsa-card.c
+\tProperties:
+\t\tdevice.description = \"Monitor of Built-in Audio Analog Stereo\"
+
+Source #1
+\tState: RUNNING
+\tName: alsa_input.usb-Logitech_Webcam_C920-02.analog-stereo
+\tDescription: HD Pro Webcam C920 Analog Stereo
+\tDriver: module-alsa-card.c
+\tProperties:
+\t\tdevice.description = \"HD Pro Webcam C920\"
+
+Source #2
+\tState: SUSPENDED
+\tName: alsa_input.pci-0000_00_1f.3.analog-stereo
+\tDescription: Built-in Audio Analog Stereo
+";
+
+  #[test]
+  fn parses_pactl_defaults() {
+    let info = "Server String: /run/user/1000/pulse/native
+Default Sink: alsa_output.pci-0000_00_1f.3.analog-stereo
+Default Source: alsa_input.pci-0000_00_1f.3.analog-stereo
+Cookie: 0a1b:2c3d";
+    assert_eq!(
+      parse_pactl_defaults(info),
+      (
+        Some("alsa_output.pci-0000_00_1f.3.analog-stereo".into()),
+        Some("alsa_input.pci-0000_00_1f.3.analog-stereo".into())
+      )
+    );
+    assert_eq!(parse_pactl_defaults(""), (None, None));
+  }
+
+  #[test]
+  fn parses_pactl_list() {
+    let devices = parse_pactl_list(
+      SOURCES,
+      AudioDeviceKind::Input,
+      Some("alsa_input.pci-0000_00_1f.3.analog-stereo"),
+    );
+    assert_eq!(
+      devices,
+      vec![
+        AudioDevice {
+          id: "alsa_input.usb-Logitech_Webcam_C920-02.analog-stereo".into(),
+          label: "HD Pro Webcam C920 Analog Stereo".into(),
+          kind: AudioDeviceKind::Input,
+          is_default: false,
+        },
+        AudioDevice {
+          id: "alsa_input.pci-0000_00_1f.3.analog-stereo".into(),
+          label: "Built-in Audio Analog Stereo".into(),
+          kind: AudioDeviceKind::Input,
+          is_default: true,
+        },
+      ]
+    );
+
+    // monitors are only skipped for the sources
+    let sinks = SOURCES.replace("Source #", "Sink #");
+    assert_eq!(
+      parse_pactl_list(&sinks, AudioDeviceKind::Output, None).len(),
+      3
+    );
+  }
+
+  #[test]
+  fn filters_pactl_events() {
+    assert!(is_pactl_device_event("Event 'new' on sink #42"));
+    assert!(is_pactl_device_event("Event 'remove' on source #7"));
+    assert!(is_pactl_device_event(
+      "Event 'change' on server #4294967295"
+    ));
+    assert!(!is_pactl_device_event("Event 'change' on sink-input #12"));
+    assert!(!is_pactl_device_event("Event 'new' on source-output #3"));
+    assert!(!is_pactl_device_event("Event 'change' on client #9"));
+    assert!(!is_pactl_device_event(""));
+  }
+
+  #[test]
+  fn serializes_device() {
+    let device = AudioDevice {
+      id: "default".into(),
+      label: "Speakers".into(),
+      kind: AudioDeviceKind::Output,
+      is_default: true,
+    };
+    assert_eq!(
+      serde_json::to_value(device).unwrap(),
+      serde_json::json!({
+        "id": "default",
+        "label": "Speakers",
+        "kind": "output",
+        "isDefault": true,
+      })
+    );
+  }
+}
@@ -0,0 +1,36 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::AudioDevice;
+use crate::{
+  command,
+  ipc::Channel,
+  plugin::{Builder, TauriPlugin},
+  Manager, ResourceId, Runtime, Webview,
+};
+
+#[command(root = "crate")]
+async fn devices() -> crate::Result<Vec<AudioDevice>> {
+  crate::async_runtime::spawn_blocking(super::devices).await?
+}
+
+#[command(root = "crate")]
+fn watch<R: Runtime>(
+  webview: Webview<R>,
+  on_change: Channel<Vec<AudioDevice>>,
+) -> crate::Result<ResourceId> {
+  let watcher = super::watch(move |devices| {
+    if let Err(e) = on_change.send(devices) {
+      log::error!("failed to send the audio devices: {e}");
+    }
+  })?;
+  // closing the resource stops watching the devices
+  Ok(webview.resources_table().add(watcher))
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("audio")
+    .invoke_handler(crate::generate_handler![devices, watch])
+    .build()
+}
@@ -0,0 +1,399 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! OS-level user authentication.
+//!
+//! Prompts the user to re-authenticate with the operating system facilities
+//! (Windows Hello, Touch ID / device password, polkit) and hands the Rust side
+//! a signed [`Assertion`] that can only be verified once.
+
+use std::{
+  collections::HashMap,
+  sync::Mutex,
+  time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::{Manager, Runtime};
+
+/// How long an [`Assertion`] is accepted by [`Assertion::verify`] after being issued.
+pub const ASSERTION_MAX_AGE: Duration = Duration::from_secs(5 * 60);
+
+/// The polkit action checked on Linux. It requires administrator authentication.
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+const POLKIT_ACTION_ID: &str = "org.freedesktop.policykit.exec";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Authentication errors.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+  /// The user cancelled the prompt or failed to authenticate.
+  #[error("the user could not be authenticated")]
+  Denied,
+  /// No authentication mechanism is available or configured on this system.
+  #[error("OS authentication is not available: {0}")]
+  Unavailable(String),
+  /// The assertion signature does not match.
+  #[error("invalid assertion signature")]
+  InvalidSignature,
+  /// The assertion was already verified once.
+  #[error("assertion was already used")]
+  Replayed,
+  /// The assertion is older than [`ASSERTION_MAX_AGE`].
+  #[error("assertion expired")]
+  Expired,
+}
+
+/// Proof that the user authenticated with the operating system.
+///
+/// An assertion is signed with a key that never leaves the process
+/// and is consumed by the first successful [`Assertion::verify`] call.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Assertion {
+  /// The reason shown to the user when prompting for authentication.
+  pub reason: String,
+  /// Random value identifying this assertion.
+  pub nonce: String,
+  /// Issue time in milliseconds since the UNIX epoch.
+  pub issued_at: u64,
+  /// Hex encoded HMAC-SHA256 signature of the fields above.
+  pub signature: String,
+}
+
+impl Assertion {
+  /// Verifies the assertion signature and marks it as consumed.
+  ///
+  /// Fails if the assertion was not issued by this application instance,
+  /// has already been verified or is older than [`ASSERTION_MAX_AGE`].
+  pub fn verify<R: Runtime, M: Manager<R>>(&self, manager: &M) -> crate::Result<()> {
+    manager
+      .state::<AssertionSigner>()
+      .verify(self)
+      .map_err(Into::into)
+  }
+}
+
+/// Signs and tracks the assertions issued by [`authenticate`].
+pub(crate) struct AssertionSigner {
+  key: [u8; 32],
+  consumed: Mutex<HashMap<String, u64>>,
+}
+
+impl AssertionSigner {
+  pub(crate) fn new() -> crate::Result<Self> {
+    let mut key = [0u8; 32];
+    getrandom::getrandom(&mut key)?;
+    Ok(Self {
+      key,
+      consumed: Default::default(),
+    })
+  }
+
+  fn mac(&self, reason: &str, nonce: &str, issued_at: u64) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any size");
+    mac.update(reason.as_bytes());
+    mac.update(&[0]);
+    mac.update(nonce.as_bytes());
+    mac.update(&[0]);
+    mac.update(&issued_at.to_be_bytes());
+    mac
+  }
+
+  fn sign(&self, reason: &str) -> crate::Result<Assertion> {
+    let mut nonce = [0u8; 16];
+    getrandom::getrandom(&mut nonce)?;
+    let nonce = to_hex(&nonce);
+    let issued_at = now_millis();
+    let signature = to_hex(&self.mac(reason, &nonce, issued_at).finalize().into_bytes());
+    Ok(Assertion {
+      reason: reason.into(),
+      nonce,
+      issued_at,
+      signature,
+    })
+  }
+
+  fn verify(&self, assertion: &Assertion) -> Result<(), Error> {
+    let signature = from_hex(&assertion.signature).ok_or(Error::InvalidSignature)?;
+    self
+      .mac(&assertion.reason, &assertion.nonce, assertion.issued_at)
+      .verify_slice(&signature)
+      .map_err(|_| Error::InvalidSignature)?;
+
+    let now = now_millis();
+    let max_age = ASSERTION_MAX_AGE.as_millis() as u64;
+    if now.saturating_sub(assertion.issued_at) > max_age {
+      return Err(Error::Expired);
+    }
+
+    let mut consumed = self.consumed.lock().unwrap();
+    // expired nonces are rejected before reaching this map so we can forget them
+    consumed.retain(|_, issued_at| now.saturating_sub(*issued_at) <= max_age);
+    if consumed
+      .insert(assertion.nonce.clone(), assertion.issued_at)
+      .is_some()
+    {
+      return Err(Error::Replayed);
+    }
+
+    Ok(())
+  }
+}
+
+/// Prompts the user to authenticate with the operating system.
+///
+/// Returns an [`Assertion`] that must be checked with [`Assertion::verify`]
+/// before performing the guarded operation.
+///
+/// This function blocks until the user answers the prompt,
+/// so it must not be called on the main thread.
+///
+/// ## Platform-specific
+///
+/// - **Windows:** Uses Windows Hello (PIN, fingerprint or face recognition).
+/// - **macOS:** Uses the LocalAuthentication framework (Touch ID or the device password).
+/// - **Linux:** Uses polkit's `pkcheck`, which requires administrator authentication. The `reason` is not displayed.
+/// - **Android / iOS:** Unsupported.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// #[tauri::command]
+/// async fn reveal_password(app: tauri::AppHandle) -> Result<String, String> {
+///   let assertion = tauri::auth::authenticate(&app, "reveal the stored password")
+///     .map_err(|e| e.to_string())?;
+///   assertion.verify(&app).map_err(|e| e.to_string())?;
+///   Ok("hunter2".into())
+/// }
+/// ```
+pub fn authenticate<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  reason: &str,
+) -> crate::Result<Assertion> {
+  platform::authenticate(reason)?;
+  manager.state::<AssertionSigner>().sign(reason)
+}
+
+fn now_millis() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_millis() as u64)
+    .unwrap_or_default()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+  bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+  if s.len() % 2 != 0 {
+    return None;
+  }
+  (0..s.len())
+    .step_by(2)
+    .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+    .collect()
+}
+
+#[cfg(windows)]
+mod platform {
+  use super::Error;
+  use windows::{
+    core::HSTRING,
+    Security::Credentials::UI::{UserConsentVerificationResult, UserConsentVerifier},
+  };
+
+  pub fn authenticate(reason: &str) -> Result<(), Error> {
+    let result = UserConsentVerifier::RequestVerificationAsync(&HSTRING::from(reason))
+      .and_then(|operation| operation.get())
+      .map_err(|e| Error::Unavailable(e.to_string()))?;
+
+    match result {
+      UserConsentVerificationResult::Verified => Ok(()),
+      UserConsentVerificationResult::DeviceNotPresent
+      | UserConsentVerificationResult::NotConfiguredForUser
+      | UserConsentVerificationResult::DisabledByPolicy => {
+        Err(Error::Unavailable(format!("{result:?}")))
+      }
+      _ => Err(Error::Denied),
+    }
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use super::Error;
+  use block2::RcBlock;
+  use objc2::{
+    class, msg_send, msg_send_id,
+    rc::Retained,
+    runtime::{AnyObject, Bool},
+  };
+  use objc2_foundation::NSString;
+
+  #[link(name = "LocalAuthentication", kind = "framework")]
+  extern "C" {}
+
+  /// `LAPolicyDeviceOwnerAuthentication`: biometrics with a fallback to the device password.
+  const LA_POLICY_DEVICE_OWNER_AUTHENTICATION: isize = 2;
+
+  pub fn authenticate(reason: &str) -> Result<(), Error> {
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    unsafe {
+      let context: Retained<AnyObject> = msg_send_id![class!(LAContext), new];
+
+      let can_evaluate: Bool = msg_send![
+        &context,
+        canEvaluatePolicy: LA_POLICY_DEVICE_OWNER_AUTHENTICATION,
+        error: std::ptr::null_mut::<*mut AnyObject>()
+      ];
+      if !can_evaluate.as_bool() {
+        return Err(Error::Unavailable(
+          "no biometrics or device password configured".into(),
+        ));
+      }
+
+      let reason = NSString::from_str(reason);
+      let reply = RcBlock::new(move |success: Bool, _error: *mut AnyObject| {
+        let _ = tx.send(success.as_bool());
+      });
+      let _: () = msg_send![
+        &context,
+        evaluatePolicy: LA_POLICY_DEVICE_OWNER_AUTHENTICATION,
+        localizedReason: &*reason,
+        reply: &*reply
+      ];
+    }
+
+    match rx.recv() {
+      Ok(true) => Ok(()),
+      _ => Err(Error::Denied),
+    }
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use super::{parse_start_time, Error, POLKIT_ACTION_ID};
+  use std::process::Command;
+
+  /// The `pid,start-time,uid` subject of pkcheck, which identifies the process even if its pid is reused.
+  fn subject() -> String {
+    // polkit looks the start time up when it is 0, e.g. on the BSDs without procfs
+    let start_time = std::fs::read_to_string("/proc/self/stat")
+      .ok()
+      .and_then(|stat| parse_start_time(&stat))
+      .unwrap_or(0);
+    let uid = unsafe { libc::getuid() };
+    format!("{},{start_time},{uid}", std::process::id())
+  }
+
+  pub fn authenticate(reason: &str) -> Result<(), Error> {
+    let status = Command::new("pkcheck")
+      .args(["--action-id", POLKIT_ACTION_ID, "--process"])
+      .arg(subject())
+      .args(["--allow-user-interaction", "--detail", "polkit.message"])
+      .arg(reason)
+      .status()
+      .map_err(|e| Error::Unavailable(format!("failed to run pkcheck: {e}")))?;
+
+    // see pkcheck(1): 0 = authorized, 1 = not authorized, 2 = dismissed, 3 = auth required
+    match status.code() {
+      Some(0) => Ok(()),
+      Some(1..=3) => Err(Error::Denied),
+      _ => Err(Error::Unavailable(format!("pkcheck exited with {status}"))),
+    }
+  }
+}
+
+/// Parses the start time of the process, the 22nd field of `/proc/<pid>/stat`.
+#[cfg_attr(any(windows, target_os = "macos", mobile), allow(dead_code))]
+fn parse_start_time(stat: &str) -> Option<u64> {
+  // the command name in the second field may contain spaces and parentheses
+  let (_, fields) = stat.rsplit_once(')')?;
+  fields.split_whitespace().nth(19)?.parse().ok()
+}
+
+#[cfg(mobile)]
+mod platform {
+  use super::Error;
+
+  pub fn authenticate(_reason: &str) -> Result<(), Error> {
+    Err(Error::Unavailable("unsupported platform".into()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{AssertionSigner, Error, ASSERTION_MAX_AGE};
+  use hmac::Mac;
+
+  #[test]
+  fn parses_start_time() {
+    let stat =
+      "1234 (my (app) x) S 1 1234 1234 0 -1 4194560 100 0 0 0 5 3 0 0 20 0 4 0 98765 1000 200";
+    assert_eq!(parse_start_time(stat), Some(98765));
+    assert_eq!(parse_start_time("1234 (app) S 1"), None);
+  }
+
+  #[test]
+  fn assertion_is_single_use() {
+    let signer = AssertionSigner::new().unwrap();
+    let assertion = signer.sign("unlock vault").unwrap();
+    assert!(signer.verify(&assertion).is_ok());
+    assert!(matches!(signer.verify(&assertion), Err(Error::Replayed)));
+  }
+
+  #[test]
+  fn tampered_assertion_is_rejected() {
+    let signer = AssertionSigner::new().unwrap();
+    let mut assertion = signer.sign("unlock vault").unwrap();
+    assertion.reason = "delete vault".into();
+    assert!(matches!(
+      signer.verify(&assertion),
+      Err(Error::InvalidSignature)
+    ));
+
+    let other_signer = AssertionSigner::new().unwrap();
+    let assertion = other_signer.sign("unlock vault").unwrap();
+    assert!(matches!(
+      signer.verify(&assertion),
+      Err(Error::InvalidSignature)
+    ));
+  }
+
+  #[test]
+  fn old_assertion_is_rejected() {
+    let signer = AssertionSigner::new().unwrap();
+    let mut assertion = signer.sign("unlock vault").unwrap();
+    assertion.issued_at -= ASSERTION_MAX_AGE.as_millis() as u64 + 1;
+    // re-sign with the old timestamp so only the age check fails
+    assertion.signature = super::to_hex(
+      &signer
+        .mac(&assertion.reason, &assertion.nonce, assertion.issued_at)
+        .finalize()
+        .into_bytes(),
+    );
+    assert!(matches!(signer.verify(&assertion), Err(Error::Expired)));
+  }
+}
@@ -0,0 +1,476 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! BlueZ D-Bus backend.
+
+use std::{
+  collections::HashMap,
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+  },
+  time::{Duration, Instant},
+};
+
+use gtk::{
+  gio::{self, DBusCallFlags, DBusConnection, DBusSignalFlags, SignalSubscriptionId},
+  glib::{self, ToVariant, Variant, VariantDict},
+};
+
+use super::{
+  normalize_uuid, Advertisement, CharacteristicProperties, Error, GattCharacteristic, GattService,
+  OnAdvertisement, OnValue, WriteType,
+};
+
+const SERVICE: &str = "org.bluez";
+const ADAPTER_INTERFACE: &str = "org.bluez.Adapter1";
+const DEVICE_INTERFACE: &str = "org.bluez.Device1";
+const GATT_SERVICE_INTERFACE: &str = "org.bluez.GattService1";
+const GATT_CHARACTERISTIC_INTERFACE: &str = "org.bluez.GattCharacteristic1";
+
+/// Interval at which the discovered devices are read while scanning.
+const SCAN_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// How long to wait for the services of a connected device to be resolved.
+const SERVICES_RESOLVE_TIMEOUT: Duration = Duration::from_secs(15);
+/// The timeout of the D-Bus calls, connecting to a device can be slow.
+const CALL_TIMEOUT_MS: i32 = 30_000;
+
+fn system_bus() -> Result<DBusConnection, Error> {
+  gio::bus_get_sync(gio::BusType::System, None::<&gio::Cancellable>)
+    .map_err(|e| Error::Unavailable(e.to_string()))
+}
+
+fn call(
+  connection: &DBusConnection,
+  path: &str,
+  interface: &str,
+  method: &str,
+  parameters: Option<&Variant>,
+) -> Result<Variant, glib::Error> {
+  connection.call_sync(
+    Some(SERVICE),
+    path,
+    interface,
+    method,
+    parameters,
+    None,
+    DBusCallFlags::NONE,
+    CALL_TIMEOUT_MS,
+    None::<&gio::Cancellable>,
+  )
+}
+
+fn gatt_error(e: glib::Error) -> Error {
+  Error::Gatt(e.to_string())
+}
+
+/// The objects implementing the interface with their properties.
+fn objects_with(
+  connection: &DBusConnection,
+  interface: &str,
+) -> Result<Vec<(String, Variant)>, Error> {
+  let objects = call(
+    connection,
+    "/",
+    "org.freedesktop.DBus.ObjectManager",
+    "GetManagedObjects",
+    None,
+  )
+  .map_err(|e| Error::Unavailable(e.to_string()))?
+  .child_value(0);
+
+  let mut found = Vec::new();
+  for object in objects.iter() {
+    let path = object.child_value(0);
+    if let Some(properties) = object.child_value(1).lookup_value(interface, None) {
+      found.push((path.str().unwrap_or_default().to_string(), properties));
+    }
+  }
+  Ok(found)
+}
+
+fn string_property(properties: &Variant, name: &str) -> Option<String> {
+  properties
+    .lookup_value(name, None)
+    .and_then(|value| value.str().map(ToString::to_string))
+}
+
+fn advertisement(properties: &Variant) -> Option<Advertisement> {
+  // the RSSI is only set for the devices seen by the current discovery
+  let rssi = properties.lookup_value("RSSI", None)?.get::<i16>();
+  let mut manufacturer_data = HashMap::new();
+  if let Some(data) = properties.lookup_value("ManufacturerData", None) {
+    for entry in data.iter() {
+      let company = entry.child_value(0).get::<u16>();
+      let value = entry
+        .child_value(1)
+        .as_variant()
+        .and_then(|value| value.get::<Vec<u8>>());
+      if let (Some(company), Some(value)) = (company, value) {
+        manufacturer_data.insert(company, value);
+      }
+    }
+  }
+  Some(Advertisement {
+    address: string_property(properties, "Address")?,
+    name: string_property(properties, "Name"),
+    rssi,
+    service_uuids: properties
+      .lookup_value("UUIDs", None)
+      .and_then(|uuids| uuids.get::<Vec<String>>())
+      .unwrap_or_default()
+      .iter()
+      .map(|uuid| normalize_uuid(uuid))
+      .collect(),
+    manufacturer_data,
+  })
+}
+
+pub struct Scan {
+  connection: DBusConnection,
+  adapter: String,
+  stopped: Arc<AtomicBool>,
+}
+
+impl Scan {
+  pub fn start(on_advertisement: OnAdvertisement) -> Result<Self, Error> {
+    let connection = system_bus()?;
+    let (adapter, properties) = objects_with(&connection, ADAPTER_INTERFACE)?
+      .into_iter()
+      .next()
+      .ok_or_else(|| Error::Unavailable("no Bluetooth adapter found".into()))?;
+    let powered = properties
+      .lookup_value("Powered", None)
+      .and_then(|powered| powered.get::<bool>())
+      .unwrap_or_default();
+    if !powered {
+      return Err(Error::Unavailable("the Bluetooth adapter is off".into()));
+    }
+
+    let filter = VariantDict::new(None);
+    filter.insert_value("Transport", &"le".to_variant());
+    // report the devices again when their advertisement changes
+    filter.insert_value("DuplicateData", &true.to_variant());
+    call(
+      &connection,
+      &adapter,
+      ADAPTER_INTERFACE,
+      "SetDiscoveryFilter",
+      Some(&Variant::tuple_from_iter([filter.end()])),
+    )
+    .map_err(|e| Error::Unavailable(e.to_string()))?;
+    call(
+      &connection,
+      &adapter,
+      ADAPTER_INTERFACE,
+      "StartDiscovery",
+      None,
+    )
+    .map_err(|e| Error::Unavailable(e.to_string()))?;
+
+    let stopped = Arc::new(AtomicBool::new(false));
+    let poll_connection = connection.clone();
+    let device_prefix = format!("{adapter}/");
+    let poll_stopped = stopped.clone();
+    std::thread::spawn(move || {
+      let mut last_seen = HashMap::new();
+      while !poll_stopped.load(Ordering::Relaxed) {
+        if let Ok(devices) = objects_with(&poll_connection, DEVICE_INTERFACE) {
+          for (path, properties) in devices {
+            if !path.starts_with(&device_prefix) {
+              continue;
+            }
+            let Some(advertisement) = advertisement(&properties) else {
+              continue;
+            };
+            if last_seen.get(&path) != Some(&advertisement) {
+              last_seen.insert(path, advertisement.clone());
+              on_advertisement(advertisement);
+            }
+          }
+        }
+        std::thread::sleep(SCAN_POLL_INTERVAL);
+      }
+    });
+
+    Ok(Self {
+      connection,
+      adapter,
+      stopped,
+    })
+  }
+}
+
+impl Drop for Scan {
+  fn drop(&mut self) {
+    self.stopped.store(true, Ordering::Relaxed);
+    let _ = call(
+      &self.connection,
+      &self.adapter,
+      ADAPTER_INTERFACE,
+      "StopDiscovery",
+      None,
+    );
+  }
+}
+
+struct Characteristic {
+  uuid: String,
+  path: String,
+  flags: Vec<String>,
+}
+
+struct Service {
+  uuid: String,
+  characteristics: Vec<Characteristic>,
+}
+
+pub struct Device {
+  connection: DBusConnection,
+  path: String,
+  services: Vec<Service>,
+  subscriptions: Mutex<HashMap<String, SignalSubscriptionId>>,
+}
+
+impl Device {
+  pub fn connect(address: &str) -> Result<Self, Error> {
+    let connection = system_bus()?;
+    let (path, _) = objects_with(&connection, DEVICE_INTERFACE)?
+      .into_iter()
+      .find(|(_, properties)| {
+        string_property(properties, "Address").is_some_and(|a| a.eq_ignore_ascii_case(address))
+      })
+      .ok_or_else(|| Error::DeviceNotFound(address.into()))?;
+
+    call(&connection, &path, DEVICE_INTERFACE, "Connect", None).map_err(gatt_error)?;
+
+    let deadline = Instant::now() + SERVICES_RESOLVE_TIMEOUT;
+    loop {
+      let resolved = call(
+        &connection,
+        &path,
+        "org.freedesktop.DBus.Properties",
+        "Get",
+        Some(&(DEVICE_INTERFACE, "ServicesResolved").to_variant()),
+      )
+      .ok()
+      .and_then(|reply| reply.child_value(0).as_variant())
+      .and_then(|resolved| resolved.get::<bool>())
+      .unwrap_or_default();
+      if resolved {
+        break;
+      }
+      if Instant::now() >= deadline {
+        let _ = call(&connection, &path, DEVICE_INTERFACE, "Disconnect", None);
+        return Err(Error::Gatt("the services were not resolved".into()));
+      }
+      std::thread::sleep(Duration::from_millis(100));
+    }
+
+    let characteristics = objects_with(&connection, GATT_CHARACTERISTIC_INTERFACE)?;
+    let services = objects_with(&connection, GATT_SERVICE_INTERFACE)?
+      .into_iter()
+      .filter(|(_, properties)| {
+        properties
+          .lookup_value("Device", None)
+          .is_some_and(|device| device.str() == Some(path.as_str()))
+      })
+      .filter_map(|(service_path, properties)| {
+        Some(Service {
+          uuid: normalize_uuid(&string_property(&properties, "UUID")?),
+          characteristics: characteristics
+            .iter()
+            .filter(|(_, properties)| {
+              properties
+                .lookup_value("Service", None)
+                .is_some_and(|service| service.str() == Some(service_path.as_str()))
+            })
+            .filter_map(|(path, properties)| {
+              Some(Characteristic {
+                uuid: normalize_uuid(&string_property(properties, "UUID")?),
+                path: path.clone(),
+                flags: properties
+                  .lookup_value("Flags", None)
+                  .and_then(|flags| flags.get::<Vec<String>>())
+                  .unwrap_or_default(),
+              })
+            })
+            .collect(),
+        })
+      })
+      .collect();
+
+    Ok(Self {
+      connection,
+      path,
+      services,
+      subscriptions: Default::default(),
+    })
+  }
+
+  pub fn services(&self) -> Vec<GattService> {
+    self
+      .services
+      .iter()
+      .map(|service| GattService {
+        uuid: service.uuid.clone(),
+        characteristics: service
+          .characteristics
+          .iter()
+          .map(|characteristic| {
+            let has = |flag: &str| characteristic.flags.iter().any(|f| f == flag);
+            GattCharacteristic {
+              uuid: characteristic.uuid.clone(),
+              properties: CharacteristicProperties {
+                read: has("read"),
+                write: has("write"),
+                write_without_response: has("write-without-response"),
+                notify: has("notify"),
+                indicate: has("indicate"),
+              },
+            }
+          })
+          .collect(),
+      })
+      .collect()
+  }
+
+  fn characteristic(&self, service: &str, characteristic: &str) -> Result<&str, Error> {
+    self
+      .services
+      .iter()
+      .filter(|s| s.uuid == service)
+      .flat_map(|s| &s.characteristics)
+      .find(|c| c.uuid == characteristic)
+      .map(|c| c.path.as_str())
+      .ok_or_else(|| Error::CharacteristicNotFound {
+        service: service.into(),
+        characteristic: characteristic.into(),
+      })
+  }
+
+  pub fn read(&self, service: &str, characteristic: &str) -> Result<Vec<u8>, Error> {
+    let path = self.characteristic(service, characteristic)?;
+    let reply = call(
+      &self.connection,
+      path,
+      GATT_CHARACTERISTIC_INTERFACE,
+      "ReadValue",
+      Some(&Variant::tuple_from_iter([VariantDict::new(None).end()])),
+    )
+    .map_err(gatt_error)?;
+    reply
+      .child_value(0)
+      .get::<Vec<u8>>()
+      .ok_or_else(|| Error::Gatt("unexpected ReadValue reply".into()))
+  }
+
+  pub fn write(
+    &self,
+    service: &str,
+    characteristic: &str,
+    value: &[u8],
+    write_type: WriteType,
+  ) -> Result<(), Error> {
+    let path = self.characteristic(service, characteristic)?;
+    let options = VariantDict::new(None);
+    options.insert_value(
+      "type",
+      &match write_type {
+        WriteType::WithResponse => "request",
+        WriteType::WithoutResponse => "command",
+      }
+      .to_variant(),
+    );
+    call(
+      &self.connection,
+      path,
+      GATT_CHARACTERISTIC_INTERFACE,
+      "WriteValue",
+      Some(&Variant::tuple_from_iter([
+        Variant::array_from_fixed_array(value),
+        options.end(),
+      ])),
+    )
+    .map(|_| ())
+    .map_err(gatt_error)
+  }
+
+  pub fn subscribe(
+    &self,
+    service: &str,
+    characteristic: &str,
+    on_value: OnValue,
+  ) -> Result<(), Error> {
+    let path = self.characteristic(service, characteristic)?;
+    let mut subscriptions = self.subscriptions.lock().unwrap();
+    if subscriptions.contains_key(path) {
+      return Ok(());
+    }
+
+    // the signals are dispatched by the main context of the event loop
+    let subscription = self.connection.signal_subscribe(
+      Some(SERVICE),
+      Some("org.freedesktop.DBus.Properties"),
+      Some("PropertiesChanged"),
+      Some(path),
+      Some(GATT_CHARACTERISTIC_INTERFACE),
+      DBusSignalFlags::NONE,
+      move |_, _, _, _, _, parameters| {
+        if let Some(value) = parameters
+          .child_value(1)
+          .lookup_value("Value", None)
+          .and_then(|value| value.get::<Vec<u8>>())
+        {
+          on_value(value);
+        }
+      },
+    );
+    if let Err(e) = call(
+      &self.connection,
+      path,
+      GATT_CHARACTERISTIC_INTERFACE,
+      "StartNotify",
+      None,
+    ) {
+      self.connection.signal_unsubscribe(subscription);
+      return Err(gatt_error(e));
+    }
+
+    subscriptions.insert(path.to_string(), subscription);
+    Ok(())
+  }
+
+  pub fn unsubscribe(&self, service: &str, characteristic: &str) -> Result<(), Error> {
+    let path = self.characteristic(service, characteristic)?;
+    let Some(subscription) = self.subscriptions.lock().unwrap().remove(path) else {
+      return Ok(());
+    };
+    self.connection.signal_unsubscribe(subscription);
+    call(
+      &self.connection,
+      path,
+      GATT_CHARACTERISTIC_INTERFACE,
+      "StopNotify",
+      None,
+    )
+    .map(|_| ())
+    .map_err(gatt_error)
+  }
+}
+
+impl Drop for Device {
+  fn drop(&mut self) {
+    for (_, subscription) in self.subscriptions.lock().unwrap().drain() {
+      self.connection.signal_unsubscribe(subscription);
+    }
+    let _ = call(
+      &self.connection,
+      &self.path,
+      DEVICE_INTERFACE,
+      "Disconnect",
+      None,
+    );
+  }
+}
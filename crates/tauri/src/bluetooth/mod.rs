@@ -0,0 +1,459 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Bluetooth Low Energy scanning and GATT client, since Web Bluetooth is not available in the webviews.
+//!
+//! The [`Bluetooth`] instance is accessed with [`Manager::bluetooth`](crate::Manager::bluetooth).
+//! Advertisements and characteristic notifications are delivered to the handler given to
+//! [`Bluetooth::start_scan`] and [`Bluetooth::subscribe`].
+//!
+//! The webviews use the `core:bluetooth` commands, which are not enabled by the default permission set.
+//! The scan and subscribe commands deliver the advertisements and notifications through an [`ipc::Channel`](crate::ipc::Channel)
+//! given by the caller, so they are only sent to the webview that requested them.
+//!
+//! UUIDs are written in their lowercase 128-bit form, e.g. `0000180d-0000-1000-8000-00805f9b34fb`,
+//! and the 16-bit and 32-bit short forms such as `180d` are accepted as input.
+//! Devices are identified by their address in the `AA:BB:CC:DD:EE:FF` form.
+
+use std::{
+  collections::HashMap,
+  marker::PhantomData,
+  sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Runtime;
+
+pub(crate) mod plugin;
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod bluez;
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+use bluez as platform;
+
+#[cfg(windows)]
+mod winrt;
+#[cfg(windows)]
+use winrt as platform;
+
+#[cfg(not(any(
+  windows,
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+)))]
+mod unsupported;
+#[cfg(not(any(
+  windows,
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+)))]
+use unsupported as platform;
+
+/// The base of the 128-bit UUIDs of the 16-bit and 32-bit Bluetooth SIG assigned numbers.
+const BASE_UUID_SUFFIX: &str = "-0000-1000-8000-00805f9b34fb";
+
+/// Bluetooth errors.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+  /// No Bluetooth adapter is available, it is turned off or the platform is not supported.
+  #[error("Bluetooth is not available: {0}")]
+  Unavailable(String),
+  /// The device was not found, it must be advertising or known to the system.
+  #[error("Bluetooth device {0} not found")]
+  DeviceNotFound(String),
+  /// The device is not connected with [`Bluetooth::connect`].
+  #[error("Bluetooth device {0} is not connected")]
+  NotConnected(String),
+  /// The service or the characteristic was not found on the device.
+  #[error("characteristic {characteristic} of the service {service} not found")]
+  CharacteristicNotFound {
+    /// The service UUID.
+    service: String,
+    /// The characteristic UUID.
+    characteristic: String,
+  },
+  /// The GATT operation failed.
+  #[error("GATT operation failed: {0}")]
+  Gatt(String),
+}
+
+/// An advertisement received while scanning.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Advertisement {
+  /// The address of the device.
+  pub address: String,
+  /// The local name of the device.
+  pub name: Option<String>,
+  /// The received signal strength in dBm.
+  pub rssi: Option<i16>,
+  /// The advertised service UUIDs.
+  pub service_uuids: Vec<String>,
+  /// The manufacturer specific data by company identifier.
+  pub manufacturer_data: HashMap<u16, Vec<u8>>,
+}
+
+/// The operations supported by a characteristic.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct CharacteristicProperties {
+  /// The value can be read with [`Bluetooth::read`].
+  pub read: bool,
+  /// The value can be written with a response.
+  pub write: bool,
+  /// The value can be written without a response.
+  pub write_without_response: bool,
+  /// The value changes can be notified with [`Bluetooth::subscribe`].
+  pub notify: bool,
+  /// The value changes can be indicated with [`Bluetooth::subscribe`].
+  pub indicate: bool,
+}
+
+/// A characteristic of a GATT service.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct GattCharacteristic {
+  /// The characteristic UUID.
+  pub uuid: String,
+  /// The supported operations.
+  pub properties: CharacteristicProperties,
+}
+
+/// A GATT service of a connected device.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct GattService {
+  /// The service UUID.
+  pub uuid: String,
+  /// The characteristics of the service.
+  pub characteristics: Vec<GattCharacteristic>,
+}
+
+/// A new value of a characteristic subscribed with [`Bluetooth::subscribe`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Notification {
+  /// The address of the device.
+  pub address: String,
+  /// The service UUID.
+  pub service: String,
+  /// The characteristic UUID.
+  pub characteristic: String,
+  /// The new value.
+  pub value: Vec<u8>,
+}
+
+/// The write type of [`Bluetooth::write`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WriteType {
+  /// The device acknowledges the write.
+  #[default]
+  WithResponse,
+  /// The write is not acknowledged, which is faster but can be lost.
+  WithoutResponse,
+}
+
+/// The Bluetooth Low Energy central of the app.
+///
+/// See the [module documentation](self).
+pub struct Bluetooth<R: Runtime> {
+  scan: Mutex<Option<platform::Scan>>,
+  devices: Mutex<HashMap<String, Arc<platform::Device>>>,
+  _runtime: PhantomData<fn() -> R>,
+}
+
+impl<R: Runtime> Bluetooth<R> {
+  pub(crate) fn new() -> Self {
+    Self {
+      scan: Default::default(),
+      devices: Default::default(),
+      _runtime: PhantomData,
+    }
+  }
+
+  /// Starts scanning for advertisements, calling `on_advertisement` for each of them.
+  ///
+  /// When `services` is not empty, only the devices advertising one of these services are reported.
+  /// A scan already in progress is replaced, along with its handler.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::Manager;
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     // heart rate monitors
+  ///     app.bluetooth().start_scan(&["180d"], |advertisement| {
+  ///       println!("found {}", advertisement.address);
+  ///     })?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  pub fn start_scan<S, F>(&self, services: &[S], on_advertisement: F) -> crate::Result<()>
+  where
+    S: AsRef<str>,
+    F: Fn(Advertisement) + Send + Sync + 'static,
+  {
+    let services = services
+      .iter()
+      .map(|service| normalize_uuid(service.as_ref()))
+      .collect::<Vec<_>>();
+    let on_advertisement = move |advertisement: Advertisement| {
+      if services.is_empty()
+        || advertisement
+          .service_uuids
+          .iter()
+          .any(|uuid| services.contains(uuid))
+      {
+        on_advertisement(advertisement);
+      }
+    };
+
+    let mut scan = self.scan.lock().unwrap();
+    // stop the previous scan before the adapter is asked to start a new one
+    scan.take();
+    scan.replace(platform::Scan::start(Box::new(on_advertisement))?);
+    Ok(())
+  }
+
+  /// Stops the scan started with [`Self::start_scan`].
+  pub fn stop_scan(&self) {
+    self.scan.lock().unwrap().take();
+  }
+
+  /// Connects to the device and discovers its GATT services.
+  ///
+  /// This function blocks until the services are discovered, so it must not be called on the main thread.
+  pub fn connect(&self, address: &str) -> crate::Result<Vec<GattService>> {
+    let address = address.to_uppercase();
+    let device = Arc::new(platform::Device::connect(&address)?);
+    let services = device.services();
+    self.devices.lock().unwrap().insert(address, device);
+    Ok(services)
+  }
+
+  /// Disconnects from the device, cancelling its subscriptions.
+  pub fn disconnect(&self, address: &str) {
+    self.devices.lock().unwrap().remove(&address.to_uppercase());
+  }
+
+  fn device(&self, address: &str) -> Result<Arc<platform::Device>, Error> {
+    let address = address.to_uppercase();
+    self
+      .devices
+      .lock()
+      .unwrap()
+      .get(&address)
+      .cloned()
+      .ok_or(Error::NotConnected(address))
+  }
+
+  /// Reads the value of a characteristic.
+  ///
+  /// This function blocks until the device responds, so it must not be called on the main thread.
+  pub fn read(&self, address: &str, service: &str, characteristic: &str) -> crate::Result<Vec<u8>> {
+    self
+      .device(address)?
+      .read(&normalize_uuid(service), &normalize_uuid(characteristic))
+      .map_err(Into::into)
+  }
+
+  /// Writes the value of a characteristic.
+  ///
+  /// This function blocks until the device responds, so it must not be called on the main thread.
+  pub fn write(
+    &self,
+    address: &str,
+    service: &str,
+    characteristic: &str,
+    value: &[u8],
+    write_type: WriteType,
+  ) -> crate::Result<()> {
+    self
+      .device(address)?
+      .write(
+        &normalize_uuid(service),
+        &normalize_uuid(characteristic),
+        value,
+        write_type,
+      )
+      .map_err(Into::into)
+  }
+
+  /// Subscribes to the notifications or indications of a characteristic,
+  /// calling `on_notification` when its value changes.
+  ///
+  /// Subscribing again to the same characteristic replaces the handler.
+  /// This function blocks until the device responds, so it must not be called on the main thread.
+  pub fn subscribe<F>(
+    &self,
+    address: &str,
+    service: &str,
+    characteristic: &str,
+    on_notification: F,
+  ) -> crate::Result<()>
+  where
+    F: Fn(Notification) + Send + Sync + 'static,
+  {
+    let device = self.device(address)?;
+    let notification = Notification {
+      address: address.to_uppercase(),
+      service: normalize_uuid(service),
+      characteristic: normalize_uuid(characteristic),
+      value: Vec::new(),
+    };
+    let (service, characteristic) = (
+      notification.service.clone(),
+      notification.characteristic.clone(),
+    );
+    // the platforms keep the handler of an existing subscription
+    device.unsubscribe(&service, &characteristic)?;
+    device
+      .subscribe(
+        &service,
+        &characteristic,
+        Box::new(move |value| {
+          on_notification(Notification {
+            value,
+            ..notification.clone()
+          })
+        }),
+      )
+      .map_err(Into::into)
+  }
+
+  /// Stops the notifications of a characteristic.
+  ///
+  /// This function blocks until the device responds, so it must not be called on the main thread.
+  pub fn unsubscribe(
+    &self,
+    address: &str,
+    service: &str,
+    characteristic: &str,
+  ) -> crate::Result<()> {
+    self
+      .device(address)?
+      .unsubscribe(&normalize_uuid(service), &normalize_uuid(characteristic))
+      .map_err(Into::into)
+  }
+}
+
+/// Called by the platforms for each received advertisement.
+type OnAdvertisement = Box<dyn Fn(Advertisement) + Send + Sync>;
+/// Called by the platforms with the new value of a subscribed characteristic.
+type OnValue = Box<dyn Fn(Vec<u8>) + Send + Sync>;
+
+/// Expands the short UUID forms and lowercases the UUID.
+fn normalize_uuid(uuid: &str) -> String {
+  let uuid = uuid.trim().to_lowercase();
+  match uuid.len() {
+    4 => format!("0000{uuid}{BASE_UUID_SUFFIX}"),
+    8 => format!("{uuid}{BASE_UUID_SUFFIX}"),
+    _ => uuid,
+  }
+}
+
+/// Formats a 48-bit address stored in an integer, as used by Windows.
+#[cfg_attr(not(any(windows, test)), allow(dead_code))]
+fn format_address(address: u64) -> String {
+  (0..6)
+    .rev()
+    .map(|byte| format!("{:02X}", (address >> (byte * 8)) & 0xff))
+    .collect::<Vec<_>>()
+    .join(":")
+}
+
+/// Parses an `AA:BB:CC:DD:EE:FF` address to an integer, as used by Windows.
+#[cfg_attr(not(any(windows, test)), allow(dead_code))]
+fn parse_address(address: &str) -> Option<u64> {
+  let bytes = address.split(':').collect::<Vec<_>>();
+  if bytes.len() != 6 {
+    return None;
+  }
+  bytes.iter().try_fold(0u64, |address, byte| {
+    (byte.len() == 2)
+      .then(|| u8::from_str_radix(byte, 16).ok())
+      .flatten()
+      .map(|byte| address << 8 | u64::from(byte))
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn normalizes_uuids() {
+    assert_eq!(
+      normalize_uuid("180D"),
+      "0000180d-0000-1000-8000-00805f9b34fb"
+    );
+    assert_eq!(
+      normalize_uuid("0000180d"),
+      "0000180d-0000-1000-8000-00805f9b34fb"
+    );
+    assert_eq!(
+      normalize_uuid("6E400001-B5A3-F393-E0A9-E50E24DCCA9E"),
+      "6e400001-b5a3-f393-e0a9-e50e24dcca9e"
+    );
+  }
+
+  #[test]
+  fn formats_and_parses_addresses() {
+    assert_eq!(format_address(0x0000_a1b2_c3d4_e5f6), "A1:B2:C3:D4:E5:F6");
+    assert_eq!(parse_address("A1:B2:C3:D4:E5:F6"), Some(0xa1b2_c3d4_e5f6));
+    assert_eq!(parse_address("a1:b2:c3:d4:e5:f6"), Some(0xa1b2_c3d4_e5f6));
+    assert_eq!(parse_address("A1:B2:C3:D4:E5"), None);
+    assert_eq!(parse_address("A1:B2:C3:D4:E5:GG"), None);
+    assert_eq!(parse_address("A1:B2:C3:D4:E5:F"), None);
+  }
+
+  #[test]
+  fn serializes_advertisement() {
+    let advertisement = Advertisement {
+      address: "A1:B2:C3:D4:E5:F6".into(),
+      name: Some("HRM".into()),
+      rssi: Some(-60),
+      service_uuids: vec![normalize_uuid("180d")],
+      manufacturer_data: HashMap::from([(0x004c, vec![1, 2])]),
+    };
+    assert_eq!(
+      serde_json::to_value(advertisement).unwrap(),
+      serde_json::json!({
+        "address": "A1:B2:C3:D4:E5:F6",
+        "name": "HRM",
+        "rssi": -60,
+        "serviceUuids": ["0000180d-0000-1000-8000-00805f9b34fb"],
+        "manufacturerData": { "76": [1, 2] }
+      })
+    );
+  }
+}
@@ -0,0 +1,124 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::{Advertisement, GattService, Notification, WriteType};
+use crate::{
+  command,
+  ipc::Channel,
+  plugin::{Builder, TauriPlugin},
+  AppHandle, Manager, Runtime,
+};
+
+#[command(root = "crate")]
+fn start_scan<R: Runtime>(
+  app: AppHandle<R>,
+  services: Option<Vec<String>>,
+  on_advertisement: Channel<Advertisement>,
+) -> crate::Result<()> {
+  app
+    .bluetooth()
+    .start_scan(&services.unwrap_or_default(), move |advertisement| {
+      let _ = on_advertisement.send(advertisement);
+    })
+}
+
+#[command(root = "crate")]
+fn stop_scan<R: Runtime>(app: AppHandle<R>) {
+  app.bluetooth().stop_scan();
+}
+
+#[command(root = "crate")]
+async fn connect<R: Runtime>(
+  app: AppHandle<R>,
+  address: String,
+) -> crate::Result<Vec<GattService>> {
+  crate::async_runtime::spawn_blocking(move || app.bluetooth().connect(&address)).await?
+}
+
+#[command(root = "crate")]
+fn disconnect<R: Runtime>(app: AppHandle<R>, address: String) {
+  app.bluetooth().disconnect(&address);
+}
+
+#[command(root = "crate")]
+async fn read<R: Runtime>(
+  app: AppHandle<R>,
+  address: String,
+  service: String,
+  characteristic: String,
+) -> crate::Result<Vec<u8>> {
+  crate::async_runtime::spawn_blocking(move || {
+    app.bluetooth().read(&address, &service, &characteristic)
+  })
+  .await?
+}
+
+#[command(root = "crate")]
+async fn write<R: Runtime>(
+  app: AppHandle<R>,
+  address: String,
+  service: String,
+  characteristic: String,
+  value: Vec<u8>,
+  write_type: Option<WriteType>,
+) -> crate::Result<()> {
+  crate::async_runtime::spawn_blocking(move || {
+    app.bluetooth().write(
+      &address,
+      &service,
+      &characteristic,
+      &value,
+      write_type.unwrap_or_default(),
+    )
+  })
+  .await?
+}
+
+#[command(root = "crate")]
+async fn subscribe<R: Runtime>(
+  app: AppHandle<R>,
+  address: String,
+  service: String,
+  characteristic: String,
+  on_notification: Channel<Notification>,
+) -> crate::Result<()> {
+  crate::async_runtime::spawn_blocking(move || {
+    app
+      .bluetooth()
+      .subscribe(&address, &service, &characteristic, move |notification| {
+        let _ = on_notification.send(notification);
+      })
+  })
+  .await?
+}
+
+#[command(root = "crate")]
+async fn unsubscribe<R: Runtime>(
+  app: AppHandle<R>,
+  address: String,
+  service: String,
+  characteristic: String,
+) -> crate::Result<()> {
+  crate::async_runtime::spawn_blocking(move || {
+    app
+      .bluetooth()
+      .unsubscribe(&address, &service, &characteristic)
+  })
+  .await?
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("bluetooth")
+    .invoke_handler(crate::generate_handler![
+      start_scan,
+      stop_scan,
+      connect,
+      disconnect,
+      read,
+      write,
+      subscribe,
+      unsubscribe,
+    ])
+    .build()
+}
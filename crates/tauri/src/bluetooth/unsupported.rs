@@ -0,0 +1,58 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Backend of the platforms without Bluetooth support.
+
+use super::{Error, GattService, OnAdvertisement, OnValue, WriteType};
+
+fn unavailable() -> Error {
+  Error::Unavailable("unsupported platform".into())
+}
+
+pub struct Scan;
+
+impl Scan {
+  pub fn start(_on_advertisement: OnAdvertisement) -> Result<Self, Error> {
+    Err(unavailable())
+  }
+}
+
+pub struct Device;
+
+impl Device {
+  pub fn connect(_address: &str) -> Result<Self, Error> {
+    Err(unavailable())
+  }
+
+  pub fn services(&self) -> Vec<GattService> {
+    Vec::new()
+  }
+
+  pub fn read(&self, _service: &str, _characteristic: &str) -> Result<Vec<u8>, Error> {
+    Err(unavailable())
+  }
+
+  pub fn write(
+    &self,
+    _service: &str,
+    _characteristic: &str,
+    _value: &[u8],
+    _write_type: WriteType,
+  ) -> Result<(), Error> {
+    Err(unavailable())
+  }
+
+  pub fn subscribe(
+    &self,
+    _service: &str,
+    _characteristic: &str,
+    _on_value: OnValue,
+  ) -> Result<(), Error> {
+    Err(unavailable())
+  }
+
+  pub fn unsubscribe(&self, _service: &str, _characteristic: &str) -> Result<(), Error> {
+    Err(unavailable())
+  }
+}
@@ -0,0 +1,308 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Windows.Devices.Bluetooth backend.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use windows::{
+  core::GUID,
+  Devices::Bluetooth::{
+    Advertisement::{
+      BluetoothLEAdvertisementReceivedEventArgs, BluetoothLEAdvertisementWatcher,
+      BluetoothLEScanningMode,
+    },
+    BluetoothCacheMode, BluetoothLEDevice,
+    GenericAttributeProfile::{
+      GattCharacteristic, GattCharacteristicProperties,
+      GattClientCharacteristicConfigurationDescriptorValue, GattCommunicationStatus,
+      GattDeviceService, GattValueChangedEventArgs, GattWriteOption,
+    },
+  },
+  Foundation::{EventRegistrationToken, TypedEventHandler},
+  Storage::Streams::{DataReader, DataWriter, IBuffer},
+};
+
+use super::{
+  format_address, parse_address, Advertisement, CharacteristicProperties, Error,
+  GattCharacteristic as Characteristic, GattService, OnAdvertisement, OnValue, WriteType,
+};
+
+fn uuid(guid: GUID) -> String {
+  format!("{guid:?}").to_lowercase()
+}
+
+fn buffer_bytes(buffer: &IBuffer) -> windows::core::Result<Vec<u8>> {
+  let reader = DataReader::FromBuffer(buffer)?;
+  let mut bytes = vec![0u8; buffer.Length()? as usize];
+  reader.ReadBytes(&mut bytes)?;
+  Ok(bytes)
+}
+
+fn gatt_error(e: windows::core::Error) -> Error {
+  Error::Gatt(e.to_string())
+}
+
+fn check(status: GattCommunicationStatus) -> Result<(), Error> {
+  if status == GattCommunicationStatus::Success {
+    Ok(())
+  } else {
+    Err(Error::Gatt(format!("{status:?}")))
+  }
+}
+
+fn advertisement(
+  args: &BluetoothLEAdvertisementReceivedEventArgs,
+) -> windows::core::Result<Advertisement> {
+  let data = args.Advertisement()?;
+  let name = data.LocalName()?.to_string();
+  let mut manufacturer_data = HashMap::new();
+  for entry in data.ManufacturerData()? {
+    manufacturer_data.insert(entry.CompanyId()?, buffer_bytes(&entry.Data()?)?);
+  }
+  Ok(Advertisement {
+    address: format_address(args.BluetoothAddress()?),
+    name: (!name.is_empty()).then_some(name),
+    rssi: args.RawSignalStrengthInDBm().ok(),
+    service_uuids: data.ServiceUuids()?.into_iter().map(uuid).collect(),
+    manufacturer_data,
+  })
+}
+
+pub struct Scan {
+  watcher: BluetoothLEAdvertisementWatcher,
+}
+
+impl Scan {
+  pub fn start(on_advertisement: OnAdvertisement) -> Result<Self, Error> {
+    let unavailable = |e: windows::core::Error| Error::Unavailable(e.to_string());
+    let watcher = BluetoothLEAdvertisementWatcher::new().map_err(unavailable)?;
+    // active scanning also requests the scan responses, which usually hold the device name
+    watcher
+      .SetScanningMode(BluetoothLEScanningMode::Active)
+      .map_err(unavailable)?;
+    watcher
+      .Received(&TypedEventHandler::new(
+        move |_, args: &Option<BluetoothLEAdvertisementReceivedEventArgs>| {
+          if let Some(args) = args {
+            on_advertisement(advertisement(args)?);
+          }
+          Ok(())
+        },
+      ))
+      .map_err(unavailable)?;
+    watcher.Start().map_err(unavailable)?;
+    Ok(Self { watcher })
+  }
+}
+
+impl Drop for Scan {
+  fn drop(&mut self) {
+    let _ = self.watcher.Stop();
+  }
+}
+
+pub struct Device {
+  device: BluetoothLEDevice,
+  services: Vec<(GattDeviceService, Vec<GattCharacteristic>)>,
+  subscriptions: Mutex<HashMap<(String, String), EventRegistrationToken>>,
+}
+
+impl Device {
+  pub fn connect(address: &str) -> Result<Self, Error> {
+    let not_found = |_| Error::DeviceNotFound(address.into());
+    let device = BluetoothLEDevice::FromBluetoothAddressAsync(
+      parse_address(address).ok_or_else(|| Error::DeviceNotFound(address.into()))?,
+    )
+    .and_then(|operation| operation.get())
+    .map_err(not_found)?;
+
+    // accessing the GATT services establishes the connection
+    let result = device
+      .GetGattServicesWithCacheModeAsync(BluetoothCacheMode::Uncached)
+      .and_then(|operation| operation.get())
+      .map_err(gatt_error)?;
+    check(result.Status().map_err(gatt_error)?)?;
+
+    let mut services = Vec::new();
+    for service in result.Services().map_err(gatt_error)? {
+      let result = service
+        .GetCharacteristicsWithCacheModeAsync(BluetoothCacheMode::Uncached)
+        .and_then(|operation| operation.get())
+        .map_err(gatt_error)?;
+      check(result.Status().map_err(gatt_error)?)?;
+      let characteristics = result
+        .Characteristics()
+        .map_err(gatt_error)?
+        .into_iter()
+        .collect();
+      services.push((service, characteristics));
+    }
+
+    Ok(Self {
+      device,
+      services,
+      subscriptions: Default::default(),
+    })
+  }
+
+  pub fn services(&self) -> Vec<GattService> {
+    self
+      .services
+      .iter()
+      .filter_map(|(service, characteristics)| {
+        Some(GattService {
+          uuid: uuid(service.Uuid().ok()?),
+          characteristics: characteristics
+            .iter()
+            .filter_map(|characteristic| {
+              let properties = characteristic.CharacteristicProperties().ok()?;
+              Some(Characteristic {
+                uuid: uuid(characteristic.Uuid().ok()?),
+                properties: CharacteristicProperties {
+                  read: properties.contains(GattCharacteristicProperties::Read),
+                  write: properties.contains(GattCharacteristicProperties::Write),
+                  write_without_response: properties
+                    .contains(GattCharacteristicProperties::WriteWithoutResponse),
+                  notify: properties.contains(GattCharacteristicProperties::Notify),
+                  indicate: properties.contains(GattCharacteristicProperties::Indicate),
+                },
+              })
+            })
+            .collect(),
+        })
+      })
+      .collect()
+  }
+
+  fn characteristic(
+    &self,
+    service: &str,
+    characteristic: &str,
+  ) -> Result<&GattCharacteristic, Error> {
+    self
+      .services
+      .iter()
+      .filter(|(s, _)| s.Uuid().is_ok_and(|uuid| self::uuid(uuid) == service))
+      .flat_map(|(_, characteristics)| characteristics)
+      .find(|c| {
+        c.Uuid()
+          .is_ok_and(|uuid| self::uuid(uuid) == characteristic)
+      })
+      .ok_or_else(|| Error::CharacteristicNotFound {
+        service: service.into(),
+        characteristic: characteristic.into(),
+      })
+  }
+
+  pub fn read(&self, service: &str, characteristic: &str) -> Result<Vec<u8>, Error> {
+    let result = self
+      .characteristic(service, characteristic)?
+      .ReadValueWithCacheModeAsync(BluetoothCacheMode::Uncached)
+      .and_then(|operation| operation.get())
+      .map_err(gatt_error)?;
+    check(result.Status().map_err(gatt_error)?)?;
+    result
+      .Value()
+      .and_then(|value| buffer_bytes(&value))
+      .map_err(gatt_error)
+  }
+
+  pub fn write(
+    &self,
+    service: &str,
+    characteristic: &str,
+    value: &[u8],
+    write_type: WriteType,
+  ) -> Result<(), Error> {
+    let characteristic = self.characteristic(service, characteristic)?;
+    let writer = DataWriter::new().map_err(gatt_error)?;
+    writer.WriteBytes(value).map_err(gatt_error)?;
+    let buffer = writer.DetachBuffer().map_err(gatt_error)?;
+    let option = match write_type {
+      WriteType::WithResponse => GattWriteOption::WriteWithResponse,
+      WriteType::WithoutResponse => GattWriteOption::WriteWithoutResponse,
+    };
+    check(
+      characteristic
+        .WriteValueWithOptionAsync(&buffer, option)
+        .and_then(|operation| operation.get())
+        .map_err(gatt_error)?,
+    )
+  }
+
+  pub fn subscribe(
+    &self,
+    service: &str,
+    characteristic: &str,
+    on_value: OnValue,
+  ) -> Result<(), Error> {
+    let key = (service.to_string(), characteristic.to_string());
+    let characteristic = self.characteristic(service, characteristic)?;
+    let mut subscriptions = self.subscriptions.lock().unwrap();
+    if subscriptions.contains_key(&key) {
+      return Ok(());
+    }
+
+    let properties = characteristic
+      .CharacteristicProperties()
+      .map_err(gatt_error)?;
+    let descriptor = if properties.contains(GattCharacteristicProperties::Notify) {
+      GattClientCharacteristicConfigurationDescriptorValue::Notify
+    } else {
+      GattClientCharacteristicConfigurationDescriptorValue::Indicate
+    };
+
+    let token = characteristic
+      .ValueChanged(&TypedEventHandler::new(
+        move |_, args: &Option<GattValueChangedEventArgs>| {
+          if let Some(args) = args {
+            on_value(buffer_bytes(&args.CharacteristicValue()?)?);
+          }
+          Ok(())
+        },
+      ))
+      .map_err(gatt_error)?;
+    let status = characteristic
+      .WriteClientCharacteristicConfigurationDescriptorAsync(descriptor)
+      .and_then(|operation| operation.get())
+      .map_err(gatt_error);
+    if let Err(e) = status.and_then(check) {
+      let _ = characteristic.RemoveValueChanged(token);
+      return Err(e);
+    }
+
+    subscriptions.insert(key, token);
+    Ok(())
+  }
+
+  pub fn unsubscribe(&self, service: &str, characteristic: &str) -> Result<(), Error> {
+    let key = (service.to_string(), characteristic.to_string());
+    let Some(token) = self.subscriptions.lock().unwrap().remove(&key) else {
+      return Ok(());
+    };
+    let characteristic = self.characteristic(service, &key.1)?;
+    characteristic
+      .RemoveValueChanged(token)
+      .map_err(gatt_error)?;
+    check(
+      characteristic
+        .WriteClientCharacteristicConfigurationDescriptorAsync(
+          GattClientCharacteristicConfigurationDescriptorValue::None,
+        )
+        .and_then(|operation| operation.get())
+        .map_err(gatt_error)?,
+    )
+  }
+}
+
+impl Drop for Device {
+  fn drop(&mut self) {
+    // the connection is closed once all the objects referencing the device are closed
+    for (service, _) in &self.services {
+      let _ = service.Close();
+    }
+    let _ = self.device.Close();
+  }
+}
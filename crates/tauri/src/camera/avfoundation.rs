@@ -0,0 +1,194 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! AVFoundation backend.
+
+use std::sync::mpsc::channel;
+
+use block2::RcBlock;
+use objc2::{
+  class, msg_send, msg_send_id,
+  rc::Retained,
+  runtime::{AnyObject, Bool},
+  Encoding, RefEncode,
+};
+use objc2_foundation::{NSData, NSString};
+
+use super::{Camera, Error, FRAME_TIMEOUT, WARM_UP};
+
+#[link(name = "AVFoundation", kind = "framework")]
+extern "C" {
+  static AVMediaTypeVideo: &'static NSString;
+}
+
+/// The `CMSampleBuffer` the still images are delivered in.
+#[repr(C)]
+struct OpaqueSampleBuffer {
+  _private: [u8; 0],
+}
+
+unsafe impl RefEncode for OpaqueSampleBuffer {
+  const ENCODING_REF: Encoding = Encoding::Pointer(&Encoding::Struct("opaqueCMSampleBuffer", &[]));
+}
+
+/// `NSBitmapImageFileTypePNG`
+const BITMAP_IMAGE_FILE_TYPE_PNG: usize = 4;
+
+/// `AVAuthorizationStatus`
+const AUTHORIZATION_STATUS_NOT_DETERMINED: isize = 0;
+const AUTHORIZATION_STATUS_AUTHORIZED: isize = 3;
+
+fn capture_error(message: &str) -> Error {
+  Error::Capture(message.into())
+}
+
+/// Prompts the user for the camera access if it was not granted or denied yet.
+fn request_access() -> Result<(), Error> {
+  unsafe {
+    let status: isize = msg_send![
+      class!(AVCaptureDevice),
+      authorizationStatusForMediaType: AVMediaTypeVideo
+    ];
+    match status {
+      AUTHORIZATION_STATUS_AUTHORIZED => Ok(()),
+      AUTHORIZATION_STATUS_NOT_DETERMINED => {
+        let (tx, rx) = channel();
+        let handler = RcBlock::new(move |granted: Bool| {
+          let _ = tx.send(granted.as_bool());
+        });
+        let _: () = msg_send![
+          class!(AVCaptureDevice),
+          requestAccessForMediaType: AVMediaTypeVideo,
+          completionHandler: &*handler
+        ];
+        match rx.recv() {
+          Ok(true) => Ok(()),
+          _ => Err(Error::Denied),
+        }
+      }
+      _ => Err(Error::Denied),
+    }
+  }
+}
+
+unsafe fn camera(device: &AnyObject) -> Camera {
+  let id: Retained<NSString> = msg_send_id![device, uniqueID];
+  let label: Retained<NSString> = msg_send_id![device, localizedName];
+  Camera {
+    id: id.to_string(),
+    label: label.to_string(),
+  }
+}
+
+pub fn cameras() -> Result<Vec<Camera>, Error> {
+  unsafe {
+    let default: Option<Retained<AnyObject>> = msg_send_id![
+      class!(AVCaptureDevice),
+      defaultDeviceWithMediaType: AVMediaTypeVideo
+    ];
+    let default = default.map(|device| camera(&device));
+
+    let devices: Retained<AnyObject> = msg_send_id![
+      class!(AVCaptureDevice),
+      devicesWithMediaType: AVMediaTypeVideo
+    ];
+    let count: usize = msg_send![&devices, count];
+    let mut cameras = Vec::with_capacity(count);
+    cameras.extend(default.clone());
+    for i in 0..count {
+      let device: Retained<AnyObject> = msg_send_id![&devices, objectAtIndex: i];
+      let found = camera(&device);
+      if Some(&found) != default.as_ref() {
+        cameras.push(found);
+      }
+    }
+    Ok(cameras)
+  }
+}
+
+/// Captures a JPEG still image from the running session.
+unsafe fn capture_jpeg(output: &AnyObject) -> Result<Vec<u8>, Error> {
+  let connection: Option<Retained<AnyObject>> =
+    msg_send_id![output, connectionWithMediaType: AVMediaTypeVideo];
+  let connection = connection.ok_or_else(|| capture_error("the camera has no video connection"))?;
+
+  let (tx, rx) = channel();
+  let handler = RcBlock::new(
+    move |buffer: *mut OpaqueSampleBuffer, _error: *mut AnyObject| {
+      let jpeg = if buffer.is_null() {
+        None
+      } else {
+        let data: Option<Retained<NSData>> = msg_send_id![
+          class!(AVCaptureStillImageOutput),
+          jpegStillImageNSDataRepresentation: buffer
+        ];
+        data.map(|data| data.bytes().to_vec())
+      };
+      let _ = tx.send(jpeg);
+    },
+  );
+  let _: () = msg_send![
+    output,
+    captureStillImageAsynchronouslyFromConnection: &*connection,
+    completionHandler: &*handler
+  ];
+
+  match rx.recv_timeout(FRAME_TIMEOUT) {
+    Ok(Some(jpeg)) => Ok(jpeg),
+    Ok(None) => Err(capture_error("the camera did not return an image")),
+    Err(_) => Err(Error::Timeout),
+  }
+}
+
+unsafe fn jpeg_to_png(jpeg: &[u8]) -> Result<Vec<u8>, Error> {
+  let data = NSData::with_bytes(jpeg);
+  let image: Option<Retained<AnyObject>> =
+    msg_send_id![class!(NSBitmapImageRep), imageRepWithData: &*data];
+  let image = image.ok_or_else(|| capture_error("failed to decode the image"))?;
+  let properties: Retained<AnyObject> = msg_send_id![class!(NSDictionary), dictionary];
+  let png: Option<Retained<NSData>> = msg_send_id![
+    &image,
+    representationUsingType: BITMAP_IMAGE_FILE_TYPE_PNG,
+    properties: &*properties
+  ];
+  png
+    .map(|png| png.bytes().to_vec())
+    .ok_or_else(|| capture_error("failed to encode the image"))
+}
+
+pub fn capture(id: &str) -> Result<Vec<u8>, Error> {
+  request_access()?;
+
+  unsafe {
+    let id = NSString::from_str(id);
+    let device: Option<Retained<AnyObject>> =
+      msg_send_id![class!(AVCaptureDevice), deviceWithUniqueID: &*id];
+    let device = device.ok_or_else(|| Error::NotFound(id.to_string()))?;
+
+    let input: Option<Retained<AnyObject>> = msg_send_id![
+      class!(AVCaptureDeviceInput),
+      deviceInputWithDevice: &*device,
+      error: std::ptr::null_mut::<*mut AnyObject>()
+    ];
+    let input = input.ok_or_else(|| capture_error("the camera is not available"))?;
+
+    let session: Retained<AnyObject> = msg_send_id![class!(AVCaptureSession), new];
+    let output: Retained<AnyObject> = msg_send_id![class!(AVCaptureStillImageOutput), new];
+    let can_add_input: Bool = msg_send![&session, canAddInput: &*input];
+    let can_add_output: Bool = msg_send![&session, canAddOutput: &*output];
+    if !(can_add_input.as_bool() && can_add_output.as_bool()) {
+      return Err(capture_error("the camera does not support still images"));
+    }
+    let _: () = msg_send![&session, addInput: &*input];
+    let _: () = msg_send![&session, addOutput: &*output];
+
+    // blocks until the camera is running
+    let _: () = msg_send![&session, startRunning];
+    std::thread::sleep(WARM_UP);
+    let jpeg = capture_jpeg(&output);
+    let _: () = msg_send![&session, stopRunning];
+
+    jpeg_to_png(&jpeg?)
+  }
+}
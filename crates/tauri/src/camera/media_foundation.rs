@@ -0,0 +1,228 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Media Foundation backend.
+
+use std::time::Instant;
+
+use windows::{
+  core::{GUID, PWSTR},
+  Win32::{
+    Foundation::{E_ACCESSDENIED, E_POINTER},
+    Media::MediaFoundation::{
+      IMFActivate, IMFAttributes, IMFMediaSource, IMFSample, MFCreateAttributes, MFCreateMediaType,
+      MFCreateSourceReaderFromMediaSource, MFEnumDeviceSources, MFMediaType_Video, MFShutdown,
+      MFStartup, MFVideoFormat_RGB32, MFSTARTUP_NOSOCKET, MF_DEVSOURCE_ATTRIBUTE_FRIENDLY_NAME,
+      MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE, MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE_VIDCAP_GUID,
+      MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE_VIDCAP_SYMBOLIC_LINK, MF_MT_DEFAULT_STRIDE,
+      MF_MT_FRAME_SIZE, MF_MT_MAJOR_TYPE, MF_MT_SUBTYPE, MF_SOURCE_READERF_ENDOFSTREAM,
+      MF_SOURCE_READERF_ERROR, MF_SOURCE_READER_ENABLE_VIDEO_PROCESSING,
+      MF_SOURCE_READER_FIRST_VIDEO_STREAM, MF_VERSION,
+    },
+    System::Com::{CoInitializeEx, CoTaskMemFree, CoUninitialize, COINIT_MULTITHREADED},
+  },
+};
+
+use super::{bgrx_to_rgba, encode_png, Camera, Error, FRAME_TIMEOUT, WARM_UP};
+
+fn error(e: windows::core::Error) -> Error {
+  // the camera access is turned off in the privacy settings
+  if e.code() == E_ACCESSDENIED {
+    Error::Denied
+  } else {
+    Error::Capture(e.to_string())
+  }
+}
+
+/// Initializes COM and Media Foundation for the current thread.
+struct Session {
+  com: bool,
+}
+
+impl Session {
+  fn start() -> Result<Self, Error> {
+    unsafe {
+      // fails if the thread already uses a single-threaded apartment, which works as well
+      let com = CoInitializeEx(None, COINIT_MULTITHREADED).is_ok();
+      if let Err(e) = MFStartup(MF_VERSION, MFSTARTUP_NOSOCKET) {
+        if com {
+          CoUninitialize();
+        }
+        return Err(error(e));
+      }
+      Ok(Self { com })
+    }
+  }
+}
+
+impl Drop for Session {
+  fn drop(&mut self) {
+    unsafe {
+      let _ = MFShutdown();
+      if self.com {
+        CoUninitialize();
+      }
+    }
+  }
+}
+
+unsafe fn attributes() -> windows::core::Result<IMFAttributes> {
+  let mut attributes = None;
+  MFCreateAttributes(&mut attributes, 1)?;
+  attributes.ok_or_else(|| E_POINTER.into())
+}
+
+unsafe fn string(activate: &IMFActivate, key: &GUID) -> windows::core::Result<String> {
+  let mut value = PWSTR::null();
+  let mut len = 0;
+  activate.GetAllocatedString(key, &mut value, &mut len)?;
+  let string = String::from_utf16_lossy(value.as_wide());
+  CoTaskMemFree(Some(value.0 as _));
+  Ok(string)
+}
+
+unsafe fn activates() -> windows::core::Result<Vec<IMFActivate>> {
+  let attributes = attributes()?;
+  attributes.SetGUID(
+    &MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE,
+    &MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE_VIDCAP_GUID,
+  )?;
+  let mut sources = std::ptr::null_mut();
+  let mut count = 0;
+  MFEnumDeviceSources(&attributes, &mut sources, &mut count)?;
+  if sources.is_null() {
+    return Ok(Vec::new());
+  }
+  // take the ownership of the activation objects so they are released
+  let activates = std::slice::from_raw_parts_mut(sources, count as usize)
+    .iter_mut()
+    .filter_map(Option::take)
+    .collect();
+  CoTaskMemFree(Some(sources as _));
+  Ok(activates)
+}
+
+pub fn cameras() -> Result<Vec<Camera>, Error> {
+  let _session = Session::start()?;
+  // released before Media Foundation is shut down
+  let activates = unsafe { activates() }.map_err(error)?;
+  let cameras = activates
+    .iter()
+    .filter_map(|activate| unsafe {
+      Some(Camera {
+        id: string(
+          activate,
+          &MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE_VIDCAP_SYMBOLIC_LINK,
+        )
+        .ok()?,
+        label: string(activate, &MF_DEVSOURCE_ATTRIBUTE_FRIENDLY_NAME).ok()?,
+      })
+    })
+    .collect();
+  Ok(cameras)
+}
+
+struct Frame {
+  bgrx: Vec<u8>,
+  width: u32,
+  height: u32,
+  stride: isize,
+}
+
+unsafe fn read_frame(source: &IMFMediaSource) -> Result<Frame, Error> {
+  let attributes = attributes().map_err(error)?;
+  // converts the YUV formats of the camera to RGB
+  attributes
+    .SetUINT32(&MF_SOURCE_READER_ENABLE_VIDEO_PROCESSING, 1)
+    .map_err(error)?;
+  let reader = MFCreateSourceReaderFromMediaSource(source, &attributes).map_err(error)?;
+
+  let stream = MF_SOURCE_READER_FIRST_VIDEO_STREAM.0 as u32;
+  let media_type = MFCreateMediaType().map_err(error)?;
+  media_type
+    .SetGUID(&MF_MT_MAJOR_TYPE, &MFMediaType_Video)
+    .map_err(error)?;
+  media_type
+    .SetGUID(&MF_MT_SUBTYPE, &MFVideoFormat_RGB32)
+    .map_err(error)?;
+  reader
+    .SetCurrentMediaType(stream, None, &media_type)
+    .map_err(error)?;
+
+  let started = Instant::now();
+  let mut last_sample = Instant::now();
+  let mut frame: Option<IMFSample> = None;
+  while frame.is_none() || started.elapsed() < WARM_UP {
+    let mut flags = 0;
+    let mut sample = None;
+    reader
+      .ReadSample(stream, 0, None, Some(&mut flags), None, Some(&mut sample))
+      .map_err(error)?;
+    if flags & (MF_SOURCE_READERF_ERROR.0 | MF_SOURCE_READERF_ENDOFSTREAM.0) as u32 != 0 {
+      return Err(Error::Capture("the camera stopped".into()));
+    }
+    // the reader returns no sample for the stream gaps
+    if sample.is_some() {
+      frame = sample;
+      last_sample = Instant::now();
+    } else if last_sample.elapsed() > FRAME_TIMEOUT {
+      return Err(Error::Timeout);
+    }
+  }
+  let Some(sample) = frame else {
+    return Err(Error::Timeout);
+  };
+
+  let media_type = reader.GetCurrentMediaType(stream).map_err(error)?;
+  let size = media_type.GetUINT64(&MF_MT_FRAME_SIZE).map_err(error)?;
+  let (width, height) = ((size >> 32) as u32, size as u32);
+  // negative for bottom-up frames
+  let stride = media_type
+    .GetUINT32(&MF_MT_DEFAULT_STRIDE)
+    .map(|stride| stride as i32 as isize)
+    .unwrap_or(width as isize * 4);
+
+  let buffer = sample.ConvertToContiguousBuffer().map_err(error)?;
+  let mut data = std::ptr::null_mut();
+  let mut len = 0;
+  buffer
+    .Lock(&mut data, None, Some(&mut len))
+    .map_err(error)?;
+  let bgrx = std::slice::from_raw_parts(data, len as usize).to_vec();
+  let _ = buffer.Unlock();
+
+  if bgrx.len() < stride.unsigned_abs() * height as usize {
+    return Err(Error::Capture("incomplete frame".into()));
+  }
+  Ok(Frame {
+    bgrx,
+    width,
+    height,
+    stride,
+  })
+}
+
+pub fn capture(id: &str) -> Result<Vec<u8>, Error> {
+  let _session = Session::start()?;
+  let frame = unsafe {
+    let activate = activates()
+      .map_err(error)?
+      .into_iter()
+      .find(|activate| {
+        string(
+          activate,
+          &MF_DEVSOURCE_ATTRIBUTE_SOURCE_TYPE_VIDCAP_SYMBOLIC_LINK,
+        )
+        .is_ok_and(|link| link.eq_ignore_ascii_case(id))
+      })
+      .ok_or_else(|| Error::NotFound(id.into()))?;
+    let source: IMFMediaSource = activate.ActivateObject().map_err(error)?;
+    let frame = read_frame(&source);
+    let _ = source.Shutdown();
+    let _ = activate.ShutdownObject();
+    frame?
+  };
+  let rgba = bgrx_to_rgba(&frame.bgrx, frame.width, frame.height, frame.stride);
+  encode_png(&rgba, frame.width, frame.height)
+}
@@ -0,0 +1,240 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Still frame capture from the cameras of the system.
+//!
+//! This is a fallback for the webviews where `getUserMedia` is restricted and for apps without any window,
+//! e.g. tray-only apps: the frames are read with V4L2, Media Foundation or AVFoundation directly and encoded to PNG.
+//!
+//! The functions of this module block until the camera delivered a frame, so call them from a blocking thread,
+//! e.g. with [`crate::async_runtime::spawn_blocking`].
+//!
+//! ## Platform-specific
+//!
+//! - **macOS:** The user is prompted for the camera access on the first capture.
+//!   The `NSCameraUsageDescription` key must be set in the `Info.plist` file, otherwise the app is terminated.
+//! - **Windows:** Fails with [`Error::Denied`] if the camera access is turned off in the privacy settings.
+//! - **Linux:** The user must have access to the `/dev/video*` devices, usually through the `video` group.
+//!   Only the cameras supporting the YUYV pixel format can be captured.
+//! - **Android / iOS:** Unsupported.
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod v4l2;
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+use v4l2 as platform;
+
+#[cfg(windows)]
+mod media_foundation;
+#[cfg(windows)]
+use media_foundation as platform;
+
+#[cfg(target_os = "macos")]
+mod avfoundation;
+#[cfg(target_os = "macos")]
+use avfoundation as platform;
+
+#[cfg(mobile)]
+mod unsupported;
+#[cfg(mobile)]
+use unsupported as platform;
+
+/// How long the camera runs before the frame is taken, so the automatic exposure and white balance settle.
+const WARM_UP: Duration = Duration::from_millis(500);
+
+/// How long to wait for the camera to deliver a frame.
+const FRAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Camera errors.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+  /// The user or a system policy denied the access to the camera.
+  #[error("the access to the camera was denied")]
+  Denied,
+  /// No camera is connected.
+  #[error("no camera available")]
+  NoCamera,
+  /// The camera with the given identifier is not connected.
+  #[error("camera {0} not found")]
+  NotFound(String),
+  /// The camera did not deliver a frame in time.
+  #[error("the camera did not deliver a frame")]
+  Timeout,
+  /// The camera failed to capture a frame.
+  #[error("failed to capture a frame: {0}")]
+  Capture(String),
+}
+
+/// A camera of the system.
+///
+/// See [`cameras`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Camera {
+  /// The identifier of the camera, to pass to [`capture`].
+  ///
+  /// This is a device path on Linux, a symbolic link on Windows and a unique ID on macOS.
+  pub id: String,
+  /// The human readable name of the camera.
+  pub label: String,
+}
+
+/// Lists the connected cameras, the default camera first.
+///
+/// Does not prompt the user for the camera access.
+pub fn cameras() -> crate::Result<Vec<Camera>> {
+  platform::cameras().map_err(Into::into)
+}
+
+/// Captures a frame from the camera with the given [`Camera::id`], or the default camera if `None`,
+/// and returns it encoded as PNG.
+///
+/// The camera is started for this capture and stopped before returning.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// let png = tauri::camera::capture(None)?;
+/// std::fs::write("snapshot.png", png)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn capture(camera: Option<&str>) -> crate::Result<Vec<u8>> {
+  let id = match camera {
+    Some(id) => id.to_string(),
+    None => {
+      platform::cameras()?
+        .into_iter()
+        .next()
+        .ok_or(Error::NoCamera)?
+        .id
+    }
+  };
+  platform::capture(&id).map_err(Into::into)
+}
+
+/// Encodes RGBA pixels to PNG.
+#[cfg_attr(any(target_os = "macos", mobile), allow(dead_code))]
+fn encode_png(rgba: &[u8], width: u32, height: u32) -> Result<Vec<u8>, Error> {
+  use image::{codecs::png::PngEncoder, ExtendedColorType, ImageEncoder};
+
+  let mut png = Vec::new();
+  PngEncoder::new(&mut png)
+    .write_image(rgba, width, height, ExtendedColorType::Rgba8)
+    .map_err(|e| Error::Capture(e.to_string()))?;
+  Ok(png)
+}
+
+/// Converts a YUYV (YUV 4:2:2) frame to RGBA pixels with the BT.601 coefficients.
+#[cfg_attr(any(windows, target_os = "macos", mobile), allow(dead_code))]
+fn yuyv_to_rgba(yuyv: &[u8], width: u32, height: u32, stride: usize) -> Vec<u8> {
+  fn pixel(y: u8, u: u8, v: u8) -> [u8; 4] {
+    let c = (y as i32 - 16) * 298;
+    let d = u as i32 - 128;
+    let e = v as i32 - 128;
+    let clamp = |value: i32| ((value + 128) >> 8).clamp(0, 255) as u8;
+    [
+      clamp(c + 409 * e),
+      clamp(c - 100 * d - 208 * e),
+      clamp(c + 516 * d),
+      255,
+    ]
+  }
+
+  let mut rgba = Vec::with_capacity(width as usize * height as usize * 4);
+  for row in yuyv.chunks(stride).take(height as usize) {
+    for macropixel in row[..width as usize * 2].chunks_exact(4) {
+      let [y0, u, y1, v] = [macropixel[0], macropixel[1], macropixel[2], macropixel[3]];
+      rgba.extend_from_slice(&pixel(y0, u, v));
+      rgba.extend_from_slice(&pixel(y1, u, v));
+    }
+  }
+  rgba
+}
+
+/// Converts a BGRX frame to RGBA pixels, `stride` being negative for bottom-up frames.
+#[cfg_attr(not(any(windows, test)), allow(dead_code))]
+fn bgrx_to_rgba(bgrx: &[u8], width: u32, height: u32, stride: isize) -> Vec<u8> {
+  let row_len = width as usize * 4;
+  let mut rgba = Vec::with_capacity(row_len * height as usize);
+  for i in 0..height as usize {
+    let row = if stride < 0 {
+      height as usize - 1 - i
+    } else {
+      i
+    };
+    let start = row * stride.unsigned_abs();
+    for pixel in bgrx[start..start + row_len].chunks_exact(4) {
+      rgba.extend_from_slice(&[pixel[2], pixel[1], pixel[0], 255]);
+    }
+  }
+  rgba
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn converts_yuyv() {
+    // black and white macropixel, followed by one byte of row padding
+    let yuyv = [16, 128, 235, 128, 0, 16, 128, 235, 128, 0];
+    assert_eq!(
+      yuyv_to_rgba(&yuyv, 2, 2, 5),
+      [
+        [0, 0, 0, 255],
+        [255, 255, 255, 255],
+        [0, 0, 0, 255],
+        [255, 255, 255, 255]
+      ]
+      .concat()
+    );
+
+    // pure red
+    let rgba = yuyv_to_rgba(&[81, 90, 81, 240], 2, 1, 4);
+    assert!(rgba[0] > 250 && rgba[1] < 5 && rgba[2] < 5);
+  }
+
+  #[test]
+  fn converts_bgrx() {
+    let bgrx = [1, 2, 3, 0, 4, 5, 6, 0];
+    assert_eq!(bgrx_to_rgba(&bgrx, 1, 2, 4), [3, 2, 1, 255, 6, 5, 4, 255]);
+    // bottom-up
+    assert_eq!(bgrx_to_rgba(&bgrx, 1, 2, -4), [6, 5, 4, 255, 3, 2, 1, 255]);
+  }
+
+  #[test]
+  fn encodes_png() {
+    let png = encode_png(&[255, 0, 0, 255], 1, 1).unwrap();
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+  }
+
+  #[test]
+  fn serializes_camera() {
+    let camera = Camera {
+      id: "/dev/video0".into(),
+      label: "HD Pro Webcam C920".into(),
+    };
+    assert_eq!(
+      serde_json::to_value(camera).unwrap(),
+      serde_json::json!({ "id": "/dev/video0", "label": "HD Pro Webcam C920" })
+    );
+  }
+}
@@ -0,0 +1,15 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Backend of the platforms without camera support.
+
+use super::{Camera, Error};
+
+pub fn cameras() -> Result<Vec<Camera>, Error> {
+  Ok(Vec::new())
+}
+
+pub fn capture(_id: &str) -> Result<Vec<u8>, Error> {
+  Err(Error::NoCamera)
+}
@@ -0,0 +1,351 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Video4Linux2 backend.
+
+use std::{
+  ffi::{c_void, CStr},
+  fs::{File, OpenOptions},
+  io,
+  os::fd::AsRawFd,
+  time::Instant,
+};
+
+use super::{encode_png, yuyv_to_rgba, Camera, Error, FRAME_TIMEOUT, WARM_UP};
+
+const BUFFER_TYPE_VIDEO_CAPTURE: u32 = 1;
+const MEMORY_MMAP: u32 = 1;
+const FIELD_NONE: u32 = 1;
+const CAP_VIDEO_CAPTURE: u32 = 0x0000_0001;
+const CAP_STREAMING: u32 = 0x0400_0000;
+const CAP_DEVICE_CAPS: u32 = 0x8000_0000;
+const PIX_FMT_YUYV: u32 = u32::from_le_bytes(*b"YUYV");
+const BUFFER_COUNT: u32 = 2;
+
+#[allow(dead_code)]
+#[repr(C)]
+struct Capability {
+  driver: [u8; 16],
+  card: [u8; 32],
+  bus_info: [u8; 32],
+  version: u32,
+  capabilities: u32,
+  device_caps: u32,
+  reserved: [u32; 3],
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct PixFormat {
+  width: u32,
+  height: u32,
+  pixelformat: u32,
+  field: u32,
+  bytesperline: u32,
+  sizeimage: u32,
+  colorspace: u32,
+  private: u32,
+  flags: u32,
+  ycbcr_enc: u32,
+  quantization: u32,
+  xfer_func: u32,
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+union FormatUnion {
+  pix: PixFormat,
+  raw: [u8; 200],
+  // the kernel union holds pointers
+  _align: [*mut c_void; 0],
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+struct Format {
+  type_: u32,
+  fmt: FormatUnion,
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+struct RequestBuffers {
+  count: u32,
+  type_: u32,
+  memory: u32,
+  capabilities: u32,
+  flags: u8,
+  reserved: [u8; 3],
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+struct Timecode {
+  type_: u32,
+  flags: u32,
+  frames: u8,
+  seconds: u8,
+  minutes: u8,
+  hours: u8,
+  userbits: [u8; 4],
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+union BufferLocation {
+  offset: u32,
+  userptr: libc::c_ulong,
+  planes: *mut c_void,
+  fd: i32,
+}
+
+#[allow(dead_code)]
+#[repr(C)]
+struct Buffer {
+  index: u32,
+  type_: u32,
+  bytesused: u32,
+  flags: u32,
+  field: u32,
+  timestamp: libc::timeval,
+  timecode: Timecode,
+  sequence: u32,
+  memory: u32,
+  m: BufferLocation,
+  length: u32,
+  reserved2: u32,
+  request_fd: i32,
+}
+
+const fn ioc(dir: libc::c_ulong, nr: libc::c_ulong, size: usize) -> libc::c_ulong {
+  (dir << 30) | ((size as libc::c_ulong) << 16) | ((b'V' as libc::c_ulong) << 8) | nr
+}
+
+const IOC_WRITE: libc::c_ulong = 1;
+const IOC_READ: libc::c_ulong = 2;
+const VIDIOC_QUERYCAP: libc::c_ulong = ioc(IOC_READ, 0, std::mem::size_of::<Capability>());
+const VIDIOC_S_FMT: libc::c_ulong = ioc(IOC_READ | IOC_WRITE, 5, std::mem::size_of::<Format>());
+const VIDIOC_REQBUFS: libc::c_ulong = ioc(
+  IOC_READ | IOC_WRITE,
+  8,
+  std::mem::size_of::<RequestBuffers>(),
+);
+const VIDIOC_QUERYBUF: libc::c_ulong = ioc(IOC_READ | IOC_WRITE, 9, std::mem::size_of::<Buffer>());
+const VIDIOC_QBUF: libc::c_ulong = ioc(IOC_READ | IOC_WRITE, 15, std::mem::size_of::<Buffer>());
+const VIDIOC_DQBUF: libc::c_ulong = ioc(IOC_READ | IOC_WRITE, 17, std::mem::size_of::<Buffer>());
+const VIDIOC_STREAMON: libc::c_ulong = ioc(IOC_WRITE, 18, std::mem::size_of::<libc::c_int>());
+const VIDIOC_STREAMOFF: libc::c_ulong = ioc(IOC_WRITE, 19, std::mem::size_of::<libc::c_int>());
+
+unsafe fn ioctl<T>(file: &File, request: libc::c_ulong, arg: &mut T) -> io::Result<()> {
+  loop {
+    if libc::ioctl(file.as_raw_fd(), request as _, arg as *mut T) != -1 {
+      return Ok(());
+    }
+    let error = io::Error::last_os_error();
+    if error.kind() != io::ErrorKind::Interrupted {
+      return Err(error);
+    }
+  }
+}
+
+fn capture_error(e: io::Error) -> Error {
+  Error::Capture(e.to_string())
+}
+
+fn open(path: &str) -> Result<File, Error> {
+  OpenOptions::new()
+    .read(true)
+    .write(true)
+    .open(path)
+    .map_err(|e| match e.kind() {
+      io::ErrorKind::PermissionDenied => Error::Denied,
+      io::ErrorKind::NotFound => Error::NotFound(path.into()),
+      _ => capture_error(e),
+    })
+}
+
+/// The card name if the device captures video, since the UVC drivers also expose metadata devices.
+fn query_camera(file: &File) -> Option<String> {
+  let mut capability: Capability = unsafe { std::mem::zeroed() };
+  unsafe { ioctl(file, VIDIOC_QUERYCAP, &mut capability) }.ok()?;
+  let caps = if capability.capabilities & CAP_DEVICE_CAPS != 0 {
+    capability.device_caps
+  } else {
+    capability.capabilities
+  };
+  if caps & CAP_VIDEO_CAPTURE == 0 || caps & CAP_STREAMING == 0 {
+    return None;
+  }
+  CStr::from_bytes_until_nul(&capability.card)
+    .ok()
+    .map(|card| card.to_string_lossy().into_owned())
+}
+
+pub fn cameras() -> Result<Vec<Camera>, Error> {
+  let mut devices = std::fs::read_dir("/dev")
+    .map_err(capture_error)?
+    .flatten()
+    .filter_map(|entry| {
+      let name = entry.file_name().into_string().ok()?;
+      let index = name.strip_prefix("video")?.parse::<u32>().ok()?;
+      Some((index, format!("/dev/{name}")))
+    })
+    .collect::<Vec<_>>();
+  // the first camera is the default one
+  devices.sort();
+
+  Ok(
+    devices
+      .into_iter()
+      .filter_map(|(_, path)| {
+        let file = open(&path).ok()?;
+        let label = query_camera(&file)?;
+        Some(Camera { id: path, label })
+      })
+      .collect(),
+  )
+}
+
+struct Mapping {
+  ptr: *mut c_void,
+  len: usize,
+}
+
+impl Drop for Mapping {
+  fn drop(&mut self) {
+    unsafe { libc::munmap(self.ptr, self.len) };
+  }
+}
+
+struct Stream<'a> {
+  file: &'a File,
+  buffers: Vec<Mapping>,
+}
+
+impl<'a> Stream<'a> {
+  fn start(file: &'a File) -> io::Result<Self> {
+    let mut request = RequestBuffers {
+      count: BUFFER_COUNT,
+      type_: BUFFER_TYPE_VIDEO_CAPTURE,
+      memory: MEMORY_MMAP,
+      capabilities: 0,
+      flags: 0,
+      reserved: [0; 3],
+    };
+    unsafe { ioctl(file, VIDIOC_REQBUFS, &mut request)? };
+
+    let mut stream = Self {
+      file,
+      buffers: Vec::new(),
+    };
+    for index in 0..request.count {
+      let mut buffer = Self::buffer(index);
+      unsafe { ioctl(file, VIDIOC_QUERYBUF, &mut buffer)? };
+      let len = buffer.length as usize;
+      let ptr = unsafe {
+        libc::mmap(
+          std::ptr::null_mut(),
+          len,
+          libc::PROT_READ | libc::PROT_WRITE,
+          libc::MAP_SHARED,
+          file.as_raw_fd(),
+          buffer.m.offset as libc::off_t,
+        )
+      };
+      if ptr == libc::MAP_FAILED {
+        return Err(io::Error::last_os_error());
+      }
+      stream.buffers.push(Mapping { ptr, len });
+      unsafe { ioctl(file, VIDIOC_QBUF, &mut buffer)? };
+    }
+
+    let mut type_ = BUFFER_TYPE_VIDEO_CAPTURE as libc::c_int;
+    unsafe { ioctl(file, VIDIOC_STREAMON, &mut type_)? };
+    Ok(stream)
+  }
+
+  fn buffer(index: u32) -> Buffer {
+    let mut buffer: Buffer = unsafe { std::mem::zeroed() };
+    buffer.index = index;
+    buffer.type_ = BUFFER_TYPE_VIDEO_CAPTURE;
+    buffer.memory = MEMORY_MMAP;
+    buffer
+  }
+
+  /// Waits for the next frame and copies it.
+  fn next_frame(&self) -> Result<Vec<u8>, Error> {
+    let mut poll = libc::pollfd {
+      fd: self.file.as_raw_fd(),
+      events: libc::POLLIN,
+      revents: 0,
+    };
+    match unsafe { libc::poll(&mut poll, 1, FRAME_TIMEOUT.as_millis() as libc::c_int) } {
+      -1 => return Err(capture_error(io::Error::last_os_error())),
+      0 => return Err(Error::Timeout),
+      _ => {}
+    }
+
+    let mut buffer = Self::buffer(0);
+    unsafe { ioctl(self.file, VIDIOC_DQBUF, &mut buffer) }.map_err(capture_error)?;
+    let mapping = &self.buffers[buffer.index as usize];
+    let len = (buffer.bytesused as usize).min(mapping.len);
+    let frame = unsafe { std::slice::from_raw_parts(mapping.ptr as *const u8, len) }.to_vec();
+    unsafe { ioctl(self.file, VIDIOC_QBUF, &mut buffer) }.map_err(capture_error)?;
+    Ok(frame)
+  }
+}
+
+impl Drop for Stream<'_> {
+  fn drop(&mut self) {
+    let mut type_ = BUFFER_TYPE_VIDEO_CAPTURE as libc::c_int;
+    let _ = unsafe { ioctl(self.file, VIDIOC_STREAMOFF, &mut type_) };
+  }
+}
+
+pub fn capture(id: &str) -> Result<Vec<u8>, Error> {
+  let file = open(id)?;
+  if query_camera(&file).is_none() {
+    return Err(Error::NotFound(id.into()));
+  }
+
+  let mut format: Format = unsafe { std::mem::zeroed() };
+  format.type_ = BUFFER_TYPE_VIDEO_CAPTURE;
+  format.fmt.pix = PixFormat {
+    width: 1280,
+    height: 720,
+    pixelformat: PIX_FMT_YUYV,
+    field: FIELD_NONE,
+    ..unsafe { std::mem::zeroed() }
+  };
+  // the driver adjusts the format to the closest one it supports
+  unsafe { ioctl(&file, VIDIOC_S_FMT, &mut format) }.map_err(|e| match e.raw_os_error() {
+    // the device is used by another app
+    Some(libc::EBUSY) => Error::Capture("the camera is busy".into()),
+    _ => capture_error(e),
+  })?;
+  let pix = unsafe { format.fmt.pix };
+  if pix.pixelformat != PIX_FMT_YUYV {
+    let fourcc = pix.pixelformat.to_le_bytes();
+    return Err(Error::Capture(format!(
+      "unsupported pixel format {}",
+      String::from_utf8_lossy(&fourcc)
+    )));
+  }
+
+  let stream = Stream::start(&file).map_err(capture_error)?;
+  let started = Instant::now();
+  let mut frame = stream.next_frame()?;
+  while started.elapsed() < WARM_UP {
+    frame = stream.next_frame()?;
+  }
+  drop(stream);
+
+  let stride = (pix.bytesperline as usize).max(pix.width as usize * 2);
+  if frame.len() < stride * pix.height as usize {
+    return Err(Error::Capture("incomplete frame".into()));
+  }
+  let rgba = yuyv_to_rgba(&frame, pix.width, pix.height, stride);
+  encode_png(&rgba, pix.width, pix.height)
+}
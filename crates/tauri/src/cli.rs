@@ -0,0 +1,604 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Parses the command line arguments with the declarative definition of `app > cli` in `tauri.conf.json`.
+//!
+//! The raw arguments of the process are available with [`crate::Env::args_os`],
+//! the parsed ones with [`crate::AppHandle::cli_matches`] and the `getCliMatches` JavaScript API.
+//!
+//! Arguments passed to secondary instances of the app can be delivered to the running instance
+//! with [`forward_args`], which emits them as the [`CLI_ARGS_EVENT`] event.
+
+use std::{collections::HashMap, ffi::OsStr, fmt::Write, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::utils::config::{CliArg, CliConfig, CliValueType};
+
+/// Event emitted with a [`ForwardedArgs`] payload when a secondary instance forwarded its arguments,
+/// see [`forward_args`].
+pub const CLI_ARGS_EVENT: &str = "tauri://cli-args";
+
+/// The name of the external IPC request carrying forwarded arguments.
+#[cfg(all(desktop, feature = "external-ipc"))]
+pub(crate) const FORWARD_ARGS_COMMAND: &str = "tauri:cli-args";
+
+/// Command line parsing errors.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+  /// The app has no `app > cli` configuration.
+  #[error("the command line interface is not configured in `app > cli`")]
+  NotConfigured,
+  /// An argument is not part of the definition.
+  #[error("unexpected argument `{0}`")]
+  UnexpectedArgument(String),
+  /// A flag that takes a value is the last argument.
+  #[error("missing value for `{0}`")]
+  MissingValue(String),
+  /// A flag without a value was given one with `--name=value`.
+  #[error("`{0}` does not take a value")]
+  UnexpectedValue(String),
+  /// A value is not one of the possible values or could not be parsed as the argument type.
+  #[error("invalid value `{value}` for `{name}`: {reason}")]
+  InvalidValue {
+    /// The argument name.
+    name: String,
+    /// The value.
+    value: String,
+    /// Why the value was rejected.
+    reason: String,
+  },
+  /// An argument that can only be set once was set multiple times.
+  #[error("`{0}` cannot be used multiple times")]
+  DuplicateArgument(String),
+  /// A required argument is missing.
+  #[error("missing required argument `{0}`")]
+  MissingRequired(String),
+}
+
+/// The value of a matched argument.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ArgData {
+  /// The value of the argument: `true` for flags without a value, the typed value otherwise,
+  /// or an array of the values for arguments with `multiple`.
+  pub value: JsonValue,
+  /// The number of times the argument was set.
+  pub occurrences: u32,
+}
+
+/// The matched subcommand.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SubcommandMatches {
+  /// The subcommand name.
+  pub name: String,
+  /// The arguments of the subcommand.
+  pub matches: Matches,
+}
+
+/// The arguments matched by [`parse`].
+///
+/// The help message is matched as the `help` argument when `--help` or `-h` is set
+/// and the definition does not declare them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Matches {
+  /// The matched arguments by name. Arguments that are not set are omitted.
+  pub args: HashMap<String, ArgData>,
+  /// The matched subcommand, if any.
+  pub subcommand: Option<Box<SubcommandMatches>>,
+}
+
+/// Arguments forwarded by a secondary instance, the payload of the [`CLI_ARGS_EVENT`] event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ForwardedArgs {
+  /// The raw arguments of the secondary instance, without the executable path.
+  pub args: Vec<String>,
+  /// The working directory of the secondary instance, to resolve relative paths.
+  pub cwd: PathBuf,
+  /// The arguments parsed with `app > cli`, or `None` if it is not configured.
+  pub matches: Option<Matches>,
+}
+
+/// Parses `args`, without the executable path, with the given definition.
+///
+/// # Examples
+///
+/// ```
+/// use tauri::utils::config::CliConfig;
+///
+/// let config: CliConfig = serde_json::from_value(serde_json::json!({
+///   "args": [
+///     { "name": "verbose", "short": "v" },
+///     { "name": "port", "takesValue": true, "type": "integer" },
+///     { "name": "file", "index": 1 }
+///   ]
+/// }))
+/// .unwrap();
+/// let matches = tauri::cli::parse(&config, ["-v", "--port=8080", "notes.txt"]).unwrap();
+/// assert_eq!(matches.args["port"].value, 8080);
+/// assert_eq!(matches.args["file"].value, "notes.txt");
+/// ```
+pub fn parse<I, S>(config: &CliConfig, args: I) -> Result<Matches, Error>
+where
+  I: IntoIterator<Item = S>,
+  S: AsRef<OsStr>,
+{
+  let args = args
+    .into_iter()
+    .map(|arg| arg.as_ref().to_string_lossy().into_owned())
+    // the process serial number macOS passes to apps launched from the Finder on old releases
+    .filter(|arg| !arg.starts_with("-psn_"))
+    .collect::<Vec<_>>();
+  parse_command(config, &[], &args)
+}
+
+fn parse_command(config: &CliConfig, path: &[&str], args: &[String]) -> Result<Matches, Error> {
+  let mut matches = Matches::default();
+  let mut positionals = config
+    .args
+    .iter()
+    .filter(|arg| arg.index.is_some())
+    .collect::<Vec<_>>();
+  positionals.sort_by_key(|arg| arg.index);
+  let mut positionals = positionals.into_iter().peekable();
+
+  let mut i = 0;
+  let mut only_positionals = false;
+  while i < args.len() {
+    let arg = &args[i];
+    i += 1;
+
+    if !only_positionals && arg == "--" {
+      only_positionals = true;
+      continue;
+    }
+
+    if !only_positionals && arg.len() > 1 && arg.starts_with('-') {
+      if let Some(long) = arg.strip_prefix("--") {
+        let (name, inline) = match long.split_once('=') {
+          Some((name, value)) => (name, Some(value.to_string())),
+          None => (long, None),
+        };
+        let Some(def) = config
+          .args
+          .iter()
+          .find(|def| def.index.is_none() && def.name == name)
+        else {
+          if name == "help" {
+            insert_help(&mut matches, config, path);
+            continue;
+          }
+          return Err(Error::UnexpectedArgument(arg.clone()));
+        };
+        let value = if def.takes_value {
+          match inline {
+            Some(value) => Some(value),
+            None => Some(next_value(args, &mut i, arg)?),
+          }
+        } else if inline.is_some() {
+          return Err(Error::UnexpectedValue(arg.clone()));
+        } else {
+          None
+        };
+        add_match(&mut matches, def, value)?;
+      } else {
+        // `-abc` sets the short flags `a`, `b` and `c`, `-p8080` or `-p 8080` the value of `p`
+        for (offset, short) in arg[1..].char_indices() {
+          let Some(def) = config
+            .args
+            .iter()
+            .find(|def| def.index.is_none() && def.short == Some(short))
+          else {
+            if short == 'h' {
+              insert_help(&mut matches, config, path);
+              continue;
+            }
+            return Err(Error::UnexpectedArgument(format!("-{short}")));
+          };
+          if def.takes_value {
+            let rest = &arg[1 + offset + short.len_utf8()..];
+            let value = match rest.strip_prefix('=').unwrap_or(rest) {
+              "" => next_value(args, &mut i, arg)?,
+              rest => rest.to_string(),
+            };
+            add_match(&mut matches, def, Some(value))?;
+            break;
+          }
+          add_match(&mut matches, def, None)?;
+        }
+      }
+      continue;
+    }
+
+    if !only_positionals && matches.subcommand.is_none() {
+      if let Some((name, subcommand)) = config.subcommands.get_key_value(arg.as_str()) {
+        let mut path = path.to_vec();
+        path.push(name);
+        let sub_matches = parse_command(subcommand, &path, &args[i..])?;
+        matches.subcommand = Some(Box::new(SubcommandMatches {
+          name: name.clone(),
+          matches: sub_matches,
+        }));
+        break;
+      }
+    }
+
+    match positionals.peek() {
+      Some(def) => {
+        add_match(&mut matches, def, Some(arg.clone()))?;
+        if !def.multiple {
+          positionals.next();
+        }
+      }
+      None => return Err(Error::UnexpectedArgument(arg.clone())),
+    }
+  }
+
+  if !matches.args.contains_key("help") {
+    if let Some(def) = config
+      .args
+      .iter()
+      .find(|def| def.required && !matches.args.contains_key(&def.name))
+    {
+      return Err(Error::MissingRequired(def.name.clone()));
+    }
+  }
+
+  Ok(matches)
+}
+
+fn next_value(args: &[String], i: &mut usize, flag: &str) -> Result<String, Error> {
+  let value = args
+    .get(*i)
+    .ok_or_else(|| Error::MissingValue(flag.into()))?
+    .clone();
+  *i += 1;
+  Ok(value)
+}
+
+fn add_match(matches: &mut Matches, def: &CliArg, value: Option<String>) -> Result<(), Error> {
+  let value = match value {
+    Some(value) => parse_value(def, value)?,
+    None => JsonValue::Bool(true),
+  };
+  let takes_value = def.takes_value || def.index.is_some();
+
+  match matches.args.get_mut(&def.name) {
+    Some(data) => {
+      if takes_value {
+        if !def.multiple {
+          return Err(Error::DuplicateArgument(def.name.clone()));
+        }
+        if let JsonValue::Array(values) = &mut data.value {
+          values.push(value);
+        }
+      }
+      data.occurrences += 1;
+    }
+    None => {
+      let value = if takes_value && def.multiple {
+        JsonValue::Array(vec![value])
+      } else {
+        value
+      };
+      matches.args.insert(
+        def.name.clone(),
+        ArgData {
+          value,
+          occurrences: 1,
+        },
+      );
+    }
+  }
+  Ok(())
+}
+
+fn parse_value(def: &CliArg, value: String) -> Result<JsonValue, Error> {
+  let invalid = |reason: String| Error::InvalidValue {
+    name: def.name.clone(),
+    value: value.clone(),
+    reason,
+  };
+
+  if let Some(possible_values) = &def.possible_values {
+    if !possible_values.contains(&value) {
+      return Err(invalid(format!(
+        "expected one of {}",
+        possible_values.join(", ")
+      )));
+    }
+  }
+
+  match def.value_type {
+    CliValueType::String => Ok(JsonValue::String(value)),
+    CliValueType::Integer => value
+      .parse::<i64>()
+      .map(Into::into)
+      .map_err(|e| invalid(e.to_string())),
+    CliValueType::Number => value
+      .parse::<f64>()
+      .ok()
+      .and_then(serde_json::Number::from_f64)
+      .map(JsonValue::Number)
+      .ok_or_else(|| invalid("expected a number".into())),
+    CliValueType::Boolean => value
+      .parse::<bool>()
+      .map(Into::into)
+      .map_err(|e| invalid(e.to_string())),
+  }
+}
+
+fn insert_help(matches: &mut Matches, config: &CliConfig, path: &[&str]) {
+  matches.args.insert(
+    "help".into(),
+    ArgData {
+      value: help(config, path).into(),
+      occurrences: 1,
+    },
+  );
+}
+
+/// The help message of the command, generated from the definition.
+fn help(config: &CliConfig, path: &[&str]) -> String {
+  let mut help = String::new();
+  if let Some(description) = &config.description {
+    let _ = writeln!(help, "{description}\n");
+  }
+
+  let mut usage = path.join(" ");
+  if !config.args.iter().all(|arg| arg.index.is_some()) {
+    usage.push_str(" [OPTIONS]");
+  }
+  let mut positionals = config
+    .args
+    .iter()
+    .filter(|arg| arg.index.is_some())
+    .collect::<Vec<_>>();
+  positionals.sort_by_key(|arg| arg.index);
+  for arg in &positionals {
+    let dots = if arg.multiple { "..." } else { "" };
+    if arg.required {
+      let _ = write!(usage, " <{}>{dots}", arg.name);
+    } else {
+      let _ = write!(usage, " [{}]{dots}", arg.name);
+    }
+  }
+  if !config.subcommands.is_empty() {
+    usage.push_str(" [COMMAND]");
+  }
+  let _ = writeln!(help, "Usage: {}", usage.trim());
+
+  let mut subcommands = config.subcommands.iter().collect::<Vec<_>>();
+  subcommands.sort_by_key(|(name, _)| *name);
+  if !subcommands.is_empty() {
+    let _ = writeln!(help, "\nCommands:");
+    for (name, subcommand) in subcommands {
+      let _ = writeln!(
+        help,
+        "  {name:<20} {}",
+        subcommand.description.as_deref().unwrap_or_default()
+      );
+    }
+  }
+
+  let _ = writeln!(help, "\nOptions:");
+  for arg in config.args.iter().filter(|arg| arg.index.is_none()) {
+    let mut flag = match arg.short {
+      Some(short) => format!("-{short}, --{}", arg.name),
+      None => format!("    --{}", arg.name),
+    };
+    if arg.takes_value {
+      let _ = write!(flag, " <{}>", arg.name.to_uppercase());
+    }
+    let _ = writeln!(
+      help,
+      "  {flag:<20} {}",
+      arg.description.as_deref().unwrap_or_default()
+    );
+  }
+  let _ = writeln!(help, "  {:<20} Print help", "-h, --help");
+  help
+}
+
+/// Sends the arguments of this process to the running instance of the app with the given bundle identifier,
+/// which emits them as the [`CLI_ARGS_EVENT`] event, and exits.
+///
+/// Returns without doing anything if no instance is running, i.e. the [`crate::ipc::external`] server
+/// of the running instance cannot be reached, so call it at the start of `main`, before the app and its windows are created.
+/// The running instance must have started the server with [`crate::ipc::external::serve`].
+///
+/// If the arguments do not match `app > cli`, the error is printed to stderr and the process exits with the code `1`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri::{Listener, Manager};
+///
+/// let context = tauri::generate_context!("test/fixture/src-tauri/tauri.conf.json");
+/// tauri::cli::forward_args(&context.config().identifier);
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     app.manage(tauri::ipc::external::serve(app.handle(), |_app, request| {
+///       Err(format!("unknown command {}", request.command))
+///     })?);
+///     app.listen(tauri::cli::CLI_ARGS_EVENT, |event| {
+///       println!("arguments of a secondary instance: {}", event.payload());
+///     });
+///     Ok(())
+///   })
+///   .run(context)
+///   .expect("error while running tauri application");
+/// ```
+#[cfg(all(desktop, feature = "external-ipc"))]
+#[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "external-ipc"))))]
+pub fn forward_args(identifier: &str) {
+  let Ok(mut client) = crate::ipc::external::Client::connect(identifier) else {
+    return;
+  };
+  let payload = serde_json::json!({
+    "args": std::env::args_os()
+      .skip(1)
+      .map(|arg| arg.to_string_lossy().into_owned())
+      .collect::<Vec<_>>(),
+    "cwd": std::env::current_dir().unwrap_or_default(),
+  });
+  match client.request(FORWARD_ARGS_COMMAND, payload) {
+    Ok(_) => std::process::exit(0),
+    Err(e) => {
+      eprintln!("{e}");
+      std::process::exit(1);
+    }
+  }
+}
+
+/// Handles the arguments forwarded by [`forward_args`] in the running instance.
+#[cfg(all(desktop, feature = "external-ipc"))]
+pub(crate) fn deliver_forwarded<R: crate::Runtime>(
+  app: &crate::AppHandle<R>,
+  payload: JsonValue,
+) -> Result<JsonValue, String> {
+  use crate::{Emitter, Manager};
+
+  #[derive(Deserialize)]
+  struct Payload {
+    args: Vec<String>,
+    cwd: PathBuf,
+  }
+
+  let Payload { args, cwd } = serde_json::from_value(payload).map_err(|e| e.to_string())?;
+  let matches = match &app.config().app.cli {
+    Some(config) => Some(parse(config, &args).map_err(|e| e.to_string())?),
+    None => None,
+  };
+  app
+    .emit(CLI_ARGS_EVENT, ForwardedArgs { args, cwd, matches })
+    .map_err(|e| e.to_string())?;
+  Ok(JsonValue::Null)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn config() -> CliConfig {
+    serde_json::from_value(serde_json::json!({
+      "description": "Notes app",
+      "args": [
+        { "name": "verbose", "short": "v" },
+        { "name": "port", "short": "p", "takesValue": true, "type": "integer" },
+        { "name": "theme", "takesValue": true, "possibleValues": ["light", "dark"] },
+        { "name": "tag", "short": "t", "takesValue": true, "multiple": true },
+        { "name": "files", "index": 1, "multiple": true }
+      ],
+      "subcommands": {
+        "open": {
+          "args": [
+            { "name": "path", "index": 1, "required": true },
+            { "name": "readonly", "takesValue": true, "type": "boolean" }
+          ]
+        }
+      }
+    }))
+    .unwrap()
+  }
+
+  #[test]
+  fn parses_flags_values_and_positionals() {
+    let matches = parse(
+      &config(),
+      [
+        "-vv",
+        "-p8080",
+        "--theme=dark",
+        "-t",
+        "a",
+        "--tag",
+        "b",
+        "x.md",
+        "--",
+        "-y.md",
+      ],
+    )
+    .unwrap();
+    assert_eq!(matches.args["verbose"].value, true);
+    assert_eq!(matches.args["verbose"].occurrences, 2);
+    assert_eq!(matches.args["port"].value, 8080);
+    assert_eq!(matches.args["theme"].value, "dark");
+    assert_eq!(matches.args["tag"].value, serde_json::json!(["a", "b"]));
+    assert_eq!(
+      matches.args["files"].value,
+      serde_json::json!(["x.md", "-y.md"])
+    );
+    assert!(matches.subcommand.is_none());
+  }
+
+  #[test]
+  fn parses_subcommands() {
+    let matches = parse(&config(), ["-v", "open", "a.md", "--readonly", "true"]).unwrap();
+    assert!(matches.args.contains_key("verbose"));
+    let subcommand = matches.subcommand.unwrap();
+    assert_eq!(subcommand.name, "open");
+    assert_eq!(subcommand.matches.args["path"].value, "a.md");
+    assert_eq!(subcommand.matches.args["readonly"].value, true);
+
+    assert!(matches!(
+      parse(&config(), ["open"]),
+      Err(Error::MissingRequired(name)) if name == "path"
+    ));
+  }
+
+  #[test]
+  fn rejects_invalid_arguments() {
+    let config = config();
+    assert!(matches!(
+      parse(&config, ["--unknown"]),
+      Err(Error::UnexpectedArgument(_))
+    ));
+    assert!(matches!(
+      parse(&config, ["--port", "http"]),
+      Err(Error::InvalidValue { .. })
+    ));
+    assert!(matches!(
+      parse(&config, ["--theme", "blue"]),
+      Err(Error::InvalidValue { .. })
+    ));
+    assert!(matches!(
+      parse(&config, ["--port"]),
+      Err(Error::MissingValue(_))
+    ));
+    assert!(matches!(
+      parse(&config, ["--verbose=1"]),
+      Err(Error::UnexpectedValue(_))
+    ));
+    assert!(matches!(
+      parse(&config, ["-p", "1", "-p", "2"]),
+      Err(Error::DuplicateArgument(_))
+    ));
+  }
+
+  #[test]
+  fn matches_help() {
+    let matches = parse(&config(), ["open", "--help"]).unwrap();
+    let help = &matches.subcommand.unwrap().matches.args["help"].value;
+    assert!(help
+      .as_str()
+      .unwrap()
+      .starts_with("Usage: open [OPTIONS] <path>"));
+
+    let matches = parse(&config(), ["-h"]).unwrap();
+    let help = matches.args["help"].value.as_str().unwrap().to_string();
+    assert!(help.starts_with("Notes app\n"));
+    assert!(help.contains("-p, --port <PORT>"));
+    assert!(help.contains("open"));
+  }
+}
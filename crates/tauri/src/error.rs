@@ -43,6 +43,9 @@ pub enum Error {
   /// Cannot use the webview reparent function on webview windows.
   #[error("cannot reparent when using a WebviewWindow")]
   CannotReparentWebviewWindow,
+  /// A window cannot be added as a tab of itself.
+  #[error("cannot add the window `{0}` as a tab of itself")]
+  CannotTabWindowWithItself(String),
   /// Embedded asset not found.
   #[error("asset not found: {0}")]
   AssetNotFound(String),
@@ -129,6 +132,14 @@ pub enum Error {
   #[cfg(not(target_os = "android"))]
   #[error("unknown path")]
   UnknownPath,
+  /// The resource has no hash recorded at build time.
+  #[cfg(all(not(target_os = "android"), feature = "resource-integrity"))]
+  #[error("no integrity hash recorded for resource {0}")]
+  UnknownResource(std::path::PathBuf),
+  /// The resource does not match the hash recorded at build time.
+  #[cfg(all(not(target_os = "android"), feature = "resource-integrity"))]
+  #[error("integrity check failed for resource {0}")]
+  ResourceIntegrity(std::path::PathBuf),
   /// Failed to invoke mobile plugin.
   #[cfg(target_os = "android")]
   #[error(transparent)]
@@ -136,6 +147,9 @@ pub enum Error {
   /// window not found.
   #[error("window not found")]
   WindowNotFound,
+  /// window configuration not found in `tauri.conf.json > app > windows`.
+  #[error("window configuration with label `{0}` not found")]
+  WindowConfigNotFound(String),
   /// The resource id is invalid.
   #[error("The resource id {0} is invalid.")]
   BadResourceId(crate::resources::ResourceId),
@@ -145,6 +159,9 @@ pub enum Error {
   /// webview not found.
   #[error("webview not found")]
   WebviewNotFound,
+  /// A webview is in several regions of a layout.
+  #[error("the webview `{0}` is in several regions of the layout")]
+  DuplicateLayoutWebview(String),
   /// API requires the unstable feature flag.
   #[error("this feature requires the `unstable` flag on Cargo.toml")]
   UnstableFeatureNotSupported,
@@ -160,6 +177,117 @@ pub enum Error {
   /// Bad `__TAURI_INVOKE_KEY__` value received in ipc message.
   #[error("bad __TAURI_INVOKE_KEY__ value received in ipc message")]
   InvokeKey,
+  /// Archive error.
+  #[cfg(feature = "archive")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "archive")))]
+  #[error(transparent)]
+  Archive(#[from] crate::archive::Error),
+  /// Audio device error.
+  #[cfg(feature = "audio")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "audio")))]
+  #[error(transparent)]
+  Audio(#[from] crate::audio::Error),
+  /// OS authentication error.
+  #[cfg(feature = "auth")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "auth")))]
+  #[error(transparent)]
+  Authentication(#[from] crate::auth::Error),
+  /// Bluetooth error.
+  #[cfg(feature = "bluetooth")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "bluetooth")))]
+  #[error(transparent)]
+  Bluetooth(#[from] crate::bluetooth::Error),
+  /// Camera error.
+  #[cfg(feature = "camera")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "camera")))]
+  #[error(transparent)]
+  Camera(#[from] crate::camera::Error),
+  /// Command line parsing error.
+  #[error(transparent)]
+  Cli(#[from] crate::cli::Error),
+  /// Feature flags error.
+  #[error(transparent)]
+  FeatureFlags(#[from] crate::feature_flags::Error),
+  /// Font error.
+  #[cfg(feature = "font")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "font")))]
+  #[error(transparent)]
+  Font(#[from] crate::font::Error),
+  /// Geolocation error.
+  #[cfg(feature = "geolocation")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "geolocation")))]
+  #[error(transparent)]
+  Geolocation(#[from] crate::geolocation::Error),
+  /// Integrity error.
+  #[cfg(feature = "integrity")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "integrity")))]
+  #[error(transparent)]
+  Integrity(#[from] crate::integrity::Error),
+  /// Printer error.
+  #[cfg(feature = "printer")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "printer")))]
+  #[error(transparent)]
+  Printer(#[from] crate::printer::Error),
+  /// Safe storage error.
+  #[cfg(feature = "safe-storage")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "safe-storage")))]
+  #[error(transparent)]
+  SafeStorage(#[from] crate::safe_storage::Error),
+  /// Serial port error.
+  #[cfg(feature = "serial")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "serial")))]
+  #[error(transparent)]
+  Serial(#[from] crate::serial::Error),
+  /// The async runtime was already set or used, see [`crate::async_runtime::try_set`].
+  #[error("the async runtime is already initialized")]
+  AsyncRuntimeAlreadySet,
+  /// The async runtime is not a multi-thread tokio runtime, see [`crate::async_runtime::try_set`].
+  #[error("the async runtime must be a multi-thread tokio runtime")]
+  UnsupportedAsyncRuntime,
+  /// The work exceeded the time limit of its [`crate::process::Budget`].
+  #[error("the time budget of {0:?} was exceeded")]
+  BudgetExceeded(std::time::Duration),
+  /// Failed to read or write a zip archive.
+  #[cfg(any(
+    feature = "archive",
+    feature = "diagnostics",
+    feature = "frontend-patches"
+  ))]
+  #[cfg_attr(
+    docsrs,
+    doc(cfg(any(
+      feature = "archive",
+      feature = "diagnostics",
+      feature = "frontend-patches"
+    )))
+  )]
+  #[error(transparent)]
+  Zip(#[from] zip::result::ZipError),
+  /// Failed to download, verify or install a frontend patch.
+  #[cfg(feature = "frontend-patches")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "frontend-patches")))]
+  #[error("invalid frontend patch: {0}")]
+  FrontendPatch(String),
+  /// Failed to load or migrate a [`crate::PersistentState`].
+  #[error("failed to load the persistent state: {0}")]
+  PersistentState(String),
+  /// An IPC payload exceeded its limit in the `app > ipc` configuration.
+  #[error(
+    "the {kind} payload of {size} bytes exceeds the `app > ipc > {option}` limit of {limit} bytes"
+  )]
+  IpcPayloadTooLarge {
+    /// The kind of payload.
+    kind: &'static str,
+    /// The configuration option setting the limit.
+    option: &'static str,
+    /// The size of the payload in bytes.
+    size: usize,
+    /// The limit in bytes.
+    limit: u64,
+  },
+  /// A command tried to access a path outside of the directories or scope it is allowed to use.
+  #[error("path not allowed: {0}")]
+  PathForbidden(std::path::PathBuf),
 }
 
 impl From<getrandom::Error> for Error {
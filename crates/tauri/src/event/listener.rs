@@ -65,6 +65,9 @@ struct InnerListeners {
   function_name: &'static str,
   listeners_object_name: &'static str,
   next_event_id: Arc<AtomicU32>,
+  /// The panics of the listeners with their payload, reported and resumed by the manager after the emit.
+  #[cfg(dev)]
+  panics: Mutex<Vec<(crate::panic::PanicReport, Box<dyn std::any::Any + Send>)>>,
 }
 
 /// A self-contained event manager.
@@ -83,6 +86,8 @@ impl Default for Listeners {
         function_name: "__internal_unstable_listeners_function_id__",
         listeners_object_name: "__internal_unstable_listeners_object_id__",
         next_event_id: Default::default(),
+        #[cfg(dev)]
+        panics: Mutex::default(),
       }),
     }
   }
@@ -203,7 +208,25 @@ impl Listeners {
           let handlers = handlers.iter();
           let handlers = handlers.filter(|(_, h)| match_any_or_filter(&h.target, &filter));
           for (&id, Handler { callback, .. }) in handlers {
+            #[cfg(feature = "tracing")]
+            let _span =
+              tracing::trace_span!("emit::listener", event = emit_args.event_name, id).entered();
             maybe_pending = true;
+            #[cfg(dev)]
+            if let Err(payload) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+              (callback)(Event::new(id, emit_args.payload.clone()))
+            })) {
+              self.inner.panics.lock().unwrap().push((
+                crate::panic::PanicReport::new(
+                  &*payload,
+                  crate::panic::PanicSource::Event {
+                    name: emit_args.event_name.clone(),
+                  },
+                ),
+                payload,
+              ));
+            }
+            #[cfg(not(dev))]
             (callback)(Event::new(id, emit_args.payload.clone()))
           }
         }
@@ -217,6 +240,14 @@ impl Listeners {
     Ok(())
   }
 
+  /// Takes the panics caught in the listeners since the last call.
+  #[cfg(dev)]
+  pub(crate) fn take_panics(
+    &self,
+  ) -> Vec<(crate::panic::PanicReport, Box<dyn std::any::Any + Send>)> {
+    std::mem::take(&mut *self.inner.panics.lock().unwrap())
+  }
+
   /// Emits the given event with its payload.
   pub(crate) fn emit(&self, emit_args: EmitArgs) -> crate::Result<()> {
     self.emit_filter(emit_args, None::<&dyn Fn(&EventTarget) -> bool>)
@@ -286,6 +317,14 @@ impl Listeners {
           .filter(|handler| match_any_or_filter(&handler.target, &filter))
           .map(|handler| handler.id)
           .collect::<Vec<_>>();
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!(
+          "emit::js",
+          event,
+          webview = webview.label(),
+          listeners = ids.len()
+        )
+        .entered();
         webview.emit_js(emit_args, &ids)?;
       }
 
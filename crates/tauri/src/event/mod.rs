@@ -196,12 +196,27 @@ pub fn emit_js_script(
   event_emit_function_name: &str,
   emit_args: &EmitArgs,
   serialized_ids: &str,
+) -> crate::Result<String> {
+  emit_js_script_with_payload(
+    event_emit_function_name,
+    emit_args,
+    &emit_args.payload,
+    serialized_ids,
+  )
+}
+
+/// Like [`emit_js_script`], with a JavaScript expression evaluating to the payload.
+pub fn emit_js_script_with_payload(
+  event_emit_function_name: &str,
+  emit_args: &EmitArgs,
+  payload: &str,
+  serialized_ids: &str,
 ) -> crate::Result<String> {
   Ok(format!(
     "(function () {{ const fn = window['{}']; fn && fn({{event: {}, payload: {}}}, {ids}) }})()",
     event_emit_function_name,
     emit_args.event,
-    emit_args.payload,
+    payload,
     ids = serialized_ids,
   ))
 }
@@ -0,0 +1,267 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Runtime feature flags shared between Rust and the frontend, e.g. for staged feature rollouts.
+//!
+//! The flags are seeded from `tauri.conf.json > app > featureFlags`, then overridden by the JSON object
+//! of the [`ENV_VAR`] environment variable, and can be updated at runtime with [`FeatureFlags::merge`],
+//! typically with a remote JSON document loaded by [`FeatureFlags::fetch`].
+//!
+//! The webviews read a snapshot of the flags with the `featureFlags` module of `@tauri-apps/api`,
+//! which is updated before the [`FEATURE_FLAGS_CHANGED_EVENT`] event is emitted and when a page starts loading.
+
+use std::{collections::BTreeMap, sync::RwLock};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{sealed::ManagerBase, AppHandle, Emitter, Manager, Runtime, Webview};
+
+/// The environment variable with a JSON object of flags overriding the configured ones.
+pub const ENV_VAR: &str = "TAURI_FEATURE_FLAGS";
+
+/// Event emitted with the new flags when they change.
+pub const FEATURE_FLAGS_CHANGED_EVENT: &str = "tauri://feature-flags-changed";
+
+/// Feature flags errors.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+  /// The flags are not a JSON object.
+  #[error("invalid feature flags: {0}")]
+  Invalid(String),
+  /// The remote flags could not be downloaded.
+  #[error("failed to fetch the feature flags: {0}")]
+  Fetch(String),
+}
+
+/// The feature flags of the app, see the [module documentation](self).
+///
+/// Access it with [`AppHandle::features`].
+pub struct FeatureFlags<R: Runtime> {
+  app: AppHandle<R>,
+  flags: RwLock<Flags>,
+}
+
+/// The flags with a version incremented on each change,
+/// so the webviews ignore the snapshots evaluated out of order.
+struct Flags {
+  version: u64,
+  values: BTreeMap<String, Value>,
+}
+
+impl Flags {
+  fn script(&self) -> crate::Result<String> {
+    snapshot_script(self.version, &self.values)
+  }
+}
+
+impl<R: Runtime> FeatureFlags<R> {
+  pub(crate) fn new(app: AppHandle<R>) -> Self {
+    let mut flags = app
+      .config()
+      .app
+      .feature_flags
+      .iter()
+      .map(|(name, value)| (name.clone(), value.clone()))
+      .collect::<BTreeMap<_, _>>();
+    if let Ok(overrides) = std::env::var(ENV_VAR) {
+      match parse(&overrides) {
+        Ok(overrides) => apply(&mut flags, overrides),
+        Err(e) => log::error!("ignoring the `{ENV_VAR}` environment variable: {e}"),
+      }
+    }
+
+    Self {
+      app,
+      flags: RwLock::new(Flags {
+        version: 0,
+        values: flags,
+      }),
+    }
+  }
+
+  /// Gets the value of the flag, or `None` if it is not set or does not deserialize to `T`.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let max_tabs = app.features().get::<u32>("maxTabs").unwrap_or(10);
+  ///     Ok(())
+  ///   });
+  /// ```
+  pub fn get<T: DeserializeOwned>(&self, name: &str) -> Option<T> {
+    let flags = self.flags.read().unwrap();
+    serde_json::from_value(flags.values.get(name)?.clone()).ok()
+  }
+
+  /// Whether the flag is set to `true`.
+  pub fn is_enabled(&self, name: &str) -> bool {
+    self.get::<bool>(name).unwrap_or(false)
+  }
+
+  /// All the flags.
+  pub fn snapshot(&self) -> BTreeMap<String, Value> {
+    self.flags.read().unwrap().values.clone()
+  }
+
+  /// Sets the value of the flag.
+  pub fn set<T: Serialize>(&self, name: impl Into<String>, value: T) -> crate::Result<()> {
+    let value = serde_json::to_value(value)?;
+    self.merge(Map::from_iter([(name.into(), value)]))
+  }
+
+  /// Removes the flag.
+  pub fn remove(&self, name: &str) -> crate::Result<()> {
+    self.merge(Map::from_iter([(name.to_string(), Value::Null)]))
+  }
+
+  /// Updates the given flags, removing the ones set to `null`, and notifies Rust and the webviews if they changed.
+  pub fn merge(&self, flags: Map<String, Value>) -> crate::Result<()> {
+    let (script, snapshot) = {
+      let mut current = self.flags.write().unwrap();
+      let previous = current.values.clone();
+      apply(&mut current.values, flags);
+      if current.values == previous {
+        return Ok(());
+      }
+      current.version += 1;
+      (current.script()?, current.values.clone())
+    };
+
+    self.app.manager().webview.eval_script_all(script)?;
+    self.app.emit(FEATURE_FLAGS_CHANGED_EVENT, snapshot)
+  }
+
+  /// Updates the flags with the given JSON object, see [`Self::merge`].
+  pub fn merge_json(&self, json: &str) -> crate::Result<()> {
+    self.merge(parse(json)?)
+  }
+
+  /// Downloads the JSON object at the given URL and updates the flags with it, see [`Self::merge`].
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let handle = app.handle().clone();
+  ///     tauri::async_runtime::spawn(async move {
+  ///       let url = "https://example.com/flags.json".parse().unwrap();
+  ///       if let Err(e) = handle.features().fetch(url).await {
+  ///         eprintln!("{e}");
+  ///       }
+  ///     });
+  ///     Ok(())
+  ///   });
+  /// ```
+  pub async fn fetch(&self, url: url::Url) -> crate::Result<()> {
+    let json = reqwest::get(url)
+      .await
+      .and_then(|response| response.error_for_status())
+      .map_err(|e| Error::Fetch(e.to_string()))?
+      .text()
+      .await
+      .map_err(|e| Error::Fetch(e.to_string()))?;
+    self.merge_json(&json)
+  }
+
+  /// The script defining the snapshot of the flags read by the webviews.
+  pub(crate) fn initialization_script(&self) -> crate::Result<String> {
+    self.flags.read().unwrap().script()
+  }
+
+  /// Defines the current snapshot in the webview, since its initialization script
+  /// holds the flags of the webview creation and runs again on each page load.
+  pub(crate) fn on_page_load(&self, webview: &Webview<R>) -> crate::Result<()> {
+    webview.eval(&self.initialization_script()?)
+  }
+}
+
+fn parse(json: &str) -> Result<Map<String, Value>, Error> {
+  match serde_json::from_str(json) {
+    Ok(Value::Object(flags)) => Ok(flags),
+    Ok(_) => Err(Error::Invalid("expected a JSON object".into())),
+    Err(e) => Err(Error::Invalid(e.to_string())),
+  }
+}
+
+fn apply(current: &mut BTreeMap<String, Value>, flags: Map<String, Value>) {
+  for (name, value) in flags {
+    if value.is_null() {
+      current.remove(&name);
+    } else {
+      current.insert(name, value);
+    }
+  }
+}
+
+fn snapshot_script(version: u64, flags: &BTreeMap<String, Value>) -> crate::Result<String> {
+  Ok(format!(
+    r#"(function () {{
+  var internals = window.__TAURI_INTERNALS__
+  if ((internals.featureFlagsVersion || 0) > {version}) return
+  Object.defineProperty(internals, 'featureFlagsVersion', {{ value: {version}, configurable: true }})
+  Object.defineProperty(internals, 'featureFlags', {{ value: Object.freeze({flags}), configurable: true }})
+}})()"#,
+    flags = serde_json::to_string(flags)?
+  ))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    test::{mock_builder, mock_context, noop_assets},
+    Listener,
+  };
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn seeds_and_merges_flags() {
+    let mut context = mock_context(noop_assets());
+    context
+      .config_mut()
+      .app
+      .feature_flags
+      .insert("newEditor".into(), Value::Bool(false));
+    context
+      .config_mut()
+      .app
+      .feature_flags
+      .insert("maxTabs".into(), Value::from(5));
+    let app = mock_builder().build(context).unwrap();
+    let features = app.features();
+
+    assert!(!features.is_enabled("newEditor"));
+    assert_eq!(features.get::<u32>("maxTabs"), Some(5));
+    assert_eq!(features.get::<String>("maxTabs"), None);
+
+    let changes = Arc::new(Mutex::new(0));
+    let changes_ = changes.clone();
+    app.listen_any(FEATURE_FLAGS_CHANGED_EVENT, move |_| {
+      *changes_.lock().unwrap() += 1;
+    });
+
+    features
+      .merge_json(r#"{ "newEditor": true, "maxTabs": null }"#)
+      .unwrap();
+    assert!(features.is_enabled("newEditor"));
+    assert_eq!(features.get::<u32>("maxTabs"), None);
+    // unchanged flags are not emitted
+    features.set("newEditor", true).unwrap();
+    assert_eq!(*changes.lock().unwrap(), 1);
+
+    assert!(features.merge_json("[]").is_err());
+
+    // the snapshots are versioned so the stale ones are ignored
+    assert_eq!(features.flags.read().unwrap().version, 1);
+    assert!(features
+      .initialization_script()
+      .unwrap()
+      .contains("featureFlagsVersion || 0) > 1)"));
+  }
+}
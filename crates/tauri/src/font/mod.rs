@@ -0,0 +1,486 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! System font enumeration and per-user font installation.
+//!
+//! The webviews cannot enumerate the installed fonts, so font pickers list them with [`families`]
+//! or the `core:font` commands, which are not enabled by the default permission set.
+//! The `install` command only installs the fonts in the app data, app local data, app cache and download directories,
+//! or allowed by the asset protocol scope when the `protocol-asset` feature is enabled.
+
+use std::{
+  collections::{BTreeMap, BTreeSet},
+  path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+pub(crate) mod plugin;
+
+/// The font file extensions supported by [`install`].
+const FONT_EXTENSIONS: &[&str] = &["ttf", "otf", "ttc", "otc"];
+
+/// Font errors.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+  /// The font system is not available on this system.
+  #[error("fonts are not available: {0}")]
+  Unavailable(String),
+  /// The file is not a TrueType or OpenType font.
+  #[error("unsupported font file {0}")]
+  UnsupportedFormat(PathBuf),
+  /// The font could not be registered with the system.
+  #[error("failed to install the font: {0}")]
+  Install(String),
+  /// A different font is already installed with the same file name.
+  #[error("a different font is already installed at {0}")]
+  AlreadyInstalled(PathBuf),
+}
+
+/// A font family installed on the system.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct FontFamily {
+  /// The family name, as used by the CSS `font-family` property.
+  pub name: String,
+  /// The style names of the family fonts, e.g. `Regular` or `Bold Italic`.
+  pub styles: Vec<String>,
+}
+
+/// Groups the `(family, style)` pairs of the installed fonts by family, sorted by name.
+fn group(fonts: impl IntoIterator<Item = (String, String)>) -> Vec<FontFamily> {
+  let mut families = BTreeMap::<String, BTreeSet<String>>::new();
+  for (family, style) in fonts {
+    let family = family.trim();
+    if family.is_empty() {
+      continue;
+    }
+    let styles = families.entry(family.to_string()).or_default();
+    let style = style.trim();
+    if !style.is_empty() {
+      styles.insert(style.to_string());
+    }
+  }
+  families
+    .into_iter()
+    .map(|(name, styles)| FontFamily {
+      name,
+      styles: styles.into_iter().collect(),
+    })
+    .collect()
+}
+
+/// Lists the font families installed on the system with their styles.
+///
+/// ## Platform-specific
+///
+/// - **Linux:** Requires the `fc-list` command of fontconfig.
+/// - **Android / iOS:** Unsupported, returns an empty list.
+pub fn families() -> crate::Result<Vec<FontFamily>> {
+  platform::fonts().map(group).map_err(Into::into)
+}
+
+/// Installs a TrueType or OpenType font file for the current user, returning the installed file path.
+///
+/// The font can be used by the webviews created after the installation.
+/// Installing the same font again returns the installed file path,
+/// while a different font with the same file name is not replaced and returns [`Error::AlreadyInstalled`].
+///
+/// ## Platform-specific
+///
+/// - **Linux:** Copies the font to `$XDG_DATA_HOME/fonts` and refreshes the fontconfig cache.
+/// - **macOS:** Copies the font to `~/Library/Fonts`.
+/// - **Windows:** Copies the font to `%LOCALAPPDATA%\Microsoft\Windows\Fonts` and registers it. Requires Windows 10 1809+.
+/// - **Android / iOS:** Unsupported.
+pub fn install<P: AsRef<Path>>(path: P) -> crate::Result<PathBuf> {
+  let path = path.as_ref();
+  let supported = path
+    .extension()
+    .and_then(|extension| extension.to_str())
+    .is_some_and(|extension| FONT_EXTENSIONS.contains(&extension.to_lowercase().as_str()));
+  let file_name = path
+    .file_name()
+    .filter(|_| supported)
+    .ok_or_else(|| Error::UnsupportedFormat(path.to_path_buf()))?;
+
+  let dir = platform::fonts_dir()?;
+  std::fs::create_dir_all(&dir)?;
+  let installed = dir.join(file_name);
+  if !copy_new(path, &installed)? {
+    // the same font is already installed
+    return Ok(installed);
+  }
+  platform::register(&installed)?;
+  Ok(installed)
+}
+
+/// Copies the font to a file that must not exist, returning `false` if the same font is already there.
+///
+/// An installed font is never replaced since other apps may use it.
+fn copy_new(path: &Path, destination: &Path) -> crate::Result<bool> {
+  let mut source = std::fs::File::open(path)?;
+  match std::fs::OpenOptions::new()
+    .write(true)
+    .create_new(true)
+    .open(destination)
+  {
+    Ok(mut file) => {
+      if let Err(e) = std::io::copy(&mut source, &mut file) {
+        drop(file);
+        let _ = std::fs::remove_file(destination);
+        return Err(e.into());
+      }
+      Ok(true)
+    }
+    Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+      if std::fs::read(path)? == std::fs::read(destination)? {
+        Ok(false)
+      } else {
+        Err(Error::AlreadyInstalled(destination.to_path_buf()).into())
+      }
+    }
+    Err(e) => Err(e.into()),
+  }
+}
+
+/// Parses the `fc-list --format '%{family[0]}\t%{style[0]}\n'` output.
+#[cfg_attr(any(windows, target_os = "macos", mobile), allow(dead_code))]
+fn parse_fc_list(output: &str) -> Vec<(String, String)> {
+  output
+    .lines()
+    .filter_map(|line| {
+      let (family, style) = line.split_once('\t').unwrap_or((line, ""));
+      (!family.is_empty()).then(|| (family.to_string(), style.to_string()))
+    })
+    .collect()
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use super::{parse_fc_list, Error};
+  use std::{
+    path::{Path, PathBuf},
+    process::Command,
+  };
+
+  pub fn fonts() -> Result<Vec<(String, String)>, Error> {
+    let output = Command::new("fc-list")
+      .args(["--format", "%{family[0]}\t%{style[0]}\n"])
+      .output()
+      .map_err(|e| Error::Unavailable(e.to_string()))?;
+    if !output.status.success() {
+      return Err(Error::Unavailable(
+        String::from_utf8_lossy(&output.stderr).trim().to_string(),
+      ));
+    }
+    Ok(parse_fc_list(&String::from_utf8_lossy(&output.stdout)))
+  }
+
+  pub fn fonts_dir() -> Result<PathBuf, Error> {
+    dirs::font_dir().ok_or_else(|| Error::Unavailable("unknown fonts directory".into()))
+  }
+
+  pub fn register(path: &Path) -> Result<(), Error> {
+    // fontconfig also rescans the directories when they change, this makes the font available right away
+    let dir = path.parent().unwrap_or(path);
+    match Command::new("fc-cache").arg(dir).status() {
+      Ok(status) if status.success() => {}
+      Ok(status) => log::warn!("fc-cache failed with {status}"),
+      Err(e) => log::warn!("failed to run fc-cache: {e}"),
+    }
+    Ok(())
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use super::Error;
+  use std::path::{Path, PathBuf};
+
+  use objc2::{class, msg_send, msg_send_id, rc::Retained, runtime::AnyObject};
+  use objc2_foundation::NSString;
+
+  pub fn fonts() -> Result<Vec<(String, String)>, Error> {
+    let mut fonts = Vec::new();
+    unsafe {
+      let manager: Retained<AnyObject> = msg_send_id![class!(NSFontManager), sharedFontManager];
+      let families: Retained<AnyObject> = msg_send_id![&manager, availableFontFamilies];
+      let count: usize = msg_send![&families, count];
+      for i in 0..count {
+        let family: Retained<NSString> = msg_send_id![&families, objectAtIndex: i];
+        // `[PostScript name, style name, weight, traits]` arrays
+        let members: Option<Retained<AnyObject>> =
+          msg_send_id![&manager, availableMembersOfFontFamily: &*family];
+        let Some(members) = members else {
+          fonts.push((family.to_string(), String::new()));
+          continue;
+        };
+        let member_count: usize = msg_send![&members, count];
+        for j in 0..member_count {
+          let member: Retained<AnyObject> = msg_send_id![&members, objectAtIndex: j];
+          let style: Retained<NSString> = msg_send_id![&member, objectAtIndex: 1usize];
+          fonts.push((family.to_string(), style.to_string()));
+        }
+      }
+    }
+    Ok(fonts)
+  }
+
+  pub fn fonts_dir() -> Result<PathBuf, Error> {
+    dirs::font_dir().ok_or_else(|| Error::Unavailable("unknown fonts directory".into()))
+  }
+
+  pub fn register(_path: &Path) -> Result<(), Error> {
+    // the fonts of `~/Library/Fonts` are activated automatically
+    Ok(())
+  }
+}
+
+#[cfg(windows)]
+mod platform {
+  use super::Error;
+  use std::{
+    os::windows::ffi::OsStrExt,
+    path::{Path, PathBuf},
+  };
+  use windows::{
+    core::{w, PCWSTR},
+    Win32::{
+      Foundation::{ERROR_SUCCESS, HWND, LPARAM, WPARAM},
+      Graphics::Gdi::{
+        AddFontResourceW, EnumFontFamiliesExW, GetDC, ReleaseDC, DEFAULT_CHARSET, ENUMLOGFONTEXW,
+        HDC, LOGFONTW, TEXTMETRICW,
+      },
+      System::Registry::{
+        RegCloseKey, RegOpenKeyExW, RegSetValueExW, HKEY, HKEY_CURRENT_USER, KEY_SET_VALUE, REG_SZ,
+      },
+      UI::WindowsAndMessaging::{PostMessageW, HWND_BROADCAST, WM_FONTCHANGE},
+    },
+  };
+
+  fn wide(value: &[u16]) -> String {
+    let len = value.iter().position(|c| *c == 0).unwrap_or(value.len());
+    String::from_utf16_lossy(&value[..len])
+  }
+
+  unsafe extern "system" fn enum_font(
+    font: *const LOGFONTW,
+    _metric: *const TEXTMETRICW,
+    _font_type: u32,
+    fonts: LPARAM,
+  ) -> i32 {
+    let fonts = &mut *(fonts.0 as *mut Vec<(String, String)>);
+    // the font is an `ENUMLOGFONTEXW` since `EnumFontFamiliesExW` is used
+    let font = &*(font as *const ENUMLOGFONTEXW);
+    fonts.push((wide(&font.elfLogFont.lfFaceName), wide(&font.elfStyle)));
+    1
+  }
+
+  /// Enumerates the fonts of the family, or one font of each family if the name is empty.
+  unsafe fn enumerate(dc: HDC, family: &str) -> Vec<(String, String)> {
+    let mut font = LOGFONTW {
+      lfCharSet: DEFAULT_CHARSET,
+      ..Default::default()
+    };
+    for (i, c) in family
+      .encode_utf16()
+      .take(font.lfFaceName.len() - 1)
+      .enumerate()
+    {
+      font.lfFaceName[i] = c;
+    }
+    let mut fonts = Vec::new();
+    EnumFontFamiliesExW(
+      dc,
+      &font,
+      Some(enum_font),
+      LPARAM(&mut fonts as *mut Vec<(String, String)> as isize),
+      0,
+    );
+    fonts
+  }
+
+  pub fn fonts() -> Result<Vec<(String, String)>, Error> {
+    unsafe {
+      let dc = GetDC(HWND::default());
+      if dc.is_invalid() {
+        return Err(Error::Unavailable(
+          "failed to get the screen device context".into(),
+        ));
+      }
+      let mut families = enumerate(dc, "")
+        .into_iter()
+        .map(|(family, _)| family)
+        // the vertical variants of the CJK fonts
+        .filter(|family| !family.starts_with('@'))
+        .collect::<Vec<_>>();
+      families.sort();
+      families.dedup();
+      let fonts = families
+        .iter()
+        .flat_map(|family| enumerate(dc, family))
+        .collect();
+      ReleaseDC(HWND::default(), dc);
+      Ok(fonts)
+    }
+  }
+
+  pub fn fonts_dir() -> Result<PathBuf, Error> {
+    dirs::data_local_dir()
+      .map(|dir| dir.join("Microsoft").join("Windows").join("Fonts"))
+      .ok_or_else(|| Error::Unavailable("unknown fonts directory".into()))
+  }
+
+  pub fn register(path: &Path) -> Result<(), Error> {
+    let path_w = path
+      .as_os_str()
+      .encode_wide()
+      .chain(std::iter::once(0))
+      .collect::<Vec<_>>();
+    let name = path
+      .file_stem()
+      .map(|stem| format!("{} (TrueType)", stem.to_string_lossy()))
+      .unwrap_or_default()
+      .encode_utf16()
+      .chain(std::iter::once(0))
+      .collect::<Vec<_>>();
+    let data = path_w
+      .iter()
+      .flat_map(|c| c.to_le_bytes())
+      .collect::<Vec<_>>();
+
+    unsafe {
+      // the per-user fonts are loaded at sign in from this key
+      let mut key = HKEY::default();
+      let result = RegOpenKeyExW(
+        HKEY_CURRENT_USER,
+        w!("Software\\Microsoft\\Windows NT\\CurrentVersion\\Fonts"),
+        0,
+        KEY_SET_VALUE,
+        &mut key,
+      );
+      if result != ERROR_SUCCESS {
+        return Err(Error::Install(
+          windows::core::Error::from(result.to_hresult()).to_string(),
+        ));
+      }
+      let result = RegSetValueExW(key, PCWSTR(name.as_ptr()), 0, REG_SZ, Some(&data));
+      let _ = RegCloseKey(key);
+      if result != ERROR_SUCCESS {
+        return Err(Error::Install(
+          windows::core::Error::from(result.to_hresult()).to_string(),
+        ));
+      }
+
+      // makes the font available to the running apps
+      if AddFontResourceW(PCWSTR(path_w.as_ptr())) == 0 {
+        return Err(Error::Install("the file is not a valid font".into()));
+      }
+      let _ = PostMessageW(HWND_BROADCAST, WM_FONTCHANGE, WPARAM(0), LPARAM(0));
+    }
+    Ok(())
+  }
+}
+
+#[cfg(mobile)]
+mod platform {
+  use super::Error;
+  use std::path::{Path, PathBuf};
+
+  pub fn fonts() -> Result<Vec<(String, String)>, Error> {
+    Ok(Vec::new())
+  }
+
+  pub fn fonts_dir() -> Result<PathBuf, Error> {
+    Err(Error::Unavailable("unsupported platform".into()))
+  }
+
+  pub fn register(_path: &Path) -> Result<(), Error> {
+    Err(Error::Unavailable("unsupported platform".into()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_fc_list() {
+    let output = "DejaVu Sans\tBold\nDejaVu Sans\tBook\nNoto Color Emoji\n";
+    assert_eq!(
+      parse_fc_list(output),
+      vec![
+        ("DejaVu Sans".into(), "Bold".into()),
+        ("DejaVu Sans".into(), "Book".into()),
+        ("Noto Color Emoji".into(), String::new()),
+      ]
+    );
+  }
+
+  #[test]
+  fn groups_families() {
+    let fonts = vec![
+      ("Inter".to_string(), "Regular".to_string()),
+      ("Cantarell".to_string(), "Bold".to_string()),
+      ("Inter".to_string(), "Bold Italic".to_string()),
+      ("Inter".to_string(), "Regular".to_string()),
+      (" ".to_string(), "Regular".to_string()),
+      ("Symbol".to_string(), String::new()),
+    ];
+    assert_eq!(
+      group(fonts),
+      vec![
+        FontFamily {
+          name: "Cantarell".into(),
+          styles: vec!["Bold".into()],
+        },
+        FontFamily {
+          name: "Inter".into(),
+          styles: vec!["Bold Italic".into(), "Regular".into()],
+        },
+        FontFamily {
+          name: "Symbol".into(),
+          styles: Vec::new(),
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn never_replaces_installed_fonts() {
+    let dir = std::env::temp_dir().join(format!("tauri-font-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let font = dir.join("font.ttf");
+    let other = dir.join("other.ttf");
+    let installed = dir.join("installed.ttf");
+    std::fs::write(&font, b"font").unwrap();
+    std::fs::write(&other, b"other font").unwrap();
+
+    assert!(copy_new(&font, &installed).unwrap());
+    assert!(!copy_new(&font, &installed).unwrap());
+    assert!(matches!(
+      copy_new(&other, &installed),
+      Err(crate::Error::Font(Error::AlreadyInstalled(path))) if path == installed
+    ));
+    assert_eq!(std::fs::read(&installed).unwrap(), b"font");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn rejects_unsupported_files() {
+    assert!(matches!(
+      install("font.woff2"),
+      Err(crate::Error::Font(Error::UnsupportedFormat(_)))
+    ));
+  }
+}
@@ -0,0 +1,33 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::path::PathBuf;
+
+use super::FontFamily;
+use crate::{
+  command,
+  path::check_command_path,
+  plugin::{Builder, TauriPlugin},
+  AppHandle, Runtime,
+};
+
+#[command(root = "crate")]
+async fn families() -> crate::Result<Vec<FontFamily>> {
+  crate::async_runtime::spawn_blocking(super::families).await?
+}
+
+#[command(root = "crate")]
+async fn install<R: Runtime>(app: AppHandle<R>, path: PathBuf) -> crate::Result<PathBuf> {
+  crate::async_runtime::spawn_blocking(move || {
+    let path = check_command_path(&app, &path)?;
+    super::install(path)
+  })
+  .await?
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("font")
+    .invoke_handler(crate::generate_handler![families, install])
+    .build()
+}
@@ -0,0 +1,577 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Signed frontend patches replacing the embedded frontend assets without updating the app.
+//!
+//! A patch is a zip archive of the built frontend, such as the `frontendDist` directory,
+//! described by a JSON manifest binding the archive to a patch version and an app version:
+//!
+//! ```json
+//! { "version": "1.0.0-patch.1", "appVersion": "1.0.0", "sha256": "<hex encoded SHA-256 of the archive>" }
+//! ```
+//!
+//! The manifest is signed with the updater key format (`tauri signer sign manifest.json`),
+//! so a signed archive cannot be installed as another patch version or for another app version.
+//! The patch version is a semantic version and must be greater than the installed patch,
+//! so a previously signed patch cannot be installed again over a newer one.
+//! The patch is installed for the current app version and served instead of the embedded assets from the next launch,
+//! so the frontend never mixes two versions. Updating the app discards the patch,
+//! since it was built against the commands of the previous version.
+//!
+//! The archive, the manifest and its signature are stored in the [app local data directory](crate::path::PathResolver::app_local_data_dir),
+//! and verified again on each launch before the patch is served from memory,
+//! so files modified after the install are never served.
+//!
+//! Patches require the `app > frontendPatches` configuration and are not applied in development,
+//! where the frontend is served by the `build > devUrl` server.
+//!
+//! The CSP hashes of the inline scripts and styles are computed at build time from the embedded assets,
+//! so a patch must not change them when a CSP is configured.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! #[tauri::command]
+//! async fn install_patch(
+//!   app: tauri::AppHandle,
+//!   url: tauri::Url,
+//!   manifest: String,
+//!   signature: String,
+//! ) -> tauri::Result<()> {
+//!   app.frontend_patcher().download_and_install(url, &manifest, &signature).await
+//! }
+//! ```
+
+use std::{
+  borrow::Cow,
+  collections::HashMap,
+  io::{Cursor, Read},
+  path::{Path, PathBuf},
+  sync::{Arc, OnceLock},
+};
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri_utils::assets::{AssetKey, AssetsIter, CspHash};
+
+use crate::{App, AppHandle, Assets, Config, Error, Manager, PackageInfo, Runtime};
+
+/// The directory of the patches in the app local data directory.
+const PATCHES_DIR: &str = "frontend-patches";
+/// The file pointing to the installed patch.
+const CURRENT: &str = "current.json";
+/// The patch archive in the directory of a patch.
+const ARCHIVE: &str = "patch.zip";
+/// The signed manifest in the directory of a patch.
+const MANIFEST: &str = "manifest.json";
+/// The signature of the manifest in the directory of a patch.
+const SIGNATURE: &str = "manifest.json.sig";
+
+/// The signed description of a patch.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+  version: String,
+  app_version: String,
+  sha256: String,
+}
+
+/// The installed patch, read at startup.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Current {
+  version: String,
+  app_version: String,
+}
+
+/// A verified patch loaded in memory.
+struct Patch {
+  version: String,
+  files: HashMap<String, Vec<u8>>,
+}
+
+/// The patch served since the app started, shared with the [`PatchedAssets`] serving it.
+#[derive(Clone, Default)]
+pub(crate) struct ActivePatch(Arc<OnceLock<Patch>>);
+
+fn patches_dir<R: Runtime>(app: &AppHandle<R>) -> crate::Result<PathBuf> {
+  app
+    .path()
+    .app_local_data_dir()
+    .map(|dir| dir.join(PATCHES_DIR))
+}
+
+/// Wraps the embedded assets so they can be replaced by the patch loaded in [`apply`].
+pub(crate) fn wrap<R: Runtime>(
+  config: &Config,
+  assets: Box<dyn Assets<R>>,
+) -> (Box<dyn Assets<R>>, ActivePatch) {
+  let active = ActivePatch::default();
+  if config.app.frontend_patches.is_none() || crate::is_dev() {
+    return (assets, active);
+  }
+  let patched = PatchedAssets {
+    patch: active.clone(),
+    embedded: assets,
+  };
+  (Box::new(patched), active)
+}
+
+/// Serves the patch installed for the current app version instead of the embedded assets, if any.
+///
+/// Must run once the path resolver is registered and before the webviews load their assets.
+pub(crate) fn apply<R: Runtime>(app: &AppHandle<R>, active: &ActivePatch) {
+  let Some(patches_config) = app
+    .config()
+    .app
+    .frontend_patches
+    .as_ref()
+    .filter(|_| !crate::is_dev())
+  else {
+    return;
+  };
+  let dir = match patches_dir(app) {
+    Ok(dir) => dir,
+    Err(e) => {
+      log::error!("failed to resolve the frontend patches directory: {e}");
+      return;
+    }
+  };
+  let package_info = app.package_info();
+  let current = std::fs::read(dir.join(CURRENT))
+    .ok()
+    .and_then(|current| serde_json::from_slice::<Current>(&current).ok());
+
+  let Some(current) = current.filter(|current| {
+    current.app_version == package_info.version.to_string() && is_valid_version(&current.version)
+  }) else {
+    return;
+  };
+
+  match load(
+    &dir.join(&current.version),
+    &patches_config.pubkey,
+    package_info,
+  ) {
+    Ok(files) => {
+      log::info!("serving the frontend patch {}", current.version);
+      let _ = active.0.set(Patch {
+        version: current.version,
+        files,
+      });
+    }
+    Err(e) => log::error!(
+      "failed to load the frontend patch {}, serving the embedded assets: {e}",
+      current.version
+    ),
+  }
+}
+
+/// Verifies an installed patch and reads its files.
+fn load(
+  dir: &Path,
+  pubkey: &str,
+  package_info: &PackageInfo,
+) -> crate::Result<HashMap<String, Vec<u8>>> {
+  let archive = std::fs::read(dir.join(ARCHIVE))?;
+  let manifest = std::fs::read_to_string(dir.join(MANIFEST))?;
+  let signature = std::fs::read_to_string(dir.join(SIGNATURE))?;
+  verify(&archive, &manifest, pubkey, &signature, package_info)?;
+  read_archive(&archive)
+}
+
+/// Patch versions are semantic versions, which also makes them safe directory names.
+fn is_valid_version(version: &str) -> bool {
+  semver::Version::parse(version).is_ok()
+}
+
+/// Whether the patch `version` can be installed over the `installed` patch.
+fn is_newer(version: &str, installed: &str) -> bool {
+  match (
+    semver::Version::parse(version),
+    semver::Version::parse(installed),
+  ) {
+    (Ok(version), Ok(installed)) => version > installed,
+    // the installed patch predates the version check
+    (Ok(_), Err(_)) => true,
+    (Err(_), _) => false,
+  }
+}
+
+/// The assets of the verified patch once loaded, falling back to the embedded assets for the CSP hashes only.
+struct PatchedAssets<R: Runtime> {
+  patch: ActivePatch,
+  embedded: Box<dyn Assets<R>>,
+}
+
+impl<R: Runtime> Assets<R> for PatchedAssets<R> {
+  fn setup(&self, app: &App<R>) {
+    self.embedded.setup(app);
+  }
+
+  fn get(&self, key: &AssetKey) -> Option<Cow<'_, [u8]>> {
+    match self.patch.0.get() {
+      Some(patch) => patch
+        .files
+        .get(key.as_ref())
+        .map(|bytes| Cow::Borrowed(bytes.as_slice())),
+      None => self.embedded.get(key),
+    }
+  }
+
+  fn iter(&self) -> Box<AssetsIter<'_>> {
+    match self.patch.0.get() {
+      Some(patch) => Box::new(
+        patch
+          .files
+          .iter()
+          .map(|(key, bytes)| (Cow::Borrowed(key.as_str()), Cow::Borrowed(bytes.as_slice()))),
+      ),
+      None => self.embedded.iter(),
+    }
+  }
+
+  fn csp_hashes(&self, html_path: &AssetKey) -> Box<dyn Iterator<Item = CspHash<'_>> + '_> {
+    self.embedded.csp_hashes(html_path)
+  }
+}
+
+/// Installs the signed frontend patches, see the [module documentation](self).
+///
+/// Use [`AppHandle::frontend_patcher`] to get it.
+pub struct FrontendPatcher<R: Runtime> {
+  pub(crate) app: AppHandle<R>,
+}
+
+impl<R: Runtime> FrontendPatcher<R> {
+  /// The version of the patch served since the app started, or `None` when the embedded assets are served.
+  pub fn active_version(&self) -> Option<String> {
+    self
+      .app
+      .state::<ActivePatch>()
+      .0
+      .get()
+      .map(|patch| patch.version.clone())
+  }
+
+  /// The version of the patch served from the next launch, or `None` when the embedded assets will be served.
+  pub fn installed_version(&self) -> Option<String> {
+    let dir = patches_dir(&self.app).ok()?;
+    let current = std::fs::read(dir.join(CURRENT)).ok()?;
+    let current = serde_json::from_slice::<Current>(&current).ok()?;
+    (current.app_version == self.app.package_info().version.to_string()).then_some(current.version)
+  }
+
+  /// Downloads the patch archive at the given URL and installs it, see [`Self::install`].
+  pub async fn download_and_install(
+    &self,
+    url: url::Url,
+    manifest: &str,
+    signature: &str,
+  ) -> crate::Result<()> {
+    let response = reqwest::get(url)
+      .await
+      .and_then(|response| response.error_for_status())
+      .map_err(|e| Error::FrontendPatch(format!("failed to download the patch: {e}")))?;
+    let archive = response
+      .bytes()
+      .await
+      .map_err(|e| Error::FrontendPatch(format!("failed to download the patch: {e}")))?;
+    self.install(&archive, manifest, signature)
+  }
+
+  /// Verifies the patch archive against its signed manifest and installs it, so it is served from the next launch.
+  ///
+  /// The `manifest` is the JSON manifest described in the [module documentation](self),
+  /// and the `signature` is the content of the signature file generated by `tauri signer sign` for the manifest.
+  /// The manifest must target the current app version with a patch version greater than the [installed one](Self::installed_version),
+  /// and the archive must contain the `index.html` file at its root.
+  ///
+  /// The patch is written next to the other patches and then made current by an atomic rename,
+  /// so an interrupted install leaves the previous patch in place.
+  pub fn install(&self, archive: &[u8], manifest: &str, signature: &str) -> crate::Result<()> {
+    let config = self
+      .app
+      .config()
+      .app
+      .frontend_patches
+      .as_ref()
+      .ok_or_else(|| Error::FrontendPatch("`app > frontendPatches` is not configured".into()))?;
+    let version = verify(
+      archive,
+      manifest,
+      &config.pubkey,
+      signature,
+      self.app.package_info(),
+    )?
+    .version;
+    if let Some(installed) = self.installed_version() {
+      if !is_newer(&version, &installed) {
+        return Err(Error::FrontendPatch(format!(
+          "the patch {version} is not newer than the installed patch {installed}"
+        )));
+      }
+    }
+    // rejects the invalid archives before they are installed
+    read_archive(archive)?;
+
+    let dir = patches_dir(&self.app)?;
+    std::fs::create_dir_all(&dir)?;
+
+    let staging = dir.join(format!(".{version}.tmp"));
+    if staging.exists() {
+      std::fs::remove_dir_all(&staging)?;
+    }
+    let written = std::fs::create_dir(&staging)
+      .and_then(|()| std::fs::write(staging.join(ARCHIVE), archive))
+      .and_then(|()| std::fs::write(staging.join(MANIFEST), manifest))
+      .and_then(|()| std::fs::write(staging.join(SIGNATURE), signature));
+    if let Err(e) = written {
+      let _ = std::fs::remove_dir_all(&staging);
+      return Err(e.into());
+    }
+
+    // the served patch is loaded in memory, so its files can be replaced
+    let target = dir.join(&version);
+    if target.exists() {
+      std::fs::remove_dir_all(&target)?;
+    }
+    if let Err(e) = std::fs::rename(&staging, &target) {
+      let _ = std::fs::remove_dir_all(&staging);
+      return Err(e.into());
+    }
+
+    let current = Current {
+      version: version.clone(),
+      app_version: self.app.package_info().version.to_string(),
+    };
+    let pending = dir.join(format!("{CURRENT}.tmp"));
+    std::fs::write(&pending, serde_json::to_vec(&current)?)?;
+    std::fs::rename(&pending, dir.join(CURRENT))?;
+
+    self.remove_unused(&dir, &version);
+    Ok(())
+  }
+
+  /// Uninstalls the patch, so the embedded assets are served from the next launch.
+  pub fn revert(&self) -> crate::Result<()> {
+    let dir = patches_dir(&self.app)?;
+    let current = dir.join(CURRENT);
+    if current.exists() {
+      std::fs::remove_file(current)?;
+    }
+    Ok(())
+  }
+
+  /// Removes the patches that are neither installed nor served.
+  ///
+  /// The staging directories of the installs running concurrently are left in place.
+  fn remove_unused(&self, dir: &Path, installed: &str) {
+    let active = self.active_version();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+      return;
+    };
+    for entry in entries.flatten() {
+      let name = entry.file_name();
+      let name = name.to_string_lossy();
+      if entry.path().is_dir()
+        && is_valid_version(&name)
+        && name != installed
+        && Some(name.as_ref()) != active.as_deref()
+      {
+        let _ = std::fs::remove_dir_all(entry.path());
+      }
+    }
+  }
+}
+
+fn decode_base64(value: &str, what: &str) -> crate::Result<String> {
+  base64::engine::general_purpose::STANDARD
+    .decode(value.trim())
+    .ok()
+    .and_then(|decoded| String::from_utf8(decoded).ok())
+    .ok_or_else(|| Error::FrontendPatch(format!("the {what} is not valid base64")))
+}
+
+/// Verifies the signature of the manifest and that it describes the archive for the current app version.
+fn verify(
+  archive: &[u8],
+  manifest: &str,
+  pubkey: &str,
+  signature: &str,
+  package_info: &PackageInfo,
+) -> crate::Result<Manifest> {
+  verify_signature(manifest.as_bytes(), pubkey, signature)?;
+  let manifest = serde_json::from_str::<Manifest>(manifest)
+    .map_err(|e| Error::FrontendPatch(format!("invalid manifest: {e}")))?;
+  check_manifest(&manifest, archive, &package_info.version.to_string())?;
+  Ok(manifest)
+}
+
+fn check_manifest(manifest: &Manifest, archive: &[u8], app_version: &str) -> crate::Result<()> {
+  if !is_valid_version(&manifest.version) {
+    return Err(Error::FrontendPatch(format!(
+      "invalid version `{}`",
+      manifest.version
+    )));
+  }
+  if manifest.app_version != app_version {
+    return Err(Error::FrontendPatch(format!(
+      "the patch targets the app version {}, not {app_version}",
+      manifest.app_version
+    )));
+  }
+  let hash = Sha256::digest(archive)
+    .iter()
+    .map(|b| format!("{b:02x}"))
+    .collect::<String>();
+  if !manifest.sha256.eq_ignore_ascii_case(&hash) {
+    return Err(Error::FrontendPatch(
+      "the archive does not match the manifest".into(),
+    ));
+  }
+  Ok(())
+}
+
+fn verify_signature(data: &[u8], pubkey: &str, signature: &str) -> crate::Result<()> {
+  let pubkey = minisign_verify::PublicKey::decode(&decode_base64(pubkey, "public key")?)
+    .map_err(|e| Error::FrontendPatch(format!("invalid public key: {e}")))?;
+  let signature = minisign_verify::Signature::decode(&decode_base64(signature, "signature")?)
+    .map_err(|e| Error::FrontendPatch(format!("invalid signature: {e}")))?;
+  pubkey
+    .verify(data, &signature, true)
+    .map_err(|e| Error::FrontendPatch(format!("signature verification failed: {e}")))
+}
+
+/// Reads the files of a patch archive, keyed by their asset key.
+fn read_archive(archive: &[u8]) -> crate::Result<HashMap<String, Vec<u8>>> {
+  let mut zip = zip::ZipArchive::new(Cursor::new(archive))?;
+  if zip.index_for_name("index.html").is_none() {
+    return Err(Error::FrontendPatch(
+      "the archive has no `index.html` at its root".into(),
+    ));
+  }
+
+  let mut files = HashMap::new();
+  for i in 0..zip.len() {
+    let mut file = zip.by_index(i)?;
+    // rejects the absolute paths and the `..` components
+    let Some(relative) = file.enclosed_name() else {
+      return Err(Error::FrontendPatch(format!(
+        "the archive entry `{}` escapes the patch directory",
+        file.name()
+      )));
+    };
+    if file.is_dir() {
+      continue;
+    }
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    files.insert(AssetKey::from(relative.as_path()).into(), bytes);
+  }
+
+  Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::Write;
+
+  use super::*;
+
+  fn archive(files: &[(&str, &str)]) -> Vec<u8> {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, contents) in files {
+      zip
+        .start_file(*name, zip::write::SimpleFileOptions::default())
+        .unwrap();
+      zip.write_all(contents.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+  }
+
+  #[test]
+  fn validates_versions() {
+    assert!(is_valid_version("1.2.3-beta.1+build"));
+    assert!(!is_valid_version(""));
+    assert!(!is_valid_version(".."));
+    assert!(!is_valid_version("../1.0.0"));
+    assert!(!is_valid_version("1.0.0/index.html"));
+  }
+
+  #[test]
+  fn requires_newer_versions() {
+    assert!(is_newer("1.0.0-patch.2", "1.0.0-patch.1"));
+    assert!(is_newer("1.0.0-patch.10", "1.0.0-patch.9"));
+    assert!(!is_newer("1.0.0-patch.1", "1.0.0-patch.1"));
+    assert!(!is_newer("1.0.0-patch.1", "1.0.0-patch.2"));
+    assert!(!is_newer("latest", "1.0.0-patch.1"));
+  }
+
+  #[test]
+  fn reads_archives() {
+    let files = read_archive(&archive(&[
+      ("index.html", "<html></html>"),
+      ("assets/app.js", "app"),
+    ]))
+    .unwrap();
+    assert_eq!(files.get("/assets/app.js").unwrap(), b"app");
+    assert_eq!(files.len(), 2);
+
+    assert!(read_archive(&archive(&[("app.js", "app")])).is_err());
+    assert!(read_archive(&archive(&[("index.html", ""), ("../escaped.js", "")])).is_err());
+  }
+
+  #[test]
+  fn checks_manifests() {
+    let archive = archive(&[("index.html", "<html></html>")]);
+    let manifest = |version: &str, app_version: &str, sha256: String| Manifest {
+      version: version.into(),
+      app_version: app_version.into(),
+      sha256,
+    };
+    let hash = Sha256::digest(&archive)
+      .iter()
+      .map(|b| format!("{b:02x}"))
+      .collect::<String>();
+
+    assert!(check_manifest(
+      &manifest("1.0.0-patch.1", "1.0.0", hash.clone()),
+      &archive,
+      "1.0.0"
+    )
+    .is_ok());
+    // a signed archive cannot be installed for another app version
+    assert!(check_manifest(
+      &manifest("1.0.0-patch.1", "0.9.0", hash.clone()),
+      &archive,
+      "1.0.0"
+    )
+    .is_err());
+    assert!(check_manifest(&manifest("../1.0.0", "1.0.0", hash), &archive, "1.0.0").is_err());
+    assert!(check_manifest(
+      &manifest("1.0.0-patch.1", "1.0.0", "00".repeat(32)),
+      &archive,
+      "1.0.0"
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn rejects_invalid_signatures() {
+    let pubkey = base64::engine::general_purpose::STANDARD.encode(
+      "untrusted comment: minisign public key\nRWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3\n",
+    );
+    let signature = base64::engine::general_purpose::STANDARD.encode("not a signature");
+    assert!(matches!(
+      verify_signature(b"archive", &pubkey, &signature),
+      Err(Error::FrontendPatch(_))
+    ));
+    assert!(matches!(
+      verify_signature(b"archive", &pubkey, "%%%"),
+      Err(Error::FrontendPatch(_))
+    ));
+  }
+}
@@ -0,0 +1,641 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The location of the device from the operating system location services.
+//!
+//! The webview `navigator.geolocation` API is unavailable or unreliable on some platforms,
+//! notably WebKitGTK on Linux, so this module reads the position from Windows.Devices.Geolocation,
+//! CoreLocation or GeoClue directly. The operating system prompts the user for the permission on the first request.
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use crate::{Manager, Runtime};
+
+/// Interval at which the platforms without a blocking API are checked for a position.
+#[cfg_attr(any(windows, mobile), allow(dead_code))]
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Geolocation errors.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+  /// The user or a system policy denied the access to the location.
+  #[error("the access to the location was denied")]
+  Denied,
+  /// The location services are disabled or not available on this system.
+  #[error("location services are not available: {0}")]
+  Unavailable(String),
+  /// No position was acquired before the [`PositionOptions::timeout`].
+  #[error("no position acquired after {0:?}")]
+  Timeout(Duration),
+}
+
+/// The options of [`current_position`].
+#[derive(Debug, Clone)]
+pub struct PositionOptions {
+  high_accuracy: bool,
+  timeout: Duration,
+  maximum_age: Duration,
+}
+
+impl Default for PositionOptions {
+  fn default() -> Self {
+    Self {
+      high_accuracy: false,
+      timeout: Duration::from_secs(30),
+      maximum_age: Duration::ZERO,
+    }
+  }
+}
+
+impl PositionOptions {
+  /// Requests the most accurate position, e.g. from GPS, at the cost of a slower response and more power.
+  #[must_use]
+  pub fn high_accuracy(mut self, high_accuracy: bool) -> Self {
+    self.high_accuracy = high_accuracy;
+    self
+  }
+
+  /// How long to wait for a position, including the permission prompt. Defaults to 30 seconds.
+  #[must_use]
+  pub fn timeout(mut self, timeout: Duration) -> Self {
+    self.timeout = timeout;
+    self
+  }
+
+  /// Accepts a position cached by the operating system if it is not older than the given age.
+  /// Defaults to zero, always acquiring a new position.
+  #[must_use]
+  pub fn maximum_age(mut self, maximum_age: Duration) -> Self {
+    self.maximum_age = maximum_age;
+    self
+  }
+}
+
+/// A position of the device, with the same fields as the webview `GeolocationCoordinates`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Position {
+  /// The latitude in decimal degrees.
+  pub latitude: f64,
+  /// The longitude in decimal degrees.
+  pub longitude: f64,
+  /// The accuracy of the latitude and longitude in meters.
+  pub accuracy: f64,
+  /// The altitude in meters above the WGS 84 ellipsoid.
+  pub altitude: Option<f64>,
+  /// The accuracy of the altitude in meters.
+  pub altitude_accuracy: Option<f64>,
+  /// The direction of travel in degrees clockwise from the true north.
+  pub heading: Option<f64>,
+  /// The ground speed in meters per second.
+  pub speed: Option<f64>,
+  /// When the position was acquired, in milliseconds since the UNIX epoch.
+  pub timestamp: u64,
+}
+
+/// Returns the current position of the device, prompting the user for the permission if needed.
+///
+/// This function blocks until a position is acquired, the permission is denied or the timeout elapses,
+/// so it must not be called on the main thread.
+///
+/// ## Platform-specific
+///
+/// - **Windows:** Uses Windows.Devices.Geolocation. The location must be enabled in the privacy settings,
+///   including for desktop apps.
+/// - **macOS:** Uses CoreLocation. The `NSLocationUsageDescription` key must be set in the `Info.plist` file.
+/// - **Linux:** Uses GeoClue, which asks its agent for the permission using the app identifier as the desktop file ID.
+/// - **Android / iOS:** Unsupported, use a mobile plugin instead.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use tauri::geolocation::PositionOptions;
+///
+/// #[tauri::command]
+/// async fn nearby_stores(app: tauri::AppHandle) -> Result<Vec<String>, String> {
+///   let position = tauri::geolocation::current_position(
+///     &app,
+///     PositionOptions::default().timeout(Duration::from_secs(10)),
+///   )
+///   .map_err(|e| e.to_string())?;
+///   println!("looking around {}, {}", position.latitude, position.longitude);
+///   Ok(Vec::new())
+/// }
+/// ```
+pub fn current_position<R: Runtime, M: Manager<R>>(
+  manager: &M,
+  options: PositionOptions,
+) -> crate::Result<Position> {
+  platform::current_position(manager, &options).map_err(Into::into)
+}
+
+/// Converts a platform value to an optional field, where the platforms use negative or non finite values for unknown.
+#[cfg_attr(any(windows, mobile), allow(dead_code))]
+fn known(value: f64, valid: impl Fn(f64) -> bool) -> Option<f64> {
+  (value.is_finite() && valid(value)).then_some(value)
+}
+
+/// Whether a position acquired at the given time in milliseconds since the UNIX epoch
+/// can be returned for a request started at `requested_at`.
+#[cfg_attr(any(windows, mobile), allow(dead_code))]
+fn is_fresh(timestamp: u64, requested_at: SystemTime, maximum_age: Duration) -> bool {
+  let oldest = requested_at
+    .checked_sub(maximum_age)
+    .unwrap_or(UNIX_EPOCH)
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_millis() as u64)
+    .unwrap_or_default();
+  timestamp >= oldest
+}
+
+/// Calls `poll` every [`POLL_INTERVAL`] until it returns a result or the timeout elapses.
+#[cfg_attr(any(windows, mobile), allow(dead_code))]
+fn poll_until<T>(
+  timeout: Duration,
+  mut poll: impl FnMut() -> Option<Result<T, Error>>,
+) -> Result<T, Error> {
+  let deadline = Instant::now() + timeout;
+  loop {
+    if let Some(result) = poll() {
+      return result;
+    }
+    if Instant::now() >= deadline {
+      return Err(Error::Timeout(timeout));
+    }
+    std::thread::sleep(POLL_INTERVAL);
+  }
+}
+
+#[cfg(windows)]
+mod platform {
+  use super::{Error, Position, PositionOptions};
+  use crate::{Manager, Runtime};
+  use windows::{
+    Devices::Geolocation::{GeolocationAccessStatus, Geolocator, PositionAccuracy},
+    Foundation::TimeSpan,
+  };
+
+  /// The difference between the Windows epoch (1601) and the UNIX epoch in 100 nanoseconds intervals.
+  const UNIX_EPOCH_OFFSET: i64 = 116_444_736_000_000_000;
+
+  fn time_span(duration: std::time::Duration) -> TimeSpan {
+    TimeSpan {
+      Duration: (duration.as_nanos() / 100).min(i64::MAX as u128) as i64,
+    }
+  }
+
+  pub fn current_position<R: Runtime, M: Manager<R>>(
+    _manager: &M,
+    options: &PositionOptions,
+  ) -> Result<Position, Error> {
+    let access = Geolocator::RequestAccessAsync()
+      .and_then(|operation| operation.get())
+      .map_err(|e| Error::Unavailable(e.to_string()))?;
+    if access != GeolocationAccessStatus::Allowed {
+      return Err(Error::Denied);
+    }
+
+    let locator = Geolocator::new().map_err(|e| Error::Unavailable(e.to_string()))?;
+    locator
+      .SetDesiredAccuracy(if options.high_accuracy {
+        PositionAccuracy::High
+      } else {
+        PositionAccuracy::Default
+      })
+      .map_err(|e| Error::Unavailable(e.to_string()))?;
+
+    let position = locator
+      .GetGeopositionAsyncWithAgeAndTimeout(
+        time_span(options.maximum_age),
+        time_span(options.timeout),
+      )
+      .and_then(|operation| operation.get())
+      .map_err(|_| Error::Timeout(options.timeout))?;
+    let coordinate = position
+      .Coordinate()
+      .map_err(|e| Error::Unavailable(e.to_string()))?;
+    let point = coordinate
+      .Point()
+      .and_then(|point| point.Position())
+      .map_err(|e| Error::Unavailable(e.to_string()))?;
+
+    let altitude_accuracy = coordinate
+      .AltitudeAccuracy()
+      .and_then(|value| value.Value())
+      .ok();
+    let timestamp = coordinate
+      .Timestamp()
+      .map(|time| ((time.UniversalTime - UNIX_EPOCH_OFFSET) / 10_000).max(0) as u64)
+      .unwrap_or_default();
+
+    Ok(Position {
+      latitude: point.Latitude,
+      longitude: point.Longitude,
+      accuracy: coordinate.Accuracy().unwrap_or_default(),
+      // the altitude is only meaningful when its accuracy is known
+      altitude: altitude_accuracy.map(|_| point.Altitude),
+      altitude_accuracy,
+      heading: coordinate
+        .Heading()
+        .and_then(|value| value.Value())
+        .ok()
+        .filter(|heading| heading.is_finite()),
+      speed: coordinate
+        .Speed()
+        .and_then(|value| value.Value())
+        .ok()
+        .filter(|speed| speed.is_finite()),
+      timestamp,
+    })
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use super::{known, poll_until, Error, Position, PositionOptions};
+  use crate::{Manager, Runtime};
+  use objc2::{
+    class,
+    encode::{Encode, Encoding},
+    msg_send, msg_send_id,
+    rc::Retained,
+    runtime::{AnyObject, Bool},
+  };
+  use std::{sync::OnceLock, time::SystemTime};
+
+  #[link(name = "CoreLocation", kind = "framework")]
+  extern "C" {}
+
+  // CLAuthorizationStatus values
+  const AUTHORIZATION_RESTRICTED: i32 = 1;
+  const AUTHORIZATION_DENIED: i32 = 2;
+
+  /// `kCLLocationAccuracyBest`
+  const ACCURACY_BEST: f64 = -1.0;
+  /// `kCLLocationAccuracyHundredMeters`
+  const ACCURACY_HUNDRED_METERS: f64 = 100.0;
+
+  #[repr(C)]
+  struct Coordinate {
+    latitude: f64,
+    longitude: f64,
+  }
+
+  unsafe impl Encode for Coordinate {
+    const ENCODING: Encoding =
+      Encoding::Struct("CLLocationCoordinate2D", &[f64::ENCODING, f64::ENCODING]);
+  }
+
+  /// The location manager, created on the main thread and kept for the lifetime of the app.
+  static LOCATION_MANAGER: OnceLock<usize> = OnceLock::new();
+
+  /// The `CLLocationManager`. Must be called on the main thread, which also delivers its updates.
+  fn location_manager() -> *mut AnyObject {
+    *LOCATION_MANAGER.get_or_init(|| unsafe {
+      let manager: Retained<AnyObject> = msg_send_id![class!(CLLocationManager), new];
+      Retained::into_raw(manager) as usize
+    }) as *mut AnyObject
+  }
+
+  fn on_main_thread<R: Runtime, M: Manager<R>, T: Send + 'static>(
+    manager: &M,
+    f: impl FnOnce() -> T + Send + 'static,
+  ) -> Result<T, Error> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    manager
+      .app_handle()
+      .run_on_main_thread(move || {
+        let _ = tx.send(f());
+      })
+      .map_err(|e| Error::Unavailable(e.to_string()))?;
+    rx.recv()
+      .map_err(|_| Error::Unavailable("the event loop is not running".into()))
+  }
+
+  /// Reads the last location of the manager. Must be called on the main thread.
+  unsafe fn last_position() -> Option<Position> {
+    let location: *mut AnyObject = msg_send![location_manager(), location];
+    if location.is_null() {
+      return None;
+    }
+    let coordinate: Coordinate = msg_send![location, coordinate];
+    let accuracy: f64 = msg_send![location, horizontalAccuracy];
+    // a negative accuracy means that the coordinate is invalid
+    if accuracy < 0.0 {
+      return None;
+    }
+    let altitude: f64 = msg_send![location, altitude];
+    let altitude_accuracy = known(msg_send![location, verticalAccuracy], |v| v >= 0.0);
+    let date: *mut AnyObject = msg_send![location, timestamp];
+    let seconds: f64 = msg_send![date, timeIntervalSince1970];
+
+    Some(Position {
+      latitude: coordinate.latitude,
+      longitude: coordinate.longitude,
+      accuracy,
+      altitude: altitude_accuracy.map(|_| altitude),
+      altitude_accuracy,
+      heading: known(msg_send![location, course], |v| v >= 0.0),
+      speed: known(msg_send![location, speed], |v| v >= 0.0),
+      timestamp: (seconds * 1000.0).max(0.0) as u64,
+    })
+  }
+
+  pub fn current_position<R: Runtime, M: Manager<R>>(
+    manager: &M,
+    options: &PositionOptions,
+  ) -> Result<Position, Error> {
+    let requested_at = SystemTime::now();
+    let accuracy = if options.high_accuracy {
+      ACCURACY_BEST
+    } else {
+      ACCURACY_HUNDRED_METERS
+    };
+
+    let enabled = on_main_thread(manager, move || unsafe {
+      let enabled: Bool = msg_send![class!(CLLocationManager), locationServicesEnabled];
+      if enabled.as_bool() {
+        let location_manager = location_manager();
+        let () = msg_send![location_manager, setDesiredAccuracy: accuracy];
+        // shows the permission prompt when the user was never asked
+        let () = msg_send![location_manager, requestWhenInUseAuthorization];
+        let () = msg_send![location_manager, startUpdatingLocation];
+      }
+      enabled.as_bool()
+    })?;
+    if !enabled {
+      return Err(Error::Unavailable("location services are disabled".into()));
+    }
+
+    let maximum_age = options.maximum_age;
+    let result = poll_until(options.timeout, || {
+      on_main_thread(manager, move || unsafe {
+        let status: i32 = msg_send![class!(CLLocationManager), authorizationStatus];
+        if status == AUTHORIZATION_DENIED || status == AUTHORIZATION_RESTRICTED {
+          return Some(Err(Error::Denied));
+        }
+        last_position()
+          .filter(|position| super::is_fresh(position.timestamp, requested_at, maximum_age))
+          .map(Ok)
+      })
+      .unwrap_or_else(|e| Some(Err(e)))
+    });
+
+    let _ = on_main_thread(manager, || unsafe {
+      let () = msg_send![location_manager(), stopUpdatingLocation];
+    });
+
+    result
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use super::{known, poll_until, Error, Position, PositionOptions};
+  use crate::{Manager, Runtime};
+  use gtk::{
+    gio::{self, DBusCallFlags, DBusConnection},
+    glib::{self, ToVariant, Variant},
+  };
+  use std::time::SystemTime;
+
+  const SERVICE: &str = "org.freedesktop.GeoClue2";
+  const CLIENT_INTERFACE: &str = "org.freedesktop.GeoClue2.Client";
+  const LOCATION_INTERFACE: &str = "org.freedesktop.GeoClue2.Location";
+
+  // GClueAccuracyLevel values
+  const ACCURACY_LEVEL_STREET: u32 = 6;
+  const ACCURACY_LEVEL_EXACT: u32 = 8;
+
+  fn call(
+    connection: &DBusConnection,
+    path: &str,
+    interface: &str,
+    method: &str,
+    parameters: Option<&Variant>,
+  ) -> Result<Variant, glib::Error> {
+    connection.call_sync(
+      Some(SERVICE),
+      path,
+      interface,
+      method,
+      parameters,
+      None,
+      DBusCallFlags::NONE,
+      -1,
+      None::<&gio::Cancellable>,
+    )
+  }
+
+  fn get_property(
+    connection: &DBusConnection,
+    path: &str,
+    interface: &str,
+    name: &str,
+  ) -> Result<Variant, glib::Error> {
+    call(
+      connection,
+      path,
+      "org.freedesktop.DBus.Properties",
+      "Get",
+      Some(&(interface, name).to_variant()),
+    )
+    .map(|reply| {
+      let value = reply.child_value(0);
+      value.as_variant().unwrap_or(value)
+    })
+  }
+
+  fn set_property(
+    connection: &DBusConnection,
+    path: &str,
+    name: &str,
+    value: Variant,
+  ) -> Result<(), glib::Error> {
+    call(
+      connection,
+      path,
+      "org.freedesktop.DBus.Properties",
+      "Set",
+      Some(&(CLIENT_INTERFACE, name, value).to_variant()),
+    )
+    .map(|_| ())
+  }
+
+  fn is_access_denied(error: &glib::Error) -> bool {
+    gio::DBusError::remote_error(error).is_some_and(|name| name.ends_with(".AccessDenied"))
+  }
+
+  fn read_location(connection: &DBusConnection, path: &str) -> Result<Position, glib::Error> {
+    let number = |name: &str| -> Result<f64, glib::Error> {
+      Ok(
+        get_property(connection, path, LOCATION_INTERFACE, name)?
+          .get::<f64>()
+          .unwrap_or(f64::NAN),
+      )
+    };
+    let (seconds, microseconds) = get_property(connection, path, LOCATION_INTERFACE, "Timestamp")?
+      .get::<(u64, u64)>()
+      .unwrap_or_default();
+
+    Ok(Position {
+      latitude: number("Latitude")?,
+      longitude: number("Longitude")?,
+      accuracy: number("Accuracy")?,
+      // GeoClue uses -DBL_MAX for an unknown altitude and -1 for an unknown heading and speed
+      altitude: known(number("Altitude")?, |v| v > f64::MIN),
+      altitude_accuracy: None,
+      heading: known(number("Heading")?, |v| v >= 0.0),
+      speed: known(number("Speed")?, |v| v >= 0.0),
+      timestamp: seconds * 1000 + microseconds / 1000,
+    })
+  }
+
+  pub fn current_position<R: Runtime, M: Manager<R>>(
+    manager: &M,
+    options: &PositionOptions,
+  ) -> Result<Position, Error> {
+    let requested_at = SystemTime::now();
+    let unavailable = |e: glib::Error| Error::Unavailable(e.to_string());
+
+    let connection =
+      gio::bus_get_sync(gio::BusType::System, None::<&gio::Cancellable>).map_err(unavailable)?;
+    let client = call(
+      &connection,
+      "/org/freedesktop/GeoClue2/Manager",
+      "org.freedesktop.GeoClue2.Manager",
+      "GetClient",
+      None,
+    )
+    .map_err(unavailable)?
+    .child_value(0)
+    .str()
+    .map(ToString::to_string)
+    .ok_or_else(|| Error::Unavailable("GeoClue returned no client".into()))?;
+
+    set_property(
+      &connection,
+      &client,
+      "DesktopId",
+      manager.config().identifier.to_variant(),
+    )
+    .map_err(unavailable)?;
+    set_property(
+      &connection,
+      &client,
+      "RequestedAccuracyLevel",
+      if options.high_accuracy {
+        ACCURACY_LEVEL_EXACT
+      } else {
+        ACCURACY_LEVEL_STREET
+      }
+      .to_variant(),
+    )
+    .map_err(unavailable)?;
+
+    // the GeoClue agent prompts the user when the client starts
+    call(&connection, &client, CLIENT_INTERFACE, "Start", None).map_err(|e| {
+      if is_access_denied(&e) {
+        Error::Denied
+      } else {
+        unavailable(e)
+      }
+    })?;
+
+    let result = poll_until(options.timeout, || {
+      let location = match get_property(&connection, &client, CLIENT_INTERFACE, "Location") {
+        Ok(location) => location.str()?.to_string(),
+        Err(e) => return Some(Err(unavailable(e))),
+      };
+      // the location is `/` until the first one is acquired
+      if location == "/" {
+        return None;
+      }
+      match read_location(&connection, &location) {
+        Ok(position) if super::is_fresh(position.timestamp, requested_at, options.maximum_age) => {
+          Some(Ok(position))
+        }
+        Ok(_) => None,
+        Err(e) => Some(Err(unavailable(e))),
+      }
+    });
+
+    let _ = call(&connection, &client, CLIENT_INTERFACE, "Stop", None);
+
+    result
+  }
+}
+
+#[cfg(mobile)]
+mod platform {
+  use super::{Error, Position, PositionOptions};
+  use crate::{Manager, Runtime};
+
+  pub fn current_position<R: Runtime, M: Manager<R>>(
+    _manager: &M,
+    _options: &PositionOptions,
+  ) -> Result<Position, Error> {
+    Err(Error::Unavailable("unsupported platform".into()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn serializes_position() {
+    let position = Position {
+      latitude: 48.85,
+      longitude: 2.35,
+      accuracy: 20.0,
+      altitude: None,
+      altitude_accuracy: None,
+      heading: Some(90.0),
+      speed: None,
+      timestamp: 1_700_000_000_000,
+    };
+    assert_eq!(
+      serde_json::to_value(position).unwrap(),
+      serde_json::json!({
+        "latitude": 48.85,
+        "longitude": 2.35,
+        "accuracy": 20.0,
+        "altitude": null,
+        "altitudeAccuracy": null,
+        "heading": 90.0,
+        "speed": null,
+        "timestamp": 1_700_000_000_000u64
+      })
+    );
+  }
+
+  #[test]
+  fn filters_unknown_values() {
+    assert_eq!(known(-1.0, |v| v >= 0.0), None);
+    assert_eq!(known(f64::NAN, |v| v >= 0.0), None);
+    assert_eq!(known(f64::MIN, |v| v > f64::MIN), None);
+    assert_eq!(known(12.5, |v| v >= 0.0), Some(12.5));
+  }
+
+  #[test]
+  fn accepts_positions_within_maximum_age() {
+    let requested_at = UNIX_EPOCH + Duration::from_secs(100);
+    assert!(is_fresh(100_000, requested_at, Duration::ZERO));
+    assert!(!is_fresh(99_999, requested_at, Duration::ZERO));
+    assert!(is_fresh(95_000, requested_at, Duration::from_secs(5)));
+    assert!(!is_fresh(94_999, requested_at, Duration::from_secs(5)));
+  }
+}
@@ -5,6 +5,8 @@
 //! Image types used by this crate and also referenced by the JavaScript API layer.
 
 pub(crate) mod plugin;
+#[cfg(desktop)]
+pub(crate) mod theme;
 
 use std::borrow::Cow;
 use std::sync::Arc;
@@ -0,0 +1,145 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Icons following the light and dark system themes.
+
+use std::sync::{Arc, Mutex};
+
+use super::Image;
+use crate::Theme;
+
+/// The variants of an icon for the light and the dark themes.
+#[derive(Debug, Clone)]
+pub(crate) struct IconPair {
+  light: Image<'static>,
+  dark: Image<'static>,
+  /// The theme of the variant currently displayed.
+  displayed: Option<Theme>,
+}
+
+impl IconPair {
+  pub(crate) fn new(light: Image<'_>, dark: Image<'_>) -> Self {
+    Self {
+      light: light.to_owned(),
+      dark: dark.to_owned(),
+      displayed: None,
+    }
+  }
+
+  /// The variant to display for the theme, or `None` if it is already displayed.
+  pub(crate) fn switch(&mut self, theme: Theme) -> Option<Image<'static>> {
+    if self.displayed == Some(theme) {
+      return None;
+    }
+    self.displayed.replace(theme);
+    match theme {
+      Theme::Dark => Some(self.dark.clone()),
+      _ => Some(self.light.clone()),
+    }
+  }
+}
+
+/// The icon pair of a tray icon or a window, shared by its clones.
+pub(crate) type SharedIconPair = Arc<Mutex<Option<IconPair>>>;
+
+/// The theme of the operating system shell, `None` if it cannot be read on this platform.
+pub(crate) fn system_theme() -> Option<Theme> {
+  platform::system_theme()
+}
+
+#[cfg(all(windows, feature = "theme-icons"))]
+mod platform {
+  use windows::{
+    core::w,
+    Win32::{
+      Foundation::ERROR_SUCCESS,
+      System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD},
+    },
+  };
+
+  use crate::Theme;
+
+  pub fn system_theme() -> Option<Theme> {
+    let mut light = 0u32;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    // the taskbar follows the system theme, which can differ from the theme of the apps
+    let result = unsafe {
+      RegGetValueW(
+        HKEY_CURRENT_USER,
+        w!("Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize"),
+        w!("SystemUsesLightTheme"),
+        RRF_RT_REG_DWORD,
+        None,
+        Some(&mut light as *mut u32 as *mut _),
+        Some(&mut size),
+      )
+    };
+    if result != ERROR_SUCCESS {
+      return None;
+    }
+    Some(if light == 0 {
+      Theme::Dark
+    } else {
+      Theme::Light
+    })
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use objc2::{class, msg_send, runtime::AnyObject};
+  use objc2_foundation::NSString;
+
+  use crate::Theme;
+
+  pub fn system_theme() -> Option<Theme> {
+    let key = NSString::from_str("AppleInterfaceStyle");
+    let style = unsafe {
+      let defaults: *mut AnyObject = msg_send![class!(NSUserDefaults), standardUserDefaults];
+      let style: *mut NSString = msg_send![defaults, stringForKey: &*key];
+      style.as_ref().map(|style| style.to_string())
+    };
+    // the key is only set when the dark appearance is used
+    Some(if style.as_deref() == Some("Dark") {
+      Theme::Dark
+    } else {
+      Theme::Light
+    })
+  }
+}
+
+#[cfg(not(any(all(windows, feature = "theme-icons"), target_os = "macos")))]
+mod platform {
+  use crate::Theme;
+
+  pub fn system_theme() -> Option<Theme> {
+    #[cfg(windows)]
+    {
+      static WARNED: std::sync::Once = std::sync::Once::new();
+      WARNED.call_once(|| {
+        log::warn!(
+          "the icon pairs follow the theme of the windows instead of the system theme without the `theme-icons` feature"
+        )
+      });
+    }
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn switches_once_per_theme() {
+    let light = Image::new_owned(vec![255; 4], 1, 1);
+    let dark = Image::new_owned(vec![0; 4], 1, 1);
+    let mut pair = IconPair::new(light, dark);
+
+    assert_eq!(pair.switch(Theme::Dark).unwrap().rgba(), &[0; 4]);
+    assert!(pair.switch(Theme::Dark).is_none());
+    assert_eq!(pair.switch(Theme::Light).unwrap().rgba(), &[255; 4]);
+    assert!(pair.switch(Theme::Light).is_none());
+  }
+}
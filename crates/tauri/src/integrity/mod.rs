@@ -0,0 +1,312 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Checksums and signature verification of downloaded files, such as plugins or content packs.
+//!
+//! The files are hashed with SHA-256 or BLAKE3, streaming their content and emitting the progress
+//! as the [`INTEGRITY_PROGRESS_EVENT`] event. The signatures use the updater key format (`tauri signer sign`),
+//! so the updater keys can sign the content as well.
+//!
+//! The webviews use the `core:integrity` commands, which are not enabled by the default permission set.
+//! The commands only read the files in the app data, app local data, app cache and download directories,
+//! or allowed by the asset protocol scope when the `protocol-asset` feature is enabled.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use tauri::integrity::HashAlgorithm;
+//!
+//! #[tauri::command]
+//! async fn verify_pack(
+//!   app: tauri::AppHandle,
+//!   path: std::path::PathBuf,
+//!   checksum: String,
+//! ) -> tauri::Result<bool> {
+//!   tauri::async_runtime::spawn_blocking(move || {
+//!     let hash = tauri::integrity::hash_file(&app, path, HashAlgorithm::Blake3)?;
+//!     Ok(hash.eq_ignore_ascii_case(&checksum))
+//!   })
+//!   .await?
+//! }
+//! ```
+
+use std::{
+  fs::File,
+  io::Read,
+  marker::PhantomData,
+  path::{Path, PathBuf},
+  time::{Duration, Instant},
+};
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+
+use crate::{Emitter, Runtime};
+
+pub(crate) mod plugin;
+
+/// Event emitted with a [`HashProgress`] payload while a file is hashed.
+pub const INTEGRITY_PROGRESS_EVENT: &str = "tauri://integrity-progress";
+
+/// Minimum interval between two [`INTEGRITY_PROGRESS_EVENT`] events of the same file.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Size of the chunks read from the hashed files.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Integrity errors.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+  /// The public key is not a base64 encoded minisign public key.
+  #[error("invalid public key: {0}")]
+  InvalidPublicKey(String),
+  /// The signature is not a base64 encoded minisign signature.
+  #[error("invalid signature: {0}")]
+  InvalidSignature(String),
+  /// The signature does not match the content.
+  #[error("signature verification failed: {0}")]
+  Verification(String),
+}
+
+/// A hash algorithm.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum HashAlgorithm {
+  /// SHA-256, as published by most download pages.
+  #[default]
+  Sha256,
+  /// BLAKE3, faster on large files.
+  Blake3,
+}
+
+enum Hasher {
+  Sha256(sha2::Sha256),
+  // the BLAKE3 state is much larger
+  Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+  fn new(algorithm: HashAlgorithm) -> Self {
+    match algorithm {
+      HashAlgorithm::Sha256 => Self::Sha256(sha2::Sha256::new()),
+      HashAlgorithm::Blake3 => Self::Blake3(Box::default()),
+    }
+  }
+
+  fn update(&mut self, data: &[u8]) {
+    match self {
+      Self::Sha256(hasher) => hasher.update(data),
+      Self::Blake3(hasher) => {
+        hasher.update(data);
+      }
+    }
+  }
+
+  fn finalize(self) -> String {
+    match self {
+      Self::Sha256(hasher) => hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect(),
+      Self::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+    }
+  }
+}
+
+/// The progress of a [`hash_file`] call.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct HashProgress {
+  /// The path of the hashed file.
+  pub path: PathBuf,
+  /// The number of bytes hashed.
+  pub bytes: u64,
+  /// The size of the file.
+  pub total_bytes: u64,
+  /// Whether the file is completely hashed.
+  pub done: bool,
+}
+
+struct Reporter<'a, R: Runtime, E: Emitter<R>> {
+  emitter: &'a E,
+  progress: HashProgress,
+  last_emit: Option<Instant>,
+  _runtime: PhantomData<R>,
+}
+
+impl<R: Runtime, E: Emitter<R>> Reporter<'_, R, E> {
+  fn advance(&mut self, bytes: u64) {
+    self.progress.bytes += bytes;
+    if self
+      .last_emit
+      .map_or(true, |last| last.elapsed() >= PROGRESS_INTERVAL)
+    {
+      self.emit();
+    }
+  }
+
+  fn finish(mut self) {
+    self.progress.done = true;
+    self.emit();
+  }
+
+  fn emit(&mut self) {
+    self.last_emit.replace(Instant::now());
+    if let Err(e) = self.emitter.emit(INTEGRITY_PROGRESS_EVENT, &self.progress) {
+      log::error!("failed to emit integrity progress: {e}");
+    }
+  }
+}
+
+/// Hashes the data, returning the lowercase hexadecimal digest.
+pub fn hash(data: &[u8], algorithm: HashAlgorithm) -> String {
+  let mut hasher = Hasher::new(algorithm);
+  hasher.update(data);
+  hasher.finalize()
+}
+
+/// Hashes the file content without loading it in memory, returning the lowercase hexadecimal digest.
+///
+/// The progress is emitted to all targets as the [`INTEGRITY_PROGRESS_EVENT`] event.
+/// This function blocks until the file is hashed, so it must not be called on the main thread.
+pub fn hash_file<R: Runtime, E: Emitter<R>, P: AsRef<Path>>(
+  emitter: &E,
+  path: P,
+  algorithm: HashAlgorithm,
+) -> crate::Result<String> {
+  let path = path.as_ref();
+  let mut file = File::open(path)?;
+  let mut reporter = Reporter {
+    emitter,
+    progress: HashProgress {
+      path: path.to_path_buf(),
+      bytes: 0,
+      total_bytes: file.metadata()?.len(),
+      done: false,
+    },
+    last_emit: None,
+    _runtime: PhantomData,
+  };
+
+  let mut hasher = Hasher::new(algorithm);
+  let mut buffer = vec![0; CHUNK_SIZE];
+  loop {
+    let read = file.read(&mut buffer)?;
+    if read == 0 {
+      break;
+    }
+    hasher.update(&buffer[..read]);
+    reporter.advance(read as u64);
+  }
+  reporter.finish();
+  Ok(hasher.finalize())
+}
+
+fn decode_base64(value: &str) -> Option<String> {
+  base64::engine::general_purpose::STANDARD
+    .decode(value.trim())
+    .ok()
+    .and_then(|decoded| String::from_utf8(decoded).ok())
+}
+
+/// Verifies the minisign signature of the data.
+///
+/// The public key and the signature are base64 encoded like the updater ones,
+/// i.e. the `pubkey` updater configuration and the content of the `.sig` files created by `tauri signer sign`.
+pub fn verify(data: &[u8], public_key: &str, signature: &str) -> crate::Result<()> {
+  let public_key = decode_base64(public_key)
+    .ok_or_else(|| Error::InvalidPublicKey("not valid base64".into()))
+    .and_then(|key| {
+      minisign_verify::PublicKey::decode(&key).map_err(|e| Error::InvalidPublicKey(e.to_string()))
+    })?;
+  let signature = decode_base64(signature)
+    .ok_or_else(|| Error::InvalidSignature("not valid base64".into()))
+    .and_then(|signature| {
+      minisign_verify::Signature::decode(&signature)
+        .map_err(|e| Error::InvalidSignature(e.to_string()))
+    })?;
+  public_key
+    .verify(data, &signature, true)
+    .map_err(|e| Error::Verification(e.to_string()).into())
+}
+
+/// Verifies the minisign signature of the file, see [`verify`].
+///
+/// The file is read in memory.
+/// This function blocks until the file is verified, so it must not be called on the main thread.
+pub fn verify_file<P: AsRef<Path>>(
+  path: P,
+  public_key: &str,
+  signature: &str,
+) -> crate::Result<()> {
+  let data = std::fs::read(path)?;
+  verify(&data, public_key, signature)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test::{mock_app, MockRuntime};
+
+  #[test]
+  fn hashes() {
+    assert_eq!(
+      hash(b"abc", HashAlgorithm::Sha256),
+      "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+      hash(b"", HashAlgorithm::Blake3),
+      "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+    );
+  }
+
+  #[test]
+  fn hashes_files_in_chunks() {
+    let app = mock_app();
+    let path = std::env::temp_dir().join(format!("tauri-integrity-{}", std::process::id()));
+    let data = vec![7; CHUNK_SIZE * 2 + 1];
+    std::fs::write(&path, &data).unwrap();
+
+    for algorithm in [HashAlgorithm::Sha256, HashAlgorithm::Blake3] {
+      assert_eq!(
+        hash_file::<MockRuntime, _, _>(app.handle(), &path, algorithm).unwrap(),
+        hash(&data, algorithm)
+      );
+    }
+
+    std::fs::remove_file(path).unwrap();
+  }
+
+  #[test]
+  fn rejects_invalid_signatures() {
+    let public_key = base64::engine::general_purpose::STANDARD.encode(
+      "untrusted comment: minisign public key\nRWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3\n",
+    );
+    assert!(matches!(
+      verify(b"data", "not base64", "not base64"),
+      Err(crate::Error::Integrity(Error::InvalidPublicKey(_)))
+    ));
+    assert!(matches!(
+      verify(
+        b"data",
+        &public_key,
+        &base64::engine::general_purpose::STANDARD.encode("not a signature")
+      ),
+      Err(crate::Error::Integrity(Error::InvalidSignature(_)))
+    ));
+  }
+
+  #[test]
+  fn deserializes_algorithm() {
+    assert_eq!(
+      serde_json::from_str::<HashAlgorithm>("\"blake3\"").unwrap(),
+      HashAlgorithm::Blake3
+    );
+  }
+}
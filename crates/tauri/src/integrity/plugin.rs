@@ -0,0 +1,46 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::path::PathBuf;
+
+use super::HashAlgorithm;
+use crate::{
+  command,
+  path::check_command_path,
+  plugin::{Builder, TauriPlugin},
+  AppHandle, Runtime,
+};
+
+#[command(root = "crate")]
+async fn hash_file<R: Runtime>(
+  app: AppHandle<R>,
+  path: PathBuf,
+  algorithm: Option<HashAlgorithm>,
+) -> crate::Result<String> {
+  crate::async_runtime::spawn_blocking(move || {
+    check_command_path(&app, &path)?;
+    super::hash_file(&app, path, algorithm.unwrap_or_default())
+  })
+  .await?
+}
+
+#[command(root = "crate")]
+async fn verify_signature<R: Runtime>(
+  app: AppHandle<R>,
+  path: PathBuf,
+  public_key: String,
+  signature: String,
+) -> crate::Result<()> {
+  crate::async_runtime::spawn_blocking(move || {
+    let path = check_command_path(&app, &path)?;
+    super::verify_file(path, &public_key, &signature)
+  })
+  .await?
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("integrity")
+    .invoke_handler(crate::generate_handler![hash_file, verify_signature])
+    .build()
+}
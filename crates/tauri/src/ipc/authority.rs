@@ -250,6 +250,53 @@ impl RuntimeAuthority {
     self.acl.contains_key(APP_ACL_KEY)
   }
 
+  /// A JSON snapshot of the resolved commands and scopes, for the diagnostics bundle.
+  #[cfg(feature = "diagnostics")]
+  pub(crate) fn snapshot(&self) -> serde_json::Value {
+    let scope_manager = &self.scope_manager;
+    let scope = |scope: &ResolvedScope| {
+      serde_json::json!({
+        "allow": scope.allow,
+        "deny": scope.deny,
+      })
+    };
+    let commands = |commands: &BTreeMap<String, Vec<ResolvedCommand>>| {
+      commands
+        .iter()
+        .map(|(name, resolved)| {
+          let resolved = resolved
+            .iter()
+            .map(|command| {
+              serde_json::json!({
+                "context": match &command.context {
+                  ExecutionContext::Local => "local".to_string(),
+                  ExecutionContext::Remote { url } => format!("remote: {}", url.as_str()),
+                },
+                "windows": command.windows.iter().map(|w| w.as_str()).collect::<Vec<_>>(),
+                "webviews": command.webviews.iter().map(|w| w.as_str()).collect::<Vec<_>>(),
+                "scope": command
+                  .scope_id
+                  .and_then(|id| scope_manager.command_scope.get(&id))
+                  .map(scope),
+              })
+            })
+            .collect::<Vec<_>>();
+          (name.clone(), serde_json::Value::from(resolved))
+        })
+        .collect::<serde_json::Map<_, _>>()
+    };
+
+    serde_json::json!({
+      "allowedCommands": commands(&self.allowed_commands),
+      "deniedCommands": commands(&self.denied_commands),
+      "globalScopes": scope_manager
+        .global_scope
+        .iter()
+        .map(|(plugin, resolved)| (plugin.clone(), scope(resolved)))
+        .collect::<serde_json::Map<_, _>>(),
+    })
+  }
+
   #[doc(hidden)]
   pub fn __allow_command(&mut self, command: String, context: ExecutionContext) {
     self.allowed_commands.insert(
@@ -544,6 +591,44 @@ impl RuntimeAuthority {
     }
   }
 
+  /// Whether a capability of the window or webview grants the remote URL access to any command.
+  pub(crate) fn has_remote_access(&self, window: &str, webview: &str, url: &Url) -> bool {
+    let origin = Origin::Remote { url: url.clone() };
+    self.allowed_commands.values().flatten().any(|cmd| {
+      origin.matches(&cmd.context)
+        && (cmd.webviews.iter().any(|w| w.matches(webview))
+          || cmd.windows.iter().any(|w| w.matches(window)))
+    })
+  }
+
+  /// The origins of the remote URL patterns granting the window or webview access to a command,
+  /// except the patterns matching several origins.
+  pub(crate) fn remote_origins(&self, window: &str, webview: &str) -> Vec<String> {
+    let mut origins = Vec::new();
+    for cmd in self.allowed_commands.values().flatten() {
+      let ExecutionContext::Remote { url } = &cmd.context else {
+        continue;
+      };
+      if !(cmd.webviews.iter().any(|w| w.matches(webview))
+        || cmd.windows.iter().any(|w| w.matches(window)))
+      {
+        continue;
+      }
+      let Ok(parsed) = Url::parse(url.as_str()) else {
+        continue;
+      };
+      let origin = parsed.origin();
+      if !origin.is_tuple() {
+        continue;
+      }
+      let origin = origin.ascii_serialization();
+      if !origin.contains('*') && !origins.contains(&origin) {
+        origins.push(origin);
+      }
+    }
+    origins
+  }
+
   /// Checks if the given IPC execution is allowed and returns the [`ResolvedCommand`] if it is.
   pub fn resolve_access(
     &self,
@@ -995,6 +1080,41 @@ mod tests {
     );
   }
 
+  #[test]
+  fn lists_exact_remote_origins() {
+    let command = |url: &str, window: &str| ResolvedCommand {
+      windows: vec![Pattern::new(window).unwrap()],
+      context: ExecutionContext::Remote {
+        url: url.parse().unwrap(),
+      },
+      ..Default::default()
+    };
+    let allowed_commands = [(
+      "my-command".to_string(),
+      vec![
+        command("https://tauri.app", "main"),
+        command("https://tauri.app/docs/*", "main"),
+        command("http://localhost:8080/*", "main"),
+        command("https://*.tauri.app", "main"),
+        command("https://github.com", "other"),
+      ],
+    )]
+    .into_iter()
+    .collect();
+    let authority = RuntimeAuthority::new(
+      Default::default(),
+      Resolved {
+        allowed_commands,
+        ..Default::default()
+      },
+    );
+
+    assert_eq!(
+      authority.remote_origins("main", "main"),
+      vec!["https://tauri.app", "http://localhost:8080"]
+    );
+  }
+
   #[test]
   fn remote_domain_matches() {
     let url = "https://tauri.app";
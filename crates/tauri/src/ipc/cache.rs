@@ -0,0 +1,191 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  collections::HashMap,
+  sync::Mutex,
+  time::{Duration, Instant},
+};
+
+use sha2::{Digest, Sha256};
+
+use super::{InvokeBody, InvokeResponseBody};
+
+/// How many responses are kept per command, the entry expiring first being evicted.
+const MAX_ENTRIES_PER_COMMAND: usize = 256;
+
+struct Entry {
+  body: InvokeResponseBody,
+  expires_at: Instant,
+}
+
+/// The successful responses of the commands declared with `#[tauri::command(cache = "30s")]`.
+///
+/// See [`crate::AppHandle::invalidate_command_cache`].
+#[derive(Default)]
+pub(crate) struct CommandCache {
+  entries: Mutex<HashMap<String, HashMap<String, Entry>>>,
+}
+
+impl CommandCache {
+  /// The cached response of the command for the key, if it did not expire.
+  pub(crate) fn get(&self, command: &str, key: &str) -> Option<InvokeResponseBody> {
+    let mut entries = self.entries.lock().unwrap();
+    let command_entries = entries.get_mut(command)?;
+    match command_entries.get(key) {
+      Some(entry) if entry.expires_at > Instant::now() => Some(entry.body.clone()),
+      Some(_) => {
+        command_entries.remove(key);
+        None
+      }
+      None => None,
+    }
+  }
+
+  pub(crate) fn insert(&self, command: &str, key: String, body: InvokeResponseBody, ttl: Duration) {
+    let now = Instant::now();
+    let mut entries = self.entries.lock().unwrap();
+    let command_entries = entries.entry(command.to_string()).or_default();
+    if command_entries.len() >= MAX_ENTRIES_PER_COMMAND && !command_entries.contains_key(&key) {
+      command_entries.retain(|_, entry| entry.expires_at > now);
+      if command_entries.len() >= MAX_ENTRIES_PER_COMMAND {
+        if let Some(first) = command_entries
+          .iter()
+          .min_by_key(|(_, entry)| entry.expires_at)
+          .map(|(key, _)| key.clone())
+        {
+          command_entries.remove(&first);
+        }
+      }
+    }
+    command_entries.insert(
+      key,
+      Entry {
+        body,
+        expires_at: now + ttl,
+      },
+    );
+  }
+
+  /// Removes the cached responses of the command.
+  pub(crate) fn invalidate(&self, command: &str) {
+    self.entries.lock().unwrap().remove(command);
+  }
+
+  /// Removes all the cached responses.
+  pub(crate) fn clear(&self) {
+    self.entries.lock().unwrap().clear();
+  }
+}
+
+/// The cache key of a command invoked by the webview with the given label and origin,
+/// so a response is never served to another webview or origin.
+///
+/// The payload is only part of the key when given.
+pub(crate) fn cache_key(webview: &str, origin: &str, payload: Option<&InvokeBody>) -> String {
+  let mut hasher = Sha256::new();
+  // length prefixes keep the boundaries between the parts unambiguous
+  for part in [webview, origin] {
+    hasher.update((part.len() as u64).to_le_bytes());
+    hasher.update(part.as_bytes());
+  }
+  match payload {
+    Some(InvokeBody::Json(json)) => {
+      hasher.update(b"json:");
+      hasher.update(json.to_string().as_bytes());
+    }
+    Some(InvokeBody::Raw(bytes)) => {
+      hasher.update(b"raw:");
+      hasher.update(bytes);
+    }
+    None => hasher.update(b"none"),
+  }
+  hasher
+    .finalize()
+    .iter()
+    .map(|b| format!("{b:02x}"))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn caches_until_expiration() {
+    let cache = CommandCache::default();
+    let key = cache_key(
+      "main",
+      "tauri://localhost",
+      Some(&InvokeBody::Json(serde_json::json!({ "path": "/tmp" }))),
+    );
+    cache.insert(
+      "list_files",
+      key.clone(),
+      InvokeResponseBody::Json("[]".into()),
+      Duration::from_secs(30),
+    );
+    assert_eq!(
+      cache.get("list_files", &key),
+      Some(InvokeResponseBody::Json("[]".into()))
+    );
+    assert_eq!(cache.get("list_files", "other"), None);
+
+    cache.invalidate("list_files");
+    assert_eq!(cache.get("list_files", &key), None);
+
+    cache.insert(
+      "list_files",
+      key.clone(),
+      InvokeResponseBody::Json("[]".into()),
+      Duration::ZERO,
+    );
+    assert_eq!(cache.get("list_files", &key), None);
+  }
+
+  #[test]
+  fn keys_depend_on_the_payload() {
+    let key = |payload: &InvokeBody| cache_key("main", "tauri://localhost", Some(payload));
+    let a = key(&InvokeBody::Json(serde_json::json!({ "path": "/a" })));
+    let b = key(&InvokeBody::Json(serde_json::json!({ "path": "/b" })));
+    assert_ne!(a, b);
+    assert_eq!(
+      a,
+      key(&InvokeBody::Json(serde_json::json!({ "path": "/a" })))
+    );
+    assert_ne!(
+      key(&InvokeBody::Raw(b"\"a\"".to_vec())),
+      key(&InvokeBody::Json(serde_json::json!("a")))
+    );
+  }
+
+  #[test]
+  fn keys_depend_on_the_webview_and_origin() {
+    let payload = InvokeBody::Json(serde_json::json!({ "path": "/a" }));
+    let a = cache_key("main", "tauri://localhost", Some(&payload));
+    assert_ne!(a, cache_key("other", "tauri://localhost", Some(&payload)));
+    assert_ne!(a, cache_key("main", "https://example.com", Some(&payload)));
+    assert_ne!(
+      cache_key("main", "tauri://localhost", None),
+      cache_key("other", "tauri://localhost", None)
+    );
+    assert_ne!(cache_key("ab", "c", None), cache_key("a", "bc", None));
+  }
+
+  #[test]
+  fn bounds_the_entries() {
+    let cache = CommandCache::default();
+    for i in 0..MAX_ENTRIES_PER_COMMAND + 10 {
+      cache.insert(
+        "scan",
+        i.to_string(),
+        InvokeResponseBody::Raw(Vec::new()),
+        Duration::from_secs(60 + i as u64),
+      );
+    }
+    let entries = cache.entries.lock().unwrap();
+    assert_eq!(entries["scan"].len(), MAX_ENTRIES_PER_COMMAND);
+    assert!(!entries["scan"].contains_key("0"));
+  }
+}
@@ -183,11 +183,15 @@ impl<'de, R: Runtime> Deserializer<'de> for CommandItem<'de, R> {
 #[doc(hidden)]
 pub mod private {
   use crate::{
-    ipc::{InvokeError, InvokeResolver, InvokeResponseBody, IpcResponse},
+    ipc::{
+      cache::cache_key, InvokeError, InvokeMessage, InvokeResolver, InvokeResponse,
+      InvokeResponseBody, IpcResponse,
+    },
+    sealed::ManagerBase,
     Runtime,
   };
   use futures_util::{FutureExt, TryFutureExt};
-  use std::future::Future;
+  use std::{future::Future, time::Duration};
   #[cfg(feature = "tracing")]
   pub use tracing;
 
@@ -335,4 +339,58 @@ pub mod private {
         .map(|result| result.and_then(|value| value.body().map_err(InvokeError::from_error)))
     }
   }
+
+  // ===== #[command(cache = "...")] =====
+
+  /// Responds with the cached response of the command if any and returns `true`,
+  /// otherwise makes the resolver cache the successful response and returns `false`.
+  pub fn cache_response<R: Runtime>(
+    message: &InvokeMessage<R>,
+    resolver: &InvokeResolver<R>,
+    ttl_ms: u64,
+    by_args: bool,
+  ) -> bool {
+    let manager = message.webview.manager_owned();
+    let origin = message
+      .webview
+      .url()
+      // the origin of custom schemes such as `tauri://localhost` is opaque, keep the scheme and host
+      .map(|url| {
+        format!(
+          "{}://{}",
+          url.scheme(),
+          &url[url::Position::BeforeHost..url::Position::AfterPort]
+        )
+      })
+      .unwrap_or_default();
+    let key = cache_key(
+      message.webview.label(),
+      &origin,
+      by_args.then_some(&message.payload),
+    );
+
+    if let Some(body) = manager.command_cache.get(&resolver.cmd, &key) {
+      InvokeResolver::return_result(
+        resolver.webview.clone(),
+        resolver.responder.clone(),
+        InvokeResponse::Ok(body),
+        resolver.cmd.clone(),
+        resolver.callback,
+        resolver.error,
+      );
+      return true;
+    }
+
+    let mut responder = resolver.responder.lock().unwrap();
+    if let Some(respond) = responder.take() {
+      let ttl = Duration::from_millis(ttl_ms);
+      responder.replace(Box::new(move |webview, cmd, response, callback, error| {
+        if let InvokeResponse::Ok(body) = &response {
+          manager.command_cache.insert(&cmd, key, body.clone(), ttl);
+        }
+        respond(webview, cmd, response, callback, error)
+      }));
+    }
+    false
+  }
 }
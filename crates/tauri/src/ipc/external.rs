@@ -0,0 +1,583 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Local IPC server for other processes of the same user.
+//!
+//! Lets companion CLIs and other local processes talk to a running app
+//! over a Unix domain socket (named pipe on Windows) without opening TCP ports.
+//!
+//! # Protocol
+//!
+//! Every message is a JSON document prefixed by its length as a big-endian `u32`.
+//! Requests look like `{ "id": 1, "token": "...", "command": "open", "payload": { ... } }`
+//! and are answered with `{ "id": 1, "result": ... }` or `{ "id": 1, "error": "..." }`.
+//!
+//! The endpoint and the random token required by every request are written to a connection file
+//! in the app local data directory, readable only by the current user. See [`connection_file`] and [`Client`].
+//!
+//! The app executable itself can be used as a client with [`forward_invoke`]:
+//! `my-app --invoke '{ "command": "open", "payload": { "path": "/tmp/file.txt" } }'`
+//! sends the request to the running instance and prints the JSON reply.
+//!
+//! The server also receives the arguments forwarded by secondary instances with [`crate::cli::forward_args`].
+
+use std::{
+  ffi::OsString,
+  io::{Read, Write},
+  path::{Path, PathBuf},
+  sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::{async_runtime, AppHandle, Manager, Runtime};
+
+/// Name of the file holding the endpoint and token, in the app local data directory.
+pub const CONNECTION_FILE_NAME: &str = "external-ipc.json";
+
+/// Messages bigger than this are rejected.
+pub const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+/// The command line flag of the secondary invocations, see [`forward_invoke`].
+pub const INVOKE_FLAG: &str = "--invoke";
+
+type Handler<R> = dyn Fn(&AppHandle<R>, Request) -> Result<JsonValue, String> + Send + Sync;
+
+/// A request received from another process.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Request {
+  /// The command name.
+  pub command: String,
+  /// The command payload.
+  #[serde(default)]
+  pub payload: JsonValue,
+}
+
+#[derive(Deserialize)]
+struct IncomingRequest {
+  id: u64,
+  token: String,
+  #[serde(flatten)]
+  request: Request,
+}
+
+#[derive(Serialize)]
+struct OutgoingRequest<'a> {
+  id: u64,
+  token: &'a str,
+  command: &'a str,
+  payload: &'a JsonValue,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Response {
+  id: u64,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  result: Option<JsonValue>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  error: Option<String>,
+}
+
+/// Endpoint and token stored in the connection file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionInfo {
+  /// Socket path, or pipe name on Windows.
+  pub endpoint: String,
+  /// The token that must be sent with every request.
+  pub token: String,
+}
+
+/// A running external IPC server.
+///
+/// The server stops and its connection file is removed when this value is dropped,
+/// so keep it alive for as long as it should be reachable, e.g. with [`Manager::manage`].
+pub struct Server {
+  info: ConnectionInfo,
+  connection_file: PathBuf,
+  task: async_runtime::JoinHandle<()>,
+}
+
+impl Server {
+  /// The endpoint and token clients need to connect.
+  pub fn connection_info(&self) -> &ConnectionInfo {
+    &self.info
+  }
+
+  /// The path to the connection file.
+  pub fn connection_file(&self) -> &Path {
+    &self.connection_file
+  }
+}
+
+impl Drop for Server {
+  fn drop(&mut self) {
+    self.task.abort();
+    let _ = std::fs::remove_file(&self.connection_file);
+    #[cfg(unix)]
+    let _ = std::fs::remove_file(&self.info.endpoint);
+  }
+}
+
+/// Starts the external IPC server, calling `handler` for each authenticated request.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri::{ipc::external, Manager};
+///
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     let server = external::serve(app.handle(), |app, request| match request.command.as_str() {
+///       "focus" => {
+///         if let Some(window) = app.get_webview_window("main") {
+///           window.set_focus().map_err(|e| e.to_string())?;
+///         }
+///         Ok(serde_json::Value::Null)
+///       }
+///       _ => Err(format!("unknown command {}", request.command)),
+///     })?;
+///     app.manage(server);
+///     Ok(())
+///   });
+/// ```
+pub fn serve<R: Runtime, F>(app: &AppHandle<R>, handler: F) -> crate::Result<Server>
+where
+  F: Fn(&AppHandle<R>, Request) -> Result<JsonValue, String> + Send + Sync + 'static,
+{
+  let data_dir = app.path().app_local_data_dir()?;
+  std::fs::create_dir_all(&data_dir)?;
+
+  let mut token = [0u8; 32];
+  getrandom::getrandom(&mut token)?;
+  let token = token.iter().map(|b| format!("{b:02x}")).collect::<String>();
+
+  let endpoint = endpoint(&app.config().identifier, &data_dir);
+  let listener = platform::Listener::bind(&endpoint)?;
+
+  let info = ConnectionInfo { endpoint, token };
+  let connection_file = data_dir.join(CONNECTION_FILE_NAME);
+  write_private(&connection_file, &serde_json::to_vec(&info)?)?;
+
+  let handler: Arc<Handler<R>> = Arc::new(handler);
+  let app = app.clone();
+  let token = info.token.clone();
+  let task = async_runtime::spawn(async move {
+    loop {
+      match listener.accept().await {
+        Ok(stream) => {
+          let app = app.clone();
+          let handler = handler.clone();
+          let token = token.clone();
+          async_runtime::spawn(async move {
+            if let Err(e) = handle_connection(stream, &app, &*handler, &token).await {
+              log::debug!("external IPC connection closed: {e}");
+            }
+          });
+        }
+        Err(e) => {
+          log::error!("failed to accept external IPC connection: {e}");
+          break;
+        }
+      }
+    }
+  });
+
+  Ok(Server {
+    info,
+    connection_file,
+    task,
+  })
+}
+
+async fn handle_connection<R: Runtime, S: AsyncRead + AsyncWrite + Unpin>(
+  mut stream: S,
+  app: &AppHandle<R>,
+  handler: &Handler<R>,
+  token: &str,
+) -> std::io::Result<()> {
+  loop {
+    let mut len = [0u8; 4];
+    match stream.read_exact(&mut len).await {
+      Ok(_) => (),
+      // the client closed the connection
+      Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+      Err(e) => return Err(e),
+    }
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_MESSAGE_SIZE {
+      return Err(invalid_data("message too large"));
+    }
+    let mut message = vec![0u8; len];
+    stream.read_exact(&mut message).await?;
+
+    let request: IncomingRequest = serde_json::from_slice(&message).map_err(invalid_data)?;
+    if !constant_time_eq(request.token.as_bytes(), token.as_bytes()) {
+      let response = Response {
+        id: request.id,
+        result: None,
+        error: Some("invalid token".into()),
+      };
+      write_frame_async(&mut stream, &response).await?;
+      return Err(std::io::Error::new(
+        std::io::ErrorKind::PermissionDenied,
+        "invalid token",
+      ));
+    }
+
+    // arguments forwarded by a secondary instance with `cli::forward_args`
+    let result = if request.request.command == crate::cli::FORWARD_ARGS_COMMAND {
+      crate::cli::deliver_forwarded(app, request.request.payload)
+    } else {
+      handler(app, request.request)
+    };
+    let response = match result {
+      Ok(result) => Response {
+        id: request.id,
+        result: Some(result),
+        error: None,
+      },
+      Err(error) => Response {
+        id: request.id,
+        result: None,
+        error: Some(error),
+      },
+    };
+    write_frame_async(&mut stream, &response).await?;
+  }
+}
+
+async fn write_frame_async<S: AsyncWrite + Unpin, T: Serialize>(
+  stream: &mut S,
+  message: &T,
+) -> std::io::Result<()> {
+  let bytes = serde_json::to_vec(message).map_err(invalid_data)?;
+  stream
+    .write_all(&(bytes.len() as u32).to_be_bytes())
+    .await?;
+  stream.write_all(&bytes).await?;
+  stream.flush().await
+}
+
+/// A blocking client for the external IPC server, meant for companion CLIs.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri::ipc::external::Client;
+///
+/// let mut client = Client::connect("com.tauri.dev").expect("app is not running");
+/// client.request("focus", serde_json::Value::Null).unwrap();
+/// ```
+pub struct Client {
+  stream: platform::Stream,
+  token: String,
+  next_id: u64,
+}
+
+impl Client {
+  /// Connects to the running app with the given bundle identifier.
+  pub fn connect(identifier: &str) -> std::io::Result<Self> {
+    let path = connection_file(identifier).ok_or_else(|| {
+      std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        "could not resolve the local data directory",
+      )
+    })?;
+    Self::from_connection_file(path)
+  }
+
+  /// Connects using the given connection file.
+  pub fn from_connection_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+    let info: ConnectionInfo =
+      serde_json::from_slice(&std::fs::read(path)?).map_err(invalid_data)?;
+    Self::from_connection_info(info)
+  }
+
+  /// Connects using the given endpoint and token.
+  pub fn from_connection_info(info: ConnectionInfo) -> std::io::Result<Self> {
+    Ok(Self {
+      stream: platform::connect(&info.endpoint)?,
+      token: info.token,
+      next_id: 0,
+    })
+  }
+
+  /// Sends a request and waits for its response.
+  pub fn request(&mut self, command: &str, payload: JsonValue) -> std::io::Result<JsonValue> {
+    self.next_id += 1;
+    let request = OutgoingRequest {
+      id: self.next_id,
+      token: &self.token,
+      command,
+      payload: &payload,
+    };
+    let bytes = serde_json::to_vec(&request).map_err(invalid_data)?;
+    self.stream.write_all(&(bytes.len() as u32).to_be_bytes())?;
+    self.stream.write_all(&bytes)?;
+    self.stream.flush()?;
+
+    let mut len = [0u8; 4];
+    self.stream.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_MESSAGE_SIZE {
+      return Err(invalid_data("message too large"));
+    }
+    let mut message = vec![0u8; len];
+    self.stream.read_exact(&mut message)?;
+    let response: Response = serde_json::from_slice(&message).map_err(invalid_data)?;
+
+    match response.error {
+      Some(error) => Err(std::io::Error::other(error)),
+      None => Ok(response.result.unwrap_or_default()),
+    }
+  }
+}
+
+/// The request passed with `--invoke <json>` or `--invoke=<json>`, if any.
+fn invoke_request(args: impl IntoIterator<Item = OsString>) -> Option<Result<Request, String>> {
+  let mut args = args.into_iter();
+  let json = loop {
+    let arg = args.next()?;
+    let arg = arg.to_string_lossy();
+    if arg == INVOKE_FLAG {
+      break args.next().map(|json| json.to_string_lossy().into_owned());
+    }
+    if let Some(json) = arg
+      .strip_prefix(INVOKE_FLAG)
+      .and_then(|arg| arg.strip_prefix('='))
+    {
+      break Some(json.to_string());
+    }
+  };
+  Some(
+    json
+      .ok_or_else(|| format!("missing JSON request after {INVOKE_FLAG}"))
+      .and_then(|json| serde_json::from_str(&json).map_err(|e| format!("invalid request: {e}"))),
+  )
+}
+
+/// Handles a secondary invocation of the app started with `--invoke <json>`,
+/// where the JSON is a request like `{ "command": "open", "payload": { ... } }`.
+///
+/// The request is sent to the [`serve`] handler of the running instance with the given bundle identifier,
+/// the JSON reply is printed to stdout and the process exits, with the code `1` and the error printed to stderr on failure.
+/// Returns without doing anything if the flag is not set, so call it at the start of `main`,
+/// before the app and its windows are created.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// let context = tauri::generate_context!("test/fixture/src-tauri/tauri.conf.json");
+/// tauri::ipc::external::forward_invoke(&context.config().identifier);
+/// tauri::Builder::default()
+///   .run(context)
+///   .expect("error while running tauri application");
+/// ```
+pub fn forward_invoke(identifier: &str) {
+  let Some(request) = invoke_request(std::env::args_os().skip(1)) else {
+    return;
+  };
+
+  // the GUI subsystem apps are detached from the terminal
+  #[cfg(windows)]
+  unsafe {
+    use windows::Win32::System::Console::{
+      AttachConsole, GetStdHandle, ATTACH_PARENT_PROCESS, STD_OUTPUT_HANDLE,
+    };
+    if GetStdHandle(STD_OUTPUT_HANDLE).map_or(true, |handle| handle.is_invalid()) {
+      let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+  }
+
+  let result = request.and_then(|request| {
+    Client::connect(identifier)
+      .map_err(|e| format!("failed to connect to the running app: {e}"))?
+      .request(&request.command, request.payload)
+      .map_err(|e| e.to_string())
+  });
+  match result {
+    Ok(reply) => {
+      println!("{reply}");
+      std::process::exit(0);
+    }
+    Err(e) => {
+      eprintln!("{e}");
+      std::process::exit(1);
+    }
+  }
+}
+
+/// The connection file of the app with the given bundle identifier.
+///
+/// Returns `None` if the local data directory cannot be resolved.
+pub fn connection_file(identifier: &str) -> Option<PathBuf> {
+  dirs::data_local_dir().map(|dir| dir.join(identifier).join(CONNECTION_FILE_NAME))
+}
+
+#[cfg(unix)]
+fn endpoint(_identifier: &str, data_dir: &Path) -> String {
+  data_dir
+    .join("external-ipc.sock")
+    .to_string_lossy()
+    .into_owned()
+}
+
+#[cfg(windows)]
+fn endpoint(identifier: &str, _data_dir: &Path) -> String {
+  format!(r"\\.\pipe\{identifier}-external-ipc")
+}
+
+fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+  let mut options = std::fs::OpenOptions::new();
+  options.write(true).create(true).truncate(true);
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+    options.mode(0o600);
+    // the file might already exist with broader permissions
+    if path.exists() {
+      std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    }
+  }
+  options.open(path)?.write_all(contents)
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+  a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn invalid_data<E: Into<Box<dyn std::error::Error + Send + Sync>>>(e: E) -> std::io::Error {
+  std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+}
+
+#[cfg(unix)]
+mod platform {
+  use std::os::unix::fs::PermissionsExt;
+
+  pub type Stream = std::os::unix::net::UnixStream;
+
+  pub fn connect(endpoint: &str) -> std::io::Result<Stream> {
+    Stream::connect(endpoint)
+  }
+
+  pub struct Listener(tokio::net::UnixListener);
+
+  impl Listener {
+    pub fn bind(endpoint: &str) -> std::io::Result<Self> {
+      // remove the socket left behind by a previous instance
+      let _ = std::fs::remove_file(endpoint);
+      let listener = std::os::unix::net::UnixListener::bind(endpoint)?;
+      std::fs::set_permissions(endpoint, std::fs::Permissions::from_mode(0o600))?;
+      listener.set_nonblocking(true)?;
+      let handle = crate::async_runtime::handle();
+      let _guard = handle.inner().enter();
+      tokio::net::UnixListener::from_std(listener).map(Self)
+    }
+
+    pub async fn accept(&self) -> std::io::Result<tokio::net::UnixStream> {
+      self.0.accept().await.map(|(stream, _)| stream)
+    }
+  }
+}
+
+#[cfg(windows)]
+mod platform {
+  use tokio::{
+    net::windows::named_pipe::{NamedPipeServer, ServerOptions},
+    sync::Mutex,
+  };
+
+  pub type Stream = std::fs::File;
+
+  pub fn connect(endpoint: &str) -> std::io::Result<Stream> {
+    std::fs::OpenOptions::new()
+      .read(true)
+      .write(true)
+      .open(endpoint)
+  }
+
+  pub struct Listener {
+    endpoint: String,
+    next: Mutex<NamedPipeServer>,
+  }
+
+  impl Listener {
+    pub fn bind(endpoint: &str) -> std::io::Result<Self> {
+      let handle = crate::async_runtime::handle();
+      let _guard = handle.inner().enter();
+      let server = ServerOptions::new()
+        .first_pipe_instance(true)
+        .create(endpoint)?;
+      Ok(Self {
+        endpoint: endpoint.into(),
+        next: Mutex::new(server),
+      })
+    }
+
+    pub async fn accept(&self) -> std::io::Result<NamedPipeServer> {
+      let mut next = self.next.lock().await;
+      next.connect().await?;
+      // create the next instance before handing out the connected one so clients can always connect
+      let server = ServerOptions::new().create(&self.endpoint)?;
+      Ok(std::mem::replace(&mut *next, server))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn request_wire_format() {
+    let payload = serde_json::json!({ "path": "/tmp/file.txt" });
+    let request = OutgoingRequest {
+      id: 1,
+      token: "secret",
+      command: "open",
+      payload: &payload,
+    };
+    let incoming: IncomingRequest =
+      serde_json::from_value(serde_json::to_value(&request).unwrap()).unwrap();
+    assert_eq!(incoming.id, 1);
+    assert_eq!(incoming.token, "secret");
+    assert_eq!(incoming.request.command, "open");
+    assert_eq!(incoming.request.payload, payload);
+
+    let incoming: IncomingRequest =
+      serde_json::from_str(r#"{ "id": 2, "token": "secret", "command": "focus" }"#).unwrap();
+    assert_eq!(incoming.request.payload, JsonValue::Null);
+  }
+
+  #[test]
+  fn parses_invoke_request() {
+    let args = |args: &[&str]| invoke_request(args.iter().map(OsString::from));
+
+    assert!(args(&["--verbose"]).is_none());
+
+    let request = args(&["--invoke", r#"{ "command": "open", "payload": [1] }"#])
+      .unwrap()
+      .unwrap();
+    assert_eq!(request.command, "open");
+    assert_eq!(request.payload, serde_json::json!([1]));
+
+    let request = args(&[r#"--invoke={ "command": "focus" }"#])
+      .unwrap()
+      .unwrap();
+    assert_eq!(request.command, "focus");
+    assert_eq!(request.payload, JsonValue::Null);
+
+    assert!(args(&["--invoke"]).unwrap().is_err());
+    assert!(args(&["--invoke", "focus"]).unwrap().is_err());
+  }
+
+  #[test]
+  fn token_comparison() {
+    assert!(constant_time_eq(b"abc", b"abc"));
+    assert!(!constant_time_eq(b"abc", b"abd"));
+    assert!(!constant_time_eq(b"abc", b"abcd"));
+  }
+}
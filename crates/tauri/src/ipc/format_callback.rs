@@ -92,16 +92,21 @@ pub fn format<T: Serialize>(function_name: CallbackFn, arg: &T) -> crate::Result
 /// See [json-parse-benchmark](https://github.com/GoogleChromeLabs/json-parse-benchmark).
 pub fn format_raw(function_name: CallbackFn, json_string: String) -> crate::Result<String> {
   serialize_js_with(json_string, Default::default(), |arg| {
-    format!(
-      r#"
+    format_expression(function_name, arg)
+  })
+}
+
+/// Formats a function name and a JavaScript expression evaluating to its argument to be evaluated as callback.
+pub(crate) fn format_expression(function_name: CallbackFn, arg: &str) -> String {
+  format!(
+    r#"
     if (window["_{fn}"]) {{
       window["_{fn}"]({arg})
     }} else {{
       console.warn("[TAURI] Couldn't find callback id {fn} in window. This happens when the app is reloaded while Rust is running an asynchronous operation.")
     }}"#,
-      fn = function_name.0
-    )
-  })
+    fn = function_name.0
+  )
 }
 
 /// Formats a serializable Result type to its Promise response.
@@ -0,0 +1,207 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, sync::Mutex};
+
+use super::{Invoke, InvokeHandler};
+use crate::Runtime;
+
+/// Composes the invoke handlers registered with [`crate::Builder::invoke_handler`]
+/// and [`crate::Builder::extend_invoke_handler`] into a single handler.
+///
+/// Until a command is handled, its invokes try the handlers in order with a copy of the invoke.
+/// The handler that handles it is remembered and the next invokes go straight to it,
+/// so this assumes whether a handler handles a command only depends on the command name,
+/// not on the rest of `invoke.message` or on `invoke.acl`, as for [`crate::generate_handler!`].
+/// Unhandled commands are not remembered, so the routes stay bounded by the registered commands.
+pub(crate) fn compose<R: Runtime>(
+  mut handlers: Vec<Box<InvokeHandler<R>>>,
+) -> Box<InvokeHandler<R>> {
+  match handlers.len() {
+    0 => Box::new(|_| false),
+    1 => handlers.remove(0),
+    _ => {
+      // the index of the handler of each handled command
+      let routes = Mutex::new(HashMap::<String, usize>::new());
+      Box::new(move |invoke| {
+        let route = routes
+          .lock()
+          .unwrap()
+          .get(invoke.message.command())
+          .copied();
+        if let Some(index) = route {
+          return handlers[index](invoke);
+        }
+        let command = invoke.message.command().to_string();
+        match route_first_invoke(&handlers, invoke) {
+          Some(index) => {
+            routes.lock().unwrap().insert(command, index);
+            true
+          }
+          None => false,
+        }
+      })
+    }
+  }
+}
+
+/// Runs the handlers until one handles the invoke, returning its index.
+fn route_first_invoke<R: Runtime>(
+  handlers: &[Box<InvokeHandler<R>>],
+  invoke: Invoke<R>,
+) -> Option<usize> {
+  let (last, others) = handlers.split_last()?;
+  for (index, handler) in others.iter().enumerate() {
+    let copy = Invoke {
+      message: invoke.message.clone(),
+      resolver: invoke.resolver.clone(),
+      acl: invoke.acl.clone(),
+    };
+    if handler(copy) {
+      return Some(index);
+    }
+  }
+  last(invoke).then_some(others.len())
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::{Arc, Mutex};
+
+  use crate::{
+    ipc::{CallbackFn, InvokeBody},
+    test::{get_ipc_response, mock_builder, mock_context, noop_assets, MockRuntime, INVOKE_KEY},
+    webview::InvokeRequest,
+    WebviewWindow, WebviewWindowBuilder,
+  };
+
+  use super::*;
+
+  type Calls = Arc<Mutex<Vec<(&'static str, String)>>>;
+
+  /// A handler resolving the given commands with its name and recording every invoke it receives.
+  fn handler(
+    name: &'static str,
+    commands: &'static [&'static str],
+    calls: Calls,
+  ) -> impl Fn(Invoke<MockRuntime>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+      let command = invoke.message.command().to_string();
+      calls.lock().unwrap().push((name, command.clone()));
+      if commands.contains(&command.as_str()) {
+        invoke.resolver.resolve(name);
+        true
+      } else {
+        false
+      }
+    }
+  }
+
+  fn invoke(webview: &WebviewWindow<MockRuntime>, cmd: &str) -> Result<String, serde_json::Value> {
+    get_ipc_response(
+      webview,
+      InvokeRequest {
+        cmd: cmd.into(),
+        callback: CallbackFn(0),
+        error: CallbackFn(1),
+        url: "http://tauri.localhost".parse().unwrap(),
+        body: InvokeBody::default(),
+        headers: Default::default(),
+        invoke_key: INVOKE_KEY.to_string(),
+      },
+    )
+    .map(|body| body.deserialize::<String>().unwrap())
+  }
+
+  #[test]
+  fn composes_handlers_in_order() {
+    let calls = Calls::default();
+    let app = mock_builder()
+      .invoke_handler(handler("first", &["shared", "first_only"], calls.clone()))
+      .extend_invoke_handler(handler("second", &["shared", "second_only"], calls.clone()))
+      .build(mock_context(noop_assets()))
+      .unwrap();
+    let webview = WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+
+    // the first handler wins, the next ones are not tried
+    assert_eq!(invoke(&webview, "shared"), Ok("first".into()));
+    assert_eq!(invoke(&webview, "first_only"), Ok("first".into()));
+    assert_eq!(
+      calls.lock().unwrap().drain(..).collect::<Vec<_>>(),
+      [("first", "shared".into()), ("first", "first_only".into())]
+    );
+
+    // unknown commands fall through to the next handler
+    assert_eq!(invoke(&webview, "second_only"), Ok("second".into()));
+    assert_eq!(
+      calls.lock().unwrap().drain(..).collect::<Vec<_>>(),
+      [
+        ("first", "second_only".into()),
+        ("second", "second_only".into())
+      ]
+    );
+
+    // the handler of the command is remembered
+    assert_eq!(invoke(&webview, "second_only"), Ok("second".into()));
+    assert_eq!(
+      calls.lock().unwrap().drain(..).collect::<Vec<_>>(),
+      [("second", "second_only".into())]
+    );
+  }
+
+  #[test]
+  fn rejects_commands_no_handler_knows() {
+    let calls = Calls::default();
+    let app = mock_builder()
+      .invoke_handler(handler("first", &["a"], calls.clone()))
+      .extend_invoke_handler(handler("second", &["b"], calls.clone()))
+      .build(mock_context(noop_assets()))
+      .unwrap();
+    let webview = WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+
+    assert_eq!(
+      invoke(&webview, "unknown"),
+      Err("Command unknown not found".into())
+    );
+    assert_eq!(
+      calls.lock().unwrap().drain(..).collect::<Vec<_>>(),
+      [("first", "unknown".into()), ("second", "unknown".into())]
+    );
+
+    // unknown commands are not remembered, the handlers are tried again
+    assert_eq!(
+      invoke(&webview, "unknown"),
+      Err("Command unknown not found".into())
+    );
+    assert_eq!(
+      calls.lock().unwrap().drain(..).collect::<Vec<_>>(),
+      [("first", "unknown".into()), ("second", "unknown".into())]
+    );
+  }
+
+  #[test]
+  fn single_and_no_handlers() {
+    let calls = Calls::default();
+    let app = mock_builder()
+      .invoke_handler(handler("only", &["a"], calls.clone()))
+      .build(mock_context(noop_assets()))
+      .unwrap();
+    let webview = WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+    assert_eq!(invoke(&webview, "a"), Ok("only".into()));
+    assert_eq!(invoke(&webview, "b"), Err("Command b not found".into()));
+    assert_eq!(calls.lock().unwrap().len(), 2);
+
+    let app = mock_builder().build(mock_context(noop_assets())).unwrap();
+    let webview = WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+    assert_eq!(invoke(&webview, "a"), Err("Command a not found".into()));
+  }
+}
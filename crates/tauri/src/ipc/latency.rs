@@ -0,0 +1,191 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  collections::{HashMap, VecDeque},
+  sync::Mutex,
+  time::Duration,
+};
+
+use serde::Serialize;
+
+/// How many of the most recent samples are kept per command to compute percentiles.
+const MAX_SAMPLES: usize = 1024;
+
+/// How many commands are tracked, the command names come from the webviews so they are not trusted.
+const MAX_COMMANDS: usize = 512;
+
+/// Latency statistics of an IPC command, measured from the moment the request reaches the core
+/// until its response is handed back to the webview.
+///
+/// See [`crate::AppHandle::command_latencies`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandLatency {
+  /// The command name, `plugin:<plugin>|<command>` for plugin commands.
+  pub command: String,
+  /// How many times the command was resolved.
+  pub count: u64,
+  /// The median latency.
+  pub p50: Duration,
+  /// The 95th percentile latency.
+  pub p95: Duration,
+  /// The 99th percentile latency.
+  pub p99: Duration,
+  /// The slowest call recorded.
+  pub max: Duration,
+}
+
+#[derive(Default)]
+struct Samples {
+  count: u64,
+  max: Duration,
+  recent: VecDeque<Duration>,
+}
+
+/// Per-command latency samples.
+#[derive(Default)]
+pub(crate) struct CommandLatencies {
+  samples: Mutex<HashMap<String, Samples>>,
+}
+
+impl CommandLatencies {
+  pub(crate) fn record(&self, command: &str, elapsed: Duration) {
+    let mut samples = self.samples.lock().unwrap();
+    if samples.len() >= MAX_COMMANDS && !samples.contains_key(command) {
+      return;
+    }
+    let samples = samples.entry(command.to_string()).or_default();
+
+    samples.count += 1;
+    samples.max = samples.max.max(elapsed);
+    if samples.recent.len() == MAX_SAMPLES {
+      samples.recent.pop_front();
+    }
+    samples.recent.push_back(elapsed);
+  }
+
+  /// Statistics of all commands, slowest median first.
+  pub(crate) fn report(&self) -> Vec<CommandLatency> {
+    let samples = self.samples.lock().unwrap();
+    let mut report = samples
+      .iter()
+      .map(|(command, samples)| {
+        let mut sorted = samples.recent.iter().copied().collect::<Vec<_>>();
+        sorted.sort_unstable();
+        CommandLatency {
+          command: command.clone(),
+          count: samples.count,
+          p50: percentile(&sorted, 50),
+          p95: percentile(&sorted, 95),
+          p99: percentile(&sorted, 99),
+          max: samples.max,
+        }
+      })
+      .collect::<Vec<_>>();
+    report.sort_by(|a, b| b.p50.cmp(&a.p50).then_with(|| a.command.cmp(&b.command)));
+    report
+  }
+
+  /// Logs a table of the command latencies, used in development when the app exits.
+  #[cfg(dev)]
+  pub(crate) fn log_report(&self) {
+    let report = self.report();
+    if report.is_empty() {
+      return;
+    }
+
+    let width = report
+      .iter()
+      .map(|l| l.command.len())
+      .max()
+      .unwrap_or_default()
+      .max("command".len());
+    let mut table = format!(
+      "IPC command latencies:\n{:width$} {:>8} {:>10} {:>10} {:>10} {:>10}",
+      "command", "count", "p50", "p95", "p99", "max"
+    );
+    for l in report {
+      table.push_str(&format!(
+        "\n{:width$} {:>8} {:>10.2?} {:>10.2?} {:>10.2?} {:>10.2?}",
+        l.command, l.count, l.p50, l.p95, l.p99, l.max
+      ));
+    }
+    tracing::info!("{table}");
+  }
+}
+
+/// Nearest-rank percentile of an ascending list of samples.
+fn percentile(sorted: &[Duration], percentile: usize) -> Duration {
+  if sorted.is_empty() {
+    return Duration::ZERO;
+  }
+  let rank = (percentile * sorted.len()).div_ceil(100);
+  sorted[rank.saturating_sub(1).min(sorted.len() - 1)]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn percentiles() {
+    let latencies = CommandLatencies::default();
+    for ms in 1..=100 {
+      latencies.record("greet", Duration::from_millis(ms));
+    }
+    latencies.record("plugin:fs|read", Duration::from_millis(500));
+
+    let report = latencies.report();
+    assert_eq!(report.len(), 2);
+    assert_eq!(report[0].command, "plugin:fs|read");
+    assert_eq!(report[0].p99, Duration::from_millis(500));
+
+    let greet = &report[1];
+    assert_eq!(greet.count, 100);
+    assert_eq!(greet.p50, Duration::from_millis(50));
+    assert_eq!(greet.p95, Duration::from_millis(95));
+    assert_eq!(greet.p99, Duration::from_millis(99));
+    assert_eq!(greet.max, Duration::from_millis(100));
+  }
+
+  #[test]
+  fn keeps_recent_samples() {
+    let latencies = CommandLatencies::default();
+    for _ in 0..MAX_SAMPLES {
+      latencies.record("greet", Duration::from_secs(1));
+    }
+    for _ in 0..MAX_SAMPLES {
+      latencies.record("greet", Duration::from_millis(1));
+    }
+
+    let report = latencies.report();
+    assert_eq!(report[0].count, 2 * MAX_SAMPLES as u64);
+    assert_eq!(report[0].p99, Duration::from_millis(1));
+    assert_eq!(report[0].max, Duration::from_secs(1));
+  }
+
+  #[test]
+  fn bounds_commands() {
+    let latencies = CommandLatencies::default();
+    for i in 0..MAX_COMMANDS + 10 {
+      latencies.record(&format!("command{i}"), Duration::from_millis(1));
+    }
+    latencies.record("command0", Duration::from_millis(1));
+
+    let report = latencies.report();
+    assert_eq!(report.len(), MAX_COMMANDS);
+    assert!(!report
+      .iter()
+      .any(|l| l.command == format!("command{MAX_COMMANDS}")));
+    assert_eq!(
+      report
+        .iter()
+        .find(|l| l.command == "command0")
+        .unwrap()
+        .count,
+      2
+    );
+  }
+}
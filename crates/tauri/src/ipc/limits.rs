@@ -0,0 +1,148 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The size limits of the `app > ipc` configuration and the chunked delivery of large payloads.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use tauri_utils::config::IpcConfig;
+
+use crate::{sealed::ManagerBase, Runtime, Webview};
+
+/// The object holding the chunks of the payloads being reassembled in the webview.
+const CHUNKS_OBJECT_NAME: &str = "__TAURI_IPC_CHUNKS__";
+
+static CHUNKED_PAYLOAD_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+/// The payloads subject to a size limit.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Payload {
+  Request,
+  Response,
+  Event,
+}
+
+impl Payload {
+  fn limit(self, config: &IpcConfig) -> Option<u64> {
+    match self {
+      Self::Request => config.max_request_size,
+      Self::Response => config.max_response_size,
+      Self::Event => config.max_event_size,
+    }
+  }
+}
+
+/// Fails with [`crate::Error::IpcPayloadTooLarge`] if the payload exceeds its limit.
+pub(crate) fn check_size(config: &IpcConfig, payload: Payload, size: usize) -> crate::Result<()> {
+  match payload.limit(config) {
+    Some(limit) if size as u64 > limit => {
+      let (kind, option) = match payload {
+        Payload::Request => ("command request", "maxRequestSize"),
+        Payload::Response => ("command response", "maxResponseSize"),
+        Payload::Event => ("event", "maxEventSize"),
+      };
+      Err(crate::Error::IpcPayloadTooLarge {
+        kind,
+        option,
+        size,
+        limit,
+      })
+    }
+    _ => Ok(()),
+  }
+}
+
+/// Whether the JSON payload is larger than the `evalChunkSize` and must be sent with [`eval_chunks`].
+pub(crate) fn needs_chunks(config: &IpcConfig, json: &str) -> bool {
+  config.eval_chunk_size > 0 && json.len() as u64 > config.eval_chunk_size
+}
+
+/// Sends the JSON payload to the webview in chunks that it accumulates,
+/// returning the JavaScript expression evaluating to the payload once all chunks are received.
+///
+/// The expression must be evaluated once, as it releases the chunks.
+pub(crate) fn eval_chunks<R: Runtime>(webview: &Webview<R>, json: &str) -> crate::Result<String> {
+  let chunk_size = webview.manager().config().app.ipc.eval_chunk_size as usize;
+  let id = CHUNKED_PAYLOAD_COUNTER.fetch_add(1, Ordering::Relaxed);
+  for chunk in split(json, chunk_size) {
+    webview.eval(&format!(
+      "(function () {{ const chunks = window['{CHUNKS_OBJECT_NAME}'] || (window['{CHUNKS_OBJECT_NAME}'] = {{}}); chunks[{id}] = (chunks[{id}] || '') + {} }})()",
+      serde_json::to_string(chunk)?
+    ))?;
+  }
+  Ok(format!(
+    "(function () {{ const chunks = window['{CHUNKS_OBJECT_NAME}']; const json = chunks[{id}]; delete chunks[{id}]; return JSON.parse(json) }})()"
+  ))
+}
+
+/// Splits the string in parts of at most `max` bytes, at char boundaries.
+fn split(s: &str, max: usize) -> impl Iterator<Item = &str> {
+  let mut rest = s;
+  std::iter::from_fn(move || {
+    if rest.is_empty() {
+      return None;
+    }
+    let mut end = max.min(rest.len());
+    while !rest.is_char_boundary(end) {
+      end -= 1;
+    }
+    if end == 0 {
+      // a char larger than `max`
+      end = rest
+        .chars()
+        .next()
+        .map(char::len_utf8)
+        .unwrap_or(rest.len());
+    }
+    let (chunk, remaining) = rest.split_at(end);
+    rest = remaining;
+    Some(chunk)
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn splits_at_char_boundaries() {
+    assert_eq!(split("abcdef", 4).collect::<Vec<_>>(), ["abcd", "ef"]);
+    assert_eq!(split("abcd", 4).collect::<Vec<_>>(), ["abcd"]);
+    assert_eq!(split("", 4).count(), 0);
+    // `é` is 2 bytes long
+    assert_eq!(split("aéé", 2).collect::<Vec<_>>(), ["a", "é", "é"]);
+    assert_eq!(split("éa", 1).collect::<Vec<_>>(), ["é", "a"]);
+    assert_eq!(split("aéé", 3).collect::<String>(), "aéé");
+  }
+
+  #[test]
+  fn chunks_above_the_chunk_size() {
+    let config = IpcConfig {
+      eval_chunk_size: 4,
+      ..Default::default()
+    };
+    assert!(!needs_chunks(&config, "[12]"));
+    assert!(needs_chunks(&config, "[123]"));
+    let config = IpcConfig {
+      eval_chunk_size: 0,
+      ..Default::default()
+    };
+    assert!(!needs_chunks(&config, "[123]"));
+  }
+
+  #[test]
+  fn checks_the_limits() {
+    let config = IpcConfig {
+      max_request_size: Some(10),
+      ..Default::default()
+    };
+    assert!(check_size(&config, Payload::Request, 10).is_ok());
+    assert!(check_size(&config, Payload::Response, 1000).is_ok());
+    let error = check_size(&config, Payload::Request, 11).unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      "the command request payload of 11 bytes exceeds the `app > ipc > maxRequestSize` limit of 10 bytes"
+    );
+  }
+}
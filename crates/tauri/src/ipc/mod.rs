@@ -22,10 +22,20 @@ use tauri_utils::acl::resolved::ResolvedCommand;
 use crate::{webview::Webview, Runtime, StateManager};
 
 mod authority;
+pub(crate) mod cache;
 pub(crate) mod channel;
 mod command;
+#[cfg(all(desktop, feature = "external-ipc"))]
+#[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "external-ipc"))))]
+pub mod external;
 pub(crate) mod format_callback;
+pub(crate) mod handler;
+#[cfg(feature = "tracing")]
+pub(crate) mod latency;
+pub(crate) mod limits;
 pub(crate) mod protocol;
+#[cfg(feature = "localhost-server")]
+pub(crate) mod stream;
 
 pub use authority::{
   CapabilityBuilder, CommandScope, GlobalScope, Origin, RuntimeAuthority, RuntimeCapability,
@@ -33,6 +43,12 @@ pub use authority::{
 };
 pub use channel::{Channel, JavaScriptChannelId};
 pub use command::{private, CommandArg, CommandItem};
+#[cfg(feature = "tracing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+pub use latency::CommandLatency;
+#[cfg(feature = "localhost-server")]
+#[cfg_attr(docsrs, doc(cfg(feature = "localhost-server")))]
+pub use stream::StreamUrl;
 
 /// A closure that is run every time Tauri receives a message it doesn't explicitly handle.
 pub type InvokeHandler<R> = dyn Fn(Invoke<R>) -> bool + Send + Sync + 'static;
@@ -273,9 +289,9 @@ impl From<InvokeError> for InvokeResponse {
 /// Resolver of a invoke message.
 #[default_runtime(crate::Wry, wry)]
 pub struct InvokeResolver<R: Runtime> {
-  webview: Webview<R>,
+  pub(crate) webview: Webview<R>,
   responder: Arc<Mutex<Option<Box<OwnedInvokeResponder<R>>>>>,
-  cmd: String,
+  pub(crate) cmd: String,
   pub(crate) callback: CallbackFn,
   pub(crate) error: CallbackFn,
 }
@@ -315,6 +331,8 @@ impl<R: Runtime> InvokeResolver<R> {
     T: IpcResponse,
     F: Future<Output = Result<T, InvokeError>> + Send + 'static,
   {
+    #[cfg(dev)]
+    let task = crate::panic::catch_task(&self.webview, &self.cmd, task);
     crate::async_runtime::spawn(async move {
       Self::return_task(
         self.webview,
@@ -333,6 +351,8 @@ impl<R: Runtime> InvokeResolver<R> {
   where
     F: Future<Output = Result<InvokeResponseBody, InvokeError>> + Send + 'static,
   {
+    #[cfg(dev)]
+    let task = crate::panic::catch_task(&self.webview, &self.cmd, task);
     crate::async_runtime::spawn(async move {
       let response = match task.await {
         Ok(ok) => InvokeResponse::Ok(ok),
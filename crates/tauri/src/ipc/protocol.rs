@@ -7,6 +7,7 @@ use std::{borrow::Cow, sync::Arc};
 use crate::{
   ipc::InvokeResponseBody,
   manager::AppManager,
+  sealed::ManagerBase,
   webview::{InvokeRequest, UriSchemeProtocolHandler},
   Runtime,
 };
@@ -41,6 +42,7 @@ pub fn get<R: Runtime>(manager: Arc<AppManager<R>>, label: String) -> UriSchemeP
     let span = tracing::trace_span!(
       "ipc::request",
       kind = "custom-protocol",
+      webview = label,
       request = tracing::field::Empty
     )
     .entered();
@@ -61,6 +63,21 @@ pub fn get<R: Runtime>(manager: Arc<AppManager<R>>, label: String) -> UriSchemeP
 
     match *request.method() {
       Method::POST => {
+        if let Err(e) = super::limits::check_size(
+          &manager.config().app.ipc,
+          super::limits::Payload::Request,
+          request.body().len(),
+        ) {
+          respond(
+            http::Response::builder()
+              .status(StatusCode::PAYLOAD_TOO_LARGE)
+              .header(CONTENT_TYPE, mime::TEXT_PLAIN.essence_str())
+              .body(e.to_string().into_bytes().into())
+              .unwrap(),
+          );
+          return;
+        }
+
         if let Some(webview) = manager.get_webview(&label) {
           match parse_invoke_request(&manager, request) {
             Ok(request) => {
@@ -73,7 +90,8 @@ pub fn get<R: Runtime>(manager: Arc<AppManager<R>>, label: String) -> UriSchemeP
                 },
               );
               #[cfg(feature = "tracing")]
-              let request_span = tracing::trace_span!("ipc::request::handle", cmd = request.cmd);
+              let request_span =
+                tracing::trace_span!("ipc::request::handle", cmd = request.cmd, webview = label);
 
               webview.on_message(
                 request,
@@ -201,6 +219,7 @@ fn handle_ipc_message<R: Runtime>(request: Request<String>, manager: &AppManager
     let _span = tracing::trace_span!(
       "ipc::request",
       kind = "post-message",
+      webview = label,
       uri = request.uri().to_string(),
       request = request.body()
     )
@@ -306,6 +325,18 @@ fn handle_ipc_message<R: Runtime>(request: Request<String>, manager: &AppManager
 
     match message {
       Ok(message) => {
+        if let Err(e) = super::limits::check_size(
+          &manager.config().app.ipc,
+          super::limits::Payload::Request,
+          request.body().len(),
+        ) {
+          let _ = webview.eval(
+            &crate::ipc::format_callback::format(message.error, &e.to_string())
+              .expect("unable to serialize response error string to json"),
+          );
+          return;
+        }
+
         let options = message.options.unwrap_or_default();
 
         let request = InvokeRequest {
@@ -319,7 +350,8 @@ fn handle_ipc_message<R: Runtime>(request: Request<String>, manager: &AppManager
         };
 
         #[cfg(feature = "tracing")]
-        let request_span = tracing::trace_span!("ipc::request::handle", cmd = request.cmd);
+        let request_span =
+          tracing::trace_span!("ipc::request::handle", cmd = request.cmd, webview = label);
 
         webview.on_message(
           request,
@@ -347,6 +379,28 @@ fn handle_ipc_message<R: Runtime>(request: Request<String>, manager: &AppManager
               let _ = webview.eval(&eval_js);
             }
 
+            // large responses are sent in chunks instead of a single script
+            fn responder_eval_json<R: Runtime>(
+              webview: &crate::Webview<R>,
+              json: crate::Result<String>,
+              callback: CallbackFn,
+              error: CallbackFn,
+            ) {
+              let js = json.and_then(|json| {
+                if crate::ipc::limits::needs_chunks(&webview.manager().config().app.ipc, &json) {
+                  crate::ipc::limits::eval_chunks(webview, &json)
+                    .map(|arg| crate::ipc::format_callback::format_expression(callback, &arg))
+                } else {
+                  crate::ipc::format_callback::format_result_raw(
+                    Result::<_, String>::Ok(json),
+                    callback,
+                    error,
+                  )
+                }
+              });
+              responder_eval(webview, js, error)
+            }
+
             let can_use_channel_for_response = cmd
               != crate::ipc::channel::FETCH_CHANNEL_DATA_COMMAND
               && !options.custom_protocol_ipc_blocked;
@@ -389,15 +443,7 @@ fn handle_ipc_message<R: Runtime>(request: Request<String>, manager: &AppManager
                   let _ =
                     Channel::from_callback_fn(webview, callback).send(InvokeResponseBody::Json(v));
                 } else {
-                  responder_eval(
-                    &webview,
-                    crate::ipc::format_callback::format_result_raw(
-                      Result::<_, String>::Ok(v),
-                      callback,
-                      error,
-                    ),
-                    error,
-                  )
+                  responder_eval_json(&webview, Ok(v), callback, error)
                 }
               }
               InvokeResponse::Ok(InvokeResponseBody::Raw(v)) => {
@@ -405,13 +451,10 @@ fn handle_ipc_message<R: Runtime>(request: Request<String>, manager: &AppManager
                   || cfg!(target_os = "ios")
                   || !can_use_channel_for_response
                 {
-                  responder_eval(
+                  responder_eval_json(
                     &webview,
-                    crate::ipc::format_callback::format_result(
-                      Result::<_, ()>::Ok(v),
-                      callback,
-                      error,
-                    ),
+                    serde_json::to_string(&v).map_err(Into::into),
+                    callback,
                     error,
                   );
                 } else {
@@ -0,0 +1,257 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  borrow::Cow,
+  collections::HashMap,
+  sync::{Arc, Mutex},
+  time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use http::{
+  header::{ACCESS_CONTROL_ALLOW_ORIGIN, CONTENT_TYPE, ORIGIN, VARY},
+  Method, Response, StatusCode,
+};
+use serde::{Serialize, Serializer};
+use tauri_utils::config::CspDirectiveSources;
+use url::Url;
+
+use crate::{
+  localhost::{Body, BodyStream},
+  AppHandle, Manager, Runtime,
+};
+
+/// The name the streams are served under on the localhost server.
+const ROUTE: &str = "ipc-stream";
+/// The CSP source allowing the app pages to fetch the streams, restricted to their path on the loopback interface.
+const CSP_SOURCE: &str = "http://127.0.0.1:*/ipc-stream/";
+/// How long a stream waits to be fetched before being dropped.
+const UNFETCHED_STREAM_TIMEOUT: Duration = Duration::from_secs(60);
+
+struct PendingStream {
+  content_type: String,
+  stream: BodyStream,
+  created_at: Instant,
+}
+
+impl PendingStream {
+  fn is_expired(&self) -> bool {
+    self.created_at.elapsed() >= UNFETCHED_STREAM_TIMEOUT
+  }
+}
+
+/// The streams waiting to be fetched by the webviews.
+#[derive(Default)]
+pub(crate) struct Streams(Mutex<HashMap<String, PendingStream>>);
+
+impl Streams {
+  fn insert(&self, id: String, stream: PendingStream) {
+    let mut streams = self.0.lock().unwrap();
+    streams.retain(|_, stream| !stream.is_expired());
+    streams.insert(id, stream);
+  }
+
+  /// Removes the stream, returning it unless it expired.
+  fn take(&self, id: &str) -> Option<PendingStream> {
+    let mut streams = self.0.lock().unwrap();
+    streams.retain(|_, stream| !stream.is_expired());
+    streams.remove(id)
+  }
+}
+
+/// A byte stream produced in Rust that the frontend reads with `fetch`,
+/// to return large results from commands without serializing them in the IPC response.
+///
+/// It serializes to the URL of the stream on the [shared localhost server](crate::localhost),
+/// which can be fetched once within a minute. The URL does not include the token of the server:
+/// the random id of the stream is its only capability, so the URL must not be shared.
+/// The response has the content type given to [`Self::new`] and its body is sent as the chunks are produced,
+/// with the chunked transfer encoding. If the stream fails, the body ends early so reading the response fails.
+///
+/// Only the pages served by the app can read the stream from another origin, and the `connect-src` directive
+/// of the CSP, if set, is extended with `http://127.0.0.1:*/ipc-stream/` for them.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri::ipc::StreamUrl;
+///
+/// #[tauri::command]
+/// fn export_logs(app: tauri::AppHandle) -> Result<StreamUrl, String> {
+///   let lines = futures_util::stream::iter(
+///     (0..100_000).map(|i| Ok::<_, std::io::Error>(format!("line {i}\n"))),
+///   );
+///   StreamUrl::new(&app, "text/plain", lines).map_err(|e| e.to_string())
+/// }
+/// ```
+///
+/// ```javascript
+/// const url = await invoke('export_logs')
+/// const response = await fetch(url)
+/// const reader = response.body.getReader()
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamUrl(String);
+
+impl StreamUrl {
+  /// Registers the stream and returns its URL, starting the localhost server if it is not running yet.
+  pub fn new<R, M, S, B, E>(
+    manager: &M,
+    content_type: impl Into<String>,
+    stream: S,
+  ) -> crate::Result<Self>
+  where
+    R: Runtime,
+    M: Manager<R>,
+    S: Stream<Item = Result<B, E>> + Send + 'static,
+    B: Into<Bytes>,
+    E: std::fmt::Display,
+  {
+    let mut id = [0u8; 16];
+    getrandom::getrandom(&mut id)?;
+    let id = id.iter().map(|b| format!("{b:02x}")).collect::<String>();
+
+    let server = manager.localhost_server();
+    // registering the route again replaces it, so the server is restarted if its routes were removed
+    server.insert_route(ROUTE, Method::GET, "/*", true, Arc::new(respond::<R>))?;
+
+    manager.state::<Streams>().insert(
+      id.clone(),
+      PendingStream {
+        content_type: content_type.into(),
+        stream: Box::pin(stream.map(|chunk| chunk.map(Into::into).map_err(|e| e.to_string()))),
+        created_at: Instant::now(),
+      },
+    );
+
+    // the route is public, so the URL does not include the token
+    let url = server
+      .url(ROUTE, &id)
+      .expect("the localhost server runs once a route is registered");
+    Ok(Self(url.into()))
+  }
+
+  /// The URL of the stream.
+  pub fn as_str(&self) -> &str {
+    &self.0
+  }
+}
+
+impl Serialize for StreamUrl {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&self.0)
+  }
+}
+
+/// Allows the pages to fetch the streams in their CSP.
+pub(crate) fn allow_in_csp(csp: &mut HashMap<String, CspDirectiveSources>) {
+  // a new `connect-src` directive would replace the `default-src` fallback,
+  // and nothing needs to be allowed without either of them
+  let Some(mut sources) = csp
+    .get("connect-src")
+    .or_else(|| csp.get("default-src"))
+    .cloned()
+  else {
+    return;
+  };
+  if !sources.contains(CSP_SOURCE) {
+    sources.push(CSP_SOURCE);
+  }
+  csp.insert("connect-src".into(), sources);
+}
+
+/// Serves a stream on the localhost server.
+fn respond<R: Runtime>(app: &AppHandle<R>, request: http::Request<Vec<u8>>) -> Response<Body> {
+  let mut response = Response::builder().header(VARY, "Origin");
+  // the requests of the other web pages are rejected before the stream is consumed,
+  // the native players do not send an origin
+  if let Some(origin) = request.headers().get(ORIGIN) {
+    let allowed = origin
+      .to_str()
+      .ok()
+      .and_then(|origin| Url::parse(origin).ok())
+      .is_some_and(|origin| app.manager.is_local_url(&origin));
+    if !allowed {
+      return response
+        .status(StatusCode::FORBIDDEN)
+        .body(Body::Full(Cow::Borrowed(&[])))
+        .unwrap();
+    }
+    response = response.header(ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+  }
+  let id = request.uri().path().rsplit('/').next().unwrap_or_default();
+  match app.state::<Streams>().take(id) {
+    Some(pending) => response
+      .status(StatusCode::OK)
+      .header(CONTENT_TYPE, pending.content_type)
+      .body(Body::Stream(pending.stream)),
+    None => response
+      .status(StatusCode::NOT_FOUND)
+      .body(Body::Full(Cow::Borrowed(&[]))),
+  }
+  .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn pending(created_at: Instant) -> PendingStream {
+    PendingStream {
+      content_type: "text/plain".into(),
+      stream: Box::pin(futures_util::stream::empty()),
+      created_at,
+    }
+  }
+
+  #[test]
+  fn streams_are_fetched_once() {
+    let streams = Streams::default();
+    streams.insert("abc".into(), pending(Instant::now()));
+    assert!(streams.take("abc").is_some());
+    assert!(streams.take("abc").is_none());
+  }
+
+  #[test]
+  fn extends_the_connect_sources() {
+    let directives = |csp: &str| {
+      HashMap::<String, CspDirectiveSources>::from(tauri_utils::config::Csp::Policy(csp.into()))
+    };
+
+    let mut csp = directives("default-src 'self'; connect-src ipc: http://ipc.localhost");
+    allow_in_csp(&mut csp);
+    assert!(csp["connect-src"].contains("ipc:"));
+    assert!(csp["connect-src"].contains(CSP_SOURCE));
+    assert!(!csp["default-src"].contains(CSP_SOURCE));
+
+    // the default sources keep applying to the connections
+    let mut csp = directives("default-src 'self' ipc:");
+    allow_in_csp(&mut csp);
+    assert!(csp["connect-src"].contains("'self'"));
+    assert!(csp["connect-src"].contains(CSP_SOURCE));
+
+    let mut csp = directives("script-src 'self'");
+    allow_in_csp(&mut csp);
+    assert!(!csp.contains_key("connect-src"));
+  }
+
+  #[test]
+  fn expired_streams_are_dropped() {
+    let streams = Streams::default();
+    let expired = Instant::now() - UNFETCHED_STREAM_TIMEOUT;
+    streams.insert("abc".into(), pending(expired));
+    assert!(streams.take("abc").is_none());
+
+    // expired streams are dropped when another stream is registered
+    streams
+      .0
+      .lock()
+      .unwrap()
+      .insert("def".into(), pending(expired));
+    streams.insert("ghi".into(), pending(Instant::now()));
+    assert!(!streams.0.lock().unwrap().contains_key("def"));
+  }
+}
@@ -0,0 +1,261 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Keyboard layout and input method information.
+//!
+//! Terminal emulators and editors need the active layout to map physical shortcuts on non-US layouts,
+//! see [`crate::AppHandle::keyboard_layout`]. The input method composition happening in a webview
+//! is forwarded to the Rust side as [`crate::WebviewEvent::ImeComposition`].
+
+use serde::{Deserialize, Serialize};
+
+/// The keyboard layout or input method selected by the user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct KeyboardLayout {
+  /// The platform identifier of the layout,
+  /// such as `com.apple.keylayout.French` on macOS, `0000040C` on Windows or `xkb:fr` on Linux.
+  pub id: String,
+  /// The localized display name of the layout.
+  pub name: Option<String>,
+  /// The languages of the layout, as BCP 47 tags such as `fr-FR`.
+  pub languages: Vec<String>,
+}
+
+/// An input method editor (IME) composition event in a webview.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum ImeComposition {
+  /// A composition session started.
+  Start,
+  /// The text being composed changed.
+  Update {
+    /// The text being composed.
+    text: String,
+  },
+  /// The composition session ended.
+  End {
+    /// The committed text, empty if the composition was cancelled.
+    text: String,
+  },
+}
+
+/// Returns the keyboard layout of the calling thread. Must be called on the main thread.
+pub(crate) fn current_layout() -> std::io::Result<Option<KeyboardLayout>> {
+  if cfg!(all(windows, not(feature = "keyboard-layout"))) {
+    return Err(std::io::Error::new(
+      std::io::ErrorKind::Unsupported,
+      "reading the keyboard layout on Windows requires the `keyboard-layout` feature",
+    ));
+  }
+  Ok(platform::current_layout())
+}
+
+/// Converts an input source of the GNOME settings, such as `("xkb", "fr+azerty")`, to a layout.
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd",
+  test
+))]
+fn gnome_input_source_layout(kind: &str, id: &str) -> KeyboardLayout {
+  KeyboardLayout {
+    id: format!("{kind}:{id}"),
+    name: None,
+    languages: Vec::new(),
+  }
+}
+
+#[cfg(all(windows, feature = "keyboard-layout"))]
+mod platform {
+  use super::KeyboardLayout;
+  use windows::Win32::{
+    Globalization::LCIDToLocaleName,
+    UI::Input::KeyboardAndMouse::{GetKeyboardLayout, GetKeyboardLayoutNameW},
+  };
+
+  /// `LOCALE_ALLOW_NEUTRAL_NAMES`
+  const ALLOW_NEUTRAL_NAMES: u32 = 0x0800_0000;
+
+  pub fn current_layout() -> Option<KeyboardLayout> {
+    unsafe {
+      // KL_NAMELENGTH
+      let mut id = [0u16; 9];
+      GetKeyboardLayoutNameW(&mut id).ok()?;
+      let id = String::from_utf16_lossy(&id[..id.iter().position(|c| *c == 0).unwrap_or(8)]);
+
+      // the low word of the layout handle is the language identifier
+      let language_id = GetKeyboardLayout(0).0 as usize as u32 & 0xffff;
+      let mut locale = [0u16; 85];
+      let len = LCIDToLocaleName(language_id, Some(&mut locale), ALLOW_NEUTRAL_NAMES);
+      let languages = if len > 1 {
+        vec![String::from_utf16_lossy(&locale[..len as usize - 1])]
+      } else {
+        Vec::new()
+      };
+
+      Some(KeyboardLayout {
+        id,
+        name: None,
+        languages,
+      })
+    }
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use super::KeyboardLayout;
+  use objc2::{msg_send, runtime::AnyObject};
+  use objc2_foundation::NSString;
+  use std::ffi::c_void;
+
+  #[link(name = "Carbon", kind = "framework")]
+  extern "C" {
+    static kTISPropertyInputSourceID: *const c_void;
+    static kTISPropertyLocalizedName: *const c_void;
+    static kTISPropertyInputSourceLanguages: *const c_void;
+    fn TISCopyCurrentKeyboardInputSource() -> *const c_void;
+    fn TISGetInputSourceProperty(source: *const c_void, key: *const c_void) -> *const AnyObject;
+  }
+
+  #[link(name = "CoreFoundation", kind = "framework")]
+  extern "C" {
+    fn CFRelease(object: *const c_void);
+  }
+
+  unsafe fn string(value: *const AnyObject) -> Option<String> {
+    // CFString is toll-free bridged with NSString
+    value
+      .cast::<NSString>()
+      .as_ref()
+      .map(|value| value.to_string())
+  }
+
+  pub fn current_layout() -> Option<KeyboardLayout> {
+    unsafe {
+      let source = TISCopyCurrentKeyboardInputSource();
+      if source.is_null() {
+        return None;
+      }
+
+      let id = string(TISGetInputSourceProperty(source, kTISPropertyInputSourceID));
+      let name = string(TISGetInputSourceProperty(source, kTISPropertyLocalizedName));
+      let mut languages = Vec::new();
+      if let Some(list) =
+        TISGetInputSourceProperty(source, kTISPropertyInputSourceLanguages).as_ref()
+      {
+        let count: usize = msg_send![list, count];
+        for i in 0..count {
+          let language: *const AnyObject = msg_send![list, objectAtIndex: i];
+          languages.extend(string(language));
+        }
+      }
+      CFRelease(source);
+
+      Some(KeyboardLayout {
+        id: id?,
+        name,
+        languages,
+      })
+    }
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use super::{gnome_input_source_layout, KeyboardLayout};
+  use gtk::gio::{prelude::*, Settings, SettingsSchemaSource};
+
+  const INPUT_SOURCES_SCHEMA: &str = "org.gnome.desktop.input-sources";
+
+  pub fn current_layout() -> Option<KeyboardLayout> {
+    // creating settings for a schema that is not installed aborts the process
+    let schema = SettingsSchemaSource::default()?.lookup(INPUT_SOURCES_SCHEMA, true)?;
+    let settings = Settings::new(INPUT_SOURCES_SCHEMA);
+
+    // the most recently used source is the current one
+    let mut sources = Vec::new();
+    if schema.has_key("mru-sources") {
+      sources = settings
+        .value("mru-sources")
+        .get::<Vec<(String, String)>>()
+        .unwrap_or_default();
+    }
+    if sources.is_empty() {
+      sources = settings
+        .value("sources")
+        .get::<Vec<(String, String)>>()
+        .unwrap_or_default();
+    }
+
+    sources
+      .first()
+      .map(|(kind, id)| gnome_input_source_layout(kind, id))
+  }
+}
+
+#[cfg(not(any(
+  all(windows, feature = "keyboard-layout"),
+  target_os = "macos",
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+)))]
+mod platform {
+  use super::KeyboardLayout;
+
+  pub fn current_layout() -> Option<KeyboardLayout> {
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn deserializes_ime_composition() {
+    assert_eq!(
+      serde_json::from_str::<ImeComposition>(r#"{ "type": "start" }"#).unwrap(),
+      ImeComposition::Start
+    );
+    assert_eq!(
+      serde_json::from_str::<ImeComposition>(r#"{ "type": "update", "text": "にほ" }"#).unwrap(),
+      ImeComposition::Update {
+        text: "にほ".into()
+      }
+    );
+    assert_eq!(
+      serde_json::from_str::<ImeComposition>(r#"{ "type": "end", "text": "日本" }"#).unwrap(),
+      ImeComposition::End {
+        text: "日本".into()
+      }
+    );
+  }
+
+  #[test]
+  fn gnome_input_source() {
+    assert_eq!(
+      gnome_input_source_layout("xkb", "fr+azerty").id,
+      "xkb:fr+azerty"
+    );
+    assert_eq!(
+      gnome_input_source_layout("ibus", "mozc-jp").id,
+      "ibus:mozc-jp"
+    );
+  }
+}
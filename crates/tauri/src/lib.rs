@@ -36,6 +36,35 @@
 //! - **image-ico**: Adds support to parse `.ico` image, see [`Image`].
 //! - **image-png**: Adds support to parse `.png` image, see [`Image`].
 //! - **macos-proxy**: Adds support for [`WebviewBuilder::proxy_url`] on macOS. Requires macOS 14+.
+//! - **archive**: Enables the [`archive`] module to extract and create zip and tar.gz archives.
+//! - **audio**: Enables the [`audio`] module and the `core:audio` commands to list the audio capture and playback devices.
+//! - **auth**: Enables the [`auth`] module to prompt the user for OS-level authentication.
+//! - **bluetooth**: Enables the [`bluetooth`] module and the `core:bluetooth` commands to scan for Bluetooth Low Energy devices and access their GATT services.
+//! - **camera**: Enables the [`camera`] module to list the cameras and capture still frames to PNG.
+//! - **external-ipc**: Enables the [`ipc::external`] module to serve requests from other local processes over a Unix domain socket or named pipe.
+//! - **diagnostics**: Enables [`AppHandle::diagnostics_bundle`] to export a support bundle with the redacted config, window states and logs.
+//! - **font**: Enables the [`font`] module and the `core:font` commands to list the installed font families and install fonts.
+//! - **frontend-patches**: Enables the [`frontend_patches`] module to install signed frontend patches without updating the app.
+//! - **geolocation**: Enables the [`geolocation`] module to read the device position from the operating system location services.
+//! - **integrity**: Enables the [`integrity`] module and the `core:integrity` commands to hash files and verify their signatures.
+//! - **keyboard-layout**: Enables [`AppHandle::keyboard_layout`] on Windows, it returns an unsupported error without it. The other platforms do not need it.
+//! - **kiosk**: Enables the suppression of the OS shortcuts of the `app > kiosk` configuration on Windows, the app fails to start without it when `suppressShortcuts` is set. The other platforms do not need it.
+//! - **localized-resources**: Enables reading the preferred languages of [`path::PathResolver::resolve_localized_resource`] from the Windows settings, it returns an unsupported error on Windows without it. The other platforms do not need it.
+//! - **localhost-server**: Enables the [`localhost`] module, a shared HTTP server on the loopback interface where plugins register routes, and [`ipc::StreamUrl`].
+//! - **metrics**: Enables the [`metrics`] module to export command, event and window metrics in the OpenMetrics format.
+//! - **network-status**: Enables [`network::status`] and its change notifications on Windows, [`AppHandle::watch_network_status`] returns an unsupported error without it. The other platforms do not need it.
+//! - **panel-windows**: Enables the native panel behavior of the `panel` window type on Windows, where panels keep their taskbar entry and a warning is logged without it. The other platforms do not need it.
+//! - **printer**: Enables the [`printer`] module and the `core:printer` commands to list the printers and submit print jobs without the print dialog.
+//! - **process-limits**: Enables the memory, CPU time and CPU rate limits of [`process::spawn_limited`] and [`process::limit_process`] on Windows, they return an unsupported error without it. The other platforms do not need it.
+//! - **recent-documents**: Enables [`App::add_recent_document`] and [`App::clear_recent_documents`] to manage the recent documents of the operating system.
+//! - **resource-integrity**: Records the SHA-256 hashes of the `bundle > resources` files at build time for [`path::PathResolver::resolve_verified_resource`].
+//! - **safe-storage**: Enables the [`safe_storage`] module to encrypt local data with a key protected by the operating system user account.
+//! - **serial**: Enables the [`serial`] module and the `core:serial` commands to enumerate and access serial ports.
+//! - **shutdown-signals** *(enabled by default)*: Runs the [`Builder::on_shutdown`] handlers on console close and session end on Windows. The other platforms do not need it.
+//! - **synthetic-input**: Enables [`Window::inject_input`] to dispatch synthetic keyboard and mouse events to the webviews, for kiosk self-tests and automation. The events are untrusted DOM events, not native input.
+//! - **system-proxy**: Enables reading [`network::system_proxy`] from the Windows settings, which returns an unsupported error on Windows without it. The other platforms do not need it.
+//! - **theme-icons**: Enables the detection of the system theme used to pick the light or dark tray and window icons on Windows, where the icons follow the theme of the windows and a warning is logged without it. The other platforms do not need it.
+//! - **window-animations**: Enables the native window show and hide animations on Windows, which show and hide the window directly and log a warning without it. The other platforms do not need it.
 //! - **specta**: Add support for [`specta::specta`](https://docs.rs/specta/%5E2.0.0-rc.9/specta/attr.specta.html) with Tauri arguments such as [`State`](crate::State), [`Window`](crate::Window) and [`AppHandle`](crate::AppHandle)
 //!
 //! ## Cargo allowlist features
@@ -80,15 +109,67 @@ use tauri_utils::assets::AssetsIter;
 pub use url::Url;
 
 pub(crate) mod app;
+#[cfg(feature = "archive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "archive")))]
+pub mod archive;
 pub mod async_runtime;
+#[cfg(feature = "audio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "audio")))]
+pub mod audio;
+#[cfg(feature = "auth")]
+#[cfg_attr(docsrs, doc(cfg(feature = "auth")))]
+pub mod auth;
+#[cfg(feature = "bluetooth")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bluetooth")))]
+pub mod bluetooth;
+#[cfg(feature = "camera")]
+#[cfg_attr(docsrs, doc(cfg(feature = "camera")))]
+pub mod camera;
+pub mod cli;
+#[cfg(feature = "diagnostics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "diagnostics")))]
+pub mod diagnostics;
 mod error;
 mod event;
+pub mod feature_flags;
+#[cfg(feature = "font")]
+#[cfg_attr(docsrs, doc(cfg(feature = "font")))]
+pub mod font;
+#[cfg(all(desktop, feature = "frontend-patches"))]
+#[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "frontend-patches"))))]
+pub mod frontend_patches;
+#[cfg(feature = "geolocation")]
+#[cfg_attr(docsrs, doc(cfg(feature = "geolocation")))]
+pub mod geolocation;
+#[cfg(feature = "integrity")]
+#[cfg_attr(docsrs, doc(cfg(feature = "integrity")))]
+pub mod integrity;
 pub mod ipc;
+pub mod keyboard;
+#[cfg(feature = "localhost-server")]
+#[cfg_attr(docsrs, doc(cfg(feature = "localhost-server")))]
+pub mod localhost;
 mod manager;
+#[cfg(feature = "metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+pub mod metrics;
+pub mod panic;
 mod pattern;
+mod persistent_state;
 pub mod plugin;
+#[cfg(feature = "printer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "printer")))]
+pub mod printer;
 pub(crate) mod protocol;
+#[cfg(all(desktop, feature = "recent-documents"))]
+mod recent_documents;
 mod resources;
+#[cfg(feature = "safe-storage")]
+#[cfg_attr(docsrs, doc(cfg(feature = "safe-storage")))]
+pub mod safe_storage;
+#[cfg(feature = "serial")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serial")))]
+pub mod serial;
 mod vibrancy;
 pub mod webview;
 pub mod window;
@@ -99,9 +180,11 @@ mod ios;
 #[cfg(desktop)]
 #[cfg_attr(docsrs, doc(cfg(desktop)))]
 pub mod menu;
+pub mod network;
 /// Path APIs.
 pub mod path;
 pub mod process;
+pub mod scheduler;
 /// The allowlist scopes.
 pub mod scope;
 mod state;
@@ -210,13 +293,17 @@ pub use runtime::ActivationPolicy;
 #[cfg(target_os = "macos")]
 pub use self::utils::TitleBarStyle;
 
+#[cfg(desktop)]
+#[cfg_attr(docsrs, doc(cfg(desktop)))]
+pub use self::app::GpuFailure;
 pub use self::event::{Event, EventId, EventTarget};
 pub use {
   self::app::{
-    App, AppHandle, AssetResolver, Builder, CloseRequestApi, RunEvent, UriSchemeContext,
-    UriSchemeResponder, WebviewEvent, WindowEvent,
+    App, AppHandle, AssetResolver, Builder, CloseRequestApi, RunEvent, ShutdownReason,
+    UriSchemeContext, UriSchemeResponder, WebviewEvent, WindowEvent, WindowEventDelivery,
   },
   self::manager::Asset,
+  self::persistent_state::{PersistentState, PersistentStateBuilder},
   self::runtime::{
     dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Pixel, Position, Size},
     webview::WebviewAttributes,
@@ -279,6 +366,7 @@ pub fn log_stdout() {
 
 /// The user event type.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum EventLoopMessage {
   /// An event from a menu item, could be on the window menu bar, application menu bar (on macOS) or tray icon menu.
   #[cfg(desktop)]
@@ -287,6 +375,26 @@ pub enum EventLoopMessage {
   #[cfg(all(desktop, feature = "tray-icon"))]
   #[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "tray-icon"))))]
   TrayIconEvent(tray::TrayIconEvent),
+  /// The kiosk mode watchdog found an unresponsive webview.
+  #[cfg(desktop)]
+  #[cfg_attr(docsrs, doc(cfg(desktop)))]
+  WebviewUnresponsive {
+    /// The webview label.
+    label: String,
+    /// Whether the webview is being reloaded.
+    reloading: bool,
+  },
+  /// A webview cannot render with the GPU.
+  #[cfg(desktop)]
+  #[cfg_attr(docsrs, doc(cfg(desktop)))]
+  GpuFailure {
+    /// The webview label.
+    label: String,
+    /// The detected failure.
+    failure: app::GpuFailure,
+    /// Whether the webviews render without the GPU from the next launch.
+    fallback: bool,
+  },
 }
 
 /// The webview runtime interface. A wrapper around [`runtime::Runtime`] with the proper user event type associated.
@@ -381,14 +489,19 @@ pub struct Context<R: Runtime> {
   pub(crate) config_parent: Option<std::path::PathBuf>,
   /// Asset provider.
   pub assets: Box<dyn Assets<R>>,
+  pub(crate) window_assets: HashMap<String, Box<dyn Assets<R>>>,
   pub(crate) default_window_icon: Option<image::Image<'static>>,
   pub(crate) app_icon: Option<Vec<u8>>,
   #[cfg(all(desktop, feature = "tray-icon"))]
   pub(crate) tray_icon: Option<image::Image<'static>>,
+  #[cfg(all(desktop, feature = "tray-icon"))]
+  pub(crate) tray_icon_pair: Option<(image::Image<'static>, image::Image<'static>)>,
   pub(crate) package_info: PackageInfo,
   pub(crate) pattern: Pattern,
   pub(crate) runtime_authority: RuntimeAuthority,
   pub(crate) plugin_global_api_scripts: Option<&'static [&'static str]>,
+  pub(crate) resource_hashes: HashMap<String, String>,
+  pub(crate) attributions: Vec<utils::attributions::Attribution>,
 }
 
 impl<R: Runtime> fmt::Debug for Context<R> {
@@ -402,7 +515,8 @@ impl<R: Runtime> fmt::Debug for Context<R> {
       .field("plugin_global_api_scripts", &self.plugin_global_api_scripts);
 
     #[cfg(all(desktop, feature = "tray-icon"))]
-    d.field("tray_icon", &self.tray_icon);
+    d.field("tray_icon", &self.tray_icon)
+      .field("tray_icon_pair", &self.tray_icon_pair);
 
     d.finish()
   }
@@ -433,6 +547,36 @@ impl<R: Runtime> Context<R> {
     std::mem::replace(&mut self.assets, assets)
   }
 
+  /// Sets the [`Assets`] served to the webviews of the window with the given label instead of [`Self::assets`],
+  /// returning the previous value if any.
+  ///
+  /// The generated context sets them for the windows configured with their own `frontendDist`.
+  #[inline(always)]
+  pub fn set_window_assets(
+    &mut self,
+    label: impl Into<String>,
+    assets: Box<dyn Assets<R>>,
+  ) -> Option<Box<dyn Assets<R>>> {
+    self.window_assets.insert(label.into(), assets)
+  }
+
+  /// Sets the SHA-256 hashes of the bundled resources, keyed by their path in the resource directory,
+  /// used by [`path::PathResolver::resolve_verified_resource`].
+  ///
+  /// The generated context sets them for the files configured in `bundle > resources`.
+  #[inline(always)]
+  pub fn set_resource_hashes(&mut self, hashes: HashMap<String, String>) {
+    self.resource_hashes = hashes;
+  }
+
+  /// Sets the third-party license attributions of the app, returned by [`AppHandle::attributions`].
+  ///
+  /// The generated context sets them when the build script enables `tauri_build::Attributes::attributions`.
+  #[inline(always)]
+  pub fn set_attributions(&mut self, attributions: Vec<utils::attributions::Attribution>) {
+    self.attributions = attributions;
+  }
+
   /// The default window icon Tauri should use when creating windows.
   #[inline(always)]
   pub fn default_window_icon(&self) -> Option<&image::Image<'_>> {
@@ -461,6 +605,30 @@ impl<R: Runtime> Context<R> {
     self.tray_icon = icon;
   }
 
+  /// The icons to use on the tray icon when the system uses a light and a dark theme, respectively.
+  #[cfg(all(desktop, feature = "tray-icon"))]
+  #[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "tray-icon"))))]
+  #[inline(always)]
+  pub fn tray_icon_pair(&self) -> Option<(&image::Image<'_>, &image::Image<'_>)> {
+    self
+      .tray_icon_pair
+      .as_ref()
+      .map(|(light, dark)| (light, dark))
+  }
+
+  /// Set the icons to use on the tray icon when the system uses a light and a dark theme, respectively.
+  ///
+  /// See [`tray::TrayIcon::set_icon_pair`].
+  #[cfg(all(desktop, feature = "tray-icon"))]
+  #[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "tray-icon"))))]
+  #[inline(always)]
+  pub fn set_tray_icon_pair(
+    &mut self,
+    icons: Option<(image::Image<'static>, image::Image<'static>)>,
+  ) {
+    self.tray_icon_pair = icons;
+  }
+
   /// Package information.
   #[inline(always)]
   pub fn package_info(&self) -> &PackageInfo {
@@ -508,14 +676,19 @@ impl<R: Runtime> Context<R> {
       #[cfg(dev)]
       config_parent: None,
       assets,
+      window_assets: HashMap::new(),
       default_window_icon,
       app_icon,
       #[cfg(all(desktop, feature = "tray-icon"))]
       tray_icon: None,
+      #[cfg(all(desktop, feature = "tray-icon"))]
+      tray_icon_pair: None,
       package_info,
       pattern,
       runtime_authority,
       plugin_global_api_scripts,
+      resource_hashes: HashMap::new(),
+      attributions: Vec::new(),
     }
   }
 
@@ -710,6 +883,35 @@ pub trait Manager<R: Runtime>: sealed::ManagerBase<R> {
     self.manager().state().set(state)
   }
 
+  /// Add state to the state managed by the application, created by `init` the first time it is accessed.
+  ///
+  /// Use this for state that is expensive to create, such as a database connection,
+  /// so it does not delay startup. The initializer runs on the thread that first accesses the state
+  /// and may access other managed state.
+  ///
+  /// Returns `false` if state of type `T` is already being managed.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::Manager;
+  ///
+  /// struct Index(Vec<String>);
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     app.manage_lazy(|| Index(vec!["expensive".into()]));
+  ///     Ok(())
+  ///   });
+  /// ```
+  fn manage_lazy<T, F>(&self, init: F) -> bool
+  where
+    T: Send + Sync + 'static,
+    F: FnOnce() -> T + Send + 'static,
+  {
+    self.manager().state().set_lazy(init)
+  }
+
   /// Removes the state managed by the application for T. Returns the state if it was actually removed.
   fn unmanage<T>(&self) -> Option<T>
   where
@@ -759,11 +961,64 @@ pub trait Manager<R: Runtime>: sealed::ManagerBase<R> {
     self.state::<Scopes>().inner().asset_protocol.clone()
   }
 
+  /// The resolver of the assets embedded in the app, such as the frontend files.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::Manager;
+  ///
+  /// #[tauri::command]
+  /// fn template(app: tauri::AppHandle) -> Option<String> {
+  ///   let asset = app.asset_resolver().get("/templates/report.html".into())?;
+  ///   String::from_utf8(asset.bytes).ok()
+  /// }
+  /// ```
+  fn asset_resolver(&self) -> AssetResolver<R> {
+    AssetResolver {
+      manager: self.manager_owned(),
+    }
+  }
+
   /// The path resolver.
   fn path(&self) -> &crate::path::PathResolver<R> {
     self.state::<crate::path::PathResolver<R>>().inner()
   }
 
+  /// The background job scheduler.
+  fn scheduler(&self) -> &crate::scheduler::Scheduler<R> {
+    self.state::<crate::scheduler::Scheduler<R>>().inner()
+  }
+
+  /// The shared localhost server where plugins register HTTP routes.
+  #[cfg(feature = "localhost-server")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "localhost-server")))]
+  fn localhost_server(&self) -> &crate::localhost::LocalhostServer<R> {
+    self.state::<crate::localhost::LocalhostServer<R>>().inner()
+  }
+
+  /// The Bluetooth Low Energy central of the app.
+  #[cfg(feature = "bluetooth")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "bluetooth")))]
+  fn bluetooth(&self) -> &crate::bluetooth::Bluetooth<R> {
+    self.state::<crate::bluetooth::Bluetooth<R>>().inner()
+  }
+
+  /// The metrics of the app.
+  #[cfg(feature = "metrics")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+  fn metrics(&self) -> &crate::metrics::Metrics<R> {
+    self.state::<crate::metrics::Metrics<R>>().inner()
+  }
+
+  /// The pool of pre-warmed webview windows.
+  ///
+  /// Its size is configured with [`Builder::webview_pool`].
+  #[cfg(desktop)]
+  fn webview_pool(&self) -> &crate::webview::WebviewPool<R> {
+    self.state::<crate::webview::WebviewPool<R>>().inner()
+  }
+
   /// Adds a capability to the app.
   ///
   /// Note that by default every capability file in the `src-tauri/capabilities` folder
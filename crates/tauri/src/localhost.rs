@@ -0,0 +1,774 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Shared HTTP server on the loopback interface for plugins.
+//!
+//! Plugins that need a plain HTTP endpoint, such as an OAuth redirect target or a media server for a native player,
+//! register routes on this server instead of each binding their own port.
+//! The server listens on a random port of `127.0.0.1` and is only started once the first route is registered.
+//!
+//! Every route is served under the name of the plugin that registered it, e.g. `/oauth/callback`,
+//! and every request must carry the server token, either as a `token` query parameter
+//! or as an `Authorization: Bearer <token>` header. [`LocalhostServer::url`] returns URLs including the token.
+//!
+//! Routes opened to other applications, such as the redirect target of an external browser,
+//! are registered with [`LocalhostServer::public_route`]: they are served without the token
+//! and must authenticate their requests themselves.
+//!
+//! The server answers a single request per connection and does not support chunked request bodies.
+//! Streamed response bodies, such as the ones of [`crate::ipc::StreamUrl`], are sent with the chunked transfer encoding.
+//! Connections that do not send their request within [`READ_TIMEOUT`] are closed.
+
+use std::{
+  borrow::Cow,
+  net::{Ipv4Addr, SocketAddr},
+  pin::Pin,
+  sync::{Arc, Mutex, RwLock},
+  time::Duration,
+};
+
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use http::{header, Method, StatusCode};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use url::Url;
+
+use crate::{async_runtime, AppHandle, Runtime};
+
+/// Request heads bigger than this are rejected.
+pub const MAX_HEAD_SIZE: usize = 16 * 1024;
+
+/// Request bodies bigger than this are rejected.
+pub const MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// The time a connection has to send its whole request.
+pub const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The handler of a route, see [`LocalhostServer::route`].
+pub type RouteHandler<R> =
+  dyn Fn(&AppHandle<R>, http::Request<Vec<u8>>) -> http::Response<Cow<'static, [u8]>> + Send + Sync;
+
+/// A response body streamed by the server, ended early if the stream fails.
+pub(crate) type BodyStream = Pin<Box<dyn Stream<Item = Result<Bytes, String>> + Send>>;
+
+/// The body of a response of the server.
+pub(crate) enum Body {
+  Full(Cow<'static, [u8]>),
+  /// Sent with the chunked transfer encoding.
+  Stream(BodyStream),
+}
+
+/// The handler of a route whose response body may be streamed.
+pub(crate) type StreamingRouteHandler<R> =
+  dyn Fn(&AppHandle<R>, http::Request<Vec<u8>>) -> http::Response<Body> + Send + Sync;
+
+struct Route<R: Runtime> {
+  method: Method,
+  path: String,
+  /// Served without the token.
+  public: bool,
+  handler: Arc<StreamingRouteHandler<R>>,
+}
+
+impl<R: Runtime> Route<R> {
+  /// Whether the route matches the path, a route ending with `/*` matching every path under it.
+  fn matches(&self, path: &str) -> bool {
+    match self.path.strip_suffix('*') {
+      Some(prefix) => path.starts_with(prefix) || path == &prefix[..prefix.len() - 1],
+      None => path == self.path,
+    }
+  }
+}
+
+/// A route registered with [`LocalhostServer::route`].
+pub struct RegisteredRoute<R: Runtime> {
+  app: AppHandle<R>,
+  method: Method,
+  path: String,
+  handler: Arc<StreamingRouteHandler<R>>,
+}
+
+impl<R: Runtime> RegisteredRoute<R> {
+  /// Removes the route, stopping the server once no route is left.
+  ///
+  /// Does nothing if the route was replaced by another handler for the same method and path.
+  pub fn remove(self) {
+    let server = crate::Manager::localhost_server(&self.app);
+    server.retain_routes(|route| {
+      route.method != self.method
+        || route.path != self.path
+        || !Arc::ptr_eq(&route.handler, &self.handler)
+    });
+  }
+}
+
+struct Running {
+  port: u16,
+  task: async_runtime::JoinHandle<()>,
+}
+
+/// The shared localhost server, accessed with [`Manager::localhost_server`](crate::Manager::localhost_server).
+///
+/// See the [module documentation](self).
+pub struct LocalhostServer<R: Runtime> {
+  app: AppHandle<R>,
+  token: String,
+  routes: Arc<RwLock<Vec<Route<R>>>>,
+  running: Mutex<Option<Running>>,
+}
+
+impl<R: Runtime> Drop for LocalhostServer<R> {
+  fn drop(&mut self) {
+    if let Some(running) = self.running.lock().unwrap().take() {
+      running.task.abort();
+    }
+  }
+}
+
+impl<R: Runtime> LocalhostServer<R> {
+  pub(crate) fn new(app: AppHandle<R>) -> crate::Result<Self> {
+    let mut token = [0u8; 32];
+    getrandom::getrandom(&mut token)?;
+    Ok(Self {
+      app,
+      token: token.iter().map(|b| format!("{b:02x}")).collect(),
+      routes: Default::default(),
+      running: Default::default(),
+    })
+  }
+
+  /// Registers a route served at `/<plugin><path>`, starting the server if it is not running yet.
+  ///
+  /// A `path` ending with `/*` matches every path under it. Registering the same method and path again replaces the handler.
+  /// The returned route can be removed with [`RegisteredRoute::remove`].
+  ///
+  /// Plugins usually register their routes with [`crate::plugin::Builder::localhost_route`] instead.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::{http::Method, Manager};
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     app.localhost_server().route("player", Method::GET, "/playlist.m3u8", |_app, _request| {
+  ///       tauri::http::Response::builder()
+  ///         .header(tauri::http::header::CONTENT_TYPE, "application/vnd.apple.mpegurl")
+  ///         .body("#EXTM3U".as_bytes().into())
+  ///         .unwrap()
+  ///     })?;
+  ///     // the URL includes the token, only give it to the native player
+  ///     let playlist = app.localhost_server().url("player", "/playlist.m3u8").unwrap();
+  ///     Ok(())
+  ///   });
+  /// ```
+  pub fn route<H>(
+    &self,
+    plugin: &str,
+    method: Method,
+    path: &str,
+    handler: H,
+  ) -> crate::Result<RegisteredRoute<R>>
+  where
+    H: Fn(&AppHandle<R>, http::Request<Vec<u8>>) -> http::Response<Cow<'static, [u8]>>
+      + Send
+      + Sync
+      + 'static,
+  {
+    self.register(plugin, method, path, false, handler)
+  }
+
+  /// Registers a route served at `/<plugin><path>` without the token, starting the server if it is not running yet.
+  ///
+  /// Any local application or web page can reach a public route, so its handler must authenticate the requests itself,
+  /// for instance with the `state` nonce of an OAuth authorization request. [`Self::url`] returns its URL without the token.
+  /// See [`Self::route`] for the paths.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::{http::{Method, Response, StatusCode}, Manager};
+  ///
+  /// # fn random_nonce() -> String { unimplemented!() }
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     // sent in the authorization request and returned by the authorization server
+  ///     let state = random_nonce();
+  ///     app.localhost_server().public_route("oauth", Method::GET, "/callback", move |_app, request| {
+  ///       let query = request.uri().query().unwrap_or_default();
+  ///       let mut code = None;
+  ///       let mut returned_state = None;
+  ///       for (name, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+  ///         match name {
+  ///           "code" => code = Some(value.to_string()),
+  ///           "state" => returned_state = Some(value),
+  ///           _ => (),
+  ///         }
+  ///       }
+  ///       let Some(code) = code.filter(|_| returned_state == Some(state.as_str())) else {
+  ///         return Response::builder()
+  ///           .status(StatusCode::FORBIDDEN)
+  ///           .body(Vec::new().into())
+  ///           .unwrap();
+  ///       };
+  ///       // exchange the code
+  ///       Response::builder()
+  ///         .header(tauri::http::header::CONTENT_TYPE, "text/html")
+  ///         .body("You can close this page.".as_bytes().into())
+  ///         .unwrap()
+  ///     })?;
+  ///     // does not include the token, so it can be registered with the authorization server
+  ///     let redirect_uri = app.localhost_server().url("oauth", "/callback").unwrap();
+  ///     Ok(())
+  ///   });
+  /// ```
+  pub fn public_route<H>(
+    &self,
+    plugin: &str,
+    method: Method,
+    path: &str,
+    handler: H,
+  ) -> crate::Result<RegisteredRoute<R>>
+  where
+    H: Fn(&AppHandle<R>, http::Request<Vec<u8>>) -> http::Response<Cow<'static, [u8]>>
+      + Send
+      + Sync
+      + 'static,
+  {
+    self.register(plugin, method, path, true, handler)
+  }
+
+  fn register<H>(
+    &self,
+    plugin: &str,
+    method: Method,
+    path: &str,
+    public: bool,
+    handler: H,
+  ) -> crate::Result<RegisteredRoute<R>>
+  where
+    H: Fn(&AppHandle<R>, http::Request<Vec<u8>>) -> http::Response<Cow<'static, [u8]>>
+      + Send
+      + Sync
+      + 'static,
+  {
+    let handler: Arc<StreamingRouteHandler<R>> =
+      Arc::new(move |app, request| handler(app, request).map(Body::Full));
+    self.insert_route(plugin, method.clone(), path, public, handler.clone())?;
+    Ok(RegisteredRoute {
+      app: self.app.clone(),
+      method,
+      path: route_path(plugin, path),
+      handler,
+    })
+  }
+
+  pub(crate) fn add_route(
+    &self,
+    plugin: &str,
+    method: Method,
+    path: &str,
+    handler: Arc<RouteHandler<R>>,
+  ) -> crate::Result<()> {
+    self.insert_route(
+      plugin,
+      method,
+      path,
+      false,
+      Arc::new(move |app, request| handler(app, request).map(Body::Full)),
+    )
+  }
+
+  pub(crate) fn insert_route(
+    &self,
+    plugin: &str,
+    method: Method,
+    path: &str,
+    public: bool,
+    handler: Arc<StreamingRouteHandler<R>>,
+  ) -> crate::Result<()> {
+    let path = route_path(plugin, path);
+    {
+      let mut routes = self.routes.write().unwrap();
+      routes.retain(|route| route.method != method || route.path != path);
+      routes.push(Route {
+        method,
+        path,
+        public,
+        handler,
+      });
+    }
+    self.start()
+  }
+
+  /// Removes the routes registered by the plugin, stopping the server once no route is left.
+  ///
+  /// The routes of a plugin are removed when it is removed with [`AppHandle::remove_plugin`].
+  pub fn remove_routes(&self, plugin: &str) {
+    let prefix = route_path(plugin, "/");
+    self.retain_routes(|route| !route.path.starts_with(&prefix));
+  }
+
+  fn retain_routes(&self, f: impl FnMut(&Route<R>) -> bool) {
+    let mut routes = self.routes.write().unwrap();
+    routes.retain(f);
+    if routes.is_empty() {
+      if let Some(running) = self.running.lock().unwrap().take() {
+        running.task.abort();
+      }
+    }
+  }
+
+  /// The port of the server, or `None` if no route is registered.
+  pub fn port(&self) -> Option<u16> {
+    self.running.lock().unwrap().as_ref().map(|r| r.port)
+  }
+
+  /// The token every request must carry.
+  pub fn token(&self) -> &str {
+    &self.token
+  }
+
+  /// The URL of `/<plugin><path>`, or `None` if no route is registered.
+  ///
+  /// The URL includes the token unless `path` is only served by [public routes](Self::public_route).
+  pub fn url(&self, plugin: &str, path: &str) -> Option<Url> {
+    let port = self.port()?;
+    let mut url = Url::parse(&format!("http://127.0.0.1:{port}")).ok()?;
+    let path = route_path(plugin, path);
+    let public = {
+      let routes = self.routes.read().unwrap();
+      let mut matching = routes
+        .iter()
+        .filter(|route| route.matches(&path))
+        .peekable();
+      matching.peek().is_some() && matching.all(|route| route.public)
+    };
+    url.set_path(&path);
+    if !public {
+      url.query_pairs_mut().append_pair("token", &self.token);
+    }
+    Some(url)
+  }
+
+  fn start(&self) -> crate::Result<()> {
+    let mut running = self.running.lock().unwrap();
+    if running.is_some() {
+      return Ok(());
+    }
+
+    let listener = std::net::TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)))?;
+    listener.set_nonblocking(true)?;
+    let port = listener.local_addr()?.port();
+    let listener = {
+      let handle = async_runtime::handle();
+      let _guard = handle.inner().enter();
+      tokio::net::TcpListener::from_std(listener)?
+    };
+
+    let app = self.app.clone();
+    let routes = self.routes.clone();
+    let token = self.token.clone();
+    let task = async_runtime::spawn(async move {
+      loop {
+        match listener.accept().await {
+          Ok((stream, _)) => {
+            let app = app.clone();
+            let routes = routes.clone();
+            let token = token.clone();
+            async_runtime::spawn(async move {
+              if let Err(e) = handle_connection(stream, &app, &routes, &token, port).await {
+                log::debug!("localhost server connection closed: {e}");
+              }
+            });
+          }
+          Err(e) => {
+            log::error!("failed to accept localhost server connection: {e}");
+            break;
+          }
+        }
+      }
+    });
+
+    running.replace(Running { port, task });
+    Ok(())
+  }
+}
+
+fn route_path(plugin: &str, path: &str) -> String {
+  format!("/{plugin}/{}", path.trim_start_matches('/'))
+}
+
+async fn handle_connection<R: Runtime, S: AsyncRead + AsyncWrite + Unpin>(
+  mut stream: S,
+  app: &AppHandle<R>,
+  routes: &RwLock<Vec<Route<R>>>,
+  token: &str,
+  port: u16,
+) -> std::io::Result<()> {
+  let request = tokio::time::timeout(READ_TIMEOUT, read_request(&mut stream))
+    .await
+    .unwrap_or(Err(StatusCode::REQUEST_TIMEOUT));
+  let response = match request {
+    Ok(request) => respond(app, routes, token, port, request).await,
+    Err(status) => error_response(status),
+  };
+  write_response(&mut stream, response).await
+}
+
+async fn respond<R: Runtime>(
+  app: &AppHandle<R>,
+  routes: &RwLock<Vec<Route<R>>>,
+  token: &str,
+  port: u16,
+  request: http::Request<Vec<u8>>,
+) -> http::Response<Body> {
+  // rejects the requests of web pages using a domain resolving to the loopback address
+  if !is_allowed_host(&request, port) {
+    return error_response(StatusCode::MISDIRECTED_REQUEST);
+  }
+  let authorized = has_token(&request, token);
+
+  let handler = {
+    let routes = routes.read().unwrap();
+    let path = request.uri().path();
+    // the requests without the token only see the public routes
+    let mut matching = routes
+      .iter()
+      .filter(|route| route.matches(path) && (authorized || route.public))
+      .peekable();
+    if matching.peek().is_none() {
+      return error_response(if authorized {
+        StatusCode::NOT_FOUND
+      } else {
+        StatusCode::UNAUTHORIZED
+      });
+    }
+    match matching.find(|route| route.method == request.method()) {
+      Some(route) => route.handler.clone(),
+      None => return error_response(StatusCode::METHOD_NOT_ALLOWED),
+    }
+  };
+
+  let app = app.clone();
+  async_runtime::spawn_blocking(move || handler(&app, request))
+    .await
+    .unwrap_or_else(|_| error_response(StatusCode::INTERNAL_SERVER_ERROR))
+}
+
+async fn read_request<S: AsyncRead + Unpin>(
+  stream: &mut S,
+) -> Result<http::Request<Vec<u8>>, StatusCode> {
+  let mut buffer = Vec::new();
+  let head_len = loop {
+    if let Some(position) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+      break position + 4;
+    }
+    if buffer.len() > MAX_HEAD_SIZE {
+      return Err(StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE);
+    }
+    let mut chunk = [0u8; 4096];
+    let read = stream
+      .read(&mut chunk)
+      .await
+      .map_err(|_| StatusCode::BAD_REQUEST)?;
+    if read == 0 {
+      return Err(StatusCode::BAD_REQUEST);
+    }
+    buffer.extend_from_slice(&chunk[..read]);
+  };
+
+  let request = parse_head(&buffer[..head_len])?;
+  if request.headers().contains_key(header::TRANSFER_ENCODING) {
+    return Err(StatusCode::NOT_IMPLEMENTED);
+  }
+  let content_length = match request.headers().get(header::CONTENT_LENGTH) {
+    Some(value) => value
+      .to_str()
+      .ok()
+      .and_then(|value| value.parse::<usize>().ok())
+      .ok_or(StatusCode::BAD_REQUEST)?,
+    None => 0,
+  };
+  if content_length > MAX_BODY_SIZE {
+    return Err(StatusCode::PAYLOAD_TOO_LARGE);
+  }
+
+  let mut body = buffer.split_off(head_len);
+  if body.len() < content_length {
+    let read = body.len();
+    body.resize(content_length, 0);
+    stream
+      .read_exact(&mut body[read..])
+      .await
+      .map_err(|_| StatusCode::BAD_REQUEST)?;
+  }
+  body.truncate(content_length);
+
+  let (parts, ()) = request.into_parts();
+  Ok(http::Request::from_parts(parts, body))
+}
+
+/// Parses the request line and the headers of a request.
+fn parse_head(head: &[u8]) -> Result<http::Request<()>, StatusCode> {
+  let head = std::str::from_utf8(head).map_err(|_| StatusCode::BAD_REQUEST)?;
+  let mut lines = head.split("\r\n");
+
+  let mut request_line = lines.next().unwrap_or_default().split(' ');
+  let (Some(method), Some(target), Some(version), None) = (
+    request_line.next(),
+    request_line.next(),
+    request_line.next(),
+    request_line.next(),
+  ) else {
+    return Err(StatusCode::BAD_REQUEST);
+  };
+  if version != "HTTP/1.1" && version != "HTTP/1.0" {
+    return Err(StatusCode::HTTP_VERSION_NOT_SUPPORTED);
+  }
+  if !target.starts_with('/') {
+    return Err(StatusCode::BAD_REQUEST);
+  }
+
+  let mut builder = http::Request::builder().method(method).uri(target);
+  for line in lines.take_while(|line| !line.is_empty()) {
+    let (name, value) = line.split_once(':').ok_or(StatusCode::BAD_REQUEST)?;
+    builder = builder.header(name.trim(), value.trim());
+  }
+  builder.body(()).map_err(|_| StatusCode::BAD_REQUEST)
+}
+
+fn is_allowed_host<T>(request: &http::Request<T>, port: u16) -> bool {
+  let Some(host) = request
+    .headers()
+    .get(header::HOST)
+    .and_then(|host| host.to_str().ok())
+  else {
+    return false;
+  };
+  host == format!("127.0.0.1:{port}") || host == format!("localhost:{port}")
+}
+
+fn has_token<T>(request: &http::Request<T>, token: &str) -> bool {
+  let from_header = request
+    .headers()
+    .get(header::AUTHORIZATION)
+    .and_then(|value| value.to_str().ok())
+    .and_then(|value| value.strip_prefix("Bearer "))
+    .map(|value| value.trim().to_string());
+  let from_query = || {
+    request.uri().query().and_then(|query| {
+      url::form_urlencoded::parse(query.as_bytes())
+        .find(|(name, _)| name == "token")
+        .map(|(_, value)| value.into_owned())
+    })
+  };
+  from_header
+    .or_else(from_query)
+    .is_some_and(|value| constant_time_eq(value.as_bytes(), token.as_bytes()))
+}
+
+fn error_response(status: StatusCode) -> http::Response<Body> {
+  let mut response = http::Response::new(Body::Full(Cow::Owned(
+    status
+      .canonical_reason()
+      .unwrap_or_default()
+      .as_bytes()
+      .to_vec(),
+  )));
+  *response.status_mut() = status;
+  response.headers_mut().insert(
+    header::CONTENT_TYPE,
+    header::HeaderValue::from_static("text/plain"),
+  );
+  response
+}
+
+async fn write_response<S: AsyncWrite + Unpin>(
+  stream: &mut S,
+  response: http::Response<Body>,
+) -> std::io::Result<()> {
+  let (parts, body) = response.into_parts();
+  let mut head = format!(
+    "HTTP/1.1 {} {}\r\n",
+    parts.status.as_u16(),
+    parts.status.canonical_reason().unwrap_or_default()
+  )
+  .into_bytes();
+  for (name, value) in parts.headers.iter() {
+    if name == header::CONTENT_LENGTH
+      || name == header::CONNECTION
+      || name == header::TRANSFER_ENCODING
+    {
+      continue;
+    }
+    head.extend_from_slice(name.as_str().as_bytes());
+    head.extend_from_slice(b": ");
+    head.extend_from_slice(value.as_bytes());
+    head.extend_from_slice(b"\r\n");
+  }
+
+  match body {
+    Body::Full(body) => {
+      head.extend_from_slice(
+        format!(
+          "content-length: {}\r\nconnection: close\r\n\r\n",
+          body.len()
+        )
+        .as_bytes(),
+      );
+      stream.write_all(&head).await?;
+      stream.write_all(&body).await?;
+    }
+    Body::Stream(mut body) => {
+      head.extend_from_slice(b"transfer-encoding: chunked\r\nconnection: close\r\n\r\n");
+      stream.write_all(&head).await?;
+      while let Some(chunk) = body.next().await {
+        // the status is already sent, the connection is closed without the last chunk
+        // so the client sees an incomplete body instead of a truncated one
+        let chunk = chunk.map_err(std::io::Error::other)?;
+        if chunk.is_empty() {
+          continue;
+        }
+        stream
+          .write_all(format!("{:x}\r\n", chunk.len()).as_bytes())
+          .await?;
+        stream.write_all(&chunk).await?;
+        stream.write_all(b"\r\n").await?;
+        stream.flush().await?;
+      }
+      stream.write_all(b"0\r\n\r\n").await?;
+    }
+  }
+  stream.flush().await?;
+  stream.shutdown().await
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+  a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_the_request_head() {
+    let request = parse_head(
+      b"POST /oauth/callback?code=abc HTTP/1.1\r\nHost: 127.0.0.1:4000\r\nContent-Length: 2\r\n\r\n",
+    )
+    .unwrap();
+    assert_eq!(request.method(), Method::POST);
+    assert_eq!(request.uri().path(), "/oauth/callback");
+    assert_eq!(request.uri().query(), Some("code=abc"));
+    assert_eq!(request.headers()[header::CONTENT_LENGTH], "2");
+    assert!(is_allowed_host(&request, 4000));
+    assert!(!is_allowed_host(&request, 4001));
+
+    assert_eq!(
+      parse_head(b"GET http://example.com/ HTTP/1.1\r\n\r\n").unwrap_err(),
+      StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+      parse_head(b"GET / HTTP/2\r\n\r\n").unwrap_err(),
+      StatusCode::HTTP_VERSION_NOT_SUPPORTED
+    );
+  }
+
+  #[test]
+  fn requires_the_token() {
+    let request = |uri: &str, authorization: Option<&str>| {
+      let mut builder = http::Request::builder().uri(uri);
+      if let Some(authorization) = authorization {
+        builder = builder.header(header::AUTHORIZATION, authorization);
+      }
+      builder.body(()).unwrap()
+    };
+
+    assert!(has_token(
+      &request("/media/a.mp4?token=secret", None),
+      "secret"
+    ));
+    assert!(has_token(
+      &request("/media/a.mp4", Some("Bearer secret")),
+      "secret"
+    ));
+    assert!(!has_token(
+      &request("/media/a.mp4?token=other", None),
+      "secret"
+    ));
+    assert!(!has_token(&request("/media/a.mp4", None), "secret"));
+  }
+
+  #[test]
+  fn matches_routes() {
+    let route = |path: &str| Route::<crate::test::MockRuntime> {
+      method: Method::GET,
+      path: route_path("media", path),
+      public: false,
+      handler: Arc::new(|_, _| http::Response::new(Body::Full(Cow::Borrowed(&[][..])))),
+    };
+
+    assert!(route("/file").matches("/media/file"));
+    assert!(!route("/file").matches("/media/file/other"));
+    assert!(route("/files/*").matches("/media/files/a/b.mp4"));
+    assert!(route("/files/*").matches("/media/files"));
+    assert!(!route("/files/*").matches("/media/filesystem"));
+  }
+
+  #[test]
+  fn serves_public_routes_without_the_token() {
+    let app = crate::test::mock_app();
+    let route = |path: &str, public: bool| Route::<crate::test::MockRuntime> {
+      method: Method::GET,
+      path: route_path("oauth", path),
+      public,
+      handler: Arc::new(|_, _| http::Response::new(Body::Full(Cow::Borrowed(&[][..])))),
+    };
+    let routes = RwLock::new(vec![route("/callback", true), route("/session", false)]);
+    let status = |uri: &str| {
+      let request = http::Request::builder()
+        .uri(uri)
+        .header(header::HOST, "127.0.0.1:4000")
+        .body(Vec::new())
+        .unwrap();
+      async_runtime::block_on(respond(app.handle(), &routes, "secret", 4000, request)).status()
+    };
+
+    assert_eq!(status("/oauth/callback?state=nonce"), StatusCode::OK);
+    assert_eq!(status("/oauth/session"), StatusCode::UNAUTHORIZED);
+    assert_eq!(status("/oauth/session?token=secret"), StatusCode::OK);
+    // the routes are not revealed without the token
+    assert_eq!(status("/oauth/missing"), StatusCode::UNAUTHORIZED);
+    assert_eq!(status("/oauth/missing?token=secret"), StatusCode::NOT_FOUND);
+  }
+
+  #[test]
+  fn writes_chunked_bodies() {
+    let write = |chunks: Vec<Result<Bytes, String>>| {
+      let response =
+        http::Response::new(Body::Stream(Box::pin(futures_util::stream::iter(chunks))));
+      let mut written = Vec::new();
+      let result = async_runtime::block_on(write_response(&mut written, response));
+      (result, String::from_utf8(written).unwrap())
+    };
+
+    let (result, written) = write(vec![
+      Ok(Bytes::from_static(b"hello ")),
+      Ok(Bytes::new()),
+      Ok(Bytes::from_static(b"world")),
+    ]);
+    result.unwrap();
+    assert!(written.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(written.contains("transfer-encoding: chunked\r\n"));
+    assert!(written.ends_with("\r\n\r\n6\r\nhello \r\n5\r\nworld\r\n0\r\n\r\n"));
+
+    let (result, written) = write(vec![
+      Ok(Bytes::from_static(b"hello")),
+      Err("disk full".into()),
+    ]);
+    assert!(result.is_err());
+    assert!(written.ends_with("5\r\nhello\r\n"));
+  }
+}
@@ -31,6 +31,7 @@ use crate::{event::EmitArgs, resources::ResourceTable, Webview};
 
 #[cfg(desktop)]
 mod menu;
+mod startup;
 #[cfg(all(desktop, feature = "tray-icon"))]
 mod tray;
 pub mod webview;
@@ -99,6 +100,11 @@ pub(crate) fn set_csp<R: Runtime>(
     );
   }
 
+  #[cfg(feature = "localhost-server")]
+  if dangerous_disable_asset_csp_modification.can_modify("connect-src") {
+    crate::ipc::stream::allow_in_csp(&mut csp);
+  }
+
   csp
 }
 
@@ -200,6 +206,9 @@ pub struct AppManager<R: Runtime> {
   #[cfg(dev)]
   pub config_parent: Option<std::path::PathBuf>,
   pub assets: Box<dyn Assets<R>>,
+  /// Assets of the webviews with their own frontend.
+  /// The frontends of the windows configured with their own `frontendDist`, keyed by window label.
+  pub window_assets: HashMap<String, Box<dyn Assets<R>>>,
 
   pub app_icon: Option<Vec<u8>>,
 
@@ -211,11 +220,28 @@ pub struct AppManager<R: Runtime> {
   /// Global API scripts collected from plugins.
   pub plugin_global_api_scripts: Arc<Option<&'static [&'static str]>>,
 
+  /// SHA-256 hashes of the bundled resources.
+  #[cfg_attr(not(feature = "resource-integrity"), allow(dead_code))]
+  pub(crate) resource_hashes: HashMap<String, String>,
+
+  /// Third-party license attributions generated at build time.
+  pub(crate) attributions: Vec<crate::utils::attributions::Attribution>,
+
   /// Application Resources Table
   pub(crate) resources_table: Arc<Mutex<ResourceTable>>,
 
   /// Runtime-generated invoke key.
   pub(crate) invoke_key: String,
+
+  /// Durations of the startup steps.
+  pub(crate) startup: startup::StartupTimeline,
+
+  /// Latency samples of the resolved IPC commands.
+  #[cfg(feature = "tracing")]
+  pub(crate) command_latencies: crate::ipc::latency::CommandLatencies,
+
+  /// Responses of the commands declared with `#[command(cache = "...")]`.
+  pub(crate) command_cache: crate::ipc::cache::CommandCache,
 }
 
 impl<R: Runtime> fmt::Debug for AppManager<R> {
@@ -265,11 +291,7 @@ impl<R: Runtime> AppManager<R> {
 
     Self {
       runtime_authority: Mutex::new(context.runtime_authority),
-      window: window::WindowManager {
-        windows: Mutex::default(),
-        default_icon: context.default_window_icon,
-        event_listeners: Arc::new(window_event_listeners),
-      },
+      window: window::WindowManager::new(context.default_window_icon, window_event_listeners),
       webview: webview::WebviewManager {
         webviews: Mutex::default(),
         invoke_handler,
@@ -282,6 +304,7 @@ impl<R: Runtime> AppManager<R> {
       #[cfg(all(desktop, feature = "tray-icon"))]
       tray: tray::TrayManager {
         icon: context.tray_icon,
+        icon_pair: context.tray_icon_pair,
         icons: Default::default(),
         global_event_listeners: Default::default(),
         event_listeners: Default::default(),
@@ -300,12 +323,19 @@ impl<R: Runtime> AppManager<R> {
       #[cfg(dev)]
       config_parent: context.config_parent,
       assets: context.assets,
+      window_assets: context.window_assets,
       app_icon: context.app_icon,
       package_info: context.package_info,
       pattern: Arc::new(context.pattern),
       plugin_global_api_scripts: Arc::new(context.plugin_global_api_scripts),
+      resource_hashes: context.resource_hashes,
+      attributions: context.attributions,
       resources_table: Arc::default(),
       invoke_key,
+      startup: startup::StartupTimeline::new(),
+      #[cfg(feature = "tracing")]
+      command_latencies: Default::default(),
+      command_cache: Default::default(),
     }
   }
 
@@ -340,6 +370,69 @@ impl<R: Runtime> AppManager<R> {
     }
   }
 
+  /// Whether the URL is served by the app: the app protocol, the dev server, the frontend or a custom protocol.
+  pub(crate) fn is_local_url(&self, current_url: &Url) -> bool {
+    // if from `tauri://` custom protocol
+    let protocol_url = self.protocol_url();
+    if current_url.scheme() == protocol_url.scheme()
+      && current_url.domain() == protocol_url.domain()
+    {
+      return true;
+    }
+
+    // or if relative to `devUrl` or `frontendDist`
+    if self.get_url().make_relative(current_url).is_some() {
+      return true;
+    }
+
+    // or from a custom protocol registered by the user
+    let scheme = current_url.scheme();
+    let protocols = self.webview.uri_scheme_protocols.lock().unwrap();
+
+    #[cfg(all(not(windows), not(target_os = "android")))]
+    let local = protocols.contains_key(scheme);
+
+    // on window and android, custom protocols are `http://<protocol-name>.path/to/route`
+    // so we check using the first part of the domain
+    #[cfg(any(windows, target_os = "android"))]
+    let local = {
+      let maybe_protocol = current_url
+        .domain()
+        .and_then(|d| d.split_once('.'))
+        .unwrap_or_default()
+        .0;
+
+      protocols.contains_key(maybe_protocol) && scheme == protocol_url.scheme()
+    };
+
+    local
+  }
+
+  /// The `window.location.origin` of the pages [`Self::is_local_url`] accepts, for scripts that
+  /// must only run on app pages.
+  pub(crate) fn local_origins(&self) -> Vec<String> {
+    // `Url::origin` is opaque for custom schemes, while webviews report them as `scheme://host`
+    let origin = |url: &Url| {
+      url.host_str().map(|host| match url.port() {
+        Some(port) => format!("{}://{host}:{port}", url.scheme()),
+        None => format!("{}://{host}", url.scheme()),
+      })
+    };
+
+    let mut origins = Vec::new();
+    origins.extend(origin(&self.protocol_url()));
+    origins.extend(origin(&self.get_url()));
+    for scheme in self.webview.uri_scheme_protocols.lock().unwrap().keys() {
+      if cfg!(windows) || cfg!(target_os = "android") {
+        origins.push(format!("http://{scheme}.localhost"));
+      } else {
+        origins.push(format!("{scheme}://localhost"));
+      }
+    }
+    origins.dedup();
+    origins
+  }
+
   /// Get the base URL to use for webview requests.
   ///
   /// In dev mode, this will be based on the `devUrl` configuration value.
@@ -364,8 +457,26 @@ impl<R: Runtime> AppManager<R> {
     }
   }
 
-  pub fn get_asset(&self, mut path: String) -> Result<Asset, Box<dyn std::error::Error>> {
-    let assets = &self.assets;
+  pub fn get_asset(&self, path: String) -> Result<Asset, Box<dyn std::error::Error>> {
+    self.get_asset_from(self.assets.as_ref(), path)
+  }
+
+  /// Gets the asset served to the webviews of the window with the given label,
+  /// using the window's own frontend if it has one.
+  pub fn get_window_asset(
+    &self,
+    window_label: &str,
+    path: String,
+  ) -> Result<Asset, Box<dyn std::error::Error>> {
+    let assets = self.window_assets.get(window_label).unwrap_or(&self.assets);
+    self.get_asset_from(assets.as_ref(), path)
+  }
+
+  fn get_asset_from(
+    &self,
+    assets: &dyn Assets<R>,
+    mut path: String,
+  ) -> Result<Asset, Box<dyn std::error::Error>> {
     if path.ends_with('/') {
       path.pop();
     }
@@ -421,7 +532,7 @@ impl<R: Runtime> AppManager<R> {
           let mut asset = String::from_utf8_lossy(&asset).into_owned();
           if let Some(csp) = self.csp() {
             #[allow(unused_mut)]
-            let mut csp_map = set_csp(&mut asset, &self.assets, &asset_path, self, csp);
+            let mut csp_map = set_csp(&mut asset, &assets, &asset_path, self, csp);
             #[cfg(feature = "isolation")]
             if let Pattern::Isolation { schema, .. } = &*self.pattern {
               let default_src = csp_map
@@ -512,6 +623,19 @@ impl<R: Runtime> AppManager<R> {
     self.listeners().unlisten(id)
   }
 
+  /// Reports the panics of the listeners of the last emit, then resumes the first one.
+  #[cfg(dev)]
+  fn report_listener_panics(&self) {
+    let mut first = None;
+    for (report, payload) in self.listeners().take_panics() {
+      crate::panic::report(self, report);
+      first.get_or_insert(payload);
+    }
+    if let Some(payload) = first {
+      std::panic::resume_unwind(payload);
+    }
+  }
+
   #[cfg_attr(
     feature = "tracing",
     tracing::instrument("app::emit", skip(self, payload))
@@ -522,11 +646,22 @@ impl<R: Runtime> AppManager<R> {
     #[cfg(feature = "tracing")]
     let _span = tracing::debug_span!("emit::run").entered();
     let emit_args = EmitArgs::new(event, payload)?;
+    crate::ipc::limits::check_size(
+      &self.config().app.ipc,
+      crate::ipc::limits::Payload::Event,
+      emit_args.payload.len(),
+    )?;
+    #[cfg(feature = "metrics")]
+    if let Some(metrics) = self.state.try_get::<crate::metrics::Metrics<R>>() {
+      metrics.record_event(event);
+    }
 
     let listeners = self.listeners();
 
     listeners.emit_js(self.webview.webviews_lock().values(), event, &emit_args)?;
     listeners.emit(emit_args)?;
+    #[cfg(dev)]
+    self.report_listener_panics();
 
     Ok(())
   }
@@ -545,6 +680,15 @@ impl<R: Runtime> AppManager<R> {
     #[cfg(feature = "tracing")]
     let _span = tracing::debug_span!("emit::run").entered();
     let emit_args = EmitArgs::new(event, payload)?;
+    crate::ipc::limits::check_size(
+      &self.config().app.ipc,
+      crate::ipc::limits::Payload::Event,
+      emit_args.payload.len(),
+    )?;
+    #[cfg(feature = "metrics")]
+    if let Some(metrics) = self.state.try_get::<crate::metrics::Metrics<R>>() {
+      metrics.record_event(event);
+    }
 
     let listeners = self.listeners();
 
@@ -556,6 +700,8 @@ impl<R: Runtime> AppManager<R> {
     )?;
 
     listeners.emit_filter(emit_args, Some(filter))?;
+    #[cfg(dev)]
+    self.report_listener_panics();
 
     Ok(())
   }
@@ -750,6 +896,63 @@ mod test {
     assert_eq!(manager.get_url().to_string(), "http://localhost:4000/");
   }
 
+  #[test]
+  fn local_origins_are_local_urls() {
+    let app = mock_app();
+    let manager = &app.manager;
+    let origins = manager.local_origins();
+
+    assert!(origins.contains(
+      &manager
+        .protocol_url()
+        .as_str()
+        .trim_end_matches('/')
+        .to_string()
+    ));
+    for origin in origins {
+      let url = format!("{origin}/index.html").parse().unwrap();
+      assert!(manager.is_local_url(&url), "{origin} is not local");
+    }
+    assert!(!manager.is_local_url(&"https://tauri.app".parse().unwrap()));
+  }
+
+  #[test]
+  fn resolve_url_for_window_frontends() {
+    let mut context =
+      generate_context!("test/fixture/src-tauri/tauri.conf.json", crate, test = true);
+    context.set_window_assets("popup", Box::new(crate::test::noop_assets()));
+    let manager: AppManager<Wry> = AppManager::with_handlers(
+      context,
+      PluginStore::default(),
+      Box::new(|_| false),
+      None,
+      Default::default(),
+      StateManager::new(),
+      Default::default(),
+      Default::default(),
+      Default::default(),
+      "".into(),
+      crate::generate_invoke_key().unwrap(),
+    );
+
+    let resolve = |window_label, path: &str| {
+      super::webview::resolve_url(&manager, window_label, &crate::WebviewUrl::App(path.into()))
+    };
+
+    // the popup window is always served from its own embedded frontend
+    assert_eq!(
+      resolve(Some("popup"), "index.html"),
+      *manager.protocol_url()
+    );
+    assert_eq!(
+      resolve(Some("popup"), "settings.html"),
+      manager.protocol_url().join("settings.html").unwrap()
+    );
+    // other windows load the main frontend
+    assert_eq!(resolve(Some("main"), "index.html"), *manager.get_url());
+    assert_eq!(resolve(None, "index.html"), *manager.get_url());
+  }
+
   struct EventSetup {
     app: App<MockRuntime>,
     window: Window<MockRuntime>,
@@ -0,0 +1,70 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  sync::Mutex,
+  time::{Duration, Instant},
+};
+
+/// Records how long each startup step takes, reported once the app finished starting.
+///
+/// Nothing is recorded in release builds.
+pub struct StartupTimeline {
+  started: Instant,
+  steps: Mutex<Option<Vec<(String, Duration)>>>,
+}
+
+impl StartupTimeline {
+  pub fn new() -> Self {
+    Self {
+      started: Instant::now(),
+      steps: Mutex::new(cfg!(debug_assertions).then(Vec::new)),
+    }
+  }
+
+  /// Runs `f` and records its duration as a startup step, unless the timeline was already reported.
+  pub fn measure<T>(&self, step: impl FnOnce() -> String, f: impl FnOnce() -> T) -> T {
+    if self.steps.lock().unwrap().is_none() {
+      return f();
+    }
+
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+    if let Some(steps) = &mut *self.steps.lock().unwrap() {
+      steps.push((step(), elapsed));
+    }
+    result
+  }
+
+  /// Logs the recorded steps, slowest first. Steps measured afterwards are ignored.
+  pub fn report(&self) {
+    let Some(mut steps) = self.steps.lock().unwrap().take() else {
+      return;
+    };
+    steps.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let mut report = format!("startup took {:.2?}", self.started.elapsed());
+    for (step, elapsed) in steps {
+      report.push_str(&format!("\n  {elapsed:>10.2?}  {step}"));
+    }
+    log::debug!("{report}");
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::StartupTimeline;
+
+  #[test]
+  fn ignores_steps_after_report() {
+    let timeline = StartupTimeline::new();
+    assert_eq!(timeline.measure(|| "plugin a".into(), || 1), 1);
+    assert_eq!(timeline.steps.lock().unwrap().as_ref().unwrap().len(), 1);
+
+    timeline.report();
+    assert_eq!(timeline.measure(|| "plugin b".into(), || 2), 2);
+    assert!(timeline.steps.lock().unwrap().is_none());
+  }
+}
@@ -13,6 +13,8 @@ use crate::{
 
 pub struct TrayManager<R: Runtime> {
   pub(crate) icon: Option<Image<'static>>,
+  /// The light and dark theme icons of the default tray icon.
+  pub(crate) icon_pair: Option<(Image<'static>, Image<'static>)>,
   /// Tray icons
   pub(crate) icons: Mutex<Vec<TrayIcon<R>>>,
   /// Global Tray icon event listeners.
@@ -25,6 +27,7 @@ impl<R: Runtime> fmt::Debug for TrayManager<R> {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     f.debug_struct("TrayManager")
       .field("icon", &self.icon)
+      .field("icon_pair", &self.icon_pair)
       .finish()
   }
 }
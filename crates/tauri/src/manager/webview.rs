@@ -30,7 +30,10 @@ use crate::{
 };
 
 use super::{
-  window::{DragDropPayload, DRAG_DROP_EVENT, DRAG_ENTER_EVENT, DRAG_LEAVE_EVENT, DRAG_OVER_EVENT},
+  window::{
+    DragDropModifiers, DragDropPayload, DRAG_DROP_EVENT, DRAG_ENTER_EVENT, DRAG_LEAVE_EVENT,
+    DRAG_OVER_EVENT,
+  },
   AppManager,
 };
 
@@ -214,7 +217,19 @@ impl<R: Runtime> WebviewManager<R> {
       let app_handle = manager.app_handle().clone();
       let webview_label = label.to_string();
 
+      #[cfg(feature = "tracing")]
+      let scheme = uri_scheme.clone();
+
       pending.register_uri_scheme_protocol(uri_scheme.clone(), move |request, responder| {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!(
+          "webview::protocol",
+          scheme,
+          webview = webview_label,
+          uri = request.uri().to_string()
+        )
+        .entered();
+
         let context = UriSchemeContext {
           app_handle: &app_handle,
           webview_label: webview_label.as_str(),
@@ -249,6 +264,7 @@ impl<R: Runtime> WebviewManager<R> {
       let web_resource_request_handler = pending.web_resource_request_handler.take();
       let protocol = crate::protocol::tauri::get(
         manager.manager_owned(),
+        window_label.to_string(),
         &window_origin,
         web_resource_request_handler,
       );
@@ -284,6 +300,22 @@ impl<R: Runtime> WebviewManager<R> {
             .lock()
             .unwrap()
             .on_page_load(&w, &payload);
+
+          if event == crate::webview::PageLoadEvent::Started {
+            if let Some(features) = w.try_state::<crate::feature_flags::FeatureFlags<R>>() {
+              if let Err(e) = features.on_page_load(&w) {
+                log::error!("failed to update the feature flags of the webview: {e}");
+              }
+            }
+          }
+
+          if event == crate::webview::PageLoadEvent::Finished {
+            if let Some(splashscreen) = w.try_state::<crate::app::splashscreen::Splashscreen>() {
+              if splashscreen.closes_on_load(&label) {
+                splashscreen.done(w.app_handle());
+              }
+            }
+          }
         }
 
         if let Some(handler) = &on_page_load_handler {
@@ -404,38 +436,11 @@ impl<R: Runtime> WebviewManager<R> {
     let app_manager = manager.manager();
 
     #[allow(unused_mut)] // mut url only for the data-url parsing
-    let mut url = match &pending.webview_attributes.url {
-      WebviewUrl::App(path) => {
-        let url = if PROXY_DEV_SERVER {
-          Cow::Owned(Url::parse("tauri://localhost").unwrap())
-        } else {
-          app_manager.get_url()
-        };
-        // ignore "index.html" just to simplify the url
-        if path.to_str() != Some("index.html") {
-          url
-            .join(&path.to_string_lossy())
-            .map_err(crate::Error::InvalidUrl)
-            // this will never fail
-            .unwrap()
-        } else {
-          url.into_owned()
-        }
-      }
-      WebviewUrl::External(url) => {
-        let config_url = app_manager.get_url();
-        let is_local = config_url.make_relative(url).is_some();
-        let mut url = url.clone();
-        if is_local && PROXY_DEV_SERVER {
-          url.set_scheme("tauri").unwrap();
-          url.set_host(Some("localhost")).unwrap();
-        }
-        url
-      }
-
-      WebviewUrl::CustomProtocol(url) => url.clone(),
-      _ => unimplemented!(),
-    };
+    let mut url = resolve_url(
+      app_manager,
+      Some(window_label),
+      &pending.webview_attributes.url,
+    );
 
     #[cfg(not(feature = "webview-data-url"))]
     if url.scheme() == "data" {
@@ -531,6 +536,83 @@ impl<R: Runtime> WebviewManager<R> {
       )
     }
 
+    #[derive(Template)]
+    #[default_template("../webview/scripts/text-input.js")]
+    struct TextInput<'a> {
+      origin: &'a str,
+      spellcheck: Option<bool>,
+      autocorrect: Option<bool>,
+      autofill: Option<bool>,
+    }
+
+    // injected in local pages and remote pages granted a capability so the options can be changed at runtime,
+    // and only runs on the origin it was injected for
+    if app_manager.is_local_url(&url)
+      || app_manager
+        .runtime_authority
+        .lock()
+        .unwrap()
+        .has_remote_access(window_label, &label, &url)
+    {
+      let host = url.host_str().unwrap_or_default();
+      let origin = match url.port() {
+        Some(port) => format!("{}://{host}:{port}", url.scheme()),
+        None => format!("{}://{host}", url.scheme()),
+      };
+      pending.webview_attributes.initialization_scripts.push(
+        TextInput {
+          origin: &origin,
+          spellcheck: pending.webview_attributes.spellcheck,
+          autocorrect: pending.webview_attributes.autocorrect,
+          autofill: pending.webview_attributes.autofill,
+        }
+        .render_default(&Default::default())?
+        .into_string(),
+      );
+    }
+
+    // compositions are only delivered to the webview event handlers registered on the builder
+    #[cfg(desktop)]
+    if !self.event_listeners.is_empty() {
+      pending
+        .webview_attributes
+        .initialization_scripts
+        .push(include_str!("../webview/scripts/ime.js").into());
+    }
+
+    #[cfg(desktop)]
+    if let Some(gpu) = app_manager.state.try_get::<crate::app::gpu::Gpu>() {
+      if gpu.software_rendering() {
+        pending.webview_attributes.hardware_acceleration = false;
+      }
+      if let Some(script) = gpu.probe_script() {
+        pending
+          .webview_attributes
+          .initialization_scripts
+          .push(script.into());
+      }
+    }
+
+    if let Some(features) = app_manager
+      .state
+      .try_get::<crate::feature_flags::FeatureFlags<R>>()
+    {
+      pending
+        .webview_attributes
+        .initialization_scripts
+        .push(features.initialization_script()?);
+    }
+
+    #[cfg(desktop)]
+    if let Some(kiosk) = app_manager.state.try_get::<crate::app::kiosk::Kiosk>() {
+      if let Some(script) = kiosk.heartbeat_script()? {
+        pending
+          .webview_attributes
+          .initialization_scripts
+          .push(script);
+      }
+    }
+
     #[cfg(feature = "isolation")]
     let pattern = app_manager.pattern.clone();
     let navigation_handler = pending.navigation_handler.take();
@@ -650,42 +732,94 @@ impl<R: Runtime> Webview<R> {
   }
 }
 
-fn on_webview_event<R: Runtime>(webview: &Webview<R>, event: &WebviewEvent) -> crate::Result<()> {
-  match event {
-    WebviewEvent::DragDrop(event) => match event {
-      DragDropEvent::Enter { paths, position } => {
-        let payload = DragDropPayload {
-          paths: Some(paths),
-          position,
-        };
-        webview.emit_to_webview(DRAG_ENTER_EVENT, payload)?
-      }
-      DragDropEvent::Over { position } => {
-        let payload = DragDropPayload {
-          position,
-          paths: None,
-        };
-        webview.emit_to_webview(DRAG_OVER_EVENT, payload)?
+/// Resolves a [`WebviewUrl`] to the URL the webview loads,
+/// `window_label` being the label of the window hosting the webview if it is already known.
+pub(crate) fn resolve_url<R: Runtime>(
+  app_manager: &AppManager<R>,
+  window_label: Option<&str>,
+  url: &WebviewUrl,
+) -> Url {
+  match url {
+    WebviewUrl::App(path) => {
+      let url = if PROXY_DEV_SERVER {
+        Cow::Owned(Url::parse("tauri://localhost").unwrap())
+      } else if window_label.is_some_and(|label| app_manager.window_assets.contains_key(label)) {
+        // windows with their own frontend never load the dev server
+        app_manager.protocol_url()
+      } else {
+        app_manager.get_url()
+      };
+      // ignore "index.html" just to simplify the url
+      if path.to_str() != Some("index.html") {
+        url
+          .join(&path.to_string_lossy())
+          .map_err(crate::Error::InvalidUrl)
+          // this will never fail
+          .unwrap()
+      } else {
+        url.into_owned()
       }
-      DragDropEvent::Drop { paths, position } => {
-        let scopes = webview.state::<Scopes>();
-        for path in paths {
-          if path.is_file() {
-            let _ = scopes.allow_file(path);
-          } else {
-            let _ = scopes.allow_directory(path, false);
-          }
-        }
-        let payload = DragDropPayload {
-          paths: Some(paths),
-          position,
-        };
-        webview.emit_to_webview(DRAG_DROP_EVENT, payload)?
+    }
+    WebviewUrl::External(url) => {
+      let config_url = app_manager.get_url();
+      let is_local = config_url.make_relative(url).is_some();
+      let mut url = url.clone();
+      if is_local && PROXY_DEV_SERVER {
+        url.set_scheme("tauri").unwrap();
+        url.set_host(Some("localhost")).unwrap();
       }
-      DragDropEvent::Leave => webview.emit_to_webview(DRAG_LEAVE_EVENT, ())?,
-      _ => unimplemented!(),
-    },
+      url
+    }
+
+    WebviewUrl::CustomProtocol(url) => url.clone(),
+    _ => unimplemented!(),
+  }
+}
+
+fn on_webview_event<R: Runtime>(webview: &Webview<R>, event: &WebviewEvent) -> crate::Result<()> {
+  match event {
+    WebviewEvent::DragDrop(event) => {
+      let webview_ = webview.clone();
+      let event = event.clone();
+      let modifiers = DragDropModifiers::current();
+      webview.manager.window.on_drag_drop(move || {
+        let _ = on_drag_drop_event(&webview_, event, modifiers);
+      });
+    }
+    // not emitted by the runtime
+    WebviewEvent::ImeComposition(_) => (),
   }
 
   Ok(())
 }
+
+fn on_drag_drop_event<R: Runtime>(
+  webview: &Webview<R>,
+  event: DragDropEvent,
+  modifiers: DragDropModifiers,
+) -> crate::Result<()> {
+  match event {
+    DragDropEvent::Enter { paths, position } => {
+      let payload = DragDropPayload::enter(paths, position, modifiers);
+      webview.emit_to_webview(DRAG_ENTER_EVENT, payload)
+    }
+    DragDropEvent::Over { position } => {
+      let payload = DragDropPayload::over(position, modifiers);
+      webview.emit_to_webview(DRAG_OVER_EVENT, payload)
+    }
+    DragDropEvent::Drop { paths, position } => {
+      let scopes = webview.state::<Scopes>();
+      for path in &paths {
+        if path.is_file() {
+          let _ = scopes.allow_file(path);
+        } else {
+          let _ = scopes.allow_directory(path, false);
+        }
+      }
+      let payload = DragDropPayload::drop(paths, position, modifiers);
+      webview.emit_to_webview(DRAG_DROP_EVENT, payload)
+    }
+    DragDropEvent::Leave => webview.emit_to_webview(DRAG_LEAVE_EVENT, ()),
+    _ => unimplemented!(),
+  }
+}
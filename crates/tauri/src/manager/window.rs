@@ -5,8 +5,13 @@
 use std::{
   collections::{HashMap, HashSet},
   fmt,
-  path::PathBuf,
-  sync::{Arc, Mutex, MutexGuard},
+  io::Read,
+  path::{Path, PathBuf},
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    mpsc::{channel, Receiver, Sender},
+    Arc, Mutex, MutexGuard,
+  },
 };
 
 use serde::Serialize;
@@ -17,8 +22,10 @@ use tauri_runtime::{
 };
 
 use crate::{
-  app::GlobalWindowEventListener, image::Image, sealed::ManagerBase, AppHandle, Emitter,
-  EventLoopMessage, EventTarget, Manager, Runtime, Scopes, Window, WindowEvent,
+  app::{GlobalWindowEventListener, WindowEventDelivery},
+  image::Image,
+  sealed::ManagerBase,
+  AppHandle, Emitter, EventLoopMessage, EventTarget, Manager, Runtime, Scopes, Window, WindowEvent,
 };
 
 const WINDOW_RESIZED_EVENT: &str = "tauri://resize";
@@ -29,27 +36,85 @@ const WINDOW_FOCUS_EVENT: &str = "tauri://focus";
 const WINDOW_BLUR_EVENT: &str = "tauri://blur";
 const WINDOW_SCALE_FACTOR_CHANGED_EVENT: &str = "tauri://scale-change";
 const WINDOW_THEME_CHANGED: &str = "tauri://theme-changed";
+#[cfg(target_os = "macos")]
+const WINDOW_TAB_SELECTED_EVENT: &str = "tauri://tab-selected";
 pub(crate) const DRAG_ENTER_EVENT: &str = "tauri://drag-enter";
 pub(crate) const DRAG_OVER_EVENT: &str = "tauri://drag-over";
 pub(crate) const DRAG_DROP_EVENT: &str = "tauri://drag-drop";
 pub(crate) const DRAG_LEAVE_EVENT: &str = "tauri://drag-leave";
 
+/// A window event waiting for the listeners.
+///
+/// Close requests carry the receiver of their [`crate::CloseRequestApi`],
+/// the window is destroyed after the listeners ran unless one of them prevented it.
+type QueuedWindowEvent<R> = (Window<R>, WindowEvent, Option<Receiver<bool>>);
+
+/// Queue of the window event thread and how many events are waiting in it.
+#[derive(Clone)]
+struct EventQueue<R: Runtime> {
+  tx: Sender<QueuedWindowEvent<R>>,
+  len: Arc<AtomicUsize>,
+}
+
+/// Emits a drag and drop event, run on the drag and drop thread.
+type DragDropTask = Box<dyn FnOnce() + Send>;
+
 pub struct WindowManager<R: Runtime> {
   pub windows: Mutex<HashMap<String, Window<R>>>,
   pub default_icon: Option<Image<'static>>,
   /// Window event listeners to all windows.
   pub event_listeners: Arc<Vec<GlobalWindowEventListener<R>>>,
+  /// Where the window event listeners run.
+  pub event_delivery: WindowEventDelivery,
+  /// Queue of the window event thread, spawned on the first event.
+  event_queue: Mutex<Option<EventQueue<R>>>,
+  /// Queue of the drag and drop thread, spawned on the first drag and drop event.
+  drag_drop_queue: Mutex<Option<Sender<DragDropTask>>>,
+  /// The label of the selected window of each native tab group, by tab group address.
+  #[cfg(target_os = "macos")]
+  selected_tabs: Mutex<HashMap<usize, String>>,
 }
 
 impl<R: Runtime> fmt::Debug for WindowManager<R> {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     f.debug_struct("WindowManager")
       .field("default_window_icon", &self.default_icon)
+      .field("event_delivery", &self.event_delivery)
       .finish()
   }
 }
 
 impl<R: Runtime> WindowManager<R> {
+  pub(crate) fn new(
+    default_icon: Option<Image<'static>>,
+    event_listeners: Vec<GlobalWindowEventListener<R>>,
+  ) -> Self {
+    Self {
+      windows: Mutex::default(),
+      default_icon,
+      event_listeners: Arc::new(event_listeners),
+      event_delivery: WindowEventDelivery::default(),
+      event_queue: Mutex::default(),
+      drag_drop_queue: Mutex::default(),
+      #[cfg(target_os = "macos")]
+      selected_tabs: Mutex::default(),
+    }
+  }
+
+  /// Records `label` as the selected window of the tab group, returning whether the selection changed.
+  #[cfg(target_os = "macos")]
+  fn select_tab(&self, group: usize, label: &str) -> bool {
+    let mut selected_tabs = self.selected_tabs.lock().unwrap();
+    if selected_tabs
+      .get(&group)
+      .is_some_and(|selected| selected == label)
+    {
+      return false;
+    }
+    selected_tabs.insert(group, label.to_string());
+    true
+  }
+
   /// Get a locked handle to the windows.
   pub(crate) fn windows_lock(&self) -> MutexGuard<'_, HashMap<String, Window<R>>> {
     self.windows.lock().expect("poisoned window manager")
@@ -87,12 +152,14 @@ impl<R: Runtime> WindowManager<R> {
     );
 
     let window_ = window.clone();
-    let window_event_listeners = self.event_listeners.clone();
     window.on_window_event(move |event| {
       let _ = on_window_event(&window_, event);
-      for handler in window_event_listeners.iter() {
-        handler(&window_, event);
-      }
+      let prevented_by_js = matches!(event, WindowEvent::CloseRequested { .. })
+        && window_.has_js_listener(WINDOW_CLOSE_REQUESTED_EVENT);
+      window_
+        .manager
+        .window
+        .dispatch_event(&window_, event, prevented_by_js);
     });
 
     // insert the window into our manager
@@ -120,6 +187,137 @@ impl<R: Runtime> WindowManager<R> {
   pub fn labels(&self) -> HashSet<String> {
     self.windows_lock().keys().cloned().collect()
   }
+
+  /// Runs a drag and drop task on the drag and drop thread.
+  ///
+  /// The dragged paths are inspected there so a slow file system does not block the event loop,
+  /// and the tasks run one after the other so the events are emitted in the order they happened.
+  pub(crate) fn on_drag_drop(&self, task: impl FnOnce() + Send + 'static) {
+    let queue = self
+      .drag_drop_queue
+      .lock()
+      .unwrap()
+      .get_or_insert_with(spawn_drag_drop_thread)
+      .clone();
+    if let Err(task) = queue.send(Box::new(task)) {
+      log::error!("drag and drop thread stopped, emitting the event on the event loop thread");
+      (task.0)();
+    }
+  }
+
+  /// Runs the global window event listeners according to the [`WindowEventDelivery`].
+  ///
+  /// With [`WindowEventDelivery::Dedicated`] the event loop never waits for the listeners:
+  /// coalescable events are dropped when the queue is full,
+  /// and close requests are prevented right away then completed with [`Window::destroy`] once the listeners ran.
+  fn dispatch_event(&self, window: &Window<R>, event: &WindowEvent, prevented_by_js: bool) {
+    if self.event_listeners.is_empty() {
+      return;
+    }
+
+    if let WindowEventDelivery::Dedicated { capacity } = self.event_delivery {
+      let queue = self
+        .event_queue
+        .lock()
+        .unwrap()
+        .get_or_insert_with(|| spawn_event_thread(self.event_listeners.clone()))
+        .clone();
+
+      if queue.len.load(Ordering::Relaxed) >= capacity && is_coalescable(event) {
+        log::debug!("window event queue is full, dropping {event:?}");
+        return;
+      }
+
+      let (event, close) = match event {
+        WindowEvent::CloseRequested { api } => {
+          // the close is completed by the event thread so the listeners can still prevent it
+          api.prevent_close();
+          let (tx, rx) = channel();
+          let event = WindowEvent::CloseRequested {
+            api: crate::CloseRequestApi(tx),
+          };
+          (event, (!prevented_by_js).then_some(rx))
+        }
+        event => (event.clone(), None),
+      };
+
+      queue.len.fetch_add(1, Ordering::Relaxed);
+      if let Err(e) = queue.tx.send((window.clone(), event, close)) {
+        queue.len.fetch_sub(1, Ordering::Relaxed);
+        log::error!("window event thread stopped, running the listeners on the event loop thread");
+        let (window, event, close) = e.0;
+        run_listeners(&self.event_listeners, &window, &event, close);
+      }
+      return;
+    }
+
+    run_listeners(&self.event_listeners, window, event, None);
+  }
+}
+
+/// Whether a newer event of the same kind supersedes the event, so it can be dropped when the queue is full.
+fn is_coalescable(event: &WindowEvent) -> bool {
+  matches!(
+    event,
+    WindowEvent::Resized(_)
+      | WindowEvent::Moved(_)
+      | WindowEvent::DragDrop(DragDropEvent::Over { .. })
+  )
+}
+
+fn run_listeners<R: Runtime>(
+  listeners: &[GlobalWindowEventListener<R>],
+  window: &Window<R>,
+  event: &WindowEvent,
+  close: Option<Receiver<bool>>,
+) {
+  for handler in listeners {
+    handler(window, event);
+  }
+  if let Some(close) = close {
+    if !matches!(close.try_recv(), Ok(true)) {
+      if let Err(e) = window.destroy() {
+        log::error!("failed to close window {}: {e}", window.label());
+      }
+    }
+  }
+}
+
+fn spawn_event_thread<R: Runtime>(
+  listeners: Arc<Vec<GlobalWindowEventListener<R>>>,
+) -> EventQueue<R> {
+  let (tx, rx) = channel::<QueuedWindowEvent<R>>();
+  let len = Arc::new(AtomicUsize::new(0));
+  let len_ = len.clone();
+  let spawned = std::thread::Builder::new()
+    .name("tauri-window-events".into())
+    .spawn(move || {
+      for (window, event, close) in rx {
+        len_.fetch_sub(1, Ordering::Relaxed);
+        run_listeners(&listeners, &window, &event, close);
+      }
+    });
+  if let Err(e) = spawned {
+    // the receiver is dropped so the listeners run on the event loop thread
+    log::error!("failed to spawn the window event thread: {e}");
+  }
+  EventQueue { tx, len }
+}
+
+fn spawn_drag_drop_thread() -> Sender<DragDropTask> {
+  let (tx, rx) = channel::<DragDropTask>();
+  let spawned = std::thread::Builder::new()
+    .name("tauri-drag-drop".into())
+    .spawn(move || {
+      for task in rx {
+        task();
+      }
+    });
+  if let Err(e) = spawned {
+    // the receiver is dropped so the events are emitted on the event loop thread
+    log::error!("failed to spawn the drag and drop thread: {e}");
+  }
+  tx
 }
 
 impl<R: Runtime> Window<R> {
@@ -143,16 +341,146 @@ impl<R: Runtime> Window<R> {
   }
 }
 
+/// How many dropped files have their content read to infer their MIME type,
+/// the type of the other ones is inferred from their extension.
+const MAX_SNIFFED_ITEMS: usize = 64;
+
 #[derive(Serialize, Clone)]
-pub(crate) struct DragDropPayload<'a> {
+pub(crate) struct DragDropPayload {
   #[serde(skip_serializing_if = "Option::is_none")]
-  pub paths: Option<&'a Vec<PathBuf>>,
-  pub position: &'a PhysicalPosition<f64>,
+  pub paths: Option<Vec<PathBuf>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub items: Option<Vec<DragDropItem>>,
+  pub position: PhysicalPosition<f64>,
+  pub modifiers: DragDropModifiers,
+}
+
+impl DragDropPayload {
+  /// The payload of a drag entering the window.
+  ///
+  /// Reads the metadata of the dragged paths, so it must not run on the event loop thread.
+  pub(crate) fn enter(
+    paths: Vec<PathBuf>,
+    position: PhysicalPosition<f64>,
+    modifiers: DragDropModifiers,
+  ) -> Self {
+    Self {
+      items: Some(paths.iter().map(|p| DragDropItem::new(p, false)).collect()),
+      paths: Some(paths),
+      position,
+      modifiers,
+    }
+  }
+
+  /// The payload of a drag moving over the window.
+  pub(crate) fn over(position: PhysicalPosition<f64>, modifiers: DragDropModifiers) -> Self {
+    Self {
+      paths: None,
+      items: None,
+      position,
+      modifiers,
+    }
+  }
+
+  /// The payload of a drop, reading the content of the first [`MAX_SNIFFED_ITEMS`] files to infer their MIME type.
+  ///
+  /// Reads the dropped files, so it must not run on the event loop thread.
+  pub(crate) fn drop(
+    paths: Vec<PathBuf>,
+    position: PhysicalPosition<f64>,
+    modifiers: DragDropModifiers,
+  ) -> Self {
+    Self {
+      items: Some(
+        paths
+          .iter()
+          .enumerate()
+          .map(|(i, p)| DragDropItem::new(p, i < MAX_SNIFFED_ITEMS))
+          .collect(),
+      ),
+      paths: Some(paths),
+      position,
+      modifiers,
+    }
+  }
+}
+
+/// The modifier keys held during a drag and drop operation.
+#[derive(Debug, Default, Serialize, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DragDropModifiers {
+  shift: bool,
+  ctrl: bool,
+  alt: bool,
+  meta: bool,
+}
+
+impl DragDropModifiers {
+  /// Reads the modifier keys held now, so it must run on the event loop thread when the event is received.
+  pub(crate) fn current() -> Self {
+    platform::drag_drop_modifiers()
+  }
+}
+
+/// Metadata of a dragged path.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DragDropItem {
+  path: PathBuf,
+  is_directory: bool,
+  size: Option<u64>,
+  mime_type: Option<String>,
+}
+
+impl DragDropItem {
+  /// Reads the metadata of the path, and its first bytes to infer its MIME type if `sniff` is set.
+  fn new(path: &Path, sniff: bool) -> Self {
+    let metadata = std::fs::metadata(path).ok();
+    let is_directory = metadata.as_ref().is_some_and(|m| m.is_dir());
+    Self {
+      path: path.to_path_buf(),
+      is_directory,
+      size: metadata.filter(|m| m.is_file()).map(|m| m.len()),
+      mime_type: if is_directory {
+        None
+      } else if sniff {
+        sniff_mime_type(path)
+      } else {
+        Some(
+          tauri_utils::mime_type::MimeType::parse_from_uri_with_fallback(
+            &path.to_string_lossy(),
+            tauri_utils::mime_type::MimeType::OctetStream,
+          )
+          .to_string(),
+        )
+      },
+    }
+  }
+}
+
+/// Infers the mime type of a file from its first bytes, falling back to its extension.
+fn sniff_mime_type(path: &Path) -> Option<String> {
+  let mut header = Vec::new();
+  std::fs::File::open(path)
+    .ok()?
+    .take(8192)
+    .read_to_end(&mut header)
+    .ok()?;
+  Some(tauri_utils::mime_type::MimeType::parse_with_fallback(
+    &header,
+    &path.to_string_lossy(),
+    tauri_utils::mime_type::MimeType::OctetStream,
+  ))
 }
 
 fn on_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) -> crate::Result<()> {
   match event {
-    WindowEvent::Resized(size) => window.emit_to_window(WINDOW_RESIZED_EVENT, size)?,
+    WindowEvent::Resized(size) => {
+      #[cfg(any(test, all(desktop, feature = "unstable")))]
+      if let Err(e) = window.on_layout_resized(*size, window.scale_factor()?) {
+        log::error!("failed to apply the webview layout: {e}");
+      }
+      window.emit_to_window(WINDOW_RESIZED_EVENT, size)?
+    }
     WindowEvent::Moved(position) => window.emit_to_window(WINDOW_MOVED_EVENT, position)?,
     WindowEvent::CloseRequested { api } => {
       if window.has_js_listener(WINDOW_CLOSE_REQUESTED_EVENT) {
@@ -162,101 +490,197 @@ fn on_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) -> crate
     }
     WindowEvent::Destroyed => {
       window.emit_to_window(WINDOW_DESTROYED_EVENT, ())?;
+      #[cfg(target_os = "macos")]
+      window
+        .manager
+        .window
+        .selected_tabs
+        .lock()
+        .unwrap()
+        .retain(|_, selected| selected != window.label());
     }
-    WindowEvent::Focused(focused) => window.emit_to_window(
-      if *focused {
-        WINDOW_FOCUS_EVENT
-      } else {
-        WINDOW_BLUR_EVENT
-      },
-      (),
-    )?,
-    WindowEvent::ScaleFactorChanged {
-      scale_factor,
-      new_inner_size,
-      ..
-    } => window.emit_to_window(
-      WINDOW_SCALE_FACTOR_CHANGED_EVENT,
-      ScaleFactorChanged {
-        scale_factor: *scale_factor,
-        size: *new_inner_size,
-      },
-    )?,
-    WindowEvent::DragDrop(event) => match event {
-      DragDropEvent::Enter { paths, position } => {
-        let payload = DragDropPayload {
-          paths: Some(paths),
-          position,
-        };
-
-        if window.is_webview_window() {
-          window.emit_to(
-            EventTarget::labeled(window.label()),
-            DRAG_ENTER_EVENT,
-            payload,
-          )?
+    WindowEvent::Focused(focused) => {
+      window.emit_to_window(
+        if *focused {
+          WINDOW_FOCUS_EVENT
         } else {
-          window.emit_to_window(DRAG_ENTER_EVENT, payload)?
-        }
+          WINDOW_BLUR_EVENT
+        },
+        (),
+      )?;
+      #[cfg(desktop)]
+      if *focused {
+        window.on_focused();
       }
-      DragDropEvent::Over { position } => {
-        let payload = DragDropPayload {
-          position,
-          paths: None,
-        };
-        if window.is_webview_window() {
-          window.emit_to(
-            EventTarget::labeled(window.label()),
-            DRAG_OVER_EVENT,
-            payload,
-          )?
-        } else {
-          window.emit_to_window(DRAG_OVER_EVENT, payload)?
-        }
+      // panels are dismissed when they lose focus
+      #[cfg(desktop)]
+      if !*focused && window.window_type() == crate::window::WindowType::Panel {
+        window.hide_animated(None)?;
       }
-      DragDropEvent::Drop { paths, position } => {
-        let scopes = window.state::<Scopes>();
-        for path in paths {
-          if path.is_file() {
-            let _ = scopes.allow_file(path);
-          } else {
-            let _ = scopes.allow_directory(path, true);
+      // selecting a tab focuses its window, but so does switching to the window of an unchanged tab group,
+      // so only emit when the `selectedWindow` of the tab group changed
+      #[cfg(target_os = "macos")]
+      if *focused {
+        if let Some(group) = window.selected_tab_group()? {
+          if window.manager.window.select_tab(group, window.label()) {
+            let tabs = window.tabbed_windows()?;
+            if tabs.len() > 1 {
+              let labels = tabs.iter().map(|w| w.label()).collect::<Vec<_>>();
+              window.emit_to_window(WINDOW_TAB_SELECTED_EVENT, labels)?;
+            }
           }
         }
-        let payload = DragDropPayload {
-          paths: Some(paths),
-          position,
-        };
-
-        if window.is_webview_window() {
-          window.emit_to(
-            EventTarget::labeled(window.label()),
-            DRAG_DROP_EVENT,
-            payload,
-          )?
-        } else {
-          window.emit_to_window(DRAG_DROP_EVENT, payload)?
-        }
       }
-      DragDropEvent::Leave => {
-        if window.is_webview_window() {
-          window.emit_to(EventTarget::labeled(window.label()), DRAG_LEAVE_EVENT, ())?
-        } else {
-          window.emit_to_window(DRAG_LEAVE_EVENT, ())?
-        }
+    }
+    WindowEvent::ScaleFactorChanged {
+      scale_factor,
+      new_inner_size,
+      ..
+    } => {
+      #[cfg(any(test, all(desktop, feature = "unstable")))]
+      if let Err(e) = window.on_layout_resized(*new_inner_size, *scale_factor) {
+        log::error!("failed to apply the webview layout: {e}");
       }
-      _ => unimplemented!(),
-    },
+      window.emit_to_window(
+        WINDOW_SCALE_FACTOR_CHANGED_EVENT,
+        ScaleFactorChanged {
+          scale_factor: *scale_factor,
+          size: *new_inner_size,
+        },
+      )?
+    }
+    WindowEvent::DragDrop(event) => {
+      let window_ = window.clone();
+      let event = event.clone();
+      let modifiers = DragDropModifiers::current();
+      window.manager.window.on_drag_drop(move || {
+        let _ = on_drag_drop_event(&window_, event, modifiers);
+      });
+    }
     WindowEvent::ThemeChanged(theme) => {
+      #[cfg(desktop)]
+      window.on_theme_changed(*theme);
       window.emit_to_window(WINDOW_THEME_CHANGED, theme.to_string())?
     }
   }
   Ok(())
 }
 
+fn on_drag_drop_event<R: Runtime>(
+  window: &Window<R>,
+  event: DragDropEvent,
+  modifiers: DragDropModifiers,
+) -> crate::Result<()> {
+  let (event_name, payload) = match event {
+    DragDropEvent::Enter { paths, position } => (
+      DRAG_ENTER_EVENT,
+      Some(DragDropPayload::enter(paths, position, modifiers)),
+    ),
+    DragDropEvent::Over { position } => (
+      DRAG_OVER_EVENT,
+      Some(DragDropPayload::over(position, modifiers)),
+    ),
+    DragDropEvent::Drop { paths, position } => {
+      let scopes = window.state::<Scopes>();
+      for path in &paths {
+        if path.is_file() {
+          let _ = scopes.allow_file(path);
+        } else {
+          let _ = scopes.allow_directory(path, true);
+        }
+      }
+      (
+        DRAG_DROP_EVENT,
+        Some(DragDropPayload::drop(paths, position, modifiers)),
+      )
+    }
+    DragDropEvent::Leave => (DRAG_LEAVE_EVENT, None),
+    _ => unimplemented!(),
+  };
+
+  if window.is_webview_window() {
+    window.emit_to(EventTarget::labeled(window.label()), event_name, payload)
+  } else {
+    window.emit_to_window(event_name, payload)
+  }
+}
+
 #[derive(Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct ScaleFactorChanged {
   scale_factor: f64,
   size: PhysicalSize<u32>,
 }
+
+#[cfg(windows)]
+mod platform {
+  use super::DragDropModifiers;
+  use windows::Win32::UI::Input::KeyboardAndMouse::{
+    GetAsyncKeyState, VIRTUAL_KEY, VK_CONTROL, VK_LWIN, VK_MENU, VK_RWIN, VK_SHIFT,
+  };
+
+  // the drag usually comes from another app, so the keyboard state of this thread is not up to date
+  fn is_down(key: VIRTUAL_KEY) -> bool {
+    unsafe { GetAsyncKeyState(key.0 as i32) < 0 }
+  }
+
+  pub fn drag_drop_modifiers() -> DragDropModifiers {
+    DragDropModifiers {
+      shift: is_down(VK_SHIFT),
+      ctrl: is_down(VK_CONTROL),
+      alt: is_down(VK_MENU),
+      meta: is_down(VK_LWIN) || is_down(VK_RWIN),
+    }
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use super::DragDropModifiers;
+  use objc2_app_kit::{NSEvent, NSEventModifierFlags};
+
+  pub fn drag_drop_modifiers() -> DragDropModifiers {
+    let flags = unsafe { NSEvent::modifierFlags_class() };
+    let is_down = |flag: NSEventModifierFlags| flags.0 & flag.0 != 0;
+    DragDropModifiers {
+      shift: is_down(NSEventModifierFlags::NSEventModifierFlagShift),
+      ctrl: is_down(NSEventModifierFlags::NSEventModifierFlagControl),
+      alt: is_down(NSEventModifierFlags::NSEventModifierFlagOption),
+      meta: is_down(NSEventModifierFlags::NSEventModifierFlagCommand),
+    }
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use super::DragDropModifiers;
+  use gtk::gdk::{Display, Keymap, ModifierType};
+
+  pub fn drag_drop_modifiers() -> DragDropModifiers {
+    let state = Display::default()
+      .and_then(|display| Keymap::for_display(&display))
+      .map(|keymap| ModifierType::from_bits_truncate(keymap.modifier_state()))
+      .unwrap_or(ModifierType::empty());
+    DragDropModifiers {
+      shift: state.contains(ModifierType::SHIFT_MASK),
+      ctrl: state.contains(ModifierType::CONTROL_MASK),
+      alt: state.contains(ModifierType::MOD1_MASK),
+      meta: state.intersects(ModifierType::SUPER_MASK | ModifierType::META_MASK),
+    }
+  }
+}
+
+#[cfg(mobile)]
+mod platform {
+  use super::DragDropModifiers;
+
+  pub fn drag_drop_modifiers() -> DragDropModifiers {
+    DragDropModifiers::default()
+  }
+}
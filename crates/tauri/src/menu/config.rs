@@ -0,0 +1,155 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Menus defined in the `app > menu` and `app > trayIcon > menu` configuration.
+
+use std::sync::Mutex;
+
+use tauri_utils::config::{MenuItemConfig, PredefinedMenuItemConfig};
+
+use super::{
+  CheckMenuItemBuilder, Menu, MenuItemBuilder, MenuItemKind, PredefinedMenuItem, Submenu,
+};
+use crate::{AppHandle, Manager, Runtime};
+
+pub(crate) type MenuTranslator<R> = dyn Fn(&AppHandle<R>, &str) -> Option<String> + Send + Sync;
+
+/// An item created with a translation key.
+struct TranslatedItem<R: Runtime> {
+  item: MenuItemKind<R>,
+  key: String,
+  fallback: Option<String>,
+}
+
+/// The translator of the configured menus and the items to update when the language changes.
+pub(crate) struct ConfigMenus<R: Runtime> {
+  translator: Option<Box<MenuTranslator<R>>>,
+  translated: Mutex<Vec<TranslatedItem<R>>>,
+}
+
+impl<R: Runtime> ConfigMenus<R> {
+  pub(crate) fn new(translator: Option<Box<MenuTranslator<R>>>) -> Self {
+    Self {
+      translator,
+      translated: Default::default(),
+    }
+  }
+
+  fn text(&self, app: &AppHandle<R>, key: &str, fallback: Option<&str>) -> String {
+    self
+      .translator
+      .as_ref()
+      .and_then(|translate| translate(app, key))
+      .or_else(|| fallback.map(ToString::to_string))
+      .unwrap_or_else(|| key.to_string())
+  }
+
+  /// Updates the text of the items created with a translation key.
+  pub(crate) fn retranslate(&self, app: &AppHandle<R>) -> crate::Result<()> {
+    for translated in self.translated.lock().unwrap().iter() {
+      let text = self.text(app, &translated.key, translated.fallback.as_deref());
+      match &translated.item {
+        MenuItemKind::MenuItem(i) => i.set_text(text)?,
+        MenuItemKind::Submenu(i) => i.set_text(text)?,
+        MenuItemKind::Predefined(i) => i.set_text(text)?,
+        MenuItemKind::Check(i) => i.set_text(text)?,
+        MenuItemKind::Icon(i) => i.set_text(text)?,
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Creates a menu with the configured items.
+pub(crate) fn build_menu<R: Runtime>(
+  app: &AppHandle<R>,
+  items: &[MenuItemConfig],
+) -> crate::Result<Menu<R>> {
+  let menu = Menu::new(app)?;
+  for item in items {
+    menu.append(build_item(app, item)?.inner())?;
+  }
+  Ok(menu)
+}
+
+fn build_item<R: Runtime>(
+  app: &AppHandle<R>,
+  config: &MenuItemConfig,
+) -> crate::Result<MenuItemKind<R>> {
+  let menus = app.state::<ConfigMenus<R>>();
+  let text = match &config.text_key {
+    Some(key) => Some(menus.text(app, key, config.text.as_deref())),
+    None => config.text.clone(),
+  };
+
+  let item = if let Some(predefined) = config.predefined {
+    MenuItemKind::Predefined(build_predefined(app, predefined, text.as_deref())?)
+  } else if let Some(items) = &config.items {
+    let text = text.unwrap_or_default();
+    let submenu = match &config.id {
+      Some(id) => Submenu::with_id(app, id, text, config.enabled)?,
+      None => Submenu::new(app, text, config.enabled)?,
+    };
+    for item in items {
+      submenu.append(build_item(app, item)?.inner())?;
+    }
+    MenuItemKind::Submenu(submenu)
+  } else if let Some(checked) = config.checked {
+    let mut builder = CheckMenuItemBuilder::new(text.unwrap_or_default())
+      .enabled(config.enabled)
+      .checked(checked);
+    if let Some(id) = &config.id {
+      builder = builder.id(id);
+    }
+    if let Some(accelerator) = &config.accelerator {
+      builder = builder.accelerator(accelerator);
+    }
+    MenuItemKind::Check(builder.build(app)?)
+  } else {
+    let mut builder = MenuItemBuilder::new(text.unwrap_or_default()).enabled(config.enabled);
+    if let Some(id) = &config.id {
+      builder = builder.id(id);
+    }
+    if let Some(accelerator) = &config.accelerator {
+      builder = builder.accelerator(accelerator);
+    }
+    MenuItemKind::MenuItem(builder.build(app)?)
+  };
+
+  if let Some(key) = &config.text_key {
+    menus.translated.lock().unwrap().push(TranslatedItem {
+      item: item.clone(),
+      key: key.clone(),
+      fallback: config.text.clone(),
+    });
+  }
+
+  Ok(item)
+}
+
+fn build_predefined<R: Runtime>(
+  app: &AppHandle<R>,
+  predefined: PredefinedMenuItemConfig,
+  text: Option<&str>,
+) -> crate::Result<PredefinedMenuItem<R>> {
+  match predefined {
+    PredefinedMenuItemConfig::Separator => PredefinedMenuItem::separator(app),
+    PredefinedMenuItemConfig::Copy => PredefinedMenuItem::copy(app, text),
+    PredefinedMenuItemConfig::Cut => PredefinedMenuItem::cut(app, text),
+    PredefinedMenuItemConfig::Paste => PredefinedMenuItem::paste(app, text),
+    PredefinedMenuItemConfig::SelectAll => PredefinedMenuItem::select_all(app, text),
+    PredefinedMenuItemConfig::Undo => PredefinedMenuItem::undo(app, text),
+    PredefinedMenuItemConfig::Redo => PredefinedMenuItem::redo(app, text),
+    PredefinedMenuItemConfig::Minimize => PredefinedMenuItem::minimize(app, text),
+    PredefinedMenuItemConfig::Maximize => PredefinedMenuItem::maximize(app, text),
+    PredefinedMenuItemConfig::Fullscreen => PredefinedMenuItem::fullscreen(app, text),
+    PredefinedMenuItemConfig::Hide => PredefinedMenuItem::hide(app, text),
+    PredefinedMenuItemConfig::HideOthers => PredefinedMenuItem::hide_others(app, text),
+    PredefinedMenuItemConfig::ShowAll => PredefinedMenuItem::show_all(app, text),
+    PredefinedMenuItemConfig::CloseWindow => PredefinedMenuItem::close_window(app, text),
+    PredefinedMenuItemConfig::Quit => PredefinedMenuItem::quit(app, text),
+    PredefinedMenuItemConfig::About => PredefinedMenuItem::about(app, text, None),
+    PredefinedMenuItemConfig::Services => PredefinedMenuItem::services(app, text),
+  }
+}
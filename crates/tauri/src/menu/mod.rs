@@ -6,6 +6,7 @@
 
 mod builders;
 mod check;
+pub(crate) mod config;
 mod icon;
 #[allow(clippy::module_inception)]
 mod menu;
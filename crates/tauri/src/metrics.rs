@@ -0,0 +1,333 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Operational metrics of the app in the [OpenMetrics] text format, also understood by Prometheus.
+//!
+//! The core records the IPC commands with their outcome and latency, the emitted events and the number of windows and webviews.
+//! Since the webviews choose the command and event names, only the first 512 names of each are tracked.
+//! Plugins and apps add their own values with [`Metrics::set_gauge`] and [`Metrics::increment_counter`],
+//! e.g. the state of the updater.
+//!
+//! The metrics are read with [`Metrics::render`], pushed periodically with [`Metrics::push_every`]
+//! or, with the `localhost-server` feature, scraped from the endpoint returned by [`Metrics::serve`].
+//!
+//! [OpenMetrics]: https://openmetrics.io
+
+use std::{collections::BTreeMap, fmt::Write, sync::Mutex, time::Duration};
+
+use crate::{sealed::ManagerBase, AppHandle, Manager, Runtime};
+
+/// The content type of [`Metrics::render`].
+pub const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// The upper bounds in seconds of the buckets of the command duration histogram.
+const DURATION_BUCKETS: [f64; 10] = [0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 1.0, 5.0];
+
+/// How many commands and events are tracked each, their names come from the webviews so they are not trusted.
+const MAX_LABELS: usize = 512;
+
+#[derive(Default)]
+struct CommandStats {
+  ok: u64,
+  error: u64,
+  buckets: [u64; DURATION_BUCKETS.len()],
+  sum: f64,
+}
+
+#[derive(Default)]
+struct Registry {
+  commands: BTreeMap<String, CommandStats>,
+  events: BTreeMap<String, u64>,
+  counters: BTreeMap<String, f64>,
+  gauges: BTreeMap<String, f64>,
+}
+
+impl Registry {
+  fn record_command(&mut self, command: &str, elapsed: Duration, ok: bool) {
+    if self.commands.len() >= MAX_LABELS && !self.commands.contains_key(command) {
+      return;
+    }
+    let stats = self.commands.entry(command.to_string()).or_default();
+    if ok {
+      stats.ok += 1;
+    } else {
+      stats.error += 1;
+    }
+    let seconds = elapsed.as_secs_f64();
+    stats.sum += seconds;
+    for (bucket, bound) in stats.buckets.iter_mut().zip(DURATION_BUCKETS) {
+      if seconds <= bound {
+        *bucket += 1;
+      }
+    }
+  }
+
+  fn record_event(&mut self, event: &str) {
+    if self.events.len() >= MAX_LABELS && !self.events.contains_key(event) {
+      return;
+    }
+    *self.events.entry(event.to_string()).or_default() += 1;
+  }
+
+  fn render(&self, windows: usize, webviews: usize) -> String {
+    let mut out = String::new();
+
+    out.push_str("# TYPE tauri_commands counter\n# HELP tauri_commands IPC commands resolved.\n");
+    for (command, stats) in &self.commands {
+      let command = escape_label(command);
+      let _ = writeln!(
+        out,
+        "tauri_commands_total{{command=\"{command}\",status=\"ok\"}} {}",
+        stats.ok
+      );
+      let _ = writeln!(
+        out,
+        "tauri_commands_total{{command=\"{command}\",status=\"error\"}} {}",
+        stats.error
+      );
+    }
+
+    out.push_str("# TYPE tauri_command_duration_seconds histogram\n# HELP tauri_command_duration_seconds Time from the IPC request to its response.\n");
+    for (command, stats) in &self.commands {
+      let command = escape_label(command);
+      for (count, bound) in stats.buckets.iter().zip(DURATION_BUCKETS) {
+        let _ = writeln!(
+          out,
+          "tauri_command_duration_seconds_bucket{{command=\"{command}\",le=\"{bound}\"}} {count}"
+        );
+      }
+      let count = stats.ok + stats.error;
+      let _ = writeln!(
+        out,
+        "tauri_command_duration_seconds_bucket{{command=\"{command}\",le=\"+Inf\"}} {count}"
+      );
+      let _ = writeln!(
+        out,
+        "tauri_command_duration_seconds_sum{{command=\"{command}\"}} {}",
+        stats.sum
+      );
+      let _ = writeln!(
+        out,
+        "tauri_command_duration_seconds_count{{command=\"{command}\"}} {count}"
+      );
+    }
+
+    out.push_str("# TYPE tauri_events counter\n# HELP tauri_events Events emitted.\n");
+    for (event, count) in &self.events {
+      let _ = writeln!(
+        out,
+        "tauri_events_total{{event=\"{}\"}} {count}",
+        escape_label(event)
+      );
+    }
+
+    let _ = writeln!(
+      out,
+      "# TYPE tauri_windows gauge\n# HELP tauri_windows Open windows.\ntauri_windows {windows}"
+    );
+    let _ = writeln!(
+      out,
+      "# TYPE tauri_webviews gauge\n# HELP tauri_webviews Open webviews.\ntauri_webviews {webviews}"
+    );
+
+    for (name, value) in &self.counters {
+      let _ = writeln!(out, "# TYPE {name} counter\n{name}_total {value}");
+    }
+    for (name, value) in &self.gauges {
+      let _ = writeln!(out, "# TYPE {name} gauge\n{name} {value}");
+    }
+
+    out.push_str("# EOF\n");
+    out
+  }
+}
+
+/// The metrics of the app, accessed with [`Manager::metrics`].
+///
+/// See the [module documentation](self).
+pub struct Metrics<R: Runtime> {
+  app: AppHandle<R>,
+  registry: Mutex<Registry>,
+}
+
+impl<R: Runtime> Metrics<R> {
+  pub(crate) fn new(app: AppHandle<R>) -> Self {
+    Self {
+      app,
+      registry: Default::default(),
+    }
+  }
+
+  pub(crate) fn record_command(&self, command: &str, elapsed: Duration, ok: bool) {
+    self
+      .registry
+      .lock()
+      .unwrap()
+      .record_command(command, elapsed, ok);
+  }
+
+  pub(crate) fn record_event(&self, event: &str) {
+    self.registry.lock().unwrap().record_event(event);
+  }
+
+  /// Sets the value of a gauge, a value that can go up and down such as a queue length or a status.
+  ///
+  /// The name is sanitized to only contain ASCII letters, digits, `_` and `:`.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::Manager;
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     app.metrics().set_gauge("updater_update_available", 1.0);
+  ///     Ok(())
+  ///   });
+  /// ```
+  pub fn set_gauge(&self, name: &str, value: f64) {
+    self
+      .registry
+      .lock()
+      .unwrap()
+      .gauges
+      .insert(sanitize_name(name), value);
+  }
+
+  /// Adds the value to a counter, a value that only goes up such as a number of downloads.
+  ///
+  /// The name is sanitized to only contain ASCII letters, digits, `_` and `:`,
+  /// and is rendered with the `_total` suffix.
+  pub fn increment_counter(&self, name: &str, value: f64) {
+    *self
+      .registry
+      .lock()
+      .unwrap()
+      .counters
+      .entry(sanitize_name(name))
+      .or_default() += value;
+  }
+
+  /// Renders the metrics in the OpenMetrics text format, served with the [`CONTENT_TYPE`].
+  pub fn render(&self) -> String {
+    let windows = self.app.manager().windows().len();
+    let webviews = self.app.manager().webviews().len();
+    self.registry.lock().unwrap().render(windows, webviews)
+  }
+
+  /// Calls the handler with the rendered metrics at every interval, e.g. to send them to a push gateway.
+  pub fn push_every<F>(&self, interval: Duration, handler: F)
+  where
+    F: Fn(&AppHandle<R>, String) + Send + 'static,
+  {
+    let app = self.app.clone();
+    crate::async_runtime::spawn(async move {
+      loop {
+        tokio::time::sleep(interval).await;
+        let metrics = app.metrics().render();
+        handler(&app, metrics);
+      }
+    });
+  }
+
+  /// Serves the metrics on the shared localhost server, returning the URL to scrape including the server token.
+  #[cfg(feature = "localhost-server")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "localhost-server")))]
+  pub fn serve(&self) -> crate::Result<url::Url> {
+    let server = self.app.localhost_server();
+    server.route("metrics", http::Method::GET, "/", |app, _request| {
+      http::Response::builder()
+        .header(http::header::CONTENT_TYPE, CONTENT_TYPE)
+        .body(app.metrics().render().into_bytes().into())
+        .unwrap()
+    })?;
+    Ok(
+      server
+        .url("metrics", "/")
+        .expect("the localhost server runs once a route is registered"),
+    )
+  }
+}
+
+fn sanitize_name(name: &str) -> String {
+  let mut sanitized = name
+    .chars()
+    .map(|c| {
+      if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+        c
+      } else {
+        '_'
+      }
+    })
+    .collect::<String>();
+  if sanitized.is_empty() || sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+    sanitized.insert(0, '_');
+  }
+  sanitized
+}
+
+fn escape_label(value: &str) -> String {
+  value
+    .replace('\\', "\\\\")
+    .replace('"', "\\\"")
+    .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn renders_open_metrics() {
+    let mut registry = Registry::default();
+    registry.record_command("greet", Duration::from_millis(3), true);
+    registry.record_command("greet", Duration::from_secs(2), false);
+    registry.events.insert("download\"progress".into(), 4);
+    registry.gauges.insert(sanitize_name("updater.status"), 1.0);
+
+    let rendered = registry.render(2, 3);
+    assert!(rendered.contains("tauri_commands_total{command=\"greet\",status=\"ok\"} 1\n"));
+    assert!(rendered.contains("tauri_commands_total{command=\"greet\",status=\"error\"} 1\n"));
+    assert!(rendered
+      .contains("tauri_command_duration_seconds_bucket{command=\"greet\",le=\"0.0025\"} 0\n"));
+    assert!(rendered
+      .contains("tauri_command_duration_seconds_bucket{command=\"greet\",le=\"0.005\"} 1\n"));
+    assert!(
+      rendered.contains("tauri_command_duration_seconds_bucket{command=\"greet\",le=\"+Inf\"} 2\n")
+    );
+    assert!(rendered.contains("tauri_command_duration_seconds_count{command=\"greet\"} 2\n"));
+    assert!(rendered.contains("tauri_events_total{event=\"download\\\"progress\"} 4\n"));
+    assert!(rendered.contains("tauri_windows 2\n"));
+    assert!(rendered.contains("tauri_webviews 3\n"));
+    assert!(rendered.contains("updater_status 1\n"));
+    assert!(rendered.ends_with("# EOF\n"));
+  }
+
+  #[test]
+  fn caps_the_tracked_commands_and_events() {
+    let mut registry = Registry::default();
+    for i in 0..MAX_LABELS {
+      registry.record_command(&format!("command{i}"), Duration::ZERO, true);
+      registry.record_event(&format!("event{i}"));
+    }
+    registry.record_command("unknown", Duration::ZERO, false);
+    registry.record_event("unknown");
+    registry.record_command("command0", Duration::ZERO, true);
+    registry.record_event("event0");
+
+    assert_eq!(registry.commands.len(), MAX_LABELS);
+    assert!(!registry.commands.contains_key("unknown"));
+    assert_eq!(registry.commands["command0"].ok, 2);
+    assert_eq!(registry.events.len(), MAX_LABELS);
+    assert!(!registry.events.contains_key("unknown"));
+    assert_eq!(registry.events["event0"], 2);
+  }
+
+  #[test]
+  fn sanitizes_names() {
+    assert_eq!(sanitize_name("updater.status"), "updater_status");
+    assert_eq!(sanitize_name("1st"), "_1st");
+    assert_eq!(sanitize_name(""), "_");
+  }
+}
@@ -0,0 +1,12 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Network configuration and connectivity of the operating system.
+
+mod proxy;
+mod status;
+
+pub use proxy::{system_proxy, SystemProxy, SYSTEM_PROXY_CHANGED_EVENT};
+pub(crate) use status::watch as watch_status;
+pub use status::{status, ConnectionType, NetworkStatus, NETWORK_STATUS_CHANGED_EVENT};
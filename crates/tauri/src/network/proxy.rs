@@ -0,0 +1,477 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::Serialize;
+use url::Url;
+
+/// Event emitted with the new [`SystemProxy`] payload by [`crate::AppHandle::watch_system_proxy`]
+/// when the proxy configuration changes.
+pub const SYSTEM_PROXY_CHANGED_EVENT: &str = "tauri://system-proxy-changed";
+
+/// The proxy configuration of the system, which the webview follows.
+///
+/// See [`system_proxy`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SystemProxy {
+  /// The proxy used for `http` URLs, as `host:port`.
+  pub http: Option<String>,
+  /// The proxy used for `https` URLs, as `host:port`.
+  pub https: Option<String>,
+  /// The URL of the proxy auto-config (PAC) script.
+  pub pac_url: Option<String>,
+  /// Whether the proxy is discovered with the Web Proxy Auto-Discovery protocol (WPAD).
+  pub auto_detect: bool,
+  /// The hosts that are accessed directly, such as `localhost`, `*.example.com` or `.example.com`.
+  ///
+  /// On Windows, `<local>` matches all host names without a dot.
+  pub bypass: Vec<String>,
+}
+
+impl SystemProxy {
+  /// Whether no proxy is configured.
+  pub fn is_direct(&self) -> bool {
+    self.http.is_none() && self.https.is_none() && self.pac_url.is_none() && !self.auto_detect
+  }
+
+  /// The proxy URL to use for the given URL, `None` if it must be accessed directly.
+  ///
+  /// The PAC script and auto-discovery are not evaluated, only the static proxies are considered.
+  ///
+  /// # Examples
+  ///
+  /// Honoring the system proxy with `reqwest`:
+  ///
+  /// ```rust,ignore
+  /// let proxy = tauri::network::system_proxy()?;
+  /// let client = reqwest::Client::builder()
+  ///   .proxy(reqwest::Proxy::custom(move |url| proxy.proxy_for(url)))
+  ///   .build()?;
+  /// ```
+  pub fn proxy_for(&self, url: &Url) -> Option<Url> {
+    let proxy = match url.scheme() {
+      "http" | "ws" => self.http.as_ref()?,
+      "https" | "wss" => self.https.as_ref()?,
+      _ => return None,
+    };
+    if url.host_str().is_some_and(|host| self.bypasses(host)) {
+      return None;
+    }
+    proxy_url(proxy)
+  }
+
+  /// The `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY` environment variables matching this configuration,
+  /// to be set on sidecars and other child processes.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// let mut command = std::process::Command::new("my-sidecar");
+  /// command.envs(tauri::network::system_proxy()?.env_vars());
+  /// # Ok::<(), std::io::Error>(())
+  /// ```
+  pub fn env_vars(&self) -> Vec<(&'static str, String)> {
+    let mut vars = Vec::new();
+    if let Some(url) = self.http.as_deref().and_then(proxy_url) {
+      vars.push(("HTTP_PROXY", url.to_string()));
+    }
+    if let Some(url) = self.https.as_deref().and_then(proxy_url) {
+      vars.push(("HTTPS_PROXY", url.to_string()));
+    }
+    if !self.bypass.is_empty() {
+      let no_proxy = self
+        .bypass
+        .iter()
+        .map(|host| host.trim_start_matches('*'))
+        .collect::<Vec<_>>()
+        .join(",");
+      vars.push(("NO_PROXY", no_proxy));
+    }
+    vars
+  }
+
+  fn bypasses(&self, host: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    self.bypass.iter().any(|pattern| {
+      let pattern = pattern.trim().to_ascii_lowercase();
+      if pattern == "*" {
+        true
+      } else if pattern == "<local>" {
+        !host.contains('.')
+      } else if let Some(suffix) = pattern.strip_prefix('*') {
+        host.ends_with(suffix)
+      } else if pattern.starts_with('.') {
+        host.ends_with(&pattern) || host == pattern[1..]
+      } else {
+        host == pattern
+      }
+    })
+  }
+}
+
+/// Returns the effective proxy configuration of the system.
+///
+/// The `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY` environment variables take precedence
+/// over the system settings when set.
+///
+/// # Platform-specific
+///
+/// - **Windows:** Reads the proxy settings of the current user, which are also used by WebView2.
+/// - **macOS:** Reads the proxy settings of the active network services.
+/// - **Linux:** Reads the GNOME proxy settings.
+/// - **Android / iOS:** Only the environment variables are read.
+///
+/// # Errors
+///
+/// On Windows, an error of kind [`std::io::ErrorKind::Unsupported`] is returned without the `system-proxy` feature.
+pub fn system_proxy() -> std::io::Result<SystemProxy> {
+  let mut proxy = platform::system_proxy()?;
+
+  let env = |name: &str| {
+    std::env::var(name)
+      .or_else(|_| std::env::var(name.to_lowercase()))
+      .ok()
+      .filter(|value| !value.is_empty())
+  };
+  let http = env("HTTP_PROXY");
+  let https = env("HTTPS_PROXY");
+  if http.is_some() || https.is_some() {
+    proxy.http = http.map(|url| strip_scheme(&url));
+    proxy.https = https.map(|url| strip_scheme(&url));
+    proxy.pac_url = None;
+    proxy.auto_detect = false;
+  }
+  if let Some(no_proxy) = env("NO_PROXY") {
+    proxy.bypass = split_list(&no_proxy);
+  }
+
+  Ok(proxy)
+}
+
+/// Parses a list of proxies in the `host:port` or `scheme=host:port;...` format used by Windows.
+#[cfg(any(all(windows, feature = "system-proxy"), test))]
+fn parse_proxy_list(list: &str) -> (Option<String>, Option<String>) {
+  let mut http = None;
+  let mut https = None;
+  for entry in split_list(list) {
+    match entry.split_once('=') {
+      Some((scheme, proxy)) => match scheme {
+        "http" => http = Some(strip_scheme(proxy)),
+        "https" => https = Some(strip_scheme(proxy)),
+        _ => {}
+      },
+      None => {
+        http.get_or_insert_with(|| strip_scheme(&entry));
+        https.get_or_insert_with(|| strip_scheme(&entry));
+      }
+    }
+  }
+  (http, https)
+}
+
+fn split_list(list: &str) -> Vec<String> {
+  list
+    .split(|c: char| c == ';' || c == ',' || c.is_whitespace())
+    .filter(|entry| !entry.is_empty())
+    .map(Into::into)
+    .collect()
+}
+
+fn strip_scheme(proxy: &str) -> String {
+  let proxy = proxy.split_once("://").map_or(proxy, |(_, rest)| rest);
+  proxy.trim_end_matches('/').to_string()
+}
+
+fn proxy_url(proxy: &str) -> Option<Url> {
+  Url::parse(&format!("http://{proxy}")).ok()
+}
+
+#[cfg(all(windows, feature = "system-proxy"))]
+mod platform {
+  use super::{parse_proxy_list, split_list, SystemProxy};
+  use windows::{
+    core::PWSTR,
+    Win32::{
+      Foundation::{GlobalFree, HGLOBAL},
+      Networking::WinHttp::{
+        WinHttpGetIEProxyConfigForCurrentUser, WINHTTP_CURRENT_USER_IE_PROXY_CONFIG,
+      },
+    },
+  };
+
+  /// Reads and frees a string allocated by WinHTTP.
+  unsafe fn take_string(value: PWSTR) -> Option<String> {
+    if value.is_null() {
+      return None;
+    }
+    let string = value.to_string().ok();
+    let _ = GlobalFree(HGLOBAL(value.0.cast()));
+    string.filter(|s| !s.is_empty())
+  }
+
+  pub fn system_proxy() -> std::io::Result<SystemProxy> {
+    let mut config = WINHTTP_CURRENT_USER_IE_PROXY_CONFIG::default();
+    if unsafe { WinHttpGetIEProxyConfigForCurrentUser(&mut config) }.is_err() {
+      return Ok(SystemProxy::default());
+    }
+
+    let (pac_url, proxy, bypass) = unsafe {
+      (
+        take_string(config.lpszAutoConfigUrl),
+        take_string(config.lpszProxy),
+        take_string(config.lpszProxyBypass),
+      )
+    };
+    let (http, https) = proxy.as_deref().map(parse_proxy_list).unwrap_or_default();
+
+    Ok(SystemProxy {
+      http,
+      https,
+      pac_url,
+      auto_detect: config.fAutoDetect.as_bool(),
+      bypass: bypass.as_deref().map(split_list).unwrap_or_default(),
+    })
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use super::SystemProxy;
+  use objc2::{msg_send, rc::Retained, runtime::AnyObject};
+  use objc2_foundation::NSString;
+
+  #[link(name = "SystemConfiguration", kind = "framework")]
+  extern "C" {
+    fn SCDynamicStoreCopyProxies(store: *const std::ffi::c_void) -> *mut AnyObject;
+  }
+
+  unsafe fn value<'a>(proxies: &'a AnyObject, key: &str) -> Option<&'a AnyObject> {
+    let key = NSString::from_str(key);
+    let value: *const AnyObject = msg_send![proxies, objectForKey: &*key];
+    value.as_ref()
+  }
+
+  unsafe fn enabled(proxies: &AnyObject, key: &str) -> bool {
+    value(proxies, key).is_some_and(|number| {
+      let value: isize = msg_send![number, integerValue];
+      value != 0
+    })
+  }
+
+  unsafe fn string(value: &AnyObject) -> String {
+    (*(value as *const AnyObject as *const NSString)).to_string()
+  }
+
+  unsafe fn proxy(proxies: &AnyObject, name: &str) -> Option<String> {
+    if !enabled(proxies, &format!("{name}Enable")) {
+      return None;
+    }
+    let host = string(value(proxies, &format!("{name}Proxy"))?);
+    match value(proxies, &format!("{name}Port")) {
+      Some(port) => {
+        let port: isize = msg_send![port, integerValue];
+        Some(format!("{host}:{port}"))
+      }
+      None => Some(host),
+    }
+  }
+
+  pub fn system_proxy() -> std::io::Result<SystemProxy> {
+    unsafe {
+      let Some(proxies) = Retained::from_raw(SCDynamicStoreCopyProxies(std::ptr::null())) else {
+        return Ok(SystemProxy::default());
+      };
+
+      let pac_url = if enabled(&proxies, "ProxyAutoConfigEnable") {
+        value(&proxies, "ProxyAutoConfigURLString").map(|url| string(url))
+      } else {
+        None
+      };
+
+      let mut bypass = Vec::new();
+      if let Some(exceptions) = value(&proxies, "ExceptionsList") {
+        let count: usize = msg_send![exceptions, count];
+        for i in 0..count {
+          let host: *const AnyObject = msg_send![exceptions, objectAtIndex: i];
+          if let Some(host) = host.as_ref() {
+            bypass.push(string(host));
+          }
+        }
+      }
+
+      Ok(SystemProxy {
+        http: proxy(&proxies, "HTTP"),
+        https: proxy(&proxies, "HTTPS"),
+        pac_url,
+        auto_detect: enabled(&proxies, "ProxyAutoDiscoveryEnable"),
+        bypass,
+      })
+    }
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use super::SystemProxy;
+  use gtk::gio::{prelude::*, Settings, SettingsSchemaSource};
+
+  const PROXY_SCHEMA: &str = "org.gnome.system.proxy";
+
+  fn settings(schema: &str) -> Option<Settings> {
+    // creating settings for a schema that is not installed aborts the process
+    SettingsSchemaSource::default()?.lookup(schema, true)?;
+    Some(Settings::new(schema))
+  }
+
+  fn proxy(schema: &str) -> Option<String> {
+    let settings = settings(schema)?;
+    let host = settings.string("host");
+    if host.is_empty() {
+      return None;
+    }
+    match settings.int("port") {
+      0 => Some(host.to_string()),
+      port => Some(format!("{host}:{port}")),
+    }
+  }
+
+  pub fn system_proxy() -> std::io::Result<SystemProxy> {
+    let Some(settings) = settings(PROXY_SCHEMA) else {
+      return Ok(SystemProxy::default());
+    };
+
+    let bypass = settings
+      .strv("ignore-hosts")
+      .iter()
+      .map(|host| host.to_string())
+      .collect();
+
+    Ok(match settings.string("mode").as_str() {
+      "manual" => SystemProxy {
+        http: proxy(&format!("{PROXY_SCHEMA}.http")),
+        https: proxy(&format!("{PROXY_SCHEMA}.https")),
+        bypass,
+        ..Default::default()
+      },
+      "auto" => {
+        let pac_url = settings.string("autoconfig-url");
+        SystemProxy {
+          auto_detect: pac_url.is_empty(),
+          pac_url: Some(pac_url.to_string()).filter(|url| !url.is_empty()),
+          bypass,
+          ..Default::default()
+        }
+      }
+      _ => SystemProxy::default(),
+    })
+  }
+}
+
+#[cfg(all(windows, not(feature = "system-proxy")))]
+mod platform {
+  use super::SystemProxy;
+
+  pub fn system_proxy() -> std::io::Result<SystemProxy> {
+    Err(std::io::Error::new(
+      std::io::ErrorKind::Unsupported,
+      "reading the system proxy on Windows requires the `system-proxy` feature",
+    ))
+  }
+}
+
+#[cfg(not(any(
+  windows,
+  target_os = "macos",
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+)))]
+mod platform {
+  use super::SystemProxy;
+
+  pub fn system_proxy() -> std::io::Result<SystemProxy> {
+    Ok(SystemProxy::default())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_windows_proxy_list() {
+    assert_eq!(
+      parse_proxy_list("proxy.corp:8080"),
+      (
+        Some("proxy.corp:8080".into()),
+        Some("proxy.corp:8080".into())
+      )
+    );
+    assert_eq!(
+      parse_proxy_list("http=http://web:80;https=secure:443;ftp=files:21"),
+      (Some("web:80".into()), Some("secure:443".into()))
+    );
+    assert_eq!(parse_proxy_list(""), (None, None));
+  }
+
+  #[test]
+  fn resolves_proxy_for_url() {
+    let proxy = SystemProxy {
+      http: Some("proxy:3128".into()),
+      https: Some("proxy:3129".into()),
+      bypass: vec![
+        "localhost".into(),
+        "*.internal.corp".into(),
+        ".example.com".into(),
+        "<local>".into(),
+      ],
+      ..Default::default()
+    };
+    let proxy_for = |url: &str| {
+      proxy
+        .proxy_for(&url.parse().unwrap())
+        .map(|url| url.to_string())
+    };
+
+    assert_eq!(
+      proxy_for("http://tauri.app"),
+      Some("http://proxy:3128/".into())
+    );
+    assert_eq!(
+      proxy_for("https://tauri.app"),
+      Some("http://proxy:3129/".into())
+    );
+    assert_eq!(proxy_for("http://localhost:1420"), None);
+    assert_eq!(proxy_for("https://api.internal.corp"), None);
+    assert_eq!(proxy_for("https://example.com"), None);
+    assert_eq!(proxy_for("https://www.example.com"), None);
+    assert_eq!(proxy_for("http://intranet"), None);
+    assert_eq!(proxy_for("file:///etc/hosts"), None);
+  }
+
+  #[test]
+  fn env_vars() {
+    let proxy = SystemProxy {
+      http: Some("proxy:3128".into()),
+      bypass: vec!["localhost".into(), "*.corp".into()],
+      ..Default::default()
+    };
+    assert_eq!(
+      proxy.env_vars(),
+      vec![
+        ("HTTP_PROXY", "http://proxy:3128/".into()),
+        ("NO_PROXY", "localhost,.corp".into())
+      ]
+    );
+  }
+}
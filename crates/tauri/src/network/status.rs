@@ -0,0 +1,387 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::sync::Arc;
+
+use serde::Serialize;
+
+/// Event emitted with the new [`NetworkStatus`] payload by [`crate::AppHandle::watch_network_status`]
+/// when the network connectivity changes.
+pub const NETWORK_STATUS_CHANGED_EVENT: &str = "tauri://network-status-changed";
+
+/// The kind of network interface used to reach the internet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum ConnectionType {
+  /// No network connection.
+  None,
+  /// A wired connection.
+  Ethernet,
+  /// A wireless LAN connection.
+  Wifi,
+  /// A mobile broadband connection.
+  Cellular,
+  /// The connection type could not be determined.
+  Unknown,
+}
+
+/// The network connectivity of the system.
+///
+/// See [`status`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct NetworkStatus {
+  /// Whether a network connection is available, matching the webview `navigator.onLine` value.
+  pub online: bool,
+  /// Whether the connection is metered, in which case large transfers should be deferred.
+  pub metered: bool,
+  /// The type of the connection.
+  pub connection: ConnectionType,
+}
+
+impl NetworkStatus {
+  #[cfg(desktop)]
+  fn offline() -> Self {
+    Self {
+      online: false,
+      metered: false,
+      connection: ConnectionType::None,
+    }
+  }
+}
+
+/// Returns the current network connectivity of the system.
+///
+/// # Platform-specific
+///
+/// - **macOS:** The connection is never reported as metered and its type is [`ConnectionType::Unknown`].
+/// - **Windows:** Requires the `network-status` feature, otherwise the system is always reported as online with an unknown connection type.
+/// - **Android / iOS:** Unsupported, the system is always reported as online with an unknown connection type.
+pub fn status() -> NetworkStatus {
+  platform::status()
+}
+
+/// Calls `on_change` when the network connectivity changes, from the platform notifications.
+///
+/// Must be called on the main thread.
+pub(crate) fn watch(on_change: impl Fn() + Send + Sync + 'static) -> std::io::Result<()> {
+  platform::watch(Arc::new(on_change))
+}
+
+/// Finds the interface of the default IPv4 route from the content of `/proc/net/route`.
+#[cfg(any(target_os = "linux", test))]
+fn default_route_interface(routes: &str) -> Option<&str> {
+  routes.lines().skip(1).find_map(|line| {
+    let mut columns = line.split_whitespace();
+    let interface = columns.next()?;
+    let destination = columns.next()?;
+    (destination == "00000000").then_some(interface)
+  })
+}
+
+#[cfg(all(windows, feature = "network-status"))]
+mod platform {
+  use super::{ConnectionType, NetworkStatus};
+  use std::sync::Arc;
+  use windows::Networking::Connectivity::{
+    NetworkConnectivityLevel, NetworkCostType, NetworkInformation, NetworkStatusChangedEventHandler,
+  };
+
+  pub fn status() -> NetworkStatus {
+    let Ok(profile) = NetworkInformation::GetInternetConnectionProfile() else {
+      return NetworkStatus::offline();
+    };
+
+    let online = profile
+      .GetNetworkConnectivityLevel()
+      .is_ok_and(|level| level != NetworkConnectivityLevel::None);
+    if !online {
+      return NetworkStatus::offline();
+    }
+
+    let metered = profile
+      .GetConnectionCost()
+      .and_then(|cost| cost.NetworkCostType())
+      .is_ok_and(|cost| cost == NetworkCostType::Fixed || cost == NetworkCostType::Variable);
+    let connection = if profile.IsWlanConnectionProfile().unwrap_or_default() {
+      ConnectionType::Wifi
+    } else if profile.IsWwanConnectionProfile().unwrap_or_default() {
+      ConnectionType::Cellular
+    } else {
+      ConnectionType::Ethernet
+    };
+
+    NetworkStatus {
+      online,
+      metered,
+      connection,
+    }
+  }
+
+  pub fn watch(on_change: Arc<dyn Fn() + Send + Sync>) -> std::io::Result<()> {
+    // the registration is kept for the lifetime of the app
+    NetworkInformation::NetworkStatusChanged(&NetworkStatusChangedEventHandler::new(move |_| {
+      on_change();
+      Ok(())
+    }))?;
+    Ok(())
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use super::{ConnectionType, NetworkStatus};
+  use std::{ffi::c_void, sync::Arc};
+
+  const REACHABLE: u32 = 1 << 1;
+  const CONNECTION_REQUIRED: u32 = 1 << 2;
+
+  type Callback = extern "C" fn(target: *const c_void, flags: u32, info: *mut c_void);
+
+  #[repr(C)]
+  struct ReachabilityContext {
+    version: isize,
+    info: *mut c_void,
+    retain: *const c_void,
+    release: *const c_void,
+    copy_description: *const c_void,
+  }
+
+  #[link(name = "SystemConfiguration", kind = "framework")]
+  extern "C" {
+    fn SCNetworkReachabilityCreateWithAddress(
+      allocator: *const c_void,
+      address: *const libc::sockaddr,
+    ) -> *const c_void;
+    fn SCNetworkReachabilityGetFlags(target: *const c_void, flags: *mut u32) -> u8;
+    fn SCNetworkReachabilitySetCallback(
+      target: *const c_void,
+      callout: Callback,
+      context: *mut ReachabilityContext,
+    ) -> u8;
+    fn SCNetworkReachabilityScheduleWithRunLoop(
+      target: *const c_void,
+      run_loop: *const c_void,
+      mode: *const c_void,
+    ) -> u8;
+  }
+
+  #[link(name = "CoreFoundation", kind = "framework")]
+  extern "C" {
+    static kCFRunLoopCommonModes: *const c_void;
+    fn CFRunLoopGetMain() -> *const c_void;
+    fn CFRelease(object: *const c_void);
+  }
+
+  /// Creates a reachability reference for the `0.0.0.0` address, which tracks the default route.
+  fn default_route_reachability() -> std::io::Result<*const c_void> {
+    let mut address: libc::sockaddr_in = unsafe { std::mem::zeroed() };
+    address.sin_len = std::mem::size_of::<libc::sockaddr_in>() as u8;
+    address.sin_family = libc::AF_INET as u8;
+    let target = unsafe {
+      SCNetworkReachabilityCreateWithAddress(
+        std::ptr::null(),
+        &address as *const libc::sockaddr_in as *const libc::sockaddr,
+      )
+    };
+    if target.is_null() {
+      Err(std::io::Error::other(
+        "failed to create network reachability",
+      ))
+    } else {
+      Ok(target)
+    }
+  }
+
+  pub fn status() -> NetworkStatus {
+    let Ok(target) = default_route_reachability() else {
+      return NetworkStatus::offline();
+    };
+    let mut flags = 0;
+    let ok = unsafe { SCNetworkReachabilityGetFlags(target, &mut flags) } != 0;
+    unsafe { CFRelease(target) };
+
+    if ok && flags & REACHABLE != 0 && flags & CONNECTION_REQUIRED == 0 {
+      NetworkStatus {
+        online: true,
+        metered: false,
+        connection: ConnectionType::Unknown,
+      }
+    } else {
+      NetworkStatus::offline()
+    }
+  }
+
+  extern "C" fn on_reachability_changed(_target: *const c_void, _flags: u32, info: *mut c_void) {
+    let on_change = unsafe { &*(info as *const Arc<dyn Fn() + Send + Sync>) };
+    on_change();
+  }
+
+  pub fn watch(on_change: Arc<dyn Fn() + Send + Sync>) -> std::io::Result<()> {
+    let target = default_route_reachability()?;
+    // the reachability reference and the callback are kept for the lifetime of the app
+    let mut context = ReachabilityContext {
+      version: 0,
+      info: Box::into_raw(Box::new(on_change)).cast(),
+      retain: std::ptr::null(),
+      release: std::ptr::null(),
+      copy_description: std::ptr::null(),
+    };
+    let scheduled = unsafe {
+      SCNetworkReachabilitySetCallback(target, on_reachability_changed, &mut context) != 0
+        && SCNetworkReachabilityScheduleWithRunLoop(
+          target,
+          CFRunLoopGetMain(),
+          kCFRunLoopCommonModes,
+        ) != 0
+    };
+    if scheduled {
+      Ok(())
+    } else {
+      Err(std::io::Error::other(
+        "failed to schedule network reachability notifications",
+      ))
+    }
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use super::{ConnectionType, NetworkStatus};
+  use gtk::gio::{prelude::*, NetworkConnectivity, NetworkMonitor};
+  use std::sync::Arc;
+
+  #[cfg(target_os = "linux")]
+  fn connection_type() -> ConnectionType {
+    let Ok(routes) = std::fs::read_to_string("/proc/net/route") else {
+      return ConnectionType::Unknown;
+    };
+    let Some(interface) = super::default_route_interface(&routes) else {
+      return ConnectionType::Unknown;
+    };
+
+    let device = std::path::Path::new("/sys/class/net").join(interface);
+    if device.join("wireless").exists() || device.join("phy80211").exists() {
+      ConnectionType::Wifi
+    } else if interface.starts_with("ww") || interface.starts_with("rmnet") {
+      ConnectionType::Cellular
+    } else {
+      ConnectionType::Ethernet
+    }
+  }
+
+  #[cfg(not(target_os = "linux"))]
+  fn connection_type() -> ConnectionType {
+    ConnectionType::Unknown
+  }
+
+  pub fn status() -> NetworkStatus {
+    let monitor = NetworkMonitor::default();
+    if !monitor.is_network_available() || monitor.connectivity() == NetworkConnectivity::Local {
+      return NetworkStatus::offline();
+    }
+
+    NetworkStatus {
+      online: true,
+      metered: monitor.is_network_metered(),
+      connection: connection_type(),
+    }
+  }
+
+  pub fn watch(on_change: Arc<dyn Fn() + Send + Sync>) -> std::io::Result<()> {
+    let monitor = NetworkMonitor::default();
+    let on_network_change = on_change.clone();
+    monitor.connect_network_changed(move |_, _| on_network_change());
+    let on_metered_change = on_change.clone();
+    monitor.connect_network_metered_notify(move |_| on_metered_change());
+    monitor.connect_connectivity_notify(move |_| on_change());
+    Ok(())
+  }
+}
+
+#[cfg(all(windows, not(feature = "network-status")))]
+mod platform {
+  use super::{ConnectionType, NetworkStatus};
+  use std::sync::Arc;
+
+  pub fn status() -> NetworkStatus {
+    NetworkStatus {
+      online: true,
+      metered: false,
+      connection: ConnectionType::Unknown,
+    }
+  }
+
+  pub fn watch(_on_change: Arc<dyn Fn() + Send + Sync>) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+      std::io::ErrorKind::Unsupported,
+      "watching the network status on Windows requires the `network-status` feature",
+    ))
+  }
+}
+
+#[cfg(not(any(
+  windows,
+  target_os = "macos",
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+)))]
+mod platform {
+  use super::{ConnectionType, NetworkStatus};
+  use std::sync::Arc;
+
+  pub fn status() -> NetworkStatus {
+    NetworkStatus {
+      online: true,
+      metered: false,
+      connection: ConnectionType::Unknown,
+    }
+  }
+
+  pub fn watch(_on_change: Arc<dyn Fn() + Send + Sync>) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn finds_default_route_interface() {
+    let routes = "Iface\tDestination\tGateway\tFlags\tRefCnt\tUse\tMetric\tMask\tMTU\tWindow\tIRTT
+docker0\t000011AC\t00000000\t0001\t0\t0\t0\t0000FFFF\t0\t0\t0
+wlp2s0\t00000000\t0101A8C0\t0003\t0\t0\t600\t00000000\t0\t0\t0
+wlp2s0\t0001A8C0\t00000000\t0001\t0\t0\t600\t00FFFFFF\t0\t0\t0";
+    assert_eq!(default_route_interface(routes), Some("wlp2s0"));
+    assert_eq!(
+      default_route_interface("Iface\tDestination\tGateway\n"),
+      None
+    );
+  }
+
+  #[test]
+  fn serializes_status() {
+    let status = NetworkStatus {
+      online: true,
+      metered: true,
+      connection: ConnectionType::Cellular,
+    };
+    assert_eq!(
+      serde_json::to_value(status).unwrap(),
+      serde_json::json!({ "online": true, "metered": true, "connection": "cellular" })
+    );
+  }
+}
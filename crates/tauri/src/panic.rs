@@ -0,0 +1,232 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Panics caught in development builds.
+//!
+//! In development, the panics of the command handlers and of the event listeners are caught instead of
+//! aborting the process or dropping the command response. The command promise is rejected with the panic message,
+//! the webviews show an overlay with the [`PanicReport`] and the [`PANIC_EVENT`] event is emitted with it.
+//!
+//! A command panic is not resumed, the command is considered handled. A listener panic is resumed once the
+//! other listeners of the event ran and the panic was reported, so the emit call still panics like in release builds.
+//!
+//! Release builds are not affected, the panics unwind as usual.
+
+#![cfg_attr(not(dev), allow(dead_code))]
+
+use std::{any::Any, backtrace::Backtrace, cell::RefCell, panic::AssertUnwindSafe, sync::Once};
+
+use futures_util::FutureExt;
+use serde::Serialize;
+use serialize_to_javascript::{default_template, DefaultTemplate, Template};
+
+use crate::{
+  ipc::{InvokeError, InvokeResolver},
+  manager::AppManager,
+  Runtime, Webview,
+};
+
+/// Event emitted with the [`PanicReport`] when a panic is caught, in development only.
+pub const PANIC_EVENT: &str = "tauri://panic";
+
+/// Where a caught panic happened.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum PanicSource {
+  /// A command handler.
+  Command {
+    /// The command name, e.g. `plugin:app|version` for plugin commands.
+    name: String,
+    /// The label of the webview that invoked the command.
+    webview: String,
+  },
+  /// A Rust event listener.
+  Event {
+    /// The event name.
+    name: String,
+  },
+}
+
+/// A panic caught in development.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct PanicReport {
+  /// The panic message.
+  pub message: String,
+  /// The source file location of the panic, e.g. `src/main.rs:10:5`.
+  pub location: Option<String>,
+  /// The backtrace of the panic.
+  pub backtrace: Option<String>,
+  /// Where the panic happened.
+  pub source: PanicSource,
+}
+
+thread_local! {
+  /// The location and backtrace of the last panic of the thread, recorded by the panic hook.
+  static LAST_PANIC: RefCell<Option<(Option<String>, String)>> = const { RefCell::new(None) };
+}
+
+/// Records the location and backtrace of the panics, which are not part of the payload caught by `catch_unwind`.
+///
+/// The previous hook is still called, so the panics are also printed to stderr.
+pub(crate) fn install_hook() {
+  static HOOK: Once = Once::new();
+  HOOK.call_once(|| {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+      let location = info.location().map(ToString::to_string);
+      let backtrace = Backtrace::force_capture().to_string();
+      LAST_PANIC.with(|last| last.borrow_mut().replace((location, backtrace)));
+      previous(info);
+    }));
+  });
+}
+
+impl PanicReport {
+  pub(crate) fn new(payload: &(dyn Any + Send), source: PanicSource) -> Self {
+    let message = payload
+      .downcast_ref::<&str>()
+      .map(ToString::to_string)
+      .or_else(|| payload.downcast_ref::<String>().cloned())
+      .unwrap_or_else(|| "Box<dyn Any>".into());
+    let (location, backtrace) = match LAST_PANIC.with(|last| last.borrow_mut().take()) {
+      Some((location, backtrace)) => (location, Some(backtrace)),
+      None => (None, None),
+    };
+    Self {
+      message,
+      location,
+      backtrace,
+      source,
+    }
+  }
+}
+
+#[derive(Template)]
+#[default_template("../scripts/panic-overlay.js")]
+struct PanicOverlay<'a> {
+  report: &'a PanicReport,
+}
+
+/// Shows the overlay in the webviews and emits the [`PANIC_EVENT`] event.
+pub(crate) fn report<R: Runtime>(manager: &AppManager<R>, report: PanicReport) {
+  log::error!("caught panic: {}", report.message);
+
+  match (PanicOverlay { report: &report }).render_default(&Default::default()) {
+    Ok(script) => {
+      if let Err(e) = manager.webview.eval_script_all(script.into_string()) {
+        log::error!("failed to show the panic overlay: {e}");
+      }
+    }
+    Err(e) => log::error!("failed to render the panic overlay: {e}"),
+  }
+
+  // a panicking listener of the panic event would be reported forever
+  if matches!(&report.source, PanicSource::Event { name } if name == PANIC_EVENT) {
+    return;
+  }
+  if let Err(e) = manager.emit(PANIC_EVENT, report) {
+    log::error!("failed to emit the panic event: {e}");
+  }
+}
+
+fn command_panic<R: Runtime>(
+  webview: &Webview<R>,
+  command: &str,
+  payload: Box<dyn Any + Send>,
+) -> InvokeError {
+  let panic = PanicReport::new(
+    &*payload,
+    PanicSource::Command {
+      name: command.into(),
+      webview: webview.label().into(),
+    },
+  );
+  let error = InvokeError::from(format!("command {command} panicked: {}", panic.message));
+  report(&webview.manager, panic);
+  error
+}
+
+/// Runs the synchronous part of the command handler, returning whether the command was handled.
+///
+/// In development, a panic rejects the command promise and the command is considered handled.
+#[cfg_attr(not(dev), allow(unused_variables))]
+pub(crate) fn catch_command<R: Runtime>(
+  resolver: &InvokeResolver<R>,
+  handler: impl FnOnce() -> bool,
+) -> bool {
+  #[cfg(dev)]
+  {
+    match std::panic::catch_unwind(AssertUnwindSafe(handler)) {
+      Ok(handled) => handled,
+      Err(payload) => {
+        let error = command_panic(&resolver.webview, &resolver.cmd, payload);
+        resolver.clone().invoke_error(error);
+        true
+      }
+    }
+  }
+  #[cfg(not(dev))]
+  {
+    handler()
+  }
+}
+
+/// Wraps the async command task to catch its panic in development, rejecting the command promise instead of dropping it.
+pub(crate) fn catch_task<R, T, F>(
+  webview: &Webview<R>,
+  command: &str,
+  task: F,
+) -> impl std::future::Future<Output = Result<T, InvokeError>> + Send + 'static
+where
+  R: Runtime,
+  F: std::future::Future<Output = Result<T, InvokeError>> + Send + 'static,
+{
+  let webview = webview.clone();
+  let command = command.to_string();
+  AssertUnwindSafe(task).catch_unwind().map(move |result| {
+    result.unwrap_or_else(|payload| Err(command_panic(&webview, &command, payload)))
+  })
+}
+
+#[cfg(all(test, dev))]
+mod tests {
+  use super::*;
+  use crate::{test::mock_app, Emitter, Listener};
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn reports_listener_panics() {
+    let app = mock_app();
+
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let reports_ = reports.clone();
+    app.listen_any(PANIC_EVENT, move |event| {
+      let report: serde_json::Value = serde_json::from_str(event.payload()).unwrap();
+      reports_.lock().unwrap().push(report);
+    });
+    app.listen_any("broken", |_| panic!("listener failed"));
+    let called = Arc::new(Mutex::new(false));
+    let called_ = called.clone();
+    app.listen_any("broken", move |_| *called_.lock().unwrap() = true);
+
+    let panic = std::panic::catch_unwind(AssertUnwindSafe(|| app.emit("broken", ())))
+      .expect_err("the listener panic is resumed");
+    assert_eq!(panic.downcast_ref::<&str>(), Some(&"listener failed"));
+
+    // the other listeners are still called
+    assert!(*called.lock().unwrap());
+    let reports = reports.lock().unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0]["message"], "listener failed");
+    assert_eq!(reports[0]["source"]["type"], "event");
+    assert_eq!(reports[0]["source"]["name"], "broken");
+    assert!(reports[0]["location"]
+      .as_str()
+      .unwrap()
+      .contains("panic.rs"));
+  }
+}
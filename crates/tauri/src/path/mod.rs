@@ -20,6 +20,8 @@ use crate::error::*;
 mod android;
 #[cfg(not(target_os = "android"))]
 mod desktop;
+#[cfg(not(target_os = "android"))]
+mod resource;
 
 #[cfg(target_os = "android")]
 pub use android::PathResolver;
@@ -319,17 +321,7 @@ fn resolve_path<R: Runtime>(
   if let Some(path) = path {
     // use the same path resolution mechanism as the bundler's resource injection algorithm
     if resolve_resource {
-      let mut resource_path = PathBuf::new();
-      for component in path.components() {
-        match component {
-          Component::Prefix(_) => {}
-          Component::RootDir => resource_path.push("_root_"),
-          Component::CurDir => {}
-          Component::ParentDir => resource_path.push("_up_"),
-          Component::Normal(p) => resource_path.push(p),
-        }
-      }
-      base_dir_path.push(resource_path);
+      base_dir_path.push(bundled_resource_path(&path));
     } else {
       base_dir_path.push(path);
     }
@@ -338,6 +330,57 @@ fn resolve_path<R: Runtime>(
   Ok(base_dir_path)
 }
 
+/// Maps a resource path to its location in the resource directory,
+/// using the same mechanism as the bundler's resource injection algorithm.
+pub(crate) fn bundled_resource_path(path: &Path) -> PathBuf {
+  let mut resource_path = PathBuf::new();
+  for component in path.components() {
+    match component {
+      Component::Prefix(_) => {}
+      Component::RootDir => resource_path.push("_root_"),
+      Component::CurDir => {}
+      Component::ParentDir => resource_path.push("_up_"),
+      Component::Normal(p) => resource_path.push(p),
+    }
+  }
+  resource_path
+}
+
+/// Checks that a file read by a command is in the app data, app local data, app cache or download directories,
+/// or allowed by the asset protocol scope, so the webviews cannot use the command on arbitrary files.
+///
+/// Returns the canonicalized path.
+#[cfg(any(feature = "integrity", feature = "font"))]
+pub(crate) fn check_command_path<R: Runtime, M: crate::Manager<R>>(
+  manager: &M,
+  path: &Path,
+) -> crate::Result<PathBuf> {
+  let forbidden = || crate::Error::PathForbidden(path.to_path_buf());
+  let path = dunce::canonicalize(path).map_err(|_| forbidden())?;
+
+  #[cfg(feature = "protocol-asset")]
+  if manager.asset_protocol_scope().is_allowed(&path) {
+    return Ok(path);
+  }
+
+  let resolver = manager.path();
+  let allowed = [
+    resolver.app_data_dir(),
+    resolver.app_local_data_dir(),
+    resolver.app_cache_dir(),
+    resolver.download_dir(),
+  ]
+  .into_iter()
+  .flatten()
+  .filter_map(|dir| dunce::canonicalize(dir).ok())
+  .any(|dir| path.starts_with(dir));
+  if allowed {
+    Ok(path)
+  } else {
+    Err(forbidden())
+  }
+}
+
 #[cfg(test)]
 mod test {
   use super::SafePathBuf;
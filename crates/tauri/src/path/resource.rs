@@ -0,0 +1,290 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "resource-integrity")]
+use sha2::{Digest, Sha256};
+
+use super::{BaseDirectory, PathResolver};
+use crate::{Result, Runtime};
+#[cfg(feature = "resource-integrity")]
+use {super::bundled_resource_path, crate::Error};
+
+impl<R: Runtime> PathResolver<R> {
+  /// Resolves a resource for the preferred languages of the user,
+  /// falling back to the resource itself when no localized variant exists.
+  ///
+  /// For a user preferring `fr-FR`, `help.html` resolves to the first existing file
+  /// of `help.fr-FR.html`, `help.fr.html` and `help.html` in the resource directory.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Returns an [`std::io::ErrorKind::Unsupported`] error unless the `localized-resources` feature is enabled,
+  ///   use [`Self::resolve_localized_resource_for`] to pass the languages instead.
+  /// - **Linux:** The languages are read from the `LANGUAGE`, `LC_ALL`, `LC_MESSAGES` and `LANG` environment variables.
+  /// - **iOS:** Unsupported, always resolves the resource itself.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::Manager;
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let help = app.path().resolve_localized_resource("help.html")?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  pub fn resolve_localized_resource<P: AsRef<Path>>(&self, path: P) -> Result<PathBuf> {
+    self.resolve_localized_resource_for(path, &preferred_languages()?)
+  }
+
+  /// Resolves a resource for the given languages, such as `["fr-FR", "en"]`, in order of preference,
+  /// falling back to the resource itself when no localized variant exists.
+  ///
+  /// See [`Self::resolve_localized_resource`].
+  pub fn resolve_localized_resource_for<P: AsRef<Path>, L: AsRef<str>>(
+    &self,
+    path: P,
+    languages: &[L],
+  ) -> Result<PathBuf> {
+    for candidate in localized_candidates(path.as_ref(), languages) {
+      let resolved = self.resolve(&candidate, BaseDirectory::Resource)?;
+      if resolved.exists() {
+        return Ok(resolved);
+      }
+    }
+    self.resolve(path, BaseDirectory::Resource)
+  }
+
+  /// Resolves a resource and verifies that its SHA-256 hash matches the hash recorded when the app was built,
+  /// so tampered or corrupted resources are rejected.
+  ///
+  /// The hashes of the files listed in `bundle > resources` are computed by the [`crate::generate_context`] macro.
+  /// Returns [`Error::UnknownResource`] for other files and [`Error::ResourceIntegrity`] when the hash does not match.
+  ///
+  /// Requires the `resource-integrity` feature, which makes the build hash the resources.
+  #[cfg(feature = "resource-integrity")]
+  pub fn resolve_verified_resource<P: AsRef<Path>>(&self, path: P) -> Result<PathBuf> {
+    let path = path.as_ref();
+    let expected = self
+      .0
+      .manager
+      .resource_hashes
+      .get(&resource_key(path))
+      .ok_or_else(|| Error::UnknownResource(path.to_path_buf()))?;
+
+    let resolved = self.resolve(path, BaseDirectory::Resource)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut std::fs::File::open(&resolved)?, &mut hasher)?;
+    let actual = hasher
+      .finalize()
+      .iter()
+      .map(|b| format!("{b:02x}"))
+      .collect::<String>();
+
+    if &actual == expected {
+      Ok(resolved)
+    } else {
+      Err(Error::ResourceIntegrity(resolved))
+    }
+  }
+}
+
+/// The key of a resource in the hashes recorded at build time: its bundled path with `/` separators.
+#[cfg(feature = "resource-integrity")]
+fn resource_key(path: &Path) -> String {
+  bundled_resource_path(path)
+    .components()
+    .map(|c| c.as_os_str().to_string_lossy())
+    .collect::<Vec<_>>()
+    .join("/")
+}
+
+/// The localized variants of a path for the given languages, from the most to the least specific.
+fn localized_candidates<L: AsRef<str>>(path: &Path, languages: &[L]) -> Vec<PathBuf> {
+  let Some(file_name) = path.file_name().map(|n| n.to_string_lossy()) else {
+    return Vec::new();
+  };
+  let (stem, extension) = match file_name.rsplit_once('.') {
+    Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+    _ => (file_name.as_ref(), None),
+  };
+
+  let mut candidates = Vec::new();
+  for language in languages {
+    let language = language.as_ref();
+    let mut tags = vec![language];
+    if let Some((primary, _)) = language.split_once('-') {
+      tags.push(primary);
+    }
+    for tag in tags {
+      let localized = match extension {
+        Some(extension) => format!("{stem}.{tag}.{extension}"),
+        None => format!("{stem}.{tag}"),
+      };
+      let candidate = path.with_file_name(localized);
+      if !candidates.contains(&candidate) {
+        candidates.push(candidate);
+      }
+    }
+  }
+  candidates
+}
+
+/// Converts a POSIX locale such as `fr_FR.UTF-8@euro` to a language tag such as `fr-FR`.
+#[cfg(any(
+  not(any(
+    all(windows, feature = "localized-resources"),
+    target_os = "macos",
+    target_os = "ios"
+  )),
+  test
+))]
+fn posix_locale_to_language_tag(locale: &str) -> Option<String> {
+  let locale = locale.split(['.', '@']).next()?;
+  if locale.is_empty() || locale == "C" || locale == "POSIX" {
+    return None;
+  }
+  Some(locale.replace('_', "-"))
+}
+
+/// The languages preferred by the user, as language tags such as `fr-FR`.
+fn preferred_languages() -> std::io::Result<Vec<String>> {
+  if cfg!(all(windows, not(feature = "localized-resources"))) {
+    return Err(std::io::Error::new(
+      std::io::ErrorKind::Unsupported,
+      "reading the preferred languages on Windows requires the `localized-resources` feature",
+    ));
+  }
+  Ok(platform::preferred_languages())
+}
+
+#[cfg(all(windows, feature = "localized-resources"))]
+mod platform {
+  use windows::Win32::Globalization::GetUserDefaultLocaleName;
+
+  pub fn preferred_languages() -> Vec<String> {
+    // LOCALE_NAME_MAX_LENGTH
+    let mut name = [0u16; 85];
+    let len = unsafe { GetUserDefaultLocaleName(&mut name) };
+    if len > 1 {
+      vec![String::from_utf16_lossy(&name[..len as usize - 1])]
+    } else {
+      Vec::new()
+    }
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use objc2::{class, msg_send, runtime::AnyObject};
+  use objc2_foundation::NSString;
+
+  pub fn preferred_languages() -> Vec<String> {
+    let mut languages = Vec::new();
+    unsafe {
+      let list: *const AnyObject = msg_send![class!(NSLocale), preferredLanguages];
+      if let Some(list) = list.as_ref() {
+        let count: usize = msg_send![list, count];
+        for i in 0..count {
+          let language: *const NSString = msg_send![list, objectAtIndex: i];
+          if let Some(language) = language.as_ref() {
+            languages.push(language.to_string());
+          }
+        }
+      }
+    }
+    languages
+  }
+}
+
+#[cfg(not(any(
+  all(windows, feature = "localized-resources"),
+  target_os = "macos",
+  target_os = "ios"
+)))]
+mod platform {
+  pub fn preferred_languages() -> Vec<String> {
+    let mut languages = Vec::new();
+    // LANGUAGE is a colon separated priority list, the locale variables hold a single locale
+    if let Ok(list) = std::env::var("LANGUAGE") {
+      languages.extend(
+        list
+          .split(':')
+          .filter_map(super::posix_locale_to_language_tag),
+      );
+    }
+    for name in ["LC_ALL", "LC_MESSAGES", "LANG"] {
+      if let Some(language) = std::env::var(name)
+        .ok()
+        .and_then(|locale| super::posix_locale_to_language_tag(&locale))
+      {
+        if !languages.contains(&language) {
+          languages.push(language);
+        }
+        break;
+      }
+    }
+    languages
+  }
+}
+
+#[cfg(target_os = "ios")]
+mod platform {
+  pub fn preferred_languages() -> Vec<String> {
+    Vec::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn localized_candidates_for_languages() {
+    assert_eq!(
+      localized_candidates(Path::new("docs/help.html"), &["fr-FR", "en"]),
+      vec![
+        PathBuf::from("docs/help.fr-FR.html"),
+        PathBuf::from("docs/help.fr.html"),
+        PathBuf::from("docs/help.en.html"),
+      ]
+    );
+    assert_eq!(
+      localized_candidates(Path::new("LICENSE"), &["de-DE", "de"]),
+      vec![PathBuf::from("LICENSE.de-DE"), PathBuf::from("LICENSE.de")]
+    );
+    assert_eq!(
+      localized_candidates(Path::new(".env"), &["es"]),
+      vec![PathBuf::from(".env.es")]
+    );
+    assert!(localized_candidates::<&str>(Path::new("help.html"), &[]).is_empty());
+  }
+
+  #[test]
+  fn posix_locales() {
+    assert_eq!(
+      posix_locale_to_language_tag("fr_FR.UTF-8@euro"),
+      Some("fr-FR".into())
+    );
+    assert_eq!(posix_locale_to_language_tag("de"), Some("de".into()));
+    assert_eq!(posix_locale_to_language_tag("C.UTF-8"), None);
+    assert_eq!(posix_locale_to_language_tag("POSIX"), None);
+  }
+
+  #[test]
+  #[cfg(feature = "resource-integrity")]
+  fn resource_keys() {
+    assert_eq!(
+      resource_key(Path::new("assets/help.html")),
+      "assets/help.html"
+    );
+    assert_eq!(
+      resource_key(Path::new("../shared/data.json")),
+      "_up_/shared/data.json"
+    );
+    assert_eq!(resource_key(Path::new("./icon.png")), "icon.png");
+  }
+}
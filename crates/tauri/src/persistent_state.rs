@@ -0,0 +1,418 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  collections::BTreeMap,
+  path::{Path, PathBuf},
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex, RwLock, RwLockReadGuard, Weak,
+  },
+  time::Duration,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::{async_runtime, AppHandle, Manager, Runtime};
+
+/// The default delay between a mutation and the save it triggers, see [`PersistentStateBuilder::debounce`].
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// The directory of the app data directory where the states are saved.
+const STATE_DIR: &str = "state";
+
+type Migration = dyn Fn(JsonValue) -> Result<JsonValue, String> + Send + Sync;
+
+/// The file format of a persisted state.
+#[derive(Serialize, Deserialize)]
+struct Persisted<T> {
+  version: u32,
+  value: T,
+}
+
+/// A managed state value saved to the app data directory.
+///
+/// The value is loaded when the state is built, saved after every mutation once the debounce delay elapsed,
+/// and saved again when the app exits. Files are written to a temporary file first and then renamed,
+/// so a crash never leaves a truncated state behind.
+///
+/// Each state has a version: when a file written by an older version of the app is loaded,
+/// the migrations registered with [`PersistentStateBuilder::migration`] convert it to the current version.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri::{Manager, PersistentState, State};
+///
+/// #[derive(Default, serde::Serialize, serde::Deserialize)]
+/// struct Settings {
+///   theme: String,
+///   font_size: u32,
+/// }
+///
+/// #[tauri::command]
+/// fn set_font_size(settings: State<'_, PersistentState<Settings>>, size: u32) {
+///   settings.update(|settings| settings.font_size = size);
+/// }
+///
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     let settings = PersistentState::<Settings>::builder("settings")
+///       .version(2)
+///       // version 1 stored the font size as a string
+///       .migration(1, |mut value| {
+///         let size = value["font_size"].as_str().and_then(|s| s.parse::<u32>().ok()).unwrap_or(14);
+///         value["font_size"] = size.into();
+///         Ok(value)
+///       })
+///       .build(app.handle())?;
+///     app.manage(settings);
+///     Ok(())
+///   })
+///   .invoke_handler(tauri::generate_handler![set_font_size]);
+/// ```
+pub struct PersistentState<T> {
+  inner: Arc<Inner<T>>,
+}
+
+impl<T> std::fmt::Debug for PersistentState<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("PersistentState")
+      .field("path", &self.inner.path)
+      .field("version", &self.inner.version)
+      .finish()
+  }
+}
+
+struct Inner<T> {
+  path: PathBuf,
+  version: u32,
+  debounce: Duration,
+  value: RwLock<T>,
+  dirty: AtomicBool,
+  save_scheduled: AtomicBool,
+  save_lock: Mutex<()>,
+}
+
+impl<T: Serialize> Inner<T> {
+  fn save(&self) -> crate::Result<()> {
+    let _guard = self.save_lock.lock().unwrap();
+    self.dirty.store(false, Ordering::SeqCst);
+    let contents = serde_json::to_vec(&Persisted {
+      version: self.version,
+      value: &*self.value.read().unwrap(),
+    })?;
+    write_atomic(&self.path, &contents)
+  }
+}
+
+/// Saves the states with pending changes on exit.
+trait Flush: Send + Sync {
+  fn flush(&self);
+}
+
+impl<T: Serialize + Send + Sync> Flush for Inner<T> {
+  fn flush(&self) {
+    if self.dirty.load(Ordering::SeqCst) {
+      if let Err(e) = self.save() {
+        log::error!("failed to save the state {}: {e}", self.path.display());
+      }
+    }
+  }
+}
+
+/// The persistent states of the app, flushed by [`AppHandle::cleanup_before_exit`].
+#[derive(Default)]
+pub(crate) struct PersistentStates(Mutex<Vec<Weak<dyn Flush>>>);
+
+impl PersistentStates {
+  pub(crate) fn flush(&self) {
+    let states = std::mem::take(&mut *self.0.lock().unwrap());
+    for state in states.iter().filter_map(Weak::upgrade) {
+      state.flush();
+    }
+  }
+}
+
+impl<T> PersistentState<T>
+where
+  T: Serialize + DeserializeOwned + Default + Send + Sync + 'static,
+{
+  /// Creates a builder for the state saved to `<app data dir>/state/<name>.json`.
+  pub fn builder(name: impl Into<String>) -> PersistentStateBuilder<T> {
+    PersistentStateBuilder::new(name)
+  }
+
+  /// Locks the value for reading.
+  pub fn get(&self) -> RwLockReadGuard<'_, T> {
+    self.inner.value.read().unwrap()
+  }
+
+  /// Mutates the value and schedules a save.
+  pub fn update<O, F: FnOnce(&mut T) -> O>(&self, f: F) -> O {
+    let output = f(&mut self.inner.value.write().unwrap());
+    self.schedule_save();
+    output
+  }
+
+  /// Replaces the value and schedules a save.
+  pub fn set(&self, value: T) {
+    self.update(|current| *current = value);
+  }
+
+  /// Saves the value immediately.
+  pub fn save(&self) -> crate::Result<()> {
+    self.inner.save()
+  }
+
+  /// The path of the file the state is saved to.
+  pub fn path(&self) -> &Path {
+    &self.inner.path
+  }
+
+  fn schedule_save(&self) {
+    self.inner.dirty.store(true, Ordering::SeqCst);
+    if self.inner.save_scheduled.swap(true, Ordering::SeqCst) {
+      return;
+    }
+    let inner = self.inner.clone();
+    async_runtime::spawn(async move {
+      tokio::time::sleep(inner.debounce).await;
+      inner.save_scheduled.store(false, Ordering::SeqCst);
+      if let Err(e) = inner.save() {
+        log::error!("failed to save the state {}: {e}", inner.path.display());
+      }
+    });
+  }
+}
+
+/// A builder for a [`PersistentState`].
+pub struct PersistentStateBuilder<T> {
+  name: String,
+  version: u32,
+  debounce: Duration,
+  migrations: BTreeMap<u32, Box<Migration>>,
+  default: Option<Box<dyn FnOnce() -> T + Send>>,
+}
+
+impl<T> PersistentStateBuilder<T>
+where
+  T: Serialize + DeserializeOwned + Default + Send + Sync + 'static,
+{
+  fn new(name: impl Into<String>) -> Self {
+    Self {
+      name: name.into(),
+      version: 1,
+      debounce: DEFAULT_DEBOUNCE,
+      migrations: Default::default(),
+      default: None,
+    }
+  }
+
+  /// The current version of the state, `1` by default.
+  #[must_use]
+  pub fn version(mut self, version: u32) -> Self {
+    self.version = version;
+    self
+  }
+
+  /// Registers the migration of a value saved with the version `from` to the version `from + 1`.
+  ///
+  /// The migrations run in order on the JSON value, so a value saved with the version 1 loaded by the version 3
+  /// goes through the migrations from 1 and from 2.
+  #[must_use]
+  pub fn migration<F>(mut self, from: u32, migration: F) -> Self
+  where
+    F: Fn(JsonValue) -> Result<JsonValue, String> + Send + Sync + 'static,
+  {
+    self.migrations.insert(from, Box::new(migration));
+    self
+  }
+
+  /// The delay between a mutation and the save it triggers, so successive mutations are saved at once.
+  /// Defaults to 500 milliseconds.
+  #[must_use]
+  pub fn debounce(mut self, debounce: Duration) -> Self {
+    self.debounce = debounce;
+    self
+  }
+
+  /// The value used when no state was saved yet, instead of [`Default::default`].
+  #[must_use]
+  pub fn default_value<F: FnOnce() -> T + Send + 'static>(mut self, default: F) -> Self {
+    self.default.replace(Box::new(default));
+    self
+  }
+
+  /// Loads the state, migrating it if needed.
+  ///
+  /// A file that cannot be parsed is moved aside to `<name>.json.corrupt` and the state starts over from its default value.
+  /// Fails if the file was saved by a newer version, or if a migration fails or is missing.
+  pub fn build<R: Runtime>(self, app: &AppHandle<R>) -> crate::Result<PersistentState<T>> {
+    let path = app
+      .path()
+      .app_data_dir()?
+      .join(STATE_DIR)
+      .join(format!("{}.json", self.name));
+    let state = self.build_at(path)?;
+    if let Some(states) = app.try_state::<PersistentStates>() {
+      let inner: Arc<dyn Flush> = state.inner.clone();
+      states.0.lock().unwrap().push(Arc::downgrade(&inner));
+    }
+    Ok(state)
+  }
+
+  fn build_at(self, path: PathBuf) -> crate::Result<PersistentState<T>> {
+    let value = match load(&path, self.version, &self.migrations)? {
+      Some(value) => value,
+      None => self.default.map(|default| default()).unwrap_or_default(),
+    };
+    Ok(PersistentState {
+      inner: Arc::new(Inner {
+        path,
+        version: self.version,
+        debounce: self.debounce,
+        value: RwLock::new(value),
+        dirty: AtomicBool::new(false),
+        save_scheduled: AtomicBool::new(false),
+        save_lock: Mutex::new(()),
+      }),
+    })
+  }
+}
+
+/// Reads and migrates the saved value, `None` if there is none.
+fn load<T: DeserializeOwned>(
+  path: &Path,
+  version: u32,
+  migrations: &BTreeMap<u32, Box<Migration>>,
+) -> crate::Result<Option<T>> {
+  let contents = match std::fs::read(path) {
+    Ok(contents) => contents,
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+    Err(e) => return Err(e.into()),
+  };
+
+  let persisted = match serde_json::from_slice::<Persisted<JsonValue>>(&contents) {
+    Ok(persisted) => persisted,
+    Err(e) => {
+      log::warn!(
+        "the state {} is corrupted, resetting it: {e}",
+        path.display()
+      );
+      std::fs::rename(path, path.with_extension("json.corrupt"))?;
+      return Ok(None);
+    }
+  };
+
+  if persisted.version > version {
+    return Err(crate::Error::PersistentState(format!(
+      "{} was saved with the version {} but the current version is {version}",
+      path.display(),
+      persisted.version
+    )));
+  }
+
+  let mut value = persisted.value;
+  for from in persisted.version..version {
+    let migration = migrations.get(&from).ok_or_else(|| {
+      crate::Error::PersistentState(format!(
+        "no migration from the version {from} of {}",
+        path.display()
+      ))
+    })?;
+    value = migration(value).map_err(|e| {
+      crate::Error::PersistentState(format!(
+        "failed to migrate {} from the version {from}: {e}",
+        path.display()
+      ))
+    })?;
+  }
+
+  serde_json::from_value(value)
+    .map(Some)
+    .map_err(|e| crate::Error::PersistentState(format!("invalid state {}: {e}", path.display())))
+}
+
+fn write_atomic(path: &Path, contents: &[u8]) -> crate::Result<()> {
+  if let Some(parent) = path.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+  // write to a temporary file first so a crash never leaves a truncated state behind
+  let tmp_path = path.with_extension("json.tmp");
+  std::fs::write(&tmp_path, contents)?;
+  std::fs::rename(tmp_path, path)?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+  struct Settings {
+    font_size: u32,
+  }
+
+  fn state_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("tauri-persistent-state-{}", std::process::id()));
+    let path = dir.join(format!("{name}.json"));
+    let _ = std::fs::remove_file(&path);
+    path
+  }
+
+  #[test]
+  fn saves_and_loads() {
+    let path = state_path("saves");
+    let state = PersistentState::<Settings>::builder("saves")
+      .build_at(path.clone())
+      .unwrap();
+    assert_eq!(*state.get(), Settings::default());
+
+    state.inner.value.write().unwrap().font_size = 16;
+    state.save().unwrap();
+
+    let state = PersistentState::<Settings>::builder("saves")
+      .build_at(path)
+      .unwrap();
+    assert_eq!(state.get().font_size, 16);
+  }
+
+  #[test]
+  fn migrates_older_versions() {
+    let path = state_path("migrates");
+    write_atomic(&path, br#"{ "version": 1, "value": { "size": "12" } }"#).unwrap();
+
+    let build = || {
+      PersistentState::<Settings>::builder("migrates")
+        .version(3)
+        .migration(1, |value| {
+          Ok(serde_json::json!({ "font_size": value["size"].as_str().unwrap() }))
+        })
+        .migration(2, |value| {
+          let size = value["font_size"].as_str().unwrap().parse::<u32>().unwrap();
+          Ok(serde_json::json!({ "font_size": size }))
+        })
+        .build_at(path.clone())
+    };
+    assert_eq!(build().unwrap().get().font_size, 12);
+
+    write_atomic(&path, br#"{ "version": 4, "value": { "font_size": 12 } }"#).unwrap();
+    assert!(matches!(build(), Err(crate::Error::PersistentState(_))));
+  }
+
+  #[test]
+  fn resets_corrupted_states() {
+    let path = state_path("corrupted");
+    write_atomic(&path, b"{ not json").unwrap();
+
+    let state = PersistentState::<Settings>::builder("corrupted")
+      .default_value(|| Settings { font_size: 14 })
+      .build_at(path.clone())
+      .unwrap();
+    assert_eq!(state.get().font_size, 14);
+    assert!(path.with_extension("json.corrupt").exists());
+  }
+}
@@ -97,6 +97,13 @@ type OnNavigation<R> = dyn Fn(&Webview<R>, &Url) -> bool + Send;
 type OnPageLoad<R> = dyn FnMut(&Webview<R>, &PageLoadPayload<'_>) + Send;
 type OnDrop<R> = dyn FnOnce(AppHandle<R>) + Send;
 
+#[cfg(feature = "localhost-server")]
+struct LocalhostRoute<R: Runtime> {
+  method: http::Method,
+  path: String,
+  handler: Arc<crate::localhost::RouteHandler<R>>,
+}
+
 /// A handle to a plugin.
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -250,6 +257,8 @@ pub struct Builder<R: Runtime, C: DeserializeOwned = ()> {
   on_event: Box<OnEvent<R>>,
   on_drop: Option<Box<OnDrop<R>>>,
   uri_scheme_protocols: HashMap<String, Arc<UriSchemeProtocol<R>>>,
+  #[cfg(feature = "localhost-server")]
+  localhost_routes: Vec<LocalhostRoute<R>>,
 }
 
 impl<R: Runtime, C: DeserializeOwned> Builder<R, C> {
@@ -267,6 +276,8 @@ impl<R: Runtime, C: DeserializeOwned> Builder<R, C> {
       on_event: Box::new(|_, _| ()),
       on_drop: None,
       uri_scheme_protocols: Default::default(),
+      #[cfg(feature = "localhost-server")]
+      localhost_routes: Vec::new(),
     }
   }
 
@@ -635,6 +646,50 @@ impl<R: Runtime, C: DeserializeOwned> Builder<R, C> {
     self
   }
 
+  /// Registers a route on the shared localhost server, served at `/<plugin name><path>`.
+  ///
+  /// The server is only started when at least one route is registered. See the [`crate::localhost`] module.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use tauri::{http::Method, plugin::{Builder, TauriPlugin}, Runtime};
+  ///
+  /// fn init<R: Runtime>() -> TauriPlugin<R> {
+  ///   Builder::new("media")
+  ///     .localhost_route(Method::GET, "/files/*", |_app, request| {
+  ///       // serves `/media/files/...`
+  ///       tauri::http::Response::builder()
+  ///         .status(tauri::http::StatusCode::NOT_FOUND)
+  ///         .body(Vec::new().into())
+  ///         .unwrap()
+  ///     })
+  ///     .build()
+  /// }
+  /// ```
+  #[cfg(feature = "localhost-server")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "localhost-server")))]
+  #[must_use]
+  pub fn localhost_route<
+    P: Into<String>,
+    H: Fn(&AppHandle<R>, http::Request<Vec<u8>>) -> http::Response<Cow<'static, [u8]>>
+      + Send
+      + Sync
+      + 'static,
+  >(
+    mut self,
+    method: http::Method,
+    path: P,
+    handler: H,
+  ) -> Self {
+    self.localhost_routes.push(LocalhostRoute {
+      method,
+      path: path.into(),
+      handler: Arc::new(handler),
+    });
+    self
+  }
+
   /// Builds the [`TauriPlugin`].
   pub fn try_build(self) -> Result<TauriPlugin<R, C>, BuilderError> {
     if let Some(&reserved) = RESERVED_PLUGIN_NAMES.iter().find(|&r| r == &self.name) {
@@ -654,6 +709,8 @@ impl<R: Runtime, C: DeserializeOwned> Builder<R, C> {
       on_event: self.on_event,
       on_drop: self.on_drop,
       uri_scheme_protocols: self.uri_scheme_protocols,
+      #[cfg(feature = "localhost-server")]
+      localhost_routes: self.localhost_routes,
     })
   }
 
@@ -681,6 +738,8 @@ pub struct TauriPlugin<R: Runtime, C: DeserializeOwned = ()> {
   on_event: Box<OnEvent<R>>,
   on_drop: Option<Box<OnDrop<R>>>,
   uri_scheme_protocols: HashMap<String, Arc<UriSchemeProtocol<R>>>,
+  #[cfg(feature = "localhost-server")]
+  localhost_routes: Vec<LocalhostRoute<R>>,
 }
 
 impl<R: Runtime, C: DeserializeOwned> Drop for TauriPlugin<R, C> {
@@ -725,6 +784,16 @@ impl<R: Runtime, C: DeserializeOwned> Plugin<R> for TauriPlugin<R, C> {
         .webview
         .register_uri_scheme_protocol(uri_scheme, protocol.clone())
     }
+
+    #[cfg(feature = "localhost-server")]
+    for route in &self.localhost_routes {
+      crate::Manager::localhost_server(app).add_route(
+        self.name,
+        route.method.clone(),
+        &route.path,
+        route.handler.clone(),
+      )?;
+    }
     Ok(())
   }
 
@@ -898,12 +967,15 @@ fn initialize<R: Runtime>(
   app: &AppHandle<R>,
   config: &PluginConfig,
 ) -> crate::Result<()> {
-  plugin
-    .initialize(
-      app,
-      config.0.get(plugin.name()).cloned().unwrap_or_default(),
+  let name = plugin.name();
+  app
+    .manager
+    .startup
+    .measure(
+      || format!("plugin {name}"),
+      || plugin.initialize(app, config.0.get(name).cloned().unwrap_or_default()),
     )
-    .map_err(|e| Error::PluginInitialization(plugin.name().to_string(), e.to_string()))
+    .map_err(|e| Error::PluginInitialization(name.to_string(), e.to_string()))
 }
 
 /// Permission state.
@@ -0,0 +1,593 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Printer enumeration and print job submission without the print dialog.
+//!
+//! This complements the webview `window.print()` API for the workflows that must not prompt the user,
+//! such as label printers or scheduled reports. The jobs are submitted to CUPS on Linux and macOS
+//! and to the Windows print spooler.
+//!
+//! The webviews use the `core:printer` commands, which are not enabled by the default permission set.
+//! They can list the printers and submit raw jobs, the documents are printed from Rust with [`print_file`].
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+pub(crate) mod plugin;
+
+/// Printer errors.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+  /// No printer is configured.
+  #[error("no printer available")]
+  NoPrinter,
+  /// The printer with the given name is not configured.
+  #[error("printer {0} not found")]
+  NotFound(String),
+  /// The print system is not available on this system.
+  #[error("printing is not available: {0}")]
+  Unavailable(String),
+  /// The print job could not be submitted.
+  #[error("failed to submit the print job: {0}")]
+  Job(String),
+}
+
+/// The features supported by a [`Printer`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct PrinterCapabilities {
+  /// Whether the printer prints in color.
+  pub color: bool,
+  /// Whether the printer prints on both sides of the paper.
+  pub duplex: bool,
+  /// The names of the supported paper sizes, e.g. `A4` or `Letter`, to use with [`PrintOptions::paper_size`].
+  pub paper_sizes: Vec<String>,
+}
+
+/// A printer configured on the system.
+///
+/// See [`list`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Printer {
+  /// The name of the printer, to use with [`PrintOptions::printer`].
+  pub name: String,
+  /// Whether this is the default printer of the user.
+  pub is_default: bool,
+  /// The features supported by the printer.
+  pub capabilities: PrinterCapabilities,
+}
+
+/// The options of [`print_file`] and [`print_raw`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PrintOptions {
+  printer: Option<String>,
+  title: Option<String>,
+  copies: u32,
+  duplex: bool,
+  paper_size: Option<String>,
+}
+
+impl Default for PrintOptions {
+  fn default() -> Self {
+    Self {
+      printer: None,
+      title: None,
+      copies: 1,
+      duplex: false,
+      paper_size: None,
+    }
+  }
+}
+
+impl PrintOptions {
+  /// The [`Printer::name`] of the printer to use. Defaults to the default printer.
+  #[must_use]
+  pub fn printer(mut self, printer: impl Into<String>) -> Self {
+    self.printer.replace(printer.into());
+    self
+  }
+
+  /// The name of the job shown in the print queue. Defaults to the file name.
+  #[must_use]
+  pub fn title(mut self, title: impl Into<String>) -> Self {
+    self.title.replace(title.into());
+    self
+  }
+
+  /// The number of copies to print. Defaults to 1.
+  #[must_use]
+  pub fn copies(mut self, copies: u32) -> Self {
+    self.copies = copies;
+    self
+  }
+
+  /// Prints on both sides of the paper, flipping on the long edge. Defaults to `false`.
+  #[must_use]
+  pub fn duplex(mut self, duplex: bool) -> Self {
+    self.duplex = duplex;
+    self
+  }
+
+  /// One of the [`PrinterCapabilities::paper_sizes`]. Defaults to the printer default paper size.
+  #[must_use]
+  pub fn paper_size(mut self, paper_size: impl Into<String>) -> Self {
+    self.paper_size.replace(paper_size.into());
+    self
+  }
+}
+
+/// Lists the printers configured on the system.
+pub fn list() -> crate::Result<Vec<Printer>> {
+  platform::list().map_err(Into::into)
+}
+
+/// Prints a document, such as a PDF file, without showing the print dialog.
+///
+/// ## Platform-specific
+///
+/// - **Linux / macOS:** Submits the file to CUPS with `lp`, which converts the common document and image formats.
+/// - **Windows:** Uses the `printto` verb of the application associated with the file type.
+///   Only the printer option is supported, the application prints with its own settings.
+/// - **Android / iOS:** Unsupported.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri::printer::PrintOptions;
+///
+/// tauri::printer::print_file("report.pdf", PrintOptions::default().duplex(true))?;
+/// # Ok::<(), tauri::Error>(())
+/// ```
+pub fn print_file<P: AsRef<Path>>(path: P, options: PrintOptions) -> crate::Result<()> {
+  platform::print_file(path.as_ref(), &options).map_err(Into::into)
+}
+
+/// Sends the data to the printer as is, bypassing the print drivers,
+/// e.g. ZPL for label printers or ESC/POS for receipt printers.
+///
+/// The duplex and paper size options are ignored, since they are part of the data.
+pub fn print_raw(data: &[u8], options: PrintOptions) -> crate::Result<()> {
+  platform::print_raw(data, &options).map_err(Into::into)
+}
+
+/// Parses the `lpstat -d` output.
+#[cfg_attr(any(windows, mobile), allow(dead_code))]
+fn parse_lpstat_default(output: &str) -> Option<String> {
+  output
+    .lines()
+    .find_map(|line| line.strip_prefix("system default destination: "))
+    .map(|name| name.trim().to_string())
+}
+
+/// Parses the `lpoptions -l` output, where each line lists the values of an option with the default one marked by `*`.
+#[cfg_attr(any(windows, mobile), allow(dead_code))]
+fn parse_lpoptions(output: &str) -> PrinterCapabilities {
+  let mut capabilities = PrinterCapabilities::default();
+  for line in output.lines() {
+    let Some((key, values)) = line.split_once(':') else {
+      continue;
+    };
+    // `Key/Human readable label`
+    let key = key.split('/').next().unwrap_or_default();
+    let mut values = values
+      .split_whitespace()
+      .map(|value| value.trim_start_matches('*'));
+    match key {
+      "ColorModel" | "print-color-mode" => {
+        capabilities.color |= values.any(|value| {
+          !["gray", "grayscale", "black", "kgray", "mono", "monochrome"]
+            .contains(&value.to_lowercase().as_str())
+        });
+      }
+      "Duplex" | "sides" => {
+        capabilities.duplex |=
+          values.any(|value| !["none", "one-sided"].contains(&value.to_lowercase().as_str()));
+      }
+      "PageSize" | "media" => {
+        capabilities.paper_sizes.extend(
+          values
+            .filter(|value| !value.starts_with("Custom."))
+            .map(ToString::to_string),
+        );
+      }
+      _ => {}
+    }
+  }
+  capabilities
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd",
+  target_os = "macos"
+))]
+mod platform {
+  use super::{parse_lpoptions, parse_lpstat_default, Error, PrintOptions, Printer};
+  use std::{
+    io::Write,
+    path::Path,
+    process::{Command, Output, Stdio},
+  };
+
+  fn command(program: &str) -> Command {
+    let mut command = Command::new(program);
+    // the messages are translated
+    command.env("LC_ALL", "C");
+    command
+  }
+
+  fn stdout(output: std::io::Result<Output>) -> Result<String, Error> {
+    let output = output.map_err(|e| Error::Unavailable(e.to_string()))?;
+    if output.status.success() {
+      Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+      Err(Error::Unavailable(
+        String::from_utf8_lossy(&output.stderr).trim().to_string(),
+      ))
+    }
+  }
+
+  pub fn list() -> Result<Vec<Printer>, Error> {
+    // fails when no printer is configured
+    let names = stdout(command("lpstat").arg("-e").output()).unwrap_or_default();
+    let default = stdout(command("lpstat").arg("-d").output())
+      .ok()
+      .and_then(|output| parse_lpstat_default(&output));
+    Ok(
+      names
+        .lines()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| Printer {
+          name: name.to_string(),
+          is_default: default.as_deref() == Some(name),
+          capabilities: stdout(command("lpoptions").args(["-p", name, "-l"]).output())
+            .map(|output| parse_lpoptions(&output))
+            .unwrap_or_default(),
+        })
+        .collect(),
+    )
+  }
+
+  fn lp(options: &PrintOptions) -> Command {
+    let mut command = command("lp");
+    if let Some(printer) = &options.printer {
+      command.args(["-d", printer]);
+    }
+    if let Some(title) = &options.title {
+      command.args(["-t", title]);
+    }
+    command.args(["-n", &options.copies.max(1).to_string()]);
+    command
+  }
+
+  fn submit(output: std::io::Result<Output>, options: &PrintOptions) -> Result<(), Error> {
+    let output = output.map_err(|e| Error::Unavailable(e.to_string()))?;
+    if output.status.success() {
+      return Ok(());
+    }
+    let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    Err(match &options.printer {
+      Some(printer) if message.contains("does not exist") => Error::NotFound(printer.clone()),
+      None if message.contains("no system default destination") => Error::NoPrinter,
+      _ => Error::Job(message),
+    })
+  }
+
+  pub fn print_file(path: &Path, options: &PrintOptions) -> Result<(), Error> {
+    let mut command = lp(options);
+    if options.duplex {
+      command.args(["-o", "sides=two-sided-long-edge"]);
+    }
+    if let Some(paper_size) = &options.paper_size {
+      command.args(["-o", &format!("media={paper_size}")]);
+    }
+    submit(command.arg("--").arg(path).output(), options)
+  }
+
+  pub fn print_raw(data: &[u8], options: &PrintOptions) -> Result<(), Error> {
+    // `lp` reads the job from stdin without a file argument
+    let mut child = lp(options)
+      .args(["-o", "raw"])
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped())
+      .spawn()
+      .map_err(|e| Error::Unavailable(e.to_string()))?;
+    if let Some(mut stdin) = child.stdin.take() {
+      stdin
+        .write_all(data)
+        .map_err(|e| Error::Job(e.to_string()))?;
+    }
+    submit(child.wait_with_output(), options)
+  }
+}
+
+#[cfg(windows)]
+mod platform {
+  use super::{Error, PrintOptions, Printer, PrinterCapabilities};
+  use std::path::Path;
+  use windows::{
+    core::{HSTRING, PCWSTR, PWSTR},
+    Win32::{
+      Foundation::{ERROR_INVALID_PRINTER_NAME, HWND},
+      Graphics::Printing::{
+        ClosePrinter, EndDocPrinter, EndPagePrinter, EnumPrintersW, GetDefaultPrinterW,
+        OpenPrinterW, StartDocPrinterW, StartPagePrinter, WritePrinter, DOC_INFO_1W,
+        PRINTER_ENUM_CONNECTIONS, PRINTER_ENUM_LOCAL, PRINTER_HANDLE, PRINTER_INFO_4W,
+      },
+      Storage::Xps::{DeviceCapabilitiesW, DC_COLORDEVICE, DC_DUPLEX, DC_PAPERNAMES},
+      UI::{Shell::ShellExecuteW, WindowsAndMessaging::SW_HIDE},
+    },
+  };
+
+  /// The length of the paper names returned by `DeviceCapabilitiesW`.
+  const PAPER_NAME_LEN: usize = 64;
+
+  fn default_printer() -> Option<String> {
+    unsafe {
+      let mut len = 0;
+      // fails with the required length
+      let _ = GetDefaultPrinterW(PWSTR::null(), &mut len);
+      if len == 0 {
+        return None;
+      }
+      let mut buffer = vec![0u16; len as usize];
+      GetDefaultPrinterW(PWSTR(buffer.as_mut_ptr()), &mut len).ok()?;
+      Some(String::from_utf16_lossy(
+        &buffer[..len.saturating_sub(1) as usize],
+      ))
+    }
+  }
+
+  fn capabilities(name: &HSTRING) -> PrinterCapabilities {
+    unsafe {
+      let color = DeviceCapabilitiesW(name, PCWSTR::null(), DC_COLORDEVICE, PWSTR::null(), None);
+      let duplex = DeviceCapabilitiesW(name, PCWSTR::null(), DC_DUPLEX, PWSTR::null(), None);
+      let count = DeviceCapabilitiesW(name, PCWSTR::null(), DC_PAPERNAMES, PWSTR::null(), None);
+      let mut paper_sizes = Vec::new();
+      if count > 0 {
+        let mut buffer = vec![0u16; count as usize * PAPER_NAME_LEN];
+        let count = DeviceCapabilitiesW(
+          name,
+          PCWSTR::null(),
+          DC_PAPERNAMES,
+          PWSTR(buffer.as_mut_ptr()),
+          None,
+        );
+        for paper_name in buffer.chunks(PAPER_NAME_LEN).take(count.max(0) as usize) {
+          let len = paper_name
+            .iter()
+            .position(|c| *c == 0)
+            .unwrap_or(PAPER_NAME_LEN);
+          paper_sizes.push(String::from_utf16_lossy(&paper_name[..len]));
+        }
+      }
+      PrinterCapabilities {
+        color: color == 1,
+        duplex: duplex == 1,
+        paper_sizes,
+      }
+    }
+  }
+
+  pub fn list() -> Result<Vec<Printer>, Error> {
+    let flags = PRINTER_ENUM_LOCAL | PRINTER_ENUM_CONNECTIONS;
+    let mut needed = 0;
+    let mut count = 0;
+    unsafe {
+      // fails with the required size
+      let _ = EnumPrintersW(flags, PCWSTR::null(), 4, None, &mut needed, &mut count);
+      if needed == 0 {
+        return Ok(Vec::new());
+      }
+      // aligned for the `PRINTER_INFO_4W` structures at the start of the buffer
+      let mut buffer = vec![0u64; (needed as usize).div_ceil(8)];
+      let bytes = std::slice::from_raw_parts_mut(buffer.as_mut_ptr().cast::<u8>(), needed as usize);
+      EnumPrintersW(
+        flags,
+        PCWSTR::null(),
+        4,
+        Some(bytes),
+        &mut needed,
+        &mut count,
+      )
+      .map_err(|e| Error::Unavailable(e.to_string()))?;
+
+      let default = default_printer();
+      let infos =
+        std::slice::from_raw_parts(buffer.as_ptr().cast::<PRINTER_INFO_4W>(), count as usize);
+      Ok(
+        infos
+          .iter()
+          .filter_map(|info| {
+            let name = info.pPrinterName.to_string().ok()?;
+            Some(Printer {
+              is_default: default.as_ref() == Some(&name),
+              capabilities: capabilities(&HSTRING::from(&name)),
+              name,
+            })
+          })
+          .collect(),
+      )
+    }
+  }
+
+  fn printer_name(options: &PrintOptions) -> Result<String, Error> {
+    match &options.printer {
+      Some(printer) => Ok(printer.clone()),
+      None => default_printer().ok_or(Error::NoPrinter),
+    }
+  }
+
+  pub fn print_file(path: &Path, options: &PrintOptions) -> Result<(), Error> {
+    let printer = printer_name(options)?;
+    let result = unsafe {
+      ShellExecuteW(
+        HWND::default(),
+        &HSTRING::from("printto"),
+        &HSTRING::from(path),
+        &HSTRING::from(format!("\"{printer}\"")),
+        PCWSTR::null(),
+        SW_HIDE,
+      )
+    };
+    // values up to 32 are error codes
+    if result.0 as isize > 32 {
+      Ok(())
+    } else {
+      Err(Error::Job(format!(
+        "no application can print {} (error {})",
+        path.display(),
+        result.0 as isize
+      )))
+    }
+  }
+
+  struct Handle(PRINTER_HANDLE);
+
+  impl Drop for Handle {
+    fn drop(&mut self) {
+      let _ = unsafe { ClosePrinter(self.0) };
+    }
+  }
+
+  pub fn print_raw(data: &[u8], options: &PrintOptions) -> Result<(), Error> {
+    let printer = printer_name(options)?;
+    let job_error = |e: windows::core::Error| Error::Job(e.to_string());
+    unsafe {
+      let mut handle = PRINTER_HANDLE::default();
+      OpenPrinterW(&HSTRING::from(&printer), &mut handle, None).map_err(|e| {
+        if e.code() == ERROR_INVALID_PRINTER_NAME.to_hresult() {
+          Error::NotFound(printer.clone())
+        } else {
+          job_error(e)
+        }
+      })?;
+      let handle = Handle(handle);
+
+      let mut title: Vec<u16> = options
+        .title
+        .as_deref()
+        .unwrap_or("Document")
+        .encode_utf16()
+        .chain(Some(0))
+        .collect();
+      let mut datatype: Vec<u16> = "RAW".encode_utf16().chain(Some(0)).collect();
+      let info = DOC_INFO_1W {
+        pDocName: PWSTR(title.as_mut_ptr()),
+        pOutputFile: PWSTR::null(),
+        pDatatype: PWSTR(datatype.as_mut_ptr()),
+      };
+      if StartDocPrinterW(handle.0, 1, &info) == 0 {
+        return Err(job_error(windows::core::Error::from_win32()));
+      }
+
+      let result = (|| {
+        StartPagePrinter(handle.0).map_err(job_error)?;
+        // the copies are part of the data of raw jobs
+        for _ in 0..options.copies.max(1) {
+          let mut written = 0;
+          WritePrinter(
+            handle.0,
+            data.as_ptr().cast(),
+            data.len() as u32,
+            &mut written,
+          )
+          .map_err(job_error)?;
+          if written as usize != data.len() {
+            return Err(Error::Job("the printer did not accept all the data".into()));
+          }
+        }
+        EndPagePrinter(handle.0).map_err(job_error)
+      })();
+      EndDocPrinter(handle.0).map_err(job_error)?;
+      result
+    }
+  }
+}
+
+#[cfg(mobile)]
+mod platform {
+  use super::{Error, PrintOptions, Printer};
+  use std::path::Path;
+
+  pub fn list() -> Result<Vec<Printer>, Error> {
+    Ok(Vec::new())
+  }
+
+  pub fn print_file(_path: &Path, _options: &PrintOptions) -> Result<(), Error> {
+    Err(Error::Unavailable("unsupported platform".into()))
+  }
+
+  pub fn print_raw(_data: &[u8], _options: &PrintOptions) -> Result<(), Error> {
+    Err(Error::Unavailable("unsupported platform".into()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_lpstat_default() {
+    assert_eq!(
+      parse_lpstat_default("system default destination: HP_LaserJet_M404\n"),
+      Some("HP_LaserJet_M404".into())
+    );
+    assert_eq!(
+      parse_lpstat_default("no system default destination\n"),
+      None
+    );
+  }
+
+  #[test]
+  fn parses_lpoptions() {
+    let output = "PageSize/Media Size: Letter *A4 A5 Env10 Custom.WIDTHxHEIGHT
+Duplex/2-Sided Printing: *None DuplexNoTumble DuplexTumble
+ColorModel/Color Mode: Gray *RGB
+cupsPrintQuality/Print Quality: Draft *Normal High";
+    assert_eq!(
+      parse_lpoptions(output),
+      PrinterCapabilities {
+        color: true,
+        duplex: true,
+        paper_sizes: vec!["Letter".into(), "A4".into(), "A5".into(), "Env10".into()],
+      }
+    );
+
+    // monochrome label printer
+    let output = "PageSize/Media Size: *w288h432
+ColorModel/Color Mode: *Gray";
+    assert_eq!(
+      parse_lpoptions(output),
+      PrinterCapabilities {
+        color: false,
+        duplex: false,
+        paper_sizes: vec!["w288h432".into()],
+      }
+    );
+  }
+
+  #[test]
+  fn deserializes_options() {
+    let options: PrintOptions =
+      serde_json::from_str(r#"{ "printer": "Zebra", "copies": 2 }"#).unwrap();
+    assert_eq!(options.printer.as_deref(), Some("Zebra"));
+    assert_eq!(options.copies, 2);
+    assert!(!options.duplex);
+
+    let options: PrintOptions = serde_json::from_str("{}").unwrap();
+    assert_eq!(options.copies, 1);
+  }
+}
@@ -0,0 +1,27 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::{PrintOptions, Printer};
+use crate::{
+  command,
+  plugin::{Builder, TauriPlugin},
+  Runtime,
+};
+
+#[command(root = "crate")]
+async fn list() -> crate::Result<Vec<Printer>> {
+  crate::async_runtime::spawn_blocking(super::list).await?
+}
+
+#[command(root = "crate")]
+async fn print_raw(data: Vec<u8>, options: Option<PrintOptions>) -> crate::Result<()> {
+  crate::async_runtime::spawn_blocking(move || super::print_raw(&data, options.unwrap_or_default()))
+    .await?
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("printer")
+    .invoke_handler(crate::generate_handler![list, print_raw])
+    .build()
+}
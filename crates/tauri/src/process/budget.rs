@@ -0,0 +1,161 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::time::{Duration, Instant};
+
+/// The minimum work time between two pauses of a throttled [`Budget`], so short checkpoints do not sleep all the time.
+const MIN_SLICE: Duration = Duration::from_millis(10);
+
+/// A cooperative resource budget for heavy work running in the app process, such as a command parsing a large file.
+///
+/// The work calls [`Budget::checkpoint`] between units of work, which pauses it to keep its CPU usage
+/// under the configured share and fails once its time limit is exceeded.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use tauri::process::Budget;
+///
+/// #[tauri::command]
+/// async fn index(files: Vec<std::path::PathBuf>) -> tauri::Result<()> {
+///   let mut budget = Budget::new()
+///     .time_limit(Duration::from_secs(60))
+///     .cpu_share(0.5);
+///   for file in files {
+///     budget.checkpoint_async().await?;
+///     // index the file
+///   }
+///   Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Budget {
+  started: Instant,
+  time_limit: Option<Duration>,
+  cpu_share: f64,
+  slice_started: Instant,
+}
+
+impl Default for Budget {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Budget {
+  /// A budget without time limit nor throttling.
+  pub fn new() -> Self {
+    let now = Instant::now();
+    Self {
+      started: now,
+      time_limit: None,
+      cpu_share: 1.0,
+      slice_started: now,
+    }
+  }
+
+  /// The maximum time the work can take, including the pauses.
+  #[must_use]
+  pub fn time_limit(mut self, limit: Duration) -> Self {
+    self.time_limit.replace(limit);
+    self
+  }
+
+  /// The share of a CPU core the work can use, from `0.01` to `1.0` (no throttling).
+  #[must_use]
+  pub fn cpu_share(mut self, share: f64) -> Self {
+    self.cpu_share = share.clamp(0.01, 1.0);
+    self
+  }
+
+  /// The time elapsed since the budget was created.
+  pub fn elapsed(&self) -> Duration {
+    self.started.elapsed()
+  }
+
+  /// The time left before the time limit is exceeded, if any.
+  pub fn remaining(&self) -> Option<Duration> {
+    self
+      .time_limit
+      .map(|limit| limit.saturating_sub(self.elapsed()))
+  }
+
+  /// Blocks the current thread to keep the CPU usage under the budget share,
+  /// and returns [`crate::Error::BudgetExceeded`] once the time limit is exceeded.
+  ///
+  /// Use [`Self::checkpoint_async`] in async code.
+  pub fn checkpoint(&mut self) -> crate::Result<()> {
+    self.check()?;
+    if let Some(pause) = self.pause() {
+      std::thread::sleep(pause);
+      self.slice_started = Instant::now();
+    }
+    Ok(())
+  }
+
+  /// Pauses the current task to keep the CPU usage under the budget share,
+  /// and returns [`crate::Error::BudgetExceeded`] once the time limit is exceeded.
+  pub async fn checkpoint_async(&mut self) -> crate::Result<()> {
+    self.check()?;
+    if let Some(pause) = self.pause() {
+      tokio::time::sleep(pause).await;
+      self.slice_started = Instant::now();
+    }
+    Ok(())
+  }
+
+  fn check(&self) -> crate::Result<()> {
+    match self.time_limit {
+      Some(limit) if self.elapsed() > limit => Err(crate::Error::BudgetExceeded(limit)),
+      _ => Ok(()),
+    }
+  }
+
+  /// The pause keeping the time worked since the last pause under the budget share.
+  fn pause(&self) -> Option<Duration> {
+    let worked = self.slice_started.elapsed();
+    if self.cpu_share >= 1.0 || worked < MIN_SLICE {
+      return None;
+    }
+    let pause = worked.mul_f64((1.0 - self.cpu_share) / self.cpu_share);
+    // never sleep past the time limit
+    Some(match self.remaining() {
+      Some(remaining) => pause.min(remaining),
+      None => pause,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn throttles_to_the_cpu_share() {
+    let mut budget = Budget::new().cpu_share(0.25);
+    assert_eq!(budget.pause(), None);
+
+    budget.slice_started = Instant::now() - Duration::from_millis(100);
+    let pause = budget.pause().unwrap();
+    assert!(pause >= Duration::from_millis(300), "{pause:?}");
+
+    let mut unthrottled = Budget::new();
+    unthrottled.slice_started = Instant::now() - Duration::from_millis(100);
+    assert_eq!(unthrottled.pause(), None);
+  }
+
+  #[test]
+  fn fails_past_the_time_limit() {
+    let mut budget = Budget::new().time_limit(Duration::from_millis(50));
+    assert!(budget.checkpoint().is_ok());
+
+    budget.started = Instant::now() - Duration::from_millis(100);
+    assert!(matches!(
+      budget.checkpoint(),
+      Err(crate::Error::BudgetExceeded(_))
+    ));
+    assert_eq!(budget.remaining(), Some(Duration::ZERO));
+  }
+}
@@ -0,0 +1,498 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  path::Path,
+  process::{Child, Command},
+};
+
+pub use tauri_utils::config::ProcessLimits;
+
+use crate::Config;
+
+/// Returns the limits configured in `app > sidecarLimits` for a sidecar,
+/// given its name such as `my-sidecar` or `binaries/my-sidecar`.
+///
+/// Falls back to the `*` entry when the sidecar has no entry.
+pub fn sidecar_limits<'a>(config: &'a Config, sidecar: &str) -> Option<&'a ProcessLimits> {
+  let name = Path::new(sidecar)
+    .file_name()
+    .and_then(|name| name.to_str())
+    .unwrap_or(sidecar);
+  let name = name.strip_suffix(".exe").unwrap_or(name);
+  config
+    .app
+    .sidecar_limits
+    .get(name)
+    .or_else(|| config.app.sidecar_limits.get("*"))
+}
+
+/// Spawns the command of a sidecar with the limits configured for it in `app > sidecarLimits`,
+/// see [`sidecar_limits`] and [`spawn_limited`].
+///
+/// The command is spawned without limits when the sidecar has none.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri::Manager;
+///
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     let path = tauri::process::current_binary(&app.env())?.with_file_name("my-sidecar");
+///     let child = tauri::process::spawn_sidecar(
+///       app.config(),
+///       "my-sidecar",
+///       &mut std::process::Command::new(path),
+///     )?;
+///     Ok(())
+///   });
+/// ```
+pub fn spawn_sidecar(
+  config: &Config,
+  sidecar: &str,
+  command: &mut Command,
+) -> std::io::Result<Child> {
+  match sidecar_limits(config, sidecar) {
+    Some(limits) => spawn_limited(command, limits),
+    None => command.spawn(),
+  }
+}
+
+/// Spawns a command with resource limits applied before the process runs any code.
+///
+/// Returns an [`std::io::ErrorKind::Unsupported`] error, without spawning the process,
+/// when a limit is not supported on the platform, see [`ProcessLimits`].
+///
+/// ## Platform-specific
+///
+/// - **Windows:** The process is created suspended, assigned to a job object and then resumed.
+///   Its creation flags are replaced with `CREATE_SUSPENDED | CREATE_NO_WINDOW`.
+/// - **Linux / macOS / BSD:** The limits are set with `setrlimit` and `setpriority` in the child process before `exec`.
+pub fn spawn_limited(command: &mut Command, limits: &ProcessLimits) -> std::io::Result<Child> {
+  platform::check(limits)?;
+  platform::spawn_limited(command, limits)
+}
+
+/// Applies resource limits to a running process.
+///
+/// Prefer [`spawn_limited`] for the processes spawned by the app,
+/// since the process runs without limits until this function is called.
+///
+/// Returns an [`std::io::ErrorKind::Unsupported`] error, before applying any limit,
+/// when a limit is not supported on the platform, see [`ProcessLimits`].
+///
+/// ## Platform-specific
+///
+/// - **macOS / BSD:** The CPU time limit of another process cannot be set, use [`spawn_limited`] instead.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri::process::{limit_process, ProcessLimits};
+///
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     let child = std::process::Command::new("my-sidecar").spawn()?;
+///     limit_process(
+///       child.id(),
+///       &ProcessLimits {
+///         max_memory: Some(512 * 1024 * 1024),
+///         nice: Some(10),
+///         ..Default::default()
+///       },
+///     )?;
+///     Ok(())
+///   });
+/// ```
+pub fn limit_process(pid: u32, limits: &ProcessLimits) -> std::io::Result<()> {
+  platform::check(limits)?;
+  // the resource limits of another process can only be changed on Linux
+  #[cfg(all(unix, not(target_os = "linux")))]
+  if limits.max_cpu_time.is_some() {
+    return Err(unsupported("CPU time"));
+  }
+  platform::limit_process(pid, limits)
+}
+
+#[cfg(not(windows))]
+fn unsupported(limit: &str) -> std::io::Error {
+  std::io::Error::new(
+    std::io::ErrorKind::Unsupported,
+    format!("the {limit} limit is not supported on this platform"),
+  )
+}
+
+#[cfg(windows)]
+mod platform {
+  use std::{
+    os::windows::{io::AsRawHandle, process::CommandExt},
+    process::{Child, Command},
+  };
+
+  use windows::Win32::{
+    Foundation::{CloseHandle, HANDLE},
+    System::{
+      Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Thread32First, Thread32Next, TH32CS_SNAPTHREAD, THREADENTRY32,
+      },
+      Threading::{
+        OpenProcess, OpenThread, ResumeThread, SetPriorityClass, ABOVE_NORMAL_PRIORITY_CLASS,
+        BELOW_NORMAL_PRIORITY_CLASS, CREATE_NO_WINDOW, CREATE_SUSPENDED, HIGH_PRIORITY_CLASS,
+        IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS, PROCESS_SET_INFORMATION, PROCESS_SET_QUOTA,
+        PROCESS_TERMINATE, THREAD_SUSPEND_RESUME,
+      },
+    },
+  };
+
+  use super::ProcessLimits;
+
+  /// Whether the limits are enforced with a job object.
+  fn needs_job(limits: &ProcessLimits) -> bool {
+    limits.max_memory.is_some() || limits.max_cpu_time.is_some() || limits.cpu_rate.is_some()
+  }
+
+  pub fn check(limits: &ProcessLimits) -> std::io::Result<()> {
+    if cfg!(not(feature = "process-limits")) && needs_job(limits) {
+      return Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "the memory, CPU time and CPU rate limits on Windows require the `process-limits` feature",
+      ));
+    }
+    Ok(())
+  }
+
+  pub fn spawn_limited(command: &mut Command, limits: &ProcessLimits) -> std::io::Result<Child> {
+    command.creation_flags((CREATE_SUSPENDED | CREATE_NO_WINDOW).0);
+    let mut child = command.spawn()?;
+    let result =
+      unsafe { apply(HANDLE(child.as_raw_handle()), limits).and_then(|()| resume(child.id())) };
+    if let Err(e) = result {
+      let _ = child.kill();
+      let _ = child.wait();
+      return Err(e);
+    }
+    Ok(child)
+  }
+
+  pub fn limit_process(pid: u32, limits: &ProcessLimits) -> std::io::Result<()> {
+    unsafe {
+      let process = OpenProcess(
+        PROCESS_SET_QUOTA | PROCESS_TERMINATE | PROCESS_SET_INFORMATION,
+        false,
+        pid,
+      )?;
+      let result = apply(process, limits);
+      let _ = CloseHandle(process);
+      result
+    }
+  }
+
+  unsafe fn apply(process: HANDLE, limits: &ProcessLimits) -> std::io::Result<()> {
+    if let Some(nice) = limits.nice {
+      let class = match nice {
+        i8::MIN..=-10 => HIGH_PRIORITY_CLASS,
+        -9..=-1 => ABOVE_NORMAL_PRIORITY_CLASS,
+        0 => NORMAL_PRIORITY_CLASS,
+        1..=9 => BELOW_NORMAL_PRIORITY_CLASS,
+        _ => IDLE_PRIORITY_CLASS,
+      };
+      SetPriorityClass(process, class)?;
+    }
+
+    #[cfg(feature = "process-limits")]
+    if needs_job(limits) {
+      job::assign(process, limits)?;
+    }
+
+    Ok(())
+  }
+
+  /// Resumes the main thread of a process created suspended, its only thread.
+  unsafe fn resume(pid: u32) -> std::io::Result<()> {
+    let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0)?;
+    let mut entry = THREADENTRY32 {
+      dwSize: std::mem::size_of::<THREADENTRY32>() as u32,
+      ..Default::default()
+    };
+    let mut result = Err(std::io::Error::new(
+      std::io::ErrorKind::NotFound,
+      "the main thread of the process was not found",
+    ));
+    let mut found = Thread32First(snapshot, &mut entry).is_ok();
+    while found {
+      if entry.th32OwnerProcessID == pid {
+        result = OpenThread(THREAD_SUSPEND_RESUME, false, entry.th32ThreadID)
+          .map_err(Into::into)
+          .and_then(|thread| {
+            let count = ResumeThread(thread);
+            let _ = CloseHandle(thread);
+            if count == u32::MAX {
+              Err(std::io::Error::last_os_error())
+            } else {
+              Ok(())
+            }
+          });
+        break;
+      }
+      found = Thread32Next(snapshot, &mut entry).is_ok();
+    }
+    let _ = CloseHandle(snapshot);
+    result
+  }
+
+  #[cfg(feature = "process-limits")]
+  mod job {
+    use std::ffi::c_void;
+
+    use windows::{
+      core::PCWSTR,
+      Win32::{
+        Foundation::{CloseHandle, HANDLE},
+        System::JobObjects::{
+          AssignProcessToJobObject, CreateJobObjectW, JobObjectCpuRateControlInformation,
+          JobObjectExtendedLimitInformation, SetInformationJobObject,
+          JOBOBJECT_CPU_RATE_CONTROL_INFORMATION, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+          JOB_OBJECT_CPU_RATE_CONTROL_ENABLE, JOB_OBJECT_CPU_RATE_CONTROL_HARD_CAP,
+          JOB_OBJECT_LIMIT_PROCESS_MEMORY, JOB_OBJECT_LIMIT_PROCESS_TIME,
+        },
+      },
+    };
+
+    use super::ProcessLimits;
+
+    pub unsafe fn assign(process: HANDLE, limits: &ProcessLimits) -> std::io::Result<()> {
+      let job = CreateJobObjectW(None, PCWSTR::null())?;
+      let result = configure(job, limits).and_then(|()| AssignProcessToJobObject(job, process));
+      // the job lives as long as the process assigned to it
+      let _ = CloseHandle(job);
+      result.map_err(Into::into)
+    }
+
+    unsafe fn configure(job: HANDLE, limits: &ProcessLimits) -> windows::core::Result<()> {
+      let mut info = JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
+      if let Some(max_memory) = limits.max_memory {
+        info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_PROCESS_MEMORY;
+        info.ProcessMemoryLimit = max_memory as usize;
+      }
+      if let Some(max_cpu_time) = limits.max_cpu_time {
+        info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_PROCESS_TIME;
+        // in 100 nanoseconds intervals
+        info.BasicLimitInformation.PerProcessUserTimeLimit = max_cpu_time as i64 * 10_000_000;
+      }
+      SetInformationJobObject(
+        job,
+        JobObjectExtendedLimitInformation,
+        &info as *const _ as *const c_void,
+        std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+      )?;
+
+      if let Some(cpu_rate) = limits.cpu_rate {
+        let mut info = JOBOBJECT_CPU_RATE_CONTROL_INFORMATION {
+          ControlFlags: JOB_OBJECT_CPU_RATE_CONTROL_ENABLE | JOB_OBJECT_CPU_RATE_CONTROL_HARD_CAP,
+          ..Default::default()
+        };
+        // in hundredths of a percent
+        info.Anonymous.CpuRate = cpu_rate.clamp(1, 100) as u32 * 100;
+        SetInformationJobObject(
+          job,
+          JobObjectCpuRateControlInformation,
+          &info as *const _ as *const c_void,
+          std::mem::size_of::<JOBOBJECT_CPU_RATE_CONTROL_INFORMATION>() as u32,
+        )?;
+      }
+
+      Ok(())
+    }
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd",
+  target_os = "macos"
+))]
+mod platform {
+  use std::{
+    os::unix::process::CommandExt,
+    process::{Child, Command},
+  };
+
+  use super::{unsupported, ProcessLimits};
+
+  pub fn check(limits: &ProcessLimits) -> std::io::Result<()> {
+    if limits.cpu_rate.is_some() {
+      return Err(unsupported("CPU rate"));
+    }
+    #[cfg(not(target_os = "linux"))]
+    if limits.max_memory.is_some() {
+      return Err(unsupported("memory"));
+    }
+    Ok(())
+  }
+
+  pub fn spawn_limited(command: &mut Command, limits: &ProcessLimits) -> std::io::Result<Child> {
+    let limits = limits.clone();
+    // only async-signal-safe system calls run between fork and exec
+    unsafe {
+      command.pre_exec(move || {
+        if let Some(nice) = limits.nice {
+          check_result(libc::setpriority(
+            libc::PRIO_PROCESS,
+            0,
+            nice as libc::c_int,
+          ))?;
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(max_memory) = limits.max_memory {
+          check_result(libc::setrlimit(
+            libc::RLIMIT_AS,
+            &rlimit(max_memory, max_memory),
+          ))?;
+        }
+        if let Some(max_cpu_time) = limits.max_cpu_time {
+          check_result(libc::setrlimit(
+            libc::RLIMIT_CPU,
+            &rlimit(max_cpu_time, max_cpu_time + 1),
+          ))?;
+        }
+        Ok(())
+      });
+    }
+    command.spawn()
+  }
+
+  pub fn limit_process(pid: u32, limits: &ProcessLimits) -> std::io::Result<()> {
+    if let Some(nice) = limits.nice {
+      check_result(unsafe {
+        libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice as libc::c_int)
+      })?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+      if let Some(max_memory) = limits.max_memory {
+        check_result(unsafe {
+          libc::prlimit(
+            pid as libc::pid_t,
+            libc::RLIMIT_AS,
+            &rlimit(max_memory, max_memory),
+            std::ptr::null_mut(),
+          )
+        })?;
+      }
+      if let Some(max_cpu_time) = limits.max_cpu_time {
+        check_result(unsafe {
+          libc::prlimit(
+            pid as libc::pid_t,
+            libc::RLIMIT_CPU,
+            &rlimit(max_cpu_time, max_cpu_time + 1),
+            std::ptr::null_mut(),
+          )
+        })?;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// SIGXCPU is sent at the soft CPU time limit and SIGKILL at the hard limit.
+  fn rlimit(soft: u64, hard: u64) -> libc::rlimit {
+    libc::rlimit {
+      rlim_cur: soft as libc::rlim_t,
+      rlim_max: hard as libc::rlim_t,
+    }
+  }
+
+  fn check_result(result: libc::c_int) -> std::io::Result<()> {
+    if result == 0 {
+      Ok(())
+    } else {
+      Err(std::io::Error::last_os_error())
+    }
+  }
+}
+
+#[cfg(not(any(
+  windows,
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd",
+  target_os = "macos"
+)))]
+mod platform {
+  use std::process::{Child, Command};
+
+  use super::{unsupported, ProcessLimits};
+
+  pub fn check(limits: &ProcessLimits) -> std::io::Result<()> {
+    if *limits == ProcessLimits::default() {
+      Ok(())
+    } else {
+      Err(unsupported("process"))
+    }
+  }
+
+  pub fn spawn_limited(command: &mut Command, _limits: &ProcessLimits) -> std::io::Result<Child> {
+    command.spawn()
+  }
+
+  pub fn limit_process(_pid: u32, _limits: &ProcessLimits) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn resolves_sidecar_limits() {
+    let mut config: Config = serde_json::from_value(serde_json::json!({
+      "identifier": "app.tauri.test",
+      "app": {
+        "sidecarLimits": {
+          "my-sidecar": { "nice": 10 },
+          "*": { "maxCpuTime": 60 }
+        }
+      }
+    }))
+    .unwrap();
+    assert_eq!(
+      sidecar_limits(&config, "binaries/my-sidecar.exe").and_then(|l| l.nice),
+      Some(10)
+    );
+    assert_eq!(
+      sidecar_limits(&config, "other").and_then(|l| l.max_cpu_time),
+      Some(60)
+    );
+    config.app.sidecar_limits.remove("*");
+    assert!(sidecar_limits(&config, "other").is_none());
+  }
+
+  #[cfg(target_os = "linux")]
+  #[test]
+  fn spawns_with_limits() {
+    let output = spawn_limited(
+      std::process::Command::new("sh")
+        .args(["-c", "ulimit -t; nice"])
+        .stdout(std::process::Stdio::piped()),
+      &ProcessLimits {
+        max_cpu_time: Some(30),
+        nice: Some(5),
+        ..Default::default()
+      },
+    )
+    .unwrap()
+    .wait_with_output()
+    .unwrap();
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(output.lines().collect::<Vec<_>>(), ["30", "5"]);
+  }
+}
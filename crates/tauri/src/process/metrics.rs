@@ -0,0 +1,366 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::collections::{HashMap, VecDeque};
+
+use serde::Serialize;
+
+/// Event emitted with the [`ProcessMetrics`] payload by [`crate::AppHandle::watch_process_metrics`].
+pub const PROCESS_METRICS_EVENT: &str = "tauri://process-metrics";
+
+/// Resource usage of the application process and its child processes.
+///
+/// See [`metrics`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ProcessMetrics {
+  /// The application process identifier.
+  pub pid: u32,
+  /// Resident memory (working set on Windows) of the application process in bytes.
+  pub resident_memory: Option<u64>,
+  /// Number of open handles on Windows, or open file descriptors on other platforms.
+  pub handle_count: Option<u64>,
+  /// The processes spawned by the application, directly or not,
+  /// including the webview engine processes on Windows and Linux.
+  pub child_processes: Vec<ChildProcessMetrics>,
+}
+
+impl ProcessMetrics {
+  /// Resident memory of the application process and all of its child processes in bytes.
+  pub fn total_resident_memory(&self) -> u64 {
+    self.resident_memory.unwrap_or_default()
+      + self
+        .child_processes
+        .iter()
+        .filter_map(|p| p.resident_memory)
+        .sum::<u64>()
+  }
+}
+
+/// Resource usage of a child process.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ChildProcessMetrics {
+  /// The process identifier.
+  pub pid: u32,
+  /// The process executable name, such as `msedgewebview2.exe` or `WebKitWebProcess`.
+  pub name: String,
+  /// Resident memory (working set on Windows) in bytes.
+  pub resident_memory: Option<u64>,
+}
+
+/// Collects the resource usage of the current process and its child processes.
+///
+/// # Platform-specific
+///
+/// - **macOS:** The WebKit processes are XPC services launched by the system rather than child processes,
+///   so their memory is not included.
+/// - **iOS / BSD:** Unsupported, only the process identifier is reported.
+///
+/// Memory usage is not attributed to individual webviews since the webview engines do not expose it.
+pub fn metrics() -> ProcessMetrics {
+  let pid = std::process::id();
+  let child_processes = descendants(pid, platform::processes())
+    .into_iter()
+    .map(|(pid, name)| ChildProcessMetrics {
+      pid,
+      name,
+      resident_memory: platform::resident_memory(pid),
+    })
+    .collect();
+
+  ProcessMetrics {
+    pid,
+    resident_memory: platform::resident_memory(pid),
+    handle_count: platform::handle_count(),
+    child_processes,
+  }
+}
+
+/// Returns the identifier and name of all descendants of the given process,
+/// from `(pid, parent pid, name)` entries.
+fn descendants(root: u32, processes: Vec<(u32, u32, String)>) -> Vec<(u32, String)> {
+  let mut children = HashMap::<u32, Vec<(u32, String)>>::new();
+  for (pid, ppid, name) in processes {
+    // guard against a process listed as its own parent
+    if pid != ppid {
+      children.entry(ppid).or_default().push((pid, name));
+    }
+  }
+
+  let mut result = Vec::new();
+  let mut queue = VecDeque::from([root]);
+  while let Some(pid) = queue.pop_front() {
+    for (child, name) in children.remove(&pid).unwrap_or_default() {
+      queue.push_back(child);
+      result.push((child, name));
+    }
+  }
+  result
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod platform {
+  pub fn resident_memory(pid: u32) -> Option<u64> {
+    let status = std::fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    status
+      .lines()
+      .find_map(|line| line.strip_prefix("VmRSS:"))
+      .and_then(|value| value.trim().strip_suffix("kB"))
+      .and_then(|kb| kb.trim().parse::<u64>().ok())
+      .map(|kb| kb * 1024)
+  }
+
+  pub fn handle_count() -> Option<u64> {
+    std::fs::read_dir("/proc/self/fd")
+      .ok()
+      .map(|entries| entries.count() as u64)
+  }
+
+  pub fn processes() -> Vec<(u32, u32, String)> {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+      return Vec::new();
+    };
+
+    entries
+      .flatten()
+      .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+      .filter_map(|pid| {
+        let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+        let (ppid, name) = super::parse_proc_stat(&stat)?;
+        Some((pid, ppid, name))
+      })
+      .collect()
+  }
+}
+
+/// Parses the parent process identifier and the name from the content of `/proc/<pid>/stat`.
+#[cfg(any(target_os = "linux", target_os = "android", test))]
+fn parse_proc_stat(stat: &str) -> Option<(u32, String)> {
+  // the name is wrapped in parenthesis and may contain spaces and parenthesis itself
+  let name_start = stat.find('(')? + 1;
+  let name_end = stat.rfind(')')?;
+  let name = stat.get(name_start..name_end)?.to_string();
+  // the state is followed by the parent pid
+  let ppid = stat[name_end + 1..]
+    .split_whitespace()
+    .nth(1)?
+    .parse()
+    .ok()?;
+  Some((ppid, name))
+}
+
+#[cfg(windows)]
+mod platform {
+  use windows::Win32::{
+    Foundation::CloseHandle,
+    System::{
+      Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+        TH32CS_SNAPPROCESS,
+      },
+      ProcessStatus::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS},
+      Threading::{
+        GetCurrentProcess, GetProcessHandleCount, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+      },
+    },
+  };
+
+  pub fn resident_memory(pid: u32) -> Option<u64> {
+    unsafe {
+      let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+      let mut counters = PROCESS_MEMORY_COUNTERS::default();
+      let result = GetProcessMemoryInfo(
+        process,
+        &mut counters,
+        std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32,
+      );
+      let _ = CloseHandle(process);
+      result.ok().map(|_| counters.WorkingSetSize as u64)
+    }
+  }
+
+  pub fn handle_count() -> Option<u64> {
+    let mut count = 0;
+    unsafe { GetProcessHandleCount(GetCurrentProcess(), &mut count) }
+      .ok()
+      .map(|_| count as u64)
+  }
+
+  pub fn processes() -> Vec<(u32, u32, String)> {
+    let mut processes = Vec::new();
+    unsafe {
+      let Ok(snapshot) = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) else {
+        return processes;
+      };
+
+      let mut entry = PROCESSENTRY32W {
+        dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+        ..Default::default()
+      };
+      let mut next = Process32FirstW(snapshot, &mut entry);
+      while next.is_ok() {
+        let len = entry
+          .szExeFile
+          .iter()
+          .position(|c| *c == 0)
+          .unwrap_or(entry.szExeFile.len());
+        processes.push((
+          entry.th32ProcessID,
+          entry.th32ParentProcessID,
+          String::from_utf16_lossy(&entry.szExeFile[..len]),
+        ));
+        next = Process32NextW(snapshot, &mut entry);
+      }
+
+      let _ = CloseHandle(snapshot);
+    }
+    processes
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  pub fn resident_memory(pid: u32) -> Option<u64> {
+    let mut info = std::mem::MaybeUninit::<libc::proc_taskinfo>::zeroed();
+    let size = std::mem::size_of::<libc::proc_taskinfo>() as libc::c_int;
+    let written = unsafe {
+      libc::proc_pidinfo(
+        pid as libc::c_int,
+        libc::PROC_PIDTASKINFO,
+        0,
+        info.as_mut_ptr().cast(),
+        size,
+      )
+    };
+    (written == size).then(|| unsafe { info.assume_init() }.pti_resident_size)
+  }
+
+  pub fn handle_count() -> Option<u64> {
+    std::fs::read_dir("/dev/fd")
+      .ok()
+      .map(|entries| entries.count() as u64)
+  }
+
+  pub fn processes() -> Vec<(u32, u32, String)> {
+    let count = unsafe { libc::proc_listallpids(std::ptr::null_mut(), 0) };
+    if count <= 0 {
+      return Vec::new();
+    }
+    // leave room for processes spawned in the meantime
+    let mut pids = vec![0 as libc::pid_t; count as usize + 32];
+    let count = unsafe {
+      libc::proc_listallpids(
+        pids.as_mut_ptr().cast(),
+        (pids.len() * std::mem::size_of::<libc::pid_t>()) as libc::c_int,
+      )
+    };
+    pids.truncate(count.max(0) as usize);
+
+    pids
+      .into_iter()
+      .filter_map(|pid| {
+        let mut info = std::mem::MaybeUninit::<libc::proc_bsdinfo>::zeroed();
+        let size = std::mem::size_of::<libc::proc_bsdinfo>() as libc::c_int;
+        let written = unsafe {
+          libc::proc_pidinfo(
+            pid,
+            libc::PROC_PIDTBSDINFO,
+            0,
+            info.as_mut_ptr().cast(),
+            size,
+          )
+        };
+        if written != size {
+          return None;
+        }
+        let info = unsafe { info.assume_init() };
+        // the name is not NUL terminated when it fills the whole buffer
+        let name = info
+          .pbi_comm
+          .iter()
+          .take_while(|c| **c != 0)
+          .map(|c| *c as u8)
+          .collect::<Vec<_>>();
+        Some((
+          pid as u32,
+          info.pbi_ppid,
+          String::from_utf8_lossy(&name).into_owned(),
+        ))
+      })
+      .collect()
+  }
+}
+
+#[cfg(not(any(
+  target_os = "linux",
+  target_os = "android",
+  windows,
+  target_os = "macos"
+)))]
+mod platform {
+  pub fn resident_memory(_pid: u32) -> Option<u64> {
+    None
+  }
+
+  pub fn handle_count() -> Option<u64> {
+    None
+  }
+
+  pub fn processes() -> Vec<(u32, u32, String)> {
+    Vec::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn finds_descendants() {
+    let processes = vec![
+      (1, 0, "init".into()),
+      (10, 1, "app".into()),
+      (11, 10, "msedgewebview2.exe".into()),
+      (12, 11, "msedgewebview2.exe".into()),
+      (13, 1, "other".into()),
+      (14, 14, "loop".into()),
+    ];
+    let mut descendants = descendants(10, processes);
+    descendants.sort();
+    assert_eq!(
+      descendants,
+      vec![
+        (11, "msedgewebview2.exe".to_string()),
+        (12, "msedgewebview2.exe".to_string())
+      ]
+    );
+  }
+
+  #[test]
+  fn parses_proc_stat() {
+    assert_eq!(
+      parse_proc_stat("4242 (WebKitWebProcess) S 4200 4200 4200 0 -1"),
+      Some((4200, "WebKitWebProcess".into()))
+    );
+    assert_eq!(
+      parse_proc_stat("7 (my (app) name) R 1 7 7 0 -1"),
+      Some((1, "my (app) name".into()))
+    );
+    assert_eq!(parse_proc_stat("garbage"), None);
+  }
+
+  #[test]
+  fn current_process() {
+    let metrics = metrics();
+    assert_eq!(metrics.pid, std::process::id());
+    #[cfg(any(target_os = "linux", windows, target_os = "macos"))]
+    {
+      assert!(metrics.resident_memory.unwrap() > 0);
+      assert!(metrics.handle_count.unwrap() > 0);
+    }
+  }
+}
@@ -8,6 +8,14 @@ use crate::Env;
 
 use std::path::PathBuf;
 
+mod budget;
+mod limits;
+mod metrics;
+
+pub use budget::Budget;
+pub use limits::{limit_process, sidecar_limits, spawn_limited, spawn_sidecar, ProcessLimits};
+pub use metrics::{metrics, ChildProcessMetrics, ProcessMetrics, PROCESS_METRICS_EVENT};
+
 /// Finds the current running binary's path.
 ///
 /// With exception to any following platform-specific behavior, the path is cached as soon as
@@ -25,6 +25,7 @@ struct CachedResponse {
 
 pub fn get<R: Runtime>(
   #[allow(unused_variables)] manager: Arc<AppManager<R>>,
+  #[allow(unused_variables)] window_label: String,
   window_origin: &str,
   web_resource_request_handler: Option<Box<WebResourceRequestHandler>>,
 ) -> UriSchemeProtocolHandler {
@@ -46,6 +47,7 @@ pub fn get<R: Runtime>(
     match get_response(
       request,
       &manager,
+      &window_label,
       &window_origin,
       web_resource_request_handler.as_deref(),
       #[cfg(all(dev, mobile))]
@@ -67,6 +69,7 @@ pub fn get<R: Runtime>(
 fn get_response<R: Runtime>(
   request: Request<Vec<u8>>,
   #[allow(unused_variables)] manager: &AppManager<R>,
+  #[allow(unused_variables)] window_label: &str,
   window_origin: &str,
   web_resource_request_handler: Option<&WebResourceRequestHandler>,
   #[cfg(all(dev, mobile))] (url, response_cache): (
@@ -98,7 +101,11 @@ fn get_response<R: Runtime>(
   let mut builder = HttpResponse::builder().header("Access-Control-Allow-Origin", window_origin);
 
   #[cfg(all(dev, mobile))]
-  let mut response = {
+  let mut response = if manager.window_assets.contains_key(window_label) {
+    // windows with their own frontend are served from their embedded assets, also in development
+    let path = path.split(&['?', '#'][..]).next().unwrap().to_string();
+    asset_response(manager, window_label, path, builder)?
+  } else {
     let decoded_path = percent_encoding::percent_decode(path.as_bytes())
       .decode_utf8_lossy()
       .to_string();
@@ -151,17 +158,24 @@ fn get_response<R: Runtime>(
   };
 
   #[cfg(not(all(dev, mobile)))]
-  let mut response = {
-    let asset = manager.get_asset(path)?;
-    builder = builder.header(CONTENT_TYPE, &asset.mime_type);
-    if let Some(csp) = &asset.csp_header {
-      builder = builder.header("Content-Security-Policy", csp);
-    }
-    builder.body(asset.bytes.into())?
-  };
+  let mut response = asset_response(manager, window_label, path, builder)?;
   if let Some(handler) = &web_resource_request_handler {
     handler(request, &mut response);
   }
 
   Ok(response)
 }
+
+fn asset_response<R: Runtime>(
+  manager: &AppManager<R>,
+  window_label: &str,
+  path: String,
+  mut builder: http::response::Builder,
+) -> Result<HttpResponse<Cow<'static, [u8]>>, Box<dyn std::error::Error>> {
+  let asset = manager.get_window_asset(window_label, path)?;
+  builder = builder.header(CONTENT_TYPE, &asset.mime_type);
+  if let Some(csp) = &asset.csp_header {
+    builder = builder.header("Content-Security-Policy", csp);
+  }
+  Ok(builder.body(asset.bytes.into())?)
+}
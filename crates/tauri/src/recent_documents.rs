@@ -0,0 +1,196 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The recent documents of the operating system, shown in the taskbar jump list, the Dock menu
+//! or the file chooser "Recent" location. The functions must be called on the main thread.
+
+use std::{
+  ffi::OsString,
+  path::{Path, PathBuf},
+};
+
+use crate::utils::config::FileAssociation;
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use gtk::prelude::*;
+  use std::path::Path;
+
+  pub fn add(path: &Path) -> Result<(), String> {
+    let uri = url::Url::from_file_path(path).map_err(|_| "invalid path".to_string())?;
+    let manager = gtk::RecentManager::default().ok_or("recent documents not available")?;
+    // writes `recently-used.xbel`
+    if manager.add_item(uri.as_str()) {
+      Ok(())
+    } else {
+      Err("failed to add the recent document".into())
+    }
+  }
+
+  pub fn clear() -> Result<(), String> {
+    let manager = gtk::RecentManager::default().ok_or("recent documents not available")?;
+    let app_name = gtk::glib::application_name();
+    // the list is shared by all apps, only removes the documents opened by this app alone
+    for item in manager.items() {
+      let applications = item.applications();
+      if applications.len() == 1 && app_name.as_ref() == Some(&applications[0]) {
+        if let Some(uri) = item.uri() {
+          manager.remove_item(&uri).map_err(|e| e.to_string())?;
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use objc2::{class, msg_send, msg_send_id, rc::Retained, runtime::AnyObject};
+  use objc2_foundation::NSString;
+  use std::path::Path;
+
+  unsafe fn document_controller() -> Retained<AnyObject> {
+    msg_send_id![class!(NSDocumentController), sharedDocumentController]
+  }
+
+  pub fn add(path: &Path) -> Result<(), String> {
+    unsafe {
+      let path = NSString::from_str(&path.to_string_lossy());
+      let url: Retained<AnyObject> = msg_send_id![class!(NSURL), fileURLWithPath: &*path];
+      let _: () = msg_send![&document_controller(), noteNewRecentDocumentURL: &*url];
+    }
+    Ok(())
+  }
+
+  pub fn clear() -> Result<(), String> {
+    unsafe {
+      let _: () = msg_send![
+        &document_controller(),
+        clearRecentDocuments: std::ptr::null_mut::<AnyObject>()
+      ];
+    }
+    Ok(())
+  }
+}
+
+#[cfg(windows)]
+mod platform {
+  use std::{os::windows::ffi::OsStrExt, path::Path};
+  use windows::Win32::{
+    System::Com::{CoCreateInstance, CLSCTX_INPROC_SERVER},
+    UI::Shell::{
+      ApplicationDestinations, IApplicationDestinations, SHAddToRecentDocs, SHARD_PATHW,
+    },
+  };
+
+  pub fn add(path: &Path) -> Result<(), String> {
+    let path = path
+      .as_os_str()
+      .encode_wide()
+      .chain(std::iter::once(0))
+      .collect::<Vec<_>>();
+    // also adds the document to the jump list of the app
+    unsafe { SHAddToRecentDocs(SHARD_PATHW.0 as u32, Some(path.as_ptr() as _)) };
+    Ok(())
+  }
+
+  pub fn clear() -> Result<(), String> {
+    unsafe {
+      let destinations: IApplicationDestinations =
+        CoCreateInstance(&ApplicationDestinations, None, CLSCTX_INPROC_SERVER)
+          .map_err(|e| e.to_string())?;
+      destinations
+        .RemoveAllDestinations()
+        .map_err(|e| e.to_string())
+    }
+  }
+}
+
+/// Adds the document to the recent documents of the app, must be called on the main thread.
+pub(crate) fn add(path: &Path) -> crate::Result<()> {
+  platform::add(path).map_err(|e| {
+    std::io::Error::other(format!(
+      "failed to add {} to the recent documents: {e}",
+      path.display()
+    ))
+    .into()
+  })
+}
+
+/// Clears the recent documents of the app, must be called on the main thread.
+pub(crate) fn clear() -> crate::Result<()> {
+  platform::clear()
+    .map_err(|e| std::io::Error::other(format!("failed to clear the recent documents: {e}")).into())
+}
+
+/// Adds the documents opened through the file associations, which the operating system passes
+/// as command line arguments on Windows and Linux, to the recent documents.
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn add_associated_files(
+  associations: &[FileAssociation],
+  args: impl IntoIterator<Item = OsString>,
+) {
+  for path in associated_files(associations, args) {
+    // the recent documents are resolved later, possibly from another working directory
+    let result = dunce::canonicalize(&path)
+      .map_err(Into::into)
+      .and_then(|path| add(&path));
+    if let Err(e) = result {
+      log::error!("{e}");
+    }
+  }
+}
+
+/// The arguments with the extension of a file association.
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+fn associated_files(
+  associations: &[FileAssociation],
+  args: impl IntoIterator<Item = OsString>,
+) -> Vec<PathBuf> {
+  args
+    .into_iter()
+    .map(PathBuf::from)
+    .filter(|path| {
+      path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+          associations
+            .iter()
+            .flat_map(|association| &association.ext)
+            .any(|ext| ext.0.eq_ignore_ascii_case(extension))
+        })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::config::{AssociationExt, BundleTypeRole};
+
+  #[test]
+  fn filters_associated_files() {
+    let associations = [FileAssociation {
+      ext: vec![AssociationExt("md".into()), AssociationExt("txt".into())],
+      name: None,
+      description: None,
+      role: BundleTypeRole::Editor,
+      mime_type: None,
+    }];
+    let args = ["--verbose", "notes.MD", "todo.txt", "image.png", "README"]
+      .into_iter()
+      .map(OsString::from);
+    assert_eq!(
+      associated_files(&associations, args),
+      vec![PathBuf::from("notes.MD"), PathBuf::from("todo.txt")]
+    );
+  }
+}
@@ -0,0 +1,706 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Encryption of local data with a key protected by the operating system user account.
+//!
+//! The data is encrypted with AES-256-GCM using a random key generated on first use
+//! and stored in the secret store of the operating system (DPAPI, the Keychain or the Secret Service),
+//! so local databases and caches can be encrypted without the app managing raw keys.
+//! The key is only readable by the same user, and on macOS by the same app unless the user allows another one.
+//! On Windows and Linux, other apps running as the same user can read it while the user session is unlocked.
+
+use std::{path::PathBuf, sync::Mutex};
+
+use aes_gcm::{
+  aead::{Aead, KeyInit},
+  Aes256Gcm, Nonce,
+};
+
+use crate::{Manager, Runtime};
+
+/// The prefix of the encrypted data, identifying the format version.
+const PREFIX: &[u8] = b"v1";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+/// Safe storage errors.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+  /// The operating system secret store is not available or refused to release the key.
+  #[error("safe storage is not available: {0}")]
+  Unavailable(String),
+  /// The data was not encrypted with the key of this app and user, or it was altered.
+  #[error("the data could not be decrypted")]
+  Decrypt,
+}
+
+/// Where the key is stored.
+struct KeyLocation {
+  /// The Keychain and Secret Service entry name.
+  #[cfg_attr(any(windows, mobile), allow(dead_code))]
+  service: String,
+  /// The file holding the DPAPI protected key, also locked while the Secret Service entry is created.
+  #[cfg_attr(any(target_os = "macos", mobile), allow(dead_code))]
+  file: PathBuf,
+}
+
+/// The key loaded by the first [`encrypt`] or [`decrypt`] call.
+#[derive(Default)]
+pub(crate) struct SafeStorageKey(Mutex<Option<[u8; KEY_LEN]>>);
+
+impl SafeStorageKey {
+  fn get<R: Runtime, M: Manager<R>>(&self, manager: &M) -> crate::Result<[u8; KEY_LEN]> {
+    let mut key = self.0.lock().unwrap();
+    if let Some(key) = *key {
+      return Ok(key);
+    }
+
+    let location = KeyLocation {
+      service: format!("{} Safe Storage", manager.config().identifier),
+      file: manager
+        .path()
+        .app_local_data_dir()?
+        .join("safe-storage.key"),
+    };
+    let secret = match platform::read_secret(&location)? {
+      Some(secret) => secret,
+      None => {
+        let mut new_key = [0u8; KEY_LEN];
+        getrandom::getrandom(&mut new_key)?;
+        platform::add_secret(&location, &to_hex(&new_key))?;
+        // another process may have stored its key first, the key that was stored wins
+        platform::read_secret(&location)?
+          .ok_or_else(|| Error::Unavailable("the key could not be stored".into()))?
+      }
+    };
+    let loaded = from_hex(&secret)
+      .and_then(|bytes| <[u8; KEY_LEN]>::try_from(bytes).ok())
+      .ok_or_else(|| Error::Unavailable("the stored key is invalid".into()))?;
+    key.replace(loaded);
+    Ok(loaded)
+  }
+}
+
+/// Encrypts the data with the safe storage key of the app.
+///
+/// The key is created and stored in the secret store of the operating system on first use.
+/// This function may block while the operating system prompts the user to unlock it.
+///
+/// ## Platform-specific
+///
+/// - **Windows:** The key is protected with DPAPI and stored in the app local data directory.
+/// - **macOS:** The key is stored in the login Keychain.
+/// - **Linux:** The key is stored in the default collection of the Secret Service (GNOME Keyring, KWallet) through D-Bus.
+///   There is no fallback to an unprotected key.
+///   The Secret Service does not isolate apps, the key is readable by any app of the user while the keyring is unlocked.
+/// - **Android / iOS:** Unsupported.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// #[tauri::command]
+/// fn save_token(app: tauri::AppHandle, token: String) -> Result<(), String> {
+///   let encrypted = tauri::safe_storage::encrypt(&app, token.as_bytes()).map_err(|e| e.to_string())?;
+///   std::fs::write("token.bin", encrypted).map_err(|e| e.to_string())
+/// }
+/// ```
+pub fn encrypt<R: Runtime, M: Manager<R>>(manager: &M, data: &[u8]) -> crate::Result<Vec<u8>> {
+  let key = manager.state::<SafeStorageKey>().get(manager)?;
+  let mut nonce = [0u8; NONCE_LEN];
+  getrandom::getrandom(&mut nonce)?;
+  Ok(seal(&key, &nonce, data))
+}
+
+/// Decrypts data returned by [`encrypt`].
+///
+/// Fails with [`Error::Decrypt`] if the data was encrypted by another app or user, or was altered.
+pub fn decrypt<R: Runtime, M: Manager<R>>(manager: &M, data: &[u8]) -> crate::Result<Vec<u8>> {
+  let key = manager.state::<SafeStorageKey>().get(manager)?;
+  open(&key, data).map_err(Into::into)
+}
+
+fn seal(key: &[u8; KEY_LEN], nonce: &[u8; NONCE_LEN], data: &[u8]) -> Vec<u8> {
+  let cipher = Aes256Gcm::new(key.into());
+  let ciphertext = cipher
+    .encrypt(Nonce::from_slice(nonce), data)
+    .expect("AES-GCM encryption cannot fail with a valid nonce");
+  let mut sealed = Vec::with_capacity(PREFIX.len() + NONCE_LEN + ciphertext.len());
+  sealed.extend_from_slice(PREFIX);
+  sealed.extend_from_slice(nonce);
+  sealed.extend_from_slice(&ciphertext);
+  sealed
+}
+
+fn open(key: &[u8; KEY_LEN], data: &[u8]) -> Result<Vec<u8>, Error> {
+  let data = data.strip_prefix(PREFIX).ok_or(Error::Decrypt)?;
+  if data.len() < NONCE_LEN {
+    return Err(Error::Decrypt);
+  }
+  let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+  Aes256Gcm::new(key.into())
+    .decrypt(Nonce::from_slice(nonce), ciphertext)
+    .map_err(|_| Error::Decrypt)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+  bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+  if s.len() % 2 != 0 {
+    return None;
+  }
+  (0..s.len())
+    .step_by(2)
+    .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+    .collect()
+}
+
+#[cfg(windows)]
+mod platform {
+  use super::{Error, KeyLocation};
+  use windows::Win32::{
+    Foundation::{LocalFree, HLOCAL},
+    Security::Cryptography::{
+      CryptProtectData, CryptUnprotectData, CRYPTPROTECT_UI_FORBIDDEN, CRYPT_INTEGER_BLOB,
+    },
+  };
+
+  pub fn read_secret(location: &KeyLocation) -> Result<Option<String>, Error> {
+    let mut protected = match std::fs::read(&location.file) {
+      Ok(protected) => protected,
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+      Err(e) => return Err(Error::Unavailable(e.to_string())),
+    };
+    let input = CRYPT_INTEGER_BLOB {
+      cbData: protected.len() as u32,
+      pbData: protected.as_mut_ptr(),
+    };
+    let mut output = CRYPT_INTEGER_BLOB::default();
+    let secret = unsafe {
+      CryptUnprotectData(
+        &input,
+        None,
+        None,
+        None,
+        None,
+        CRYPTPROTECT_UI_FORBIDDEN,
+        &mut output,
+      )
+      .map_err(|e| Error::Unavailable(e.to_string()))?;
+      let secret = std::slice::from_raw_parts(output.pbData, output.cbData as usize).to_vec();
+      let _ = LocalFree(HLOCAL(output.pbData as _));
+      secret
+    };
+    String::from_utf8(secret)
+      .map(Some)
+      .map_err(|_| Error::Unavailable("the stored key is invalid".into()))
+  }
+
+  /// Stores the secret unless a secret is already stored.
+  pub fn add_secret(location: &KeyLocation, secret: &str) -> Result<(), Error> {
+    let mut secret = secret.as_bytes().to_vec();
+    let input = CRYPT_INTEGER_BLOB {
+      cbData: secret.len() as u32,
+      pbData: secret.as_mut_ptr(),
+    };
+    let mut output = CRYPT_INTEGER_BLOB::default();
+    let protected = unsafe {
+      CryptProtectData(
+        &input,
+        windows::core::w!("Safe Storage"),
+        None,
+        None,
+        None,
+        CRYPTPROTECT_UI_FORBIDDEN,
+        &mut output,
+      )
+      .map_err(|e| Error::Unavailable(e.to_string()))?;
+      let protected = std::slice::from_raw_parts(output.pbData, output.cbData as usize).to_vec();
+      let _ = LocalFree(HLOCAL(output.pbData as _));
+      protected
+    };
+
+    if let Some(parent) = location.file.parent() {
+      std::fs::create_dir_all(parent).map_err(|e| Error::Unavailable(e.to_string()))?;
+    }
+    // the key is written to a temporary file and linked to its location, which fails if it exists,
+    // so concurrent processes never overwrite or read a partially written key
+    let temp = location
+      .file
+      .with_extension(format!("{}.tmp", std::process::id()));
+    std::fs::write(&temp, protected).map_err(|e| Error::Unavailable(e.to_string()))?;
+    let result = std::fs::hard_link(&temp, &location.file);
+    let _ = std::fs::remove_file(&temp);
+    match result {
+      Ok(()) => Ok(()),
+      Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(()),
+      Err(e) => Err(Error::Unavailable(e.to_string())),
+    }
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use super::{Error, KeyLocation};
+  use std::{ffi::c_void, ptr};
+
+  type CFTypeRef = *const c_void;
+
+  /// The `CFDictionaryKeyCallBacks` and `CFDictionaryValueCallBacks` structures, only used by address.
+  #[repr(C)]
+  struct CallBacks {
+    _private: [u8; 0],
+  }
+
+  #[link(name = "CoreFoundation", kind = "framework")]
+  extern "C" {
+    static kCFTypeDictionaryKeyCallBacks: CallBacks;
+    static kCFTypeDictionaryValueCallBacks: CallBacks;
+    static kCFBooleanTrue: CFTypeRef;
+    fn CFDictionaryCreate(
+      allocator: CFTypeRef,
+      keys: *const CFTypeRef,
+      values: *const CFTypeRef,
+      num_values: isize,
+      key_callbacks: *const CallBacks,
+      value_callbacks: *const CallBacks,
+    ) -> CFTypeRef;
+    fn CFStringCreateWithBytes(
+      allocator: CFTypeRef,
+      bytes: *const u8,
+      num_bytes: isize,
+      encoding: u32,
+      is_external_representation: u8,
+    ) -> CFTypeRef;
+    fn CFDataCreate(allocator: CFTypeRef, bytes: *const u8, length: isize) -> CFTypeRef;
+    fn CFDataGetBytePtr(data: CFTypeRef) -> *const u8;
+    fn CFDataGetLength(data: CFTypeRef) -> isize;
+    fn CFRelease(cf: CFTypeRef);
+  }
+
+  #[link(name = "Security", kind = "framework")]
+  extern "C" {
+    static kSecClass: CFTypeRef;
+    static kSecClassGenericPassword: CFTypeRef;
+    static kSecAttrService: CFTypeRef;
+    static kSecAttrAccount: CFTypeRef;
+    static kSecValueData: CFTypeRef;
+    static kSecReturnData: CFTypeRef;
+    static kSecMatchLimit: CFTypeRef;
+    static kSecMatchLimitOne: CFTypeRef;
+    fn SecItemCopyMatching(query: CFTypeRef, result: *mut CFTypeRef) -> i32;
+    fn SecItemAdd(attributes: CFTypeRef, result: *mut CFTypeRef) -> i32;
+  }
+
+  /// `errSecItemNotFound`
+  const ERR_SEC_ITEM_NOT_FOUND: i32 = -25300;
+  /// `errSecDuplicateItem`
+  const ERR_SEC_DUPLICATE_ITEM: i32 = -25299;
+  /// `kCFStringEncodingUTF8`
+  const UTF8: u32 = 0x0800_0100;
+  const ACCOUNT: &str = "Tauri";
+
+  /// A Core Foundation object released when dropped.
+  struct Owned(CFTypeRef);
+
+  impl Drop for Owned {
+    fn drop(&mut self) {
+      if !self.0.is_null() {
+        unsafe { CFRelease(self.0) };
+      }
+    }
+  }
+
+  fn string(s: &str) -> Owned {
+    Owned(unsafe { CFStringCreateWithBytes(ptr::null(), s.as_ptr(), s.len() as isize, UTF8, 0) })
+  }
+
+  fn dictionary(entries: &[(CFTypeRef, CFTypeRef)]) -> Owned {
+    let keys = entries.iter().map(|(key, _)| *key).collect::<Vec<_>>();
+    let values = entries.iter().map(|(_, value)| *value).collect::<Vec<_>>();
+    Owned(unsafe {
+      CFDictionaryCreate(
+        ptr::null(),
+        keys.as_ptr(),
+        values.as_ptr(),
+        entries.len() as isize,
+        &kCFTypeDictionaryKeyCallBacks,
+        &kCFTypeDictionaryValueCallBacks,
+      )
+    })
+  }
+
+  pub fn read_secret(location: &KeyLocation) -> Result<Option<String>, Error> {
+    let service = string(&location.service);
+    let account = string(ACCOUNT);
+    let query = unsafe {
+      dictionary(&[
+        (kSecClass, kSecClassGenericPassword),
+        (kSecAttrService, service.0),
+        (kSecAttrAccount, account.0),
+        (kSecReturnData, kCFBooleanTrue),
+        (kSecMatchLimit, kSecMatchLimitOne),
+      ])
+    };
+    let mut data = ptr::null();
+    let status = unsafe { SecItemCopyMatching(query.0, &mut data) };
+    match status {
+      0 => {
+        let data = Owned(data);
+        let secret = unsafe {
+          std::slice::from_raw_parts(CFDataGetBytePtr(data.0), CFDataGetLength(data.0) as usize)
+            .to_vec()
+        };
+        String::from_utf8(secret)
+          .map(Some)
+          .map_err(|_| Error::Unavailable("the stored key is invalid".into()))
+      }
+      ERR_SEC_ITEM_NOT_FOUND => Ok(None),
+      status => Err(Error::Unavailable(format!(
+        "failed to read the Keychain: OSStatus {status}"
+      ))),
+    }
+  }
+
+  /// Stores the secret unless a secret is already stored, the Keychain never replaces an existing item on add.
+  pub fn add_secret(location: &KeyLocation, secret: &str) -> Result<(), Error> {
+    let service = string(&location.service);
+    let account = string(ACCOUNT);
+    let data = Owned(unsafe { CFDataCreate(ptr::null(), secret.as_ptr(), secret.len() as isize) });
+    let attributes = unsafe {
+      dictionary(&[
+        (kSecClass, kSecClassGenericPassword),
+        (kSecAttrService, service.0),
+        (kSecAttrAccount, account.0),
+        (kSecValueData, data.0),
+      ])
+    };
+    let status = unsafe { SecItemAdd(attributes.0, ptr::null_mut()) };
+    match status {
+      0 | ERR_SEC_DUPLICATE_ITEM => Ok(()),
+      status => Err(Error::Unavailable(format!(
+        "failed to write to the Keychain: OSStatus {status}"
+      ))),
+    }
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use super::{Error, KeyLocation};
+  use gtk::{
+    gio::{self, DBusCallFlags, DBusConnection, DBusSignalFlags},
+    glib::{self, variant::ObjectPath, ToVariant, Variant},
+  };
+  use std::{cell::RefCell, collections::HashMap, fs::File, os::fd::AsRawFd, rc::Rc};
+
+  const SERVICE: &str = "org.freedesktop.secrets";
+  const SERVICE_PATH: &str = "/org/freedesktop/secrets";
+  const SERVICE_INTERFACE: &str = "org.freedesktop.Secret.Service";
+  const DEFAULT_COLLECTION: &str = "/org/freedesktop/secrets/aliases/default";
+  /// The path returned instead of a prompt when none is needed.
+  const NO_PROMPT: &str = "/";
+
+  fn unavailable(e: glib::Error) -> Error {
+    Error::Unavailable(e.to_string())
+  }
+
+  fn object_path(path: &str) -> Result<ObjectPath, Error> {
+    ObjectPath::try_from(path.to_string())
+      .map_err(|_| Error::Unavailable(format!("invalid D-Bus object path `{path}`")))
+  }
+
+  fn call(
+    connection: &DBusConnection,
+    path: &str,
+    interface: &str,
+    method: &str,
+    parameters: Option<&Variant>,
+  ) -> Result<Variant, Error> {
+    connection
+      .call_sync(
+        Some(SERVICE),
+        path,
+        interface,
+        method,
+        parameters,
+        None,
+        DBusCallFlags::NONE,
+        -1,
+        None::<&gio::Cancellable>,
+      )
+      .map_err(unavailable)
+  }
+
+  /// A session of the Secret Service, closed when dropped.
+  struct Session {
+    connection: DBusConnection,
+    path: ObjectPath,
+  }
+
+  impl Session {
+    /// Opens a session transferring the secrets unencrypted, which is fine on the private session bus.
+    fn open() -> Result<Self, Error> {
+      let connection =
+        gio::bus_get_sync(gio::BusType::Session, None::<&gio::Cancellable>).map_err(unavailable)?;
+      let reply = call(
+        &connection,
+        SERVICE_PATH,
+        SERVICE_INTERFACE,
+        "OpenSession",
+        Some(&("plain", "".to_variant()).to_variant()),
+      )?;
+      let path = reply
+        .child_value(1)
+        .get::<ObjectPath>()
+        .ok_or_else(|| Error::Unavailable("the Secret Service returned no session".into()))?;
+      Ok(Self { connection, path })
+    }
+
+    /// The items holding the key, unlocking them first if needed.
+    fn find_items(&self, location: &KeyLocation) -> Result<Vec<ObjectPath>, Error> {
+      let (unlocked, locked) = self.search(location)?;
+      if locked.is_empty() {
+        return Ok(unlocked);
+      }
+      let reply = call(
+        &self.connection,
+        SERVICE_PATH,
+        SERVICE_INTERFACE,
+        "Unlock",
+        Some(&(locked,).to_variant()),
+      )?;
+      if let Some(prompt) = reply.child_value(1).get::<ObjectPath>() {
+        self.prompt(&prompt)?;
+      }
+      Ok(self.search(location)?.0)
+    }
+
+    /// The unlocked and locked items holding the key.
+    fn search(&self, location: &KeyLocation) -> Result<(Vec<ObjectPath>, Vec<ObjectPath>), Error> {
+      call(
+        &self.connection,
+        SERVICE_PATH,
+        SERVICE_INTERFACE,
+        "SearchItems",
+        Some(&(attributes(location),).to_variant()),
+      )?
+      .get::<(Vec<ObjectPath>, Vec<ObjectPath>)>()
+      .ok_or_else(|| Error::Unavailable("unexpected Secret Service reply".into()))
+    }
+
+    fn read(&self, item: &ObjectPath) -> Result<Vec<u8>, Error> {
+      let reply = call(
+        &self.connection,
+        item.as_str(),
+        "org.freedesktop.Secret.Item",
+        "GetSecret",
+        Some(&(self.path.clone(),).to_variant()),
+      )?;
+      // the secret is a (session, parameters, value, content type) structure
+      reply
+        .child_value(0)
+        .child_value(2)
+        .get::<Vec<u8>>()
+        .ok_or_else(|| Error::Unavailable("unexpected Secret Service reply".into()))
+    }
+
+    fn create(&self, location: &KeyLocation, secret: &str) -> Result<(), Error> {
+      let properties = HashMap::from([
+        (
+          "org.freedesktop.Secret.Item.Label".to_string(),
+          location.service.to_variant(),
+        ),
+        (
+          "org.freedesktop.Secret.Item.Attributes".to_string(),
+          attributes(location).to_variant(),
+        ),
+      ]);
+      let secret = (
+        self.path.clone(),
+        Vec::<u8>::new(),
+        secret.as_bytes().to_vec(),
+        "text/plain",
+      );
+      let reply = call(
+        &self.connection,
+        DEFAULT_COLLECTION,
+        "org.freedesktop.Secret.Collection",
+        "CreateItem",
+        Some(&(properties, secret, false).to_variant()),
+      )?;
+      // the default collection is unlocked by a prompt, the item is created once it completes
+      if let Some(prompt) = reply.child_value(1).get::<ObjectPath>() {
+        self.prompt(&prompt)?;
+      }
+      Ok(())
+    }
+
+    /// Shows the prompt of the Secret Service and waits for the user to complete it.
+    fn prompt(&self, prompt: &ObjectPath) -> Result<(), Error> {
+      if prompt.as_str() == NO_PROMPT {
+        return Ok(());
+      }
+      // the completion signal is dispatched to the thread default context of the subscription
+      let context = glib::MainContext::new();
+      context
+        .with_thread_default(|| {
+          let dismissed = Rc::new(RefCell::new(None));
+          let dismissed_ = dismissed.clone();
+          let subscription = self.connection.signal_subscribe(
+            Some(SERVICE),
+            Some("org.freedesktop.Secret.Prompt"),
+            Some("Completed"),
+            Some(prompt.as_str()),
+            None,
+            DBusSignalFlags::NONE,
+            move |_connection, _sender, _path, _interface, _signal, parameters| {
+              dismissed_.replace(Some(
+                parameters.child_value(0).get::<bool>().unwrap_or(true),
+              ));
+            },
+          );
+          let shown = call(
+            &self.connection,
+            prompt.as_str(),
+            "org.freedesktop.Secret.Prompt",
+            "Prompt",
+            Some(&("",).to_variant()),
+          );
+          if shown.is_ok() {
+            while dismissed.borrow().is_none() {
+              context.iteration(true);
+            }
+          }
+          self.connection.signal_unsubscribe(subscription);
+          shown?;
+          match dismissed.take() {
+            Some(false) => Ok(()),
+            _ => Err(Error::Unavailable(
+              "the user dismissed the Secret Service prompt".into(),
+            )),
+          }
+        })
+        .map_err(|e| Error::Unavailable(e.to_string()))?
+    }
+  }
+
+  impl Drop for Session {
+    fn drop(&mut self) {
+      let _ = call(
+        &self.connection,
+        self.path.as_str(),
+        "org.freedesktop.Secret.Session",
+        "Close",
+        None,
+      );
+    }
+  }
+
+  /// The attributes identifying the key, which are the ones used by `secret-tool`.
+  fn attributes(location: &KeyLocation) -> HashMap<String, String> {
+    HashMap::from([("service".to_string(), location.service.clone())])
+  }
+
+  pub fn read_secret(location: &KeyLocation) -> Result<Option<String>, Error> {
+    let session = Session::open()?;
+    let Some(item) = session.find_items(location)?.into_iter().next() else {
+      return Ok(None);
+    };
+    String::from_utf8(session.read(&item)?)
+      .map(|secret| Some(secret.trim().to_string()))
+      .map_err(|_| Error::Unavailable("the stored key is invalid".into()))
+  }
+
+  /// Stores the secret unless a secret is already stored.
+  ///
+  /// The Secret Service can store several items with the same attributes, so the store is serialized
+  /// between processes with a lock on the key file and skipped if another process stored its secret first.
+  pub fn add_secret(location: &KeyLocation, secret: &str) -> Result<(), Error> {
+    let _lock = lock(location)?;
+    let session = Session::open()?;
+    if !session.find_items(location)?.is_empty() {
+      return Ok(());
+    }
+    session.create(location, secret)
+  }
+
+  /// Takes an exclusive lock on the lock file of the key, released when the file is dropped.
+  fn lock(location: &KeyLocation) -> Result<File, Error> {
+    let path = location.file.with_extension("lock");
+    if let Some(parent) = path.parent() {
+      std::fs::create_dir_all(parent).map_err(|e| Error::Unavailable(e.to_string()))?;
+    }
+    let file = File::options()
+      .create(true)
+      .truncate(false)
+      .write(true)
+      .open(&path)
+      .map_err(|e| Error::Unavailable(e.to_string()))?;
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } == 0 {
+      Ok(file)
+    } else {
+      Err(Error::Unavailable(
+        std::io::Error::last_os_error().to_string(),
+      ))
+    }
+  }
+}
+
+#[cfg(mobile)]
+mod platform {
+  use super::{Error, KeyLocation};
+
+  pub fn read_secret(_location: &KeyLocation) -> Result<Option<String>, Error> {
+    Err(Error::Unavailable("unsupported platform".into()))
+  }
+
+  pub fn add_secret(_location: &KeyLocation, _secret: &str) -> Result<(), Error> {
+    Err(Error::Unavailable("unsupported platform".into()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const KEY: [u8; KEY_LEN] = [7; KEY_LEN];
+  const NONCE: [u8; NONCE_LEN] = [1; NONCE_LEN];
+
+  #[test]
+  fn round_trip() {
+    let sealed = seal(&KEY, &NONCE, b"secret");
+    assert!(sealed.starts_with(PREFIX));
+    assert_ne!(&sealed[PREFIX.len() + NONCE_LEN..], b"secret");
+    assert_eq!(open(&KEY, &sealed).unwrap(), b"secret");
+  }
+
+  #[test]
+  fn rejects_other_keys_and_altered_data() {
+    let sealed = seal(&KEY, &NONCE, b"secret");
+    assert!(matches!(open(&[8; KEY_LEN], &sealed), Err(Error::Decrypt)));
+
+    let mut altered = sealed.clone();
+    *altered.last_mut().unwrap() ^= 1;
+    assert!(matches!(open(&KEY, &altered), Err(Error::Decrypt)));
+
+    assert!(matches!(open(&KEY, &sealed[1..]), Err(Error::Decrypt)));
+    assert!(matches!(open(&KEY, PREFIX), Err(Error::Decrypt)));
+  }
+
+  #[test]
+  fn hex_round_trip() {
+    assert_eq!(from_hex(&to_hex(&KEY)).unwrap(), KEY);
+    assert!(from_hex("abc").is_none());
+    assert!(from_hex("zz").is_none());
+  }
+}
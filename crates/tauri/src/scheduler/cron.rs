@@ -0,0 +1,319 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{fmt, str::FromStr};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+/// Upper bound on the search for the next matching minute,
+/// enough to skip over several years of non matching days.
+const MAX_ITERATIONS: usize = 100_000;
+
+/// Error returned when parsing an invalid [`Cron`] expression.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid cron expression `{expression}`: {reason}")]
+pub struct CronParseError {
+  expression: String,
+  reason: String,
+}
+
+/// A cron expression evaluated in UTC.
+///
+/// The expression has five whitespace separated fields:
+///
+/// ```text
+/// ┌───────── minute (0-59)
+/// │ ┌─────── hour (0-23)
+/// │ │ ┌───── day of month (1-31)
+/// │ │ │ ┌─── month (1-12)
+/// │ │ │ │ ┌─ day of week (0-7, 0 and 7 are Sunday)
+/// * * * * *
+/// ```
+///
+/// Each field accepts `*`, single values, ranges (`1-5`), lists (`1,15,30`) and steps (`*/15`, `0-30/10`).
+/// The `@yearly`, `@monthly`, `@weekly`, `@daily` and `@hourly` shorthands are also supported.
+///
+/// Like the traditional cron, when both day of month and day of week are restricted
+/// the expression matches a day satisfying either of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cron {
+  expression: String,
+  minutes: u64,
+  hours: u64,
+  days_of_month: u64,
+  months: u64,
+  days_of_week: u64,
+  day_of_month_restricted: bool,
+  day_of_week_restricted: bool,
+}
+
+impl Cron {
+  /// The expression this schedule was parsed from.
+  pub fn expression(&self) -> &str {
+    &self.expression
+  }
+
+  /// Returns the first matching time strictly after `after`, in seconds since the UNIX epoch.
+  pub(crate) fn next_after(&self, after: u64) -> Option<u64> {
+    let mut t = (after as i64 / 60 + 1) * 60;
+
+    for _ in 0..MAX_ITERATIONS {
+      let days = t.div_euclid(SECONDS_PER_DAY);
+      let seconds = t.rem_euclid(SECONDS_PER_DAY);
+      let (year, month, day) = civil_from_days(days);
+
+      if !contains(self.months, month) {
+        let (year, month) = if month == 12 {
+          (year + 1, 1)
+        } else {
+          (year, month + 1)
+        };
+        t = days_from_civil(year, month, 1) * SECONDS_PER_DAY;
+        continue;
+      }
+
+      if !self.matches_day(day, weekday(days)) {
+        t = (days + 1) * SECONDS_PER_DAY;
+        continue;
+      }
+
+      let hour = (seconds / 3600) as u32;
+      if !contains(self.hours, hour) {
+        t = days * SECONDS_PER_DAY + (hour as i64 + 1) * 3600;
+        continue;
+      }
+
+      let minute = ((seconds % 3600) / 60) as u32;
+      if !contains(self.minutes, minute) {
+        t += 60;
+        continue;
+      }
+
+      return u64::try_from(t).ok();
+    }
+
+    None
+  }
+
+  fn matches_day(&self, day_of_month: u32, day_of_week: u32) -> bool {
+    let dom = contains(self.days_of_month, day_of_month);
+    let dow = contains(self.days_of_week, day_of_week);
+    match (self.day_of_month_restricted, self.day_of_week_restricted) {
+      (true, true) => dom || dow,
+      _ => dom && dow,
+    }
+  }
+}
+
+impl fmt::Display for Cron {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.expression)
+  }
+}
+
+impl FromStr for Cron {
+  type Err = CronParseError;
+
+  fn from_str(expression: &str) -> Result<Self, Self::Err> {
+    let error = |reason: String| CronParseError {
+      expression: expression.into(),
+      reason,
+    };
+
+    let expanded = match expression.trim() {
+      "@yearly" | "@annually" => "0 0 1 1 *",
+      "@monthly" => "0 0 1 * *",
+      "@weekly" => "0 0 * * 0",
+      "@daily" | "@midnight" => "0 0 * * *",
+      "@hourly" => "0 * * * *",
+      other => other,
+    };
+
+    let fields = expanded.split_whitespace().collect::<Vec<_>>();
+    let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+      return Err(error(format!("expected 5 fields, found {}", fields.len())));
+    };
+
+    let mut days_of_week = parse_field(day_of_week, 0, 7).map_err(error)?;
+    // 7 is an alias for Sunday
+    if contains(days_of_week, 7) {
+      days_of_week = (days_of_week | 1) & !(1 << 7);
+    }
+
+    Ok(Self {
+      expression: expression.trim().into(),
+      minutes: parse_field(minute, 0, 59).map_err(error)?,
+      hours: parse_field(hour, 0, 23).map_err(error)?,
+      days_of_month: parse_field(day_of_month, 1, 31).map_err(error)?,
+      months: parse_field(month, 1, 12).map_err(error)?,
+      days_of_week,
+      day_of_month_restricted: day_of_month != "*",
+      day_of_week_restricted: day_of_week != "*",
+    })
+  }
+}
+
+fn contains(mask: u64, value: u32) -> bool {
+  mask & (1 << value) != 0
+}
+
+/// Parses a single cron field into a bit mask of the allowed values.
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+  let parse_value = |value: &str| -> Result<u32, String> {
+    let value = value
+      .parse::<u32>()
+      .map_err(|_| format!("`{value}` is not a number"))?;
+    if value < min || value > max {
+      Err(format!("{value} is out of the {min}-{max} range"))
+    } else {
+      Ok(value)
+    }
+  };
+
+  let mut mask = 0u64;
+  for part in field.split(',') {
+    let (range, step) = match part.split_once('/') {
+      Some((range, step)) => {
+        let step = step
+          .parse::<u32>()
+          .ok()
+          .filter(|s| *s > 0)
+          .ok_or_else(|| format!("invalid step `{step}`"))?;
+        (range, step)
+      }
+      None => (part, 1),
+    };
+
+    let (start, end) = if range == "*" {
+      (min, max)
+    } else if let Some((start, end)) = range.split_once('-') {
+      (parse_value(start)?, parse_value(end)?)
+    } else {
+      let start = parse_value(range)?;
+      // `5/15` means every 15 starting at 5
+      (start, if step > 1 { max } else { start })
+    };
+
+    if start > end {
+      return Err(format!("invalid range `{range}`"));
+    }
+
+    for value in (start..=end).step_by(step as usize) {
+      mask |= 1 << value;
+    }
+  }
+
+  Ok(mask)
+}
+
+/// Day of the week for the given days since the UNIX epoch, 0 being Sunday.
+fn weekday(days: i64) -> u32 {
+  // 1970-01-01 was a Thursday
+  (days + 4).rem_euclid(7) as u32
+}
+
+/// Converts days since the UNIX epoch to a `(year, month, day)` triple.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+  let z = days + 719_468;
+  let era = z.div_euclid(146_097);
+  let doe = z.rem_euclid(146_097);
+  let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+  let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+  let mp = (5 * doy + 2) / 153;
+  let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+  let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+  let year = yoe + era * 400 + i64::from(month <= 2);
+  (year, month, day)
+}
+
+/// Converts a `(year, month, day)` triple to days since the UNIX epoch.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+  let year = if month <= 2 { year - 1 } else { year };
+  let era = year.div_euclid(400);
+  let yoe = year.rem_euclid(400);
+  let month = month as i64;
+  let mp = if month > 2 { month - 3 } else { month + 9 };
+  let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+  let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+  era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // 2024-03-15T10:20:30Z, a Friday
+  const NOW: u64 = 1_710_498_030;
+
+  fn ts(year: i64, month: u32, day: u32, hour: u64, minute: u64) -> u64 {
+    days_from_civil(year, month, day) as u64 * 86_400 + hour * 3600 + minute * 60
+  }
+
+  #[test]
+  fn civil_round_trip() {
+    for days in [-800_000, -1, 0, 1, 11_016, 19_797, 2_932_896] {
+      let (y, m, d) = civil_from_days(days);
+      assert_eq!(days_from_civil(y, m, d), days);
+    }
+    assert_eq!(civil_from_days(0), (1970, 1, 1));
+    assert_eq!(civil_from_days(19_797), (2024, 3, 15));
+    assert_eq!(weekday(19_797), 5);
+  }
+
+  #[test]
+  fn next_matches() {
+    let every_minute: Cron = "* * * * *".parse().unwrap();
+    assert_eq!(every_minute.next_after(NOW), Some(ts(2024, 3, 15, 10, 21)));
+
+    let quarter: Cron = "*/15 * * * *".parse().unwrap();
+    assert_eq!(quarter.next_after(NOW), Some(ts(2024, 3, 15, 10, 30)));
+
+    let daily: Cron = "@daily".parse().unwrap();
+    assert_eq!(daily.next_after(NOW), Some(ts(2024, 3, 16, 0, 0)));
+
+    let weekdays: Cron = "30 9 * * 1-5".parse().unwrap();
+    assert_eq!(weekdays.next_after(NOW), Some(ts(2024, 3, 18, 9, 30)));
+
+    let sunday: Cron = "0 12 * * 7".parse().unwrap();
+    assert_eq!(sunday.next_after(NOW), Some(ts(2024, 3, 17, 12, 0)));
+
+    let leap_day: Cron = "0 0 29 2 *".parse().unwrap();
+    assert_eq!(leap_day.next_after(NOW), Some(ts(2028, 2, 29, 0, 0)));
+
+    let never: Cron = "0 0 31 2 *".parse().unwrap();
+    assert_eq!(never.next_after(NOW), None);
+  }
+
+  #[test]
+  fn day_of_month_or_day_of_week() {
+    // the 1st of the month or any Monday
+    let cron: Cron = "0 0 1 * 1".parse().unwrap();
+    assert_eq!(cron.next_after(NOW), Some(ts(2024, 3, 18, 0, 0)));
+    assert_eq!(
+      cron.next_after(ts(2024, 3, 25, 0, 0)),
+      Some(ts(2024, 4, 1, 0, 0))
+    );
+  }
+
+  #[test]
+  fn invalid_expressions() {
+    for expression in [
+      "",
+      "* * * *",
+      "60 * * * *",
+      "* 24 * * *",
+      "* * 0 * *",
+      "* * * 13 *",
+      "* * * * 8",
+      "*/0 * * * *",
+      "5-1 * * * *",
+      "a * * * *",
+    ] {
+      assert!(expression.parse::<Cron>().is_err(), "{expression}");
+    }
+  }
+}
@@ -0,0 +1,352 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Background jobs tied to the application lifecycle.
+//!
+//! Jobs registered on the [`Scheduler`] run on the async runtime, independently of any window,
+//! so they keep running when all windows are closed as long as the app does not exit.
+//! The next run time of each job is persisted to the app data directory,
+//! so a job that was due while the app was not running executes as soon as it is registered again.
+
+use std::{
+  collections::HashMap,
+  future::Future,
+  path::PathBuf,
+  pin::Pin,
+  sync::{Arc, Mutex},
+  time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+
+use crate::{async_runtime, AppHandle, Emitter, Manager, Runtime};
+
+mod cron;
+
+pub use cron::{Cron, CronParseError};
+
+/// Event emitted to all targets after a scheduled job runs. The payload is a [`JobRun`].
+pub const JOB_RUN_EVENT: &str = "tauri://scheduler-job-run";
+
+const STORE_FILE_NAME: &str = "scheduler.json";
+
+/// Longest time a job loop sleeps before checking the wall clock again,
+/// so system sleep or clock changes don't delay jobs for too long.
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+type JobFn<R> = dyn Fn(AppHandle<R>) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync;
+
+/// When a job should run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Schedule {
+  /// Runs once after the given delay.
+  After(Duration),
+  /// Runs repeatedly with the given interval between runs.
+  Every(Duration),
+  /// Runs whenever the cron expression matches.
+  Cron(Cron),
+}
+
+impl Schedule {
+  /// Parses a [`Cron`] expression.
+  pub fn cron(expression: &str) -> Result<Self, CronParseError> {
+    expression.parse().map(Self::Cron)
+  }
+
+  /// Next run time after `now`, in seconds since the UNIX epoch.
+  fn next_after(&self, now: u64) -> Option<u64> {
+    match self {
+      Self::After(delay) | Self::Every(delay) => Some(now + delay.as_secs().max(1)),
+      Self::Cron(cron) => cron.next_after(now),
+    }
+  }
+}
+
+/// Payload of the [`JOB_RUN_EVENT`] event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobRun {
+  /// The job identifier.
+  pub id: String,
+  /// When the job started, in seconds since the UNIX epoch.
+  pub started_at: u64,
+  /// When the job finished, in seconds since the UNIX epoch.
+  pub finished_at: u64,
+  /// When the job will run again, in seconds since the UNIX epoch. `None` if it won't run again.
+  pub next_run: Option<u64>,
+}
+
+struct Job {
+  next_run: Arc<Mutex<Option<u64>>>,
+  task: async_runtime::JoinHandle<()>,
+}
+
+/// Runs recurring or delayed jobs for the lifetime of the application.
+///
+/// Access it with [`Manager::scheduler`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use tauri::{scheduler::Schedule, Manager};
+///
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     app.scheduler().schedule("sync", Schedule::Every(Duration::from_secs(15 * 60)), |app| async move {
+///       // synchronize data
+///     });
+///     app.scheduler().schedule("cleanup", Schedule::cron("0 3 * * *")?, |app| async move {
+///       // runs every day at 03:00 UTC
+///     });
+///     Ok(())
+///   });
+/// ```
+pub struct Scheduler<R: Runtime> {
+  app: AppHandle<R>,
+  jobs: Mutex<HashMap<String, Job>>,
+  store: Arc<Store>,
+}
+
+impl<R: Runtime> Scheduler<R> {
+  pub(crate) fn new(app: AppHandle<R>) -> Self {
+    let path = app
+      .path()
+      .app_data_dir()
+      .ok()
+      .map(|dir| dir.join(STORE_FILE_NAME));
+    Self {
+      app,
+      jobs: Default::default(),
+      store: Arc::new(Store::load(path)),
+    }
+  }
+
+  /// Registers a job, replacing any existing job with the same identifier.
+  ///
+  /// If a previous run of the app persisted an earlier run time for this identifier, it takes precedence,
+  /// and a job that was due while the app was not running executes immediately.
+  ///
+  /// A job never overlaps with itself: the next run is only scheduled after the current one finishes.
+  pub fn schedule<F, Fut>(&self, id: impl Into<String>, schedule: Schedule, job: F)
+  where
+    F: Fn(AppHandle<R>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+  {
+    let id = id.into();
+    let job: Arc<JobFn<R>> = Arc::new(
+      move |app: AppHandle<R>| -> Pin<Box<dyn Future<Output = ()> + Send>> { Box::pin(job(app)) },
+    );
+
+    let now = now_secs();
+    let next_run = match (schedule.next_after(now), self.store.get(&id)) {
+      (Some(next), Some(persisted)) => Some(next.min(persisted)),
+      (next, _) => next,
+    };
+    self.store.set(&id, next_run);
+    let next_run = Arc::new(Mutex::new(next_run));
+
+    let task = async_runtime::spawn(run_job(
+      self.app.clone(),
+      id.clone(),
+      schedule,
+      job,
+      next_run.clone(),
+      self.store.clone(),
+    ));
+
+    if let Some(previous) = self.jobs.lock().unwrap().insert(id, Job { next_run, task }) {
+      previous.task.abort();
+    }
+  }
+
+  /// Cancels the job with the given identifier and forgets its persisted run time.
+  ///
+  /// Returns `false` if there is no job registered with this identifier.
+  pub fn cancel(&self, id: &str) -> bool {
+    self.store.set(id, None);
+    if let Some(job) = self.jobs.lock().unwrap().remove(id) {
+      job.task.abort();
+      true
+    } else {
+      false
+    }
+  }
+
+  /// The identifiers of all registered jobs.
+  pub fn jobs(&self) -> Vec<String> {
+    self.jobs.lock().unwrap().keys().cloned().collect()
+  }
+
+  /// When the job with the given identifier will run next.
+  pub fn next_run(&self, id: &str) -> Option<SystemTime> {
+    self
+      .jobs
+      .lock()
+      .unwrap()
+      .get(id)
+      .and_then(|job| *job.next_run.lock().unwrap())
+      .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+  }
+
+  /// Stops all jobs without forgetting their persisted run times.
+  pub(crate) fn shutdown(&self) {
+    for (_, job) in self.jobs.lock().unwrap().drain() {
+      job.task.abort();
+    }
+  }
+}
+
+async fn run_job<R: Runtime>(
+  app: AppHandle<R>,
+  id: String,
+  schedule: Schedule,
+  job: Arc<JobFn<R>>,
+  next_run: Arc<Mutex<Option<u64>>>,
+  store: Arc<Store>,
+) {
+  loop {
+    let Some(due) = *next_run.lock().unwrap() else {
+      break;
+    };
+
+    let now = now_secs();
+    if now < due {
+      let wait = Duration::from_secs(due - now).min(MAX_SLEEP);
+      tokio::time::sleep(wait).await;
+      continue;
+    }
+
+    let started_at = now_secs();
+    job(app.clone()).await;
+    let finished_at = now_secs();
+
+    let next = match schedule {
+      Schedule::After(_) => None,
+      _ => schedule.next_after(finished_at),
+    };
+    *next_run.lock().unwrap() = next;
+    store.set(&id, next);
+
+    let _ = app.emit(
+      JOB_RUN_EVENT,
+      JobRun {
+        id: id.clone(),
+        started_at,
+        finished_at,
+        next_run: next,
+      },
+    );
+  }
+
+  // the job won't run again
+  if let Some(scheduler) = app.try_state::<Scheduler<R>>() {
+    let mut jobs = scheduler.jobs.lock().unwrap();
+    if jobs
+      .get(&id)
+      .is_some_and(|job| Arc::ptr_eq(&job.next_run, &next_run))
+    {
+      jobs.remove(&id);
+    }
+  }
+}
+
+/// The persisted next run times.
+struct Store {
+  path: Option<PathBuf>,
+  next_runs: Mutex<HashMap<String, u64>>,
+}
+
+impl Store {
+  fn load(path: Option<PathBuf>) -> Self {
+    let next_runs = path
+      .as_ref()
+      .and_then(|path| std::fs::read(path).ok())
+      .and_then(|contents| serde_json::from_slice(&contents).ok())
+      .unwrap_or_default();
+    Self {
+      path,
+      next_runs: Mutex::new(next_runs),
+    }
+  }
+
+  fn get(&self, id: &str) -> Option<u64> {
+    self.next_runs.lock().unwrap().get(id).copied()
+  }
+
+  fn set(&self, id: &str, next_run: Option<u64>) {
+    let mut next_runs = self.next_runs.lock().unwrap();
+    let changed = match next_run {
+      Some(next_run) => next_runs.insert(id.into(), next_run) != Some(next_run),
+      None => next_runs.remove(id).is_some(),
+    };
+
+    if changed {
+      if let Err(e) = self.save(&next_runs) {
+        log::error!("failed to persist scheduled jobs: {e}");
+      }
+    }
+  }
+
+  fn save(&self, next_runs: &HashMap<String, u64>) -> crate::Result<()> {
+    let Some(path) = &self.path else {
+      return Ok(());
+    };
+    if let Some(parent) = path.parent() {
+      std::fs::create_dir_all(parent)?;
+    }
+    // write to a temporary file first so a crash never leaves a truncated store behind
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, serde_json::to_vec(next_runs)?)?;
+    std::fs::rename(tmp_path, path)?;
+    Ok(())
+  }
+}
+
+fn now_secs() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_secs())
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{Schedule, Store};
+
+  #[test]
+  fn schedule_next_run() {
+    let now = 1_710_498_030;
+    assert_eq!(
+      Schedule::After(std::time::Duration::from_secs(30)).next_after(now),
+      Some(now + 30)
+    );
+    assert_eq!(
+      Schedule::Every(std::time::Duration::from_millis(10)).next_after(now),
+      Some(now + 1)
+    );
+    assert_eq!(
+      Schedule::cron("0 * * * *").unwrap().next_after(now),
+      Some(1_710_500_400)
+    );
+  }
+
+  #[test]
+  fn store_persists_next_runs() {
+    let dir = std::env::temp_dir().join(format!("tauri-scheduler-test-{}", std::process::id()));
+    let path = dir.join("scheduler.json");
+
+    let store = Store::load(Some(path.clone()));
+    store.set("sync", Some(42));
+    store.set("cleanup", Some(7));
+    store.set("cleanup", None);
+
+    let store = Store::load(Some(path));
+    assert_eq!(store.get("sync"), Some(42));
+    assert_eq!(store.get("cleanup"), None);
+
+    std::fs::remove_dir_all(dir).unwrap();
+  }
+}
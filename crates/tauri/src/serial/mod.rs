@@ -0,0 +1,666 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Serial port enumeration and access, for apps talking to hardware such as USB to serial adapters and microcontrollers.
+//!
+//! On the Rust side, use [`available_ports`] and [`SerialPort::open`].
+//! The webviews use the `core:serial` commands, which are not enabled by the default permission set
+//! and only access the ports matching the `allow` scope of the permission:
+//!
+//! ```json
+//! {
+//!   "identifier": "main",
+//!   "windows": ["main"],
+//!   "permissions": [
+//!     "core:serial:allow-available-ports",
+//!     {
+//!       "identifier": "core:serial:allow-open",
+//!       "allow": [{ "path": "/dev/ttyUSB*" }, { "path": "COM*" }]
+//!     },
+//!     "core:serial:allow-write"
+//!   ]
+//! }
+//! ```
+//!
+//! The data read from an opened port is sent to the webview through the channel given to the `open` command.
+
+use std::{
+  fs::File,
+  io::{Read, Write},
+  path::Path,
+  sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::ipc::ScopeObjectMatch;
+
+pub(crate) mod plugin;
+
+/// Serial port errors.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+  /// The port is not allowed by the scope of the command.
+  #[error("access to the serial port {0} is not allowed")]
+  NotAllowed(String),
+  /// The baud rate is not supported by the platform.
+  #[error("unsupported baud rate {0}")]
+  UnsupportedBaudRate(u32),
+  /// The number of data bits is not between 5 and 8.
+  #[error("unsupported number of data bits {0}")]
+  UnsupportedDataBits(u8),
+}
+
+/// Information about a serial port found by [`available_ports`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct PortInfo {
+  /// The path of the port to give to [`SerialPort::open`], e.g. `/dev/ttyUSB0` or `COM3`.
+  pub path: String,
+  /// The USB device of the port, for USB to serial adapters.
+  pub usb: Option<UsbInfo>,
+}
+
+/// The USB device of a serial port.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct UsbInfo {
+  /// The USB vendor ID.
+  pub vendor_id: u16,
+  /// The USB product ID.
+  pub product_id: u16,
+  /// The manufacturer name.
+  pub manufacturer: Option<String>,
+  /// The product name.
+  pub product: Option<String>,
+  /// The serial number of the device.
+  pub serial_number: Option<String>,
+}
+
+/// The parity checking mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Parity {
+  /// No parity bit.
+  #[default]
+  None,
+  /// The parity bit makes the number of set bits odd.
+  Odd,
+  /// The parity bit makes the number of set bits even.
+  Even,
+}
+
+/// The number of stop bits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StopBits {
+  /// One stop bit.
+  #[default]
+  One,
+  /// Two stop bits.
+  Two,
+}
+
+/// The flow control mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FlowControl {
+  /// No flow control.
+  #[default]
+  None,
+  /// XON/XOFF flow control.
+  Software,
+  /// RTS/CTS flow control.
+  Hardware,
+}
+
+/// The settings of an opened port. Defaults to 9600 baud, 8 data bits, no parity, one stop bit and no flow control.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SerialOptions {
+  /// The baud rate.
+  pub baud_rate: u32,
+  /// The number of data bits, from 5 to 8.
+  pub data_bits: u8,
+  /// The parity checking mode.
+  pub parity: Parity,
+  /// The number of stop bits.
+  pub stop_bits: StopBits,
+  /// The flow control mode.
+  pub flow_control: FlowControl,
+}
+
+impl Default for SerialOptions {
+  fn default() -> Self {
+    Self {
+      baud_rate: 9600,
+      data_bits: 8,
+      parity: Parity::None,
+      stop_bits: StopBits::One,
+      flow_control: FlowControl::None,
+    }
+  }
+}
+
+/// An entry of the scope of the `core:serial` commands.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScopeEntry {
+  /// A glob pattern matching the port paths, e.g. `/dev/ttyUSB*` or `COM*`.
+  pub path: String,
+}
+
+impl ScopeObjectMatch for ScopeEntry {
+  type Input = str;
+
+  fn matches(&self, input: &str) -> bool {
+    glob::Pattern::new(&self.path).is_ok_and(|pattern| pattern.matches(input))
+  }
+}
+
+/// Lists the serial ports of the system.
+///
+/// ## Platform-specific
+///
+/// - **macOS / Windows:** The [`PortInfo::usb`] information is not available.
+/// - **Android / iOS:** Unsupported, returns an empty list.
+pub fn available_ports() -> crate::Result<Vec<PortInfo>> {
+  platform::available_ports().map_err(Into::into)
+}
+
+/// An opened serial port.
+///
+/// Reads return after a short timeout when no data is received, with `Ok(0)` or an error of kind
+/// [`std::io::ErrorKind::TimedOut`] depending on the platform, so a reader can check whether it should stop.
+#[derive(Debug)]
+pub struct SerialPort {
+  path: String,
+  file: Arc<File>,
+}
+
+impl SerialPort {
+  /// Opens the port at the given path with exclusive access and configures it.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use std::io::Write;
+  /// use tauri::serial::{SerialOptions, SerialPort};
+  ///
+  /// let mut port = SerialPort::open(
+  ///   "/dev/ttyUSB0",
+  ///   &SerialOptions {
+  ///     baud_rate: 115200,
+  ///     ..Default::default()
+  ///   },
+  /// )?;
+  /// port.write_all(b"AT\r\n")?;
+  /// # Ok::<(), tauri::Error>(())
+  /// ```
+  pub fn open(path: impl AsRef<Path>, options: &SerialOptions) -> crate::Result<Self> {
+    if !(5..=8).contains(&options.data_bits) {
+      return Err(Error::UnsupportedDataBits(options.data_bits).into());
+    }
+    let path = path.as_ref();
+    let file = platform::open(path, options)?;
+    Ok(Self {
+      path: path.display().to_string(),
+      file: Arc::new(file),
+    })
+  }
+
+  /// The path of the port.
+  pub fn path(&self) -> &str {
+    &self.path
+  }
+
+  /// Creates a new handle to the same port, e.g. to read and write from different threads.
+  pub fn try_clone(&self) -> Self {
+    Self {
+      path: self.path.clone(),
+      file: self.file.clone(),
+    }
+  }
+}
+
+impl Read for SerialPort {
+  fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+    (&*self.file).read(buf)
+  }
+}
+
+impl Write for SerialPort {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    (&*self.file).write(buf)
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    (&*self.file).flush()
+  }
+}
+
+#[cfg(unix)]
+mod termios {
+  use super::{Error, FlowControl, Parity, SerialOptions, StopBits};
+  use std::{
+    fs::{File, OpenOptions},
+    io,
+    os::{fd::AsRawFd, unix::fs::OpenOptionsExt},
+    path::Path,
+  };
+
+  fn check(result: libc::c_int) -> io::Result<()> {
+    if result == -1 {
+      Err(io::Error::last_os_error())
+    } else {
+      Ok(())
+    }
+  }
+
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  fn speed(baud_rate: u32) -> Option<libc::speed_t> {
+    Some(match baud_rate {
+      1200 => libc::B1200,
+      2400 => libc::B2400,
+      4800 => libc::B4800,
+      9600 => libc::B9600,
+      19200 => libc::B19200,
+      38400 => libc::B38400,
+      57600 => libc::B57600,
+      115200 => libc::B115200,
+      230400 => libc::B230400,
+      460800 => libc::B460800,
+      500000 => libc::B500000,
+      576000 => libc::B576000,
+      921600 => libc::B921600,
+      1000000 => libc::B1000000,
+      1500000 => libc::B1500000,
+      2000000 => libc::B2000000,
+      3000000 => libc::B3000000,
+      4000000 => libc::B4000000,
+      _ => return None,
+    })
+  }
+
+  // the speed is the baud rate itself on the BSD systems
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  fn speed(baud_rate: u32) -> Option<libc::speed_t> {
+    Some(baud_rate as libc::speed_t)
+  }
+
+  pub fn open(path: &Path, options: &SerialOptions) -> crate::Result<File> {
+    let speed = speed(options.baud_rate).ok_or(Error::UnsupportedBaudRate(options.baud_rate))?;
+
+    // non blocking so opening does not wait for the carrier detect line
+    let file = OpenOptions::new()
+      .read(true)
+      .write(true)
+      .custom_flags(libc::O_NOCTTY | libc::O_NONBLOCK)
+      .open(path)?;
+    let fd = file.as_raw_fd();
+
+    unsafe {
+      check(libc::ioctl(fd, libc::TIOCEXCL as _))?;
+
+      let mut tty: libc::termios = std::mem::zeroed();
+      check(libc::tcgetattr(fd, &mut tty))?;
+      libc::cfmakeraw(&mut tty);
+      tty.c_cflag |= libc::CREAD | libc::CLOCAL;
+
+      tty.c_cflag &= !libc::CSIZE;
+      tty.c_cflag |= match options.data_bits {
+        5 => libc::CS5,
+        6 => libc::CS6,
+        7 => libc::CS7,
+        _ => libc::CS8,
+      };
+
+      tty.c_cflag &= !(libc::PARENB | libc::PARODD);
+      tty.c_iflag &= !libc::INPCK;
+      match options.parity {
+        Parity::None => (),
+        Parity::Odd => {
+          tty.c_cflag |= libc::PARENB | libc::PARODD;
+          tty.c_iflag |= libc::INPCK;
+        }
+        Parity::Even => {
+          tty.c_cflag |= libc::PARENB;
+          tty.c_iflag |= libc::INPCK;
+        }
+      }
+
+      match options.stop_bits {
+        StopBits::One => tty.c_cflag &= !libc::CSTOPB,
+        StopBits::Two => tty.c_cflag |= libc::CSTOPB,
+      }
+
+      tty.c_cflag &= !libc::CRTSCTS;
+      tty.c_iflag &= !(libc::IXON | libc::IXOFF);
+      match options.flow_control {
+        FlowControl::None => (),
+        FlowControl::Software => tty.c_iflag |= libc::IXON | libc::IXOFF,
+        FlowControl::Hardware => tty.c_cflag |= libc::CRTSCTS,
+      }
+
+      // reads return after 100ms without data
+      tty.c_cc[libc::VMIN] = 0;
+      tty.c_cc[libc::VTIME] = 1;
+
+      check(libc::cfsetspeed(&mut tty, speed))?;
+      check(libc::tcsetattr(fd, libc::TCSANOW, &tty))?;
+
+      let flags = libc::fcntl(fd, libc::F_GETFL);
+      check(flags)?;
+      check(libc::fcntl(fd, libc::F_SETFL, flags & !libc::O_NONBLOCK))?;
+    }
+
+    Ok(file)
+  }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+  use super::{PortInfo, UsbInfo};
+  use std::{io, path::Path};
+
+  pub use super::termios::open;
+
+  fn read_attribute(device: &Path, name: &str) -> Option<String> {
+    std::fs::read_to_string(device.join(name))
+      .ok()
+      .map(|value| value.trim().to_string())
+  }
+
+  /// Finds the USB device of a tty device by walking up the sysfs hierarchy.
+  fn usb_info(device: &Path) -> Option<UsbInfo> {
+    let device = device.canonicalize().ok()?;
+    let usb_device = device
+      .ancestors()
+      .find(|dir| dir.join("idVendor").exists())?;
+    Some(UsbInfo {
+      vendor_id: u16::from_str_radix(&read_attribute(usb_device, "idVendor")?, 16).ok()?,
+      product_id: u16::from_str_radix(&read_attribute(usb_device, "idProduct")?, 16).ok()?,
+      manufacturer: read_attribute(usb_device, "manufacturer"),
+      product: read_attribute(usb_device, "product"),
+      serial_number: read_attribute(usb_device, "serial"),
+    })
+  }
+
+  pub fn available_ports() -> io::Result<Vec<PortInfo>> {
+    let mut ports = Vec::new();
+    for entry in std::fs::read_dir("/sys/class/tty")? {
+      let entry = entry?;
+      let device = entry.path().join("device");
+      let Ok(driver) = device.join("driver").read_link() else {
+        // virtual terminals and pseudo terminals have no device driver
+        continue;
+      };
+      // the legacy UARTs are always listed even when no hardware is present
+      if driver.file_name().is_some_and(|name| name == "serial8250") {
+        continue;
+      }
+      ports.push(PortInfo {
+        path: Path::new("/dev")
+          .join(entry.file_name())
+          .display()
+          .to_string(),
+        usb: usb_info(&device),
+      });
+    }
+    ports.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(ports)
+  }
+}
+
+#[cfg(any(
+  target_os = "macos",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use super::PortInfo;
+  use std::io;
+
+  pub use super::termios::open;
+
+  /// The prefixes of the callout devices, which do not wait for the carrier detect line when opened.
+  #[cfg(target_os = "macos")]
+  const PREFIXES: &[&str] = &["cu."];
+  #[cfg(any(target_os = "dragonfly", target_os = "freebsd"))]
+  const PREFIXES: &[&str] = &["cuau", "cuaU"];
+  #[cfg(any(target_os = "netbsd", target_os = "openbsd"))]
+  const PREFIXES: &[&str] = &["cua", "dty"];
+
+  pub fn available_ports() -> io::Result<Vec<PortInfo>> {
+    let mut ports = Vec::new();
+    for entry in std::fs::read_dir("/dev")? {
+      let name = entry?.file_name();
+      let name = name.to_string_lossy();
+      // skip the lock and initial state devices of FreeBSD
+      if PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+        && !name.ends_with(".lock")
+        && !name.ends_with(".init")
+      {
+        ports.push(PortInfo {
+          path: format!("/dev/{name}"),
+          usb: None,
+        });
+      }
+    }
+    ports.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(ports)
+  }
+}
+
+#[cfg(windows)]
+mod platform {
+  use super::{Error, FlowControl, Parity, PortInfo, SerialOptions, StopBits};
+  use std::{
+    fs::{File, OpenOptions},
+    io,
+    os::windows::{fs::OpenOptionsExt, io::AsRawHandle},
+    path::Path,
+  };
+  use windows::{
+    core::{w, PWSTR},
+    Win32::{
+      Devices::Communication::{
+        GetCommState, SetCommState, SetCommTimeouts, COMMTIMEOUTS, DCB, EVENPARITY, NOPARITY,
+        ODDPARITY, ONESTOPBIT, TWOSTOPBITS,
+      },
+      Foundation::{ERROR_SUCCESS, HANDLE},
+      System::Registry::{
+        RegCloseKey, RegEnumValueW, RegOpenKeyExW, HKEY, HKEY_LOCAL_MACHINE, KEY_READ,
+      },
+    },
+  };
+
+  // DCB bit fields
+  const F_BINARY: u32 = 1;
+  const F_PARITY: u32 = 1 << 1;
+  const F_OUTX_CTS_FLOW: u32 = 1 << 2;
+  const F_DTR_CONTROL_ENABLE: u32 = 1 << 4;
+  const F_OUTX: u32 = 1 << 8;
+  const F_INX: u32 = 1 << 9;
+  const F_RTS_CONTROL_ENABLE: u32 = 1 << 12;
+  const F_RTS_CONTROL_HANDSHAKE: u32 = 2 << 12;
+
+  pub fn available_ports() -> io::Result<Vec<PortInfo>> {
+    let mut ports = Vec::new();
+    let mut key = HKEY::default();
+    let result = unsafe {
+      RegOpenKeyExW(
+        HKEY_LOCAL_MACHINE,
+        w!("HARDWARE\\DEVICEMAP\\SERIALCOMM"),
+        0,
+        KEY_READ,
+        &mut key,
+      )
+    };
+    // the key only exists when a serial port is present
+    if result != ERROR_SUCCESS {
+      return Ok(ports);
+    }
+
+    for index in 0.. {
+      let mut name = [0u16; 256];
+      let mut name_len = name.len() as u32;
+      let mut data = [0u16; 256];
+      let mut data_len = std::mem::size_of_val(&data) as u32;
+      let result = unsafe {
+        RegEnumValueW(
+          key,
+          index,
+          PWSTR(name.as_mut_ptr()),
+          &mut name_len,
+          None,
+          None,
+          Some(data.as_mut_ptr().cast()),
+          Some(&mut data_len),
+        )
+      };
+      if result != ERROR_SUCCESS {
+        break;
+      }
+      let data = &data[..data_len as usize / 2];
+      let path = String::from_utf16_lossy(data)
+        .trim_end_matches('\0')
+        .to_string();
+      ports.push(PortInfo { path, usb: None });
+    }
+    unsafe {
+      let _ = RegCloseKey(key);
+    }
+
+    ports.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(ports)
+  }
+
+  pub fn open(path: &Path, options: &SerialOptions) -> crate::Result<File> {
+    // COM10 and above are only reachable through the device namespace
+    let path = path.to_string_lossy();
+    let path = if path.starts_with(r"\\.\") {
+      path.to_string()
+    } else {
+      format!(r"\\.\{path}")
+    };
+    let file = OpenOptions::new()
+      .read(true)
+      .write(true)
+      .share_mode(0)
+      .open(path)?;
+    let handle = HANDLE(file.as_raw_handle());
+
+    let mut dcb = DCB {
+      DCBlength: std::mem::size_of::<DCB>() as u32,
+      ..Default::default()
+    };
+    unsafe { GetCommState(handle, &mut dcb) }.map_err(io::Error::from)?;
+
+    dcb.BaudRate = options.baud_rate;
+    dcb.ByteSize = options.data_bits;
+    dcb.Parity = match options.parity {
+      Parity::None => NOPARITY,
+      Parity::Odd => ODDPARITY,
+      Parity::Even => EVENPARITY,
+    };
+    dcb.StopBits = match options.stop_bits {
+      StopBits::One => ONESTOPBIT,
+      StopBits::Two => TWOSTOPBITS,
+    };
+    dcb._bitfield = F_BINARY | F_DTR_CONTROL_ENABLE;
+    if options.parity != Parity::None {
+      dcb._bitfield |= F_PARITY;
+    }
+    dcb._bitfield |= match options.flow_control {
+      FlowControl::None => F_RTS_CONTROL_ENABLE,
+      FlowControl::Software => F_RTS_CONTROL_ENABLE | F_OUTX | F_INX,
+      FlowControl::Hardware => F_RTS_CONTROL_HANDSHAKE | F_OUTX_CTS_FLOW,
+    };
+    dcb.XonChar = 0x11;
+    dcb.XoffChar = 0x13;
+    unsafe { SetCommState(handle, &dcb) }
+      .map_err(|_| Error::UnsupportedBaudRate(options.baud_rate))?;
+
+    // reads return after 100ms without data
+    let timeouts = COMMTIMEOUTS {
+      ReadIntervalTimeout: u32::MAX,
+      ReadTotalTimeoutMultiplier: u32::MAX,
+      ReadTotalTimeoutConstant: 100,
+      WriteTotalTimeoutMultiplier: 0,
+      WriteTotalTimeoutConstant: 0,
+    };
+    unsafe { SetCommTimeouts(handle, &timeouts) }.map_err(io::Error::from)?;
+
+    Ok(file)
+  }
+}
+
+#[cfg(mobile)]
+mod platform {
+  use super::PortInfo;
+  use std::io;
+
+  pub use super::termios::open;
+
+  pub fn available_ports() -> io::Result<Vec<PortInfo>> {
+    Ok(Vec::new())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn deserializes_options_with_defaults() {
+    let options: SerialOptions = serde_json::from_value(serde_json::json!({
+      "baudRate": 115200,
+      "flowControl": "hardware"
+    }))
+    .unwrap();
+    assert_eq!(
+      options,
+      SerialOptions {
+        baud_rate: 115200,
+        flow_control: FlowControl::Hardware,
+        ..Default::default()
+      }
+    );
+  }
+
+  #[test]
+  fn scope_matches_glob() {
+    let entry = ScopeEntry {
+      path: "/dev/ttyUSB*".into(),
+    };
+    assert!(entry.matches("/dev/ttyUSB0"));
+    assert!(!entry.matches("/dev/ttyS0"));
+
+    let entry = ScopeEntry {
+      path: "COM[0-9]*".into(),
+    };
+    assert!(entry.matches("COM3"));
+    assert!(!entry.matches("LPT1"));
+  }
+
+  #[test]
+  fn rejects_invalid_data_bits() {
+    let result = SerialPort::open(
+      "/dev/null",
+      &SerialOptions {
+        data_bits: 9,
+        ..Default::default()
+      },
+    );
+    assert!(matches!(
+      result,
+      Err(crate::Error::Serial(Error::UnsupportedDataBits(9)))
+    ));
+  }
+}
@@ -0,0 +1,116 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  io::{ErrorKind, Read, Write},
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+  },
+};
+
+use serde::Serialize;
+
+use super::{Error, PortInfo, ScopeEntry, SerialOptions, SerialPort};
+use crate::{
+  command,
+  ipc::{Channel, CommandScope},
+  plugin::{Builder, TauriPlugin},
+  Manager, Resource, ResourceId, Runtime, Webview,
+};
+
+/// Sent to the channel of the `open` command.
+#[derive(Clone, Serialize)]
+#[serde(tag = "event", content = "data", rename_all = "camelCase")]
+enum SerialEvent {
+  Data(Vec<u8>),
+  Error(String),
+  Closed,
+}
+
+/// A port opened by a webview, read until the resource is dropped.
+struct PortResource {
+  port: SerialPort,
+  closed: Arc<AtomicBool>,
+}
+
+impl Resource for PortResource {}
+
+impl Drop for PortResource {
+  fn drop(&mut self) {
+    self.closed.store(true, Ordering::Relaxed);
+  }
+}
+
+impl PortResource {
+  fn new(port: SerialPort, on_event: Channel<SerialEvent>) -> Self {
+    let closed = Arc::new(AtomicBool::new(false));
+    let mut reader = port.try_clone();
+    let reader_closed = closed.clone();
+    std::thread::spawn(move || {
+      let mut buf = [0u8; 4096];
+      // the read timeout of the port lets this loop notice the resource was closed
+      while !reader_closed.load(Ordering::Relaxed) {
+        match reader.read(&mut buf) {
+          Ok(0) => (),
+          Ok(n) => {
+            if on_event.send(SerialEvent::Data(buf[..n].to_vec())).is_err() {
+              break;
+            }
+          }
+          Err(e)
+            if matches!(
+              e.kind(),
+              ErrorKind::TimedOut | ErrorKind::WouldBlock | ErrorKind::Interrupted
+            ) => {}
+          Err(e) => {
+            let _ = on_event.send(SerialEvent::Error(e.to_string()));
+            break;
+          }
+        }
+      }
+      let _ = on_event.send(SerialEvent::Closed);
+    });
+    Self { port, closed }
+  }
+}
+
+#[command(root = "crate")]
+fn available_ports(scope: CommandScope<ScopeEntry>) -> crate::Result<Vec<PortInfo>> {
+  let mut ports = super::available_ports()?;
+  ports.retain(|port| scope.matches(&port.path));
+  Ok(ports)
+}
+
+#[command(root = "crate")]
+fn open<R: Runtime>(
+  webview: Webview<R>,
+  scope: CommandScope<ScopeEntry>,
+  path: String,
+  options: Option<SerialOptions>,
+  on_event: Channel<SerialEvent>,
+) -> crate::Result<ResourceId> {
+  // unlike the other commands, opening a port requires an explicit scope
+  if scope.allows().is_empty() || !scope.matches(&path) {
+    return Err(Error::NotAllowed(path).into());
+  }
+  let port = SerialPort::open(&path, &options.unwrap_or_default())?;
+  let rid = webview
+    .resources_table()
+    .add(PortResource::new(port, on_event));
+  Ok(rid)
+}
+
+#[command(root = "crate")]
+fn write<R: Runtime>(webview: Webview<R>, rid: ResourceId, data: Vec<u8>) -> crate::Result<()> {
+  let port = webview.resources_table().get::<PortResource>(rid)?;
+  port.port.try_clone().write_all(&data)?;
+  Ok(())
+}
+
+pub(crate) fn init<R: Runtime>() -> TauriPlugin<R> {
+  Builder::new("serial")
+    .invoke_handler(crate::generate_handler![available_ports, open, write])
+    .build()
+}
@@ -7,7 +7,7 @@ use std::{
   cell::UnsafeCell,
   collections::HashMap,
   hash::BuildHasherDefault,
-  sync::Mutex,
+  sync::{Mutex, OnceLock},
 };
 
 use crate::{
@@ -99,6 +99,28 @@ impl std::hash::Hasher for IdentHash {
 
 type TypeIdMap = HashMap<TypeId, Box<dyn Any>, BuildHasherDefault<IdentHash>>;
 
+type LazyInit<T> = Box<dyn FnOnce() -> T + Send>;
+
+/// A state value initialized on first access.
+struct LazyState<T> {
+  value: OnceLock<T>,
+  init: Mutex<Option<LazyInit<T>>>,
+}
+
+impl<T> LazyState<T> {
+  fn get(&self) -> &T {
+    self.value.get_or_init(|| {
+      let init = self
+        .init
+        .lock()
+        .unwrap()
+        .take()
+        .expect("lazy state initializer already consumed");
+      init()
+    })
+  }
+}
+
 /// The Tauri state manager.
 #[derive(Debug)]
 pub struct StateManager {
@@ -140,12 +162,36 @@ impl StateManager {
     })
   }
 
+  pub(crate) fn set_lazy<T, F>(&self, init: F) -> bool
+  where
+    T: Send + Sync + 'static,
+    F: FnOnce() -> T + Send + 'static,
+  {
+    self.with_map_mut(|map| {
+      let type_id = TypeId::of::<T>();
+      let already_set = map.contains_key(&type_id);
+      if !already_set {
+        let state = LazyState::<T> {
+          value: OnceLock::new(),
+          init: Mutex::new(Some(Box::new(init))),
+        };
+        map.insert(type_id, Box::new(state) as Box<dyn Any>);
+      }
+      !already_set
+    })
+  }
+
   pub(crate) fn unmanage<T: Send + Sync + 'static>(&self) -> Option<T> {
     self.with_map_mut(|map| {
       let type_id = TypeId::of::<T>();
-      map
-        .remove(&type_id)
-        .and_then(|ptr| ptr.downcast().ok().map(|b| *b))
+      map.remove(&type_id).and_then(|ptr| match ptr.downcast() {
+        Ok(b) => Some(*b),
+        // a lazy state that was never accessed has no value to return
+        Err(ptr) => ptr
+          .downcast::<LazyState<T>>()
+          .ok()
+          .and_then(|lazy| lazy.value.into_inner()),
+      })
     })
   }
 
@@ -158,12 +204,19 @@ impl StateManager {
 
   /// Gets the state associated with the specified type.
   pub fn try_get<T: Send + Sync + 'static>(&self) -> Option<State<'_, T>> {
-    self.with_map_ref(|map| {
-      map
-        .get(&TypeId::of::<T>())
-        .and_then(|ptr| ptr.downcast_ref::<T>())
-        .map(State)
-    })
+    // the boxed values do not move when the map is rehashed, so the downcast references outlive the lock
+    let entry = self.with_map_ref(|map| {
+      let ptr = map.get(&TypeId::of::<T>())?;
+      match ptr.downcast_ref::<T>() {
+        Some(value) => Some(Ok(value)),
+        None => ptr.downcast_ref::<LazyState<T>>().map(Err),
+      }
+    })?;
+    // the lock is released before initializing lazy state so the initializer can access other state
+    match entry {
+      Ok(value) => Some(State(value)),
+      Err(lazy) => Some(State(lazy.get())),
+    }
   }
 }
 
@@ -288,4 +341,47 @@ mod tests {
     assert!(*drop_flag_a.read().unwrap());
     assert!(*drop_flag_b.read().unwrap());
   }
+
+  #[test]
+  fn lazy_set_get() {
+    let state = Arc::new(StateManager::new());
+    let calls = Arc::new(RwLock::new(0));
+
+    assert!(state.set(2u32));
+    let state_ = state.clone();
+    let calls_ = calls.clone();
+    assert!(state.set_lazy(move || {
+      *calls_.write().unwrap() += 1;
+      // initializers can access other state
+      *state_.get::<u32>() as u64 * 10
+    }));
+    assert!(!state.set(1u64));
+    assert_eq!(*calls.read().unwrap(), 0);
+
+    assert_eq!(*state.get::<u64>(), 20);
+    assert_eq!(*state.get::<u64>(), 20);
+    assert_eq!(*calls.read().unwrap(), 1);
+
+    assert_eq!(state.unmanage::<u64>(), Some(20));
+    assert!(state.set_lazy(|| 5u64));
+    assert_eq!(state.unmanage::<u64>(), None);
+  }
+
+  #[test]
+  fn state_outlives_rehash() {
+    let state = StateManager::new();
+    assert!(state.set(1u8));
+    assert!(state.set_lazy(|| 2u16));
+    let eager = state.get::<u8>();
+    let lazy = state.get::<u16>();
+
+    // growing the map moves its entries but not the boxed values
+    macro_rules! manage_arrays {
+      ($($n:literal)*) => { $(assert!(state.set([0u8; $n]));)* };
+    }
+    manage_arrays!(1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32);
+
+    assert_eq!(*eager, 1);
+    assert_eq!(*lazy, 2);
+  }
 }
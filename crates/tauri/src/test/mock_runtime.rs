@@ -581,6 +581,22 @@ impl<T: UserEvent> WebviewDispatch<T> for MockWebviewDispatcher {
     Ok(())
   }
 
+  fn clear_cache(&self) -> Result<()> {
+    Ok(())
+  }
+
+  fn set_cache_mode(&self, _mode: tauri_runtime::webview::CacheMode) -> Result<()> {
+    Ok(())
+  }
+
+  fn set_additional_headers(&self, _headers: http::HeaderMap) -> Result<()> {
+    Ok(())
+  }
+
+  fn set_html_drag_drop_regions(&self, _regions: Vec<Rect>) -> Result<()> {
+    Ok(())
+  }
+
   fn hide(&self) -> Result<()> {
     Ok(())
   }
@@ -118,18 +118,30 @@ pub fn mock_context<R: Runtime, A: Assets<R>>(assets: A) -> crate::Context<R> {
         windows: Vec::new(),
         security: Default::default(),
         tray_icon: None,
+        menu: None,
         macos_private_api: false,
         enable_gtk_app_id: false,
+        splashscreen: None,
+        kiosk: None,
+        cli: None,
+        software_rendering_fallback: false,
+        sidecar_limits: Default::default(),
+        frontend_patches: None,
+        ipc: Default::default(),
+        feature_flags: Default::default(),
       },
       bundle: Default::default(),
       build: Default::default(),
       plugins: Default::default(),
     },
     assets: Box::new(assets),
+    window_assets: Default::default(),
     default_window_icon: None,
     app_icon: None,
     #[cfg(all(desktop, feature = "tray-icon"))]
     tray_icon: None,
+    #[cfg(all(desktop, feature = "tray-icon"))]
+    tray_icon_pair: None,
     package_info: crate::PackageInfo {
       name: "test".into(),
       version: "0.1.0".parse().unwrap(),
@@ -140,6 +152,8 @@ pub fn mock_context<R: Runtime, A: Assets<R>>(assets: A) -> crate::Context<R> {
     pattern: Pattern::Brownfield,
     runtime_authority: RuntimeAuthority::new(Default::default(), Resolved::default()),
     plugin_global_api_scripts: None,
+    resource_hashes: Default::default(),
+    attributions: Default::default(),
 
     #[cfg(dev)]
     config_parent: None,
@@ -7,14 +7,19 @@
 pub(crate) mod plugin;
 
 use crate::app::{GlobalMenuEventListener, GlobalTrayIconEventListener};
+use crate::image::theme::{system_theme, IconPair, SharedIconPair};
 use crate::menu::ContextMenu;
 use crate::menu::MenuEvent;
 use crate::resources::Resource;
 use crate::{
   image::Image, menu::run_item_main_thread, AppHandle, Manager, PhysicalPosition, Rect, Runtime,
+  Theme,
 };
 use serde::Serialize;
-use std::path::Path;
+use std::{
+  path::Path,
+  sync::{Arc, Mutex},
+};
 pub use tray_icon::TrayIconId;
 
 /// Describes the mouse button state.
@@ -212,6 +217,7 @@ impl From<tray_icon::TrayIconEvent> for TrayIconEvent {
 pub struct TrayIconBuilder<R: Runtime> {
   on_menu_event: Option<GlobalMenuEventListener<AppHandle<R>>>,
   on_tray_icon_event: Option<GlobalTrayIconEventListener<TrayIcon<R>>>,
+  icon_pair: Option<IconPair>,
   inner: tray_icon::TrayIconBuilder,
 }
 
@@ -227,6 +233,7 @@ impl<R: Runtime> TrayIconBuilder<R> {
       inner: tray_icon::TrayIconBuilder::new(),
       on_menu_event: None,
       on_tray_icon_event: None,
+      icon_pair: None,
     }
   }
 
@@ -266,6 +273,13 @@ impl<R: Runtime> TrayIconBuilder<R> {
     self
   }
 
+  /// Set the icons of this tray icon for the light and dark system themes,
+  /// see [`TrayIcon::set_icon_pair`]. Takes precedence over [`Self::icon`].
+  pub fn icon_pair(mut self, light: Image<'_>, dark: Image<'_>) -> Self {
+    self.icon_pair.replace(IconPair::new(light, dark));
+    self
+  }
+
   /// Set a tooltip for this tray icon.
   ///
   /// ## Platform-specific:
@@ -340,13 +354,20 @@ impl<R: Runtime> TrayIconBuilder<R> {
   }
 
   /// Builds and adds a new [`TrayIcon`] to the system tray.
-  pub fn build<M: Manager<R>>(self, manager: &M) -> crate::Result<TrayIcon<R>> {
+  pub fn build<M: Manager<R>>(mut self, manager: &M) -> crate::Result<TrayIcon<R>> {
     let id = self.id().clone();
+    let app_handle = manager.app_handle();
+    if let Some(pair) = &mut self.icon_pair {
+      if let Some(icon) = pair.switch(tray_theme(app_handle)) {
+        self.inner = self.inner.with_icon(icon.try_into()?);
+      }
+    }
     let inner = self.inner.build()?;
     let icon = TrayIcon {
       id,
       inner,
-      app_handle: manager.app_handle().clone(),
+      app_handle: app_handle.clone(),
+      icon_pair: Arc::new(Mutex::new(self.icon_pair)),
     };
 
     icon.register(
@@ -359,6 +380,35 @@ impl<R: Runtime> TrayIconBuilder<R> {
   }
 }
 
+/// The theme followed by the icon pairs of the tray icons.
+fn tray_theme<R: Runtime>(app: &AppHandle<R>) -> Theme {
+  system_theme()
+    .or_else(|| {
+      app
+        .manager
+        .windows()
+        .values()
+        .find_map(|window| window.theme().ok())
+    })
+    .unwrap_or(Theme::Light)
+}
+
+/// Switches the icon pairs of the tray icons when a window reports a theme change.
+///
+/// The system theme is preferred where it can be read, since the shell can use another theme than the apps.
+pub(crate) fn on_window_theme_changed<R: Runtime>(app: &AppHandle<R>, theme: Theme) {
+  let theme = system_theme().unwrap_or(theme);
+  let icons = app.manager.tray.icons.lock().unwrap().clone();
+  for icon in icons {
+    if let Err(e) = icon.apply_theme(theme) {
+      log::debug!(
+        "failed to switch the icon of the tray icon `{}`: {e}",
+        icon.id.0
+      );
+    }
+  }
+}
+
 /// Tray icon struct and associated methods.
 ///
 /// This type is reference-counted and the icon is removed when the last instance is dropped.
@@ -369,6 +419,7 @@ pub struct TrayIcon<R: Runtime> {
   id: TrayIconId,
   inner: tray_icon::TrayIcon,
   app_handle: AppHandle<R>,
+  icon_pair: SharedIconPair,
 }
 
 impl<R: Runtime> Clone for TrayIcon<R> {
@@ -377,6 +428,7 @@ impl<R: Runtime> Clone for TrayIcon<R> {
       id: self.id.clone(),
       inner: self.inner.clone(),
       app_handle: self.app_handle.clone(),
+      icon_pair: self.icon_pair.clone(),
     }
   }
 }
@@ -464,7 +516,67 @@ impl<R: Runtime> TrayIcon<R> {
   }
 
   /// Sets a new tray icon. If `None` is provided, it will remove the icon.
+  ///
+  /// The icon no longer follows the system theme if [`Self::set_icon_pair`] was used.
   pub fn set_icon(&self, icon: Option<Image<'_>>) -> crate::Result<()> {
+    self.icon_pair.lock().unwrap().take();
+    self.set_inner_icon(icon)
+  }
+
+  /// Sets the icons to display when the system uses a light and a dark theme, respectively,
+  /// the tray icon switching between them when the system theme changes.
+  ///
+  /// Theme changes are reported by the windows of the app, so the icon is only switched
+  /// while the app has a window, which can be hidden.
+  ///
+  /// The icon for the light theme is usually a dark glyph, and conversely.
+  /// On macOS, prefer a single [template](Self::set_icon_as_template) icon when the icon is monochrome.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux:** The system theme cannot be read, so the icon follows the theme of the windows of the app
+  ///   and the icon for the light theme is used until a window is created.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::image::Image;
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let tray = app.tray_by_id("main").unwrap();
+  ///     tray.set_icon_pair(
+  ///       Image::from_path("icons/tray-light.png")?,
+  ///       Image::from_path("icons/tray-dark.png")?,
+  ///     )?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  pub fn set_icon_pair(&self, light: Image<'_>, dark: Image<'_>) -> crate::Result<()> {
+    let mut pair = IconPair::new(light, dark);
+    let icon = pair.switch(tray_theme(&self.app_handle));
+    self.icon_pair.lock().unwrap().replace(pair);
+    if let Some(icon) = icon {
+      self.set_inner_icon(Some(icon))?;
+    }
+    Ok(())
+  }
+
+  /// Displays the variant of the icon pair for the theme, if any.
+  pub(crate) fn apply_theme(&self, theme: Theme) -> crate::Result<()> {
+    let icon = self
+      .icon_pair
+      .lock()
+      .unwrap()
+      .as_mut()
+      .and_then(|pair| pair.switch(theme));
+    match icon {
+      Some(icon) => self.set_inner_icon(Some(icon)),
+      None => Ok(()),
+    }
+  }
+
+  fn set_inner_icon(&self, icon: Option<Image<'_>>) -> crate::Result<()> {
     let icon = match icon {
       Some(i) => Some(i.try_into()?),
       None => None,
@@ -0,0 +1,208 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use tauri_runtime::dpi::{LogicalPosition, LogicalSize};
+
+use crate::Rect;
+
+/// The direction of a [`Layout::Split`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitDirection {
+  /// The regions are side by side, the first one on the left.
+  Row,
+  /// The regions are stacked, the first one on top.
+  Column,
+}
+
+/// The layout of the webviews of a window, applied with [`crate::Window::set_layout`].
+///
+/// The layout splits the window in regions, each showing one webview. The regions keep their proportions
+/// when the window is resized.
+///
+/// # Examples
+///
+/// An IDE-like layout with a sidebar and an editor above a terminal:
+///
+/// ```
+/// use tauri::webview::Layout;
+///
+/// let layout = Layout::row(
+///   0.25,
+///   Layout::webview("sidebar"),
+///   Layout::column(0.7, Layout::webview("editor"), Layout::webview("terminal")),
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Layout {
+  /// A region showing the webview with the given label.
+  Webview(String),
+  /// A region split in two.
+  Split {
+    /// The direction of the split.
+    direction: SplitDirection,
+    /// The share of the region given to `first`, between `0.0` and `1.0`.
+    ratio: f64,
+    /// The left or top region.
+    first: Box<Layout>,
+    /// The right or bottom region.
+    second: Box<Layout>,
+  },
+}
+
+impl Layout {
+  /// A region showing the webview with the given label.
+  pub fn webview(label: impl Into<String>) -> Self {
+    Self::Webview(label.into())
+  }
+
+  /// Splits the region in two side by side regions, giving `ratio` of the width to `first`.
+  pub fn row(ratio: f64, first: Layout, second: Layout) -> Self {
+    Self::split(SplitDirection::Row, ratio, first, second)
+  }
+
+  /// Splits the region in two stacked regions, giving `ratio` of the height to `first`.
+  pub fn column(ratio: f64, first: Layout, second: Layout) -> Self {
+    Self::split(SplitDirection::Column, ratio, first, second)
+  }
+
+  fn split(direction: SplitDirection, ratio: f64, first: Layout, second: Layout) -> Self {
+    Self::Split {
+      direction,
+      ratio,
+      first: Box::new(first),
+      second: Box::new(second),
+    }
+  }
+
+  /// The labels of the webviews of the layout.
+  pub fn labels(&self) -> Vec<&str> {
+    match self {
+      Self::Webview(label) => vec![label.as_str()],
+      Self::Split { first, second, .. } => {
+        let mut labels = first.labels();
+        labels.extend(second.labels());
+        labels
+      }
+    }
+  }
+
+  /// The first label shown in several regions of the layout.
+  pub(crate) fn duplicate_label(&self) -> Option<&str> {
+    let mut seen = std::collections::HashSet::new();
+    self.labels().into_iter().find(|label| !seen.insert(*label))
+  }
+
+  /// The bounds of the webviews of the layout in a window with the given inner size.
+  pub fn regions(&self, size: LogicalSize<f64>) -> Vec<(String, Rect)> {
+    let mut regions = Vec::new();
+    self.collect_regions(LogicalPosition::new(0., 0.), size, &mut regions);
+    regions
+  }
+
+  fn collect_regions(
+    &self,
+    position: LogicalPosition<f64>,
+    size: LogicalSize<f64>,
+    regions: &mut Vec<(String, Rect)>,
+  ) {
+    match self {
+      Self::Webview(label) => regions.push((
+        label.clone(),
+        Rect {
+          position: position.into(),
+          size: size.into(),
+        },
+      )),
+      Self::Split {
+        direction,
+        ratio,
+        first,
+        second,
+      } => {
+        let ratio = ratio.clamp(0., 1.);
+        let (first_size, second_position, second_size) = match direction {
+          SplitDirection::Row => {
+            let width = (size.width * ratio).round();
+            (
+              LogicalSize::new(width, size.height),
+              LogicalPosition::new(position.x + width, position.y),
+              LogicalSize::new(size.width - width, size.height),
+            )
+          }
+          SplitDirection::Column => {
+            let height = (size.height * ratio).round();
+            (
+              LogicalSize::new(size.width, height),
+              LogicalPosition::new(position.x, position.y + height),
+              LogicalSize::new(size.width, size.height - height),
+            )
+          }
+        };
+        first.collect_regions(position, first_size, regions);
+        second.collect_regions(second_position, second_size, regions);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tauri_runtime::dpi::{Position, Size};
+
+  fn logical(rect: &Rect) -> (f64, f64, f64, f64) {
+    match (rect.position, rect.size) {
+      (Position::Logical(position), Size::Logical(size)) => {
+        (position.x, position.y, size.width, size.height)
+      }
+      _ => panic!("expected logical bounds"),
+    }
+  }
+
+  #[test]
+  fn splits_regions() {
+    let layout = Layout::row(
+      0.25,
+      Layout::webview("sidebar"),
+      Layout::column(0.75, Layout::webview("editor"), Layout::webview("terminal")),
+    );
+    assert_eq!(layout.labels(), vec!["sidebar", "editor", "terminal"]);
+
+    let regions = layout
+      .regions(LogicalSize::new(800., 600.))
+      .iter()
+      .map(|(label, rect)| (label.clone(), logical(rect)))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      regions,
+      vec![
+        ("sidebar".into(), (0., 0., 200., 600.)),
+        ("editor".into(), (200., 0., 600., 450.)),
+        ("terminal".into(), (200., 450., 600., 150.)),
+      ]
+    );
+  }
+
+  #[test]
+  fn finds_duplicate_labels() {
+    let layout = Layout::row(
+      0.5,
+      Layout::webview("a"),
+      Layout::column(0.5, Layout::webview("b"), Layout::webview("a")),
+    );
+    assert_eq!(layout.duplicate_label(), Some("a"));
+    assert_eq!(
+      Layout::row(0.5, Layout::webview("a"), Layout::webview("b")).duplicate_label(),
+      None
+    );
+  }
+
+  #[test]
+  fn clamps_ratio() {
+    let regions = Layout::row(2., Layout::webview("a"), Layout::webview("b"))
+      .regions(LogicalSize::new(100., 100.));
+    assert_eq!(logical(&regions[0].1), (0., 0., 100., 100.));
+    assert_eq!(logical(&regions[1].1), (100., 0., 0., 100.));
+  }
+}
@@ -4,15 +4,24 @@
 
 //! The Tauri webview types and functions.
 
+#[cfg(any(test, all(desktop, feature = "unstable")))]
+mod layout;
 pub(crate) mod plugin;
+#[cfg(desktop)]
+mod pool;
 mod webview_window;
 
+#[cfg(any(test, all(desktop, feature = "unstable")))]
+#[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "unstable"))))]
+pub use layout::{Layout, SplitDirection};
+#[cfg(desktop)]
+pub use pool::{WebviewPool, POOL_WINDOW_LABEL_PREFIX};
 pub use webview_window::{WebviewWindow, WebviewWindowBuilder};
 
 use http::HeaderMap;
 use serde::Serialize;
 use tauri_macros::default_runtime;
-pub use tauri_runtime::webview::PageLoadEvent;
+pub use tauri_runtime::webview::{CacheMode, PageLoadEvent};
 #[cfg(desktop)]
 use tauri_runtime::{
   dpi::{PhysicalPosition, PhysicalSize, Position, Size},
@@ -30,7 +39,7 @@ use crate::{
   event::{EmitArgs, EventTarget},
   ipc::{
     CallbackFn, CommandArg, CommandItem, Invoke, InvokeBody, InvokeError, InvokeMessage,
-    InvokeResolver, Origin, OwnedInvokeResponder,
+    InvokeResolver, InvokeResponse, InvokeResponseBody, Origin, OwnedInvokeResponder,
   },
   manager::AppManager,
   sealed::{ManagerBase, RuntimeOrDispatch},
@@ -236,6 +245,7 @@ unstable_struct!(
     pub(crate) navigation_handler: Option<Box<NavigationHandler>>,
     pub(crate) on_page_load_handler: Option<Box<OnPageLoad<R>>>,
     pub(crate) download_handler: Option<Arc<DownloadHandler<R>>>,
+    pub(crate) injected_context: Option<serde_json::Value>,
   }
 );
 
@@ -312,6 +322,7 @@ async fn create_window(app: tauri::AppHandle) {
       navigation_handler: None,
       on_page_load_handler: None,
       download_handler: None,
+      injected_context: None,
     }
   }
 
@@ -352,6 +363,7 @@ async fn reopen_window(app: tauri::AppHandle) {
       navigation_handler: None,
       on_page_load_handler: None,
       download_handler: None,
+      injected_context: None,
     }
   }
 
@@ -547,6 +559,22 @@ tauri::Builder::default()
     manager: &M,
     window_label: &str,
   ) -> crate::Result<PendingWebview<EventLoopMessage, R>> {
+    if let Some(context) = self.injected_context.take() {
+      let app_manager = manager.manager();
+      let mut origins = app_manager.local_origins();
+      origins.extend(
+        app_manager
+          .runtime_authority
+          .lock()
+          .unwrap()
+          .remote_origins(window_label, &self.label),
+      );
+      self
+        .webview_attributes
+        .initialization_scripts
+        .insert(0, injected_context_script(&context, &origins)?);
+    }
+
     let mut pending = PendingWebview::new(self.webview_attributes, self.label.clone())?;
     pending.navigation_handler = self.navigation_handler.take();
     pending.web_resource_request_handler = self.web_resource_request_handler.take();
@@ -668,6 +696,21 @@ fn main() {
     self
   }
 
+  /// Exposes a read-only JSON value to the pages of the webview as `window.__APP_CONTEXT__`,
+  /// defined before the initialization scripts and the page scripts run.
+  ///
+  /// This lets a page know its role or feature flags without invoking a command when it loads.
+  /// The value is frozen and is not updated after the webview is created.
+  ///
+  /// Only the top-level frame receives the context, and only on app pages or on the remote origins
+  /// a capability grants this webview access to with an exact host, so third-party pages
+  /// and iframes navigated to by the webview cannot read it.
+  #[must_use]
+  pub fn injected_context(mut self, context: serde_json::Value) -> Self {
+    self.injected_context.replace(context);
+    self
+  }
+
   /// Set the user agent for the webview
   #[must_use]
   pub fn user_agent(mut self, user_agent: &str) -> Self {
@@ -787,6 +830,95 @@ fn main() {
     self.webview_attributes.browser_extensions_enabled = enabled;
     self
   }
+
+  /// Whether the text fields are spell checked. Defaults to the webview engine behavior.
+  ///
+  /// This option, [`Self::autocorrect`] and [`Self::autofill`] are set as defaults on the text fields
+  /// of the pages on the origin of the initial URL, so attributes set by the page take precedence.
+  /// After navigating to another origin the engine defaults apply.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux**: Also enables spell checking on the WebKitGTK web context,
+  ///   which is shared by all webviews with the same data directory and is not updated by `set_spellcheck`.
+  #[must_use]
+  pub fn spellcheck(mut self, enabled: bool) -> Self {
+    self.webview_attributes.spellcheck.replace(enabled);
+    self
+  }
+
+  /// The languages used to spell check, such as `en_US`.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux**: Set on the web context, so it applies to all webviews sharing the same data directory.
+  /// - **Windows / macOS / iOS / Android** - Unsupported, the system languages are used.
+  #[must_use]
+  pub fn spellcheck_languages(mut self, languages: Vec<String>) -> Self {
+    self.webview_attributes.spellcheck_languages = languages;
+    self
+  }
+
+  /// Whether the text fields are automatically corrected while typing. Defaults to the webview engine behavior.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows / Linux / Android** - Unsupported, the engines do not correct text.
+  #[must_use]
+  pub fn autocorrect(mut self, enabled: bool) -> Self {
+    self.webview_attributes.autocorrect.replace(enabled);
+    self
+  }
+
+  /// Whether the webview suggests previously entered values and saved passwords in forms.
+  /// Defaults to the webview engine behavior.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows**: Also controls WebView2's `IsGeneralAutofillEnabled` and `IsPasswordAutosaveEnabled` settings.
+  #[must_use]
+  pub fn autofill(mut self, enabled: bool) -> Self {
+    self.webview_attributes.autofill.replace(enabled);
+    self
+  }
+
+  /// Headers sent with the initial page request and the navigations made with [`Webview::navigate`],
+  /// such as an authorization token for an internal web app.
+  ///
+  /// Navigations started by the page itself, such as following a link, are sent without them.
+  #[must_use]
+  pub fn additional_headers(mut self, headers: http::HeaderMap) -> Self {
+    self.webview_attributes.additional_headers.replace(headers);
+    self
+  }
+
+  /// Whether the webview renders with the GPU. Defaults to `true`.
+  ///
+  /// Disable it to work around graphics driver bugs, such as blank windows, at the cost of rendering performance.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows**: Passes `--disable-gpu` to the browser process, which is shared by the webviews with the same data directory,
+  /// so they must all use the same value.
+  /// - **macOS / iOS / Android**: Unsupported.
+  #[must_use]
+  pub fn hardware_acceleration(mut self, enabled: bool) -> Self {
+    self.webview_attributes.hardware_acceleration = enabled;
+    self
+  }
+
+  /// The maximum size in bytes of the HTTP disk cache.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows**: Passes `--disk-cache-size` to the browser process, which is shared by the webviews with the same data directory,
+  /// so they must all use the same value.
+  /// - **macOS / Linux / iOS / Android**: Unsupported.
+  #[must_use]
+  pub fn disk_cache_size(mut self, size: u64) -> Self {
+    self.webview_attributes.disk_cache_size = Some(size);
+    self
+  }
 }
 
 /// Webview.
@@ -1096,44 +1228,7 @@ fn main() {
   }
 
   fn is_local_url(&self, current_url: &Url) -> bool {
-    // if from `tauri://` custom protocol
-    ({
-      let protocol_url = self.manager().protocol_url();
-      current_url.scheme() == protocol_url.scheme()
-      && current_url.domain() == protocol_url.domain()
-    }) ||
-
-    // or if relative to `devUrl` or `frontendDist`
-      self
-          .manager()
-          .get_url()
-          .make_relative(current_url)
-          .is_some()
-
-      // or from a custom protocol registered by the user
-      || ({
-        let scheme = current_url.scheme();
-        let protocols = self.manager().webview.uri_scheme_protocols.lock().unwrap();
-
-        #[cfg(all(not(windows), not(target_os = "android")))]
-        let local = protocols.contains_key(scheme);
-
-        // on window and android, custom protocols are `http://<protocol-name>.path/to/route`
-        // so we check using the first part of the domain
-        #[cfg(any(windows, target_os = "android"))]
-        let local = {
-          let protocol_url = self.manager().protocol_url();
-          let maybe_protocol = current_url
-            .domain()
-            .and_then(|d| d .split_once('.'))
-            .unwrap_or_default()
-            .0;
-
-          protocols.contains_key(maybe_protocol) && scheme == protocol_url.scheme()
-        };
-
-        local
-      })
+    self.manager().is_local_url(current_url)
   }
 
   /// Handles this window receiving an [`InvokeRequest`].
@@ -1159,11 +1254,50 @@ fn main() {
       return;
     }
 
+    #[cfg(any(feature = "tracing", feature = "metrics"))]
+    let started = std::time::Instant::now();
+    #[cfg(feature = "tracing")]
+    let latency_manager = manager.clone();
+
     let resolver = InvokeResolver::new(
       self.clone(),
       Arc::new(Mutex::new(Some(Box::new(
         #[allow(unused_variables)]
-        move |webview: Webview<R>, cmd, response, callback, error| {
+        move |webview: Webview<R>, cmd: String, response, callback, error| {
+          #[cfg(any(feature = "tracing", feature = "metrics"))]
+          let elapsed = started.elapsed();
+          #[cfg(feature = "tracing")]
+          {
+            tracing::debug!(
+              cmd,
+              webview = webview.label(),
+              ?elapsed,
+              "ipc command resolved"
+            );
+            latency_manager.command_latencies.record(&cmd, elapsed);
+          }
+          let response = match response {
+            InvokeResponse::Ok(body) => match crate::ipc::limits::check_size(
+              &webview.manager().config().app.ipc,
+              crate::ipc::limits::Payload::Response,
+              match &body {
+                InvokeResponseBody::Json(json) => json.len(),
+                InvokeResponseBody::Raw(bytes) => bytes.len(),
+              },
+            ) {
+              Ok(()) => InvokeResponse::Ok(body),
+              Err(e) => InvokeResponse::Err(e.into()),
+            },
+            response => response,
+          };
+          #[cfg(feature = "metrics")]
+          if let Some(metrics) = webview
+            .manager()
+            .state
+            .try_get::<crate::metrics::Metrics<R>>()
+          {
+            metrics.record_command(&cmd, elapsed, matches!(response, InvokeResponse::Ok(_)));
+          }
           responder(webview, cmd, response, callback, error);
         },
       )))),
@@ -1255,7 +1389,8 @@ fn main() {
       let message = invoke.message.clone();
 
       #[allow(unused_mut)]
-      let mut handled = manager.extend_api(plugin, invoke);
+      let mut handled =
+        crate::panic::catch_command(&resolver, || manager.extend_api(plugin, invoke));
 
       #[cfg(mobile)]
       {
@@ -1301,7 +1436,7 @@ fn main() {
       }
     } else {
       let command = invoke.message.command.clone();
-      let handled = manager.run_invoke_handler(invoke);
+      let handled = crate::panic::catch_command(&resolver, || manager.run_invoke_handler(invoke));
       if !handled {
         resolver.reject(format!("Command {command} not found"));
       }
@@ -1353,11 +1488,22 @@ fn main() {
   }
 
   pub(crate) fn emit_js(&self, emit_args: &EmitArgs, ids: &[u32]) -> crate::Result<()> {
-    self.eval(&crate::event::emit_js_script(
-      self.manager().listeners().function_name(),
-      emit_args,
-      &serde_json::to_string(ids)?,
-    )?)?;
+    let script =
+      if crate::ipc::limits::needs_chunks(&self.manager().config().app.ipc, &emit_args.payload) {
+        crate::event::emit_js_script_with_payload(
+          self.manager().listeners().function_name(),
+          emit_args,
+          &crate::ipc::limits::eval_chunks(self, &emit_args.payload)?,
+          &serde_json::to_string(ids)?,
+        )?
+      } else {
+        crate::event::emit_js_script(
+          self.manager().listeners().function_name(),
+          emit_args,
+          &serde_json::to_string(ids)?,
+        )?
+      };
+    self.eval(&script)?;
     Ok(())
   }
 
@@ -1492,6 +1638,113 @@ tauri::Builder::default()
       .clear_all_browsing_data()
       .map_err(Into::into)
   }
+
+  /// Clears the HTTP cache, keeping the cookies and the storage, e.g. to force-refresh frequently updated remote content.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **iOS**: Unsupported.
+  pub fn clear_cache(&self) -> crate::Result<()> {
+    self.webview.dispatcher.clear_cache().map_err(Into::into)
+  }
+
+  /// Sets how the webview uses its HTTP cache. [`CacheMode::Disabled`] loads every request from the network.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows**: [`CacheMode::Disabled`] bypasses the cache with the DevTools protocol, like the "Disable cache" option of the DevTools.
+  /// - **Linux**: [`CacheMode::Disabled`] switches the web context to the `DocumentViewer` cache model of WebKitGTK,
+  ///   which disables the memory caches but not the disk cache, for all the webviews sharing the same web context.
+  /// - **Android**: [`CacheMode::Disabled`] maps to `WebSettings.LOAD_NO_CACHE`.
+  /// - **macOS / iOS**: Unsupported, returns an [`std::io::ErrorKind::Unsupported`] error.
+  pub fn set_cache_mode(&self, mode: CacheMode) -> crate::Result<()> {
+    // WKWebView has no per-webview cache policy for the requests made by the page
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    {
+      let _ = mode;
+      Err(
+        std::io::Error::new(
+          std::io::ErrorKind::Unsupported,
+          "the cache mode cannot be set on this platform",
+        )
+        .into(),
+      )
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+    self
+      .webview
+      .dispatcher
+      .set_cache_mode(mode)
+      .map_err(Into::into)
+  }
+
+  /// Sets whether the text fields of the current page are spell checked.
+  ///
+  /// The value set with [`WebviewBuilder::spellcheck`] applies again after a navigation.
+  /// Remote pages are only affected when a capability is granted to them.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux**: Only changes the page attributes, the WebKitGTK web context keeps the value it was created with.
+  pub fn set_spellcheck(&self, enabled: bool) -> crate::Result<()> {
+    self.update_text_input(serde_json::json!({ "spellcheck": enabled }))
+  }
+
+  /// Sets whether the text fields of the current page are automatically corrected while typing.
+  ///
+  /// The value set with [`WebviewBuilder::autocorrect`] applies again after a navigation.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows / Linux / Android** - Unsupported, the engines do not correct text.
+  pub fn set_autocorrect(&self, enabled: bool) -> crate::Result<()> {
+    self.update_text_input(serde_json::json!({ "autocorrect": enabled }))
+  }
+
+  /// Sets whether the forms of the current page suggest previously entered values.
+  ///
+  /// The value set with [`WebviewBuilder::autofill`] applies again after a navigation.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows**: The WebView2 autofill settings are only set when the webview is created.
+  pub fn set_autofill(&self, enabled: bool) -> crate::Result<()> {
+    self.update_text_input(serde_json::json!({ "autofill": enabled }))
+  }
+
+  /// Sets the headers sent with the next navigations made with [`Self::navigate`],
+  /// replacing the ones set with [`WebviewBuilder::additional_headers`].
+  pub fn set_additional_headers(&self, headers: http::HeaderMap) -> crate::Result<()> {
+    self
+      .webview
+      .dispatcher
+      .set_additional_headers(headers)
+      .map_err(Into::into)
+  }
+
+  /// Sets the regions of the webview, relative to its top left corner,
+  /// where HTML5 drag and drop is used instead of the native file drop handler.
+  ///
+  /// This lets draggable elements work while files can still be dropped on the rest of the webview.
+  /// The regions must be updated when the layout changes, and replace the previous regions.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows**: Unsupported. The native handler replaces the WebView2 drop target, so the webview never receives the drops it skips.
+  ///   Use [`WebviewBuilder::disable_drag_drop_handler`] to let HTML5 drag and drop handle the whole webview instead.
+  pub fn set_html_drag_drop_regions(&self, regions: Vec<crate::Rect>) -> crate::Result<()> {
+    self
+      .webview
+      .dispatcher
+      .set_html_drag_drop_regions(regions)
+      .map_err(Into::into)
+  }
+
+  fn update_text_input(&self, changes: serde_json::Value) -> crate::Result<()> {
+    // the script is not injected in remote pages without a capability
+    self.eval(&format!("window.__TAURI_TEXT_INPUT__?.update({changes})"))
+  }
 }
 
 impl<R: Runtime> Listener<R> for Webview<R> {
@@ -1702,6 +1955,31 @@ impl<'de, R: Runtime> CommandArg<'de, R> for Webview<R> {
   }
 }
 
+/// The script defining `window.__APP_CONTEXT__` as a deeply frozen, non-writable copy of the context
+/// in the top-level frame of the given origins.
+fn injected_context_script(
+  context: &serde_json::Value,
+  origins: &[String],
+) -> crate::Result<String> {
+  Ok(format!(
+    r#"
+      if (window.self === window.top && {origins}.includes(window.location.origin)) {{
+        Object.defineProperty(window, '__APP_CONTEXT__', {{
+          value: (function freeze(value) {{
+            if (value && typeof value === 'object') {{
+              Object.values(value).forEach(freeze)
+              Object.freeze(value)
+            }}
+            return value
+          }})(JSON.parse({context}))
+        }})
+      }}
+    "#,
+    origins = serde_json::to_string(origins)?,
+    context = serde_json::to_string(&context.to_string())?,
+  ))
+}
+
 #[cfg(test)]
 mod tests {
   #[test]
@@ -1709,4 +1987,40 @@ mod tests {
     crate::test_utils::assert_send::<super::Webview>();
     crate::test_utils::assert_sync::<super::Webview>();
   }
+
+  /// Reads the JSON value following `prefix` in the script.
+  fn argument<T: serde::de::DeserializeOwned>(script: &str, prefix: &str) -> T {
+    let start = script.find(prefix).unwrap() + prefix.len();
+    let mut stream = serde_json::Deserializer::from_str(&script[start..]).into_iter();
+    stream.next().unwrap().unwrap()
+  }
+
+  #[test]
+  fn injected_context_round_trips() {
+    let context = serde_json::json!({
+      "role": "'settings'",
+      "flags": ["\")}); alert(1); //", "</script>"],
+      "nested": { "count": 1 },
+    });
+    let script = super::injected_context_script(&context, &[]).unwrap();
+
+    let json: String = argument(&script, "JSON.parse(");
+    assert_eq!(
+      serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+      context
+    );
+  }
+
+  #[test]
+  fn injected_context_is_limited_to_the_top_frame_of_the_origins() {
+    let origins = vec![
+      "tauri://localhost".to_string(),
+      "https://tauri.app".to_string(),
+    ];
+    let script = super::injected_context_script(&serde_json::json!({}), &origins).unwrap();
+
+    let guarded: Vec<String> = argument(&script, "window.self === window.top && ");
+    assert_eq!(guarded, origins);
+    assert!(script.contains(".includes(window.location.origin)"));
+  }
 }
@@ -42,6 +42,13 @@ mod desktop_commands {
     incognito: bool,
     #[serde(default)]
     zoom_hotkeys_enabled: bool,
+    spellcheck: Option<bool>,
+    #[serde(default)]
+    spellcheck_languages: Vec<String>,
+    autocorrect: Option<bool>,
+    autofill: Option<bool>,
+    hardware_acceleration: Option<bool>,
+    disk_cache_size: Option<u64>,
   }
 
   #[derive(Serialize)]
@@ -99,6 +106,13 @@ mod desktop_commands {
     builder.webview_attributes.window_effects = options.window_effects;
     builder.webview_attributes.incognito = options.incognito;
     builder.webview_attributes.zoom_hotkeys_enabled = options.zoom_hotkeys_enabled;
+    builder.webview_attributes.spellcheck = options.spellcheck;
+    builder.webview_attributes.spellcheck_languages = options.spellcheck_languages;
+    builder.webview_attributes.autocorrect = options.autocorrect;
+    builder.webview_attributes.autofill = options.autofill;
+    builder.webview_attributes.hardware_acceleration =
+      options.hardware_acceleration.unwrap_or(true);
+    builder.webview_attributes.disk_cache_size = options.disk_cache_size;
 
     window.add_child(
       builder,
@@ -180,6 +194,17 @@ mod desktop_commands {
   setter!(webview_show, show);
   setter!(set_webview_zoom, set_zoom, f64);
   setter!(clear_all_browsing_data, clear_all_browsing_data);
+  setter!(clear_webview_cache, clear_cache);
+  setter!(
+    set_webview_cache_mode,
+    set_cache_mode,
+    crate::webview::CacheMode
+  );
+  setter!(
+    set_webview_html_drag_drop_regions,
+    set_html_drag_drop_regions,
+    Vec<crate::Rect>
+  );
 
   #[command(root = "crate")]
   pub async fn reparent<R: Runtime>(
@@ -194,6 +219,40 @@ mod desktop_commands {
     Ok(())
   }
 
+  #[command(root = "crate")]
+  pub async fn internal_ime_composition<R: Runtime>(
+    webview: crate::Webview<R>,
+    event: crate::keyboard::ImeComposition,
+  ) -> crate::Result<()> {
+    let event = crate::WebviewEvent::ImeComposition(event);
+    for handler in webview.manager.webview.event_listeners.iter() {
+      handler(&webview, &event);
+    }
+    Ok(())
+  }
+
+  #[command(root = "crate")]
+  pub async fn internal_kiosk_heartbeat<R: Runtime>(webview: crate::Webview<R>) {
+    if let Some(kiosk) = webview
+      .manager()
+      .state
+      .try_get::<crate::app::kiosk::Kiosk>()
+    {
+      kiosk.heartbeat(webview.label());
+    }
+  }
+
+  #[command(root = "crate")]
+  pub async fn internal_gpu_failure<R: Runtime>(
+    app: AppHandle<R>,
+    webview: crate::Webview<R>,
+    failure: crate::GpuFailure,
+  ) {
+    if let Some(gpu) = webview.manager().state.try_get::<crate::app::gpu::Gpu>() {
+      gpu.report(&app, webview.label(), failure);
+    }
+  }
+
   #[cfg(any(debug_assertions, feature = "devtools"))]
   #[command(root = "crate")]
   pub async fn internal_toggle_devtools<R: Runtime>(
@@ -268,6 +327,12 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             desktop_commands::print,
             desktop_commands::reparent,
             desktop_commands::clear_all_browsing_data,
+            desktop_commands::clear_webview_cache,
+            desktop_commands::set_webview_cache_mode,
+            desktop_commands::set_webview_html_drag_drop_regions,
+            desktop_commands::internal_ime_composition,
+            desktop_commands::internal_kiosk_heartbeat,
+            desktop_commands::internal_gpu_failure,
             #[cfg(any(debug_assertions, feature = "devtools"))]
             desktop_commands::internal_toggle_devtools,
           ]);
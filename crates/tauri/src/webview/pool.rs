@@ -0,0 +1,243 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+  collections::{HashMap, VecDeque},
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex,
+  },
+};
+
+use url::Url;
+
+use crate::{AppHandle, Manager, Runtime, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+/// Prefix of the labels of the windows created by the [`WebviewPool`].
+pub const POOL_WINDOW_LABEL_PREFIX: &str = "webview-pool-";
+
+/// A pool of hidden, already initialized webview windows.
+///
+/// Creating a webview window can take hundreds of milliseconds.
+/// Apps that open windows frequently, such as pickers or detail windows,
+/// can instead [acquire](Self::acquire) a pre-warmed window from the pool and only pay for the navigation.
+/// The pool is refilled in the background after each acquisition.
+///
+/// Pre-warmed windows are labeled `webview-pool-<n>` since the label of a window cannot change after its creation,
+/// so capabilities for them must target the `webview-pool-*` window label pattern.
+///
+/// Windows handed back with [`Self::release`] keep their label instead,
+/// and [`WebviewWindowBuilder::build`] reuses them when a window with the same label is built again.
+///
+/// Access it with [`Manager::webview_pool`] and configure its size with [`crate::Builder::webview_pool`].
+pub struct WebviewPool<R: Runtime> {
+  app: AppHandle<R>,
+  size: AtomicUsize,
+  next_id: AtomicUsize,
+  idle: Mutex<Idle<R>>,
+}
+
+struct Idle<R: Runtime> {
+  /// Pre-warmed windows, labeled `webview-pool-<n>`.
+  windows: VecDeque<WebviewWindow<R>>,
+  /// Windows being created by [`WebviewPool::fill`], counted so concurrent fills don't exceed the size.
+  pending: usize,
+  /// Released windows, keyed by label.
+  released: HashMap<String, WebviewWindow<R>>,
+}
+
+impl<R: Runtime> Default for Idle<R> {
+  fn default() -> Self {
+    Self {
+      windows: Default::default(),
+      pending: 0,
+      released: Default::default(),
+    }
+  }
+}
+
+impl<R: Runtime> WebviewPool<R> {
+  pub(crate) fn new(app: AppHandle<R>, size: usize) -> Self {
+    Self {
+      app,
+      size: AtomicUsize::new(size),
+      next_id: AtomicUsize::new(0),
+      idle: Default::default(),
+    }
+  }
+
+  /// How many pre-warmed windows the pool keeps.
+  pub fn size(&self) -> usize {
+    self.size.load(Ordering::Relaxed)
+  }
+
+  /// Changes how many pre-warmed windows the pool keeps, creating or destroying idle windows as needed.
+  pub fn set_size(&self, size: usize) -> crate::Result<()> {
+    self.size.store(size, Ordering::Relaxed);
+    let extra = {
+      let mut idle = self.idle.lock().unwrap();
+      let len = idle.windows.len();
+      idle.windows.split_off(len.min(size))
+    };
+    for window in extra {
+      window.destroy()?;
+    }
+    self.fill()
+  }
+
+  /// How many pre-warmed windows are ready to be acquired.
+  pub fn idle(&self) -> usize {
+    self.idle.lock().unwrap().windows.len()
+  }
+
+  /// Takes a hidden pre-warmed window from the pool and navigates it to the given URL.
+  ///
+  /// A new window is created if the pool is empty.
+  /// Windows built with [`WebviewWindowBuilder::build`] only use the pool when they were [released](Self::release),
+  /// since a pre-warmed window cannot take the label requested by the builder.
+  /// The window is returned hidden, so it can be configured before calling [`WebviewWindow::show`].
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::{Manager, WebviewUrl};
+  ///
+  /// #[tauri::command]
+  /// fn open_picker(app: tauri::AppHandle) -> tauri::Result<()> {
+  ///   let picker = app.webview_pool().acquire(WebviewUrl::App("picker.html".into()))?;
+  ///   picker.set_title("Pick a file")?;
+  ///   picker.show()?;
+  ///   Ok(())
+  /// }
+  /// ```
+  pub fn acquire(&self, url: WebviewUrl) -> crate::Result<WebviewWindow<R>> {
+    let url = crate::manager::webview::resolve_url(&self.app.manager, None, &url);
+
+    let pooled = loop {
+      let Some(window) = self.idle.lock().unwrap().windows.pop_front() else {
+        break None;
+      };
+      // skip windows destroyed while idle
+      if self.app.get_webview_window(window.label()).is_some() {
+        break Some(window);
+      }
+    };
+
+    let window = match pooled {
+      Some(mut window) => {
+        window.navigate(url)?;
+        window
+      }
+      None => self.create(&url)?,
+    };
+
+    let app = self.app.clone();
+    self.app.run_on_main_thread(move || {
+      if let Err(e) = app.webview_pool().fill() {
+        log::error!("failed to refill the webview pool: {e}");
+      }
+    })?;
+
+    Ok(window)
+  }
+
+  /// Hides the window and keeps it in the pool instead of destroying it,
+  /// so the next [`WebviewWindowBuilder::build`] call with the same label reuses it.
+  ///
+  /// The window is navigated to `about:blank` to free the resources of its page.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::{Manager, WindowEvent};
+  ///
+  /// tauri::Builder::default()
+  ///   .on_window_event(|window, event| {
+  ///     if let WindowEvent::CloseRequested { api, .. } = event {
+  ///       if window.label() == "picker" {
+  ///         api.prevent_close();
+  ///         let picker = window.get_webview_window("picker").unwrap();
+  ///         window.webview_pool().release(picker).unwrap();
+  ///       }
+  ///     }
+  ///   });
+  /// ```
+  pub fn release(&self, mut window: WebviewWindow<R>) -> crate::Result<()> {
+    window.hide()?;
+    window.navigate(Url::parse("about:blank").unwrap())?;
+    self
+      .idle
+      .lock()
+      .unwrap()
+      .released
+      .insert(window.label().to_string(), window);
+    Ok(())
+  }
+
+  /// Takes the released window with the given label and navigates it to the given URL.
+  ///
+  /// Used by [`WebviewWindowBuilder::build`], which shows the returned window.
+  pub(crate) fn take(
+    &self,
+    label: &str,
+    url: &WebviewUrl,
+  ) -> crate::Result<Option<WebviewWindow<R>>> {
+    let Some(mut window) = self.idle.lock().unwrap().released.remove(label) else {
+      return Ok(None);
+    };
+    // the window was destroyed while released
+    if self.app.get_webview_window(label).is_none() {
+      return Ok(None);
+    }
+    let url = crate::manager::webview::resolve_url(&self.app.manager, Some(label), url);
+    window.navigate(url)?;
+    Ok(Some(window))
+  }
+
+  /// Creates windows until the pool has [`Self::size`] idle windows.
+  pub(crate) fn fill(&self) -> crate::Result<()> {
+    loop {
+      // reserve the slot under the lock so concurrent fills don't create more windows than the size
+      {
+        let mut idle = self.idle.lock().unwrap();
+        if idle.windows.len() + idle.pending >= self.size() {
+          return Ok(());
+        }
+        idle.pending += 1;
+      }
+
+      let window = self.create(&Url::parse("about:blank").unwrap());
+
+      let mut idle = self.idle.lock().unwrap();
+      idle.pending -= 1;
+      idle.windows.push_back(window?);
+    }
+  }
+
+  /// Destroys the idle windows if they are the only windows left,
+  /// so they don't prevent the app from exiting when its last window is closed.
+  pub(crate) fn on_window_destroyed(&self) {
+    let mut idle = self.idle.lock().unwrap();
+    let only_idle_left = self.app.manager.windows().keys().all(|label| {
+      idle.released.contains_key(label) || idle.windows.iter().any(|w| w.label() == label)
+    });
+    if only_idle_left {
+      let released = std::mem::take(&mut idle.released);
+      for window in idle.windows.drain(..).chain(released.into_values()) {
+        let _ = window.destroy();
+      }
+    }
+  }
+
+  fn create(&self, url: &Url) -> crate::Result<WebviewWindow<R>> {
+    let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+    WebviewWindowBuilder::new(
+      &self.app,
+      format!("{POOL_WINDOW_LABEL_PREFIX}{id}"),
+      WebviewUrl::External(url.clone()),
+    )
+    .visible(false)
+    .build()
+  }
+}
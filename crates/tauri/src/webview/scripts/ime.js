@@ -0,0 +1,55 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  // composition updates are coalesced so fast typing does not flood the IPC
+  const UPDATE_INTERVAL = 50
+
+  let pendingUpdate = null
+  let updateTimeout = null
+
+  function forward(event) {
+    window.__TAURI_INTERNALS__
+      .invoke('plugin:webview|internal_ime_composition', { event })
+      // webviews without access to the command do not forward compositions
+      .catch(() => {})
+  }
+
+  function flushUpdate() {
+    if (updateTimeout !== null) {
+      clearTimeout(updateTimeout)
+      updateTimeout = null
+    }
+    if (pendingUpdate !== null) {
+      forward({ type: 'update', text: pendingUpdate })
+      pendingUpdate = null
+    }
+  }
+
+  document.addEventListener(
+    'compositionstart',
+    () => forward({ type: 'start' }),
+    true
+  )
+  document.addEventListener(
+    'compositionupdate',
+    (event) => {
+      pendingUpdate = event.data
+      if (updateTimeout === null) {
+        updateTimeout = setTimeout(flushUpdate, UPDATE_INTERVAL)
+      }
+    },
+    true
+  )
+  document.addEventListener(
+    'compositionend',
+    (event) => {
+      // the last update is superseded by the committed text
+      pendingUpdate = null
+      flushUpdate()
+      forward({ type: 'end', text: event.data })
+    },
+    true
+  )
+})()
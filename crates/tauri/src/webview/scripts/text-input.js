@@ -0,0 +1,96 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  if (
+    window.location.protocol + '//' + window.location.host !==
+    __TEMPLATE_origin__
+  ) {
+    return
+  }
+
+  const options = {
+    spellcheck: __TEMPLATE_spellcheck__,
+    autocorrect: __TEMPLATE_autocorrect__,
+    autofill: __TEMPLATE_autofill__
+  }
+
+  const FIELDS = 'form, input, textarea, [contenteditable]'
+
+  // attributes set by this script, the ones set by the page are never changed
+  const managed = new WeakMap()
+
+  function setDefault(element, name, value) {
+    const owned = managed.get(element) || new Set()
+    if (element.hasAttribute(name) && !owned.has(name)) {
+      return
+    }
+    if (value === null) {
+      if (owned.has(name)) {
+        element.removeAttribute(name)
+        owned.delete(name)
+      }
+    } else {
+      element.setAttribute(name, value)
+      owned.add(name)
+    }
+    managed.set(element, owned)
+  }
+
+  function applyTo(element) {
+    setDefault(
+      element,
+      'spellcheck',
+      options.spellcheck === null ? null : String(options.spellcheck)
+    )
+    setDefault(
+      element,
+      'autocorrect',
+      options.autocorrect === null ? null : options.autocorrect ? 'on' : 'off'
+    )
+    // a missing `autocomplete` attribute already lets the engine autofill
+    setDefault(
+      element,
+      'autocomplete',
+      options.autofill === false ? 'off' : null
+    )
+  }
+
+  function apply(root) {
+    if (root.matches && root.matches(FIELDS)) {
+      applyTo(root)
+    }
+    if (root.querySelectorAll) {
+      root.querySelectorAll(FIELDS).forEach(applyTo)
+    }
+  }
+
+  let observer = null
+
+  function update(changes) {
+    Object.assign(options, changes)
+    if (document.documentElement) {
+      apply(document.documentElement)
+    }
+
+    const needsObserver =
+      options.spellcheck !== null ||
+      options.autocorrect !== null ||
+      options.autofill !== null
+    if (needsObserver && !observer) {
+      observer = new MutationObserver((mutations) => {
+        for (const mutation of mutations) {
+          mutation.addedNodes.forEach(apply)
+        }
+      })
+      observer.observe(document, { childList: true, subtree: true })
+    }
+  }
+
+  Object.defineProperty(window, '__TAURI_TEXT_INPUT__', {
+    value: Object.freeze({ update })
+  })
+
+  update({})
+})()
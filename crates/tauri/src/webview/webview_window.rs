@@ -143,6 +143,35 @@ impl<'a, R: Runtime, M: Manager<R>> WebviewWindowBuilder<'a, R, M> {
     })
   }
 
+  /// Initializes a webview window builder from the entry of `tauri.conf.json > app > windows` with the given label,
+  /// including the windows configured with `"create": false` to be created later.
+  ///
+  /// Unlike re-specifying the window in code, the webview and security options such as the URL, the proxy
+  /// or the incognito mode always match the configuration.
+  ///
+  /// See [`Self::from_config`] for the known issues.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #[tauri::command]
+  /// async fn open_settings(app: tauri::AppHandle) -> tauri::Result<()> {
+  ///   tauri::WebviewWindowBuilder::from_config_label(&app, "settings")?.build()?;
+  ///   Ok(())
+  /// }
+  /// ```
+  pub fn from_config_label(manager: &'a M, label: &str) -> crate::Result<Self> {
+    let config = manager
+      .config()
+      .app
+      .windows
+      .iter()
+      .find(|config| config.label == label)
+      .cloned()
+      .ok_or_else(|| crate::Error::WindowConfigNotFound(label.to_string()))?;
+    Self::from_config(manager, &config)
+  }
+
   /// Registers a global menu event listener.
   ///
   /// Note that this handler is called for any menu event,
@@ -351,7 +380,25 @@ tauri::Builder::default()
   }
 
   /// Creates a new window.
+  ///
+  /// If a window with the same label was handed back with [`crate::webview::WebviewPool::release`],
+  /// it is navigated to the builder URL and shown instead of creating a new one.
+  /// The other builder options are not reapplied to it.
   pub fn build(self) -> crate::Result<WebviewWindow<R>> {
+    if let Some(pool) = self
+      .window_builder
+      .manager
+      .try_state::<crate::webview::WebviewPool<R>>()
+    {
+      if let Some(window) = pool.take(
+        &self.window_builder.label,
+        &self.webview_builder.webview_attributes.url,
+      )? {
+        window.show()?;
+        return Ok(window);
+      }
+    }
+
     let (window, webview) = self.window_builder.with_webview(self.webview_builder)?;
     Ok(WebviewWindow { window, webview })
   }
@@ -503,6 +550,38 @@ impl<'a, R: Runtime, M: Manager<R>> WebviewWindowBuilder<'a, R, M> {
     self
   }
 
+  /// The animation played when the window is shown with [`WebviewWindow::show_animated`],
+  /// and when it is created if it is visible.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux:** The slide effects fade the window.
+  #[must_use]
+  pub fn show_animation(mut self, animation: crate::window::WindowAnimation) -> Self {
+    self.window_builder = self.window_builder.show_animation(animation);
+    self
+  }
+
+  /// The animation played when the window is hidden with [`WebviewWindow::hide_animated`].
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux:** The slide effects fade the window.
+  #[must_use]
+  pub fn hide_animation(mut self, animation: crate::window::WindowAnimation) -> Self {
+    self.window_builder = self.window_builder.hide_animation(animation);
+    self
+  }
+
+  /// The type of the window, see [`WindowType`](crate::window::WindowType).
+  ///
+  /// A panel is usually created hidden and shown with [`WebviewWindow::show_at_tray`] or [`WebviewWindow::show_at`].
+  #[must_use]
+  pub fn window_type(mut self, window_type: crate::window::WindowType) -> Self {
+    self.window_builder = self.window_builder.window_type(window_type);
+    self
+  }
+
   /// Forces a theme or uses the system settings if None was provided.
   ///
   /// ## Platform-specific
@@ -776,6 +855,29 @@ impl<'a, R: Runtime, M: Manager<R>> WebviewWindowBuilder<'a, R, M> {
     self
   }
 
+  /// Exposes a read-only JSON value to the pages of the window as `window.__APP_CONTEXT__`,
+  /// defined before the initialization scripts and the page scripts run.
+  ///
+  /// This lets a page know its role or feature flags without invoking a command when it loads.
+  /// The value is frozen and is not updated after the window is created.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     tauri::WebviewWindowBuilder::new(app, "settings", tauri::WebviewUrl::App("settings.html".into()))
+  ///       .injected_context(serde_json::json!({ "role": "settings", "flags": { "beta": true } }))
+  ///       .build()?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  #[must_use]
+  pub fn injected_context(mut self, context: serde_json::Value) -> Self {
+    self.webview_builder = self.webview_builder.injected_context(context);
+    self
+  }
+
   /// Set the user agent for the webview
   #[must_use]
   pub fn user_agent(mut self, user_agent: &str) -> Self {
@@ -895,6 +997,95 @@ impl<'a, R: Runtime, M: Manager<R>> WebviewWindowBuilder<'a, R, M> {
     self.webview_builder = self.webview_builder.browser_extensions_enabled(enabled);
     self
   }
+
+  /// Whether the text fields are spell checked. Defaults to the webview engine behavior.
+  ///
+  /// This option, [`Self::autocorrect`] and [`Self::autofill`] are set as defaults on the text fields
+  /// of the pages on the origin of the initial URL, so attributes set by the page take precedence.
+  /// After navigating to another origin the engine defaults apply.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux**: Also enables spell checking on the WebKitGTK web context,
+  ///   which is shared by all webviews with the same data directory and is not updated by `set_spellcheck`.
+  #[must_use]
+  pub fn spellcheck(mut self, enabled: bool) -> Self {
+    self.webview_builder = self.webview_builder.spellcheck(enabled);
+    self
+  }
+
+  /// The languages used to spell check, such as `en_US`.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux**: Set on the web context, so it applies to all webviews sharing the same data directory.
+  /// - **Windows / macOS / iOS / Android** - Unsupported, the system languages are used.
+  #[must_use]
+  pub fn spellcheck_languages(mut self, languages: Vec<String>) -> Self {
+    self.webview_builder = self.webview_builder.spellcheck_languages(languages);
+    self
+  }
+
+  /// Whether the text fields are automatically corrected while typing. Defaults to the webview engine behavior.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows / Linux / Android** - Unsupported, the engines do not correct text.
+  #[must_use]
+  pub fn autocorrect(mut self, enabled: bool) -> Self {
+    self.webview_builder = self.webview_builder.autocorrect(enabled);
+    self
+  }
+
+  /// Whether the webview suggests previously entered values and saved passwords in forms.
+  /// Defaults to the webview engine behavior.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows**: Also controls WebView2's `IsGeneralAutofillEnabled` and `IsPasswordAutosaveEnabled` settings.
+  #[must_use]
+  pub fn autofill(mut self, enabled: bool) -> Self {
+    self.webview_builder = self.webview_builder.autofill(enabled);
+    self
+  }
+
+  /// Headers sent with the initial page request and the navigations made with [`WebviewWindow::navigate`],
+  /// such as an authorization token for an internal web app.
+  ///
+  /// Navigations started by the page itself, such as following a link, are sent without them.
+  #[must_use]
+  pub fn additional_headers(mut self, headers: http::HeaderMap) -> Self {
+    self.webview_builder = self.webview_builder.additional_headers(headers);
+    self
+  }
+
+  /// Whether the webview renders with the GPU. Defaults to `true`.
+  ///
+  /// Disable it to work around graphics driver bugs, such as blank windows, at the cost of rendering performance.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows**: Passes `--disable-gpu` to the browser process, which is shared by the webviews with the same data directory,
+  /// so they must all use the same value.
+  /// - **macOS / iOS / Android**: Unsupported.
+  #[must_use]
+  pub fn hardware_acceleration(mut self, enabled: bool) -> Self {
+    self.webview_builder = self.webview_builder.hardware_acceleration(enabled);
+    self
+  }
+
+  /// The maximum size in bytes of the HTTP disk cache.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows**: Passes `--disk-cache-size` to the browser process, which is shared by the webviews with the same data directory,
+  /// so they must all use the same value.
+  /// - **macOS / Linux / iOS / Android**: Unsupported.
+  #[must_use]
+  pub fn disk_cache_size(mut self, size: u64) -> Self {
+    self.webview_builder = self.webview_builder.disk_cache_size(size);
+    self
+  }
 }
 
 /// A type that wraps a [`Window`] together with a [`Webview`].
@@ -1321,6 +1512,71 @@ impl<R: Runtime> WebviewWindow<R> {
     self.window.request_user_attention(request_type)
   }
 
+  /// Requests user attention to the window with the given options,
+  /// which can cancel the request when the window is focused, repeat it or cancel it after a duration.
+  ///
+  /// The request replaces the previous one of the window, so repeated notifications are coalesced in a single request.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **macOS:** The request cannot be cancelled, a repeated request stops when cancelled.
+  pub fn request_user_attention_with_options(
+    &self,
+    options: crate::window::UserAttentionOptions,
+  ) -> crate::Result<()> {
+    self.window.request_user_attention_with_options(options)
+  }
+
+  /// Bounces the dock icon on macOS, flashes the window and its taskbar button on Windows
+  /// and sets the urgency hint on Linux until the window is focused.
+  ///
+  /// Use [`crate::AppHandle::is_frontmost`] to skip it when the user is already using the app.
+  pub fn request_user_attention_until_focused(&self) -> crate::Result<()> {
+    self.window.request_user_attention_until_focused()
+  }
+
+  /// Adds the window with the given label as a tab of this window, selecting it.
+  ///
+  /// See [`Window::add_tabbed_window`].
+  pub fn add_tabbed_window(&self, label: &str) -> crate::Result<()> {
+    self.window.add_tabbed_window(label)
+  }
+
+  /// Merges all windows of the app into tabs of this window.
+  ///
+  /// See [`Window::merge_all_windows`].
+  pub fn merge_all_windows(&self) -> crate::Result<()> {
+    self.window.merge_all_windows()
+  }
+
+  /// Selects the tab after this window in its tab group.
+  ///
+  /// See [`Window::select_next_tab`].
+  pub fn select_next_tab(&self) -> crate::Result<()> {
+    self.window.select_next_tab()
+  }
+
+  /// Selects the tab before this window in its tab group.
+  ///
+  /// See [`Window::select_previous_tab`].
+  pub fn select_previous_tab(&self) -> crate::Result<()> {
+    self.window.select_previous_tab()
+  }
+
+  /// Moves this window out of its tab group into a new window.
+  ///
+  /// See [`Window::move_tab_to_new_window`].
+  pub fn move_tab_to_new_window(&self) -> crate::Result<()> {
+    self.window.move_tab_to_new_window()
+  }
+
+  /// Returns the windows in the tab group of this window, in tab order.
+  ///
+  /// See [`Window::tabbed_windows`].
+  pub fn tabbed_windows(&self) -> crate::Result<Vec<Window<R>>> {
+    self.window.tabbed_windows()
+  }
+
   /// Determines if this window should be resizable.
   /// When resizable is set to false, native window's maximize button is automatically disabled.
   pub fn set_resizable(&self, resizable: bool) -> crate::Result<()> {
@@ -1398,6 +1654,57 @@ impl<R: Runtime> WebviewWindow<R> {
     self.window.hide()
   }
 
+  /// Shows this window with an animation, or with the `showAnimation` of its configuration if `None`.
+  /// Shows it directly when it has no animation.
+  ///
+  /// See [`Window::show_animated`](crate::window::Window::show_animated).
+  #[cfg(desktop)]
+  pub fn show_animated(
+    &self,
+    animation: Option<crate::window::WindowAnimation>,
+  ) -> crate::Result<()> {
+    self.window.show_animated(animation)
+  }
+
+  /// Hides this window with an animation, or with the `hideAnimation` of its configuration if `None`.
+  /// Hides it directly when it has no animation.
+  ///
+  /// See [`Window::hide_animated`](crate::window::Window::hide_animated).
+  #[cfg(desktop)]
+  pub fn hide_animated(
+    &self,
+    animation: Option<crate::window::WindowAnimation>,
+  ) -> crate::Result<()> {
+    self.window.hide_animated(animation)
+  }
+
+  /// The type of this window, see [`WindowType`](crate::window::WindowType).
+  #[cfg(desktop)]
+  pub fn window_type(&self) -> crate::window::WindowType {
+    self.window.window_type()
+  }
+
+  /// Moves this window so its `gravity` point is at `position` and shows it.
+  ///
+  /// See [`Window::show_at`](crate::window::Window::show_at).
+  #[cfg(desktop)]
+  pub fn show_at<P: Into<Position>>(
+    &self,
+    position: P,
+    gravity: crate::window::Gravity,
+  ) -> crate::Result<()> {
+    self.window.show_at(position, gravity)
+  }
+
+  /// Shows this window next to the tray icon.
+  ///
+  /// See [`Window::show_at_tray`](crate::window::Window::show_at_tray).
+  #[cfg(all(desktop, feature = "tray-icon"))]
+  #[cfg_attr(docsrs, doc(cfg(feature = "tray-icon")))]
+  pub fn show_at_tray(&self, tray: &crate::tray::TrayIcon<R>) -> crate::Result<()> {
+    self.window.show_at_tray(tray)
+  }
+
   /// Closes this window. It emits [`crate::RunEvent::CloseRequested`] first like a user-initiated close request so you can intercept it.
   pub fn close(&self) -> crate::Result<()> {
     self.window.close()
@@ -1526,11 +1833,27 @@ impl<R: Runtime> WebviewWindow<R> {
     self.window.set_focus()
   }
 
+  /// Injects a synthetic input event in this webview window, for kiosk self-tests and accessibility automation.
+  ///
+  /// The events are untrusted DOM events rather than native input,
+  /// see [`Window::inject_input`] for the limitations.
+  #[cfg(feature = "synthetic-input")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "synthetic-input")))]
+  pub fn inject_input(&self, event: crate::window::InputEvent) -> crate::Result<()> {
+    self.window.inject_input(event)
+  }
+
   /// Sets this window' icon.
   pub fn set_icon(&self, icon: Image<'_>) -> crate::Result<()> {
     self.window.set_icon(icon)
   }
 
+  /// Sets the icons of this window when it uses a light and a dark theme, respectively,
+  /// the icon switching between them when the [theme](Self::theme) of the window changes.
+  pub fn set_icon_pair(&self, light: Image<'_>, dark: Image<'_>) -> crate::Result<()> {
+    self.window.set_icon_pair(light, dark)
+  }
+
   /// Whether to hide the window icon from the taskbar or not.
   ///
   /// ## Platform-specific
@@ -1814,6 +2137,67 @@ impl<R: Runtime> WebviewWindow<R> {
   pub fn clear_all_browsing_data(&self) -> crate::Result<()> {
     self.webview.clear_all_browsing_data()
   }
+
+  /// Clears the HTTP cache, keeping the cookies and the storage, e.g. to force-refresh frequently updated remote content.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **iOS**: Unsupported.
+  pub fn clear_cache(&self) -> crate::Result<()> {
+    self.webview.clear_cache()
+  }
+
+  /// Sets how the webview uses its HTTP cache. [`crate::webview::CacheMode::Disabled`] loads every request from the network.
+  ///
+  /// See [`crate::webview::Webview::set_cache_mode`] for the platform-specific behavior.
+  pub fn set_cache_mode(&self, mode: crate::webview::CacheMode) -> crate::Result<()> {
+    self.webview.set_cache_mode(mode)
+  }
+
+  /// Sets whether the text fields of the current page are spell checked.
+  ///
+  /// The value set with [`WebviewWindowBuilder::spellcheck`] applies again after a navigation.
+  ///
+  /// See [`crate::webview::Webview::set_spellcheck`] for the platform-specific behavior.
+  pub fn set_spellcheck(&self, enabled: bool) -> crate::Result<()> {
+    self.webview.set_spellcheck(enabled)
+  }
+
+  /// Sets whether the text fields of the current page are automatically corrected while typing.
+  ///
+  /// The value set with [`WebviewWindowBuilder::autocorrect`] applies again after a navigation.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows / Linux / Android** - Unsupported, the engines do not correct text.
+  pub fn set_autocorrect(&self, enabled: bool) -> crate::Result<()> {
+    self.webview.set_autocorrect(enabled)
+  }
+
+  /// Sets whether the forms of the current page suggest previously entered values.
+  ///
+  /// The value set with [`WebviewWindowBuilder::autofill`] applies again after a navigation.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows**: The WebView2 autofill settings are only set when the webview is created.
+  pub fn set_autofill(&self, enabled: bool) -> crate::Result<()> {
+    self.webview.set_autofill(enabled)
+  }
+
+  /// Sets the headers sent with the next navigations made with [`Self::navigate`],
+  /// replacing the ones set with [`WebviewWindowBuilder::additional_headers`].
+  pub fn set_additional_headers(&self, headers: http::HeaderMap) -> crate::Result<()> {
+    self.webview.set_additional_headers(headers)
+  }
+
+  /// Sets the regions of the webview, relative to its top left corner,
+  /// where HTML5 drag and drop is used instead of the native file drop handler.
+  ///
+  /// See [`Webview::set_html_drag_drop_regions`].
+  pub fn set_html_drag_drop_regions(&self, regions: Vec<crate::Rect>) -> crate::Result<()> {
+    self.webview.set_html_drag_drop_regions(regions)
+  }
 }
 
 impl<R: Runtime> Listener<R> for WebviewWindow<R> {
@@ -1993,3 +2377,36 @@ impl<R: Runtime> ManagerBase<R> for WebviewWindow<R> {
     self.webview.managed_app_handle()
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    test::{mock_builder, mock_context, noop_assets},
+    utils::config::WindowConfig,
+    Manager, WebviewWindowBuilder,
+  };
+
+  #[test]
+  fn from_config_label() {
+    let mut context = mock_context(noop_assets());
+    context.config_mut().app.windows.push(WindowConfig {
+      label: "settings".into(),
+      title: "Settings".into(),
+      create: false,
+      ..Default::default()
+    });
+    let app = mock_builder().build(context).unwrap();
+    assert!(app.get_webview_window("settings").is_none());
+
+    let window = WebviewWindowBuilder::from_config_label(&app, "settings")
+      .unwrap()
+      .build()
+      .unwrap();
+    assert_eq!(window.label(), "settings");
+
+    assert!(matches!(
+      WebviewWindowBuilder::from_config_label(&app, "missing"),
+      Err(crate::Error::WindowConfigNotFound(label)) if label == "missing"
+    ));
+  }
+}
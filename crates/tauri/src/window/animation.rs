@@ -0,0 +1,263 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Native show and hide animations of the windows.
+
+pub use tauri_utils::config::{WindowAnimation, WindowAnimationEffect};
+
+use crate::{Runtime, Window};
+
+/// The animations configured for a window with `showAnimation` and `hideAnimation`.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Animations {
+  pub show: Option<WindowAnimation>,
+  pub hide: Option<WindowAnimation>,
+}
+
+/// Shows the window with the animation on the main thread, falling back to showing it directly.
+pub(crate) fn show<R: Runtime>(
+  window: &Window<R>,
+  animation: WindowAnimation,
+) -> crate::Result<()> {
+  let window_ = window.clone();
+  window.run_on_main_thread(move || {
+    if let Err(e) = platform::show(&window_, &animation) {
+      log::warn!("failed to animate the window `{}`: {e}", window_.label());
+      let _ = window_.show();
+    }
+  })
+}
+
+/// Hides the window with the animation on the main thread, falling back to hiding it directly.
+pub(crate) fn hide<R: Runtime>(
+  window: &Window<R>,
+  animation: WindowAnimation,
+) -> crate::Result<()> {
+  let window_ = window.clone();
+  window.run_on_main_thread(move || {
+    if let Err(e) = platform::hide(&window_, &animation) {
+      log::warn!("failed to animate the window `{}`: {e}", window_.label());
+      let _ = window_.hide();
+    }
+  })
+}
+
+#[cfg(all(windows, feature = "window-animations"))]
+mod platform {
+  use windows::Win32::UI::WindowsAndMessaging::{
+    AnimateWindow, ANIMATE_WINDOW_FLAGS, AW_ACTIVATE, AW_BLEND, AW_HIDE, AW_HOR_NEGATIVE,
+    AW_HOR_POSITIVE, AW_SLIDE, AW_VER_NEGATIVE, AW_VER_POSITIVE,
+  };
+
+  use super::{WindowAnimation, WindowAnimationEffect};
+  use crate::{Runtime, Window};
+
+  fn flags(effect: WindowAnimationEffect) -> ANIMATE_WINDOW_FLAGS {
+    match effect {
+      WindowAnimationEffect::Fade => AW_BLEND,
+      WindowAnimationEffect::SlideUp => AW_SLIDE | AW_VER_NEGATIVE,
+      WindowAnimationEffect::SlideDown => AW_SLIDE | AW_VER_POSITIVE,
+      WindowAnimationEffect::SlideLeft => AW_SLIDE | AW_HOR_NEGATIVE,
+      WindowAnimationEffect::SlideRight => AW_SLIDE | AW_HOR_POSITIVE,
+    }
+  }
+
+  pub fn show<R: Runtime>(window: &Window<R>, animation: &WindowAnimation) -> crate::Result<()> {
+    let hwnd = window.hwnd()?;
+    // blocks the event loop for the duration of the animation
+    unsafe {
+      AnimateWindow(
+        hwnd,
+        animation.duration as u32,
+        AW_ACTIVATE | flags(animation.effect),
+      )
+    }
+    .map_err(std::io::Error::from)?;
+    // keeps the window state of the runtime in sync
+    window.show()
+  }
+
+  pub fn hide<R: Runtime>(window: &Window<R>, animation: &WindowAnimation) -> crate::Result<()> {
+    let hwnd = window.hwnd()?;
+    unsafe {
+      AnimateWindow(
+        hwnd,
+        animation.duration as u32,
+        AW_HIDE | flags(animation.effect),
+      )
+    }
+    .map_err(std::io::Error::from)?;
+    window.hide()
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use std::time::Duration;
+
+  use objc2::{class, msg_send, runtime::AnyObject};
+  use objc2_foundation::NSRect;
+
+  use super::{WindowAnimation, WindowAnimationEffect};
+  use crate::{Runtime, Window};
+
+  /// The frame of the window moved by the slide effects, by a quarter of its size.
+  fn slide(frame: NSRect, effect: WindowAnimationEffect, sign: f64) -> Option<NSRect> {
+    let (dx, dy) = match effect {
+      WindowAnimationEffect::Fade => return None,
+      // the y axis points upwards
+      WindowAnimationEffect::SlideUp => (0.0, frame.size.height / 4.0),
+      WindowAnimationEffect::SlideDown => (0.0, -frame.size.height / 4.0),
+      WindowAnimationEffect::SlideLeft => (-frame.size.width / 4.0, 0.0),
+      WindowAnimationEffect::SlideRight => (frame.size.width / 4.0, 0.0),
+    };
+    let mut moved = frame;
+    moved.origin.x += dx * sign;
+    moved.origin.y += dy * sign;
+    Some(moved)
+  }
+
+  unsafe fn animate(window: *mut AnyObject, duration: u64, alpha: f64, frame: Option<NSRect>) {
+    let () = msg_send![class!(NSAnimationContext), beginGrouping];
+    let context: *mut AnyObject = msg_send![class!(NSAnimationContext), currentContext];
+    let () = msg_send![context, setDuration: duration as f64 / 1000.0];
+    let animator: *mut AnyObject = msg_send![window, animator];
+    let () = msg_send![animator, setAlphaValue: alpha];
+    if let Some(frame) = frame {
+      let () = msg_send![animator, setFrame: frame, display: true];
+    }
+    let () = msg_send![class!(NSAnimationContext), endGrouping];
+  }
+
+  pub fn show<R: Runtime>(window: &Window<R>, animation: &WindowAnimation) -> crate::Result<()> {
+    let ns_window = window.ns_window()? as *mut AnyObject;
+    let frame: NSRect = unsafe { msg_send![ns_window, frame] };
+    let start = slide(frame, animation.effect, -1.0);
+    unsafe {
+      let () = msg_send![ns_window, setAlphaValue: 0.0f64];
+      if let Some(start) = start {
+        let () = msg_send![ns_window, setFrame: start, display: false];
+      }
+    }
+    window.show()?;
+    unsafe { animate(ns_window, animation.duration, 1.0, start.map(|_| frame)) };
+    Ok(())
+  }
+
+  pub fn hide<R: Runtime>(window: &Window<R>, animation: &WindowAnimation) -> crate::Result<()> {
+    let ns_window = window.ns_window()? as *mut AnyObject;
+    let frame: NSRect = unsafe { msg_send![ns_window, frame] };
+    unsafe {
+      animate(
+        ns_window,
+        animation.duration,
+        0.0,
+        slide(frame, animation.effect, 1.0),
+      )
+    };
+
+    // hides the window once the animation completed and restores its opacity and frame for the next show
+    let window = window.clone();
+    let duration = Duration::from_millis(animation.duration);
+    std::thread::spawn(move || {
+      std::thread::sleep(duration);
+      let window_ = window.clone();
+      let _ = window.run_on_main_thread(move || {
+        let _ = window_.hide();
+        if let Ok(ns_window) = window_.ns_window() {
+          let ns_window = ns_window as *mut AnyObject;
+          unsafe {
+            let () = msg_send![ns_window, setAlphaValue: 1.0f64];
+            let () = msg_send![ns_window, setFrame: frame, display: false];
+          }
+        }
+      });
+    });
+    Ok(())
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use std::time::{Duration, Instant};
+
+  use gtk::{glib::ControlFlow, prelude::*};
+
+  use super::WindowAnimation;
+  use crate::{Runtime, Window};
+
+  /// The interval between two steps of the fade.
+  const FRAME: Duration = Duration::from_millis(16);
+
+  /// Fades the window, windows cannot be moved on Wayland so the slide effects fade too.
+  fn fade(
+    gtk_window: gtk::ApplicationWindow,
+    duration: u64,
+    visible: bool,
+    done: impl FnOnce() + 'static,
+  ) {
+    let started = Instant::now();
+    let duration = Duration::from_millis(duration.max(1));
+    let mut done = Some(done);
+    gtk::glib::timeout_add_local(FRAME, move || {
+      let progress = (started.elapsed().as_secs_f64() / duration.as_secs_f64()).min(1.0);
+      gtk_window.set_opacity(if visible { progress } else { 1.0 - progress });
+      if progress < 1.0 {
+        return ControlFlow::Continue;
+      }
+      if let Some(done) = done.take() {
+        done();
+      }
+      ControlFlow::Break
+    });
+  }
+
+  pub fn show<R: Runtime>(window: &Window<R>, animation: &WindowAnimation) -> crate::Result<()> {
+    let gtk_window = window.gtk_window()?;
+    gtk_window.set_opacity(0.0);
+    window.show()?;
+    fade(gtk_window, animation.duration, true, || {});
+    Ok(())
+  }
+
+  pub fn hide<R: Runtime>(window: &Window<R>, animation: &WindowAnimation) -> crate::Result<()> {
+    let gtk_window = window.gtk_window()?;
+    let window = window.clone();
+    let gtk_window_ = gtk_window.clone();
+    fade(gtk_window, animation.duration, false, move || {
+      let _ = window.hide();
+      // restores the opacity for the next show
+      gtk_window_.set_opacity(1.0);
+    });
+    Ok(())
+  }
+}
+
+#[cfg(all(windows, not(feature = "window-animations")))]
+mod platform {
+  use super::WindowAnimation;
+  use crate::{Runtime, Window};
+
+  fn unsupported() -> crate::Error {
+    std::io::Error::new(
+      std::io::ErrorKind::Unsupported,
+      "window animations require the `window-animations` feature on Windows",
+    )
+    .into()
+  }
+
+  pub fn show<R: Runtime>(_window: &Window<R>, _animation: &WindowAnimation) -> crate::Result<()> {
+    Err(unsupported())
+  }
+
+  pub fn hide<R: Runtime>(_window: &Window<R>, _animation: &WindowAnimation) -> crate::Result<()> {
+    Err(unsupported())
+  }
+}
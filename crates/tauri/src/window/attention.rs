@@ -0,0 +1,169 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Requests for the attention of the user that repeat, expire and stop when the window is focused.
+
+use std::time::{Duration, Instant};
+
+use crate::{Runtime, UserAttentionType, Window};
+
+/// The options of [`Window::request_user_attention_with_options`].
+#[derive(Debug, Clone)]
+pub struct UserAttentionOptions {
+  request_type: UserAttentionType,
+  repeat_interval: Option<Duration>,
+  duration: Option<Duration>,
+  cancel_on_focus: bool,
+}
+
+impl UserAttentionOptions {
+  /// Requests the attention with the given type, cancelled when the window is focused.
+  pub fn new(request_type: UserAttentionType) -> Self {
+    Self {
+      request_type,
+      repeat_interval: None,
+      duration: None,
+      cancel_on_focus: true,
+    }
+  }
+
+  /// Repeats the request at the given interval until it is cancelled,
+  /// e.g. to keep bouncing the dock icon on macOS with [`UserAttentionType::Informational`].
+  #[must_use]
+  pub fn repeat(mut self, interval: Duration) -> Self {
+    self.repeat_interval.replace(interval);
+    self
+  }
+
+  /// Cancels the request after the given duration.
+  #[must_use]
+  pub fn duration(mut self, duration: Duration) -> Self {
+    self.duration.replace(duration);
+    self
+  }
+
+  /// Whether the request is cancelled when the window is focused. Defaults to `true`.
+  #[must_use]
+  pub fn cancel_on_focus(mut self, cancel: bool) -> Self {
+    self.cancel_on_focus = cancel;
+    self
+  }
+}
+
+/// The attention request of a window, replaced by its next request.
+#[derive(Debug, Default)]
+pub(crate) struct Attention {
+  generation: u64,
+  cancel_on_focus: bool,
+}
+
+/// Requests the attention, replacing the previous request of the window.
+pub(crate) fn request<R: Runtime>(
+  window: &Window<R>,
+  options: UserAttentionOptions,
+) -> crate::Result<()> {
+  let generation = {
+    let mut attention = window.attention.lock().unwrap();
+    attention.generation += 1;
+    attention.cancel_on_focus = options.cancel_on_focus;
+    attention.generation
+  };
+  window
+    .window
+    .dispatcher
+    .request_user_attention(Some(options.request_type))?;
+
+  if options.repeat_interval.is_none() && options.duration.is_none() {
+    return Ok(());
+  }
+
+  let window = window.clone();
+  crate::async_runtime::spawn(async move {
+    let started = Instant::now();
+    while let Some(wait) = next_wait(&options, started.elapsed()) {
+      tokio::time::sleep(wait).await;
+      if !is_current(&window, generation) {
+        return;
+      }
+      if next_wait(&options, started.elapsed()).is_some() {
+        let _ = window
+          .window
+          .dispatcher
+          .request_user_attention(Some(options.request_type));
+      }
+    }
+    if is_current(&window, generation) {
+      let _ = cancel(&window);
+    }
+  });
+
+  Ok(())
+}
+
+/// Cancels the request of the window.
+pub(crate) fn cancel<R: Runtime>(window: &Window<R>) -> crate::Result<()> {
+  let mut attention = window.attention.lock().unwrap();
+  attention.generation += 1;
+  attention.cancel_on_focus = false;
+  drop(attention);
+  window
+    .window
+    .dispatcher
+    .request_user_attention(None)
+    .map_err(Into::into)
+}
+
+/// Cancels the request of the window if it was made with [`UserAttentionOptions::cancel_on_focus`].
+pub(crate) fn on_focused<R: Runtime>(window: &Window<R>) {
+  let cancel_on_focus = window.attention.lock().unwrap().cancel_on_focus;
+  if cancel_on_focus {
+    if let Err(e) = cancel(window) {
+      log::debug!(
+        "failed to cancel the attention request of the window `{}`: {e}",
+        window.label()
+      );
+    }
+  }
+}
+
+fn is_current<R: Runtime>(window: &Window<R>, generation: u64) -> bool {
+  window.attention.lock().unwrap().generation == generation
+}
+
+/// How long to wait before repeating the request, `None` once its duration has elapsed.
+fn next_wait(options: &UserAttentionOptions, elapsed: Duration) -> Option<Duration> {
+  let remaining = options
+    .duration
+    .map(|duration| duration.saturating_sub(elapsed));
+  match (options.repeat_interval, remaining) {
+    (_, Some(Duration::ZERO)) => None,
+    (Some(interval), Some(remaining)) => Some(interval.min(remaining)),
+    (interval, remaining) => interval.or(remaining),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn waits_until_the_duration_elapses() {
+    let secs = Duration::from_secs;
+    let options = UserAttentionOptions::new(UserAttentionType::Informational);
+    assert_eq!(next_wait(&options, secs(0)), None);
+
+    let repeat = options.clone().repeat(secs(2));
+    assert_eq!(next_wait(&repeat, secs(0)), Some(secs(2)));
+    assert_eq!(next_wait(&repeat, secs(100)), Some(secs(2)));
+
+    let expiring = options.clone().duration(secs(5));
+    assert_eq!(next_wait(&expiring, secs(0)), Some(secs(5)));
+    assert_eq!(next_wait(&expiring, secs(5)), None);
+
+    let both = options.repeat(secs(2)).duration(secs(5));
+    assert_eq!(next_wait(&both, secs(0)), Some(secs(2)));
+    assert_eq!(next_wait(&both, secs(4)), Some(secs(1)));
+    assert_eq!(next_wait(&both, secs(6)), None);
+  }
+}
@@ -0,0 +1,218 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Synthetic input events injected in the webviews of a window.
+//!
+//! The events are untrusted DOM events, see the limitations of [`crate::Window::inject_input`].
+
+use serde::Serialize;
+use serialize_to_javascript::{default_template, DefaultTemplate, Template};
+
+use crate::{LogicalPosition, Runtime, Webview};
+
+/// A mouse button of a synthetic [`InputEvent`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MouseButton {
+  /// The primary button.
+  #[default]
+  Left,
+  /// The auxiliary button, usually the wheel.
+  Middle,
+  /// The secondary button.
+  Right,
+}
+
+/// The modifier keys held during a synthetic [`InputEvent`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Modifiers {
+  /// The shift key.
+  pub shift: bool,
+  /// The control key.
+  pub ctrl: bool,
+  /// The alt or option key.
+  pub alt: bool,
+  /// The Windows or command key.
+  pub meta: bool,
+}
+
+/// A synthetic input event injected with [`crate::Window::inject_input`].
+///
+/// The event is dispatched as a DOM event whose `isTrusted` property is `false`, not as native input.
+///
+/// Positions are in logical pixels relative to the top-left corner of the window content.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum InputEvent {
+  /// A key is pressed.
+  KeyDown {
+    /// The key value, as in the DOM `KeyboardEvent.key` property, such as `a` or `Enter`.
+    key: String,
+    /// The physical key, as in the DOM `KeyboardEvent.code` property, such as `KeyA` or `Enter`.
+    code: String,
+    /// The held modifier keys.
+    modifiers: Modifiers,
+  },
+  /// A key is released.
+  KeyUp {
+    /// The key value, as in the DOM `KeyboardEvent.key` property, such as `a` or `Enter`.
+    key: String,
+    /// The physical key, as in the DOM `KeyboardEvent.code` property, such as `KeyA` or `Enter`.
+    code: String,
+    /// The held modifier keys.
+    modifiers: Modifiers,
+  },
+  /// Text is typed in the focused element, one key press per character.
+  Text {
+    /// The typed text.
+    text: String,
+  },
+  /// The mouse cursor moves.
+  MouseMove {
+    /// The position of the cursor.
+    position: LogicalPosition<f64>,
+    /// The held modifier keys.
+    modifiers: Modifiers,
+  },
+  /// A mouse button is pressed.
+  MouseDown {
+    /// The position of the cursor.
+    position: LogicalPosition<f64>,
+    /// The pressed button.
+    button: MouseButton,
+    /// The held modifier keys.
+    modifiers: Modifiers,
+  },
+  /// A mouse button is released.
+  MouseUp {
+    /// The position of the cursor.
+    position: LogicalPosition<f64>,
+    /// The released button.
+    button: MouseButton,
+    /// The held modifier keys.
+    modifiers: Modifiers,
+  },
+  /// A mouse button is pressed and released, triggering a click or context menu event.
+  Click {
+    /// The position of the cursor.
+    position: LogicalPosition<f64>,
+    /// The clicked button.
+    button: MouseButton,
+    /// The held modifier keys.
+    modifiers: Modifiers,
+  },
+  /// The mouse wheel is scrolled.
+  #[serde(rename_all = "camelCase")]
+  Wheel {
+    /// The position of the cursor.
+    position: LogicalPosition<f64>,
+    /// The horizontal scroll amount in pixels.
+    delta_x: f64,
+    /// The vertical scroll amount in pixels.
+    delta_y: f64,
+    /// The held modifier keys.
+    modifiers: Modifiers,
+  },
+}
+
+impl InputEvent {
+  fn position_mut(&mut self) -> Option<&mut LogicalPosition<f64>> {
+    match self {
+      Self::MouseMove { position, .. }
+      | Self::MouseDown { position, .. }
+      | Self::MouseUp { position, .. }
+      | Self::Click { position, .. }
+      | Self::Wheel { position, .. } => Some(position),
+      Self::KeyDown { .. } | Self::KeyUp { .. } | Self::Text { .. } => None,
+    }
+  }
+}
+
+/// Finds the webview receiving the event and translates the event position to the webview coordinates.
+///
+/// Keyboard events go to the topmost webview, mouse events to the topmost webview under the cursor.
+pub(crate) fn route<R: Runtime>(
+  mut webviews: Vec<Webview<R>>,
+  is_webview_window: bool,
+  scale_factor: f64,
+  mut event: InputEvent,
+) -> crate::Result<Option<(Webview<R>, InputEvent)>> {
+  let Some(position) = event.position_mut() else {
+    return Ok(webviews.pop().map(|w| (w, event)));
+  };
+  // the webview of a webview window fills the window content
+  if is_webview_window {
+    return Ok(webviews.pop().map(|w| (w, event)));
+  }
+
+  // webviews added last are drawn on top
+  for webview in webviews.into_iter().rev() {
+    let bounds = webview.bounds()?;
+    let origin = bounds.position.to_logical::<f64>(scale_factor);
+    let size = bounds.size.to_logical::<f64>(scale_factor);
+    let x = position.x - origin.x;
+    let y = position.y - origin.y;
+    if (0.0..size.width).contains(&x) && (0.0..size.height).contains(&y) {
+      *position = LogicalPosition::new(x, y);
+      return Ok(Some((webview, event)));
+    }
+  }
+  Ok(None)
+}
+
+/// The script dispatching the event in the webview.
+pub(crate) fn script(event: &InputEvent) -> crate::Result<String> {
+  #[derive(Template)]
+  #[default_template("./scripts/input.js")]
+  struct Input<'a> {
+    event: &'a InputEvent,
+  }
+
+  Ok(
+    Input { event }
+      .render_default(&Default::default())?
+      .into_string(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn serializes_events() {
+    assert_eq!(
+      serde_json::to_value(InputEvent::Click {
+        position: LogicalPosition::new(10.0, 20.5),
+        button: MouseButton::Right,
+        modifiers: Modifiers {
+          ctrl: true,
+          ..Default::default()
+        },
+      })
+      .unwrap(),
+      serde_json::json!({
+        "type": "click",
+        "position": { "x": 10.0, "y": 20.5 },
+        "button": "right",
+        "modifiers": { "shift": false, "ctrl": true, "alt": false, "meta": false }
+      })
+    );
+    assert_eq!(
+      serde_json::to_value(InputEvent::Wheel {
+        position: LogicalPosition::new(0.0, 0.0),
+        delta_x: 0.0,
+        delta_y: 120.0,
+        modifiers: Modifiers::default(),
+      })
+      .unwrap()["deltaY"],
+      120.0
+    );
+    assert_eq!(
+      serde_json::to_value(InputEvent::Text { text: "hi".into() }).unwrap(),
+      serde_json::json!({ "type": "text", "text": "hi" })
+    );
+  }
+}
@@ -4,6 +4,14 @@
 
 //! The Tauri window types and functions.
 
+#[cfg(desktop)]
+mod animation;
+#[cfg(desktop)]
+mod attention;
+#[cfg(feature = "synthetic-input")]
+mod input;
+#[cfg(desktop)]
+mod panel;
 pub(crate) mod plugin;
 
 use tauri_runtime::{
@@ -15,6 +23,17 @@ pub use tauri_utils::{config::Color, WindowEffect as Effect, WindowEffectState a
 #[cfg(desktop)]
 pub use crate::runtime::ProgressBarStatus;
 
+#[cfg(desktop)]
+pub use animation::{WindowAnimation, WindowAnimationEffect};
+#[cfg(desktop)]
+pub use attention::UserAttentionOptions;
+#[cfg(desktop)]
+pub use panel::{Gravity, WindowType};
+
+#[cfg(feature = "synthetic-input")]
+#[cfg_attr(docsrs, doc(cfg(feature = "synthetic-input")))]
+pub use input::{InputEvent, Modifiers, MouseButton};
+
 use crate::{
   app::AppHandle,
   event::{Event, EventId, EventTarget},
@@ -113,7 +132,7 @@ macro_rules! unstable_struct {
 unstable_struct!(
   #[doc = "A builder for a window managed by Tauri."]
   struct WindowBuilder<'a, R: Runtime, M: Manager<R>> {
-    manager: &'a M,
+    pub(crate) manager: &'a M,
     pub(crate) label: String,
     pub(crate) window_builder:
       <R::WindowDispatcher as WindowDispatch<EventLoopMessage>>::WindowBuilder,
@@ -122,6 +141,12 @@ unstable_struct!(
     #[cfg(desktop)]
     on_menu_event: Option<crate::app::GlobalMenuEventListener<Window<R>>>,
     window_effects: Option<WindowEffectsConfig>,
+    #[cfg(desktop)]
+    visible: bool,
+    #[cfg(desktop)]
+    animations: animation::Animations,
+    #[cfg(desktop)]
+    window_type: WindowType,
   }
 );
 
@@ -208,6 +233,12 @@ async fn create_window(app: tauri::AppHandle) {
       #[cfg(desktop)]
       on_menu_event: None,
       window_effects: None,
+      #[cfg(desktop)]
+      visible: true,
+      #[cfg(desktop)]
+      animations: Default::default(),
+      #[cfg(desktop)]
+      window_type: WindowType::Normal,
     }
   }
 
@@ -254,6 +285,15 @@ async fn reopen_window(app: tauri::AppHandle) {
       menu: None,
       #[cfg(desktop)]
       on_menu_event: None,
+      #[cfg(desktop)]
+      visible: config.visible,
+      #[cfg(desktop)]
+      animations: animation::Animations {
+        show: config.show_animation,
+        hide: config.hide_animation,
+      },
+      #[cfg(desktop)]
+      window_type: config.window_type,
     };
 
     #[cfg(desktop)]
@@ -268,6 +308,37 @@ async fn reopen_window(app: tauri::AppHandle) {
     Ok(builder)
   }
 
+  /// Initializes a window builder from the entry of `tauri.conf.json > app > windows` with the given label,
+  /// including the windows configured with `"create": false` to be created later.
+  ///
+  /// See [`Self::from_config`] for the known issues.
+  ///
+  /// # Examples
+  ///
+  #[cfg_attr(
+    feature = "unstable",
+    doc = r####"
+```
+#[tauri::command]
+async fn open_settings(app: tauri::AppHandle) -> tauri::Result<()> {
+  tauri::window::WindowBuilder::from_config_label(&app, "settings")?.build()?;
+  Ok(())
+}
+```
+  "####
+  )]
+  pub fn from_config_label(manager: &'a M, label: &str) -> crate::Result<Self> {
+    let config = manager
+      .config()
+      .app
+      .windows
+      .iter()
+      .find(|config| config.label == label)
+      .cloned()
+      .ok_or_else(|| crate::Error::WindowConfigNotFound(label.to_string()))?;
+    Self::from_config(manager, &config)
+  }
+
   /// Registers a global menu event listener.
   ///
   /// Note that this handler is called for any menu event,
@@ -341,7 +412,25 @@ tauri::Builder::default()
     self,
     webview: Option<PendingWebview<EventLoopMessage, R>>,
   ) -> crate::Result<Window<R>> {
-    let mut pending = PendingWindow::new(self.window_builder.clone(), self.label.clone())?;
+    // a window shown with an animation is created hidden
+    #[cfg(desktop)]
+    let animate_show = self.visible && self.animations.show.is_some();
+    #[cfg(desktop)]
+    let window_builder = if animate_show {
+      self.window_builder.clone().visible(false)
+    } else {
+      self.window_builder.clone()
+    };
+    #[cfg(desktop)]
+    let window_builder = if self.window_type == WindowType::Panel {
+      window_builder.skip_taskbar(true).always_on_top(true)
+    } else {
+      window_builder
+    };
+    #[cfg(not(desktop))]
+    let window_builder = self.window_builder.clone();
+
+    let mut pending = PendingWindow::new(window_builder, self.label.clone())?;
     if let Some(webview) = webview {
       pending.set_webview(webview);
     }
@@ -396,6 +485,18 @@ tauri::Builder::default()
       crate::vibrancy::set_window_effects(&window, Some(effects))?;
     }
 
+    #[cfg(desktop)]
+    {
+      *window.window_type.lock().unwrap() = self.window_type;
+      if self.window_type == WindowType::Panel {
+        panel::setup(&window)?;
+      }
+      *window.animations.lock().unwrap() = self.animations;
+      if animate_show {
+        window.show_animated(None)?;
+      }
+    }
+
     let app_manager = self.manager.manager_owned();
     let window_label = window.label().to_string();
     // run on the main thread to fix a deadlock on webview.eval if the tracing feature is enabled
@@ -556,6 +657,39 @@ impl<'a, R: Runtime, M: Manager<R>> WindowBuilder<'a, R, M> {
   #[must_use]
   pub fn visible(mut self, visible: bool) -> Self {
     self.window_builder = self.window_builder.visible(visible);
+    self.visible = visible;
+    self
+  }
+
+  /// The animation played when the window is shown with [`Window::show_animated`],
+  /// and when it is created if it is visible.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux:** The slide effects fade the window.
+  #[must_use]
+  pub fn show_animation(mut self, animation: WindowAnimation) -> Self {
+    self.animations.show.replace(animation);
+    self
+  }
+
+  /// The animation played when the window is hidden with [`Window::hide_animated`].
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux:** The slide effects fade the window.
+  #[must_use]
+  pub fn hide_animation(mut self, animation: WindowAnimation) -> Self {
+    self.animations.hide.replace(animation);
+    self
+  }
+
+  /// The type of the window, see [`WindowType`].
+  ///
+  /// A [`WindowType::Panel`] is usually created hidden and shown with [`Window::show_at_tray`] or [`Window::show_at`].
+  #[must_use]
+  pub fn window_type(mut self, window_type: WindowType) -> Self {
+    self.window_type = window_type;
     self
   }
 
@@ -859,6 +993,21 @@ pub struct Window<R: Runtime> {
   #[cfg(desktop)]
   pub(crate) menu: Arc<Mutex<Option<WindowMenu<R>>>>,
   pub(crate) resources_table: Arc<Mutex<ResourceTable>>,
+  /// The animations configured for this window.
+  #[cfg(desktop)]
+  pub(crate) animations: Arc<Mutex<animation::Animations>>,
+  /// The type of this window.
+  #[cfg(desktop)]
+  pub(crate) window_type: Arc<Mutex<WindowType>>,
+  /// The icons of this window for the light and dark themes.
+  #[cfg(desktop)]
+  pub(crate) icon_pair: crate::image::theme::SharedIconPair,
+  /// The current attention request of this window.
+  #[cfg(desktop)]
+  pub(crate) attention: Arc<Mutex<attention::Attention>>,
+  /// The webview layout of this window, applied again when the window is resized.
+  #[cfg(any(test, all(desktop, feature = "unstable")))]
+  pub(crate) layout: Arc<Mutex<Option<crate::webview::Layout>>>,
 }
 
 impl<R: Runtime> std::fmt::Debug for Window<R> {
@@ -896,6 +1045,16 @@ impl<R: Runtime> Clone for Window<R> {
       #[cfg(desktop)]
       menu: self.menu.clone(),
       resources_table: self.resources_table.clone(),
+      #[cfg(desktop)]
+      animations: self.animations.clone(),
+      #[cfg(desktop)]
+      window_type: self.window_type.clone(),
+      #[cfg(desktop)]
+      icon_pair: self.icon_pair.clone(),
+      #[cfg(desktop)]
+      attention: self.attention.clone(),
+      #[cfg(any(test, all(desktop, feature = "unstable")))]
+      layout: self.layout.clone(),
     }
   }
 }
@@ -949,6 +1108,21 @@ impl<'de, R: Runtime> CommandArg<'de, R> for Window<R> {
   }
 }
 
+/// Sets the bounds of the webviews of the layout, skipping the ones closed since it was set.
+#[cfg(any(test, all(desktop, feature = "unstable")))]
+fn apply_layout<R: Runtime>(
+  layout: &crate::webview::Layout,
+  size: crate::LogicalSize<f64>,
+  webviews: &[Webview<R>],
+) -> crate::Result<()> {
+  for (label, bounds) in layout.regions(size) {
+    if let Some(webview) = webviews.iter().find(|webview| webview.label() == label) {
+      webview.set_bounds(bounds)?;
+    }
+  }
+  Ok(())
+}
+
 /// Base window functions.
 impl<R: Runtime> Window<R> {
   /// Create a new window that is attached to the manager.
@@ -965,6 +1139,16 @@ impl<R: Runtime> Window<R> {
       #[cfg(desktop)]
       menu: Arc::new(std::sync::Mutex::new(menu)),
       resources_table: Default::default(),
+      #[cfg(desktop)]
+      animations: Default::default(),
+      #[cfg(desktop)]
+      window_type: Default::default(),
+      #[cfg(desktop)]
+      icon_pair: Default::default(),
+      #[cfg(desktop)]
+      attention: Default::default(),
+      #[cfg(any(test, all(desktop, feature = "unstable")))]
+      layout: Default::default(),
     }
   }
 
@@ -999,6 +1183,89 @@ impl<R: Runtime> Window<R> {
     rx.recv().unwrap()
   }
 
+  /// Arranges the webviews of this window in the regions of the given layout.
+  ///
+  /// The layout is applied again when the window is resized, so the regions keep their proportions.
+  /// The webviews of the window missing from the layout are hidden, so switching layouts can implement tabs.
+  /// The regions never overlap, so the layout does not change the stacking order of the webviews.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use tauri::{webview::{Layout, WebviewBuilder}, LogicalPosition, LogicalSize, WebviewUrl};
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let window = tauri::window::WindowBuilder::new(app, "main").build()?;
+  ///     for label in ["sidebar", "content"] {
+  ///       let url = WebviewUrl::App(format!("{label}.html").into());
+  ///       window.add_child(
+  ///         WebviewBuilder::new(label, url),
+  ///         LogicalPosition::new(0, 0),
+  ///         LogicalSize::new(1, 1),
+  ///       )?;
+  ///     }
+  ///     window.set_layout(&Layout::row(
+  ///       0.3,
+  ///       Layout::webview("sidebar"),
+  ///       Layout::webview("content"),
+  ///     ))?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  ///
+  /// # Errors
+  ///
+  /// - [`crate::Error::WebviewNotFound`] if a webview of the layout is not a webview of this window.
+  /// - [`crate::Error::DuplicateLayoutWebview`] if a webview is in several regions of the layout.
+  #[cfg(any(test, all(desktop, feature = "unstable")))]
+  #[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "unstable"))))]
+  pub fn set_layout(&self, layout: &crate::webview::Layout) -> crate::Result<()> {
+    let webviews = self.webviews();
+    let labels = layout.labels();
+    if let Some(label) = layout.duplicate_label() {
+      return Err(crate::Error::DuplicateLayoutWebview(label.to_string()));
+    }
+    if !labels
+      .iter()
+      .all(|label| webviews.iter().any(|webview| webview.label() == *label))
+    {
+      return Err(crate::Error::WebviewNotFound);
+    }
+
+    let size = self.inner_size()?.to_logical::<f64>(self.scale_factor()?);
+    self.layout.lock().unwrap().replace(layout.clone());
+    apply_layout(layout, size, &webviews)?;
+    for webview in &webviews {
+      if labels.contains(&webview.label()) {
+        // the layout resizes the webviews itself
+        webview.set_auto_resize(false)?;
+        webview.show()?;
+      } else {
+        webview.hide()?;
+      }
+    }
+    Ok(())
+  }
+
+  /// Applies the layout of the window to its new inner size.
+  #[cfg(any(test, all(desktop, feature = "unstable")))]
+  pub(crate) fn on_layout_resized(
+    &self,
+    size: PhysicalSize<u32>,
+    scale_factor: f64,
+  ) -> crate::Result<()> {
+    let layout = self.layout.lock().unwrap().clone();
+    match layout {
+      Some(layout) => apply_layout(
+        &layout,
+        size.to_logical::<f64>(scale_factor),
+        &self.webviews(),
+      ),
+      None => Ok(()),
+    }
+  }
+
   /// List of webviews associated with this window.
   pub fn webviews(&self) -> Vec<Webview<R>> {
     self
@@ -1578,6 +1845,8 @@ impl<R: Runtime> Window<R> {
   /// Providing `None` will unset the request for user attention. Unsetting the request for
   /// user attention might not be done automatically by the WM when the window receives input.
   ///
+  /// This replaces the request made with [`Self::request_user_attention_with_options`].
+  ///
   /// ## Platform-specific
   ///
   /// - **macOS:** `None` has no effect.
@@ -1586,11 +1855,216 @@ impl<R: Runtime> Window<R> {
     &self,
     request_type: Option<UserAttentionType>,
   ) -> crate::Result<()> {
-    self
-      .window
-      .dispatcher
-      .request_user_attention(request_type)
-      .map_err(Into::into)
+    match request_type {
+      Some(request_type) => self.request_user_attention_with_options(
+        UserAttentionOptions::new(request_type).cancel_on_focus(false),
+      ),
+      None => attention::cancel(self),
+    }
+  }
+
+  /// Requests user attention to the window with the given options,
+  /// which can cancel the request when the window is focused, repeat it or cancel it after a duration.
+  ///
+  /// The request replaces the previous one of the window, so repeated notifications are coalesced in a single request.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use std::time::Duration;
+  /// use tauri::{window::UserAttentionOptions, Manager, UserAttentionType};
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let window = app.get_webview_window("main").unwrap();
+  ///     window.request_user_attention_with_options(
+  ///       UserAttentionOptions::new(UserAttentionType::Informational)
+  ///         .repeat(Duration::from_secs(5))
+  ///         .duration(Duration::from_secs(60)),
+  ///     )?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **macOS:** The request cannot be cancelled, a repeated request stops when cancelled.
+  pub fn request_user_attention_with_options(
+    &self,
+    options: UserAttentionOptions,
+  ) -> crate::Result<()> {
+    attention::request(self, options)
+  }
+
+  /// Bounces the dock icon on macOS, flashes the window and its taskbar button on Windows
+  /// and sets the urgency hint on Linux until the window is focused.
+  ///
+  /// Use [`crate::AppHandle::is_frontmost`] to skip it when the user is already using the app.
+  pub fn request_user_attention_until_focused(&self) -> crate::Result<()> {
+    self.request_user_attention_with_options(UserAttentionOptions::new(UserAttentionType::Critical))
+  }
+
+  /// Cancels the attention request made with [`UserAttentionOptions::cancel_on_focus`] when the window is focused.
+  pub(crate) fn on_focused(&self) {
+    attention::on_focused(self)
+  }
+
+  /// Adds the window with the given label as a tab of this window, selecting it.
+  ///
+  /// Both windows should share the same [`WindowBuilder::tabbing_identifier`]
+  /// so the system keeps them grouped. Fails with [`crate::Error::CannotTabWindowWithItself`]
+  /// if `label` is the label of this window.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows / Linux:** Unsupported, does nothing.
+  pub fn add_tabbed_window(&self, label: &str) -> crate::Result<()> {
+    if label == self.label() {
+      return Err(crate::Error::CannotTabWindowWithItself(label.into()));
+    }
+    let tab = self
+      .manager
+      .get_window(label)
+      .ok_or(crate::Error::WindowNotFound)?;
+    #[cfg(target_os = "macos")]
+    self.with_ns_window(move |ns_window| {
+      if let Ok(tab) = tab.ns_window() {
+        let tab = tab.cast::<objc2::runtime::AnyObject>();
+        // NSWindowAbove
+        let _: () = unsafe { objc2::msg_send![ns_window, addTabbedWindow: tab, ordered: 1isize] };
+      }
+    })?;
+    #[cfg(not(target_os = "macos"))]
+    let _ = tab;
+    Ok(())
+  }
+
+  /// Merges all windows of the app into tabs of this window.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows / Linux:** Unsupported, does nothing.
+  pub fn merge_all_windows(&self) -> crate::Result<()> {
+    #[cfg(target_os = "macos")]
+    self.send_tab_action(objc2::sel!(mergeAllWindows:))?;
+    Ok(())
+  }
+
+  /// Selects the tab after this window in its tab group.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows / Linux:** Unsupported, does nothing.
+  pub fn select_next_tab(&self) -> crate::Result<()> {
+    #[cfg(target_os = "macos")]
+    self.send_tab_action(objc2::sel!(selectNextTab:))?;
+    Ok(())
+  }
+
+  /// Selects the tab before this window in its tab group.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows / Linux:** Unsupported, does nothing.
+  pub fn select_previous_tab(&self) -> crate::Result<()> {
+    #[cfg(target_os = "macos")]
+    self.send_tab_action(objc2::sel!(selectPreviousTab:))?;
+    Ok(())
+  }
+
+  /// Moves this window out of its tab group into a new window.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows / Linux:** Unsupported, does nothing.
+  pub fn move_tab_to_new_window(&self) -> crate::Result<()> {
+    #[cfg(target_os = "macos")]
+    self.send_tab_action(objc2::sel!(moveTabToNewWindow:))?;
+    Ok(())
+  }
+
+  /// Returns the windows in the tab group of this window, in tab order,
+  /// or only this window if it is not tabbed.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows / Linux:** Unsupported, only returns this window.
+  pub fn tabbed_windows(&self) -> crate::Result<Vec<Window<R>>> {
+    #[cfg(target_os = "macos")]
+    {
+      let windows = self.manager.windows();
+      let tabs = self.with_ns_window(|ns_window| unsafe {
+        let tabs: *const objc2::runtime::AnyObject = objc2::msg_send![ns_window, tabbedWindows];
+        let Some(tabs) = tabs.as_ref() else {
+          return Vec::new();
+        };
+        let count: usize = objc2::msg_send![tabs, count];
+        (0..count)
+          .map(|i| {
+            let tab: *mut std::ffi::c_void = objc2::msg_send![tabs, objectAtIndex: i];
+            tab as usize
+          })
+          .collect::<Vec<_>>()
+      })?;
+      if !tabs.is_empty() {
+        return Ok(
+          tabs
+            .into_iter()
+            .filter_map(|tab| {
+              windows
+                .values()
+                .find(|w| {
+                  w.ns_window()
+                    .is_ok_and(|ns_window| ns_window as usize == tab)
+                })
+                .cloned()
+            })
+            .collect(),
+        );
+      }
+    }
+    Ok(vec![self.clone()])
+  }
+
+  /// The address of the tab group of this window if this window is the selected tab of the group.
+  #[cfg(target_os = "macos")]
+  pub(crate) fn selected_tab_group(&self) -> crate::Result<Option<usize>> {
+    self.with_ns_window(|ns_window| unsafe {
+      let group: *const objc2::runtime::AnyObject = objc2::msg_send![ns_window, tabGroup];
+      if group.is_null() {
+        return None;
+      }
+      let selected: *const objc2::runtime::AnyObject = objc2::msg_send![group, selectedWindow];
+      std::ptr::eq(selected, ns_window).then_some(group as usize)
+    })
+  }
+
+  /// Sends a tab action such as `selectNextTab:` to the native window.
+  #[cfg(target_os = "macos")]
+  fn send_tab_action(&self, action: objc2::runtime::Sel) -> crate::Result<()> {
+    self.with_ns_window(move |ns_window| {
+      let sender = std::ptr::null::<objc2::runtime::AnyObject>();
+      let _: *mut objc2::runtime::AnyObject =
+        unsafe { objc2::msg_send![ns_window, performSelector: action, withObject: sender] };
+    })
+  }
+
+  /// Runs the closure with the native window on the main thread and returns its result.
+  #[cfg(target_os = "macos")]
+  fn with_ns_window<T: Send + 'static>(
+    &self,
+    f: impl FnOnce(&objc2::runtime::AnyObject) -> T + Send + 'static,
+  ) -> crate::Result<T> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let window = self.clone();
+    self.run_on_main_thread(move || {
+      let _ = tx.send(
+        window
+          .ns_window()
+          .map(|ns_window| f(unsafe { &*ns_window.cast::<objc2::runtime::AnyObject>() })),
+      );
+    })?;
+    rx.recv()
+      .map_err(|_| crate::Error::FailedToReceiveMessage)?
   }
 
   /// Determines if this window should be resizable.
@@ -1694,6 +2168,58 @@ impl<R: Runtime> Window<R> {
     self.window.dispatcher.hide().map_err(Into::into)
   }
 
+  /// Shows this window with an animation, or with the `showAnimation` of its configuration if `None`.
+  /// Shows it directly when it has no animation.
+  ///
+  /// The animation runs on the main thread, and this function returns before it completes.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Blocks the event loop for the duration of the animation.
+  ///   Requires the `window-animations` feature, without it the window is shown or hidden directly and a warning is logged.
+  /// - **Linux:** The slide effects fade the window.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::{window::{WindowAnimation, WindowAnimationEffect}, Manager};
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let window = app.get_webview_window("popover").unwrap();
+  ///     window.show_animated(Some(WindowAnimation {
+  ///       effect: WindowAnimationEffect::SlideDown,
+  ///       duration: 150,
+  ///     }))?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  #[cfg(desktop)]
+  pub fn show_animated(&self, animation: Option<WindowAnimation>) -> crate::Result<()> {
+    match animation.or(self.animations.lock().unwrap().show) {
+      Some(animation) => animation::show(self, animation),
+      None => self.show(),
+    }
+  }
+
+  /// Hides this window with an animation, or with the `hideAnimation` of its configuration if `None`.
+  /// Hides it directly when it has no animation.
+  ///
+  /// The animation runs on the main thread, and this function returns before it completes.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** Blocks the event loop for the duration of the animation.
+  ///   Requires the `window-animations` feature, without it the window is shown or hidden directly and a warning is logged.
+  /// - **Linux:** The slide effects fade the window.
+  #[cfg(desktop)]
+  pub fn hide_animated(&self, animation: Option<WindowAnimation>) -> crate::Result<()> {
+    match animation.or(self.animations.lock().unwrap().hide) {
+      Some(animation) => animation::hide(self, animation),
+      None => self.hide(),
+    }
+  }
+
   /// Closes this window. It emits [`crate::RunEvent::CloseRequested`] first like a user-initiated close request so you can intercept it.
   pub fn close(&self) -> crate::Result<()> {
     self.window.dispatcher.close().map_err(Into::into)
@@ -1877,8 +2403,63 @@ tauri::Builder::default()
     self.window.dispatcher.set_focus().map_err(Into::into)
   }
 
+  /// Injects a synthetic input event in the webviews of this window, for kiosk self-tests and accessibility automation.
+  ///
+  /// Mouse events are dispatched to the element under the cursor in the topmost webview at that position,
+  /// keyboard events to the focused element of the topmost webview.
+  ///
+  /// # Limitations
+  ///
+  /// The events are DOM events dispatched by a script in the main frame of the webview, not native OS input,
+  /// so they only exercise the page event handlers:
+  ///
+  /// - Their `isTrusted` property is `false`, handlers ignoring untrusted events do not run.
+  /// - They do not grant the transient user activation, so APIs requiring a user gesture such as
+  ///   `requestFullscreen`, `navigator.clipboard.writeText`, `window.open` or media playback with sound are rejected.
+  /// - Typed text is inserted in the focused element, clicks move the focus and wheel events scroll,
+  ///   but other browser default actions do not happen: no text selection, caret movement, `Tab` focus navigation,
+  ///   form submission on `Enter`, clipboard shortcuts, `:hover` styles or file pickers.
+  /// - Native UI is not reached: menu accelerators, global shortcuts, context menus of the webview,
+  ///   input method editors and the elements of iframes, including same-origin ones.
+  ///
+  /// Use the OS input APIs, such as `SendInput` on Windows or `CGEventPost` on macOS, when the trusted behavior must be tested.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::{window::{InputEvent, Modifiers, MouseButton}, LogicalPosition, Manager};
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let window = app.get_webview_window("main").unwrap();
+  ///     window.inject_input(InputEvent::Click {
+  ///       position: LogicalPosition::new(100.0, 50.0),
+  ///       button: MouseButton::Left,
+  ///       modifiers: Modifiers::default(),
+  ///     })?;
+  ///     window.inject_input(InputEvent::Text { text: "hello".into() })?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  #[cfg(feature = "synthetic-input")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "synthetic-input")))]
+  pub fn inject_input(&self, event: InputEvent) -> crate::Result<()> {
+    let scale_factor = self.scale_factor()?;
+    if let Some((webview, event)) = input::route(
+      self.webviews(),
+      self.is_webview_window(),
+      scale_factor,
+      event,
+    )? {
+      webview.eval(&input::script(&event)?)?;
+    }
+    Ok(())
+  }
+
   /// Sets this window' icon.
+  ///
+  /// The icon no longer follows the theme if [`Self::set_icon_pair`] was used.
   pub fn set_icon(&self, icon: Image<'_>) -> crate::Result<()> {
+    self.icon_pair.lock().unwrap().take();
     self
       .window
       .dispatcher
@@ -1886,6 +2467,43 @@ tauri::Builder::default()
       .map_err(Into::into)
   }
 
+  /// Sets the icons of this window when it uses a light and a dark theme, respectively,
+  /// the icon switching between them when the [theme](Self::theme) of the window changes.
+  pub fn set_icon_pair(&self, light: Image<'_>, dark: Image<'_>) -> crate::Result<()> {
+    let mut pair = crate::image::theme::IconPair::new(light, dark);
+    let icon = pair.switch(self.theme()?);
+    self.icon_pair.lock().unwrap().replace(pair);
+    match icon {
+      Some(icon) => self
+        .window
+        .dispatcher
+        .set_icon(icon.into())
+        .map_err(Into::into),
+      None => Ok(()),
+    }
+  }
+
+  /// Follows a theme change with the icon pairs of this window and of the tray icons.
+  pub(crate) fn on_theme_changed(&self, theme: crate::Theme) {
+    let icon = self
+      .icon_pair
+      .lock()
+      .unwrap()
+      .as_mut()
+      .and_then(|pair| pair.switch(theme));
+    if let Some(icon) = icon {
+      if let Err(e) = self.window.dispatcher.set_icon(icon.into()) {
+        log::debug!(
+          "failed to switch the icon of the window `{}`: {e}",
+          self.label()
+        );
+      }
+    }
+
+    #[cfg(feature = "tray-icon")]
+    crate::tray::on_window_theme_changed(&self.app_handle, theme);
+  }
+
   /// Whether to hide the window icon from the taskbar or not.
   ///
   /// ## Platform-specific
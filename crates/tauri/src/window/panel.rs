@@ -0,0 +1,353 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Panel windows anchored to a tray icon or to a point of the screen.
+
+use tauri_runtime::dpi::{PhysicalPosition, PhysicalSize, Position};
+pub use tauri_utils::config::WindowType;
+
+use crate::{Runtime, Window};
+
+/// The point of a window placed at the anchor given to [`Window::show_at`].
+///
+/// For instance, [`Gravity::Top`] places the middle of the top edge of the window at the anchor,
+/// so the window is shown below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Gravity {
+  /// The top left corner of the window.
+  TopLeft,
+  /// The middle of the top edge of the window.
+  Top,
+  /// The top right corner of the window.
+  TopRight,
+  /// The middle of the left edge of the window.
+  Left,
+  /// The center of the window.
+  Center,
+  /// The middle of the right edge of the window.
+  Right,
+  /// The bottom left corner of the window.
+  BottomLeft,
+  /// The middle of the bottom edge of the window.
+  Bottom,
+  /// The bottom right corner of the window.
+  BottomRight,
+}
+
+impl Gravity {
+  /// The horizontal and vertical fractions of the window size between its top left corner and the gravity point.
+  fn offset(self) -> (f64, f64) {
+    match self {
+      Self::TopLeft => (0.0, 0.0),
+      Self::Top => (0.5, 0.0),
+      Self::TopRight => (1.0, 0.0),
+      Self::Left => (0.0, 0.5),
+      Self::Center => (0.5, 0.5),
+      Self::Right => (1.0, 0.5),
+      Self::BottomLeft => (0.0, 1.0),
+      Self::Bottom => (0.5, 1.0),
+      Self::BottomRight => (1.0, 1.0),
+    }
+  }
+}
+
+/// The position of a window of the given size placing its gravity point at the anchor,
+/// moved inside the bounds of the monitor if any.
+fn anchored_position(
+  anchor: PhysicalPosition<f64>,
+  size: PhysicalSize<u32>,
+  gravity: Gravity,
+  monitor: Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>,
+) -> PhysicalPosition<i32> {
+  let (dx, dy) = gravity.offset();
+  let mut x = (anchor.x - size.width as f64 * dx).round() as i64;
+  let mut y = (anchor.y - size.height as f64 * dy).round() as i64;
+
+  if let Some((position, monitor_size)) = monitor {
+    let (left, top) = (position.x as i64, position.y as i64);
+    let right = left + monitor_size.width as i64 - size.width as i64;
+    let bottom = top + monitor_size.height as i64 - size.height as i64;
+    // a window larger than the monitor sticks to its top left corner
+    x = x.min(right).max(left);
+    y = y.min(bottom).max(top);
+  }
+
+  PhysicalPosition::new(x as i32, y as i32)
+}
+
+/// Applies the native behavior of panels to a newly created window.
+pub(crate) fn setup<R: Runtime>(window: &Window<R>) -> crate::Result<()> {
+  let window_ = window.clone();
+  window.run_on_main_thread(move || {
+    if let Err(e) = platform::setup(&window_) {
+      log::warn!(
+        "failed to configure the panel window `{}`: {e}",
+        window_.label()
+      );
+    }
+  })
+}
+
+impl<R: Runtime> Window<R> {
+  /// The type of this window, see [`WindowType`].
+  pub fn window_type(&self) -> WindowType {
+    *self.window_type.lock().unwrap()
+  }
+
+  /// Moves this window so its `gravity` point is at `position`, kept inside the monitor containing `position`,
+  /// and shows it with its `showAnimation`.
+  ///
+  /// Panel windows are hidden again when they lose focus, see [`WindowType::Panel`].
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **macOS:** The window is shown without activating the application, so a panel only gets focus,
+  ///   and is only hidden when losing it, once it is clicked.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::{window::Gravity, LogicalPosition, Manager};
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let window = app.get_webview_window("popover").unwrap();
+  ///     window.show_at(LogicalPosition::new(400.0, 30.0), Gravity::Top)?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  pub fn show_at<P: Into<Position>>(&self, position: P, gravity: Gravity) -> crate::Result<()> {
+    let anchor = position.into().to_physical::<f64>(self.scale_factor()?);
+    self.show_at_physical(anchor, gravity)
+  }
+
+  /// Shows this window next to the tray icon with [`Self::show_at`], below the icon when the tray is at the top of the screen
+  /// and above it otherwise.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux:** The position of tray icons is unknown so the window is shown at the cursor.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::{tray::{TrayIconBuilder, TrayIconEvent}, Manager};
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     TrayIconBuilder::with_id("main")
+  ///       .on_tray_icon_event(|tray, event| {
+  ///         if let TrayIconEvent::Click { .. } = event {
+  ///           let window = tray.app_handle().get_webview_window("popover").unwrap();
+  ///           let _ = window.show_at_tray(tray);
+  ///         }
+  ///       })
+  ///       .build(app)?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  #[cfg(feature = "tray-icon")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "tray-icon")))]
+  pub fn show_at_tray(&self, tray: &crate::tray::TrayIcon<R>) -> crate::Result<()> {
+    let scale_factor = self.scale_factor()?;
+    let (top, bottom) = match tray.rect()? {
+      Some(rect) => {
+        let position = rect.position.to_physical::<f64>(scale_factor);
+        let size = rect.size.to_physical::<f64>(scale_factor);
+        let x = position.x + size.width / 2.0;
+        (
+          PhysicalPosition::new(x, position.y),
+          PhysicalPosition::new(x, position.y + size.height),
+        )
+      }
+      None => {
+        let cursor = self.cursor_position()?;
+        (cursor, cursor)
+      }
+    };
+
+    let at_top = self
+      .monitor_from_point(bottom.x, bottom.y)?
+      .map(|monitor| (bottom.y - monitor.position().y as f64) < monitor.size().height as f64 / 2.0)
+      .unwrap_or(true);
+
+    if at_top {
+      self.show_at_physical(bottom, Gravity::Top)
+    } else {
+      self.show_at_physical(top, Gravity::Bottom)
+    }
+  }
+
+  fn show_at_physical(&self, anchor: PhysicalPosition<f64>, gravity: Gravity) -> crate::Result<()> {
+    let monitor = self
+      .monitor_from_point(anchor.x, anchor.y)?
+      .map(|monitor| (*monitor.position(), *monitor.size()));
+    let position = anchored_position(anchor, self.outer_size()?, gravity, monitor);
+    self.set_position(position)?;
+    self.show_animated(None)?;
+    // focusing the window activates the application on macOS
+    #[cfg(not(target_os = "macos"))]
+    self.set_focus()?;
+    Ok(())
+  }
+}
+
+#[cfg(all(windows, feature = "panel-windows"))]
+mod platform {
+  use windows::Win32::{
+    Foundation::HWND,
+    UI::WindowsAndMessaging::{
+      GetWindowLongPtrW, SetWindowLongPtrW, SetWindowPos, GWL_EXSTYLE, SWP_FRAMECHANGED,
+      SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, WS_EX_APPWINDOW, WS_EX_TOOLWINDOW,
+    },
+  };
+
+  use crate::{Runtime, Window};
+
+  pub fn setup<R: Runtime>(window: &Window<R>) -> crate::Result<()> {
+    let hwnd = window.hwnd()?;
+    unsafe {
+      // tool windows are not listed in the taskbar nor in the Alt+Tab switcher
+      let style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+      let style = (style & !(WS_EX_APPWINDOW.0 as isize)) | WS_EX_TOOLWINDOW.0 as isize;
+      SetWindowLongPtrW(hwnd, GWL_EXSTYLE, style);
+      SetWindowPos(
+        hwnd,
+        HWND::default(),
+        0,
+        0,
+        0,
+        0,
+        SWP_FRAMECHANGED | SWP_NOACTIVATE | SWP_NOMOVE | SWP_NOSIZE | SWP_NOZORDER,
+      )
+      .map_err(std::io::Error::from)?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use objc2::{msg_send, runtime::AnyObject};
+
+  use crate::{Runtime, Window};
+
+  /// `NSWindowCollectionBehaviorMoveToActiveSpace`
+  const MOVE_TO_ACTIVE_SPACE: usize = 1 << 1;
+  /// `NSWindowCollectionBehaviorIgnoresCycle`
+  const IGNORES_CYCLE: usize = 1 << 6;
+  /// `NSWindowCollectionBehaviorFullScreenAuxiliary`
+  const FULL_SCREEN_AUXILIARY: usize = 1 << 8;
+  /// `NSPopUpMenuWindowLevel`
+  const POP_UP_MENU_WINDOW_LEVEL: isize = 101;
+
+  pub fn setup<R: Runtime>(window: &Window<R>) -> crate::Result<()> {
+    let ns_window = window.ns_window()? as *mut AnyObject;
+    unsafe {
+      let () = msg_send![
+        ns_window,
+        setCollectionBehavior: MOVE_TO_ACTIVE_SPACE | IGNORES_CYCLE | FULL_SCREEN_AUXILIARY
+      ];
+      // above the menu bar and the windows of the other applications, including full screen ones
+      let () = msg_send![ns_window, setLevel: POP_UP_MENU_WINDOW_LEVEL];
+    }
+    Ok(())
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use gtk::prelude::*;
+
+  use crate::{Runtime, Window};
+
+  pub fn setup<R: Runtime>(window: &Window<R>) -> crate::Result<()> {
+    let gtk_window = window.gtk_window()?;
+    gtk_window.set_skip_pager_hint(true);
+    Ok(())
+  }
+}
+
+#[cfg(all(windows, not(feature = "panel-windows")))]
+mod platform {
+  use crate::{Runtime, Window};
+
+  pub fn setup<R: Runtime>(_window: &Window<R>) -> crate::Result<()> {
+    Err(
+      std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "panel windows require the `panel-windows` feature on Windows",
+      )
+      .into(),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn anchors_the_gravity_point() {
+    let size = PhysicalSize::new(200, 100);
+    let anchor = PhysicalPosition::new(500.0, 30.0);
+
+    assert_eq!(
+      anchored_position(anchor, size, Gravity::Top, None),
+      PhysicalPosition::new(400, 30)
+    );
+    assert_eq!(
+      anchored_position(anchor, size, Gravity::BottomRight, None),
+      PhysicalPosition::new(300, -70)
+    );
+    assert_eq!(
+      anchored_position(anchor, size, Gravity::Center, None),
+      PhysicalPosition::new(400, -20)
+    );
+  }
+
+  #[test]
+  fn stays_inside_the_monitor() {
+    let size = PhysicalSize::new(200, 100);
+    let monitor = Some((PhysicalPosition::new(0, 0), PhysicalSize::new(1000, 800)));
+
+    // a tray icon at the right of a top menu bar
+    assert_eq!(
+      anchored_position(
+        PhysicalPosition::new(980.0, 25.0),
+        size,
+        Gravity::Top,
+        monitor
+      ),
+      PhysicalPosition::new(800, 25)
+    );
+    // a tray icon at the left of a bottom taskbar
+    assert_eq!(
+      anchored_position(
+        PhysicalPosition::new(10.0, 760.0),
+        size,
+        Gravity::Bottom,
+        monitor
+      ),
+      PhysicalPosition::new(0, 660)
+    );
+    // a window larger than the monitor
+    assert_eq!(
+      anchored_position(
+        PhysicalPosition::new(10.0, 10.0),
+        PhysicalSize::new(2000, 100),
+        Gravity::Center,
+        monitor
+      ),
+      PhysicalPosition::new(0, 0)
+    );
+  }
+}
@@ -0,0 +1,132 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  const event = __TEMPLATE_event__
+  const BUTTONS = { left: 0, middle: 1, right: 2 }
+  // the `buttons` bit of each button, see MouseEvent.buttons
+  const BUTTON_BITS = { left: 1, middle: 4, right: 2 }
+
+  function modifiers(m) {
+    return {
+      shiftKey: m.shift,
+      ctrlKey: m.ctrl,
+      altKey: m.alt,
+      metaKey: m.meta
+    }
+  }
+
+  function focused() {
+    return document.activeElement || document.body
+  }
+
+  function key(type, key, code, m) {
+    return focused().dispatchEvent(
+      new KeyboardEvent(type, {
+        key,
+        code,
+        bubbles: true,
+        cancelable: true,
+        composed: true,
+        view: window,
+        ...modifiers(m)
+      })
+    )
+  }
+
+  function mouse(type, e, pressed) {
+    const { x, y } = e.position
+    const target = document.elementFromPoint(x, y) || document.body
+    const init = {
+      clientX: x,
+      clientY: y,
+      screenX: window.screenX + x,
+      screenY: window.screenY + y,
+      button: BUTTONS[e.button] || 0,
+      buttons: pressed ? BUTTON_BITS[e.button] || 1 : 0,
+      bubbles: true,
+      cancelable: true,
+      composed: true,
+      view: window,
+      ...modifiers(e.modifiers)
+    }
+    const notCancelled =
+      type === 'wheel'
+        ? target.dispatchEvent(
+            new WheelEvent(type, {
+              ...init,
+              deltaX: e.deltaX,
+              deltaY: e.deltaY,
+              deltaMode: WheelEvent.DOM_DELTA_PIXEL
+            })
+          )
+        : target.dispatchEvent(new MouseEvent(type, init))
+    return { target, notCancelled }
+  }
+
+  const noModifiers = { shift: false, ctrl: false, alt: false, meta: false }
+
+  switch (event.type) {
+    case 'keyDown':
+      key('keydown', event.key, event.code, event.modifiers)
+      break
+    case 'keyUp':
+      key('keyup', event.key, event.code, event.modifiers)
+      break
+    case 'text':
+      for (const char of event.text) {
+        if (key('keydown', char, '', noModifiers)) {
+          // synthetic key events do not edit the focused element
+          document.execCommand('insertText', false, char)
+        }
+        key('keyup', char, '', noModifiers)
+      }
+      break
+    case 'mouseMove':
+      mouse('mousemove', event, false)
+      break
+    case 'mouseDown': {
+      const { target } = mouse('mousedown', event, true)
+      // synthetic mouse events do not move the focus
+      if (typeof target.focus === 'function') target.focus()
+      break
+    }
+    case 'mouseUp':
+      mouse('mouseup', event, false)
+      break
+    case 'click': {
+      const { target } = mouse('mousedown', event, true)
+      if (typeof target.focus === 'function') target.focus()
+      mouse('mouseup', event, false)
+      if (event.button === 'right') {
+        mouse('contextmenu', event, false)
+      } else if (event.button === 'middle') {
+        mouse('auxclick', event, false)
+      } else {
+        mouse('click', event, false)
+      }
+      break
+    }
+    case 'wheel': {
+      const { target, notCancelled } = mouse('wheel', event, false)
+      // synthetic wheel events do not scroll
+      if (notCancelled) {
+        let element = target
+        while (
+          element &&
+          element !== document.scrollingElement &&
+          element.scrollHeight <= element.clientHeight &&
+          element.scrollWidth <= element.clientWidth
+        ) {
+          element = element.parentElement
+        }
+        ;(element || document.scrollingElement).scrollBy(
+          event.deltaX,
+          event.deltaY
+        )
+      }
+      break
+    }
+  }
+})()
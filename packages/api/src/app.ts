@@ -121,6 +121,146 @@ async function setTheme(theme?: Theme | null): Promise<void> {
   return invoke('plugin:app|set_app_theme', { theme })
 }
 
+/**
+ * Closes the splashscreen configured in `app > splashscreen` and shows its main window,
+ * once the configured minimum display time elapsed.
+ *
+ * Use it with `autoClose: false` to keep the splashscreen until the frontend finished its startup work.
+ *
+ * @example
+ * ```typescript
+ * import { splashDone } from '@tauri-apps/api/app';
+ * await splashDone();
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function splashDone(): Promise<void> {
+  return invoke('plugin:app|splash_done')
+}
+
+/**
+ * Writes a support bundle to the given path: a zip archive with the redacted app configuration,
+ * the versions, the recent app events, the window states, the permission scopes and the app logs.
+ *
+ * The path must be in the app log or cache directory and must not exist.
+ *
+ * Requires the `diagnostics` Cargo feature and the `core:app:allow-diagnostics-bundle` permission.
+ *
+ * @example
+ * ```typescript
+ * import { diagnosticsBundle } from '@tauri-apps/api/app';
+ * import { appCacheDir, join } from '@tauri-apps/api/path';
+ * await diagnosticsBundle(await join(await appCacheDir(), 'diagnostics.zip'));
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function diagnosticsBundle(path: string): Promise<void> {
+  return invoke('plugin:app|diagnostics_bundle', { path })
+}
+
+/**
+ * A third-party package the application depends on, with its license.
+ *
+ * @since 2.1.0
+ */
+interface Attribution {
+  /** The package name. */
+  name: string
+  /** The package version. */
+  version: string
+  /** The package registry. */
+  ecosystem: 'cargo' | 'npm'
+  /** The SPDX license expression, e.g. `MIT OR Apache-2.0`. */
+  license?: string
+  /** The license text, for the packages without an SPDX license expression. */
+  licenseText?: string
+  /** The package authors. */
+  authors?: string[]
+  /** The source repository URL. */
+  repository?: string
+}
+
+/**
+ * Gets the third-party license attributions of the application, e.g. to show an "Open source licenses" screen.
+ *
+ * The list is empty unless the build script enables them with `tauri_build::Attributes::attributions`.
+ *
+ * @example
+ * ```typescript
+ * import { attributions } from '@tauri-apps/api/app';
+ * const licenses = new Set((await attributions()).map((a) => a.license));
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function attributions(): Promise<Attribution[]> {
+  return invoke('plugin:app|attributions')
+}
+
+/**
+ * The value of a matched command line argument.
+ *
+ * @since 2.1.0
+ */
+interface ArgMatch {
+  /**
+   * `true` for flags without a value, the typed value otherwise,
+   * or an array of the values for arguments with `multiple`.
+   */
+  value: string | number | boolean | Array<string | number | boolean>
+  /** The number of times the argument was set. */
+  occurrences: number
+}
+
+/**
+ * The matched subcommand.
+ *
+ * @since 2.1.0
+ */
+interface SubcommandMatch {
+  /** The subcommand name. */
+  name: string
+  /** The arguments of the subcommand. */
+  matches: CliMatches
+}
+
+/**
+ * The command line arguments matched with `app > cli` in `tauri.conf.json`.
+ *
+ * @since 2.1.0
+ */
+interface CliMatches {
+  /** The matched arguments by name, the help message is matched as `help`. */
+  args: Record<string, ArgMatch>
+  /** The matched subcommand, if any. */
+  subcommand: SubcommandMatch | null
+}
+
+/**
+ * Parses the command line arguments of the application with the `app > cli` configuration.
+ *
+ * Arguments of the secondary instances forwarded with `tauri::cli::forward_args`
+ * are emitted as the `tauri://cli-args` event.
+ *
+ * @example
+ * ```typescript
+ * import { getCliMatches } from '@tauri-apps/api/app';
+ * const matches = await getCliMatches();
+ * if (matches.subcommand?.name === 'open') {
+ *   console.log(matches.subcommand.matches.args.path?.value);
+ * }
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function getCliMatches(): Promise<CliMatches> {
+  return invoke('plugin:app|cli_matches')
+}
+
+export type { Attribution, ArgMatch, SubcommandMatch, CliMatches }
+
 export {
   getName,
   getVersion,
@@ -128,5 +268,9 @@ export {
   show,
   hide,
   defaultWindowIcon,
-  setTheme
+  setTheme,
+  splashDone,
+  diagnosticsBundle,
+  attributions,
+  getCliMatches
 }
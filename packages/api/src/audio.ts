@@ -0,0 +1,83 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * List the audio capture and playback devices without the `getUserMedia` permission prompt, to build device pickers.
+ *
+ * Requires the `audio` Cargo feature of the `tauri` crate. The commands are not part of the default permissions
+ * and must be enabled with the `core:audio:allow-devices` and `core:audio:allow-watch` permissions.
+ *
+ * This package is also accessible with `window.__TAURI__.audio` when [`app.withGlobalTauri`](https://v2.tauri.app/reference/config/#withglobaltauri) in `tauri.conf.json` is set to `true`.
+ *
+ * #### Platform-specific
+ *
+ * - **Linux:** Requires the `pactl` command of PulseAudio or PipeWire.
+ * - **Android / iOS:** Unsupported.
+ * @module
+ */
+
+import { Channel, Resource, invoke } from './core'
+import type { UnlistenFn } from './event'
+
+/**
+ * An audio capture or playback device.
+ *
+ * @since 2.1.0
+ */
+interface AudioDevice {
+  /** The identifier of the device in the operating system audio stack, which is not the webview `deviceId`. */
+  id: string
+  /** The name of the device, matching the `label` of the webview `MediaDeviceInfo`. */
+  label: string
+  /** Whether the device captures or plays audio. */
+  kind: 'input' | 'output'
+  /** Whether this is the default device of its kind, used by `getUserMedia` when no device is requested. */
+  isDefault: boolean
+}
+
+/**
+ * Lists the active audio capture devices followed by the playback devices.
+ *
+ * @example
+ * ```typescript
+ * import { devices } from '@tauri-apps/api/audio';
+ * const microphones = (await devices()).filter((device) => device.kind === 'input');
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function devices(): Promise<AudioDevice[]> {
+  return invoke('plugin:audio|devices')
+}
+
+/**
+ * Calls the handler with the new device list when a device is connected or removed or the default device changes.
+ *
+ * The changes are notified by the operating system and only delivered to this webview.
+ *
+ * @example
+ * ```typescript
+ * import { onDevicesChange } from '@tauri-apps/api/audio';
+ * const unlisten = await onDevicesChange((devices) => console.log(devices));
+ * // stop watching the devices
+ * unlisten();
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function onDevicesChange(
+  handler: (devices: AudioDevice[]) => void
+): Promise<UnlistenFn> {
+  const onChange = new Channel<AudioDevice[]>()
+  onChange.onmessage = handler
+  const rid = await invoke<number>('plugin:audio|watch', { onChange })
+  const watcher = new Resource(rid)
+  return () => {
+    void watcher.close()
+  }
+}
+
+export type { AudioDevice }
+
+export { devices, onDevicesChange }
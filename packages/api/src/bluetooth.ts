@@ -0,0 +1,247 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Scan for Bluetooth Low Energy devices and access their GATT services, since Web Bluetooth is not available in the webviews.
+ *
+ * Requires the `bluetooth` Cargo feature of the `tauri` crate. The commands are not part of the default permissions
+ * and must be enabled with the `core:bluetooth:allow-*` permissions.
+ *
+ * UUIDs are returned in their lowercase 128-bit form, and the 16-bit and 32-bit short forms such as `180d` are accepted as input.
+ *
+ * This package is also accessible with `window.__TAURI__.bluetooth` when [`app.withGlobalTauri`](https://v2.tauri.app/reference/config/#withglobaltauri) in `tauri.conf.json` is set to `true`.
+ *
+ * #### Platform-specific
+ *
+ * - **macOS / Android / iOS:** Unsupported.
+ * @module
+ */
+
+import { Channel, invoke } from './core'
+
+/**
+ * An advertisement received while scanning.
+ *
+ * @since 2.1.0
+ */
+interface Advertisement {
+  /** The address of the device, e.g. `A1:B2:C3:D4:E5:F6`. */
+  address: string
+  /** The local name of the device. */
+  name: string | null
+  /** The received signal strength in dBm. */
+  rssi: number | null
+  /** The advertised service UUIDs. */
+  serviceUuids: string[]
+  /** The manufacturer specific data by company identifier. */
+  manufacturerData: Record<number, number[]>
+}
+
+/**
+ * A characteristic of a GATT service.
+ *
+ * @since 2.1.0
+ */
+interface GattCharacteristic {
+  /** The characteristic UUID. */
+  uuid: string
+  /** The supported operations. */
+  properties: {
+    read: boolean
+    write: boolean
+    writeWithoutResponse: boolean
+    notify: boolean
+    indicate: boolean
+  }
+}
+
+/**
+ * A GATT service of a connected device.
+ *
+ * @since 2.1.0
+ */
+interface GattService {
+  /** The service UUID. */
+  uuid: string
+  /** The characteristics of the service. */
+  characteristics: GattCharacteristic[]
+}
+
+/**
+ * A new value of a subscribed characteristic.
+ *
+ * @since 2.1.0
+ */
+interface Notification {
+  /** The address of the device. */
+  address: string
+  /** The service UUID. */
+  service: string
+  /** The characteristic UUID. */
+  characteristic: string
+  /** The new value. */
+  value: number[]
+}
+
+/**
+ * The write type of {@link write}.
+ *
+ * @since 2.1.0
+ */
+type WriteType = 'withResponse' | 'withoutResponse'
+
+/**
+ * Starts scanning for advertisements, calling `onAdvertisement` for each of them.
+ * A scan already in progress is replaced, along with its handler.
+ *
+ * @example
+ * ```typescript
+ * import { startScan } from '@tauri-apps/api/bluetooth';
+ * // heart rate monitors
+ * await startScan(['180d'], (advertisement) => {
+ *   console.log(`found ${advertisement.name ?? advertisement.address}`);
+ * });
+ * ```
+ *
+ * @param services Only report the devices advertising one of these services, all devices if empty.
+ * @param onAdvertisement Called with each received advertisement.
+ *
+ * @since 2.1.0
+ */
+async function startScan(
+  services: string[],
+  onAdvertisement: (advertisement: Advertisement) => void
+): Promise<void> {
+  const channel = new Channel<Advertisement>()
+  channel.onmessage = onAdvertisement
+  return invoke('plugin:bluetooth|start_scan', {
+    services,
+    onAdvertisement: channel
+  })
+}
+
+/**
+ * Stops the scan started with {@link startScan}.
+ *
+ * @since 2.1.0
+ */
+async function stopScan(): Promise<void> {
+  return invoke('plugin:bluetooth|stop_scan')
+}
+
+/**
+ * Connects to the device and discovers its GATT services.
+ *
+ * @since 2.1.0
+ */
+async function connect(address: string): Promise<GattService[]> {
+  return invoke('plugin:bluetooth|connect', { address })
+}
+
+/**
+ * Disconnects from the device, cancelling its subscriptions.
+ *
+ * @since 2.1.0
+ */
+async function disconnect(address: string): Promise<void> {
+  return invoke('plugin:bluetooth|disconnect', { address })
+}
+
+/**
+ * Reads the value of a characteristic.
+ *
+ * @since 2.1.0
+ */
+async function read(
+  address: string,
+  service: string,
+  characteristic: string
+): Promise<Uint8Array> {
+  return invoke<number[]>('plugin:bluetooth|read', {
+    address,
+    service,
+    characteristic
+  }).then((value) => new Uint8Array(value))
+}
+
+/**
+ * Writes the value of a characteristic.
+ *
+ * @since 2.1.0
+ */
+async function write(
+  address: string,
+  service: string,
+  characteristic: string,
+  value: number[] | Uint8Array | ArrayBuffer,
+  writeType?: WriteType
+): Promise<void> {
+  return invoke('plugin:bluetooth|write', {
+    address,
+    service,
+    characteristic,
+    value: Array.from(
+      value instanceof ArrayBuffer ? new Uint8Array(value) : value
+    ),
+    writeType
+  })
+}
+
+/**
+ * Subscribes to the notifications or indications of a characteristic, calling `onNotification` when its value changes.
+ * Subscribing again to the same characteristic replaces the handler.
+ *
+ * @since 2.1.0
+ */
+async function subscribe(
+  address: string,
+  service: string,
+  characteristic: string,
+  onNotification: (notification: Notification) => void
+): Promise<void> {
+  const channel = new Channel<Notification>()
+  channel.onmessage = onNotification
+  return invoke('plugin:bluetooth|subscribe', {
+    address,
+    service,
+    characteristic,
+    onNotification: channel
+  })
+}
+
+/**
+ * Stops the notifications of a characteristic.
+ *
+ * @since 2.1.0
+ */
+async function unsubscribe(
+  address: string,
+  service: string,
+  characteristic: string
+): Promise<void> {
+  return invoke('plugin:bluetooth|unsubscribe', {
+    address,
+    service,
+    characteristic
+  })
+}
+
+export type {
+  Advertisement,
+  GattCharacteristic,
+  GattService,
+  Notification,
+  WriteType
+}
+
+export {
+  startScan,
+  stopScan,
+  connect,
+  disconnect,
+  read,
+  write,
+  subscribe,
+  unsubscribe
+}
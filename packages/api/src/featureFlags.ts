@@ -0,0 +1,82 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Read the runtime feature flags of the application, shared with the Rust side with `AppHandle::features`.
+ *
+ * The flags are a read-only snapshot updated by the Rust side before the change event is emitted and when a page starts loading,
+ * so they can be read synchronously, e.g. while rendering.
+ *
+ * This package is also accessible with `window.__TAURI__.featureFlags` when [`app.withGlobalTauri`](https://v2.tauri.app/reference/config/#withglobaltauri) in `tauri.conf.json` is set to `true`.
+ * @module
+ */
+
+import { listen } from './event'
+import type { UnlistenFn } from './event'
+
+const FEATURE_FLAGS_CHANGED_EVENT = 'tauri://feature-flags-changed'
+
+/**
+ * The feature flags, keyed by name.
+ *
+ * @since 2.1.0
+ */
+type FeatureFlags = Readonly<Record<string, unknown>>
+
+/**
+ * Gets all the feature flags.
+ *
+ * @since 2.1.0
+ */
+function flags(): FeatureFlags {
+  return window.__TAURI_INTERNALS__.featureFlags ?? {}
+}
+
+/**
+ * Gets the value of the feature flag, or `undefined` if it is not set.
+ *
+ * @example
+ * ```typescript
+ * import { flag } from '@tauri-apps/api/featureFlags';
+ * const maxTabs = flag<number>('maxTabs') ?? 10;
+ * ```
+ *
+ * @since 2.1.0
+ */
+function flag<T = unknown>(name: string): T | undefined {
+  return flags()[name] as T | undefined
+}
+
+/**
+ * Whether the feature flag is set to `true`.
+ *
+ * @since 2.1.0
+ */
+function isEnabled(name: string): boolean {
+  return flag(name) === true
+}
+
+/**
+ * Listens to the feature flags changes.
+ *
+ * @example
+ * ```typescript
+ * import { onChange } from '@tauri-apps/api/featureFlags';
+ * const unlisten = await onChange((flags) => console.log(flags));
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function onChange(
+  handler: (flags: FeatureFlags) => void
+): Promise<UnlistenFn> {
+  // the snapshot is the latest version, the events of concurrent changes may be delivered out of order
+  return listen<FeatureFlags>(FEATURE_FLAGS_CHANGED_EVENT, () =>
+    handler(flags())
+  )
+}
+
+export type { FeatureFlags }
+
+export { flags, flag, isEnabled, onChange }
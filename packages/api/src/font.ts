@@ -0,0 +1,66 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * List the installed font families and install fonts for the current user, e.g. to build font pickers.
+ *
+ * Requires the `font` Cargo feature of the `tauri` crate. The commands are not part of the default permissions
+ * and must be enabled with the `core:font:allow-*` permissions.
+ *
+ * This package is also accessible with `window.__TAURI__.font` when [`app.withGlobalTauri`](https://v2.tauri.app/reference/config/#withglobaltauri) in `tauri.conf.json` is set to `true`.
+ *
+ * #### Platform-specific
+ *
+ * - **Linux:** Requires fontconfig.
+ * - **Android / iOS:** Unsupported.
+ * @module
+ */
+
+import { invoke } from './core'
+
+/**
+ * A font family installed on the system.
+ *
+ * @since 2.1.0
+ */
+interface FontFamily {
+  /** The family name, as used by the CSS `font-family` property. */
+  name: string
+  /** The style names of the family fonts, e.g. `Regular` or `Bold Italic`. */
+  styles: string[]
+}
+
+/**
+ * Lists the font families installed on the system with their styles, sorted by name.
+ *
+ * @example
+ * ```typescript
+ * import { families } from '@tauri-apps/api/font';
+ * const names = (await families()).map((family) => family.name);
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function families(): Promise<FontFamily[]> {
+  return invoke('plugin:font|families')
+}
+
+/**
+ * Installs a TrueType or OpenType font file for the current user, resolving to the installed file path.
+ *
+ * The font can be used by the webviews created after the installation.
+ * The file must be in the app data, app local data, app cache or download directories,
+ * or allowed by the [asset protocol scope](https://v2.tauri.app/reference/config/#assetprotocolconfig).
+ * Installing the same font again resolves to the installed file path,
+ * while a different font with the same file name is rejected instead of replacing it.
+ *
+ * @since 2.1.0
+ */
+async function install(path: string): Promise<string> {
+  return invoke('plugin:font|install', { path })
+}
+
+export type { FontFamily }
+
+export { families, install }
@@ -26,6 +26,7 @@ declare global {
         webviews: WebviewDef[]
         currentWebview: WebviewDef
       }
+      featureFlags?: Readonly<Record<string, unknown>>
       plugins: {
         path: {
           sep: string
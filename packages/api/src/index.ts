@@ -14,29 +14,45 @@
  */
 
 import * as app from './app'
+import * as audio from './audio'
+import * as bluetooth from './bluetooth'
 import * as core from './core'
 import * as dpi from './dpi'
 import * as event from './event'
+import * as featureFlags from './featureFlags'
+import * as font from './font'
 import * as image from './image'
+import * as integrity from './integrity'
 import * as menu from './menu'
 import * as mocks from './mocks'
 import * as path from './path'
+import * as printer from './printer'
+import * as serial from './serial'
 import * as tray from './tray'
 import * as webview from './webview'
 import * as webviewWindow from './webviewWindow'
 import * as window from './window'
+import * as windowChannel from './windowChannel'
 
 export {
   app,
+  audio,
+  bluetooth,
   core,
   dpi,
   event,
+  featureFlags,
+  font,
   image,
+  integrity,
   menu,
   mocks,
   path,
+  printer,
+  serial,
   tray,
   webview,
   webviewWindow,
-  window
+  window,
+  windowChannel
 }
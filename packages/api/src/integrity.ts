@@ -0,0 +1,113 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Hash files and verify their signatures, e.g. to check downloaded plugins or content packs.
+ *
+ * Requires the `integrity` Cargo feature of the `tauri` crate. The commands are not part of the default permissions
+ * and must be enabled with the `core:integrity:allow-*` permissions.
+ *
+ * The files must be in the app data, app local data, app cache or download directories,
+ * or allowed by the [asset protocol scope](https://v2.tauri.app/reference/config/#assetprotocolconfig).
+ *
+ * This package is also accessible with `window.__TAURI__.integrity` when [`app.withGlobalTauri`](https://v2.tauri.app/reference/config/#withglobaltauri) in `tauri.conf.json` is set to `true`.
+ * @module
+ */
+
+import { invoke } from './core'
+import { listen } from './event'
+
+const PROGRESS_EVENT = 'tauri://integrity-progress'
+
+/**
+ * A hash algorithm.
+ *
+ * @since 2.1.0
+ */
+type HashAlgorithm = 'sha256' | 'blake3'
+
+/**
+ * The progress of a {@link hashFile} call.
+ *
+ * @since 2.1.0
+ */
+interface HashProgress {
+  /** The path of the hashed file. */
+  path: string
+  /** The number of bytes hashed. */
+  bytes: number
+  /** The size of the file. */
+  totalBytes: number
+  /** Whether the file is completely hashed. */
+  done: boolean
+}
+
+/**
+ * The options of {@link hashFile}.
+ *
+ * @since 2.1.0
+ */
+interface HashOptions {
+  /** The hash algorithm. Defaults to `sha256`. */
+  algorithm?: HashAlgorithm
+  /** Called while the file is hashed. */
+  onProgress?: (progress: HashProgress) => void
+}
+
+/**
+ * Hashes the file, returning the lowercase hexadecimal digest.
+ *
+ * @example
+ * ```typescript
+ * import { hashFile } from '@tauri-apps/api/integrity';
+ * const checksum = await hashFile('/downloads/pack.zip', {
+ *   algorithm: 'blake3',
+ *   onProgress: ({ bytes, totalBytes }) => console.log(`${bytes}/${totalBytes}`)
+ * });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function hashFile(path: string, options?: HashOptions): Promise<string> {
+  const onProgress = options?.onProgress
+  const unlisten = onProgress
+    ? await listen<HashProgress>(PROGRESS_EVENT, (event) => {
+        if (event.payload.path === path) {
+          onProgress(event.payload)
+        }
+      })
+    : undefined
+  try {
+    return await invoke('plugin:integrity|hash_file', {
+      path,
+      algorithm: options?.algorithm
+    })
+  } finally {
+    unlisten?.()
+  }
+}
+
+/**
+ * Verifies the minisign signature of the file, rejecting if it does not match.
+ *
+ * The public key and the signature are base64 encoded like the updater ones,
+ * i.e. the `pubkey` updater configuration and the content of the `.sig` files created by `tauri signer sign`.
+ *
+ * @since 2.1.0
+ */
+async function verifySignature(
+  path: string,
+  publicKey: string,
+  signature: string
+): Promise<void> {
+  return invoke('plugin:integrity|verify_signature', {
+    path,
+    publicKey,
+    signature
+  })
+}
+
+export type { HashAlgorithm, HashOptions, HashProgress }
+
+export { hashFile, verifySignature }
@@ -0,0 +1,97 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * List the printers and send raw print jobs without the print dialog, e.g. to label or receipt printers.
+ *
+ * Requires the `printer` Cargo feature of the `tauri` crate. The commands are not part of the default permissions
+ * and must be enabled with the `core:printer:allow-*` permissions.
+ *
+ * This package is also accessible with `window.__TAURI__.printer` when [`app.withGlobalTauri`](https://v2.tauri.app/reference/config/#withglobaltauri) in `tauri.conf.json` is set to `true`.
+ *
+ * #### Platform-specific
+ *
+ * - **Linux / macOS:** Requires CUPS.
+ * - **Android / iOS:** Unsupported.
+ * @module
+ */
+
+import { invoke } from './core'
+
+/**
+ * The features supported by a printer.
+ *
+ * @since 2.1.0
+ */
+interface PrinterCapabilities {
+  /** Whether the printer prints in color. */
+  color: boolean
+  /** Whether the printer prints on both sides of the paper. */
+  duplex: boolean
+  /** The names of the supported paper sizes, e.g. `A4` or `Letter`. */
+  paperSizes: string[]
+}
+
+/**
+ * A printer configured on the system.
+ *
+ * @since 2.1.0
+ */
+interface Printer {
+  /** The name of the printer. */
+  name: string
+  /** Whether this is the default printer of the user. */
+  isDefault: boolean
+  /** The features supported by the printer. */
+  capabilities: PrinterCapabilities
+}
+
+/**
+ * The options of {@link printRaw}.
+ *
+ * @since 2.1.0
+ */
+interface PrintOptions {
+  /** The name of the printer to use. Defaults to the default printer. */
+  printer?: string
+  /** The name of the job shown in the print queue. */
+  title?: string
+  /** The number of copies to print. Defaults to 1. */
+  copies?: number
+}
+
+/**
+ * Lists the printers configured on the system.
+ *
+ * @since 2.1.0
+ */
+async function list(): Promise<Printer[]> {
+  return invoke('plugin:printer|list')
+}
+
+/**
+ * Sends the data to the printer as is, bypassing the print drivers, e.g. ZPL for label printers or ESC/POS for receipt printers.
+ *
+ * @example
+ * ```typescript
+ * import { printRaw } from '@tauri-apps/api/printer';
+ * const label = '^XA^FO50,50^ADN,36,20^FDShipping label^FS^XZ';
+ * await printRaw(new TextEncoder().encode(label), { printer: 'Zebra_ZD420' });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function printRaw(
+  data: number[] | Uint8Array | ArrayBuffer,
+  options?: PrintOptions
+): Promise<void> {
+  return invoke('plugin:printer|print_raw', {
+    data: Array.from(data instanceof ArrayBuffer ? new Uint8Array(data) : data),
+    options
+  })
+}
+
+export type { Printer, PrinterCapabilities, PrintOptions }
+
+export { list, printRaw }
@@ -0,0 +1,146 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Enumerate and access the serial ports of the system.
+ *
+ * Requires the `serial` Cargo feature of the `tauri` crate. The commands are not part of the default permissions,
+ * and {@link SerialPort.open} only opens the ports matching the `allow` scope of the `core:serial:allow-open` permission.
+ *
+ * This package is also accessible with `window.__TAURI__.serial` when [`app.withGlobalTauri`](https://v2.tauri.app/reference/config/#withglobaltauri) in `tauri.conf.json` is set to `true`.
+ * @module
+ */
+
+import { Channel, Resource, invoke } from './core'
+
+/**
+ * The USB device of a serial port.
+ *
+ * @since 2.1.0
+ */
+interface UsbInfo {
+  /** The USB vendor ID. */
+  vendorId: number
+  /** The USB product ID. */
+  productId: number
+  /** The manufacturer name. */
+  manufacturer: string | null
+  /** The product name. */
+  product: string | null
+  /** The serial number of the device. */
+  serialNumber: string | null
+}
+
+/**
+ * A serial port of the system.
+ *
+ * @since 2.1.0
+ */
+interface PortInfo {
+  /** The path of the port, e.g. `/dev/ttyUSB0` or `COM3`. */
+  path: string
+  /** The USB device of the port, for USB to serial adapters. Only available on Linux. */
+  usb: UsbInfo | null
+}
+
+/**
+ * The settings of an opened port.
+ *
+ * @since 2.1.0
+ */
+interface SerialOptions {
+  /** The baud rate. Defaults to 9600. */
+  baudRate?: number
+  /** The number of data bits, from 5 to 8. Defaults to 8. */
+  dataBits?: number
+  /** The parity checking mode. Defaults to `none`. */
+  parity?: 'none' | 'odd' | 'even'
+  /** The number of stop bits. Defaults to `one`. */
+  stopBits?: 'one' | 'two'
+  /** The flow control mode. Defaults to `none`. */
+  flowControl?: 'none' | 'software' | 'hardware'
+}
+
+/**
+ * An event of an opened port.
+ *
+ * @since 2.1.0
+ */
+type SerialEvent =
+  | { event: 'data'; data: number[] }
+  | { event: 'error'; data: string }
+  | { event: 'closed' }
+
+/**
+ * Lists the serial ports matching the scope of the `core:serial:allow-available-ports` permission.
+ *
+ * @since 2.1.0
+ */
+async function availablePorts(): Promise<PortInfo[]> {
+  return invoke('plugin:serial|available_ports')
+}
+
+/**
+ * An opened serial port. Closing it with {@link SerialPort.close} releases the port.
+ *
+ * @since 2.1.0
+ */
+class SerialPort extends Resource {
+  /**
+   * Creates a SerialPort from a resource ID. For internal use only.
+   *
+   * @ignore
+   */
+  constructor(rid: number) {
+    super(rid)
+  }
+
+  /**
+   * Opens the port with exclusive access, calling `onEvent` with the received data.
+   *
+   * @example
+   * ```typescript
+   * import { SerialPort } from '@tauri-apps/api/serial';
+   * const port = await SerialPort.open('/dev/ttyUSB0', { baudRate: 115200 }, (event) => {
+   *   if (event.event === 'data') {
+   *     console.log(new TextDecoder().decode(new Uint8Array(event.data)));
+   *   }
+   * });
+   * await port.write(new TextEncoder().encode('AT\r\n'));
+   * ```
+   *
+   * @since 2.1.0
+   */
+  static async open(
+    path: string,
+    options: SerialOptions,
+    onEvent: (event: SerialEvent) => void
+  ): Promise<SerialPort> {
+    const channel = new Channel<SerialEvent>()
+    channel.onmessage = onEvent
+    return invoke<number>('plugin:serial|open', {
+      path,
+      options,
+      onEvent: channel
+    }).then((rid) => new SerialPort(rid))
+  }
+
+  /**
+   * Writes the data to the port.
+   *
+   * @since 2.1.0
+   */
+  async write(data: number[] | Uint8Array | ArrayBuffer): Promise<void> {
+    return invoke('plugin:serial|write', {
+      rid: this.rid,
+      data: Array.from(
+        data instanceof ArrayBuffer ? new Uint8Array(data) : data
+      )
+    })
+  }
+}
+
+export type { UsbInfo, PortInfo, SerialOptions, SerialEvent }
+
+export { availablePorts, SerialPort }
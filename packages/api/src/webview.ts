@@ -32,13 +32,87 @@ import { invoke } from './core'
 import { Window, getCurrentWindow } from './window'
 import { WebviewWindow } from './webviewWindow'
 
+/**
+ * Metadata of a dragged or dropped path.
+ *
+ * @since 2.1.0
+ */
+interface DragDropItem {
+  /** The path of the item. */
+  path: string
+  /** Whether the path is a directory. */
+  isDirectory: boolean
+  /** The file size in bytes, `null` for directories or if it could not be read. */
+  size: number | null
+  /**
+   * The MIME type of the file, `null` for directories.
+   * It is inferred from the content of the first 64 dropped files and from the extension otherwise,
+   * including while the files are dragged over the webview.
+   */
+  mimeType: string | null
+}
+
+/**
+ * The modifier keys held during a drag and drop operation,
+ * for instance to copy instead of moving the dropped files.
+ *
+ * #### Platform-specific
+ *
+ * - **Linux (Wayland)**: Always `false` when the compositor does not share the keyboard state with the app.
+ * - **iOS / Android**: Always `false`.
+ *
+ * @since 2.1.0
+ */
+interface DragDropModifiers {
+  shift: boolean
+  ctrl: boolean
+  alt: boolean
+  /** The Command key on macOS and the Windows key on Windows. */
+  meta: boolean
+}
+
 /** The drag and drop event types. */
 type DragDropEvent =
-  | { type: 'enter'; paths: string[]; position: PhysicalPosition }
-  | { type: 'over'; position: PhysicalPosition }
-  | { type: 'drop'; paths: string[]; position: PhysicalPosition }
+  | {
+      type: 'enter'
+      paths: string[]
+      items: DragDropItem[]
+      position: PhysicalPosition
+      modifiers: DragDropModifiers
+    }
+  | {
+      type: 'over'
+      position: PhysicalPosition
+      modifiers: DragDropModifiers
+    }
+  | {
+      type: 'drop'
+      paths: string[]
+      items: DragDropItem[]
+      position: PhysicalPosition
+      modifiers: DragDropModifiers
+    }
   | { type: 'leave' }
 
+/**
+ * How a webview uses its HTTP cache, see {@link Webview.setCacheMode}.
+ *
+ * @since 2.1.0
+ */
+type CacheMode = 'default' | 'disabled'
+
+/**
+ * A rectangle in logical pixels, relative to the top left corner of the webview.
+ *
+ * @since 2.1.0
+ */
+interface DragDropRegion {
+  x: number
+  y: number
+  width: number
+  height: number
+}
+
 /**
  * Get an instance of `Webview` for the current webview.
  *
@@ -563,6 +637,96 @@ class Webview {
     return invoke('plugin:webview|clear_all_browsing_data')
   }
 
+  /**
+   * Clears the HTTP cache of this webview, keeping the cookies and the storage,
+   * e.g. to force-refresh frequently updated remote content.
+   * @example
+   * ```typescript
+   * import { getCurrentWebview } from '@tauri-apps/api/webview';
+   * await getCurrentWebview().clearCache();
+   * ```
+   *
+   * #### Platform-specific
+   *
+   * - **iOS**: Unsupported.
+   *
+   * @returns A promise indicating the success or failure of the operation.
+   *
+   * @since 2.1.0
+   */
+  async clearCache(): Promise<void> {
+    return invoke('plugin:webview|clear_webview_cache', {
+      label: this.label
+    })
+  }
+
+  /**
+   * Sets how this webview uses its HTTP cache. `'disabled'` loads every request from the network.
+   * @example
+   * ```typescript
+   * import { getCurrentWebview } from '@tauri-apps/api/webview';
+   * await getCurrentWebview().setCacheMode('disabled');
+   * ```
+   *
+   * #### Platform-specific
+   *
+   * - **Windows**: `'disabled'` bypasses the cache with the DevTools protocol, like the "Disable cache" option of the DevTools.
+   * - **Linux**: `'disabled'` switches the web context to the `DocumentViewer` cache model of WebKitGTK,
+   *   which disables the memory caches but not the disk cache, for all the webviews sharing the same web context.
+   * - **Android**: `'disabled'` maps to `WebSettings.LOAD_NO_CACHE`.
+   * - **macOS / iOS**: Unsupported, the promise is rejected.
+   *
+   * @returns A promise indicating the success or failure of the operation.
+   *
+   * @since 2.1.0
+   */
+  async setCacheMode(mode: CacheMode): Promise<void> {
+    return invoke('plugin:webview|set_webview_cache_mode', {
+      label: this.label,
+      value: mode
+    })
+  }
+
+  /**
+   * Sets the regions of the webview where HTML5 drag and drop is used instead of the native file drop handler,
+   * so draggable elements keep working while files can still be dropped on the rest of the webview.
+   * Replaces the previously set regions, so it must be called again when the layout changes.
+   *
+   * #### Platform-specific
+   *
+   * - **Windows**: Unsupported, the native handler receives every drop while it is enabled.
+   *   Set `dragDropEnabled: false` to let HTML5 drag and drop handle the whole webview instead.
+   *
+   * @example
+   * ```typescript
+   * import { getCurrentWebview } from '@tauri-apps/api/webview';
+   * const list = document.getElementById('sortable-list');
+   * await getCurrentWebview().setHtmlDragDropRegions([list]);
+   * ```
+   *
+   * @param regions Elements or rectangles in logical pixels relative to the webview.
+   * @returns A promise indicating the success or failure of the operation.
+   *
+   * @since 2.1.0
+   */
+  async setHtmlDragDropRegions(
+    regions: Array<Element | DragDropRegion>
+  ): Promise<void> {
+    const value = regions.map((region) => {
+      const { x, y, width, height } =
+        region instanceof Element ? region.getBoundingClientRect() : region
+      return {
+        position: { Logical: { x, y } },
+        size: { Logical: { width, height } }
+      }
+    })
+
+    return invoke('plugin:webview|set_webview_html_drag_drop_regions', {
+      label: this.label,
+      value
+    })
+  }
+
   // Listeners
 
   /**
@@ -593,7 +757,12 @@ class Webview {
   async onDragDropEvent(
     handler: EventCallback<DragDropEvent>
   ): Promise<UnlistenFn> {
-    type DragPayload = { paths: string[]; position: PhysicalPosition }
+    type DragPayload = {
+      paths: string[]
+      items?: DragDropItem[]
+      position: PhysicalPosition
+      modifiers: DragDropModifiers
+    }
 
     const unlistenDragEnter = await this.listen<DragPayload>(
       TauriEvent.DRAG_ENTER,
@@ -603,7 +772,9 @@ class Webview {
           payload: {
             type: 'enter',
             paths: event.payload.paths,
-            position: mapPhysicalPosition(event.payload.position)
+            items: event.payload.items ?? [],
+            position: mapPhysicalPosition(event.payload.position),
+            modifiers: event.payload.modifiers
           }
         })
       }
@@ -616,7 +787,8 @@ class Webview {
           ...event,
           payload: {
             type: 'over',
-            position: mapPhysicalPosition(event.payload.position)
+            position: mapPhysicalPosition(event.payload.position),
+            modifiers: event.payload.modifiers
           }
         })
       }
@@ -630,7 +802,9 @@ class Webview {
           payload: {
             type: 'drop',
             paths: event.payload.paths,
-            position: mapPhysicalPosition(event.payload.position)
+            items: event.payload.items ?? [],
+            position: mapPhysicalPosition(event.payload.position),
+            modifiers: event.payload.modifiers
           }
         })
       }
@@ -728,8 +902,84 @@ interface WebviewOptions {
    * - **Android / iOS**: Unsupported.
    */
   zoomHotkeysEnabled?: boolean
+  /**
+   * Whether the text fields are spell checked. Defaults to the webview engine behavior.
+   *
+   * Set as a default on the text fields of the pages on the origin of the initial URL,
+   * so attributes set by the page take precedence.
+   *
+   * #### Platform-specific:
+   *
+   * - **Linux**: Also enables spell checking on the WebKitGTK web context, shared by all webviews with the same data directory.
+   *
+   * @since 2.1.0
+   */
+  spellcheck?: boolean
+  /**
+   * The languages used to spell check, such as `en_US`.
+   *
+   * #### Platform-specific:
+   *
+   * - **Linux**: Set on the web context, so it applies to all webviews sharing the same data directory.
+   * - **Windows / macOS / iOS / Android** - Unsupported, the system languages are used.
+   *
+   * @since 2.1.0
+   */
+  spellcheckLanguages?: string[]
+  /**
+   * Whether the text fields are automatically corrected while typing. Defaults to the webview engine behavior.
+   *
+   * #### Platform-specific:
+   *
+   * - **Windows / Linux / Android** - Unsupported, the engines do not correct text.
+   *
+   * @since 2.1.0
+   */
+  autocorrect?: boolean
+  /**
+   * Whether the webview suggests previously entered values and saved passwords in forms.
+   * Defaults to the webview engine behavior.
+   *
+   * #### Platform-specific:
+   *
+   * - **Windows**: Also controls WebView2's `IsGeneralAutofillEnabled` and `IsPasswordAutosaveEnabled` settings.
+   *
+   * @since 2.1.0
+   */
+  autofill?: boolean
+  /**
+   * Whether the webview renders with the GPU. Defaults to `true`.
+   *
+   * #### Platform-specific:
+   *
+   * - **Windows**: Passes `--disable-gpu` to the browser process, which is shared by the webviews with the same data directory,
+   * so they must all use the same value.
+   * - **macOS / iOS / Android**: Unsupported.
+   *
+   * @since 2.1.0
+   */
+  hardwareAcceleration?: boolean
+  /**
+   * The maximum size in bytes of the HTTP disk cache.
+   *
+   * #### Platform-specific:
+   *
+   * - **Windows**: Passes `--disk-cache-size` to the browser process, which is shared by the webviews with the same data directory,
+   * so they must all use the same value.
+   * - **macOS / Linux / iOS / Android**: Unsupported.
+   *
+   * @since 2.1.0
+   */
+  diskCacheSize?: number
 }
 
 export { Webview, getCurrentWebview, getAllWebviews }
 
-export type { DragDropEvent, WebviewOptions }
+export type {
+  CacheMode,
+  DragDropEvent,
+  DragDropItem,
+  DragDropModifiers,
+  DragDropRegion,
+  WebviewOptions
+}
@@ -34,7 +34,11 @@ import {
 } from './event'
 import { invoke } from './core'
 import { WebviewWindow } from './webviewWindow'
-import type { DragDropEvent } from './webview'
+import type {
+  DragDropEvent,
+  DragDropItem,
+  DragDropModifiers
+} from './webview'
 import { Image, transformImage } from './image'
 
 /**
@@ -1843,7 +1847,12 @@ class Window {
   async onDragDropEvent(
     handler: EventCallback<DragDropEvent>
   ): Promise<UnlistenFn> {
-    type DragPayload = { paths: string[]; position: PhysicalPosition }
+    type DragPayload = {
+      paths: string[]
+      items?: DragDropItem[]
+      position: PhysicalPosition
+      modifiers: DragDropModifiers
+    }
 
     const unlistenDrag = await this.listen<DragPayload>(
       TauriEvent.DRAG_ENTER,
@@ -1853,7 +1862,9 @@ class Window {
           payload: {
             type: 'enter',
             paths: event.payload.paths,
-            position: mapPhysicalPosition(event.payload.position)
+            items: event.payload.items ?? [],
+            position: mapPhysicalPosition(event.payload.position),
+            modifiers: event.payload.modifiers
           }
         })
       }
@@ -1866,7 +1877,8 @@ class Window {
           ...event,
           payload: {
             type: 'over',
-            position: mapPhysicalPosition(event.payload.position)
+            position: mapPhysicalPosition(event.payload.position),
+            modifiers: event.payload.modifiers
           }
         })
       }
@@ -1880,7 +1892,9 @@ class Window {
           payload: {
             type: 'drop',
             paths: event.payload.paths,
-            position: mapPhysicalPosition(event.payload.position)
+            items: event.payload.items ?? [],
+            position: mapPhysicalPosition(event.payload.position),
+            modifiers: event.payload.modifiers
           }
         })
       }
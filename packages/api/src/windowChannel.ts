@@ -0,0 +1,128 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+/**
+ * Exchange messages between the webviews of the app without going through the Rust IPC layer.
+ *
+ * Messages are sent with the webview engine's [`BroadcastChannel`](https://developer.mozilla.org/en-US/docs/Web/API/BroadcastChannel)
+ * and copied with the structured clone algorithm, so large payloads such as objects, `ArrayBuffer`s and `Blob`s
+ * are never serialized to JSON nor routed through the Rust process, unlike events emitted with `emitTo`.
+ *
+ * Only webviews loading the same origin can communicate, which is the case for all webviews loading the app's own assets.
+ *
+ * This package is also accessible with `window.__TAURI__.windowChannel` when [`app.withGlobalTauri`](https://v2.tauri.app/reference/config/#withglobaltauri) in `tauri.conf.json` is set to `true`.
+ * @module
+ */
+
+import type { UnlistenFn } from './event'
+
+const CHANNEL_PREFIX = 'tauri://window-channel/'
+
+/**
+ * A message received on a {@link WindowChannel}.
+ *
+ * @since 2.1.0
+ */
+interface WindowChannelMessage<T> {
+  /** Label of the webview that sent the message. */
+  source: string
+  /** Label of the webview the message was sent to, `null` if it was sent to all webviews. */
+  target: string | null
+  /** The message payload. */
+  payload: T
+}
+
+/**
+ * Options for {@link WindowChannel.postMessage}.
+ *
+ * @since 2.1.0
+ */
+interface PostMessageOptions {
+  /** Only deliver the message to the webview with this label. */
+  target?: string
+}
+
+/**
+ * A named channel shared by all webviews of the app.
+ *
+ * @example
+ * ```typescript
+ * import { WindowChannel } from '@tauri-apps/api/windowChannel';
+ *
+ * // editor window
+ * const channel = new WindowChannel<{ document: ArrayBuffer }>('document-sync');
+ * channel.postMessage({ document: buffer }, { target: 'preview' });
+ *
+ * // preview window
+ * const channel = new WindowChannel<{ document: ArrayBuffer }>('document-sync');
+ * const unlisten = channel.onMessage(({ source, payload }) => {
+ *   console.log(`received ${payload.document.byteLength} bytes from ${source}`);
+ * });
+ * ```
+ *
+ * @since 2.1.0
+ */
+class WindowChannel<T = unknown> {
+  /** The channel name. */
+  readonly name: string
+  #channel: BroadcastChannel
+  #label: string
+
+  /**
+   * Joins the channel with the given name, creating it if no other webview joined it yet.
+   *
+   * @param name The channel name.
+   */
+  constructor(name: string) {
+    this.name = name
+    this.#channel = new BroadcastChannel(`${CHANNEL_PREFIX}${name}`)
+    this.#label = window.__TAURI_INTERNALS__.metadata.currentWebview.label
+  }
+
+  /**
+   * Sends a message to the other webviews that joined this channel.
+   * The sender does not receive its own messages.
+   *
+   * @param payload The message payload, it must be supported by the structured clone algorithm.
+   * @param options Message options.
+   */
+  postMessage(payload: T, options?: PostMessageOptions): void {
+    const message: WindowChannelMessage<T> = {
+      source: this.#label,
+      target: options?.target ?? null,
+      payload
+    }
+    this.#channel.postMessage(message)
+  }
+
+  /**
+   * Listens to the messages sent to this webview on the channel.
+   *
+   * @param handler Message handler.
+   * @returns A function to stop listening.
+   */
+  onMessage(handler: (message: WindowChannelMessage<T>) => void): UnlistenFn {
+    const listener = (event: MessageEvent<WindowChannelMessage<T>>): void => {
+      const message = event.data
+      if (message.target === null || message.target === this.#label) {
+        handler(message)
+      }
+    }
+    this.#channel.addEventListener('message', listener)
+    return () => {
+      this.#channel.removeEventListener('message', listener)
+    }
+  }
+
+  /**
+   * Leaves the channel. The channel can no longer be used after this call.
+   */
+  close(): void {
+    this.#channel.close()
+  }
+}
+
+export type { WindowChannelMessage, PostMessageOptions }
+
+export { WindowChannel }